@@ -0,0 +1,123 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use crate::CliError;
+
+const AUDIT_PROTOCOL_VERSION: u32 = 1;
+
+/// An entry of a Splinter node's REST API audit log; the timestamp is in seconds since the Unix
+/// epoch
+#[derive(Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub identity: Option<AuditIdentity>,
+    pub permission_id: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub decision: String,
+    pub status: u16,
+    pub request_digest: String,
+}
+
+/// The identity of the client that made an audited request
+#[derive(Deserialize)]
+pub struct AuditIdentity {
+    pub identity_type: String,
+    pub identity: String,
+}
+
+#[derive(Deserialize)]
+struct Page {
+    data: Vec<AuditEntry>,
+    paging: Paging,
+}
+
+#[derive(Deserialize)]
+struct Paging {
+    next: String,
+    total: usize,
+    limit: usize,
+    offset: usize,
+}
+
+impl Paging {
+    fn has_next(&self) -> bool {
+        self.total - self.offset > self.limit
+    }
+}
+
+/// Fetches every page of the audit log entries that match the given time range.
+pub fn list_entries(
+    base_url: &str,
+    auth: &str,
+    since: Option<u64>,
+    until: Option<u64>,
+) -> Result<Vec<AuditEntry>, CliError> {
+    let mut link = "/audit?".to_string();
+    if let Some(since) = since {
+        link.push_str(&format!("since={}&", since));
+    }
+    if let Some(until) = until {
+        link.push_str(&format!("until={}&", until));
+    }
+
+    let mut entries = vec![];
+    loop {
+        let page = load_page(base_url, auth, &link)?;
+        entries.extend(page.data);
+
+        if !page.paging.has_next() {
+            break Ok(entries);
+        }
+        link = page.paging.next;
+    }
+}
+
+fn load_page(base_url: &str, auth: &str, link: &str) -> Result<Page, CliError> {
+    Client::new()
+        .get(&format!("{}{}", base_url, link))
+        .header("SplinterProtocolVersion", AUDIT_PROTOCOL_VERSION)
+        .header("Authorization", auth)
+        .send()
+        .map_err(|err| CliError::ActionError(format!("Failed to fetch audit log page: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<Page>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else {
+                let message = res
+                    .json::<super::ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Fetch audit log request failed with status code '{}', but error \
+                             response was not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to fetch audit log page: {}",
+                    message
+                )))
+            }
+        })
+}
@@ -14,21 +14,29 @@
 
 //! Provides convenient functions for sending REST API requests to a splinter node.
 
+#[cfg(feature = "rest-api-audit")]
+mod audit;
 #[cfg(feature = "authorization-handler-rbac")]
 mod rbac;
+#[cfg(feature = "rest-api-tokens")]
+mod token;
 
 use reqwest::blocking::Client;
 use serde::Deserialize;
 
 use super::CliError;
 
+#[cfg(feature = "rest-api-audit")]
+pub use audit::AuditEntry;
 #[cfg(feature = "authorization-handler-rbac")]
 pub use rbac::{
     assignments::{
         Assignment, AssignmentBuilder, AssignmentUpdate, AssignmentUpdateBuilder, Identity,
     },
-    roles::{Role, RoleBuilder, RoleUpdate, RoleUpdateBuilder},
+    roles::{Role, RoleBuilder, RoleScope, RoleUpdate, RoleUpdateBuilder},
 };
+#[cfg(feature = "rest-api-tokens")]
+pub use token::{ApiToken, NewApiToken};
 
 #[derive(Default)]
 pub struct SplinterRestClientBuilder {
@@ -107,12 +115,13 @@ impl SplinterRestClient {
             })
     }
 
-    /// Checks whether or not maintenance mode is enabled for the Splinter node.
+    /// Gets the state of maintenance mode for the Splinter node.
     #[cfg(feature = "authorization-handler-maintenance")]
-    pub fn is_maintenance_mode_enabled(&self) -> Result<bool, CliError> {
+    pub fn get_maintenance_mode(&self) -> Result<MaintenanceMode, CliError> {
         Client::new()
             .get(&format!("{}/authorization/maintenance", self.url))
             .header("Authorization", &self.auth)
+            .header("Accept", "application/json")
             .send()
             .map_err(|err| {
                 CliError::ActionError(format!("Failed to check maintenance mode status: {}", err))
@@ -120,19 +129,28 @@ impl SplinterRestClient {
             .and_then(|res| {
                 let status = res.status();
                 if status.is_success() {
-                    res.text()
-                        .map_err(|err| {
-                            CliError::ActionError(format!(
-                                "Request was successful, but failed to parse response body: {}",
-                                err
-                            ))
-                        })?
-                        .parse()
-                        .map_err(|_| {
-                            CliError::ActionError(
-                                "Request was successful, but received an invalid response".into(),
-                            )
-                        })
+                    let body = res.text().map_err(|err| {
+                        CliError::ActionError(format!(
+                            "Request was successful, but failed to parse response body: {}",
+                            err
+                        ))
+                    })?;
+
+                    // Nodes that do not record the details of maintenance mode only respond with
+                    // whether or not it is enabled
+                    serde_json::from_str::<MaintenanceMode>(&body).or_else(|_| {
+                        body.parse()
+                            .map(|enabled| MaintenanceMode {
+                                enabled,
+                                ..Default::default()
+                            })
+                            .map_err(|_| {
+                                CliError::ActionError(
+                                    "Request was successful, but received an invalid response"
+                                        .into(),
+                                )
+                            })
+                    })
                 } else {
                     let message = res
                         .json::<ServerError>()
@@ -154,11 +172,27 @@ impl SplinterRestClient {
     }
 
     /// Turns maintenance mode on or off for the Splinter node.
+    ///
+    /// When turning maintenance mode on, `expires_in` is the number of seconds after which it is
+    /// automatically turned off again.
     #[cfg(feature = "authorization-handler-maintenance")]
-    pub fn set_maintenance_mode(&self, enabled: bool) -> Result<(), CliError> {
+    pub fn set_maintenance_mode(
+        &self,
+        enabled: bool,
+        reason: Option<&str>,
+        expires_in: Option<u64>,
+    ) -> Result<(), CliError> {
+        let mut query = vec![("enabled", enabled.to_string())];
+        if let Some(reason) = reason {
+            query.push(("reason", reason.to_string()));
+        }
+        if let Some(expires_in) = expires_in {
+            query.push(("expires_in", expires_in.to_string()));
+        }
+
         Client::new()
             .post(&format!("{}/authorization/maintenance", self.url))
-            .query(&[("enabled", enabled)])
+            .query(&query)
             .header("Authorization", &self.auth)
             .send()
             .map_err(|err| {
@@ -231,6 +265,43 @@ impl SplinterRestClient {
             })
     }
 
+    /// Lists the entries of the REST API audit log for a Splinter node, from oldest to newest.
+    ///
+    /// `since` and `until` are in seconds since the Unix epoch; entries recorded at `since` are
+    /// included and entries recorded at `until` are not.
+    #[cfg(feature = "rest-api-audit")]
+    pub fn list_audit_entries(
+        &self,
+        since: Option<u64>,
+        until: Option<u64>,
+    ) -> Result<Vec<AuditEntry>, CliError> {
+        audit::list_entries(&self.url, &self.auth, since, until)
+    }
+
+    /// Creates a new API token for the client, which expires after `expires_in` seconds if given
+    /// and is granted the given roles.
+    #[cfg(feature = "rest-api-tokens")]
+    pub fn create_api_token(
+        &self,
+        name: &str,
+        expires_in: Option<u64>,
+        roles: &[String],
+    ) -> Result<NewApiToken, CliError> {
+        token::create_token(&self.url, &self.auth, name, expires_in, roles)
+    }
+
+    /// Lists the API tokens issued by a Splinter node, from oldest to newest.
+    #[cfg(feature = "rest-api-tokens")]
+    pub fn list_api_tokens(&self) -> Result<Vec<ApiToken>, CliError> {
+        token::list_tokens(&self.url, &self.auth)
+    }
+
+    /// Revokes the API token with the given ID.
+    #[cfg(feature = "rest-api-tokens")]
+    pub fn revoke_api_token(&self, token_id: &str) -> Result<(), CliError> {
+        token::revoke_token(&self.url, &self.auth, token_id)
+    }
+
     #[cfg(feature = "authorization-handler-rbac")]
     pub fn list_roles(&self) -> Result<rbac::PagingIter<Role>, CliError> {
         Ok(rbac::PagingIter::new(
@@ -304,6 +375,25 @@ pub struct NodeStatus {
     pub version: String,
 }
 
+/// The state of maintenance mode; times are in seconds since the Unix epoch
+#[cfg(feature = "authorization-handler-maintenance")]
+#[derive(Default, Deserialize)]
+pub struct MaintenanceMode {
+    pub enabled: bool,
+    pub set_by: Option<MaintenanceModeSetBy>,
+    pub set_at: Option<u64>,
+    pub reason: Option<String>,
+    pub expires_at: Option<u64>,
+}
+
+/// The identity of the client that last set maintenance mode
+#[cfg(feature = "authorization-handler-maintenance")]
+#[derive(Deserialize)]
+pub struct MaintenanceModeSetBy {
+    pub identity_type: String,
+    pub identity: String,
+}
+
 #[derive(Deserialize)]
 struct PermissionsResponse {
     pub data: Vec<Permission>,
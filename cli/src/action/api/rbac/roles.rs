@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::fmt;
+use std::str::FromStr;
 
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
@@ -27,6 +28,8 @@ pub struct Role {
     pub role_id: String,
     pub display_name: String,
     pub permissions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<RoleScope>,
 }
 
 impl fmt::Display for Role {
@@ -39,10 +42,93 @@ impl fmt::Display for Role {
             write!(f, "\n        {}", perm)?;
         }
 
+        if !self.scopes.is_empty() {
+            f.write_str("\n    Scopes:")?;
+
+            for scope in self.scopes.iter() {
+                write!(f, "\n        {}", scope)?;
+            }
+        }
+
         Ok(())
     }
 }
 
+/// A set of resources that a role's permissions are limited to.
+///
+/// Each field is a pattern, where `*` matches any sequence of characters. On the command line, a
+/// scope is written as comma-separated `field=pattern` pairs, such as
+/// `circuit_id=abcde-*,service_id=a000`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct RoleScope {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub management_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl FromStr for RoleScope {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scope = RoleScope::default();
+        for pair in s.split(',') {
+            let (field, pattern) = match pair.split_once('=') {
+                Some((field, pattern)) if !pattern.is_empty() => (field, pattern.to_string()),
+                _ => {
+                    return Err(CliError::ActionError(format!(
+                        "Invalid scope \"{}\": expected field=pattern pairs",
+                        s
+                    )))
+                }
+            };
+
+            let value = match field {
+                "circuit_id" => &mut scope.circuit_id,
+                "management_type" => &mut scope.management_type,
+                "service_id" => &mut scope.service_id,
+                _ => {
+                    return Err(CliError::ActionError(format!(
+                        "Invalid scope \"{}\": unknown field {}; expected one of circuit_id, \
+                        management_type or service_id",
+                        s, field
+                    )))
+                }
+            };
+
+            if value.replace(pattern).is_some() {
+                return Err(CliError::ActionError(format!(
+                    "Invalid scope \"{}\": {} is set more than once",
+                    s, field
+                )));
+            }
+        }
+
+        Ok(scope)
+    }
+}
+
+impl fmt::Display for RoleScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fields = [
+            ("circuit_id", &self.circuit_id),
+            ("management_type", &self.management_type),
+            ("service_id", &self.service_id),
+        ]
+        .iter()
+        .filter_map(|(field, pattern)| {
+            pattern
+                .as_ref()
+                .map(|pattern| format!("{}={}", field, pattern))
+        })
+        .collect::<Vec<_>>();
+
+        f.write_str(&fields.join(","))
+    }
+}
+
 impl Pageable for Role {
     fn label() -> &'static str {
         "role list"
@@ -55,6 +141,7 @@ pub struct RoleBuilder {
     role_id: Option<String>,
     display_name: Option<String>,
     permissions: Vec<String>,
+    scopes: Vec<RoleScope>,
 }
 
 impl RoleBuilder {
@@ -80,12 +167,21 @@ impl RoleBuilder {
         self
     }
 
+    /// Sets the resource scopes the resulting Role is limited to.
+    ///
+    /// The role applies to all resources if this is empty.
+    pub fn with_scopes(mut self, scopes: Vec<RoleScope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     /// Constructs the Role.
     pub fn build(self) -> Result<Role, CliError> {
         let RoleBuilder {
             role_id,
             display_name,
             permissions,
+            scopes,
         } = self;
 
         if permissions.is_empty() {
@@ -107,6 +203,7 @@ impl RoleBuilder {
             role_id,
             display_name,
             permissions,
+            scopes,
         })
     }
 }
@@ -120,6 +217,8 @@ pub struct RoleUpdate {
     display_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     permissions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scopes: Option<Vec<RoleScope>>,
 }
 
 #[derive(Default)]
@@ -127,6 +226,7 @@ pub struct RoleUpdateBuilder {
     role_id: Option<String>,
     display_name: Option<String>,
     permissions: Option<Vec<String>>,
+    scopes: Option<Vec<RoleScope>>,
 }
 
 impl RoleUpdateBuilder {
@@ -152,12 +252,21 @@ impl RoleUpdateBuilder {
         self
     }
 
+    /// Sets the resource scopes of the resulting Role.
+    ///
+    /// An empty list removes all of the scopes, so that the role applies to all resources.
+    pub fn with_scopes(mut self, scopes: Option<Vec<RoleScope>>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     /// Constructs the Role.
     pub fn build(self) -> Result<RoleUpdate, CliError> {
         let RoleUpdateBuilder {
             role_id,
             display_name,
             permissions,
+            scopes,
         } = self;
 
         let role_id =
@@ -178,6 +287,7 @@ impl RoleUpdateBuilder {
             role_id,
             display_name,
             permissions,
+            scopes,
         })
     }
 }
@@ -403,6 +513,45 @@ mod tests {
         assert!(res.is_err());
     }
 
+    /// Tests parsing role scopes from the command line format
+    /// 1. Parse a scope with a single field
+    /// 2. Parse a scope with all fields and verify it is displayed in the same format
+    /// 3. Fail with an unknown field, a missing pattern, an empty pattern and a repeated field
+    #[test]
+    fn test_role_scope_from_str() {
+        let scope: RoleScope = "circuit_id=abcde-*".parse().expect("Unable to parse scope");
+        assert_eq!(
+            RoleScope {
+                circuit_id: Some("abcde-*".into()),
+                ..Default::default()
+            },
+            scope
+        );
+
+        let scope: RoleScope = "circuit_id=abcde-01234,management_type=gameroom,service_id=a000"
+            .parse()
+            .expect("Unable to parse scope");
+        assert_eq!(
+            RoleScope {
+                circuit_id: Some("abcde-01234".into()),
+                management_type: Some("gameroom".into()),
+                service_id: Some("a000".into()),
+            },
+            scope
+        );
+        assert_eq!(
+            "circuit_id=abcde-01234,management_type=gameroom,service_id=a000",
+            scope.to_string()
+        );
+
+        assert!("node_id=acme".parse::<RoleScope>().is_err());
+        assert!("circuit_id".parse::<RoleScope>().is_err());
+        assert!("circuit_id=".parse::<RoleScope>().is_err());
+        assert!("service_id=a000,service_id=a001"
+            .parse::<RoleScope>()
+            .is_err());
+    }
+
     /// Tests the role update builder in both Ok and Err scenarios
     /// 1. Construct a valid update with all items
     /// 2. Construct a valid update with no permission changes
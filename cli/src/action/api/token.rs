@@ -0,0 +1,219 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::CliError;
+
+use super::ServerError;
+
+const TOKENS_PROTOCOL_VERSION: u32 = 1;
+
+/// An API token issued by a Splinter node, without the token itself; times are in seconds since
+/// the Unix epoch
+#[derive(Deserialize)]
+pub struct ApiToken {
+    pub token_id: String,
+    pub name: String,
+    pub owner: ApiTokenOwner,
+    pub created_at: u64,
+    pub expires_at: Option<u64>,
+    pub roles: Vec<String>,
+}
+
+/// The identity of the client that created an API token
+#[derive(Deserialize)]
+pub struct ApiTokenOwner {
+    pub identity_type: String,
+    pub identity: String,
+}
+
+/// A newly created API token; this is the only time the token itself is returned by the node
+#[derive(Deserialize)]
+pub struct NewApiToken {
+    pub token: String,
+    #[serde(flatten)]
+    pub details: ApiToken,
+}
+
+#[derive(Serialize)]
+struct CreatePayload<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_in: Option<u64>,
+    roles: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct Created {
+    data: NewApiToken,
+}
+
+#[derive(Deserialize)]
+struct Page {
+    data: Vec<ApiToken>,
+    paging: Paging,
+}
+
+#[derive(Deserialize)]
+struct Paging {
+    next: String,
+    total: usize,
+    limit: usize,
+    offset: usize,
+}
+
+impl Paging {
+    fn has_next(&self) -> bool {
+        self.total - self.offset > self.limit
+    }
+}
+
+/// Creates a new API token that expires after `expires_in` seconds, if given.
+pub fn create_token(
+    base_url: &str,
+    auth: &str,
+    name: &str,
+    expires_in: Option<u64>,
+    roles: &[String],
+) -> Result<NewApiToken, CliError> {
+    Client::new()
+        .post(&format!("{}/authorization/tokens", base_url))
+        .header("SplinterProtocolVersion", TOKENS_PROTOCOL_VERSION)
+        .header("Authorization", auth)
+        .json(&CreatePayload {
+            name,
+            expires_in,
+            roles,
+        })
+        .send()
+        .map_err(|err| CliError::ActionError(format!("Failed to create token: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<Created>()
+                    .map(|created| created.data)
+                    .map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+            } else if status.as_u16() == 401 {
+                Err(CliError::ActionError("Not Authorized".into()))
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Create token request failed with status code '{}', but error \
+                             response was not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to create token: {}",
+                    message
+                )))
+            }
+        })
+}
+
+/// Fetches every page of the API tokens, from oldest to newest.
+pub fn list_tokens(base_url: &str, auth: &str) -> Result<Vec<ApiToken>, CliError> {
+    let mut link = "/authorization/tokens?".to_string();
+    let mut tokens = vec![];
+    loop {
+        let page = load_page(base_url, auth, &link)?;
+        tokens.extend(page.data);
+
+        if !page.paging.has_next() {
+            break Ok(tokens);
+        }
+        link = page.paging.next;
+    }
+}
+
+fn load_page(base_url: &str, auth: &str, link: &str) -> Result<Page, CliError> {
+    Client::new()
+        .get(&format!("{}{}", base_url, link))
+        .header("SplinterProtocolVersion", TOKENS_PROTOCOL_VERSION)
+        .header("Authorization", auth)
+        .send()
+        .map_err(|err| CliError::ActionError(format!("Failed to fetch tokens page: {}", err)))
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<Page>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Fetch tokens request failed with status code '{}', but error \
+                             response was not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to fetch tokens page: {}",
+                    message
+                )))
+            }
+        })
+}
+
+/// Revokes the API token with the given ID.
+pub fn revoke_token(base_url: &str, auth: &str, token_id: &str) -> Result<(), CliError> {
+    Client::new()
+        .delete(&format!("{}/authorization/tokens/{}", base_url, token_id))
+        .header("SplinterProtocolVersion", TOKENS_PROTOCOL_VERSION)
+        .header("Authorization", auth)
+        .send()
+        .map_err(|err| {
+            CliError::ActionError(format!("Failed to revoke token {}: {}", token_id, err))
+        })
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                Ok(())
+            } else if status.as_u16() == 401 {
+                Err(CliError::ActionError("Not Authorized".into()))
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Revoke token request failed with status code '{}', but error \
+                             response was not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to revoke token {}: {}",
+                    token_id, message
+                )))
+            }
+        })
+}
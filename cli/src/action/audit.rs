@@ -0,0 +1,151 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions to support the audit log subcommands.
+
+use std::time::SystemTime;
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{AuditEntry, SplinterRestClient, SplinterRestClientBuilder},
+    print_table,
+    time::{format_timestamp, parse_duration},
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// The action responsible for listing the entries of the audit log.
+///
+/// The specific args for this action:
+///
+/// * format: specifies the output format; one of "human" or "csv"
+/// * since: only list entries recorded at or after this time
+/// * until: only list entries recorded before this time
+pub struct ListAction;
+
+impl Action for ListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+        let since = arg_matches
+            .and_then(|args| args.value_of("since"))
+            .map(parse_time)
+            .transpose()?;
+        let until = arg_matches
+            .and_then(|args| args.value_of("until"))
+            .map(parse_time)
+            .transpose()?;
+
+        let entries = new_client(arg_matches)?.list_audit_entries(since, until)?;
+
+        let header = vec![
+            "TIME".to_string(),
+            "IDENTITY".to_string(),
+            "METHOD".to_string(),
+            "ROUTE".to_string(),
+            "PERMISSION".to_string(),
+            "DECISION".to_string(),
+            "STATUS".to_string(),
+        ];
+
+        if format == "csv" {
+            println!("{}", header.join(","));
+            for entry in entries {
+                println!("{}", format_row(entry).join(","));
+            }
+        } else {
+            let mut rows = vec![header];
+            rows.extend(entries.into_iter().map(format_row));
+            print_table(rows);
+        }
+
+        Ok(())
+    }
+}
+
+fn new_client(arg_matches: Option<&ArgMatches<'_>>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
+
+fn format_row(entry: AuditEntry) -> Vec<String> {
+    vec![
+        format_timestamp(entry.timestamp),
+        entry
+            .identity
+            .map(|identity| format!("{} {}", identity.identity_type, identity.identity))
+            .unwrap_or_else(|| "-".to_string()),
+        entry.method,
+        entry.route,
+        entry.permission_id.unwrap_or_else(|| "-".to_string()),
+        entry.decision,
+        entry.status.to_string(),
+    ]
+}
+
+/// Parses a time given on the command line into a number of seconds since the Unix epoch
+///
+/// A plain number is a time in seconds since the Unix epoch; a duration such as "30m", "2h" or
+/// "1d" is that long before the current time.
+fn parse_time(time: &str) -> Result<u64, CliError> {
+    if let Ok(timestamp) = time.parse::<u64>() {
+        return Ok(timestamp);
+    }
+
+    let ago = parse_duration(time)?;
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_err(|err| CliError::ActionError(format!("System time is invalid: {}", err)))?
+        .as_secs();
+
+    Ok(now.saturating_sub(ago))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that plain numbers are read as times since the Unix epoch and durations as times
+    /// before the current time.
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            parse_time("1650000000").expect("Unable to parse"),
+            1_650_000_000
+        );
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Invalid system time")
+            .as_secs();
+        let an_hour_ago = parse_time("1h").expect("Unable to parse");
+        assert!(an_hour_ago <= now - 3600 && an_hour_ago >= now - 3600 - 5);
+
+        assert!(parse_time("1w").is_err());
+    }
+}
@@ -64,21 +64,13 @@ impl SplinterRestClient {
             })
     }
 
-    pub fn list_circuits(
-        &self,
-        member_filter: Option<&str>,
-        status_filter: Option<&str>,
-    ) -> Result<CircuitListSlice, CliError> {
-        let mut url = format!("{}/admin/circuits?limit={}", self.url, PAGING_LIMIT);
-        if let Some(member_filter) = member_filter {
-            url = format!("{}&filter={}", &url, &member_filter);
-        }
-        if let Some(status_filter) = status_filter {
-            url = format!("{}&status={}", &url, &status_filter);
-        }
-
+    /// Lists the circuits that match the given filters, which are passed to the REST API as query
+    /// parameters.
+    pub fn list_circuits(&self, filters: &[(&str, &str)]) -> Result<CircuitListSlice, CliError> {
         Client::new()
-            .get(&url)
+            .get(&format!("{}/admin/circuits", self.url))
+            .query(&[("limit", PAGING_LIMIT)])
+            .query(filters)
             .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
             .header("Authorization", &self.auth)
             .send()
@@ -148,26 +140,13 @@ impl SplinterRestClient {
             })
     }
 
-    pub fn list_proposals(
-        &self,
-        management_type_filter: Option<&str>,
-        member_filter: Option<&str>,
-    ) -> Result<ProposalListSlice, CliError> {
-        let mut filters = vec![];
-        if let Some(management_type) = management_type_filter {
-            filters.push(format!("management_type={}", management_type));
-        }
-        if let Some(member) = member_filter {
-            filters.push(format!("member={}", member));
-        }
-
-        let mut url = format!("{}/admin/proposals?limit={}", self.url, PAGING_LIMIT);
-        if !filters.is_empty() {
-            url.push_str(&format!("&{}", filters.join("&")));
-        }
-
+    /// Lists the circuit proposals that match the given filters, which are passed to the REST API
+    /// as query parameters.
+    pub fn list_proposals(&self, filters: &[(&str, &str)]) -> Result<ProposalListSlice, CliError> {
         Client::new()
-            .get(&url)
+            .get(&format!("{}/admin/proposals", self.url))
+            .query(&[("limit", PAGING_LIMIT)])
+            .query(filters)
             .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
             .header("Authorization", &self.auth)
             .send()
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use splinter::admin::messages::AuthorizationType;
+use splinter::admin::messages::{AuthorizationType, DurabilityType, RouteType};
 use splinter::admin::messages::{
     BuilderError, CircuitStatus, CreateCircuit, CreateCircuitBuilder, SplinterNode,
     SplinterNodeBuilder, SplinterServiceBuilder,
@@ -20,7 +20,7 @@ use splinter::admin::messages::{
 
 use crate::error::CliError;
 
-const PEER_SERVICES_ARG: &str = "peer_services";
+pub(super) const PEER_SERVICES_ARG: &str = "peer_services";
 const MANAGEMENT_TYPE_ENV: &str = "SPLINTER_CIRCUIT_MANAGEMENT_TYPE";
 const SERVICE_TYPE_ENV: &str = "SPLINTER_CIRCUIT_SERVICE_TYPE";
 
@@ -30,6 +30,7 @@ pub struct CreateCircuitMessageBuilder {
     nodes: Vec<SplinterNode>,
     management_type: Option<String>,
     authorization_type: Option<AuthorizationType>,
+    durability: Option<DurabilityType>,
     application_metadata: Vec<u8>,
     comments: Option<String>,
     display_name: Option<String>,
@@ -45,6 +46,7 @@ impl CreateCircuitMessageBuilder {
             nodes: vec![],
             management_type: None,
             authorization_type: None,
+            durability: None,
             application_metadata: vec![],
             comments: None,
             display_name: None,
@@ -213,6 +215,33 @@ impl CreateCircuitMessageBuilder {
         Ok(())
     }
 
+    pub fn set_node_relay(&mut self, node_id: &str, relay_node_id: &str) -> Result<(), CliError> {
+        if !self.nodes.iter().any(|node| node.node_id == relay_node_id) {
+            return Err(CliError::ActionError(format!(
+                "Relay node '{}' is not a node of the circuit",
+                relay_node_id
+            )));
+        }
+
+        let node = self
+            .nodes
+            .iter_mut()
+            .find(|node| node.node_id == node_id)
+            .ok_or_else(|| {
+                CliError::ActionError(format!("Relay node set for unknown node '{}'", node_id))
+            })?;
+
+        if node.relay_node_id.is_some() {
+            return Err(CliError::ActionError(format!(
+                "Duplicate relay node detected for node '{}'",
+                node_id
+            )));
+        }
+
+        node.relay_node_id = Some(relay_node_id.into());
+        Ok(())
+    }
+
     pub fn set_management_type(&mut self, management_type: &str) {
         self.management_type = Some(management_type.into());
     }
@@ -233,6 +262,22 @@ impl CreateCircuitMessageBuilder {
         Ok(())
     }
 
+    pub fn set_durability(&mut self, durability: &str) -> Result<(), CliError> {
+        let durability_type = match durability {
+            "none" => DurabilityType::NoDurability,
+            "guaranteed-delivery" => DurabilityType::GuaranteedDelivery,
+            _ => {
+                return Err(CliError::ActionError(format!(
+                    "Invalid durability type: {}",
+                    durability
+                )))
+            }
+        };
+
+        self.durability = Some(durability_type);
+        Ok(())
+    }
+
     pub fn set_application_metadata(&mut self, application_metadata: &[u8]) {
         self.application_metadata = application_metadata.into();
     }
@@ -335,6 +380,18 @@ impl CreateCircuitMessageBuilder {
             None => create_circuit_builder,
         };
 
+        let create_circuit_builder = match self.durability {
+            Some(durability) => create_circuit_builder.with_durability(&durability),
+            None => create_circuit_builder,
+        };
+
+        // circuits with relay nodes must use relayed routes
+        let create_circuit_builder = if self.nodes.iter().any(|node| node.relay_node_id.is_some()) {
+            create_circuit_builder.with_routes(&RouteType::Relayed)
+        } else {
+            create_circuit_builder
+        };
+
         let create_circuit = create_circuit_builder.build().map_err(|err| {
             CliError::ActionError(format!(
                 "Failed to build circuit: {}",
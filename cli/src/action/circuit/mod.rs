@@ -31,7 +31,7 @@ use splinter::admin::{
     CIRCUIT_PROTOCOL_VERSION,
 };
 
-use crate::circuit::builder::parse_hex;
+use crate::circuit::builder::{parse_hex, PEER_SERVICES_ARG};
 use crate::error::CliError;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 #[cfg(feature = "circuit-template")]
@@ -47,6 +47,8 @@ use api::{CircuitMembers, CircuitServiceSlice, CircuitSlice};
 pub(crate) use builder::CreateCircuitMessageBuilder;
 use payload::make_signed_payload;
 
+const SCABBARD_SERVICE_TYPE: &str = "scabbard";
+
 pub struct CircuitProposeAction;
 
 impl Action for CircuitProposeAction {
@@ -82,6 +84,18 @@ impl Action for CircuitProposeAction {
             }
         }
 
+        if let Some(node_relays) = args.values_of("node_relay") {
+            if args.value_of("compat_version") == Some("0.4") {
+                return Err(CliError::ActionError(
+                    "Relayed routes are not compatible with Splinter v0.4".to_string(),
+                ));
+            }
+            for node_argument in node_relays {
+                let (node, relay_node) = parse_node_relay(node_argument)?;
+                builder.set_node_relay(&node, &relay_node)?;
+            }
+        }
+
         #[cfg(feature = "circuit-template")]
         {
             if let Some(template_name) = args.value_of("template") {
@@ -139,41 +153,25 @@ impl Action for CircuitProposeAction {
             }
         }
 
+        if let Some(durability) = args.value_of("durability") {
+            if args.value_of("compat_version") == Some("0.4") && durability != "none" {
+                return Err(CliError::ActionError(
+                    "Guaranteed delivery is not compatible with Splinter v0.4".to_string(),
+                ));
+            }
+            builder.set_durability(durability)?;
+        }
+
         if let Some(management_type) = args.value_of("management_type") {
             builder.set_management_type(management_type);
         }
 
-        if let Some(mut application_metadata) = args.values_of("metadata") {
+        if let Some(application_metadata) = args.values_of("metadata") {
             let encoding = args.value_of("metadata_encoding").unwrap_or("string");
-            match encoding {
-                "string" => {
-                    if application_metadata.len() > 1 {
-                        return Err(CliError::ActionError(
-                            "Multiple metadata values with encoding 'string' is not allowed".into(),
-                        ));
-                    }
-                    if let Some(metadata) = application_metadata.next() {
-                        builder.set_application_metadata(metadata.as_bytes());
-                    }
-                }
-                "json" => {
-                    let mut json_string = "{".to_string();
-                    for metadata in application_metadata {
-                        let values = parse_application_metadata_json(metadata)?;
-                        json_string = format!("{}{},", json_string, values);
-                    }
-                    json_string.pop();
-                    json_string.push('}');
-
-                    builder.set_application_metadata(json_string.as_bytes());
-                }
-                _ => {
-                    return Err(CliError::ActionError(format!(
-                        "Metadata encoding '{}' is not supported",
-                        encoding
-                    )))
-                }
-            }
+            builder.set_application_metadata(&parse_application_metadata(
+                application_metadata.collect(),
+                encoding,
+            )?);
         }
 
         if let Some(service_types) = args.values_of("service_type") {
@@ -334,6 +332,40 @@ fn parse_node_argument(node_argument: &str) -> Result<(String, Vec<String>), Cli
     Ok((node_id, endpoints))
 }
 
+fn parse_node_relay(node_argument: &str) -> Result<(String, String), CliError> {
+    let mut iter = node_argument.split("::");
+
+    let node_id = iter
+        .next()
+        .expect("str::split cannot return an empty iterator")
+        .to_string();
+    if node_id.is_empty() {
+        return Err(CliError::ActionError(
+            "Empty '--node-relay' argument detected".into(),
+        ));
+    }
+
+    let relay_node_id = iter
+        .next()
+        .ok_or_else(|| CliError::ActionError(format!("Missing relay node for node '{}'", node_id)))?
+        .to_string();
+    if relay_node_id.is_empty() {
+        return Err(CliError::ActionError(format!(
+            "No relay node detected for node '{}'",
+            node_id
+        )));
+    }
+
+    if relay_node_id == node_id {
+        return Err(CliError::ActionError(format!(
+            "Node '{}' cannot be its own relay node",
+            node_id
+        )));
+    }
+
+    Ok((node_id, relay_node_id))
+}
+
 fn parse_node_public_key(node_argument: &str) -> Result<(String, String), CliError> {
     let mut iter = node_argument.split("::");
 
@@ -427,6 +459,39 @@ fn parse_service_peer_group(peer_group: &str) -> Result<Vec<&str>, CliError> {
         .collect::<Result<_, _>>()
 }
 
+/// Encodes the values of the `--metadata` argument as application metadata, using the given
+/// encoding.
+fn parse_application_metadata(metadata: Vec<&str>, encoding: &str) -> Result<Vec<u8>, CliError> {
+    match encoding {
+        "string" => {
+            if metadata.len() > 1 {
+                return Err(CliError::ActionError(
+                    "Multiple metadata values with encoding 'string' is not allowed".into(),
+                ));
+            }
+            Ok(metadata
+                .first()
+                .map(|metadata| metadata.as_bytes().to_vec())
+                .unwrap_or_default())
+        }
+        "json" => {
+            let mut json_string = "{".to_string();
+            for metadata in metadata {
+                let values = parse_application_metadata_json(metadata)?;
+                json_string = format!("{}{},", json_string, values);
+            }
+            json_string.pop();
+            json_string.push('}');
+
+            Ok(json_string.into_bytes())
+        }
+        _ => Err(CliError::ActionError(format!(
+            "Metadata encoding '{}' is not supported",
+            encoding
+        ))),
+    }
+}
+
 fn parse_application_metadata_json(metadata: &str) -> Result<String, CliError> {
     let mut iter = metadata.split('=');
 
@@ -771,6 +836,554 @@ fn propose_circuit_disband(
     }
 }
 
+struct CircuitUpdateRoster {
+    circuit_id: String,
+    add_services: Vec<SplinterService>,
+    remove_services: Vec<SplinterService>,
+}
+
+pub struct CircuitUpdateRosterAction;
+
+impl Action for CircuitUpdateRosterAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        let mut add_services = vec![];
+        if let Some(services) = args.values_of("add_service") {
+            for service in services {
+                let (service_id, allowed_nodes) = parse_service(service)?;
+                add_services.push(SplinterService {
+                    service_id,
+                    service_type: String::new(),
+                    allowed_nodes,
+                    arguments: vec![],
+                });
+            }
+        }
+
+        if let Some(service_types) = args.values_of("service_type") {
+            for service_type_arg in service_types {
+                let (service_id, service_type) = parse_service_type_argument(service_type_arg)?;
+                find_added_service(&mut add_services, &service_id)?.service_type = service_type;
+            }
+        }
+
+        if let Some(service_arguments) = args.values_of("service_argument") {
+            for service_argument in service_arguments {
+                let (service_id, argument) = parse_service_argument(service_argument)?;
+                find_added_service(&mut add_services, &service_id)?
+                    .arguments
+                    .push(argument);
+            }
+        }
+
+        if let Some(service) = add_services
+            .iter()
+            .find(|service| service.service_type.is_empty())
+        {
+            return Err(CliError::ActionError(format!(
+                "Missing service type for service '{}'",
+                service.service_id
+            )));
+        }
+
+        let remove_services = args
+            .values_of("remove_service")
+            .map(|services| services.map(String::from).collect())
+            .unwrap_or_default();
+
+        propose_circuit_update_roster(&url, signer, circuit_id, add_services, remove_services)
+    }
+}
+
+fn find_added_service<'a>(
+    add_services: &'a mut [SplinterService],
+    service_id: &str,
+) -> Result<&'a mut SplinterService, CliError> {
+    add_services
+        .iter_mut()
+        .find(|service| service.service_id == service_id)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Service '{}' is not being added to the circuit",
+                service_id
+            ))
+        })
+}
+
+fn propose_circuit_update_roster(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+    add_services: Vec<SplinterService>,
+    remove_services: Vec<String>,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let circuit = client.fetch_circuit(circuit_id)?;
+
+    if let Some(circuit) = circuit {
+        // Check the services to be removed are in the circuit's current roster
+        let remove_services = remove_services
+            .iter()
+            .map(|service_id| {
+                circuit
+                    .roster
+                    .iter()
+                    .find(|service| &service.service_id == service_id)
+                    .map(|service| SplinterService {
+                        service_id: service.service_id.clone(),
+                        service_type: service.service_type.clone(),
+                        allowed_nodes: vec![service.node_id.clone()],
+                        arguments: service
+                            .arguments
+                            .iter()
+                            .map(|(key, value)| (key.clone(), value.clone()))
+                            .collect(),
+                    })
+                    .ok_or_else(|| {
+                        CliError::ActionError(format!(
+                            "Service '{}' is not in circuit '{}'",
+                            service_id, circuit_id
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<SplinterService>, CliError>>()?;
+
+        let circuit_update_roster_request = CircuitUpdateRoster {
+            circuit_id: circuit_id.into(),
+            add_services,
+            remove_services,
+        };
+        let signed_payload =
+            make_signed_payload(&requester_node, signer, circuit_update_roster_request)?;
+        client.submit_admin_payload(signed_payload)
+    } else {
+        Err(CliError::ActionError(format!(
+            "Circuit '{}' does not exist",
+            circuit_id
+        )))
+    }
+}
+
+struct CircuitAddNode {
+    circuit_id: String,
+    node: SplinterNode,
+    services: Vec<SplinterService>,
+}
+
+pub struct CircuitAddNodeAction;
+
+impl Action for CircuitAddNodeAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        let (node_id, endpoints) = parse_node_argument(
+            args.value_of("node")
+                .ok_or_else(|| CliError::ActionError("'node' argument is required".into()))?,
+        )?;
+        let public_key = match args.value_of("node_public_key") {
+            Some(public_key) => Some(parse_hex(public_key)?),
+            None => None,
+        };
+        let node = SplinterNode {
+            node_id,
+            endpoints,
+            public_key,
+            relay_node_id: None,
+        };
+
+        let mut services = vec![];
+        if let Some(service_args) = args.values_of("service") {
+            for service in service_args {
+                let (service_id, allowed_nodes) = parse_service(service)?;
+                services.push(SplinterService {
+                    service_id,
+                    service_type: String::new(),
+                    allowed_nodes,
+                    arguments: vec![],
+                });
+            }
+        }
+
+        if let Some(service_types) = args.values_of("service_type") {
+            for service_type_arg in service_types {
+                let (service_id, service_type) = parse_service_type_argument(service_type_arg)?;
+                find_added_service(&mut services, &service_id)?.service_type = service_type;
+            }
+        }
+
+        if let Some(service_arguments) = args.values_of("service_argument") {
+            for service_argument in service_arguments {
+                let (service_id, argument) = parse_service_argument(service_argument)?;
+                find_added_service(&mut services, &service_id)?
+                    .arguments
+                    .push(argument);
+            }
+        }
+
+        if let Some(service) = services
+            .iter()
+            .find(|service| service.service_type.is_empty())
+        {
+            return Err(CliError::ActionError(format!(
+                "Missing service type for service '{}'",
+                service.service_id
+            )));
+        }
+
+        propose_circuit_add_node(&url, signer, circuit_id, node, services)
+    }
+}
+
+fn propose_circuit_add_node(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+    node: SplinterNode,
+    mut services: Vec<SplinterService>,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let circuit = client.fetch_circuit(circuit_id)?;
+
+    if let Some(circuit) = circuit {
+        if circuit
+            .members
+            .iter()
+            .any(|member| member.node_id == node.node_id)
+        {
+            return Err(CliError::ActionError(format!(
+                "Node '{}' is already a member of circuit '{}'",
+                node.node_id, circuit_id
+            )));
+        }
+
+        add_scabbard_peer_services(&circuit.roster, &mut services)?;
+
+        let circuit_add_node_request = CircuitAddNode {
+            circuit_id: circuit_id.into(),
+            node,
+            services,
+        };
+        let signed_payload =
+            make_signed_payload(&requester_node, signer, circuit_add_node_request)?;
+        client.submit_admin_payload(signed_payload)
+    } else {
+        Err(CliError::ActionError(format!(
+            "Circuit '{}' does not exist",
+            circuit_id
+        )))
+    }
+}
+
+/// Makes the scabbard services being added to a circuit peers of the circuit's existing scabbard
+/// services. New scabbard services without peer services are given all other scabbard services as
+/// peers, and updated definitions of the existing scabbard services are added to the request.
+fn add_scabbard_peer_services(
+    roster: &[CircuitServiceSlice],
+    services: &mut Vec<SplinterService>,
+) -> Result<(), CliError> {
+    let existing_scabbard_ids = roster
+        .iter()
+        .filter(|service| service.service_type == SCABBARD_SERVICE_TYPE)
+        .map(|service| service.service_id.clone())
+        .collect::<Vec<String>>();
+    let new_scabbard_ids = services
+        .iter()
+        .filter(|service| service.service_type == SCABBARD_SERVICE_TYPE)
+        .map(|service| service.service_id.clone())
+        .collect::<Vec<String>>();
+
+    if new_scabbard_ids.is_empty() {
+        return Ok(());
+    }
+
+    for service in services
+        .iter_mut()
+        .filter(|service| service.service_type == SCABBARD_SERVICE_TYPE)
+    {
+        if service
+            .arguments
+            .iter()
+            .any(|(key, _)| key == PEER_SERVICES_ARG)
+        {
+            continue;
+        }
+
+        let peers = existing_scabbard_ids
+            .iter()
+            .chain(new_scabbard_ids.iter())
+            .filter(|peer| **peer != service.service_id)
+            .cloned()
+            .collect::<Vec<String>>();
+        service
+            .arguments
+            .push((PEER_SERVICES_ARG.into(), format_peer_services(&peers)?));
+    }
+
+    for existing in roster
+        .iter()
+        .filter(|service| service.service_type == SCABBARD_SERVICE_TYPE)
+    {
+        if services
+            .iter()
+            .any(|service| service.service_id == existing.service_id)
+        {
+            continue;
+        }
+
+        let mut peers = parse_peer_services(existing)?;
+        peers.extend(new_scabbard_ids.iter().cloned());
+
+        services.push(with_peer_services(existing, &peers)?);
+    }
+
+    Ok(())
+}
+
+/// Returns the peer services of an existing scabbard service, which may be given as either a JSON
+/// list or a comma-separated list.
+fn parse_peer_services(service: &CircuitServiceSlice) -> Result<Vec<String>, CliError> {
+    match service.arguments.get(PEER_SERVICES_ARG) {
+        Some(peers) if peers.starts_with('[') => serde_json::from_str::<Vec<String>>(peers)
+            .map_err(|err| {
+                CliError::ActionError(format!(
+                    "Unable to parse peer services of service '{}': {}",
+                    service.service_id, err
+                ))
+            }),
+        Some(peers) => Ok(peers.split(',').map(String::from).collect()),
+        None => Ok(vec![]),
+    }
+}
+
+/// Makes an updated definition of an existing service with the given peer services.
+fn with_peer_services(
+    service: &CircuitServiceSlice,
+    peers: &[String],
+) -> Result<SplinterService, CliError> {
+    let mut arguments = service
+        .arguments
+        .iter()
+        .filter(|(key, _)| key.as_str() != PEER_SERVICES_ARG)
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect::<Vec<(String, String)>>();
+    arguments.push((PEER_SERVICES_ARG.into(), format_peer_services(peers)?));
+
+    Ok(SplinterService {
+        service_id: service.service_id.clone(),
+        service_type: service.service_type.clone(),
+        allowed_nodes: vec![service.node_id.clone()],
+        arguments,
+    })
+}
+
+/// Formats a list of peer services as the JSON list expected by scabbard.
+fn format_peer_services(peers: &[String]) -> Result<String, CliError> {
+    serde_json::to_string(peers)
+        .map_err(|err| CliError::ActionError(format!("Unable to format peer services: {}", err)))
+}
+
+struct CircuitRemoveNode {
+    circuit_id: String,
+    node_id: String,
+    services: Vec<SplinterService>,
+}
+
+pub struct CircuitRemoveNodeAction;
+
+impl Action for CircuitRemoveNodeAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        let node_id = args
+            .value_of("node")
+            .ok_or_else(|| CliError::ActionError("'node' argument is required".into()))?;
+
+        propose_circuit_remove_node(&url, signer, circuit_id, node_id)
+    }
+}
+
+fn propose_circuit_remove_node(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+    node_id: &str,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let circuit = client.fetch_circuit(circuit_id)?;
+
+    if let Some(circuit) = circuit {
+        if !circuit
+            .members
+            .iter()
+            .any(|member| member.node_id == node_id)
+        {
+            return Err(CliError::ActionError(format!(
+                "Node '{}' is not a member of circuit '{}'",
+                node_id, circuit_id
+            )));
+        }
+
+        let services = remove_scabbard_peer_services(&circuit.roster, node_id)?;
+
+        let circuit_remove_node_request = CircuitRemoveNode {
+            circuit_id: circuit_id.into(),
+            node_id: node_id.into(),
+            services,
+        };
+        let signed_payload =
+            make_signed_payload(&requester_node, signer, circuit_remove_node_request)?;
+        client.submit_admin_payload(signed_payload)
+    } else {
+        Err(CliError::ActionError(format!(
+            "Circuit '{}' does not exist",
+            circuit_id
+        )))
+    }
+}
+
+/// Returns updated definitions of the scabbard services that remain in a circuit after a node is
+/// removed, with the scabbard services of the removed node removed from their peer services.
+fn remove_scabbard_peer_services(
+    roster: &[CircuitServiceSlice],
+    node_id: &str,
+) -> Result<Vec<SplinterService>, CliError> {
+    let removed_scabbard_ids = roster
+        .iter()
+        .filter(|service| {
+            service.service_type == SCABBARD_SERVICE_TYPE && service.node_id == node_id
+        })
+        .map(|service| service.service_id.clone())
+        .collect::<Vec<String>>();
+
+    let mut services = vec![];
+    for remaining in roster.iter().filter(|service| {
+        service.service_type == SCABBARD_SERVICE_TYPE && service.node_id != node_id
+    }) {
+        let peers = parse_peer_services(remaining)?;
+        if !peers.iter().any(|peer| removed_scabbard_ids.contains(peer)) {
+            continue;
+        }
+
+        let peers = peers
+            .into_iter()
+            .filter(|peer| !removed_scabbard_ids.contains(peer))
+            .collect::<Vec<String>>();
+        services.push(with_peer_services(remaining, &peers)?);
+    }
+
+    Ok(services)
+}
+
+struct CircuitUpdateMetadata {
+    circuit_id: String,
+    application_metadata: Vec<u8>,
+}
+
+pub struct CircuitUpdateMetadataAction;
+
+impl Action for CircuitUpdateMetadataAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        let metadata = args
+            .values_of("metadata")
+            .ok_or_else(|| CliError::ActionError("'metadata' argument is required".into()))?;
+        let encoding = args.value_of("metadata_encoding").unwrap_or("string");
+        let application_metadata = parse_application_metadata(metadata.collect(), encoding)?;
+
+        propose_circuit_update_metadata(&url, signer, circuit_id, application_metadata)
+    }
+}
+
+fn propose_circuit_update_metadata(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+    application_metadata: Vec<u8>,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+
+    if client.fetch_circuit(circuit_id)?.is_none() {
+        return Err(CliError::ActionError(format!(
+            "Circuit '{}' does not exist",
+            circuit_id
+        )));
+    }
+
+    let circuit_update_metadata_request = CircuitUpdateMetadata {
+        circuit_id: circuit_id.into(),
+        application_metadata,
+    };
+    let signed_payload =
+        make_signed_payload(&requester_node, signer, circuit_update_metadata_request)?;
+    client.submit_admin_payload(signed_payload)
+}
+
 struct CircuitPurge {
     circuit_id: String,
 }
@@ -955,8 +1568,17 @@ impl Action for CircuitListAction {
             .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
             .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
 
-        let member_filter = arg_matches.and_then(|args| args.value_of("member"));
-        let status_filter = arg_matches.and_then(|args| args.value_of("circuit_status"));
+        let filters = filter_query_params(
+            arg_matches,
+            &[
+                ("member", "filter"),
+                ("circuit_status", "status"),
+                ("service_type", "service_type"),
+                ("service_arg", "service_arg"),
+                ("display_name", "display_name"),
+                ("circuit_version", "circuit_version"),
+            ],
+        );
 
         let format = arg_matches
             .and_then(|args| {
@@ -970,14 +1592,29 @@ impl Action for CircuitListAction {
 
         let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
 
-        list_circuits(&url, member_filter, status_filter, format, signer)
+        list_circuits(&url, &filters, format, signer)
     }
 }
 
+/// Collects the query parameters for the filter arguments that were provided; `params` maps each
+/// argument name to the name of its query parameter.
+fn filter_query_params<'a>(
+    arg_matches: Option<&'a ArgMatches<'_>>,
+    params: &[(&str, &'static str)],
+) -> Vec<(&'static str, &'a str)> {
+    params
+        .iter()
+        .filter_map(|(arg, param)| {
+            arg_matches
+                .and_then(|args| args.value_of(arg))
+                .map(|value| (*param, value))
+        })
+        .collect()
+}
+
 fn list_circuits(
     url: &str,
-    member_filter: Option<&str>,
-    status_filter: Option<&str>,
+    filters: &[(&str, &str)],
     format: &str,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
@@ -986,7 +1623,7 @@ fn list_circuits(
         .with_auth(create_cylinder_jwt_auth(signer)?)
         .build()?;
 
-    let circuits = client.list_circuits(member_filter, status_filter)?;
+    let circuits = client.list_circuits(filters)?;
     let mut data = vec![
         // Header
         vec![
@@ -1136,9 +1773,20 @@ impl Action for CircuitProposalsAction {
             .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
             .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
 
-        let management_type_filter = arg_matches.and_then(|args| args.value_of("management_type"));
-
-        let member_filter = arg_matches.and_then(|args| args.value_of("member"));
+        let filters = filter_query_params(
+            arg_matches,
+            &[
+                ("management_type", "management_type"),
+                ("member", "member"),
+                ("service_type", "service_type"),
+                ("service_arg", "service_arg"),
+                ("display_name", "display_name"),
+                ("circuit_version", "circuit_version"),
+                ("requester", "requester"),
+                ("voted_by", "voted_by"),
+                ("awaiting_vote_from", "awaiting_vote_from"),
+            ],
+        );
 
         let format = arg_matches
             .and_then(|args| {
@@ -1152,14 +1800,13 @@ impl Action for CircuitProposalsAction {
 
         let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
 
-        list_proposals(&url, management_type_filter, member_filter, format, signer)
+        list_proposals(&url, &filters, format, signer)
     }
 }
 
 fn list_proposals(
     url: &str,
-    management_type_filter: Option<&str>,
-    member_filter: Option<&str>,
+    filters: &[(&str, &str)],
     format: &str,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
@@ -1168,7 +1815,7 @@ fn list_proposals(
         .with_auth(create_cylinder_jwt_auth(signer)?)
         .build()?;
 
-    let proposals = client.list_proposals(management_type_filter, member_filter)?;
+    let proposals = client.list_proposals(filters)?;
     let mut data = vec![
         // header
         vec![
@@ -14,20 +14,25 @@
 
 use cylinder::Signer;
 use openssl::hash::{hash, MessageDigest};
-use protobuf::Message;
-use splinter::admin::messages::CreateCircuit;
+use protobuf::{Message, RepeatedField};
+use splinter::admin::messages::{CreateCircuit, SplinterService};
 use splinter::protos::admin::CircuitAbandon;
 use splinter::protos::admin::ProposalRemoveRequest;
 use splinter::protos::admin::{
     CircuitCreateRequest, CircuitDisbandRequest, CircuitManagementPayload,
     CircuitManagementPayload_Action as Action, CircuitManagementPayload_Header as Header,
     CircuitProposalVote, CircuitProposalVote_Vote, CircuitPurgeRequest,
+    CircuitUpdateAddNodeRequest, CircuitUpdateApplicationMetadataRequest,
+    CircuitUpdateRemoveNodeRequest, CircuitUpdateRosterRequest,
 };
 
 use crate::error::CliError;
 
 use super::RemoveProposal;
-use super::{AbandonedCircuit, CircuitDisband, CircuitPurge};
+use super::{
+    AbandonedCircuit, CircuitAddNode, CircuitDisband, CircuitPurge, CircuitRemoveNode,
+    CircuitUpdateMetadata, CircuitUpdateRoster,
+};
 use super::{CircuitVote, Vote};
 
 /// A circuit action that has a type and can be converted into a protobuf-serializable struct.
@@ -158,6 +163,105 @@ impl ApplyToEnvelope for CircuitDisbandRequest {
     }
 }
 
+impl CircuitAction<CircuitUpdateRosterRequest> for CircuitUpdateRoster {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_UPDATE_ROSTER_REQUEST
+    }
+
+    fn into_proto(self) -> Result<CircuitUpdateRosterRequest, CliError> {
+        let mut update_roster_request = CircuitUpdateRosterRequest::new();
+        update_roster_request.set_circuit_id(self.circuit_id);
+        update_roster_request.set_add_services(RepeatedField::from_vec(
+            self.add_services
+                .into_iter()
+                .map(SplinterService::into_proto)
+                .collect(),
+        ));
+        update_roster_request.set_remove_services(RepeatedField::from_vec(
+            self.remove_services
+                .into_iter()
+                .map(SplinterService::into_proto)
+                .collect(),
+        ));
+        Ok(update_roster_request)
+    }
+}
+
+impl ApplyToEnvelope for CircuitUpdateRosterRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_update_roster_request(self);
+    }
+}
+
+impl CircuitAction<CircuitUpdateAddNodeRequest> for CircuitAddNode {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_UPDATE_ADD_NODE
+    }
+
+    fn into_proto(self) -> Result<CircuitUpdateAddNodeRequest, CliError> {
+        let mut add_node_request = CircuitUpdateAddNodeRequest::new();
+        add_node_request.set_circuit_id(self.circuit_id);
+        add_node_request.set_node(self.node.into_proto());
+        add_node_request.set_services(RepeatedField::from_vec(
+            self.services
+                .into_iter()
+                .map(SplinterService::into_proto)
+                .collect(),
+        ));
+        Ok(add_node_request)
+    }
+}
+
+impl ApplyToEnvelope for CircuitUpdateAddNodeRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_update_add_node(self);
+    }
+}
+
+impl CircuitAction<CircuitUpdateRemoveNodeRequest> for CircuitRemoveNode {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_UPDATE_REMOVE_NODE
+    }
+
+    fn into_proto(self) -> Result<CircuitUpdateRemoveNodeRequest, CliError> {
+        let mut remove_node_request = CircuitUpdateRemoveNodeRequest::new();
+        remove_node_request.set_circuit_id(self.circuit_id);
+        remove_node_request.set_node_id(self.node_id);
+        remove_node_request.set_services(RepeatedField::from_vec(
+            self.services
+                .into_iter()
+                .map(SplinterService::into_proto)
+                .collect(),
+        ));
+        Ok(remove_node_request)
+    }
+}
+
+impl ApplyToEnvelope for CircuitUpdateRemoveNodeRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_update_remove_node(self);
+    }
+}
+
+impl CircuitAction<CircuitUpdateApplicationMetadataRequest> for CircuitUpdateMetadata {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST
+    }
+
+    fn into_proto(self) -> Result<CircuitUpdateApplicationMetadataRequest, CliError> {
+        let mut update_metadata_request = CircuitUpdateApplicationMetadataRequest::new();
+        update_metadata_request.set_circuit_id(self.circuit_id);
+        update_metadata_request.set_application_metedata(self.application_metadata);
+        Ok(update_metadata_request)
+    }
+}
+
+impl ApplyToEnvelope for CircuitUpdateApplicationMetadataRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_update_application_metadata_request(self);
+    }
+}
+
 impl CircuitAction<CircuitPurgeRequest> for CircuitPurge {
     fn action_type(&self) -> Action {
         Action::CIRCUIT_PURGE_REQUEST
@@ -0,0 +1,377 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The format of a Splinter database backup archive
+
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+
+use protobuf::Message;
+use serde::{Deserialize, Serialize};
+use splinter::admin::store::{Circuit, CircuitProposal};
+use splinter::biome::credentials::store::Credentials;
+use splinter::biome::key_management::Key;
+use splinter::biome::profile::store::Profile;
+use splinter::protos::admin;
+use splinter::registry::YamlNode;
+use splinter::rest_api::auth::authorization::rbac::store::{
+    Assignment, AssignmentBuilder, Identity, Role, RoleBuilder, RoleScope, RoleScopeBuilder,
+};
+
+use crate::error::CliError;
+
+/// The version of the archive format written by this release.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// A backup of all of the data a Splinter daemon keeps in its database.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Backup {
+    /// The version of the archive format; used to reject archives that this release cannot read.
+    pub version: u32,
+    /// The ID of the node the backup was taken from, if one had been set.
+    pub node_id: Option<String>,
+    pub admin: AdminBackup,
+    pub registry: Vec<YamlNode>,
+    pub rbac: RbacBackup,
+    pub biome: BiomeBackup,
+    pub scabbard: Vec<ScabbardServiceBackup>,
+}
+
+/// The circuits and circuit proposals of the admin service store.
+///
+/// Circuits and proposals are stored as hex-encoded admin protobuf messages, so that the archive
+/// uses the same representation that is exchanged between nodes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AdminBackup {
+    pub circuits: Vec<String>,
+    pub proposals: Vec<String>,
+}
+
+/// The roles and assignments of the role-based authorization store.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RbacBackup {
+    pub roles: Vec<BackupRole>,
+    pub assignments: Vec<BackupAssignment>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupRole {
+    pub id: String,
+    pub display_name: String,
+    pub permissions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<BackupRoleScope>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupRoleScope {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub management_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "lowercase")]
+pub enum BackupIdentity {
+    Key(String),
+    User(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupAssignment {
+    pub identity: BackupIdentity,
+    pub roles: Vec<String>,
+}
+
+/// The users, keys and profiles of Biome.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BiomeBackup {
+    pub credentials: Vec<BackupCredentials>,
+    pub keys: Vec<BackupKey>,
+    pub profiles: Vec<Profile>,
+}
+
+/// A user's credentials; the password is the hash stored in the database, never the plain text
+/// password.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupCredentials {
+    pub user_id: String,
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupKey {
+    pub public_key: String,
+    pub encrypted_private_key: String,
+    pub user_id: String,
+    pub display_name: String,
+}
+
+/// The state of a scabbard service run by the local node.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScabbardServiceBackup {
+    pub circuit_id: String,
+    pub service_id: String,
+    /// The current commit hash of the service; `None` if the service has not committed a batch.
+    pub commit_hash: Option<String>,
+    /// The leaves of the service's merkle state at the current commit hash.
+    pub state: Vec<StateEntry>,
+}
+
+/// A single leaf of a scabbard service's merkle state.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateEntry {
+    pub address: String,
+    /// The hex-encoded value stored at the address.
+    pub value: String,
+}
+
+impl Backup {
+    /// Parses a backup archive, checking its version before reading the rest of the archive.
+    pub fn from_json(json: &str) -> Result<Self, CliError> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|err| CliError::ActionError(format!("Backup is not valid JSON: {}", err)))?;
+
+        match value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == u64::from(BACKUP_VERSION) => (),
+            Some(version) => {
+                return Err(CliError::ActionError(format!(
+                    "Backup version {} is not supported; expected version {}",
+                    version, BACKUP_VERSION
+                )))
+            }
+            None => {
+                return Err(CliError::ActionError(
+                    "Backup does not contain a version".to_string(),
+                ))
+            }
+        }
+
+        serde_json::from_value(value)
+            .map_err(|err| CliError::ActionError(format!("Backup is not valid: {}", err)))
+    }
+}
+
+pub fn circuit_to_backup(circuit: &Circuit) -> Result<String, CliError> {
+    admin::Circuit::from(circuit)
+        .write_to_bytes()
+        .map(|bytes| to_hex(&bytes))
+        .map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to serialize circuit {}: {}",
+                circuit.circuit_id(),
+                err
+            ))
+        })
+}
+
+pub fn circuit_from_backup(hex: &str) -> Result<Circuit, CliError> {
+    let proto = admin::Circuit::parse_from_bytes(&parse_hex(hex)?)
+        .map_err(|err| CliError::ActionError(format!("Unable to parse circuit: {}", err)))?;
+    Circuit::try_from(&proto)
+        .map_err(|err| CliError::ActionError(format!("Invalid circuit in backup: {}", err)))
+}
+
+pub fn proposal_to_backup(proposal: CircuitProposal) -> Result<String, CliError> {
+    let circuit_id = proposal.circuit_id().to_string();
+    proposal
+        .into_proto()
+        .write_to_bytes()
+        .map(|bytes| to_hex(&bytes))
+        .map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to serialize proposal for circuit {}: {}",
+                circuit_id, err
+            ))
+        })
+}
+
+pub fn proposal_from_backup(hex: &str) -> Result<CircuitProposal, CliError> {
+    let proto = admin::CircuitProposal::parse_from_bytes(&parse_hex(hex)?)
+        .map_err(|err| CliError::ActionError(format!("Unable to parse proposal: {}", err)))?;
+    CircuitProposal::from_proto(proto)
+        .map_err(|err| CliError::ActionError(format!("Invalid proposal in backup: {}", err)))
+}
+
+impl From<Role> for BackupRole {
+    fn from(role: Role) -> Self {
+        let (id, display_name, permissions, scopes) = role.into_parts();
+        BackupRole {
+            id,
+            display_name,
+            permissions,
+            scopes: scopes.into_iter().map(BackupRoleScope::from).collect(),
+        }
+    }
+}
+
+impl BackupRole {
+    pub fn into_role(self) -> Result<Role, CliError> {
+        let scopes = self
+            .scopes
+            .into_iter()
+            .map(BackupRoleScope::into_scope)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        RoleBuilder::new()
+            .with_id(self.id)
+            .with_display_name(self.display_name)
+            .with_permissions(self.permissions)
+            .with_scopes(scopes)
+            .build()
+            .map_err(|err| CliError::ActionError(format!("Invalid role in backup: {}", err)))
+    }
+}
+
+impl From<RoleScope> for BackupRoleScope {
+    fn from(scope: RoleScope) -> Self {
+        let (circuit_id, management_type, service_id) = scope.into_parts();
+        BackupRoleScope {
+            circuit_id,
+            management_type,
+            service_id,
+        }
+    }
+}
+
+impl BackupRoleScope {
+    pub fn into_scope(self) -> Result<RoleScope, CliError> {
+        let mut builder = RoleScopeBuilder::new();
+        if let Some(circuit_id) = self.circuit_id {
+            builder = builder.with_circuit_id(circuit_id);
+        }
+        if let Some(management_type) = self.management_type {
+            builder = builder.with_management_type(management_type);
+        }
+        if let Some(service_id) = self.service_id {
+            builder = builder.with_service_id(service_id);
+        }
+        builder
+            .build()
+            .map_err(|err| CliError::ActionError(format!("Invalid role scope in backup: {}", err)))
+    }
+}
+
+impl From<Assignment> for BackupAssignment {
+    fn from(assignment: Assignment) -> Self {
+        let (identity, roles) = assignment.into_parts();
+        let identity = match identity {
+            Identity::Key(key) => BackupIdentity::Key(key),
+            Identity::User(user_id) => BackupIdentity::User(user_id),
+        };
+        BackupAssignment { identity, roles }
+    }
+}
+
+impl BackupAssignment {
+    pub fn into_assignment(self) -> Result<Assignment, CliError> {
+        let identity = match self.identity {
+            BackupIdentity::Key(key) => Identity::Key(key),
+            BackupIdentity::User(user_id) => Identity::User(user_id),
+        };
+        AssignmentBuilder::new()
+            .with_identity(identity)
+            .with_roles(self.roles)
+            .build()
+            .map_err(|err| CliError::ActionError(format!("Invalid assignment in backup: {}", err)))
+    }
+}
+
+impl From<Credentials> for BackupCredentials {
+    fn from(credentials: Credentials) -> Self {
+        BackupCredentials {
+            user_id: credentials.user_id,
+            username: credentials.username,
+            password: credentials.password,
+        }
+    }
+}
+
+impl From<BackupCredentials> for Credentials {
+    fn from(credentials: BackupCredentials) -> Self {
+        Credentials {
+            user_id: credentials.user_id,
+            username: credentials.username,
+            password: credentials.password,
+        }
+    }
+}
+
+impl From<Key> for BackupKey {
+    fn from(key: Key) -> Self {
+        BackupKey {
+            public_key: key.public_key,
+            encrypted_private_key: key.encrypted_private_key,
+            user_id: key.user_id,
+            display_name: key.display_name,
+        }
+    }
+}
+
+impl From<BackupKey> for Key {
+    fn from(key: BackupKey) -> Self {
+        Key::new(
+            &key.public_key,
+            &key.encrypted_private_key,
+            &key.user_id,
+            &key.display_name,
+        )
+    }
+}
+
+impl StateEntry {
+    pub fn new(address: String, value: &[u8]) -> Self {
+        StateEntry {
+            address,
+            value: to_hex(value),
+        }
+    }
+
+    pub fn into_parts(self) -> Result<(String, Vec<u8>), CliError> {
+        let value = parse_hex(&self.value)?;
+        Ok((self.address, value))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut buf = String::new();
+    for b in bytes {
+        write!(&mut buf, "{:02x}", b).expect("Unable to write to string");
+    }
+
+    buf
+}
+
+fn parse_hex(hex: &str) -> Result<Vec<u8>, CliError> {
+    if hex.len() % 2 != 0 {
+        return Err(CliError::ActionError(format!(
+            "{} is not valid hex: odd number of digits",
+            hex
+        )));
+    }
+
+    let mut res = vec![];
+    for i in (0..hex.len()).step_by(2) {
+        res.push(
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|_| CliError::ActionError(format!("{} contains invalid hex", hex)))?,
+        );
+    }
+
+    Ok(res)
+}
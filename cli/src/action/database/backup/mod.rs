@@ -0,0 +1,788 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides database backup and restore functionality
+//!
+//! A backup is a single, versioned JSON archive containing the admin service store, the
+//! registry, the role-based authorization store, Biome, the node ID, and the merkle state and
+//! commit hash of every scabbard service run by the local node. A backup taken from a SQLite
+//! database may be restored into a PostgreSQL database, and vice versa.
+//!
+//! A backup reads the database, and a restore writes to it, in a single transaction; a restore
+//! that fails leaves the database as it was.
+
+mod archive;
+mod transaction;
+
+use std::convert::TryInto;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use clap::ArgMatches;
+use scabbard::store::transact::factory::LmdbDatabaseFactory;
+use splinter::store::StoreFactory;
+use transact::state::merkle::MerkleRadixLeafReader;
+
+#[cfg(feature = "postgres")]
+use crate::action::database::postgres::postgres_migrations;
+#[cfg(feature = "sqlite")]
+use crate::action::database::sqlite::sqlite_migrations;
+use crate::action::database::{
+    get_default_database,
+    state::{database_exists, get_merkle_state, get_state_dir, write_state},
+    stores::UpgradeStores,
+    ConnectionUri,
+};
+use crate::error::CliError;
+
+use super::Action;
+
+use self::archive::{
+    circuit_from_backup, circuit_to_backup, proposal_from_backup, proposal_to_backup, Backup,
+    BackupAssignment, BackupRole, ScabbardServiceBackup, StateEntry, BACKUP_VERSION,
+};
+use self::transaction::in_transaction;
+
+const SCABBARD_SERVICE_TYPE: &str = "scabbard";
+
+/// Writes the contents of a Splinter database to a backup archive
+pub struct BackupAction;
+
+impl Action for BackupAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let output = args
+            .value_of("output")
+            .ok_or_else(|| CliError::ActionError("'output' argument is required".to_string()))?;
+        let database = get_database(args)?;
+        let state_dir = get_state_dir(arg_matches)?;
+
+        info!("Backing up Splinter database {}", database);
+
+        // The stores are read in one transaction, so that the backup is a consistent snapshot of
+        // the database
+        let backup = in_transaction(&database, |store_factory, upgrade_stores| {
+            let mut backup = export_stores(store_factory)?;
+            backup.scabbard = export_scabbard_state(
+                &database,
+                backup.node_id.as_deref(),
+                store_factory,
+                upgrade_stores,
+                &LmdbDatabaseFactory::new_state_db_factory(&state_dir, None),
+            )?;
+            Ok(backup)
+        })?;
+
+        if Path::new(output).exists() && !args.is_present("force") {
+            return Err(CliError::EnvironmentError(format!(
+                "Backup file {} already exists; use --force to overwrite it",
+                output
+            )));
+        }
+
+        let json = serde_json::to_string(&backup)
+            .map_err(|err| CliError::ActionError(format!("Unable to serialize backup: {}", err)))?;
+        fs::write(output, json).map_err(|err| {
+            CliError::ActionError(format!("Unable to write backup file {}: {}", output, err))
+        })?;
+
+        info!("Backup written to {}", output);
+
+        Ok(())
+    }
+}
+
+/// Restores the contents of a backup archive into an empty Splinter database
+pub struct RestoreAction;
+
+impl Action for RestoreAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let input = args
+            .value_of("input")
+            .ok_or_else(|| CliError::ActionError("'input' argument is required".to_string()))?;
+        let database = get_database(args)?;
+
+        let backup = Backup::from_json(&fs::read_to_string(input).map_err(|err| {
+            CliError::ActionError(format!("Unable to read backup file {}: {}", input, err))
+        })?)?;
+
+        info!("Restoring {} into Splinter database {}", input, database);
+
+        // The target database may be new, so bring it up to date before writing to it
+        match ConnectionUri::from_str(&database)? {
+            #[cfg(feature = "postgres")]
+            ConnectionUri::Postgres(url) => postgres_migrations(&url)?,
+            #[cfg(feature = "sqlite")]
+            ConnectionUri::Sqlite(connection_string) => sqlite_migrations(connection_string)?,
+        }
+
+        // The backup is restored in one transaction, so that a restore that fails part way
+        // through does not leave the database partially restored
+        in_transaction(&database, |store_factory, upgrade_stores| {
+            check_database_is_empty(store_factory)?;
+
+            let scabbard = import_stores(backup, store_factory)?;
+            import_scabbard_state(&database, scabbard, upgrade_stores)
+        })?;
+
+        info!("Backup successfully restored to {}", database);
+
+        Ok(())
+    }
+}
+
+fn get_database(args: &ArgMatches) -> Result<String, CliError> {
+    match args.value_of("connect") {
+        Some(database) => Ok(database.to_string()),
+        None => get_default_database(),
+    }
+}
+
+fn get_stores(
+    upgrade_stores: &Option<Box<dyn UpgradeStores>>,
+) -> Result<&dyn UpgradeStores, CliError> {
+    upgrade_stores.as_deref().ok_or_else(|| {
+        // this should never happen
+        CliError::ActionError("Upgrade store for database type is not configured".to_string())
+    })
+}
+
+/// Reads the contents of every store, other than scabbard's, into a backup.
+fn export_stores(store_factory: &dyn StoreFactory) -> Result<Backup, CliError> {
+    let mut backup = Backup {
+        version: BACKUP_VERSION,
+        ..Default::default()
+    };
+
+    backup.node_id = store_factory
+        .get_node_id_store()
+        .get_node_id()
+        .map_err(|err| CliError::ActionError(format!("Unable to get node ID: {}", err)))?;
+
+    let admin_store = store_factory.get_admin_service_store();
+    backup.admin.circuits = admin_store
+        .list_circuits(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to list circuits: {}", err)))?
+        .map(|circuit| circuit_to_backup(&circuit))
+        .collect::<Result<_, _>>()?;
+    backup.admin.proposals = admin_store
+        .list_proposals(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to list proposals: {}", err)))?
+        .map(proposal_to_backup)
+        .collect::<Result<_, _>>()?;
+
+    backup.registry = store_factory
+        .get_registry_store()
+        .list_nodes(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to list registry nodes: {}", err)))?
+        .map(Into::into)
+        .collect();
+
+    let rbac_store = store_factory.get_role_based_authorization_store();
+    backup.rbac.roles = rbac_store
+        .list_roles()
+        .map_err(|err| CliError::ActionError(format!("Unable to list roles: {}", err)))?
+        .map(BackupRole::from)
+        .collect();
+    backup.rbac.assignments = rbac_store
+        .list_assignments()
+        .map_err(|err| CliError::ActionError(format!("Unable to list assignments: {}", err)))?
+        .map(BackupAssignment::from)
+        .collect();
+
+    let credentials_store = store_factory.get_biome_credentials_store();
+    backup.biome.credentials = credentials_store
+        .list_usernames()
+        .map_err(|err| CliError::ActionError(format!("Unable to list users: {}", err)))?
+        .into_iter()
+        .map(|username_id| {
+            credentials_store
+                .fetch_credential_by_user_id(&username_id.user_id)
+                .map(Into::into)
+                .map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Unable to get credentials for user {}: {}",
+                        username_id.username, err
+                    ))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+    backup.biome.keys = store_factory
+        .get_biome_key_store()
+        .list_keys(None)
+        .map_err(|err| CliError::ActionError(format!("Unable to list keys: {}", err)))?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    backup.biome.profiles = store_factory
+        .get_biome_user_profile_store()
+        .list_profiles()
+        .map_err(|err| CliError::ActionError(format!("Unable to list profiles: {}", err)))?
+        .unwrap_or_default();
+
+    Ok(backup)
+}
+
+/// Reads the commit hash and current merkle state of each scabbard service run by the local
+/// node.
+///
+/// The state of a service is read from the database if the database contains its merkle tree,
+/// otherwise it is read from the service's LMDB file in the state directory.
+fn export_scabbard_state(
+    database: &str,
+    node_id: Option<&str>,
+    store_factory: &dyn StoreFactory,
+    upgrade_stores: &Option<Box<dyn UpgradeStores>>,
+    lmdb_db_factory: &LmdbDatabaseFactory,
+) -> Result<Vec<ScabbardServiceBackup>, CliError> {
+    let stores = get_stores(upgrade_stores)?;
+    let node_id = match node_id {
+        Some(node_id) => node_id,
+        // This node has not set a node id, so it cannot have any services
+        None => return Ok(vec![]),
+    };
+
+    let local_services = store_factory
+        .get_admin_service_store()
+        .list_circuits(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to list circuits: {}", err)))?
+        .flat_map(|circuit| {
+            circuit
+                .roster()
+                .iter()
+                .filter(|svc| {
+                    svc.node_id() == node_id && svc.service_type() == SCABBARD_SERVICE_TYPE
+                })
+                .map(|svc| {
+                    (
+                        circuit.circuit_id().to_string(),
+                        svc.service_id().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut services = vec![];
+    for (circuit_id, service_id) in local_services {
+        info!("Backing up state for {}::{}", circuit_id, service_id);
+        let commit_hash = stores
+            .new_commit_hash_store(&circuit_id, &service_id)
+            .get_current_commit_hash()
+            .map_err(|err| CliError::ActionError(format!("{}", err)))?;
+
+        let state = match &commit_hash {
+            Some(commit_hash) => {
+                let state_database = if database_exists(
+                    database,
+                    &circuit_id,
+                    &service_id,
+                    upgrade_stores,
+                    lmdb_db_factory,
+                )? {
+                    database
+                } else {
+                    "lmdb"
+                };
+
+                let state_reader = get_merkle_state(
+                    state_database,
+                    &circuit_id,
+                    &service_id,
+                    upgrade_stores,
+                    lmdb_db_factory,
+                    false,
+                )?;
+
+                state_reader
+                    .leaves(commit_hash, None)
+                    .map_err(|err| {
+                        CliError::ActionError(format!(
+                            "Unable to get leaves for commit hash: {}",
+                            err
+                        ))
+                    })?
+                    .map(|leaf| {
+                        leaf.map(|(address, value)| StateEntry::new(address, &value))
+                            .map_err(|err| {
+                                CliError::ActionError(format!("Cannot get state entry: {}", err))
+                            })
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            None => vec![],
+        };
+
+        services.push(ScabbardServiceBackup {
+            circuit_id,
+            service_id,
+            commit_hash,
+            state,
+        });
+    }
+
+    Ok(services)
+}
+
+/// Verifies that none of the stores written by a restore already contain data, so that a restore
+/// never merges two nodes' data.
+fn check_database_is_empty(store_factory: &dyn StoreFactory) -> Result<(), CliError> {
+    let mut contents = vec![];
+
+    if store_factory
+        .get_node_id_store()
+        .get_node_id()
+        .map_err(|err| CliError::ActionError(format!("Unable to get node ID: {}", err)))?
+        .is_some()
+    {
+        contents.push("a node ID");
+    }
+
+    let admin_store = store_factory.get_admin_service_store();
+    if admin_store
+        .count_circuits(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to count circuits: {}", err)))?
+        > 0
+    {
+        contents.push("circuits");
+    }
+    if admin_store
+        .count_proposals(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to count proposals: {}", err)))?
+        > 0
+    {
+        contents.push("circuit proposals");
+    }
+
+    if store_factory
+        .get_registry_store()
+        .count_nodes(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to count registry nodes: {}", err)))?
+        > 0
+    {
+        contents.push("registry nodes");
+    }
+
+    let rbac_store = store_factory.get_role_based_authorization_store();
+    if rbac_store
+        .count_roles()
+        .map_err(|err| CliError::ActionError(format!("Unable to count roles: {}", err)))?
+        > 0
+    {
+        contents.push("roles");
+    }
+    if rbac_store
+        .count_assignments()
+        .map_err(|err| CliError::ActionError(format!("Unable to count assignments: {}", err)))?
+        > 0
+    {
+        contents.push("role assignments");
+    }
+
+    if !store_factory
+        .get_biome_credentials_store()
+        .list_usernames()
+        .map_err(|err| CliError::ActionError(format!("Unable to list users: {}", err)))?
+        .is_empty()
+    {
+        contents.push("Biome users");
+    }
+    if !store_factory
+        .get_biome_key_store()
+        .list_keys(None)
+        .map_err(|err| CliError::ActionError(format!("Unable to list keys: {}", err)))?
+        .is_empty()
+    {
+        contents.push("Biome keys");
+    }
+    if !store_factory
+        .get_biome_user_profile_store()
+        .list_profiles()
+        .map_err(|err| CliError::ActionError(format!("Unable to list profiles: {}", err)))?
+        .unwrap_or_default()
+        .is_empty()
+    {
+        contents.push("Biome profiles");
+    }
+
+    if !contents.is_empty() {
+        return Err(CliError::ActionError(format!(
+            "Database already contains {}; a backup can only be restored into an empty database",
+            contents.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Writes the contents of a backup, other than scabbard's state, to the stores; the scabbard
+/// state is returned so that it may be restored separately.
+fn import_stores(
+    backup: Backup,
+    store_factory: &dyn StoreFactory,
+) -> Result<Vec<ScabbardServiceBackup>, CliError> {
+    if let Some(node_id) = backup.node_id {
+        store_factory
+            .get_node_id_store()
+            .set_node_id(node_id)
+            .map_err(|err| CliError::ActionError(format!("Unable to set node ID: {}", err)))?;
+    }
+
+    let admin_store = store_factory.get_admin_service_store();
+    for circuit in &backup.admin.circuits {
+        let circuit = circuit_from_backup(circuit)?;
+        let members = circuit.members().to_vec();
+        admin_store
+            .add_circuit(circuit, members)
+            .map_err(|err| CliError::ActionError(format!("Unable to restore circuit: {}", err)))?;
+    }
+    for proposal in &backup.admin.proposals {
+        admin_store
+            .add_proposal(proposal_from_backup(proposal)?)
+            .map_err(|err| CliError::ActionError(format!("Unable to restore proposal: {}", err)))?;
+    }
+
+    let registry = store_factory.get_registry_store();
+    for node in backup.registry {
+        let node = node.try_into().map_err(|err| {
+            CliError::ActionError(format!("Invalid registry node in backup: {}", err))
+        })?;
+        registry.add_node(node).map_err(|err| {
+            CliError::ActionError(format!("Unable to restore registry node: {}", err))
+        })?;
+    }
+
+    let rbac_store = store_factory.get_role_based_authorization_store();
+    for role in backup.rbac.roles {
+        rbac_store
+            .add_role(role.into_role()?)
+            .map_err(|err| CliError::ActionError(format!("Unable to restore role: {}", err)))?;
+    }
+    for assignment in backup.rbac.assignments {
+        rbac_store
+            .add_assignment(assignment.into_assignment()?)
+            .map_err(|err| {
+                CliError::ActionError(format!("Unable to restore assignment: {}", err))
+            })?;
+    }
+
+    let credentials_store = store_factory.get_biome_credentials_store();
+    for credentials in backup.biome.credentials {
+        credentials_store
+            .add_credentials(credentials.into())
+            .map_err(|err| CliError::ActionError(format!("Unable to restore user: {}", err)))?;
+    }
+    let key_store = store_factory.get_biome_key_store();
+    for key in backup.biome.keys {
+        key_store
+            .add_key(key.into())
+            .map_err(|err| CliError::ActionError(format!("Unable to restore key: {}", err)))?;
+    }
+    let profile_store = store_factory.get_biome_user_profile_store();
+    for profile in backup.biome.profiles {
+        profile_store
+            .add_profile(profile)
+            .map_err(|err| CliError::ActionError(format!("Unable to restore profile: {}", err)))?;
+    }
+
+    Ok(backup.scabbard)
+}
+
+/// Writes the merkle state and commit hash of each scabbard service to the database.
+fn import_scabbard_state(
+    database: &str,
+    services: Vec<ScabbardServiceBackup>,
+    upgrade_stores: &Option<Box<dyn UpgradeStores>>,
+) -> Result<(), CliError> {
+    let stores = get_stores(upgrade_stores)?;
+    // Scabbard state is always restored to the database, so the LMDB factory is never used
+    let lmdb_db_factory = LmdbDatabaseFactory::new_state_db_factory(&std::env::temp_dir(), None);
+
+    for service in services {
+        let commit_hash = match service.commit_hash {
+            Some(commit_hash) => commit_hash,
+            None => continue,
+        };
+
+        info!(
+            "Restoring state for {}::{}",
+            service.circuit_id, service.service_id
+        );
+
+        let state_writer = get_merkle_state(
+            database,
+            &service.circuit_id,
+            &service.service_id,
+            upgrade_stores,
+            &lmdb_db_factory,
+            true,
+        )?;
+        write_state(
+            service.state.into_iter().map(StateEntry::into_parts),
+            commit_hash.clone(),
+            &state_writer,
+        )?;
+
+        stores
+            .new_commit_hash_store(&service.circuit_id, &service.service_id)
+            .set_current_commit_hash(&commit_hash)
+            .map_err(|err| {
+                CliError::ActionError(format!(
+                    "Unable to restore commit hash for {}::{}: {}",
+                    service.circuit_id, service.service_id, err
+                ))
+            })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::r2d2::{ConnectionManager, Pool};
+    use diesel::sqlite::SqliteConnection;
+
+    use splinter::admin::store::{
+        AuthorizationType, CircuitBuilder, CircuitNodeBuilder, CircuitStatus, DurabilityType,
+        PersistenceType, RouteType, ServiceBuilder,
+    };
+    use splinter::biome::credentials::store::{CredentialsBuilder, PasswordEncryptionCost};
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::registry::Node;
+    use splinter::rest_api::auth::authorization::rbac::store::{
+        AssignmentBuilder, Identity, RoleBuilder, RoleScopeBuilder,
+    };
+    use splinter::store::sqlite::SqliteStoreFactory;
+
+    /// Verify that the stores of a database are restored unchanged from a backup.
+    ///
+    /// 1. Populate a database's stores and export them to a backup
+    /// 2. Round trip the backup through JSON
+    /// 3. Import the backup into an empty database and verify the stores' contents match
+    #[test]
+    fn test_backup_and_restore_stores() {
+        let source = create_store_factory();
+
+        source
+            .get_node_id_store()
+            .set_node_id("acme-node-000".to_string())
+            .expect("Unable to set node ID");
+
+        let node = CircuitNodeBuilder::new()
+            .with_node_id("acme-node-000")
+            .with_endpoints(&["tcps://splinterd-node-acme:8044".to_string()])
+            .build()
+            .expect("Unable to build node");
+        let circuit = CircuitBuilder::new()
+            .with_circuit_id("WBKLF-AAAAA")
+            .with_roster(&[ServiceBuilder::new()
+                .with_service_id("a000")
+                .with_service_type("scabbard")
+                .with_node_id("acme-node-000")
+                .with_arguments(&[("admin_keys".to_string(), "[]".to_string())])
+                .build()
+                .expect("Unable to build service")])
+            .with_members(&[node.clone()])
+            .with_authorization_type(&AuthorizationType::Trust)
+            .with_persistence(&PersistenceType::Any)
+            .with_durability(&DurabilityType::NoDurability)
+            .with_routes(&RouteType::Any)
+            .with_circuit_management_type("gameroom")
+            .with_display_name("test_display")
+            .with_circuit_status(&CircuitStatus::Active)
+            .build()
+            .expect("Unable to build circuit");
+        source
+            .get_admin_service_store()
+            .add_circuit(circuit.clone(), vec![node])
+            .expect("Unable to add circuit");
+
+        let registry_node = Node::builder("acme-node-000")
+            .with_endpoint("tcps://splinterd-node-acme:8044")
+            .with_display_name("Acme")
+            .with_key("abcd")
+            .build()
+            .expect("Unable to build registry node");
+        source
+            .get_registry_store()
+            .add_node(registry_node.clone())
+            .expect("Unable to add registry node");
+
+        let role = RoleBuilder::new()
+            .with_id("admin".to_string())
+            .with_display_name("Administrator".to_string())
+            .with_permissions(vec!["circuit.read".to_string()])
+            .with_scopes(vec![RoleScopeBuilder::new()
+                .with_management_type("gameroom".to_string())
+                .build()
+                .expect("Unable to build role scope")])
+            .build()
+            .expect("Unable to build role");
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("user-1".to_string()))
+            .with_roles(vec!["admin".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        let rbac_store = source.get_role_based_authorization_store();
+        rbac_store
+            .add_role(role.clone())
+            .expect("Unable to add role");
+        rbac_store
+            .add_assignment(assignment.clone())
+            .expect("Unable to add assignment");
+
+        let credentials = CredentialsBuilder::default()
+            .with_user_id("user-1")
+            .with_username("alice")
+            .with_password("password")
+            .with_password_encryption_cost(PasswordEncryptionCost::Low)
+            .build()
+            .expect("Unable to build credentials");
+        source
+            .get_biome_credentials_store()
+            .add_credentials(credentials.clone())
+            .expect("Unable to add credentials");
+
+        let backup = export_stores(&source).expect("Unable to export stores");
+        let json = serde_json::to_string(&backup).expect("Unable to serialize backup");
+        let backup = Backup::from_json(&json).expect("Unable to parse backup");
+
+        let target = create_store_factory();
+        check_database_is_empty(&target).expect("Target database is not empty");
+        import_stores(backup, &target).expect("Unable to import stores");
+
+        assert!(check_database_is_empty(&target).is_err());
+        assert_eq!(
+            target.get_node_id_store().get_node_id().unwrap(),
+            Some("acme-node-000".to_string())
+        );
+        assert_eq!(
+            target
+                .get_admin_service_store()
+                .get_circuit("WBKLF-AAAAA")
+                .unwrap(),
+            Some(circuit)
+        );
+        assert_eq!(
+            target
+                .get_registry_store()
+                .get_node("acme-node-000")
+                .unwrap(),
+            Some(registry_node)
+        );
+        let target_rbac_store = target.get_role_based_authorization_store();
+        let restored_role = target_rbac_store
+            .get_role("admin")
+            .unwrap()
+            .expect("Role was not restored");
+        assert_eq!(restored_role.display_name(), role.display_name());
+        assert_eq!(restored_role.permissions(), role.permissions());
+        let restored_assignment = target_rbac_store
+            .get_assignment(assignment.identity())
+            .unwrap()
+            .expect("Assignment was not restored");
+        assert_eq!(restored_assignment.roles(), assignment.roles());
+        let restored_credentials = target
+            .get_biome_credentials_store()
+            .fetch_credential_by_user_id("user-1")
+            .unwrap();
+        assert_eq!(restored_credentials, credentials);
+        assert!(restored_credentials.verify_password("password").unwrap());
+    }
+
+    /// Verify that a restore that fails part way through leaves the database empty.
+    ///
+    /// 1. Create a backup with a node ID and the same role twice, so that restoring the second
+    ///    role fails after the node ID and the first role have been written
+    /// 2. Import the backup into an empty database within a transaction and verify it fails
+    /// 3. Verify that the database is still empty
+    #[test]
+    fn test_restore_rolled_back_on_failure() {
+        let role = RoleBuilder::new()
+            .with_id("admin".to_string())
+            .with_display_name("Administrator".to_string())
+            .with_permissions(vec!["circuit.read".to_string()])
+            .build()
+            .expect("Unable to build role");
+        let mut backup = Backup {
+            version: BACKUP_VERSION,
+            node_id: Some("acme-node-000".to_string()),
+            ..Default::default()
+        };
+        backup.rbac.roles = vec![BackupRole::from(role.clone()), BackupRole::from(role)];
+
+        let pool = create_connection_pool();
+        let target = SqliteStoreFactory::new(pool.clone());
+        let result =
+            transaction::run_in_transaction(&pool, None, &target, &None, |store_factory, _| {
+                check_database_is_empty(store_factory)?;
+                import_stores(backup, store_factory)
+            });
+
+        assert!(result.is_err());
+        check_database_is_empty(&target).expect("Failed restore was not rolled back");
+    }
+
+    /// Verify that a database is not considered empty if any of the stores restored from a backup
+    /// contains data, not only the node ID and circuits.
+    #[test]
+    fn test_check_database_is_empty() {
+        let store_factory = create_store_factory();
+        check_database_is_empty(&store_factory).expect("New database is not empty");
+
+        store_factory
+            .get_registry_store()
+            .add_node(
+                Node::builder("acme-node-000")
+                    .with_endpoint("tcps://splinterd-node-acme:8044")
+                    .with_display_name("Acme")
+                    .with_key("abcd")
+                    .build()
+                    .expect("Unable to build registry node"),
+            )
+            .expect("Unable to add registry node");
+
+        assert!(check_database_is_empty(&store_factory).is_err());
+    }
+
+    /// Verify that a backup with an unknown version is rejected.
+    #[test]
+    fn test_backup_unsupported_version() {
+        assert!(Backup::from_json(r#"{"version": 1000}"#).is_err());
+        assert!(Backup::from_json(r#"{"node_id": null}"#).is_err());
+    }
+
+    fn create_store_factory() -> SqliteStoreFactory {
+        SqliteStoreFactory::new(create_connection_pool())
+    }
+
+    fn create_connection_pool() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runs a backup or restore against the database in a single transaction
+
+use std::str::FromStr;
+
+use diesel::{
+    connection::{AnsiTransactionManager, TransactionManager},
+    r2d2::{Builder, ConnectionManager, Pool},
+    Connection,
+};
+use splinter::store::StoreFactory;
+
+#[cfg(feature = "postgres")]
+use crate::action::database::stores::PostgresUpgradeStores;
+#[cfg(feature = "sqlite")]
+use crate::action::database::stores::SqliteUpgradeStores;
+use crate::action::database::{stores::UpgradeStores, ConnectionUri};
+use crate::error::CliError;
+
+/// The statement run at the start of a PostgreSQL transaction, so that every read in the
+/// transaction sees the same snapshot of the database
+#[cfg(feature = "postgres")]
+const POSTGRES_REPEATABLE_READ: &str = "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ";
+
+/// Runs `f` with stores for the given database that all operate within one transaction.
+///
+/// The transaction is committed if `f` succeeds and rolled back if it returns an error, so either
+/// all of the changes made by `f` are written to the database or none of them are.
+pub(super) fn in_transaction<T, F>(database: &str, f: F) -> Result<T, CliError>
+where
+    F: FnOnce(&dyn StoreFactory, &Option<Box<dyn UpgradeStores>>) -> Result<T, CliError>,
+{
+    match ConnectionUri::from_str(database)? {
+        #[cfg(feature = "postgres")]
+        ConnectionUri::Postgres(url) => {
+            let pool = single_connection_pool(
+                Pool::builder(),
+                ConnectionManager::<diesel::pg::PgConnection>::new(url),
+            )?;
+            let upgrade_stores: Option<Box<dyn UpgradeStores>> =
+                Some(Box::new(PostgresUpgradeStores(pool.clone())));
+            run_in_transaction(
+                &pool,
+                Some(POSTGRES_REPEATABLE_READ),
+                &splinter::store::postgres::PgStoreFactory::new(pool.clone()),
+                &upgrade_stores,
+                f,
+            )
+        }
+        #[cfg(feature = "sqlite")]
+        ConnectionUri::Sqlite(conn_str) => {
+            let pool = single_connection_pool(
+                Pool::builder().connection_customizer(Box::new(
+                    splinter::store::sqlite::ConnectionCustomizer::default(),
+                )),
+                ConnectionManager::<diesel::sqlite::SqliteConnection>::new(conn_str),
+            )?;
+            let upgrade_stores: Option<Box<dyn UpgradeStores>> =
+                Some(Box::new(SqliteUpgradeStores(pool.clone())));
+            run_in_transaction(
+                &pool,
+                None,
+                &splinter::store::sqlite::SqliteStoreFactory::new(pool.clone()),
+                &upgrade_stores,
+                f,
+            )
+        }
+    }
+}
+
+/// Runs `f` within a transaction on the only connection in `pool`.
+///
+/// The transaction is begun before the connection is returned to the pool, so every store built
+/// on the pool operates within it; the transactions of the stores themselves become savepoints.
+///
+/// # Arguments
+///
+/// * `pool` - A pool that holds a single connection, which the given stores are built on
+/// * `begin_sql` - A statement to run right after the transaction is begun, if any
+/// * `store_factory` - The store factory passed to `f`
+/// * `upgrade_stores` - The upgrade stores passed to `f`
+/// * `f` - The function to run within the transaction
+pub(super) fn run_in_transaction<C, T, F>(
+    pool: &Pool<ConnectionManager<C>>,
+    begin_sql: Option<&str>,
+    store_factory: &dyn StoreFactory,
+    upgrade_stores: &Option<Box<dyn UpgradeStores>>,
+    f: F,
+) -> Result<T, CliError>
+where
+    C: Connection<TransactionManager = AnsiTransactionManager> + Send + 'static,
+    F: FnOnce(&dyn StoreFactory, &Option<Box<dyn UpgradeStores>>) -> Result<T, CliError>,
+{
+    {
+        let conn = get_connection(pool)?;
+        conn.transaction_manager()
+            .begin_transaction(&*conn)
+            .map_err(|err| {
+                CliError::ActionError(format!("Unable to begin transaction: {}", err))
+            })?;
+        if let Some(sql) = begin_sql {
+            if let Err(err) = conn.batch_execute(sql) {
+                if let Err(rollback_err) = conn.transaction_manager().rollback_transaction(&*conn) {
+                    error!("Unable to roll back transaction: {}", rollback_err);
+                }
+                return Err(CliError::ActionError(format!(
+                    "Unable to begin transaction: {}",
+                    err
+                )));
+            }
+        }
+    }
+
+    let result = f(store_factory, upgrade_stores);
+
+    let conn = get_connection(pool)?;
+    match result {
+        Ok(value) => {
+            conn.transaction_manager()
+                .commit_transaction(&*conn)
+                .map_err(|err| {
+                    CliError::ActionError(format!("Unable to commit transaction: {}", err))
+                })?;
+            Ok(value)
+        }
+        Err(err) => {
+            if let Err(rollback_err) = conn.transaction_manager().rollback_transaction(&*conn) {
+                error!("Unable to roll back transaction: {}", rollback_err);
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Builds a pool that holds a single connection, which is kept open for as long as the pool
+/// exists so that a transaction begun on it is never lost.
+fn single_connection_pool<C>(
+    builder: Builder<ConnectionManager<C>>,
+    connection_manager: ConnectionManager<C>,
+) -> Result<Pool<ConnectionManager<C>>, CliError>
+where
+    C: Connection + Send + 'static,
+{
+    builder
+        .max_size(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .build(connection_manager)
+        .map_err(|err| CliError::ActionError(format!("Failed to build connection pool: {}", err)))
+}
+
+fn get_connection<C>(
+    pool: &Pool<ConnectionManager<C>>,
+) -> Result<diesel::r2d2::PooledConnection<ConnectionManager<C>>, CliError>
+where
+    C: Connection + Send + 'static,
+{
+    pool.get()
+        .map_err(|err| CliError::ActionError(format!("Unable to get database connection: {}", err)))
+}
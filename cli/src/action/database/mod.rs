@@ -17,6 +17,8 @@ mod postgres;
 #[cfg(feature = "sqlite")]
 mod sqlite;
 
+#[cfg(feature = "database-backup")]
+mod backup;
 mod state;
 mod stores;
 #[cfg(feature = "upgrade")]
@@ -28,6 +30,8 @@ use std::{env, fs};
 
 use clap::ArgMatches;
 
+#[cfg(feature = "database-backup")]
+pub use self::backup::{BackupAction, RestoreAction};
 #[cfg(not(feature = "sqlite"))]
 use self::postgres::get_default_database;
 #[cfg(feature = "sqlite")]
@@ -256,7 +256,7 @@ impl Action for StateMigrateAction {
 ///
 /// * PathBuf to state_dir if present in arg_matches, otherwise just the default from
 /// SplinterEnvironment
-fn get_state_dir(arg_matches: Option<&ArgMatches>) -> Result<PathBuf, CliError> {
+pub(super) fn get_state_dir(arg_matches: Option<&ArgMatches>) -> Result<PathBuf, CliError> {
     if let Some(arg_matches) = arg_matches {
         match arg_matches.value_of("state_dir") {
             Some(state_dir) => {
@@ -297,6 +297,30 @@ fn copy_state(
             CliError::ActionError(format!("Unable to get leaves for commit hash: {}", e))
         })?;
 
+    write_state(state_changes_iter, current_commit_hash, state_writer)
+}
+
+/// Write the given state entries to the state writer MerkleState
+///
+/// # Arguments
+///
+/// * `state_changes_iter` - The key/value entries that make up the state
+/// * `current_commit_hash` - The state root hash that the entries are expected to produce
+/// * `state_writer` - The MerkleState that the state should be written to
+///
+/// # Returns
+///
+/// * Ok if the state was sucessfully written and results in the correct state root hash,
+/// otherwise a CliError is returned
+pub(super) fn write_state<I, E>(
+    state_changes_iter: I,
+    current_commit_hash: String,
+    state_writer: &MerkleState,
+) -> Result<(), CliError>
+where
+    I: IntoIterator<Item = Result<(String, Vec<u8>), E>>,
+    E: std::fmt::Display,
+{
     let mut count = 0;
     let mut last_state_id = state_writer.get_state_root()?;
     let mut state_changes = vec![];
@@ -357,7 +381,7 @@ fn copy_state(
 /// *   pool
 /// * `lmdb_db_factory` - The factory to create LMDB databases
 /// * `create_tree` - Whether the tree should be created if it does not exist
-fn get_merkle_state(
+pub(super) fn get_merkle_state(
     database: &str,
     circuit_id: &str,
     service_id: &str,
@@ -458,7 +482,7 @@ fn get_merkle_state(
 /// * `upgrade_stores` - The UpgradeStores struct that should be used to get Postgres or Sqlite
 /// *   pool
 /// * `lmdb_db_factory` - The factory to check LMDB databases
-fn database_exists(
+pub(super) fn database_exists(
     database: &str,
     circuit_id: &str,
     service_id: &str,
@@ -92,7 +92,9 @@ pub fn new_upgrade_stores(
 }
 
 #[cfg(feature = "postgres")]
-struct PostgresUpgradeStores(Pool<ConnectionManager<diesel::pg::PgConnection>>);
+pub(super) struct PostgresUpgradeStores(
+    pub(super) Pool<ConnectionManager<diesel::pg::PgConnection>>,
+);
 
 #[cfg(feature = "postgres")]
 impl UpgradeStores for PostgresUpgradeStores {
@@ -135,7 +137,7 @@ impl UpgradeStores for PostgresUpgradeStores {
 }
 
 #[cfg(feature = "sqlite")]
-struct SqliteUpgradeStores(Pool<ConnectionManager<diesel::SqliteConnection>>);
+pub(super) struct SqliteUpgradeStores(pub(super) Pool<ConnectionManager<diesel::SqliteConnection>>);
 
 #[cfg(feature = "sqlite")]
 impl UpgradeStores for SqliteUpgradeStores {
@@ -13,13 +13,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Write as _;
+
 use clap::ArgMatches;
 
 use crate::error::CliError;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 
 use super::{
-    api::{SplinterRestClient, SplinterRestClientBuilder},
+    api::{MaintenanceMode, SplinterRestClient, SplinterRestClientBuilder},
+    time::{format_timestamp, parse_duration},
     Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
 };
 
@@ -27,12 +30,8 @@ pub struct StatusAction;
 
 impl Action for StatusAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        let status = if new_client(arg_matches)?.is_maintenance_mode_enabled()? {
-            "enabled"
-        } else {
-            "disabled"
-        };
-        println!("Maintenance mode is currently {}", status);
+        let maintenance_mode = new_client(arg_matches)?.get_maintenance_mode()?;
+        println!("{}", format_status(&maintenance_mode));
         Ok(())
     }
 }
@@ -41,7 +40,13 @@ pub struct EnableAction;
 
 impl Action for EnableAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        new_client(arg_matches)?.set_maintenance_mode(true)?;
+        let reason = arg_matches.and_then(|args| args.value_of("reason"));
+        let expires_in = arg_matches
+            .and_then(|args| args.value_of("expires_in"))
+            .map(parse_duration)
+            .transpose()?;
+
+        new_client(arg_matches)?.set_maintenance_mode(true, reason, expires_in)?;
         println!("Maintenance mode has been enabled");
         Ok(())
     }
@@ -51,7 +56,9 @@ pub struct DisableAction;
 
 impl Action for DisableAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        new_client(arg_matches)?.set_maintenance_mode(false)?;
+        let reason = arg_matches.and_then(|args| args.value_of("reason"));
+
+        new_client(arg_matches)?.set_maintenance_mode(false, reason, None)?;
         println!("Maintenance mode has been disabled");
         Ok(())
     }
@@ -71,3 +78,40 @@ fn new_client(arg_matches: Option<&ArgMatches<'_>>) -> Result<SplinterRestClient
         .with_auth(create_cylinder_jwt_auth(signer)?)
         .build()
 }
+
+fn format_status(maintenance_mode: &MaintenanceMode) -> String {
+    let mut status = format!(
+        "Maintenance mode is currently {}",
+        if maintenance_mode.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+
+    // Writing to a string cannot fail
+    if let Some(set_by) = &maintenance_mode.set_by {
+        let _ = write!(
+            status,
+            "\n    Set by: {} {}",
+            set_by.identity_type, set_by.identity
+        );
+    }
+    if let Some(set_at) = maintenance_mode.set_at {
+        let _ = write!(status, "\n    Set at: {}", format_timestamp(set_at));
+    }
+    if let Some(reason) = &maintenance_mode.reason {
+        let _ = write!(status, "\n    Reason: {}", reason);
+    }
+    if let Some(expires_at) = maintenance_mode.expires_at {
+        // Maintenance mode is only reported as disabled with an expiration time once it expired
+        let label = if maintenance_mode.enabled {
+            "Expires at"
+        } else {
+            "Expired at"
+        };
+        let _ = write!(status, "\n    {}: {}", label, format_timestamp(expires_at));
+    }
+
+    status
+}
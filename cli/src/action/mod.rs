@@ -14,6 +14,8 @@
 // limitations under the License.
 
 mod api;
+#[cfg(feature = "rest-api-audit")]
+pub mod audit;
 pub mod certs;
 pub mod circuit;
 #[cfg(feature = "database")]
@@ -25,6 +27,14 @@ pub mod permissions;
 #[cfg(feature = "authorization-handler-rbac")]
 pub mod rbac;
 pub mod registry;
+#[cfg(any(
+    feature = "authorization-handler-maintenance",
+    feature = "rest-api-audit",
+    feature = "rest-api-tokens"
+))]
+mod time;
+#[cfg(feature = "rest-api-tokens")]
+pub mod token;
 #[cfg(feature = "user")]
 pub mod user;
 
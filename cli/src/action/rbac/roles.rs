@@ -19,7 +19,7 @@ use std::collections::BTreeSet;
 use clap::ArgMatches;
 
 use crate::action::{
-    api::{RoleBuilder, RoleUpdateBuilder, SplinterRestClient},
+    api::{RoleBuilder, RoleScope, RoleUpdateBuilder, SplinterRestClient},
     print_table, Action,
 };
 use crate::error::CliError;
@@ -114,6 +114,7 @@ impl Action for ShowRoleAction {
 /// * role_id: the specified role ID
 /// * display_name: the role's display name
 /// * permission: a permission granted by the resulting role; repeated
+/// * scope: a resource scope the role's permissions are limited to; repeated
 /// * dry_run: validate the inputs but do not submit the role
 pub struct CreateRoleAction;
 
@@ -135,10 +136,13 @@ impl Action for CreateRoleAction {
             .map(|s| s.to_owned())
             .collect();
 
+        let scopes = parse_scopes(arg_matches, "scope")?;
+
         let role = RoleBuilder::default()
             .with_role_id(role_id.into())
             .with_display_name(display_name.into())
             .with_permissions(permissions)
+            .with_scopes(scopes)
             .build()?;
 
         let client = new_client(&arg_matches)?;
@@ -164,7 +168,10 @@ impl Action for CreateRoleAction {
 /// * add_permission: a permission to add to the role; repeated
 /// * rm_permission: a permission to remove from the role; repeated
 /// * rm_all: remove all the currently granted permissions from the role
-/// * force: applies the changes, even if a permission is added and removed
+/// * add_scope: a resource scope to add to the role; repeated
+/// * rm_scope: a resource scope to remove from the role; repeated
+/// * rm_all_scopes: remove all the resource scopes from the role
+/// * force: applies the changes, even if a permission or scope is added and removed
 /// * dry_run: validate the inputs but do not submit the changes
 pub struct UpdateRoleAction;
 
@@ -197,6 +204,16 @@ impl Action for UpdateRoleAction {
             )
         };
 
+        let scopes_to_add = parse_scopes(arg_matches, "add_scope")?;
+        let scope_removal = if arg_matches
+            .map(|args| args.is_present("rm_all_scopes"))
+            .unwrap_or(false)
+        {
+            ScopeRemoval::RemoveAll
+        } else {
+            ScopeRemoval::Remove(parse_scopes(arg_matches, "rm_scope")?)
+        };
+
         let force = arg_matches
             .map(|args| args.is_present("force"))
             .unwrap_or(false);
@@ -207,6 +224,8 @@ impl Action for UpdateRoleAction {
             display_name,
             permissions_to_add,
             permission_removal,
+            scopes_to_add,
+            scope_removal,
             force,
             is_dry_run(&arg_matches),
         )
@@ -218,12 +237,20 @@ enum PermissionRemoval {
     Remove(Vec<String>),
 }
 
+enum ScopeRemoval {
+    RemoveAll,
+    Remove(Vec<RoleScope>),
+}
+
+#[allow(clippy::too_many_arguments)]
 fn update_role(
     client: SplinterRestClient,
     role_id: &str,
     display_name: Option<String>,
     permissions_to_add: Vec<String>,
     permission_removal: PermissionRemoval,
+    scopes_to_add: Vec<RoleScope>,
+    scope_removal: ScopeRemoval,
     force: bool,
     is_dry_run: bool,
 ) -> Result<(), CliError> {
@@ -276,10 +303,76 @@ fn update_role(
         }
     };
 
+    let scopes = match scope_removal {
+        ScopeRemoval::RemoveAll => {
+            if !role.scopes.is_empty() {
+                println!(
+                    "Removing scopes {}",
+                    role.scopes
+                        .iter()
+                        .map(|scope| scope.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            Some(scopes_to_add)
+        }
+        ScopeRemoval::Remove(scopes_to_rm)
+            if scopes_to_add.is_empty() && scopes_to_rm.is_empty() =>
+        {
+            None
+        }
+        ScopeRemoval::Remove(mut scopes_to_rm) => {
+            if !force
+                && scopes_to_add
+                    .iter()
+                    .any(|scope| scopes_to_rm.contains(scope))
+            {
+                return Err(CliError::ActionError(
+                    "Cannot add and remove the same scopes".into(),
+                ));
+            }
+
+            let mut scopes = role
+                .scopes
+                .into_iter()
+                .filter(
+                    |scope| match scopes_to_rm.iter().position(|rm| rm == scope) {
+                        Some(idx) => {
+                            scopes_to_rm.remove(idx);
+                            false
+                        }
+                        None => true,
+                    },
+                )
+                .collect::<Vec<_>>();
+
+            if !force && !scopes_to_rm.is_empty() {
+                return Err(CliError::ActionError(format!(
+                    "Cannot remove scopes that do not belong to the role: {}",
+                    scopes_to_rm
+                        .iter()
+                        .map(|scope| scope.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+
+            for scope in scopes_to_add {
+                if !scopes.contains(&scope) {
+                    scopes.push(scope);
+                }
+            }
+
+            Some(scopes)
+        }
+    };
+
     let updated_role = RoleUpdateBuilder::default()
         .with_role_id(role_id.into())
         .with_display_name(display_name)
         .with_permissions(Some(permissions))
+        .with_scopes(scopes)
         .build()?;
 
     if !is_dry_run {
@@ -310,6 +403,16 @@ impl Action for DeleteRoleAction {
     }
 }
 
+fn parse_scopes<'a>(
+    arg_matches: Option<&ArgMatches<'a>>,
+    name: &str,
+) -> Result<Vec<RoleScope>, CliError> {
+    arg_matches
+        .and_then(|args| args.values_of(name))
+        .map(|vals| vals.map(|s| s.parse()).collect())
+        .unwrap_or_else(|| Ok(Vec::new()))
+}
+
 fn is_dry_run<'a>(arg_matches: &Option<&ArgMatches<'a>>) -> bool {
     arg_matches
         .map(|args| args.is_present("dry_run"))
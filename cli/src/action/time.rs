@@ -0,0 +1,103 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for reading and displaying times on the command line
+
+use crate::error::CliError;
+
+/// Parses a duration such as "90", "30s", "15m", "2h" or "1d" into a number of seconds
+pub fn parse_duration(duration: &str) -> Result<u64, CliError> {
+    let (value, multiplier) = match duration.char_indices().last() {
+        Some((i, 's')) => (&duration[..i], 1),
+        Some((i, 'm')) => (&duration[..i], 60),
+        Some((i, 'h')) => (&duration[..i], 60 * 60),
+        Some((i, 'd')) => (&duration[..i], 24 * 60 * 60),
+        _ => (duration, 1),
+    };
+
+    value
+        .parse::<u64>()
+        .ok()
+        .and_then(|value| value.checked_mul(multiplier))
+        .filter(|seconds| *seconds > 0)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Invalid duration '{}': expected a positive number of seconds, optionally \
+                 followed by s, m, h or d",
+                duration
+            ))
+        })
+}
+
+/// Formats a number of seconds since the Unix epoch as a UTC date and time
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let seconds = timestamp % 86400;
+
+    // Convert the number of days since the epoch to a civil date; see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that durations are parsed into seconds, and that invalid or zero durations are
+    /// rejected.
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90").expect("Unable to parse"), 90);
+        assert_eq!(parse_duration("30s").expect("Unable to parse"), 30);
+        assert_eq!(parse_duration("15m").expect("Unable to parse"), 900);
+        assert_eq!(parse_duration("2h").expect("Unable to parse"), 7200);
+        assert_eq!(parse_duration("1d").expect("Unable to parse"), 86400);
+
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("-1h").is_err());
+        assert!(parse_duration("1w").is_err());
+    }
+
+    /// Verify that timestamps are formatted as UTC dates and times.
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_timestamp(1_650_000_000), "2022-04-15 05:20:00 UTC");
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions to support the API token subcommands.
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{ApiToken, SplinterRestClient, SplinterRestClientBuilder},
+    print_table,
+    time::{format_timestamp, parse_duration},
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// The action responsible for creating an API token.
+///
+/// The specific args for this action:
+///
+/// * name: the name of the token
+/// * expires_in: the duration after which the token expires; the token never expires if not set
+/// * role: the roles that are granted to the token
+pub struct CreateAction;
+
+impl Action for CreateAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let name = arg_matches
+            .and_then(|args| args.value_of("name"))
+            .ok_or_else(|| CliError::ActionError("A token name must be specified".into()))?;
+        let expires_in = arg_matches
+            .and_then(|args| args.value_of("expires_in"))
+            .map(parse_duration)
+            .transpose()?;
+        let roles = arg_matches
+            .and_then(|args| args.values_of("role"))
+            .map(|roles| roles.map(String::from).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let new_token = new_client(arg_matches)?.create_api_token(name, expires_in, &roles)?;
+
+        println!("Token ID: {}", new_token.details.token_id);
+        println!("Token: {}", new_token.token);
+        println!("The token cannot be displayed again; store it somewhere safe.");
+
+        Ok(())
+    }
+}
+
+/// The action responsible for listing API tokens.
+///
+/// The specific args for this action:
+///
+/// * format: specifies the output format; one of "human" or "csv"
+pub struct ListAction;
+
+impl Action for ListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+
+        let tokens = new_client(arg_matches)?.list_api_tokens()?;
+
+        let header = vec![
+            "ID".to_string(),
+            "NAME".to_string(),
+            "OWNER".to_string(),
+            "CREATED".to_string(),
+            "EXPIRES".to_string(),
+            "ROLES".to_string(),
+        ];
+
+        if format == "csv" {
+            println!("{}", header.join(","));
+            for token in tokens {
+                println!("{}", format_row(token).join(","));
+            }
+        } else {
+            let mut rows = vec![header];
+            rows.extend(tokens.into_iter().map(format_row));
+            print_table(rows);
+        }
+
+        Ok(())
+    }
+}
+
+/// The action responsible for revoking an API token.
+///
+/// The specific args for this action:
+///
+/// * token_id: the ID of the token to revoke
+pub struct RevokeAction;
+
+impl Action for RevokeAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let token_id = arg_matches
+            .and_then(|args| args.value_of("token_id"))
+            .ok_or_else(|| CliError::ActionError("A token ID must be specified".into()))?;
+
+        new_client(arg_matches)?.revoke_api_token(token_id)
+    }
+}
+
+fn new_client(arg_matches: Option<&ArgMatches<'_>>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
+
+fn format_row(token: ApiToken) -> Vec<String> {
+    vec![
+        token.token_id,
+        token.name,
+        format!("{} {}", token.owner.identity_type, token.owner.identity),
+        format_timestamp(token.created_at),
+        token
+            .expires_at
+            .map(format_timestamp)
+            .unwrap_or_else(|| "never".to_string()),
+        if token.roles.is_empty() {
+            "-".to_string()
+        } else {
+            token.roles.join(" ")
+        },
+    ]
+}
@@ -264,6 +264,14 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             .help("Authorization type for the circuit"),
     );
 
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("durability")
+            .long("durability")
+            .possible_values(&["none", "guaranteed-delivery"])
+            .takes_value(true)
+            .help("Durability type for the circuit"),
+    );
+
     let propose_circuit = propose_circuit.arg(
         Arg::with_name("node_public_key")
             .long("node-public-key")
@@ -275,6 +283,17 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("node_relay")
+            .long("node-relay")
+            .takes_value(true)
+            .multiple(true)
+            .help(
+                "Node that relays messages between a node and the rest of the circuit \
+                 (<node_id>::<relay_node_id>)",
+            ),
+    );
+
     #[cfg(feature = "circuit-template")]
     let propose_circuit = propose_circuit
         .arg(
@@ -364,6 +383,31 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         .possible_values(&["active", "disbanded", "abandoned"])
                         .takes_value(true),
                 )
+                .arg(
+                    Arg::with_name("service_type")
+                        .long("service-type")
+                        .help("Filter circuits by the type of one of their services")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("service_arg")
+                        .long("service-arg")
+                        .value_name("key=value")
+                        .help("Filter circuits by an argument of one of their services")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("display_name")
+                        .long("display-name")
+                        .help("Filter circuits by a substring of their display name")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("circuit_version")
+                        .long("circuit-version")
+                        .help("Filter circuits by circuit version")
+                        .takes_value(true),
+                )
                 .arg(
                     Arg::with_name("format")
                         .short("F")
@@ -460,6 +504,49 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         )
                         .takes_value(true),
                 )
+                .arg(
+                    Arg::with_name("service_type")
+                        .long("service-type")
+                        .help("Filter circuit proposals by the type of one of their services")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("service_arg")
+                        .long("service-arg")
+                        .value_name("key=value")
+                        .help("Filter circuit proposals by an argument of one of their services")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("display_name")
+                        .long("display-name")
+                        .help("Filter circuit proposals by a substring of their display name")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("circuit_version")
+                        .long("circuit-version")
+                        .help("Filter circuit proposals by circuit version")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("requester")
+                        .long("requester")
+                        .help("Show proposals requested by the node with the given ID")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("voted_by")
+                        .long("voted-by")
+                        .help("Show proposals the node with the given ID has voted on")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("awaiting_vote_from")
+                        .long("awaiting-vote-from")
+                        .help("Show proposals waiting for a vote from the node with the given ID")
+                        .takes_value(true),
+                )
                 .arg(
                     Arg::with_name("format")
                         .short("F")
@@ -514,6 +601,231 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("update-roster")
+            .about("Propose to update the services of an existing circuit")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("add_service")
+                    .long("add-service")
+                    .takes_value(true)
+                    .multiple(true)
+                    .required_unless("remove_service")
+                    .help(
+                        "Service to add to the circuit \
+                         (<service_id>::<allowed_nodes>)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("service_type")
+                    .long("service-type")
+                    .takes_value(true)
+                    .multiple(true)
+                    .requires("add_service")
+                    .help(
+                        "Service type of an added service \
+                         (<service_id>::<service_type>)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("service_argument")
+                    .long("service-arg")
+                    .takes_value(true)
+                    .multiple(true)
+                    .requires("add_service")
+                    .help(
+                        "Pass arguments to an added service \
+                         (<service_id>::<key>=<value>)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("remove_service")
+                    .long("remove-service")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help("ID of a service to remove from the circuit"),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to be updated"),
+            ),
+    );
+
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("add-node")
+            .about("Propose to add a node to an existing circuit")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("node")
+                    .long("node")
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Node to add to the circuit \
+                         (<node_id>::<endpoint1>,<endpoint2>)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("node_public_key")
+                    .long("node-public-key")
+                    .takes_value(true)
+                    .help(
+                        "Public key of the node to add, used in challenge authorization \
+                         (hex-encoded)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("service")
+                    .long("service")
+                    .takes_value(true)
+                    .multiple(true)
+                    .help(
+                        "Service to run on the node being added \
+                         (<service_id>::<allowed_nodes>)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("service_type")
+                    .long("service-type")
+                    .takes_value(true)
+                    .multiple(true)
+                    .requires("service")
+                    .help(
+                        "Service type of an added service \
+                         (<service_id>::<service_type>)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("service_argument")
+                    .long("service-arg")
+                    .takes_value(true)
+                    .multiple(true)
+                    .requires("service")
+                    .help(
+                        "Pass arguments to an added service \
+                         (<service_id>::<key>=<value>)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to add the node to"),
+            ),
+    );
+
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("remove-node")
+            .about("Propose to remove a node from an existing circuit")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("node")
+                    .long("node")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the node to remove from the circuit"),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to remove the node from"),
+            ),
+    );
+
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("update-metadata")
+            .about("Propose to update the application metadata of an existing circuit")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("metadata")
+                    .long("metadata")
+                    .value_name("application_metadata")
+                    .takes_value(true)
+                    .multiple(true)
+                    .required(true)
+                    .help("New application metadata of the circuit"),
+            )
+            .arg(
+                Arg::with_name("metadata_encoding")
+                    .long("metadata-encoding")
+                    .takes_value(true)
+                    .possible_values(&["json", "string"])
+                    .help(
+                        "Set encoding of application metadata \
+                           (default: string)",
+                    ),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to update"),
+            ),
+    );
+
     let circuit_command = circuit_command.subcommand(
         SubCommand::with_name("purge")
             .about("Purge an existing inactive circuit")
@@ -902,21 +1214,79 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     #[cfg(feature = "database")]
     {
-        app = app.subcommand(
-            SubCommand::with_name("database")
-                .about("Database commands")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("migrate")
-                        .about("Runs database migrations Splinter")
-                        .arg(
-                            Arg::with_name("connect")
-                                .short("C")
-                                .takes_value(true)
-                                .help("Database connection URI"),
-                        ),
-                ),
-        );
+        let database_command = SubCommand::with_name("database")
+            .about("Database commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("migrate")
+                    .about("Runs database migrations Splinter")
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    ),
+            );
+
+        #[cfg(feature = "database-backup")]
+        let database_command = database_command
+            .subcommand(
+                SubCommand::with_name("backup")
+                    .about("Writes the contents of the Splinter database to a backup file")
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .long("connect")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    )
+                    .arg(
+                        Arg::with_name("output")
+                            .short("o")
+                            .long("output")
+                            .takes_value(true)
+                            .required(true)
+                            .help("Path of the backup file to write"),
+                    )
+                    .arg(
+                        Arg::with_name("state_dir")
+                            .long("state-dir")
+                            .long_help(
+                                "The location of the state directory for scabbard LMDB files. \
+                                Defaults to /var/lib/splinter. This location can also be \
+                                changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                                environment variables",
+                            )
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("force")
+                            .short("f")
+                            .long("force")
+                            .help("Overwrite the backup file if it already exists"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("restore")
+                    .about("Restores a backup file into an empty Splinter database")
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .long("connect")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    )
+                    .arg(
+                        Arg::with_name("input")
+                            .short("i")
+                            .long("input")
+                            .takes_value(true)
+                            .required(true)
+                            .help("Path of the backup file to restore"),
+                    ),
+            );
+
+        app = app.subcommand(database_command);
 
         app = app.subcommand(
             SubCommand::with_name("state")
@@ -995,6 +1365,65 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         );
     }
 
+    #[cfg(feature = "rest-api-audit")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("audit")
+                .about("REST API audit log commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("Lists the entries of a Splinter node's REST API audit log")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("since")
+                                .value_name("time")
+                                .long("since")
+                                .takes_value(true)
+                                .help(
+                                    "Only list entries recorded at or after the given time, \
+                                    either in seconds since the Unix epoch or a duration ago, \
+                                    such as 30m, 2h or 1d",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("until")
+                                .value_name("time")
+                                .long("until")
+                                .takes_value(true)
+                                .help(
+                                    "Only list entries recorded before the given time, either \
+                                    in seconds since the Unix epoch or a duration ago, such as \
+                                    30m, 2h or 1d",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "csv"])
+                                .default_value("human")
+                                .takes_value(true),
+                        ),
+                ),
+        )
+    }
+
     #[cfg(feature = "authorization-handler-maintenance")]
     {
         app = app.subcommand(
@@ -1037,6 +1466,22 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .long("key")
                                 .takes_value(true)
                                 .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("reason")
+                                .long("reason")
+                                .takes_value(true)
+                                .help("Reason for enabling maintenance mode"),
+                        )
+                        .arg(
+                            Arg::with_name("expires_in")
+                                .value_name("duration")
+                                .long("expires-in")
+                                .takes_value(true)
+                                .help(
+                                    "Disable maintenance mode automatically after the given \
+                                    duration, such as 90s, 30m, 2h or 1d",
+                                ),
                         ),
                 )
                 .subcommand(
@@ -1056,6 +1501,12 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .long("key")
                                 .takes_value(true)
                                 .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("reason")
+                                .long("reason")
+                                .takes_value(true)
+                                .help("Reason for disabling maintenance mode"),
                         ),
                 ),
         )
@@ -1168,6 +1619,18 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .required(true)
                                 .help("A permission allowed by the role"),
                         )
+                        .arg(
+                            Arg::with_name("scope")
+                                .value_name("scope")
+                                .long("scope")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help(
+                                    "A resource scope that the role's permissions are limited \
+                                    to, such as circuit_id=abcde-*,service_id=a000",
+                                ),
+                        )
                         .arg(
                             Arg::with_name("role_id")
                                 .required(true)
@@ -1236,6 +1699,34 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                     role",
                                 ),
                         )
+                        .arg(
+                            Arg::with_name("add_scope")
+                                .value_name("scope")
+                                .long("add-scope")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help("A resource scope to be added to the role"),
+                        )
+                        .arg(
+                            Arg::with_name("rm_scope")
+                                .value_name("scope")
+                                .long("rm-scope")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .conflicts_with("rm_all_scopes")
+                                .help("A resource scope to be removed from the role"),
+                        )
+                        .arg(
+                            Arg::with_name("rm_all_scopes")
+                                .long("rm-all-scopes")
+                                .conflicts_with("rm_scope")
+                                .help(
+                                    "Remove all of the resource scopes currently associated with \
+                                    the role",
+                                ),
+                        )
                         .arg(
                             Arg::with_name("force")
                                 .short("f")
@@ -1575,6 +2066,114 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    #[cfg(feature = "rest-api-tokens")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("token")
+                .about("REST API token commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("create")
+                        .about("Creates a new API token for a Splinter node")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("name")
+                                .required(true)
+                                .takes_value(true)
+                                .value_name("NAME")
+                                .help("Name of the token"),
+                        )
+                        .arg(
+                            Arg::with_name("expires_in")
+                                .value_name("duration")
+                                .long("expires-in")
+                                .takes_value(true)
+                                .help(
+                                    "Duration after which the token expires, such as 30m, 2h \
+                                    or 90d; the token never expires if not set",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("role")
+                                .value_name("role")
+                                .long("role")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help("A role to be granted to the token"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("Lists the API tokens issued by a Splinter node")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "csv"])
+                                .default_value("human")
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("revoke")
+                        .about("Revokes an API token issued by a Splinter node")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("token_id")
+                                .required(true)
+                                .takes_value(true)
+                                .value_name("TOKEN ID")
+                                .help("ID of the token to revoke"),
+                        ),
+                ),
+        )
+    }
+
     #[cfg(feature = "user")]
     {
         app = app.subcommand(
@@ -1658,6 +2257,10 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         .with_command("show", circuit::CircuitShowAction)
         .with_command("proposals", circuit::CircuitProposalsAction)
         .with_command("disband", circuit::CircuitDisbandAction)
+        .with_command("update-roster", circuit::CircuitUpdateRosterAction)
+        .with_command("add-node", circuit::CircuitAddNodeAction)
+        .with_command("remove-node", circuit::CircuitRemoveNodeAction)
+        .with_command("update-metadata", circuit::CircuitUpdateMetadataAction)
         .with_command("abandon", circuit::CircuitAbandonAction)
         .with_command("purge", circuit::CircuitPurgeAction);
 
@@ -1686,10 +2289,15 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
     #[cfg(feature = "database")]
     {
         use action::database;
-        subcommands = subcommands.with_command(
-            "database",
-            SubcommandActions::new().with_command("migrate", database::MigrateAction),
-        );
+        let database_command =
+            SubcommandActions::new().with_command("migrate", database::MigrateAction);
+
+        #[cfg(feature = "database-backup")]
+        let database_command = database_command
+            .with_command("backup", database::BackupAction)
+            .with_command("restore", database::RestoreAction);
+
+        subcommands = subcommands.with_command("database", database_command);
 
         subcommands = subcommands.with_command(
             "state",
@@ -1703,6 +2311,15 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         subcommands = subcommands.with_command("upgrade", database::UpgradeAction);
     }
 
+    #[cfg(feature = "rest-api-audit")]
+    {
+        use action::audit;
+        subcommands = subcommands.with_command(
+            "audit",
+            SubcommandActions::new().with_command("list", audit::ListAction),
+        )
+    }
+
     #[cfg(feature = "authorization-handler-maintenance")]
     {
         use action::maintenance;
@@ -1740,6 +2357,18 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     subcommands = subcommands.with_command("permissions", permissions::ListAction);
 
+    #[cfg(feature = "rest-api-tokens")]
+    {
+        use action::token;
+        subcommands = subcommands.with_command(
+            "token",
+            SubcommandActions::new()
+                .with_command("create", token::CreateAction)
+                .with_command("list", token::ListAction)
+                .with_command("revoke", token::RevokeAction),
+        )
+    }
+
     #[cfg(feature = "user")]
     {
         use action::user;
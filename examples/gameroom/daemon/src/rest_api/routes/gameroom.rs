@@ -133,6 +133,7 @@ pub async fn propose_gameroom(
             node_id: node.identity.to_string(),
             endpoints: node.endpoints.to_vec(),
             public_key: None,
+            relay_node_id: None,
         })
         .collect::<Vec<SplinterNode>>();
 
@@ -140,6 +141,7 @@ pub async fn propose_gameroom(
         node_id: node_info.identity.to_string(),
         endpoints: node_info.endpoints.to_vec(),
         public_key: None,
+        relay_node_id: None,
     });
 
     let node_ids = nodes
@@ -63,6 +63,8 @@ pub enum EventType {
     ProposalRejected { requester: PublicKey },
     CircuitReady,
     CircuitDisbanded,
+    CircuitApplicationMetadataUpdated,
+    ProposalExpired,
 }
 
 impl AdminServiceEvent {
@@ -90,6 +92,8 @@ pub enum EventQuery<'a> {
     ProposalRejected { circuit_id: &'a str, key: PublicKey },
     CircuitReady { circuit_id: &'a str },
     CircuitDisbanded { circuit_id: &'a str },
+    CircuitApplicationMetadataUpdated { circuit_id: &'a str },
+    ProposalExpired { circuit_id: &'a str },
 }
 
 impl<'a> EventQuery<'a> {
@@ -126,6 +130,14 @@ impl<'a> EventQuery<'a> {
                 event.event_type() == &EventType::CircuitDisbanded
                     && &event.proposal().circuit_id == circuit_id
             }
+            EventQuery::CircuitApplicationMetadataUpdated { circuit_id } => {
+                event.event_type() == &EventType::CircuitApplicationMetadataUpdated
+                    && &event.proposal().circuit_id == circuit_id
+            }
+            EventQuery::ProposalExpired { circuit_id } => {
+                event.event_type() == &EventType::ProposalExpired
+                    && &event.proposal().circuit_id == circuit_id
+            }
         }
     }
 }
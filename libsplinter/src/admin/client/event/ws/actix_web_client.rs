@@ -334,6 +334,10 @@ impl ParseBytes<AdminServiceEvent> for AdminServiceEvent {
             ),
             CircuitReady(proposal) => (proposal, EventType::CircuitReady),
             CircuitDisbanded(proposal) => (proposal, EventType::CircuitDisbanded),
+            CircuitApplicationMetadataUpdated(proposal) => {
+                (proposal, EventType::CircuitApplicationMetadataUpdated)
+            }
+            ProposalExpired(proposal) => (proposal, EventType::ProposalExpired),
         };
 
         Ok(AdminServiceEvent {
@@ -361,6 +365,7 @@ impl From<messages::CircuitProposal> for ProposalSlice {
             AddNode => "AddNode",
             RemoveNode => "RemoveNode",
             Disband => "Disband",
+            UpdateApplicationMetadata => "UpdateApplicationMetadata",
         }
         .to_owned();
 
@@ -393,6 +398,11 @@ impl From<messages::CreateCircuit> for ProposalCircuitSlice {
             management_type: create_circuit.circuit_management_type,
             comments: create_circuit.comments,
             display_name: create_circuit.display_name,
+            application_metadata: if create_circuit.application_metadata.is_empty() {
+                None
+            } else {
+                Some(hex::to_hex(&create_circuit.application_metadata))
+            },
         }
     }
 }
@@ -56,6 +56,8 @@ pub struct CircuitSlice {
     pub roster: Vec<CircuitServiceSlice>,
     pub management_type: String,
     pub display_name: Option<String>,
+    #[serde(default)]
+    pub application_metadata: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -87,6 +89,8 @@ pub struct ProposalCircuitSlice {
     pub management_type: String,
     pub comments: Option<String>,
     pub display_name: Option<String>,
+    #[serde(default)]
+    pub application_metadata: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
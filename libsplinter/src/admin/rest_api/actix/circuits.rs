@@ -15,6 +15,8 @@
 //! This module provides the `GET /admin/circuits` endpoint for listing the definitions of circuits
 //! in Splinter's state.
 
+#[cfg(feature = "authorization")]
+use actix_web::HttpMessage;
 use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, Future};
 use std::collections::HashMap;
@@ -22,9 +24,11 @@ use std::collections::HashMap;
 #[cfg(feature = "authorization")]
 use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
 use crate::admin::store::{AdminServiceStore, CircuitPredicate, CircuitStatus};
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::authorization::{RequestedResource, ResourceAuthorization};
 use crate::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
-    paging::{get_response_paging_info, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    paging::{get_response_cursor_paging_info, get_response_paging_info, Cursor, DEFAULT_LIMIT},
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
@@ -39,7 +43,7 @@ pub fn make_list_circuits_resource(store: Box<dyn AdminServiceStore>) -> Resourc
     );
     #[cfg(feature = "authorization")]
     {
-        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |r, _| {
+        resource.add_scoped_method(Method::Get, CIRCUIT_READ_PERMISSION, move |r, _| {
             list_circuits(r, web::Data::new(store.clone()))
         })
     }
@@ -68,7 +72,7 @@ fn list_circuits(
 
     let offset = match query.get("offset") {
         Some(value) => match value.parse::<usize>() {
-            Ok(val) => val,
+            Ok(val) => Some(val),
             Err(err) => {
                 return Box::new(
                     HttpResponse::BadRequest()
@@ -80,7 +84,24 @@ fn list_circuits(
                 )
             }
         },
-        None => DEFAULT_OFFSET,
+        None => None,
+    };
+
+    let cursor = match query.get("cursor") {
+        Some(value) => match Cursor::decode(value) {
+            Ok(cursor) => Some(cursor),
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid cursor value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => None,
     };
 
     let limit = match query.get("limit") {
@@ -101,21 +122,71 @@ fn list_circuits(
     };
 
     let mut new_queries = vec![];
-    let member_filter = match query.get("filter") {
-        Some(value) => {
-            new_queries.push(format!("filter={}", value));
-            Some(value.to_string())
+    let mut filters = vec![];
+    if let Some(value) = query.get("filter") {
+        new_queries.push(format!("filter={}", value));
+        filters.push(CircuitPredicate::MembersInclude(vec![value.to_string()]));
+    }
+
+    if let Some(value) = query.get("status") {
+        new_queries.push(format!("status={}", value));
+        filters.push(CircuitPredicate::CircuitStatus(CircuitStatus::from(
+            value.to_string(),
+        )));
+    }
+
+    if let Some(value) = query.get("service_type") {
+        new_queries.push(format!("service_type={}", value));
+        filters.push(CircuitPredicate::ServiceTypeEq(value.to_string()));
+    }
+
+    if let Some(value) = query.get("service_arg") {
+        let mut parts = value.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(key), Some(arg_value)) if !key.is_empty() => {
+                new_queries.push(format!("service_arg={}", value));
+                filters.push(CircuitPredicate::ServiceArgumentEq(
+                    key.to_string(),
+                    arg_value.to_string(),
+                ));
+            }
+            _ => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid service_arg value passed: {}. Expected <key>=<value>",
+                            value
+                        )))
+                        .into_future(),
+                )
+            }
         }
-        None => None,
-    };
+    }
+
+    if let Some(value) = query.get("display_name") {
+        new_queries.push(format!("display_name={}", value));
+        filters.push(CircuitPredicate::DisplayNameContains(value.to_string()));
+    }
 
-    let status_filter = match query.get("status") {
-        Some(value) => {
-            new_queries.push(format!("status={}", value));
-            Some(value.to_string())
+    if let Some(value) = query.get("circuit_version") {
+        match value.parse::<i32>() {
+            Ok(version) => {
+                new_queries.push(format!("circuit_version={}", value));
+                filters.push(CircuitPredicate::CircuitVersionEq(version));
+            }
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid circuit_version value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
         }
-        None => None,
-    };
+    }
+
     let mut link = req.uri().path().to_string();
     if !new_queries.is_empty() {
         link.push_str(&format!("?{}&", new_queries.join("&")));
@@ -140,59 +211,134 @@ fn list_circuits(
     Box::new(query_list_circuits(
         store,
         link,
-        member_filter,
-        status_filter,
-        Some(offset),
-        Some(limit),
+        filters,
+        offset,
+        cursor,
+        limit,
         protocol_version,
+        #[cfg(feature = "authorization")]
+        req.extensions().get::<ResourceAuthorization>().cloned(),
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn query_list_circuits(
     store: web::Data<Box<dyn AdminServiceStore>>,
     link: String,
-    member_filter: Option<String>,
-    status_filter: Option<String>,
+    filters: Vec<CircuitPredicate>,
     offset: Option<usize>,
-    limit: Option<usize>,
+    cursor: Option<Cursor>,
+    limit: usize,
     protocol_version: String,
+    #[cfg(feature = "authorization")] resource_authorization: Option<ResourceAuthorization>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
     web::block(move || {
-        let mut filters = {
-            if let Some(member) = member_filter {
-                vec![CircuitPredicate::MembersInclude(vec![member])]
-            } else {
-                vec![]
+        // A client that is only authorized for some circuits is only shown those circuits, so
+        // the circuits are filtered before the page is taken
+        #[cfg(feature = "authorization")]
+        if let Some(resource_authorization) = resource_authorization {
+            let circuits = store
+                .list_circuits(&filters)
+                .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?
+                .filter(|circuit| {
+                    resource_authorization.is_authorized(
+                        &RequestedResource::new()
+                            .with_circuit_id(circuit.circuit_id())
+                            .with_management_type(circuit.circuit_management_type()),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let total = circuits.len();
+
+            if let (Some(offset), None) = (offset, cursor.as_ref()) {
+                let circuits = circuits
+                    .into_iter()
+                    .skip(offset)
+                    .take(limit)
+                    .collect::<Vec<_>>();
+                let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+                return Ok((circuits, paging, protocol_version));
             }
-        };
-        if let Some(status) = status_filter {
-            filters.push(CircuitPredicate::CircuitStatus(CircuitStatus::from(status)));
+
+            // Circuits are listed in descending order of their IDs, so the page starts with the
+            // circuit following the cursor's key
+            let circuits = circuits
+                .into_iter()
+                .skip_while(|circuit| {
+                    cursor
+                        .as_ref()
+                        .map(|cursor| circuit.circuit_id() >= cursor.key())
+                        .unwrap_or(false)
+                })
+                .take(limit.saturating_add(1))
+                .collect::<Vec<_>>();
+            let (circuits, has_more) = Cursor::split_page(circuits, limit);
+
+            let next_cursor = Cursor::following(
+                cursor.as_ref(),
+                limit,
+                has_more,
+                circuits.last().map(|circuit| circuit.circuit_id()),
+            );
+            let paging = get_response_cursor_paging_info(
+                limit,
+                cursor.as_ref(),
+                next_cursor.as_ref(),
+                &link,
+                total,
+            );
+
+            return Ok((circuits, paging, protocol_version));
         }
 
-        let circuits = store
-            .list_circuits(&filters)
-            .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?;
+        // An explicit offset without a cursor is served as before, so that existing offset links
+        // keep working; otherwise the page is fetched from the store starting after the cursor
+        if let (Some(offset), None) = (offset, cursor.as_ref()) {
+            let circuits = store
+                .list_circuits(&filters)
+                .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?;
+
+            let total = circuits.len();
+            let circuits = circuits.skip(offset).take(limit).collect::<Vec<_>>();
+            let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
 
-        let offset_value = offset.unwrap_or(0);
-        let total = circuits.len();
-        let limit_value = limit.unwrap_or(total as usize);
+            return Ok((circuits, paging, protocol_version));
+        }
 
-        let circuits = circuits
-            .skip(offset_value)
-            .take(limit_value)
+        let total = store
+            .count_circuits(&filters)
+            .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?
+            as usize;
+        // One more circuit than the limit is fetched to find out whether there is a next page
+        let circuits = store
+            .list_circuits_page(
+                &filters,
+                cursor.as_ref().map(Cursor::key),
+                limit.saturating_add(1),
+            )
+            .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?
             .collect::<Vec<_>>();
+        let (circuits, has_more) = Cursor::split_page(circuits, limit);
 
-        Ok((
-            circuits,
-            link,
+        let next_cursor = Cursor::following(
+            cursor.as_ref(),
             limit,
-            offset,
-            total as usize,
-            protocol_version,
-        ))
+            has_more,
+            circuits.last().map(|circuit| circuit.circuit_id()),
+        );
+        let paging = get_response_cursor_paging_info(
+            limit,
+            cursor.as_ref(),
+            next_cursor.as_ref(),
+            &link,
+            total,
+        );
+
+        Ok((circuits, paging, protocol_version))
     })
     .then(|res| match res {
-        Ok((circuits, link, limit, offset, total_count, protocol_version)) => {
+        Ok((circuits, paging, protocol_version)) => {
             match protocol_version.as_str() {
                 "1" => Ok(
                     HttpResponse::Ok().json(resources::v1::circuits::ListCircuitsResponse {
@@ -200,7 +346,7 @@ fn query_list_circuits(
                             .iter()
                             .map(resources::v1::circuits::CircuitResponse::from)
                             .collect(),
-                        paging: get_response_paging_info(limit, offset, &link, total_count),
+                        paging,
                     }),
                 ),
 
@@ -211,7 +357,7 @@ fn query_list_circuits(
                             .iter()
                             .map(resources::v2::circuits::CircuitResponse::from)
                             .collect(),
-                        paging: get_response_paging_info(limit, offset, &link, total_count),
+                        paging,
                     }),
                 ),
                 _ => Ok(
@@ -557,18 +703,57 @@ mod tests {
             .expect("failed to convert expected data"),
         );
 
+        // the next page is referenced by a cursor after the last circuit on this page
+        let mut expected_paging = create_test_paging_response(0, 1, 1, 0, 1, 2, "/admin/circuits?");
+        expected_paging.next = format!(
+            "/admin/circuits?limit=1&cursor={}",
+            Cursor::new(1, "efghi-56789").encode()
+        );
         assert_eq!(
             circuits.get("paging").expect("no paging field in response"),
-            &to_value(create_test_paging_response(
-                0,
-                1,
-                1,
-                0,
-                1,
-                2,
-                "/admin/circuits?",
-            ))
-            .expect("failed to convert expected paging")
+            &to_value(expected_paging).expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /admin/circuits?limit=1&cursor=<cursor> request, using the cursor for the
+    /// second page, returns the circuit after the one on the first page.
+    fn test_list_circuit_with_cursor() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_circuits_resource(filled_splinter_state())]);
+
+        let cursor = Cursor::new(1, "efghi-56789").encode();
+        let url = Url::parse(&format!(
+            "http://{}/admin/circuits?limit=1&cursor={}",
+            bind_url, cursor
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let circuits: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            circuits.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::circuits::CircuitResponse::from(
+                &get_circuit_1().0
+            )])
+            .expect("failed to convert expected data"),
+        );
+
+        let mut expected_paging = create_test_paging_response(1, 1, 1, 0, 1, 2, "/admin/circuits?");
+        expected_paging.current = format!("/admin/circuits?limit=1&cursor={}", cursor);
+        assert_eq!(
+            circuits.get("paging").expect("no paging field in response"),
+            &to_value(expected_paging).expect("failed to convert expected paging")
         );
 
         shutdown_handle
@@ -577,6 +762,27 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[test]
+    /// Tests a GET /admin/circuits?cursor=<invalid> request returns a bad request error.
+    fn test_list_circuit_with_invalid_cursor() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!("http://{}/admin/circuits?cursor=zzzz", bind_url))
+            .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     #[test]
     /// Tests a GET /admin/circuits?offset=1 request returns the expected circuit.
     fn test_list_circuit_with_offset() {
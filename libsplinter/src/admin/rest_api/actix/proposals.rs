@@ -16,6 +16,8 @@
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
+#[cfg(feature = "authorization")]
+use actix_web::HttpMessage;
 use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, Future};
 
@@ -23,9 +25,11 @@ use futures::{future::IntoFuture, Future};
 use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
 use crate::admin::service::proposal_store::ProposalStore;
 use crate::admin::store::CircuitPredicate;
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::authorization::{RequestedResource, ResourceAuthorization};
 use crate::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
-    paging::{get_response_paging_info, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    paging::{get_response_cursor_paging_info, get_response_paging_info, Cursor, DEFAULT_LIMIT},
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
@@ -43,7 +47,7 @@ pub fn make_list_proposals_resource<PS: ProposalStore + 'static>(proposal_store:
 
     #[cfg(feature = "authorization")]
     {
-        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |r, _| {
+        resource.add_scoped_method(Method::Get, CIRCUIT_READ_PERMISSION, move |r, _| {
             list_proposals(r, web::Data::new(proposal_store.clone()))
         })
     }
@@ -72,7 +76,7 @@ fn list_proposals<PS: ProposalStore + 'static>(
 
     let offset = match query.get("offset") {
         Some(value) => match value.parse::<usize>() {
-            Ok(val) => val,
+            Ok(val) => Some(val),
             Err(err) => {
                 return Box::new(
                     HttpResponse::BadRequest()
@@ -84,7 +88,24 @@ fn list_proposals<PS: ProposalStore + 'static>(
                 )
             }
         },
-        None => DEFAULT_OFFSET,
+        None => None,
+    };
+
+    let cursor = match query.get("cursor") {
+        Some(value) => match Cursor::decode(value) {
+            Ok(cursor) => Some(cursor),
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid cursor value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => None,
     };
 
     let limit = match query.get("limit") {
@@ -105,14 +126,79 @@ fn list_proposals<PS: ProposalStore + 'static>(
     };
 
     let mut new_queries = vec![];
-    let management_type_filter = query.get("management_type").map(|management_type| {
+    let mut filters = vec![];
+    if let Some(management_type) = query.get("management_type") {
         new_queries.push(format!("management_type={}", management_type));
-        management_type.to_string()
-    });
-    let member_filter = query.get("member").map(|member| {
+        filters.push(CircuitPredicate::ManagementTypeEq(
+            management_type.to_string(),
+        ));
+    }
+    if let Some(member) = query.get("member") {
         new_queries.push(format!("member={}", member));
-        member.to_string()
-    });
+        filters.push(CircuitPredicate::MembersInclude(vec![member.to_string()]));
+    }
+    if let Some(service_type) = query.get("service_type") {
+        new_queries.push(format!("service_type={}", service_type));
+        filters.push(CircuitPredicate::ServiceTypeEq(service_type.to_string()));
+    }
+    if let Some(value) = query.get("service_arg") {
+        let mut parts = value.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(key), Some(arg_value)) if !key.is_empty() => {
+                new_queries.push(format!("service_arg={}", value));
+                filters.push(CircuitPredicate::ServiceArgumentEq(
+                    key.to_string(),
+                    arg_value.to_string(),
+                ));
+            }
+            _ => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid service_arg value passed: {}. Expected <key>=<value>",
+                            value
+                        )))
+                        .into_future(),
+                )
+            }
+        }
+    }
+    if let Some(display_name) = query.get("display_name") {
+        new_queries.push(format!("display_name={}", display_name));
+        filters.push(CircuitPredicate::DisplayNameContains(
+            display_name.to_string(),
+        ));
+    }
+    if let Some(value) = query.get("circuit_version") {
+        match value.parse::<i32>() {
+            Ok(version) => {
+                new_queries.push(format!("circuit_version={}", value));
+                filters.push(CircuitPredicate::CircuitVersionEq(version));
+            }
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid circuit_version value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        }
+    }
+    if let Some(requester) = query.get("requester") {
+        new_queries.push(format!("requester={}", requester));
+        filters.push(CircuitPredicate::RequesterNodeIdEq(requester.to_string()));
+    }
+    if let Some(voted_by) = query.get("voted_by") {
+        new_queries.push(format!("voted_by={}", voted_by));
+        filters.push(CircuitPredicate::VotedBy(voted_by.to_string()));
+    }
+    if let Some(node_id) = query.get("awaiting_vote_from") {
+        new_queries.push(format!("awaiting_vote_from={}", node_id));
+        filters.push(CircuitPredicate::AwaitingVoteFrom(node_id.to_string()));
+    }
 
     let mut link = req.uri().path().to_string();
     if !new_queries.is_empty() {
@@ -138,48 +224,137 @@ fn list_proposals<PS: ProposalStore + 'static>(
     Box::new(query_list_proposals(
         proposal_store,
         link,
-        management_type_filter,
-        member_filter,
-        Some(offset),
-        Some(limit),
+        filters,
+        offset,
+        cursor,
+        limit,
         protocol_version,
+        #[cfg(feature = "authorization")]
+        req.extensions().get::<ResourceAuthorization>().cloned(),
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn query_list_proposals<PS: ProposalStore + 'static>(
     proposal_store: web::Data<PS>,
     link: String,
-    management_type_filter: Option<String>,
-    member_filter: Option<String>,
+    filters: Vec<CircuitPredicate>,
     offset: Option<usize>,
-    limit: Option<usize>,
+    cursor: Option<Cursor>,
+    limit: usize,
     protocol_version: String,
+    #[cfg(feature = "authorization")] resource_authorization: Option<ResourceAuthorization>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
     web::block(move || {
-        let mut filters = vec![];
-        if let Some(management_type) = management_type_filter {
-            filters.push(CircuitPredicate::ManagementTypeEq(management_type));
+        // A client that is only authorized for some circuits is only shown the proposals for
+        // those circuits, so the proposals are filtered before the page is taken
+        #[cfg(feature = "authorization")]
+        if let Some(resource_authorization) = resource_authorization {
+            let proposals = proposal_store
+                .proposals(filters)
+                .map_err(|err| ProposalListError::InternalError(err.to_string()))?
+                .filter(|proposal| {
+                    resource_authorization.is_authorized(
+                        &RequestedResource::new()
+                            .with_circuit_id(&proposal.circuit_id)
+                            .with_management_type(&proposal.circuit.circuit_management_type),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let total = proposals.len();
+
+            if let (Some(offset), None) = (offset, cursor.as_ref()) {
+                let proposals = proposals
+                    .into_iter()
+                    .skip(offset)
+                    .take(limit)
+                    .collect::<Vec<_>>();
+                let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+                return Ok((proposals, paging, protocol_version));
+            }
+
+            // Proposals are listed in descending order of their circuit IDs, so the page starts
+            // with the proposal following the cursor's key
+            let proposals = proposals
+                .into_iter()
+                .skip_while(|proposal| {
+                    cursor
+                        .as_ref()
+                        .map(|cursor| proposal.circuit_id.as_str() >= cursor.key())
+                        .unwrap_or(false)
+                })
+                .take(limit.saturating_add(1))
+                .collect::<Vec<_>>();
+            let (proposals, has_more) = Cursor::split_page(proposals, limit);
+
+            let next_cursor = Cursor::following(
+                cursor.as_ref(),
+                limit,
+                has_more,
+                proposals
+                    .last()
+                    .map(|proposal| proposal.circuit_id.as_str()),
+            );
+            let paging = get_response_cursor_paging_info(
+                limit,
+                cursor.as_ref(),
+                next_cursor.as_ref(),
+                &link,
+                total,
+            );
+
+            return Ok((proposals, paging, protocol_version));
         }
-        if let Some(member) = member_filter {
-            filters.push(CircuitPredicate::MembersInclude(vec![member]));
+
+        // An explicit offset without a cursor is served as before, so that existing offset links
+        // keep working; otherwise the page is fetched from the store starting after the cursor
+        if let (Some(offset), None) = (offset, cursor.as_ref()) {
+            let proposals = proposal_store
+                .proposals(filters)
+                .map_err(|err| ProposalListError::InternalError(err.to_string()))?;
+
+            let total = proposals.total();
+            let proposals = proposals.skip(offset).take(limit).collect::<Vec<_>>();
+            let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+            return Ok((proposals, paging, protocol_version));
         }
 
-        let proposals = proposal_store
-            .proposals(filters)
+        let total = proposal_store
+            .count_proposals(filters.clone())
             .map_err(|err| ProposalListError::InternalError(err.to_string()))?;
-        let offset_value = offset.unwrap_or(0);
-        let total = proposals.total() as usize;
-        let limit_value = limit.unwrap_or(total);
-
-        let proposals = proposals
-            .skip(offset_value)
-            .take(limit_value)
+        // One more proposal than the limit is fetched to find out whether there is a next page
+        let proposals = proposal_store
+            .proposals_page(
+                filters,
+                cursor.as_ref().map(Cursor::key),
+                limit.saturating_add(1),
+            )
+            .map_err(|err| ProposalListError::InternalError(err.to_string()))?
             .collect::<Vec<_>>();
+        let (proposals, has_more) = Cursor::split_page(proposals, limit);
+
+        let next_cursor = Cursor::following(
+            cursor.as_ref(),
+            limit,
+            has_more,
+            proposals
+                .last()
+                .map(|proposal| proposal.circuit_id.as_str()),
+        );
+        let paging = get_response_cursor_paging_info(
+            limit,
+            cursor.as_ref(),
+            next_cursor.as_ref(),
+            &link,
+            total,
+        );
 
-        Ok((proposals, link, limit, offset, total, protocol_version))
+        Ok((proposals, paging, protocol_version))
     })
     .then(|res| match res {
-        Ok((proposals, link, limit, offset, total_count, protocol_version)) => {
+        Ok((proposals, paging, protocol_version)) => {
             match protocol_version.as_str() {
                 "1" => Ok(HttpResponse::Ok().json(
                     resources::v1::proposals::ListProposalsResponse {
@@ -187,7 +362,7 @@ fn query_list_proposals<PS: ProposalStore + 'static>(
                             .iter()
                             .map(resources::v1::proposals::ProposalResponse::from)
                             .collect(),
-                        paging: get_response_paging_info(limit, offset, &link, total_count),
+                        paging,
                     },
                 )),
                 // Handles 2
@@ -207,7 +382,7 @@ fn query_list_proposals<PS: ProposalStore + 'static>(
                     Ok(
                         HttpResponse::Ok().json(resources::v2::proposals::ListProposalsResponse {
                             data: proposal_responses,
-                            paging: get_response_paging_info(limit, offset, &link, total_count),
+                            paging,
                         }),
                     )
                 }
@@ -547,20 +722,69 @@ mod tests {
             .expect("failed to convert expected data"),
         );
 
+        // the next page is referenced by a cursor after the last proposal on this page
+        let mut expected_paging =
+            create_test_paging_response(0, 1, 1, 0, 2, 3, "/admin/proposals?");
+        expected_paging.next = format!(
+            "/admin/proposals?limit=1&cursor={}",
+            Cursor::new(1, "abcDE-00000").encode()
+        );
         assert_eq!(
             proposals
                 .get("paging")
                 .expect("no paging field in response"),
-            &to_value(create_test_paging_response(
-                0,
-                1,
-                1,
-                0,
-                2,
-                3,
-                "/admin/proposals?"
-            ))
-            .expect("failed to convert expected paging")
+            &to_value(expected_paging).expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /admin/proposals?limit=1&cursor=<cursor> request, using the cursor for the
+    /// second page, returns the proposal after the one on the first page along with a cursor for
+    /// the third page.
+    fn test_list_proposal_with_cursor() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_proposals_resource(MockProposalStore)]);
+
+        let cursor = Cursor::new(1, "abcDE-00000").encode();
+        let url = Url::parse(&format!(
+            "http://{}/admin/proposals?limit=1&cursor={}",
+            bind_url, cursor
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let proposals: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            proposals.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::proposals::ProposalResponse::try_from(
+                &get_proposal_2()
+            )
+            .expect("Unable to get ProposalResponse")])
+            .expect("failed to convert expected data"),
+        );
+
+        let mut expected_paging =
+            create_test_paging_response(1, 1, 2, 0, 2, 3, "/admin/proposals?");
+        expected_paging.current = format!("/admin/proposals?limit=1&cursor={}", cursor);
+        expected_paging.next = format!(
+            "/admin/proposals?limit=1&cursor={}",
+            Cursor::new(2, "abcDE-00001").encode()
+        );
+        assert_eq!(
+            proposals
+                .get("paging")
+                .expect("no paging field in response"),
+            &to_value(expected_paging).expect("failed to convert expected paging")
         );
 
         shutdown_handle
@@ -665,6 +889,47 @@ mod tests {
             Ok(ProposalIter::new(Box::new(proposals.into_iter())))
         }
 
+        fn proposals_page(
+            &self,
+            filters: Vec<CircuitPredicate>,
+            after: Option<&str>,
+            limit: usize,
+        ) -> Result<ProposalIter, ProposalStoreError> {
+            // The mock proposals are already listed in order, so the page starts with the
+            // proposal following `after` in the list
+            let mut proposals = get_proposal_list();
+            proposals.retain(|proposal| {
+                filters
+                    .iter()
+                    .all(|predicate| predicate.apply_to_proposals(proposal))
+            });
+
+            let start = after
+                .and_then(|after| {
+                    proposals
+                        .iter()
+                        .position(|proposal| proposal.circuit_id() == after)
+                })
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            Ok(ProposalIter::new(Box::new(
+                proposals
+                    .into_iter()
+                    .skip(start)
+                    .take(limit)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            )))
+        }
+
+        fn count_proposals(
+            &self,
+            filters: Vec<CircuitPredicate>,
+        ) -> Result<usize, ProposalStoreError> {
+            Ok(self.proposals(filters)?.total())
+        }
+
         fn proposal(
             &self,
             _circuit_id: &str,
@@ -767,6 +1032,7 @@ mod tests {
                     node_id: "node_id".into(),
                     endpoints: vec!["".into()],
                     public_key: None,
+                    relay_node_id: None,
                 }],
                 authorization_type: AuthorizationType::Trust,
                 persistence: PersistenceType::Any,
@@ -782,6 +1048,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
@@ -808,6 +1075,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
@@ -823,6 +1091,7 @@ mod tests {
                     node_id: "node_id".into(),
                     endpoints: vec!["".into()],
                     public_key: None,
+                    relay_node_id: None,
                 }],
                 authorization_type: AuthorizationType::Trust,
                 persistence: PersistenceType::Any,
@@ -838,6 +1107,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
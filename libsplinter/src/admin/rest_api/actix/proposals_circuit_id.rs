@@ -256,6 +256,22 @@ mod tests {
             unimplemented!()
         }
 
+        fn proposals_page(
+            &self,
+            _filters: Vec<CircuitPredicate>,
+            _after: Option<&str>,
+            _limit: usize,
+        ) -> Result<ProposalIter, ProposalStoreError> {
+            unimplemented!()
+        }
+
+        fn count_proposals(
+            &self,
+            _filters: Vec<CircuitPredicate>,
+        ) -> Result<usize, ProposalStoreError> {
+            unimplemented!()
+        }
+
         fn proposal(
             &self,
             circuit_id: &str,
@@ -291,6 +307,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
@@ -12,17 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "authorization")]
+use actix_web::HttpMessage;
 use actix_web::HttpResponse;
 use futures::{Future, IntoFuture};
+#[cfg(feature = "authorization")]
+use protobuf::Message;
 
 #[cfg(feature = "authorization")]
 use crate::admin::rest_api::CIRCUIT_WRITE_PERMISSION;
 use crate::admin::service::{AdminCommands, AdminServiceError};
 use crate::protos::admin::CircuitManagementPayload;
+#[cfg(feature = "authorization")]
+use crate::protos::admin::{CircuitManagementPayload_Action, CircuitManagementPayload_Header};
 use crate::rest_api::{
     actix_web_1::{into_protobuf, Method, ProtocolVersionRangeGuard, Resource},
     SPLINTER_PROTOCOL_VERSION,
 };
+#[cfg(feature = "authorization")]
+use crate::rest_api::{
+    auth::authorization::{RequestedResource, ResourceAuthorization},
+    ErrorResponse,
+};
 use crate::service::instance::ServiceError;
 
 const ADMIN_SUBMIT_PROTOCOL_MIN: u32 = 1;
@@ -34,37 +45,54 @@ pub fn make_submit_route<A: AdminCommands + Clone + 'static>(admin_commands: A)
 
     #[cfg(feature = "authorization")]
     {
-        resource.add_method(Method::Post, CIRCUIT_WRITE_PERMISSION, move |_, payload| {
-            let admin_commands = admin_commands.clone();
-            Box::new(
-                into_protobuf::<CircuitManagementPayload>(payload).and_then(move |payload| {
-                    match admin_commands.submit_circuit_change(payload) {
-                        Ok(()) => HttpResponse::Accepted().finish().into_future(),
-                        Err(AdminServiceError::ServiceError(
-                            ServiceError::UnableToHandleMessage(err),
-                        )) => {
-                            debug!("{}", err);
-                            HttpResponse::BadRequest()
+        resource.add_scoped_method(
+            Method::Post,
+            CIRCUIT_WRITE_PERMISSION,
+            move |request, payload| {
+                let admin_commands = admin_commands.clone();
+                let resource_authorization =
+                    request.extensions().get::<ResourceAuthorization>().cloned();
+                Box::new(into_protobuf::<CircuitManagementPayload>(payload).and_then(
+                    move |payload| {
+                        // A client that is only authorized for some circuits may only submit
+                        // changes to those circuits
+                        if let Some(resource_authorization) = resource_authorization {
+                            if !resource_authorization.is_authorized(&requested_resource(&payload))
+                            {
+                                return HttpResponse::Unauthorized()
+                                    .json(ErrorResponse::unauthorized())
+                                    .into_future();
+                            }
+                        }
+
+                        match admin_commands.submit_circuit_change(payload) {
+                            Ok(()) => HttpResponse::Accepted().finish().into_future(),
+                            Err(AdminServiceError::ServiceError(
+                                ServiceError::UnableToHandleMessage(err),
+                            )) => {
+                                debug!("{}", err);
+                                HttpResponse::BadRequest()
+                                    .json(json!({
+                                        "message": format!("Unable to handle message: {}", err)
+                                    }))
+                                    .into_future()
+                            }
+                            Err(AdminServiceError::ServiceError(
+                                ServiceError::InvalidMessageFormat(err),
+                            )) => HttpResponse::BadRequest()
                                 .json(json!({
-                                    "message": format!("Unable to handle message: {}", err)
+                                    "message": format!("Failed to parse payload: {}", err)
                                 }))
-                                .into_future()
+                                .into_future(),
+                            Err(err) => {
+                                error!("{}", err);
+                                HttpResponse::InternalServerError().finish().into_future()
+                            }
                         }
-                        Err(AdminServiceError::ServiceError(
-                            ServiceError::InvalidMessageFormat(err),
-                        )) => HttpResponse::BadRequest()
-                            .json(json!({
-                                "message": format!("Failed to parse payload: {}", err)
-                            }))
-                            .into_future(),
-                        Err(err) => {
-                            error!("{}", err);
-                            HttpResponse::InternalServerError().finish().into_future()
-                        }
-                    }
-                }),
-            )
-        })
+                    },
+                ))
+            },
+        )
     }
     #[cfg(not(feature = "authorization"))]
     {
@@ -101,3 +129,125 @@ pub fn make_submit_route<A: AdminCommands + Clone + 'static>(admin_commands: A)
         })
     }
 }
+
+/// Returns the circuit that is changed by the payload's action. The resource is empty if the
+/// payload's header can't be parsed, in which case the payload will also be rejected by the admin
+/// service.
+#[cfg(feature = "authorization")]
+fn requested_resource(payload: &CircuitManagementPayload) -> RequestedResource {
+    let header = match CircuitManagementPayload_Header::parse_from_bytes(payload.get_header()) {
+        Ok(header) => header,
+        Err(_) => return RequestedResource::new(),
+    };
+
+    let circuit_id = match header.get_action() {
+        CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
+            let circuit = payload.get_circuit_create_request().get_circuit();
+            return RequestedResource::new()
+                .with_circuit_id(circuit.get_circuit_id())
+                .with_management_type(circuit.get_circuit_management_type());
+        }
+        CircuitManagementPayload_Action::CIRCUIT_JOIN_REQUEST => {
+            let circuit = payload.get_circuit_join_request().get_circuit();
+            return RequestedResource::new()
+                .with_circuit_id(circuit.get_circuit_id())
+                .with_management_type(circuit.get_circuit_management_type());
+        }
+        CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
+            payload.get_circuit_proposal_vote().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+            payload.get_circuit_update_roster_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE => {
+            payload.get_circuit_update_add_node().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+            payload.get_circuit_update_remove_node().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => payload
+            .get_circuit_update_application_metadata_request()
+            .get_circuit_id(),
+        CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
+            payload.get_circuit_disband_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_PURGE_REQUEST => {
+            payload.get_circuit_purge_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_ABANDON => {
+            payload.get_circuit_abandon().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::PROPOSAL_REMOVE_REQUEST => {
+            payload.get_proposal_remove_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_EXPIRE => {
+            payload.get_circuit_proposal_expire().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::ACTION_UNSET => return RequestedResource::new(),
+    };
+
+    RequestedResource::new().with_circuit_id(circuit_id)
+}
+
+#[cfg(all(test, feature = "authorization"))]
+mod tests {
+    use super::*;
+
+    use crate::protos::admin::{
+        Circuit, CircuitCreateRequest, CircuitDisbandRequest, CircuitProposalVote,
+    };
+
+    /// Verifies that the requested resource is the circuit of the action given in the payload's
+    /// header, even if the payload also contains a request for another action.
+    #[test]
+    fn test_requested_resource() {
+        let mut circuit = Circuit::new();
+        circuit.set_circuit_id("abcde-01234".into());
+        circuit.set_circuit_management_type("gameroom".into());
+        let mut create_request = CircuitCreateRequest::new();
+        create_request.set_circuit(circuit);
+
+        let mut payload = CircuitManagementPayload::new();
+        payload.set_header(create_header(
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST,
+        ));
+        payload.set_circuit_create_request(create_request);
+        assert_eq!(
+            requested_resource(&payload),
+            RequestedResource::new()
+                .with_circuit_id("abcde-01234")
+                .with_management_type("gameroom")
+        );
+
+        let mut vote = CircuitProposalVote::new();
+        vote.set_circuit_id("fghij-01234".into());
+        payload.set_header(create_header(
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE,
+        ));
+        payload.set_circuit_proposal_vote(vote);
+        assert_eq!(
+            requested_resource(&payload),
+            RequestedResource::new().with_circuit_id("fghij-01234")
+        );
+
+        let mut disband_request = CircuitDisbandRequest::new();
+        disband_request.set_circuit_id("klmno-01234".into());
+        payload.set_header(create_header(
+            CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST,
+        ));
+        payload.set_circuit_disband_request(disband_request);
+        assert_eq!(
+            requested_resource(&payload),
+            RequestedResource::new().with_circuit_id("klmno-01234")
+        );
+
+        payload.set_header(b"invalid".to_vec());
+        assert!(requested_resource(&payload).is_empty());
+    }
+
+    fn create_header(action: CircuitManagementPayload_Action) -> Vec<u8> {
+        let mut header = CircuitManagementPayload_Header::new();
+        header.set_action(action);
+        header.write_to_bytes().expect("Unable to serialize header")
+    }
+}
@@ -24,6 +24,8 @@ use crate::admin::messages::v1;
 use crate::admin::messages::AdminServiceEvent;
 #[cfg(feature = "authorization")]
 use crate::admin::rest_api::CIRCUIT_READ_PERMISSION;
+#[cfg(feature = "admin-service-event-retention")]
+use crate::admin::service::AdminServiceError;
 use crate::admin::service::{
     AdminCommands, AdminServiceEventSubscriber, AdminServiceStatus, AdminSubscriberError,
 };
@@ -145,6 +147,22 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
                                 }
                             }
                         }
+                        #[cfg(feature = "admin-service-event-retention")]
+                        Err(err @ AdminServiceError::EventsCompacted { .. }) => {
+                            debug!(
+                                "Unable to catch up on admin events for {}: {}",
+                                &circuit_management_type, err
+                            );
+                            return Box::new(
+                                HttpResponse::Gone()
+                                    .json(ErrorResponse::gone(&format!(
+                                        "{}; resync from /admin/circuits and reconnect \
+                                        without a last seen event",
+                                        err
+                                    )))
+                                    .into_future(),
+                            );
+                        }
                         Err(err) => {
                             error!(
                                 "Unable to load initial set of admin events for {}: {}",
@@ -273,6 +291,22 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
                             }
                         }
                     }
+                    #[cfg(feature = "admin-service-event-retention")]
+                    Err(err @ AdminServiceError::EventsCompacted { .. }) => {
+                        debug!(
+                            "Unable to catch up on admin events for {}: {}",
+                            &circuit_management_type, err
+                        );
+                        return Box::new(
+                            HttpResponse::Gone()
+                                .json(ErrorResponse::gone(&format!(
+                                    "{}; resync from /admin/circuits and reconnect without a \
+                                    last seen event",
+                                    err
+                                )))
+                                .into_future(),
+                        );
+                    }
                     Err(err) => {
                         error!(
                             "Unable to load initial set of admin events for {}: {}",
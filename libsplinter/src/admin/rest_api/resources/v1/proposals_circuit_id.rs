@@ -37,6 +37,7 @@ impl<'a> From<&'a CircuitProposal> for ProposalResponse<'a> {
             ProposalType::AddNode => "AddNode",
             ProposalType::RemoveNode => "RemoveNode",
             ProposalType::Disband => "Disband",
+            ProposalType::UpdateApplicationMetadata => "UpdateApplicationMetadata",
         };
 
         Self {
@@ -26,6 +26,7 @@ pub(crate) struct CircuitResponse<'a> {
     pub display_name: &'a Option<String>,
     pub circuit_version: i32,
     pub circuit_status: &'a CircuitStatus,
+    pub application_metadata: Option<String>,
 }
 
 impl<'a> From<&'a Circuit> for CircuitResponse<'a> {
@@ -42,6 +43,10 @@ impl<'a> From<&'a Circuit> for CircuitResponse<'a> {
             display_name: circuit.display_name(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status(),
+            application_metadata: circuit
+                .application_metadata()
+                .as_ref()
+                .map(|application_metadata| to_hex(application_metadata)),
         }
     }
 }
@@ -18,6 +18,8 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+#[cfg(feature = "admin-service-proposal-expiry")]
+use cylinder::Signer;
 use cylinder::Verifier as SignatureVerifier;
 
 use crate::admin::lifecycle::LifecycleDispatch;
@@ -53,6 +55,8 @@ pub struct AdminServiceBuilder {
     routing_table_writer: Option<Box<dyn RoutingTableWriter>>,
     event_store: Option<Box<dyn AdminServiceStore>>,
     public_keys: Option<Vec<PublicKey>>,
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    proposal_expiry: Option<(Duration, Box<dyn Signer>)>,
 }
 
 impl AdminServiceBuilder {
@@ -158,6 +162,23 @@ impl AdminServiceBuilder {
         self
     }
 
+    /// Sets how long a circuit proposal may remain pending on this node.
+    ///
+    /// Once a proposal has been pending for longer than this duration, this node submits a
+    /// request to expire the proposal, signed with the given signer, to the proposal's members.
+    /// The proposal is removed once all members have agreed to the expiry. If not set, this node
+    /// does not expire proposals.
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    pub fn with_proposal_expiry(
+        mut self,
+        proposal_expiry: Duration,
+        signer: Box<dyn Signer>,
+    ) -> Self {
+        self.proposal_expiry = Some((proposal_expiry, signer));
+
+        self
+    }
+
     /// Constructs the AdminServce.
     ///
     /// # Errors
@@ -219,7 +240,7 @@ impl AdminServiceBuilder {
 
         let public_keys = self.public_keys.unwrap_or_default();
 
-        let admin_service_shared = Arc::new(Mutex::new(AdminServiceShared::new(
+        let admin_service_shared = AdminServiceShared::new(
             node_id.clone(),
             lifecycle_dispatch,
             service_arg_validators,
@@ -231,7 +252,16 @@ impl AdminServiceBuilder {
             routing_table_writer,
             admin_event_store,
             public_keys,
-        )));
+        );
+
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        let admin_service_shared = {
+            let mut admin_service_shared = admin_service_shared;
+            admin_service_shared.set_proposal_expiry(self.proposal_expiry);
+            admin_service_shared
+        };
+
+        let admin_service_shared = Arc::new(Mutex::new(admin_service_shared));
 
         Ok(AdminService {
             service_id,
@@ -241,6 +271,8 @@ impl AdminServiceBuilder {
             consensus: None,
             peer_connector,
             peer_notification_run_state: None,
+            #[cfg(feature = "admin-service-proposal-expiry")]
+            proposal_expiry_run_state: None,
         })
     }
 }
@@ -151,19 +151,14 @@ impl ProposalManager for AdminProposalManager {
         _previous_proposal_id: Option<ProposalId>,
         _consensus_data: Vec<u8>,
     ) -> Result<(), ProposalManagerError> {
-        let network_sender = self
-            .shared
-            .lock()
-            .map_err(|_| ServiceError::PoisonedLock("the admin state lock was poisoned".into()))?
-            .network_sender()
-            .as_ref()
-            .cloned()
-            .ok_or(ServiceError::NotStarted)?;
-
         let mut shared = self
             .shared
             .lock()
             .map_err(|_| ServiceError::PoisonedLock("the admin state lock was poisoned".into()))?;
+        if shared.network_sender().is_none() {
+            return Err(ServiceError::NotStarted.into());
+        }
+
         if let Some(circuit_payload) = shared.pop_pending_circuit_payload() {
             let (expected_hash, circuit_proposal) = shared
                 .propose_change(circuit_payload.clone())
@@ -202,6 +197,16 @@ impl ProposalManager for AdminProposalManager {
 
             // Send the proposal to the other services
             let mut proposed_circuit = ProposedCircuit::new();
+            // The node being added to a circuit does not have the circuit yet, so the current
+            // definition of the circuit is sent along with the proposal
+            if circuit_payload.has_circuit_update_add_node() {
+                if let Some(circuit) = shared
+                    .get_proto_circuit(circuit_proposal.get_circuit_id())
+                    .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?
+                {
+                    proposed_circuit.set_circuit(circuit);
+                }
+            }
             proposed_circuit.set_circuit_payload(circuit_payload);
             proposed_circuit.set_expected_hash(expected_hash.as_bytes().into());
             proposed_circuit.set_required_verifiers(required_verifiers_bytes);
@@ -230,12 +235,9 @@ impl ProposalManager for AdminProposalManager {
 
             for node in peer_node {
                 if node.node_id != shared.node_id() {
-                    network_sender
-                        .send(
-                            &admin_service_id(
-                                &PeerTokenPair::new(node.token.clone(), local_node.clone())
-                                    .id_as_string(),
-                            ),
+                    shared
+                        .send_to_admin_service(
+                            &PeerTokenPair::new(node.token.clone(), local_node.clone()),
                             &envelope_bytes,
                         )
                         .unwrap();
@@ -373,16 +375,18 @@ impl ConsensusNetworkSender for AdminConsensusNetworkSender {
             .clone()
             .ok_or(ConsensusSendError::NotReady)?;
 
-        let service_id = shared
+        let token = shared
             .token_to_peer()
             .iter()
             .find(|(_, node)| node.peer_node.admin_service == peer_id_string)
-            .map(|(token, _)| admin_service_id(&token.id_as_string()))
-            .unwrap_or(peer_id_string);
+            .map(|(token, _)| token.clone());
 
-        network_sender
-            .send(&service_id, msg.write_to_bytes()?.as_slice())
-            .map_err(|err| ConsensusSendError::Internal(Box::new(err)))?;
+        let message_bytes = msg.write_to_bytes()?;
+        let result = match token {
+            Some(token) => shared.send_to_admin_service(&token, &message_bytes),
+            None => network_sender.send(&peer_id_string, &message_bytes),
+        };
+        result.map_err(|err| ConsensusSendError::Internal(Box::new(err)))?;
 
         Ok(())
     }
@@ -399,10 +403,9 @@ impl ConsensusNetworkSender for AdminConsensusNetworkSender {
             )))
         })?;
 
-        let network_sender = shared
-            .network_sender()
-            .clone()
-            .ok_or(ConsensusSendError::NotReady)?;
+        if shared.network_sender().is_none() {
+            return Err(ConsensusSendError::NotReady);
+        }
 
         // Since there are not a fixed set of peers to send messages too, use the set of verifiers
         // in the current_consensus_verifiers which comes from the pending_changes
@@ -410,11 +413,8 @@ impl ConsensusNetworkSender for AdminConsensusNetworkSender {
             {
                 // don't send a message back to this service
                 if !shared.is_local_node(verifier.peer_id()) {
-                    network_sender
-                        .send(
-                            &admin_service_id(&verifier.id_as_string()),
-                            msg.write_to_bytes()?.as_slice(),
-                        )
+                    shared
+                        .send_to_admin_service(verifier, msg.write_to_bytes()?.as_slice())
                         .map_err(|err| ConsensusSendError::Internal(Box::new(err)))?;
                 }
             }
@@ -31,6 +31,13 @@ pub enum AdminServiceError {
         context: String,
         source: Option<Box<dyn Error>>,
     },
+
+    /// The requested events have been removed from the event log by compaction.
+    #[cfg(feature = "admin-service-event-retention")]
+    EventsCompacted {
+        since_event_id: i64,
+        compacted_event_id: i64,
+    },
 }
 
 impl AdminServiceError {
@@ -54,6 +61,8 @@ impl Error for AdminServiceError {
         match self {
             AdminServiceError::ServiceError(err) => Some(err),
             AdminServiceError::GeneralError { source, .. } => source.as_ref().map(|err| &**err),
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminServiceError::EventsCompacted { .. } => None,
         }
     }
 }
@@ -69,6 +78,15 @@ impl fmt::Display for AdminServiceError {
                     f.write_str(context)
                 }
             }
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminServiceError::EventsCompacted {
+                since_event_id,
+                compacted_event_id,
+            } => write!(
+                f,
+                "events since {} are no longer available; events up to {} have been compacted",
+                since_event_id, compacted_event_id
+            ),
         }
     }
 }
@@ -165,6 +183,13 @@ pub enum AdminSharedError {
 
     // An error occured while trying to negotiated protocol versions
     ServiceProtocolError(String),
+
+    /// The requested events have been removed from the event log by compaction.
+    #[cfg(feature = "admin-service-event-retention")]
+    EventsCompacted {
+        since_event_id: i64,
+        compacted_event_id: i64,
+    },
 }
 
 impl Error for AdminSharedError {
@@ -193,6 +218,8 @@ impl Error for AdminSharedError {
             AdminSharedError::ValidationFailed(_) => None,
             AdminSharedError::UnableToAddSubscriber(_) => None,
             AdminSharedError::ServiceProtocolError(_) => None,
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminSharedError::EventsCompacted { .. } => None,
         }
     }
 }
@@ -237,6 +264,15 @@ impl fmt::Display for AdminSharedError {
                 "error occured while trying to agree on protocol: {}",
                 msg
             ),
+            #[cfg(feature = "admin-service-event-retention")]
+            AdminSharedError::EventsCompacted {
+                since_event_id,
+                compacted_event_id,
+            } => write!(
+                f,
+                "events since {} are no longer available; events up to {} have been compacted",
+                since_event_id, compacted_event_id
+            ),
         }
     }
 }
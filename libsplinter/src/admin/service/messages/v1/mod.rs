@@ -71,6 +71,11 @@ impl CreateCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                return Err(MarshallingError::UnsetField(
+                    "Unsupported durability type: guaranteed delivery".to_string(),
+                ));
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset durability type".to_string(),
@@ -80,6 +85,11 @@ impl CreateCircuit {
 
         let routes = match proto.get_routes() {
             admin::Circuit_RouteType::ANY_ROUTE => RouteType::Any,
+            admin::Circuit_RouteType::RELAYED_ROUTE => {
+                return Err(MarshallingError::UnsetField(
+                    "Unsupported route type: relayed".to_string(),
+                ));
+            }
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => {
                 return Err(MarshallingError::UnsetField("Unset route type".to_string()));
             }
@@ -302,6 +312,9 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Destroy,
+            admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA => {
+                ProposalType::UpdateApplicationMetadata
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset proposal type".to_string(),
@@ -333,6 +346,9 @@ impl CircuitProposal {
             ProposalType::AddNode => admin::CircuitProposal_ProposalType::ADD_NODE,
             ProposalType::RemoveNode => admin::CircuitProposal_ProposalType::REMOVE_NODE,
             ProposalType::Destroy => admin::CircuitProposal_ProposalType::DISBAND,
+            ProposalType::UpdateApplicationMetadata => {
+                admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA
+            }
         };
 
         let votes = self
@@ -363,6 +379,7 @@ pub enum ProposalType {
     AddNode,
     RemoveNode,
     Destroy,
+    UpdateApplicationMetadata,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -488,7 +505,9 @@ impl TryFrom<&store::AdminServiceEvent> for AdminServiceEvent {
                 AdminServiceEvent::ProposalRejected((admin_proposal, requester.to_vec()))
             }
             EventType::CircuitReady => AdminServiceEvent::CircuitReady(admin_proposal),
-            EventType::CircuitDisbanded => {
+            EventType::CircuitDisbanded
+            | EventType::CircuitApplicationMetadataUpdated
+            | EventType::ProposalExpired => {
                 return Err(MarshallingError::UnsetField(
                     "Unsupported proposal type".to_string(),
                 ))
@@ -344,6 +344,7 @@ pub struct SplinterNodeBuilder {
     node_id: Option<String>,
     endpoints: Option<Vec<String>>,
     public_key: Option<Vec<u8>>,
+    relay_node_id: Option<String>,
 }
 
 impl SplinterNodeBuilder {
@@ -363,6 +364,10 @@ impl SplinterNodeBuilder {
         self.public_key.clone()
     }
 
+    pub fn relay_node_id(&self) -> Option<String> {
+        self.relay_node_id.clone()
+    }
+
     pub fn with_node_id(mut self, node_id: &str) -> SplinterNodeBuilder {
         self.node_id = Some(node_id.into());
         self
@@ -378,6 +383,11 @@ impl SplinterNodeBuilder {
         self
     }
 
+    pub fn with_relay_node_id(mut self, relay_node_id: &str) -> SplinterNodeBuilder {
+        self.relay_node_id = Some(relay_node_id.into());
+        self
+    }
+
     pub fn build(self) -> Result<SplinterNode, BuilderError> {
         let node_id = self
             .node_id
@@ -391,6 +401,7 @@ impl SplinterNodeBuilder {
             node_id,
             endpoints,
             public_key: self.public_key,
+            relay_node_id: self.relay_node_id,
         };
 
         Ok(node)
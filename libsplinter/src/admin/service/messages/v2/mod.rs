@@ -16,6 +16,7 @@ pub mod builders;
 
 use protobuf::{self, RepeatedField};
 use std::convert::TryInto;
+use std::time::UNIX_EPOCH;
 
 use crate::admin::error::MarshallingError;
 use crate::admin::store;
@@ -74,6 +75,9 @@ impl CreateCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                DurabilityType::GuaranteedDelivery
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset durability type".to_string(),
@@ -83,6 +87,7 @@ impl CreateCircuit {
 
         let routes = match proto.get_routes() {
             admin::Circuit_RouteType::ANY_ROUTE => RouteType::Any,
+            admin::Circuit_RouteType::RELAYED_ROUTE => RouteType::Relayed,
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => {
                 return Err(MarshallingError::UnsetField("Unset route type".to_string()));
             }
@@ -194,10 +199,14 @@ impl CreateCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            DurabilityType::GuaranteedDelivery => {
+                circuit.set_durability(admin::Circuit_DurabilityType::GUARANTEED_DELIVERY);
+            }
         };
 
         match self.routes {
             RouteType::Any => circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE),
+            RouteType::Relayed => circuit.set_routes(admin::Circuit_RouteType::RELAYED_ROUTE),
         };
 
         if self.circuit_version > UNSET_CIRCUIT_VERSION {
@@ -277,10 +286,14 @@ impl TryInto<admin::Circuit> for CreateCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            DurabilityType::GuaranteedDelivery => {
+                circuit.set_durability(admin::Circuit_DurabilityType::GUARANTEED_DELIVERY);
+            }
         };
 
         match self.routes {
             RouteType::Any => circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE),
+            RouteType::Relayed => circuit.set_routes(admin::Circuit_RouteType::RELAYED_ROUTE),
         };
 
         match self.circuit_status {
@@ -349,12 +362,14 @@ impl From<&store::PersistenceType> for PersistenceType {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum DurabilityType {
     NoDurability,
+    GuaranteedDelivery,
 }
 
 impl From<&store::DurabilityType> for DurabilityType {
     fn from(store_enum: &store::DurabilityType) -> Self {
         match *store_enum {
             store::DurabilityType::NoDurability => DurabilityType::NoDurability,
+            store::DurabilityType::GuaranteedDelivery => DurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -362,6 +377,7 @@ impl From<&store::DurabilityType> for DurabilityType {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum RouteType {
     Any,
+    Relayed,
 }
 
 impl Default for RouteType {
@@ -374,6 +390,7 @@ impl From<&store::RouteType> for RouteType {
     fn from(store_enum: &store::RouteType) -> Self {
         match *store_enum {
             store::RouteType::Any => RouteType::Any,
+            store::RouteType::Relayed => RouteType::Relayed,
         }
     }
 }
@@ -416,6 +433,7 @@ pub struct SplinterNode {
     pub node_id: String,
     pub endpoints: Vec<String>,
     pub public_key: Option<Vec<u8>>,
+    pub relay_node_id: Option<String>,
 }
 
 impl SplinterNode {
@@ -429,6 +447,10 @@ impl SplinterNode {
             proto.set_public_key(public_key);
         }
 
+        if let Some(relay_node_id) = self.relay_node_id {
+            proto.set_relay_node_id(relay_node_id);
+        }
+
         proto
     }
 
@@ -442,10 +464,20 @@ impl SplinterNode {
             }
         };
 
+        let relay_node_id = {
+            let relay_node_id = proto.take_relay_node_id();
+            if relay_node_id.is_empty() {
+                None
+            } else {
+                Some(relay_node_id)
+            }
+        };
+
         Ok(Self {
             node_id: proto.take_node_id(),
             endpoints: proto.take_endpoints().into(),
             public_key,
+            relay_node_id,
         })
     }
 }
@@ -516,6 +548,11 @@ pub struct CircuitProposal {
     #[serde(deserialize_with = "deserialize_hex")]
     pub requester: Vec<u8>,
     pub requester_node_id: String,
+    /// The time, in seconds since the Unix epoch, after which this node requests that the
+    /// proposal be expired. The expiry time is local to the node and is not sent to the other
+    /// members.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 impl CircuitProposal {
@@ -526,6 +563,9 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Disband,
+            admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA => {
+                ProposalType::UpdateApplicationMetadata
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset proposal type".to_string(),
@@ -547,6 +587,7 @@ impl CircuitProposal {
             votes,
             requester: proto.take_requester(),
             requester_node_id: proto.take_requester_node_id(),
+            expires_at: None,
         })
     }
 
@@ -557,6 +598,9 @@ impl CircuitProposal {
             ProposalType::AddNode => admin::CircuitProposal_ProposalType::ADD_NODE,
             ProposalType::RemoveNode => admin::CircuitProposal_ProposalType::REMOVE_NODE,
             ProposalType::Disband => admin::CircuitProposal_ProposalType::DISBAND,
+            ProposalType::UpdateApplicationMetadata => {
+                admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA
+            }
         };
 
         let votes = self
@@ -588,6 +632,9 @@ impl From<store::CircuitProposal> for CircuitProposal {
             store::ProposalType::AddNode => ProposalType::AddNode,
             store::ProposalType::RemoveNode => ProposalType::RemoveNode,
             store::ProposalType::Disband => ProposalType::Disband,
+            store::ProposalType::UpdateApplicationMetadata => {
+                ProposalType::UpdateApplicationMetadata
+            }
         };
 
         let store_circuit = store_proposal.circuit();
@@ -618,12 +665,13 @@ impl From<store::CircuitProposal> for CircuitProposal {
                         .public_key()
                         .clone()
                         .map(|public_key| public_key.into_bytes()),
+                    relay_node_id: node.relay_node_id().clone(),
                 })
                 .collect::<Vec<SplinterNode>>(),
             authorization_type: AuthorizationType::from(store_circuit.authorization_type()),
             persistence: PersistenceType::Any,
-            durability: DurabilityType::NoDurability,
-            routes: RouteType::Any,
+            durability: DurabilityType::from(store_circuit.durability()),
+            routes: RouteType::from(store_circuit.routes()),
             circuit_management_type: store_circuit.circuit_management_type().into(),
             application_metadata: store_circuit
                 .application_metadata()
@@ -658,6 +706,12 @@ impl From<store::CircuitProposal> for CircuitProposal {
                 .collect(),
             requester: store_proposal.requester().as_slice().to_vec(),
             requester_node_id: store_proposal.requester_node_id().into(),
+            expires_at: store_proposal.expires_at().map(|expires_at| {
+                expires_at
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0)
+            }),
         }
     }
 }
@@ -669,6 +723,7 @@ pub enum ProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    UpdateApplicationMetadata,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -766,6 +821,8 @@ pub enum AdminServiceEvent {
     ProposalRejected((CircuitProposal, PublicKey)),
     CircuitReady(CircuitProposal),
     CircuitDisbanded(CircuitProposal),
+    CircuitApplicationMetadataUpdated(CircuitProposal),
+    ProposalExpired(CircuitProposal),
 }
 
 impl AdminServiceEvent {
@@ -777,6 +834,8 @@ impl AdminServiceEvent {
             AdminServiceEvent::ProposalRejected((proposal, _)) => proposal,
             AdminServiceEvent::CircuitReady(proposal) => proposal,
             AdminServiceEvent::CircuitDisbanded(proposal) => proposal,
+            AdminServiceEvent::CircuitApplicationMetadataUpdated(proposal) => proposal,
+            AdminServiceEvent::ProposalExpired(proposal) => proposal,
         }
     }
 }
@@ -797,6 +856,10 @@ impl From<&store::AdminServiceEvent> for AdminServiceEvent {
             }
             EventType::CircuitReady => AdminServiceEvent::CircuitReady(admin_proposal),
             EventType::CircuitDisbanded => AdminServiceEvent::CircuitDisbanded(admin_proposal),
+            EventType::CircuitApplicationMetadataUpdated => {
+                AdminServiceEvent::CircuitApplicationMetadataUpdated(admin_proposal)
+            }
+            EventType::ProposalExpired => AdminServiceEvent::ProposalExpired(admin_proposal),
         }
     }
 }
@@ -22,6 +22,8 @@ mod subscriber;
 
 use std::any::Any;
 use std::collections::HashMap;
+#[cfg(feature = "admin-service-proposal-expiry")]
+use std::sync::mpsc::{RecvTimeoutError, Sender};
 use std::sync::{mpsc::channel, Arc, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
@@ -51,7 +53,9 @@ use crate::service::instance::{
 use self::consensus::AdminConsensusManager;
 use self::error::{AdminError, AdminSharedError, Sha256Error};
 use self::proposal_store::{AdminServiceProposals, ProposalStore};
-use self::shared::{get_peer_token_from_service_id, AdminServiceShared, PeerNodePair};
+use self::shared::{
+    get_peer_token_from_service_id, make_routing_relays, AdminServiceShared, PeerNodePair,
+};
 
 pub use self::builder::AdminServiceBuilder;
 pub use self::error::AdminKeyVerifierError;
@@ -61,6 +65,8 @@ pub use self::shared::AdminServiceStatus;
 pub use self::subscriber::AdminServiceEventSubscriber;
 
 const ADMIN_SERVICE_PROTOCOL_MIN: u32 = 1;
+#[cfg(feature = "admin-service-proposal-expiry")]
+const PROPOSAL_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 pub(crate) const ADMIN_SERVICE_PROTOCOL_VERSION: u32 = 2;
 
 pub trait AdminCommands: Send + Sync {
@@ -145,6 +151,8 @@ pub struct AdminService {
     peer_connector: PeerManagerConnector,
 
     peer_notification_run_state: Option<(usize, JoinHandle<()>)>,
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    proposal_expiry_run_state: Option<(Sender<()>, JoinHandle<()>)>,
 }
 
 impl AdminService {
@@ -196,6 +204,50 @@ impl AdminService {
         AdminServiceProposals::new(&self.admin_service_shared)
     }
 
+    /// Starts the thread that periodically removes expired proposals, if a proposal expiry has
+    /// been configured.
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    fn start_proposal_expiry(&mut self) -> Result<(), ServiceStartError> {
+        let proposal_expiry = self
+            .admin_service_shared
+            .lock()
+            .map_err(|_| {
+                ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
+            })?
+            .proposal_expiry();
+
+        if proposal_expiry.is_none() {
+            return Ok(());
+        }
+
+        let (shutdown_sender, shutdown_receiver) = channel();
+        let expiry_admin_shared = self.admin_service_shared.clone();
+
+        debug!("Starting admin service's proposal expiry thread");
+        let expiry_join_handle = thread::Builder::new()
+            .name("ProposalExpiry".into())
+            .spawn(move || loop {
+                match shutdown_receiver.recv_timeout(PROPOSAL_EXPIRY_CHECK_INTERVAL) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                if let Ok(mut admin_shared) = expiry_admin_shared.lock() {
+                    if let Err(err) = admin_shared.expire_proposals() {
+                        error!("Unable to expire circuit proposals: {}", err);
+                    }
+                } else {
+                    error!("the admin shared lock was poisoned");
+                    break;
+                }
+            })
+            .map_err(|err| ServiceStartError::Internal(err.to_string()))?;
+
+        self.proposal_expiry_run_state = Some((shutdown_sender, expiry_join_handle));
+
+        Ok(())
+    }
+
     /// On restart of a splinter node, all services that this node should run on the existing
     /// circuits should be initialized using the service orchestrator. This may not include all
     /// services if they are not supported locally. It is expected that some services will be
@@ -283,18 +335,33 @@ impl AdminService {
                 ))
             })?;
 
+            // members that are only reachable through relays are not peered with
+            let relayed_members = self
+                .admin_service_shared
+                .lock()
+                .map_err(|_| {
+                    ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?
+                .add_relayed_members(
+                    &members,
+                    make_routing_relays(&circuit),
+                    &local_required_auth,
+                );
+
             // restart all peer in the circuit
             for member in members {
                 if member.node_id != self.node_id {
-                    let peer_ref = self.peer_connector.add_peer_ref(
-                        member.token.clone(),
-                        member.endpoints.to_vec(),
-                        local_required_auth.clone(),
-                    );
-                    if let Ok(peer_ref) = peer_ref {
-                        peer_refs.push(peer_ref);
-                    } else {
-                        info!("Unable to peer with {} at this time", member.node_id);
+                    if !relayed_members.contains(&member.node_id) {
+                        let peer_ref = self.peer_connector.add_peer_ref(
+                            member.token.clone(),
+                            member.endpoints.to_vec(),
+                            local_required_auth.clone(),
+                        );
+                        if let Ok(peer_ref) = peer_ref {
+                            peer_refs.push(peer_ref);
+                        } else {
+                            info!("Unable to peer with {} at this time", member.node_id);
+                        }
                     }
 
                     token_to_peer.insert(
@@ -344,7 +411,9 @@ impl AdminService {
                             .map(|node| node.node_id().to_string())
                             .collect(),
                         circuit.authorization_type().into(),
-                    ),
+                        circuit.durability().into(),
+                    )
+                    .with_relays(make_routing_relays(&circuit)),
                     routing_members,
                 )
                 .map_err(|err| ServiceStartError::Internal(err.reduce_to_string()))?;
@@ -575,6 +644,9 @@ impl ServiceInstance for AdminService {
 
         self.peer_notification_run_state = Some((peer_subscriber_id, notification_join_handle));
 
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        self.start_proposal_expiry()?;
+
         // Setup consensus
         let consensus = AdminConsensusManager::new(
             self.service_id().into(),
@@ -671,6 +743,15 @@ impl ServiceInstance for AdminService {
             }
         }
 
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        if let Some((shutdown_sender, expiry_join_handle)) = self.proposal_expiry_run_state.take() {
+            // The thread exits once the channel is closed, so a failed send can be ignored
+            let _ = shutdown_sender.send(());
+            if let Err(err) = expiry_join_handle.join() {
+                error!("Failed to join proposal expiry thread: {:?}", err);
+            }
+        }
+
         info!("Admin service stopped and disconnected");
 
         Ok(())
@@ -724,6 +805,17 @@ impl ServiceInstance for AdminService {
                     ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
                 })?;
 
+                // A proposal to add this node to an existing circuit includes the current
+                // definition of the circuit
+                if proposed_circuit.has_circuit() {
+                    admin_service_shared
+                        .add_joining_circuit(
+                            circuit_payload,
+                            proposed_circuit.get_circuit().clone(),
+                        )
+                        .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                }
+
                 admin_service_shared.handle_proposed_circuit(
                     proposal,
                     circuit_payload.clone(),
@@ -843,6 +935,17 @@ impl ServiceInstance for AdminService {
                 );
                 Ok(())
             }
+            AdminMessage_Type::REMOVED_MEMBER => {
+                let removed_member = admin_message.get_removed_member();
+                let circuit_id = removed_member.get_circuit_id();
+                let member_node_id = removed_member.get_member_node_id();
+
+                let mut shared = self.admin_service_shared.lock().map_err(|_| {
+                    ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?;
+
+                shared.add_removed_member_notice(circuit_id, member_node_id)
+            }
             AdminMessage_Type::UNSET => Err(ServiceError::InvalidMessageFormat(Box::new(
                 AdminError::MessageTypeUnset,
             ))),
@@ -916,8 +1019,19 @@ impl AdminCommands for AdminServiceCommands {
             .lock()
             .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
             .get_events_since(since_event_id, event_type)
-            .map_err(|err| {
-                AdminServiceError::general_error_with_source("Unable to get events", Box::new(err))
+            .map_err(|err| match err {
+                #[cfg(feature = "admin-service-event-retention")]
+                AdminSharedError::EventsCompacted {
+                    since_event_id,
+                    compacted_event_id,
+                } => AdminServiceError::EventsCompacted {
+                    since_event_id,
+                    compacted_event_id,
+                },
+                err => AdminServiceError::general_error_with_source(
+                    "Unable to get events",
+                    Box::new(err),
+                ),
             })
     }
 
@@ -25,6 +25,19 @@ pub trait ProposalStore: Send + Sync + Clone {
     fn proposals(&self, filters: Vec<CircuitPredicate>)
         -> Result<ProposalIter, ProposalStoreError>;
 
+    /// Return an iterator over a page of the proposals in this store, in descending order of
+    /// circuit ID. The page starts after the proposal with the circuit ID `after`, if provided,
+    /// and contains at most `limit` proposals.
+    fn proposals_page(
+        &self,
+        filters: Vec<CircuitPredicate>,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<ProposalIter, ProposalStoreError>;
+
+    /// Return the number of proposals in this store that match the given filters.
+    fn count_proposals(&self, filters: Vec<CircuitPredicate>) -> Result<usize, ProposalStoreError>;
+
     fn proposal(&self, circuit_id: &str) -> Result<Option<CircuitProposal>, ProposalStoreError>;
 }
 
@@ -100,6 +113,35 @@ impl ProposalStore for AdminServiceProposals {
         Ok(ProposalIter::new(proposals))
     }
 
+    fn proposals_page(
+        &self,
+        filters: Vec<CircuitPredicate>,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<ProposalIter, ProposalStoreError> {
+        let proposals = self
+            .shared
+            .lock()
+            .map_err(|_| ProposalStoreError::new("Admin shared lock was lock poisoned"))?
+            .get_proposals_page(&filters, after, limit)
+            .map_err(|err| {
+                ProposalStoreError::from_source("Unable to get proposals", Box::new(err))
+            })?;
+
+        Ok(ProposalIter::new(proposals))
+    }
+
+    fn count_proposals(&self, filters: Vec<CircuitPredicate>) -> Result<usize, ProposalStoreError> {
+        self.shared
+            .lock()
+            .map_err(|_| ProposalStoreError::new("Admin shared lock was lock poisoned"))?
+            .count_proposals(&filters)
+            .map(|count| count as usize)
+            .map_err(|err| {
+                ProposalStoreError::from_source("Unable to count proposals", Box::new(err))
+            })
+    }
+
     fn proposal(&self, circuit_id: &str) -> Result<Option<CircuitProposal>, ProposalStoreError> {
         self.shared
             .lock()
@@ -12,20 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::convert::{TryFrom, TryInto};
 use std::iter::ExactSizeIterator;
 use std::sync::mpsc::Sender;
 use std::time::Instant;
+#[cfg(feature = "admin-service-proposal-expiry")]
+use std::time::{Duration, SystemTime};
 
+#[cfg(feature = "admin-service-proposal-expiry")]
+use cylinder::Signer;
 use cylinder::{PublicKey, Signature, Verifier as SignatureVerifier};
+#[cfg(feature = "admin-service-proposal-expiry")]
+use openssl::hash::{hash, MessageDigest};
 use protobuf::{Message, RepeatedField};
 
 use crate::admin::lifecycle::LifecycleDispatch;
 use crate::admin::store::{
     AdminServiceStore, Circuit as StoreCircuit, CircuitBuilder as StoreCircuitBuilder,
     CircuitPredicate, CircuitProposal as StoreProposal, CircuitStatus as StoreCircuitStatus,
-    ProposalType, ProposedCircuit, Service as StoreService, Vote, VoteRecordBuilder,
+    ProposalType, ProposedCircuit, RouteType as StoreRouteType, Service as StoreService, Vote,
+    VoteRecordBuilder,
 };
 use crate::admin::token::{PeerAuthorizationTokenReader, PeerNode};
 use crate::admin::CIRCUIT_PROTOCOL_VERSION;
@@ -36,16 +43,22 @@ use crate::hex::parse_hex;
 use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
 use crate::peer::{PeerAuthorizationToken, PeerManagerConnector, PeerRef, PeerTokenPair};
+#[cfg(feature = "admin-service-proposal-expiry")]
+use crate::protos::admin::CircuitProposalExpire;
 use crate::protos::admin::{
     AbandonedCircuit, AdminMessage, AdminMessage_Type, Circuit, CircuitManagementPayload,
     CircuitManagementPayload_Action, CircuitManagementPayload_Header, CircuitProposal,
     CircuitProposalVote, CircuitProposalVote_Vote, CircuitProposal_ProposalType,
-    Circuit_AuthorizationType, Circuit_CircuitStatus, Circuit_DurabilityType,
-    Circuit_PersistenceType, Circuit_RouteType, MemberReady, RemovedProposal,
-    ServiceProtocolVersionRequest, SplinterNode, SplinterService,
+    CircuitUpdateAddNodeRequest, CircuitUpdateApplicationMetadataRequest,
+    CircuitUpdateRemoveNodeRequest, CircuitUpdateRosterRequest, Circuit_AuthorizationType,
+    Circuit_CircuitStatus, Circuit_DurabilityType, Circuit_PersistenceType, Circuit_RouteType,
+    MemberReady, RemovedMember, RemovedProposal, ServiceProtocolVersionRequest, SplinterNode,
+    SplinterService,
 };
 use crate::public_key;
-use crate::service::instance::{ServiceArgValidator, ServiceError, ServiceNetworkSender};
+use crate::service::instance::{
+    ServiceArgValidator, ServiceError, ServiceNetworkSender, ServiceSendError,
+};
 
 use super::error::{AdminSharedError, MarshallingError};
 use super::messages;
@@ -100,6 +113,9 @@ struct CircuitProposalContext {
 struct UninitializedCircuit {
     pub circuit: Option<CircuitProposal>,
     pub ready_members: HashSet<String>,
+    // the roster of the circuit before it was updated, only set for proposals that update an
+    // existing circuit
+    pub previous_roster: Option<Vec<StoreService>>,
 }
 
 pub struct AdminServiceShared {
@@ -153,6 +169,19 @@ pub struct AdminServiceShared {
     // Temporarily hold on to peers that should be removed. This helps avoid dropping messages
     // when removing a proposal.
     peers_to_be_removed: Vec<(Instant, Vec<PeerTokenPair>)>,
+    // the current definitions of the circuits this node has been proposed to join, by circuit id
+    joining_circuits: HashMap<String, Circuit>,
+    // the members that have reported removing this node from a circuit, by circuit id
+    removed_member_notices: HashMap<String, HashSet<String>>,
+    // the nodes that messages must be relayed through to reach members of relayed circuits that
+    // are not direct neighbours of this node, by member node id
+    relay_routes: HashMap<String, Vec<String>>,
+    // how long a proposal may remain pending on this node before it is expired
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    proposal_expiry: Option<Duration>,
+    // the signer used to sign the expiry requests submitted by this node
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    expiry_signer: Option<Box<dyn Signer>>,
 }
 
 impl AdminServiceShared {
@@ -197,6 +226,13 @@ impl AdminServiceShared {
             public_keys,
             token_to_peer: HashMap::new(),
             peers_to_be_removed: Vec::new(),
+            joining_circuits: HashMap::new(),
+            removed_member_notices: HashMap::new(),
+            relay_routes: HashMap::new(),
+            #[cfg(feature = "admin-service-proposal-expiry")]
+            proposal_expiry: None,
+            #[cfg(feature = "admin-service-proposal-expiry")]
+            expiry_signer: None,
         }
     }
 
@@ -235,6 +271,79 @@ impl AdminServiceShared {
         &self.network_sender
     }
 
+    /// Sends the message to the admin service of the given peer. Members of relayed circuits that
+    /// are not direct neighbours of this node are sent the message through their relays.
+    pub fn send_to_admin_service(
+        &self,
+        token: &PeerTokenPair,
+        message: &[u8],
+    ) -> Result<(), ServiceSendError> {
+        if let Some(ref network_sender) = self.network_sender {
+            let recipient = admin_service_id(&token.id_as_string());
+            match self
+                .token_to_peer
+                .get(token)
+                .and_then(|peer_node_pair| self.relay_routes.get(&peer_node_pair.peer_node.node_id))
+            {
+                Some(relay_route) => {
+                    network_sender.send_with_relay_route(&recipient, message, relay_route)?
+                }
+                None => network_sender.send(&recipient, message)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the relay routes to the members of a circuit that are not direct neighbours of this
+    /// node, and returns the node IDs of those members.
+    ///
+    /// These members are not peered with; their messages are passed along by the relays in
+    /// between. As a protocol version cannot be agreed on without a connection, they are assumed
+    /// to support the current admin service protocol.
+    pub fn add_relayed_members(
+        &mut self,
+        members: &[PeerNode],
+        relays: BTreeMap<String, String>,
+        local_required_auth: &PeerAuthorizationToken,
+    ) -> HashSet<String> {
+        let mut relayed_members = HashSet::new();
+        if relays.is_empty() {
+            return relayed_members;
+        }
+
+        let routing_circuit = routing::Circuit::new(
+            String::new(),
+            vec![],
+            members.iter().map(|node| node.node_id.clone()).collect(),
+            routing::AuthorizationType::Trust,
+            routing::DurabilityType::NoDurability,
+        )
+        .with_relays(relays);
+
+        for node in members {
+            if node.node_id == self.node_id {
+                continue;
+            }
+
+            let route = routing_circuit.route(&self.node_id, &node.node_id);
+            if route.len() <= 2 {
+                // the member is a direct neighbour
+                continue;
+            }
+
+            self.relay_routes
+                .insert(node.node_id.clone(), route[1..route.len() - 1].to_vec());
+            self.service_protocols.insert(
+                PeerTokenPair::new(node.token.clone(), local_required_auth.clone()),
+                ADMIN_SERVICE_PROTOCOL_VERSION,
+            );
+            relayed_members.insert(node.node_id.clone());
+        }
+
+        relayed_members
+    }
+
     pub fn lifecycle_dispatch(&self) -> &[Box<dyn LifecycleDispatch>] {
         &self.lifecycle_dispatch
     }
@@ -247,6 +356,25 @@ impl AdminServiceShared {
         self.proposal_sender = proposal_sender;
     }
 
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    pub fn proposal_expiry(&self) -> Option<Duration> {
+        self.proposal_expiry
+    }
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    pub fn set_proposal_expiry(&mut self, proposal_expiry: Option<(Duration, Box<dyn Signer>)>) {
+        match proposal_expiry {
+            Some((proposal_expiry, signer)) => {
+                self.proposal_expiry = Some(proposal_expiry);
+                self.expiry_signer = Some(signer);
+            }
+            None => {
+                self.proposal_expiry = None;
+                self.expiry_signer = None;
+            }
+        }
+    }
+
     pub fn pop_pending_circuit_payload(&mut self) -> Option<CircuitManagementPayload> {
         self.pending_circuit_payloads.pop_front()
     }
@@ -358,6 +486,12 @@ impl AdminServiceShared {
                     .circuit_management_type
                     .clone();
 
+                // An expired proposal is removed regardless of the votes it has received
+                #[cfg(feature = "admin-service-proposal-expiry")]
+                if action == CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_EXPIRE {
+                    return self.commit_proposal_expiry(&circuit_proposal);
+                }
+
                 match self.check_approved(&circuit_proposal) {
                     CircuitProposalStatus::Accepted => {
                         let status = circuit_proposal.get_circuit_proposal().get_circuit_status();
@@ -366,8 +500,23 @@ impl AdminServiceShared {
                         // a disband request. Otherwise, the admin service should continue with
                         // committing a new circuit proposal. For 0.4 compatibility, this is the
                         // default action as these proposals will not have the `circuit_status`
-                        // field set.
-                        if status == Circuit_CircuitStatus::DISBANDED {
+                        // field set. Proposals that update an existing circuit are committed
+                        // separately.
+                        if circuit_proposal.get_proposal_type()
+                            == CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA
+                        {
+                            // Updating the application metadata does not change the circuit's
+                            // services, so there is no further service handling required
+                            return self.commit_application_metadata_update(
+                                &circuit_proposal,
+                                circuit_proposal_context.signer_public_key,
+                            );
+                        } else if is_circuit_update(circuit_proposal.get_proposal_type()) {
+                            self.commit_circuit_update(
+                                &circuit_proposal,
+                                circuit_proposal_context.signer_public_key,
+                            )?;
+                        } else if status == Circuit_CircuitStatus::DISBANDED {
                             let store_circuit =
                                 StoreCircuit::try_from(circuit_proposal.get_circuit_proposal())
                                     .map_err(|err| {
@@ -407,36 +556,8 @@ impl AdminServiceShared {
                                 circuit_proposal_context.signer_public_key,
                             ));
                             self.send_event(&mgmt_type, event);
-                            // send MEMBER_READY message to all other members' admin
-                            // services
-                            if let Some(ref network_sender) = self.network_sender {
-                                let mut member_ready = MemberReady::new();
-                                member_ready.set_circuit_id(circuit_id.to_string());
-                                member_ready.set_member_node_id(self.node_id.clone());
-                                let mut msg = AdminMessage::new();
-                                msg.set_message_type(AdminMessage_Type::MEMBER_READY);
-                                msg.set_member_ready(member_ready);
-
-                                let envelope_bytes =
-                                    msg.write_to_bytes().map_err(MarshallingError::from)?;
-                                for token in store_circuit
-                                    .list_tokens(&self.node_id)
-                                    .map_err(|_| {
-                                        AdminSharedError::SplinterStateError(format!(
-                                            "Unable to get member peer tokens from {}",
-                                            circuit_id
-                                        ))
-                                    })?
-                                    .iter()
-                                {
-                                    if !self.is_local_node(token.peer_id()) {
-                                        network_sender.send(
-                                            &admin_service_id(&token.id_as_string()),
-                                            &envelope_bytes,
-                                        )?;
-                                    }
-                                }
-                            }
+                            // send MEMBER_READY message to all other members' admin services
+                            self.send_member_ready(&store_circuit)?;
                         } else {
                             // commit new circuit
                             self.admin_store.upgrade_proposal_to_circuit(circuit_id)?;
@@ -451,46 +572,9 @@ impl AdminServiceShared {
                                     ))
                                 })?;
 
-                            let routing_circuit = routing::Circuit::new(
-                                circuit.circuit_id().to_string(),
-                                circuit
-                                    .roster()
-                                    .iter()
-                                    .map(|service| {
-                                        routing::Service::new(
-                                            service.service_id().to_string(),
-                                            service.service_type().to_string(),
-                                            service.node_id().to_string(),
-                                            service.arguments().to_vec(),
-                                        )
-                                    })
-                                    .collect(),
-                                circuit
-                                    .members()
-                                    .iter()
-                                    .map(|node| node.node_id().to_string())
-                                    .collect(),
-                                circuit.authorization_type().into(),
-                            );
-
-                            let routing_members = circuit_proposal
-                                .get_circuit_proposal()
-                                .get_members()
-                                .iter()
-                                .map(|node| {
-                                    routing::CircuitNode::new(
-                                        node.get_node_id().to_string(),
-                                        node.get_endpoints().to_vec(),
-                                        if node.get_public_key().is_empty() {
-                                            None
-                                        } else {
-                                            Some(public_key::PublicKey::from_bytes(
-                                                node.get_public_key().to_vec(),
-                                            ))
-                                        },
-                                    )
-                                })
-                                .collect::<Vec<routing::CircuitNode>>();
+                            let routing_circuit = make_routing_circuit(&circuit);
+                            let routing_members =
+                                make_routing_members(circuit_proposal.get_circuit_proposal());
 
                             self.routing_table_writer
                                 .add_circuit(
@@ -516,35 +600,7 @@ impl AdminServiceShared {
                             self.send_event(&mgmt_type, event);
 
                             // send MEMBER_READY message to all other members' admin services
-                            if let Some(ref network_sender) = self.network_sender {
-                                let mut member_ready = MemberReady::new();
-                                member_ready.set_circuit_id(circuit_id.to_string());
-                                member_ready.set_member_node_id(self.node_id.clone());
-                                let mut msg = AdminMessage::new();
-                                msg.set_message_type(AdminMessage_Type::MEMBER_READY);
-                                msg.set_member_ready(member_ready);
-
-                                let envelope_bytes =
-                                    msg.write_to_bytes().map_err(MarshallingError::from)?;
-
-                                for token in circuit
-                                    .list_tokens(&self.node_id)
-                                    .map_err(|_| {
-                                        AdminSharedError::SplinterStateError(format!(
-                                            "Unable to get member peer tokens from {}",
-                                            circuit.circuit_id()
-                                        ))
-                                    })?
-                                    .iter()
-                                {
-                                    if !self.is_local_node(token.peer_id()) {
-                                        network_sender.send(
-                                            &admin_service_id(&token.id_as_string()),
-                                            &envelope_bytes,
-                                        )?;
-                                    }
-                                }
-                            }
+                            self.send_member_ready(&circuit)?;
                         }
                         // add circuit as pending further service handling
                         self.add_uninitialized_circuit(circuit_proposal.clone())?;
@@ -607,6 +663,78 @@ impl AdminServiceShared {
                                 );
                                 Ok(())
                             }
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+                                self.add_proposal(circuit_proposal.clone())?;
+                                self.update_metrics()?;
+                                // notify registered application authorization handlers of the
+                                // committed update roster circuit proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for new circuit proposal to update the \
+                                     roster of circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE => {
+                                self.add_proposal(circuit_proposal.clone())?;
+                                self.update_metrics()?;
+                                // notify registered application authorization handlers of the
+                                // committed add node circuit proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for new circuit proposal to add a node to \
+                                     circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+                                self.add_proposal(circuit_proposal.clone())?;
+                                self.update_metrics()?;
+                                // notify registered application authorization handlers of the
+                                // committed remove node circuit proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for new circuit proposal to remove a node \
+                                     from circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => {
+                                self.add_proposal(circuit_proposal.clone())?;
+                                self.update_metrics()?;
+                                // notify registered application authorization handlers of the
+                                // committed update application metadata circuit proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for new circuit proposal to update the \
+                                     application metadata of circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
                             _ => Err(AdminSharedError::UnknownAction(format!(
                                 "Received unknown action: {:?}",
                                 action
@@ -618,20 +746,38 @@ impl AdminServiceShared {
                         let proposal = self.remove_proposal(circuit_id)?;
                         self.update_metrics()?;
                         if let Some(proposal) = proposal {
-                            self.peers_to_be_removed.push((
-                                Instant::now(),
-                                proposal
-                                    .circuit()
-                                    .list_tokens(&self.node_id)
-                                    .map_err(|err| {
-                                        AdminSharedError::SplinterStateError(format!(
-                                            "Unable to remove peer refs for proposal {}: {}",
-                                            proposal.circuit_id(),
-                                            err
-                                        ))
-                                    })?,
-                            ));
+                            match proposal.proposal_type() {
+                                // Proposals to update a circuit use the peer refs of the existing
+                                // circuit, so these must not be removed
+                                ProposalType::UpdateRoster
+                                | ProposalType::RemoveNode
+                                | ProposalType::UpdateApplicationMetadata => (),
+                                // Only the peer refs added for the node being added to the
+                                // circuit are removed
+                                ProposalType::AddNode => {
+                                    let peers = self.get_add_node_peer_tokens(
+                                        circuit_proposal.get_circuit_proposal(),
+                                    )?;
+                                    self.peers_to_be_removed.push((Instant::now(), peers));
+                                }
+                                _ => {
+                                    self.peers_to_be_removed.push((
+                                        Instant::now(),
+                                        proposal.circuit().list_tokens(&self.node_id).map_err(
+                                            |err| {
+                                                AdminSharedError::SplinterStateError(format!(
+                                                    "Unable to remove peer refs for proposal \
+                                                     {}: {}",
+                                                    proposal.circuit_id(),
+                                                    err
+                                                ))
+                                            },
+                                        )?,
+                                    ));
+                                }
+                            }
                         }
+                        self.joining_circuits.remove(circuit_id);
                         let circuit_proposal_proto =
                             messages::CircuitProposal::from_proto(circuit_proposal.clone())
                                 .map_err(AdminSharedError::InvalidMessageFormat)?;
@@ -662,131 +808,452 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    pub fn propose_change(
+    /// Commits an accepted proposal to update an existing circuit. The circuit is updated in the
+    /// admin store and the routing table, and the circuit's previous roster is held on to until
+    /// all members are ready, at which point the services added to or removed from the circuit
+    /// are started or stopped.
+    ///
+    /// If this node is the node being added to the circuit by the proposal, the circuit does not
+    /// exist in the admin store yet, so the proposal is upgraded to a circuit instead. If a node
+    /// was removed from the circuit, the node is removed from the routing table, unless it is a
+    /// member of another circuit, and the peer ref held for the node is released.
+    fn commit_circuit_update(
         &mut self,
-        mut circuit_payload: CircuitManagementPayload,
-    ) -> Result<(String, CircuitProposal), AdminSharedError> {
-        self.cleanup_held_peer_refs();
-        let header = Message::parse_from_bytes(circuit_payload.get_header())
-            .map_err(MarshallingError::from)?;
-        self.validate_circuit_management_payload(&circuit_payload, &header)?;
-        self.verify_signature(&circuit_payload).map_err(|_| {
-            AdminSharedError::ValidationFailed(String::from("Unable to verify signature"))
-        })?;
-        match header.get_action() {
-            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
-                let mut create_request = circuit_payload.take_circuit_create_request();
-                let proposed_circuit = create_request.take_circuit();
-                let mut verifiers = vec![];
-                let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+        circuit_proposal: &CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id();
+        let mgmt_type = circuit_proposal
+            .get_circuit_proposal()
+            .get_circuit_management_type()
+            .to_string();
 
-                let local_required_auth = proposed_circuit
-                    .get_node_token(&self.node_id)
+        let previous_circuit = match self.admin_store.get_circuit(circuit_id)? {
+            Some(previous_circuit) => {
+                let store_circuit = StoreCircuit::try_from(circuit_proposal.get_circuit_proposal())
                     .map_err(|err| {
-                        AdminSharedError::ValidationFailed(format!(
-                            "Unable to get local nodes token: {}",
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to convert proto Circuit to store Circuit: {}",
                             err
                         ))
-                    })?
-                    .ok_or_else(|| {
-                        AdminSharedError::ValidationFailed(
-                            "Circuit does not have the local node".to_string(),
-                        )
                     })?;
 
-                for member in proposed_circuit.list_nodes().map_err(|_| {
-                    AdminSharedError::SplinterStateError(format!(
-                        "Unable to get tokens for proposal: {}",
-                        proposed_circuit.get_circuit_id()
-                    ))
-                })? {
-                    verifiers.push(member.admin_service.clone());
-                    // Figure out what protocol version should be used for this proposal
-                    if let Some(protocol_version) = self.service_protocols.get(&PeerTokenPair::new(
-                        member.token.clone(),
-                        local_required_auth.clone(),
-                    )) {
-                        if protocol_version < &protocol {
-                            protocol = *protocol_version
-                        }
-                    }
-                }
+                // Updating the existing circuit in the admin store and then removing the
+                // corresponding `CircuitProposal`
+                self.admin_store
+                    .update_circuit(store_circuit)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to update circuit {}",
+                            circuit_id
+                        ))
+                    })
+                    .and_then(|_| self.remove_proposal(circuit_id))?;
 
-                let signer_public_key = header.get_requester();
-                let requester_node_id = header.get_requester_node_id();
+                Some(previous_circuit)
+            }
+            None if circuit_proposal.get_proposal_type()
+                == CircuitProposal_ProposalType::ADD_NODE =>
+            {
+                // This node is being added to the circuit, so none of the circuit's services
+                // were previously running on this node
+                self.admin_store.upgrade_proposal_to_circuit(circuit_id)?;
+                None
+            }
+            None => {
+                return Err(AdminSharedError::SplinterStateError(format!(
+                    "Unable to get circuit being updated: {}",
+                    circuit_id
+                )))
+            }
+        };
+        self.joining_circuits.remove(circuit_id);
 
-                self.validate_create_circuit(
-                    &proposed_circuit,
-                    signer_public_key,
-                    requester_node_id,
-                    protocol,
-                )
-                .map_err(|err| {
-                    match proposed_circuit.list_tokens(&self.node_id) {
-                        Ok(tokens) => self.remove_peer_refs(tokens),
-                        Err(err) => {
-                            error!(
-                                "Unable to remove peer refs for proposal {}: {}",
-                                proposed_circuit.get_circuit_id(),
-                                err
-                            );
-                        }
-                    };
+        self.update_metrics()?;
 
-                    err
-                })?;
-                debug!("proposing {}", proposed_circuit.get_circuit_id());
+        let store_circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get circuit that was just updated: {}",
+                circuit_id
+            ))
+        })?;
 
-                let mut circuit_proposal = CircuitProposal::new();
-                circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::CREATE);
-                circuit_proposal.set_circuit_id(proposed_circuit.get_circuit_id().into());
-                circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
-                circuit_proposal.set_circuit_proposal(proposed_circuit.clone());
-                circuit_proposal.set_requester(header.get_requester().to_vec());
-                circuit_proposal.set_requester_node_id(header.get_requester_node_id().to_string());
+        let previous_roster = previous_circuit
+            .as_ref()
+            .map(|previous_circuit| previous_circuit.roster().to_vec())
+            .unwrap_or_default();
 
-                let expected_hash = sha256(&circuit_proposal)?;
-                self.pending_changes = Some(CircuitProposalContext {
-                    circuit_proposal: circuit_proposal.clone(),
-                    signer_public_key: header.get_requester().to_vec(),
-                    action: CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST,
-                });
-                self.current_consensus_verifiers =
-                    proposed_circuit.list_tokens(&self.node_id).map_err(|_| {
+        // Adding the circuit to the routing table only adds or replaces the circuit's current
+        // services, so services that are no longer in the roster must be removed first
+        for service in previous_roster.iter() {
+            if !store_circuit
+                .roster()
+                .iter()
+                .any(|updated| updated.service_id() == service.service_id())
+            {
+                self.routing_table_writer
+                    .remove_service(&routing::ServiceId::new(
+                        circuit_id.to_string(),
+                        service.service_id().to_string(),
+                    ))
+                    .map_err(|_| {
                         AdminSharedError::SplinterStateError(format!(
-                            "Unable to get tokens for proposal: {}",
-                            proposed_circuit.get_circuit_id()
+                            "Unable to remove service {} from routing table: {}",
+                            service.service_id(),
+                            circuit_id
                         ))
                     })?;
-
-                Ok((expected_hash, circuit_proposal))
             }
-            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
-                let proposal_vote = circuit_payload.get_circuit_proposal_vote();
-                // validate vote proposal
-                // check that the circuit proposal exists
-                let mut circuit_proposal = self
-                    .get_proposal(proposal_vote.get_circuit_id())
-                    .map_err(|err| {
-                        AdminSharedError::ValidationFailed(format!(
-                            "error occurred when trying to get proposal {}",
-                            err
-                        ))
-                    })?
-                    .ok_or_else(|| {
-                        AdminSharedError::ValidationFailed(format!(
-                            "Received vote for a proposal that does not exist: circuit id {}",
-                            proposal_vote.circuit_id
-                        ))
-                    })?;
-
-                let mut verifiers = vec![];
-                for member in circuit_proposal.circuit().members() {
-                    verifiers.push(admin_service_id(member.node_id()));
-                }
-                let signer_public_key = header.get_requester();
+        }
 
-                self.validate_circuit_vote(
+        self.routing_table_writer
+            .add_circuit(
+                circuit_id.to_string(),
+                make_routing_circuit(&store_circuit),
+                make_routing_members(circuit_proposal.get_circuit_proposal()),
+            )
+            .map_err(|_| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to update circuit in routing table: {}",
+                    circuit_id
+                ))
+            })?;
+
+        if let Some(previous_circuit) = previous_circuit.as_ref() {
+            self.remove_circuit_members(previous_circuit, &store_circuit)?;
+        }
+
+        // send message about circuit update proposal being accepted
+        let circuit_proposal_proto =
+            messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                .map_err(AdminSharedError::InvalidMessageFormat)?;
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            circuit_proposal_proto,
+            signer_public_key,
+        ));
+        self.send_event(&mgmt_type, event);
+
+        self.send_member_ready(&store_circuit)?;
+
+        // Hold on to the previous roster, so the changed services can be started or stopped once
+        // all members are ready
+        self.uninitialized_circuits
+            .entry(circuit_id.to_string())
+            .or_insert_with(|| UninitializedCircuit {
+                circuit: None,
+                ready_members: HashSet::new(),
+                previous_roster: None,
+            })
+            .previous_roster = Some(previous_roster);
+
+        Ok(())
+    }
+
+    /// Commits an accepted proposal to update the application metadata of an existing circuit.
+    /// The circuit is updated in the admin store, and the `CircuitApplicationMetadataUpdated`
+    /// event is sent to the circuit's application authorization handlers.
+    fn commit_application_metadata_update(
+        &mut self,
+        circuit_proposal: &CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id();
+        let mgmt_type = circuit_proposal
+            .get_circuit_proposal()
+            .get_circuit_management_type()
+            .to_string();
+
+        let store_circuit = StoreCircuit::try_from(circuit_proposal.get_circuit_proposal())
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to convert proto Circuit to store Circuit: {}",
+                    err
+                ))
+            })?;
+
+        // Updating the existing circuit in the admin store and then removing the corresponding
+        // `CircuitProposal`
+        self.admin_store
+            .update_circuit(store_circuit)
+            .map_err(|_| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to update circuit {}",
+                    circuit_id
+                ))
+            })
+            .and_then(|_| self.remove_proposal(circuit_id))?;
+
+        self.update_metrics()?;
+
+        // send message about the circuit update proposal being accepted, followed by the message
+        // that the circuit's application metadata has been updated
+        let circuit_proposal_proto =
+            messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                .map_err(AdminSharedError::InvalidMessageFormat)?;
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            circuit_proposal_proto.clone(),
+            signer_public_key,
+        ));
+        self.send_event(&mgmt_type, event);
+
+        let event =
+            messages::AdminServiceEvent::CircuitApplicationMetadataUpdated(circuit_proposal_proto);
+        self.send_event(&mgmt_type, event);
+
+        info!(
+            "application metadata of circuit {} has been updated",
+            circuit_id
+        );
+        Ok(())
+    }
+
+    /// Removes the members that are no longer part of an updated circuit from the routing table,
+    /// unless they are a member of another circuit, and releases the peer refs held for them.
+    fn remove_circuit_members(
+        &mut self,
+        previous_circuit: &StoreCircuit,
+        circuit: &StoreCircuit,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit.circuit_id();
+        for member in previous_circuit.members() {
+            if circuit
+                .members()
+                .iter()
+                .any(|current| current.node_id() == member.node_id())
+            {
+                continue;
+            }
+
+            // The node may still be a member of another circuit, in which case it must stay in
+            // the routing table
+            let predicates = [
+                CircuitPredicate::MembersInclude(vec![member.node_id().to_string()]),
+                CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active),
+            ];
+            if self.admin_store.count_circuits(&predicates)? == 0 {
+                self.routing_table_writer
+                    .remove_node(member.node_id())
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to remove node {} from routing table: {}",
+                            member.node_id(),
+                            circuit_id
+                        ))
+                    })?;
+            }
+        }
+
+        let current_peers = circuit.list_tokens(&self.node_id).map_err(|err| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get peer tokens for circuit {}: {}",
+                circuit_id, err
+            ))
+        })?;
+        let removed_peers = previous_circuit
+            .list_tokens(&self.node_id)
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get peer tokens for circuit {}: {}",
+                    circuit_id, err
+                ))
+            })?
+            .into_iter()
+            .filter(|peer| !current_peers.contains(peer))
+            .collect::<Vec<PeerTokenPair>>();
+
+        self.send_removed_member(circuit_id, &removed_peers)?;
+
+        // The peer refs are held on to for a short time, so the final messages for the proposal
+        // can still be delivered to the removed members
+        if !removed_peers.is_empty() {
+            self.peers_to_be_removed
+                .push((Instant::now(), removed_peers));
+        }
+
+        Ok(())
+    }
+
+    /// Sends a REMOVED_MEMBER message to the admin services of the nodes that were removed from
+    /// the circuit
+    fn send_removed_member(
+        &self,
+        circuit_id: &str,
+        removed_peers: &[PeerTokenPair],
+    ) -> Result<(), AdminSharedError> {
+        if self.network_sender.is_some() {
+            let mut removed_member = RemovedMember::new();
+            removed_member.set_circuit_id(circuit_id.to_string());
+            removed_member.set_member_node_id(self.node_id.clone());
+            let mut msg = AdminMessage::new();
+            msg.set_message_type(AdminMessage_Type::REMOVED_MEMBER);
+            msg.set_removed_member(removed_member);
+
+            let envelope_bytes = msg.write_to_bytes().map_err(MarshallingError::from)?;
+
+            for token in removed_peers {
+                self.send_to_admin_service(token, &envelope_bytes)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends a MEMBER_READY message to the admin services of all other members of the circuit
+    fn send_member_ready(&self, circuit: &StoreCircuit) -> Result<(), AdminSharedError> {
+        if self.network_sender.is_some() {
+            let mut member_ready = MemberReady::new();
+            member_ready.set_circuit_id(circuit.circuit_id().to_string());
+            member_ready.set_member_node_id(self.node_id.clone());
+            let mut msg = AdminMessage::new();
+            msg.set_message_type(AdminMessage_Type::MEMBER_READY);
+            msg.set_member_ready(member_ready);
+
+            let envelope_bytes = msg.write_to_bytes().map_err(MarshallingError::from)?;
+
+            for token in circuit
+                .list_tokens(&self.node_id)
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to get member peer tokens from {}",
+                        circuit.circuit_id()
+                    ))
+                })?
+                .iter()
+            {
+                if !self.is_local_node(token.peer_id()) {
+                    self.send_to_admin_service(token, &envelope_bytes)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn propose_change(
+        &mut self,
+        mut circuit_payload: CircuitManagementPayload,
+    ) -> Result<(String, CircuitProposal), AdminSharedError> {
+        self.cleanup_held_peer_refs();
+        let header = Message::parse_from_bytes(circuit_payload.get_header())
+            .map_err(MarshallingError::from)?;
+        self.validate_circuit_management_payload(&circuit_payload, &header)?;
+        self.verify_signature(&circuit_payload).map_err(|_| {
+            AdminSharedError::ValidationFailed(String::from("Unable to verify signature"))
+        })?;
+        match header.get_action() {
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
+                let mut create_request = circuit_payload.take_circuit_create_request();
+                let proposed_circuit = create_request.take_circuit();
+                let mut verifiers = vec![];
+                let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+
+                let local_required_auth = proposed_circuit
+                    .get_node_token(&self.node_id)
+                    .map_err(|err| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Unable to get local nodes token: {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(
+                            "Circuit does not have the local node".to_string(),
+                        )
+                    })?;
+
+                for member in proposed_circuit.list_nodes().map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to get tokens for proposal: {}",
+                        proposed_circuit.get_circuit_id()
+                    ))
+                })? {
+                    verifiers.push(member.admin_service.clone());
+                    // Figure out what protocol version should be used for this proposal
+                    if let Some(protocol_version) = self.service_protocols.get(&PeerTokenPair::new(
+                        member.token.clone(),
+                        local_required_auth.clone(),
+                    )) {
+                        if protocol_version < &protocol {
+                            protocol = *protocol_version
+                        }
+                    }
+                }
+
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                self.validate_create_circuit(
+                    &proposed_circuit,
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )
+                .map_err(|err| {
+                    match proposed_circuit.list_tokens(&self.node_id) {
+                        Ok(tokens) => self.remove_peer_refs(tokens),
+                        Err(err) => {
+                            error!(
+                                "Unable to remove peer refs for proposal {}: {}",
+                                proposed_circuit.get_circuit_id(),
+                                err
+                            );
+                        }
+                    };
+
+                    err
+                })?;
+                debug!("proposing {}", proposed_circuit.get_circuit_id());
+
+                let mut circuit_proposal = CircuitProposal::new();
+                circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::CREATE);
+                circuit_proposal.set_circuit_id(proposed_circuit.get_circuit_id().into());
+                circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+                circuit_proposal.set_circuit_proposal(proposed_circuit.clone());
+                circuit_proposal.set_requester(header.get_requester().to_vec());
+                circuit_proposal.set_requester_node_id(header.get_requester_node_id().to_string());
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action: CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST,
+                });
+                self.current_consensus_verifiers =
+                    proposed_circuit.list_tokens(&self.node_id).map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            proposed_circuit.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
+                let proposal_vote = circuit_payload.get_circuit_proposal_vote();
+                // validate vote proposal
+                // check that the circuit proposal exists
+                let mut circuit_proposal = self
+                    .get_proposal(proposal_vote.get_circuit_id())
+                    .map_err(|err| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "error occurred when trying to get proposal {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Received vote for a proposal that does not exist: circuit id {}",
+                            proposal_vote.circuit_id
+                        ))
+                    })?;
+
+                let mut verifiers = vec![];
+                for member in circuit_proposal.circuit().members() {
+                    verifiers.push(admin_service_id(member.node_id()));
+                }
+                let signer_public_key = header.get_requester();
+
+                self.validate_circuit_vote(
                     proposal_vote,
                     signer_public_key,
                     &circuit_proposal,
@@ -961,31 +1428,373 @@ impl AdminServiceShared {
 
                 Ok((expected_hash, circuit_proposal))
             }
-            CircuitManagementPayload_Action::ACTION_UNSET => Err(
-                AdminSharedError::ValidationFailed("Action must be set".to_string()),
-            ),
-            unknown_action => Err(AdminSharedError::ValidationFailed(format!(
-                "Unable to handle {:?}",
-                unknown_action
-            ))),
-        }
-    }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+                debug!("Circuit update roster request being processed");
+                let update_roster_request = circuit_payload.get_circuit_update_roster_request();
 
-    pub fn has_proposal(&self, circuit_id: &str) -> Result<bool, AdminSharedError> {
-        Ok(self.admin_store.get_proposal(circuit_id)?.is_some())
-    }
+                // Creating the proposal to update the roster of this circuit
+                let circuit_proposal = self.make_update_roster_circuit_proposal(
+                    update_roster_request,
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                )?;
 
-    /// Propose a new circuit
-    ///
-    /// This operation will propose a new circuit to all the member nodes of the circuit.  If there
-    /// is no peer connection, a connection to the peer will also be established.
-    pub fn propose_circuit(
-        &mut self,
-        payload: CircuitManagementPayload,
-        message_sender: String,
-    ) -> Result<(), ServiceError> {
-        debug!(
-            "received circuit proposal for {}",
+                let protocol = self.get_agreed_protocol(circuit_proposal.get_circuit_proposal())?;
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                self.validate_update_roster(
+                    update_roster_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )?;
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action: CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST,
+                });
+                self.current_consensus_verifiers = circuit_proposal
+                    .get_circuit_proposal()
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE => {
+                debug!("Circuit add node request being processed");
+                let add_node_request = circuit_payload.get_circuit_update_add_node();
+
+                // Creating the proposal to add the node to this circuit
+                let circuit_proposal = self.make_add_node_circuit_proposal(
+                    add_node_request,
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                )?;
+
+                let protocol = self.get_agreed_protocol(circuit_proposal.get_circuit_proposal())?;
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                self.validate_add_node(
+                    add_node_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )
+                .map_err(|err| {
+                    match self.get_add_node_peer_tokens(circuit_proposal.get_circuit_proposal()) {
+                        Ok(tokens) => self.remove_peer_refs(tokens),
+                        Err(err) => {
+                            error!(
+                                "Unable to remove peer refs for proposal {}: {}",
+                                circuit_proposal.get_circuit_id(),
+                                err
+                            );
+                        }
+                    };
+
+                    err
+                })?;
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action: CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE,
+                });
+                self.current_consensus_verifiers = circuit_proposal
+                    .get_circuit_proposal()
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+                debug!("Circuit remove node request being processed");
+                let remove_node_request = circuit_payload.get_circuit_update_remove_node();
+
+                // Creating the proposal to remove the node from this circuit
+                let circuit_proposal = self.make_remove_node_circuit_proposal(
+                    remove_node_request,
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                )?;
+
+                let protocol = self.get_agreed_protocol(circuit_proposal.get_circuit_proposal())?;
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                self.validate_remove_node(
+                    remove_node_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )?;
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action: CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE,
+                });
+                // The node being removed does not take part in the proposal, so only the
+                // remaining members of the circuit are verifiers
+                self.current_consensus_verifiers = circuit_proposal
+                    .get_circuit_proposal()
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => {
+                debug!("Circuit update application metadata request being processed");
+                let update_metadata_request =
+                    circuit_payload.get_circuit_update_application_metadata_request();
+
+                // Creating the proposal to update the application metadata of this circuit
+                let circuit_proposal = self.make_update_application_metadata_circuit_proposal(
+                    update_metadata_request,
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                )?;
+
+                let protocol = self.get_agreed_protocol(circuit_proposal.get_circuit_proposal())?;
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                self.validate_update_application_metadata(
+                    update_metadata_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )?;
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action:
+                        CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST,
+                });
+                self.current_consensus_verifiers = circuit_proposal
+                    .get_circuit_proposal()
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
+            #[cfg(feature = "admin-service-proposal-expiry")]
+            CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_EXPIRE => {
+                let expire_request = circuit_payload.get_circuit_proposal_expire();
+                let circuit_proposal = self
+                    .get_proposal(expire_request.get_circuit_id())
+                    .map_err(|err| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "error occurred when trying to get proposal {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Received expiry for a proposal that does not exist: circuit id {}",
+                            expire_request.get_circuit_id()
+                        ))
+                    })?;
+
+                let signer_public_key = header.get_requester();
+                self.validate_proposal_expire(
+                    expire_request,
+                    signer_public_key,
+                    &circuit_proposal,
+                    header.get_requester_node_id(),
+                )?;
+
+                self.current_consensus_verifiers = circuit_proposal
+                    .circuit()
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.circuit_id()
+                        ))
+                    })?;
+
+                // The proposal itself is unchanged; committing the expiry removes it
+                let proto_circuit_proposal = circuit_proposal.into_proto();
+
+                let expected_hash = sha256(&proto_circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: proto_circuit_proposal.clone(),
+                    signer_public_key: signer_public_key.to_vec(),
+                    action: CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_EXPIRE,
+                });
+
+                Ok((expected_hash, proto_circuit_proposal))
+            }
+            CircuitManagementPayload_Action::ACTION_UNSET => Err(
+                AdminSharedError::ValidationFailed("Action must be set".to_string()),
+            ),
+            unknown_action => Err(AdminSharedError::ValidationFailed(format!(
+                "Unable to handle {:?}",
+                unknown_action
+            ))),
+        }
+    }
+
+    pub fn has_proposal(&self, circuit_id: &str) -> Result<bool, AdminSharedError> {
+        Ok(self.admin_store.get_proposal(circuit_id)?.is_some())
+    }
+
+    /// Returns the lowest protocol version agreed upon with the members of the given circuit
+    fn get_agreed_protocol(&self, circuit: &Circuit) -> Result<u32, AdminSharedError> {
+        let local_required_auth = circuit
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Unable to get local nodes token: {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
+                )
+            })?;
+
+        let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+        for member in circuit.list_nodes().map_err(|_| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get tokens for circuit: {}",
+                circuit.get_circuit_id()
+            ))
+        })? {
+            if let Some(protocol_version) = self.service_protocols.get(&PeerTokenPair::new(
+                member.token.clone(),
+                local_required_auth.clone(),
+            )) {
+                if protocol_version < &protocol {
+                    protocol = *protocol_version
+                }
+            }
+        }
+
+        Ok(protocol)
+    }
+
+    /// Returns the current definition of an existing circuit. If the circuit is not in the admin
+    /// store, this node may have been proposed to join the circuit, in which case the definition
+    /// received with the proposal is returned.
+    pub fn get_proto_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminSharedError> {
+        match self.admin_store.get_circuit(circuit_id)? {
+            Some(store_circuit) => Ok(Some(make_proto_circuit(&store_circuit)?)),
+            None => Ok(self.joining_circuits.get(circuit_id).cloned()),
+        }
+    }
+
+    /// Holds on to the current definition of a circuit that this node has been proposed to join.
+    /// The definition is only held if the circuit does not exist locally and the payload is a
+    /// request to add this node to the circuit.
+    pub fn add_joining_circuit(
+        &mut self,
+        payload: &CircuitManagementPayload,
+        circuit: Circuit,
+    ) -> Result<(), AdminSharedError> {
+        if !payload.has_circuit_update_add_node() {
+            return Err(AdminSharedError::ValidationFailed(
+                "Circuit definitions are only accepted with add node requests".to_string(),
+            ));
+        }
+
+        let add_node_request = payload.get_circuit_update_add_node();
+        if add_node_request.get_node().get_node_id() != self.node_id
+            || circuit.get_circuit_id() != add_node_request.get_circuit_id()
+            || self
+                .admin_store
+                .get_circuit(circuit.get_circuit_id())?
+                .is_some()
+        {
+            return Ok(());
+        }
+
+        self.joining_circuits
+            .insert(circuit.get_circuit_id().to_string(), circuit);
+
+        Ok(())
+    }
+
+    /// Returns the peer tokens that were referenced for a proposal to add a node to a circuit.
+    /// The node being added references all other members of the circuit, while the existing
+    /// members only reference the node being added.
+    fn get_add_node_peer_tokens(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<Vec<PeerTokenPair>, AdminSharedError> {
+        let tokens = circuit.list_tokens(&self.node_id).map_err(|err| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get peer tokens for proposal {}: {}",
+                circuit.get_circuit_id(),
+                err
+            ))
+        })?;
+
+        match self.admin_store.get_circuit(circuit.get_circuit_id())? {
+            Some(existing_circuit) => {
+                let existing_tokens =
+                    existing_circuit.list_tokens(&self.node_id).map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get peer tokens for circuit {}: {}",
+                            circuit.get_circuit_id(),
+                            err
+                        ))
+                    })?;
+
+                Ok(tokens
+                    .into_iter()
+                    .filter(|token| !existing_tokens.contains(token))
+                    .collect())
+            }
+            None => Ok(tokens),
+        }
+    }
+
+    /// Propose a new circuit
+    ///
+    /// This operation will propose a new circuit to all the member nodes of the circuit.  If there
+    /// is no peer connection, a connection to the peer will also be established.
+    pub fn propose_circuit(
+        &mut self,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit proposal for {}",
             payload
                 .get_circuit_create_request()
                 .get_circuit()
@@ -1134,16 +1943,247 @@ impl AdminServiceShared {
         )
     }
 
-    pub fn update_metrics(&self) -> Result<(), AdminSharedError> {
-        // initialize circuit and proposal metrics
-        gauge!(
-            "splinter.admin.circuits.active",
-            self.admin_store.count_circuits(&[])? as f64
-        );
-        gauge!(
-            "splinter.admin.proposals",
-            self.admin_store.count_proposals(&[])? as f64
-        );
+    /// Once a local `CircuitUpdateRosterRequest` has been validated, the admin service may now
+    /// proceed to communicating with the remote circuit members to propose the roster change.
+    pub fn propose_update_roster(
+        &mut self,
+        payload: CircuitManagementPayload,
+        requester: &[u8],
+        requester_node_id: &str,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit update roster request {}",
+            payload.get_circuit_update_roster_request().get_circuit_id()
+        );
+        let circuit_proposal = self
+            .make_update_roster_circuit_proposal(
+                payload.get_circuit_update_roster_request(),
+                requester,
+                requester_node_id,
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        let local_required_auth = circuit_proposal
+            .get_circuit_proposal()
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get local nodes token: {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
+                )))
+            })?;
+
+        let members = circuit_proposal
+            .get_circuit_proposal()
+            .list_nodes()
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get peer tokens for members: {}", err),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_update(
+            &members,
+            local_required_auth,
+            payload,
+            message_sender,
+        )
+    }
+
+    /// Propose adding a node to an existing circuit
+    ///
+    /// This operation will propose the updated circuit to all the member nodes of the circuit,
+    /// including the node being added. A connection to the node being added will be established
+    /// if one does not exist already.
+    pub fn propose_add_node(
+        &mut self,
+        payload: CircuitManagementPayload,
+        requester: &[u8],
+        requester_node_id: &str,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit add node request {}",
+            payload.get_circuit_update_add_node().get_circuit_id()
+        );
+        let circuit_proposal = self
+            .make_add_node_circuit_proposal(
+                payload.get_circuit_update_add_node(),
+                requester,
+                requester_node_id,
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        let local_required_auth = circuit_proposal
+            .get_circuit_proposal()
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get local nodes token: {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
+                )))
+            })?;
+
+        let members = circuit_proposal
+            .get_circuit_proposal()
+            .list_nodes()
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get peer tokens for members: {}", err),
+                )))
+            })?;
+
+        let added_node_id = payload
+            .get_circuit_update_add_node()
+            .get_node()
+            .get_node_id()
+            .to_string();
+
+        // members that are only reachable through relays are not peered with
+        let relayed_members = self.add_relayed_members(
+            &members,
+            make_proto_relays(circuit_proposal.get_circuit_proposal()),
+            &local_required_auth,
+        );
+
+        self.check_connected_peers_payload_add_node(
+            &members,
+            &added_node_id,
+            &relayed_members,
+            local_required_auth,
+            payload,
+            message_sender,
+        )
+    }
+
+    /// Propose removing a node from an existing circuit
+    ///
+    /// This operation will propose the updated circuit to the remaining member nodes of the
+    /// circuit. The node being removed is not sent the proposal.
+    pub fn propose_remove_node(
+        &mut self,
+        payload: CircuitManagementPayload,
+        requester: &[u8],
+        requester_node_id: &str,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit remove node request {}",
+            payload.get_circuit_update_remove_node().get_circuit_id()
+        );
+        let circuit_proposal = self
+            .make_remove_node_circuit_proposal(
+                payload.get_circuit_update_remove_node(),
+                requester,
+                requester_node_id,
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        let local_required_auth = circuit_proposal
+            .get_circuit_proposal()
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get local nodes token: {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
+                )))
+            })?;
+
+        let members = circuit_proposal
+            .get_circuit_proposal()
+            .list_nodes()
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get peer tokens for members: {}", err),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_update(
+            &members,
+            local_required_auth,
+            payload,
+            message_sender,
+        )
+    }
+
+    /// Propose updating the application metadata of an existing circuit
+    ///
+    /// This operation will propose the updated circuit to all the member nodes of the circuit.
+    pub fn propose_update_application_metadata(
+        &mut self,
+        payload: CircuitManagementPayload,
+        requester: &[u8],
+        requester_node_id: &str,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit update application metadata request {}",
+            payload
+                .get_circuit_update_application_metadata_request()
+                .get_circuit_id()
+        );
+        let circuit_proposal = self
+            .make_update_application_metadata_circuit_proposal(
+                payload.get_circuit_update_application_metadata_request(),
+                requester,
+                requester_node_id,
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        let local_required_auth = circuit_proposal
+            .get_circuit_proposal()
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get local nodes token: {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
+                )))
+            })?;
+
+        let members = circuit_proposal
+            .get_circuit_proposal()
+            .list_nodes()
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get peer tokens for members: {}", err),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_update(
+            &members,
+            local_required_auth,
+            payload,
+            message_sender,
+        )
+    }
+
+    pub fn update_metrics(&self) -> Result<(), AdminSharedError> {
+        // initialize circuit and proposal metrics
+        gauge!(
+            "splinter.admin.circuits.active",
+            self.admin_store.count_circuits(&[])? as f64
+        );
+        gauge!(
+            "splinter.admin.proposals",
+            self.admin_store.count_proposals(&[])? as f64
+        );
         Ok(())
     }
 
@@ -1212,7 +2252,7 @@ impl AdminServiceShared {
             })?;
 
         // send ABANDONED_CIRCUIT message to all other members' admin services
-        if let Some(ref network_sender) = self.network_sender {
+        if self.network_sender.is_some() {
             let mut abandoned_circuit = AbandonedCircuit::new();
             abandoned_circuit.set_circuit_id(circuit_id.to_string());
             abandoned_circuit.set_member_node_id(self.node_id.clone());
@@ -1237,14 +2277,21 @@ impl AdminServiceShared {
                 .iter()
             {
                 if !self.is_local_node(token.peer_id()) {
-                    network_sender
-                        .send(&admin_service_id(&token.id_as_string()), &envelope_bytes)?;
+                    self.send_to_admin_service(token, &envelope_bytes)?;
                 }
             }
         }
 
+        self.deactivate_circuit(&stored_circuit)
+    }
+
+    /// Deactivates a circuit on this node, without notifying the other members. The circuit's
+    /// services are stopped, the peer refs associated with the circuit are removed, the circuit is
+    /// removed from the local routing table, and the circuit's `circuit_status` is updated to
+    /// `Abandoned`.
+    fn deactivate_circuit(&mut self, stored_circuit: &StoreCircuit) -> Result<(), ServiceError> {
         let (abandoned_proto_circuit, abandoned_store_circuit) = self
-            .make_abandoned_circuit(&stored_circuit)
+            .make_abandoned_circuit(stored_circuit)
             .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
         // Updating the corresponding `active` circuit from the admin store to have the
         // `Abandoned` `circuit_status`
@@ -1252,7 +2299,7 @@ impl AdminServiceShared {
             .update_circuit(abandoned_store_circuit)
             .map_err(|_| {
                 ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
-                    format!("Unable to update circuit {}", circuit_id),
+                    format!("Unable to update circuit {}", stored_circuit.circuit_id()),
                 )))
             })?;
 
@@ -1279,7 +2326,7 @@ impl AdminServiceShared {
                 ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
                     format!(
                         "Unable to remove circuit from routing table: {}",
-                        circuit_id
+                        stored_circuit.circuit_id()
                     ),
                 )))
             })?;
@@ -1288,7 +2335,8 @@ impl AdminServiceShared {
             ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
                 format!(
                     "Unable to remove peer refs for circuit: {}: {}",
-                    circuit_id, err
+                    stored_circuit.circuit_id(),
+                    err
                 ),
             )))
         })?);
@@ -1296,6 +2344,61 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Handles a REMOVED_MEMBER message from a member of a circuit this node has been removed
+    /// from. This node is not a party to the proposal that removed it, so the circuit is only
+    /// deactivated locally once every other member of the circuit has reported the removal, as
+    /// all of them must have accepted the proposal.
+    pub fn add_removed_member_notice(
+        &mut self,
+        circuit_id: &str,
+        member_node_id: &str,
+    ) -> Result<(), ServiceError> {
+        let stored_circuit = match self.admin_store.get_circuit(circuit_id).map_err(|err| {
+            ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
+                format!("error occurred when trying to get circuit {}", err),
+            )))
+        })? {
+            Some(circuit) if circuit.circuit_status() == &StoreCircuitStatus::Active => circuit,
+            _ => {
+                debug!(
+                    "Ignoring removal notice for circuit {} that is not active",
+                    circuit_id
+                );
+                return Ok(());
+            }
+        };
+
+        let other_members = stored_circuit
+            .members()
+            .iter()
+            .map(|member| member.node_id().to_string())
+            .filter(|node_id| node_id != &self.node_id)
+            .collect::<HashSet<String>>();
+        if !other_members.contains(member_node_id) {
+            warn!(
+                "Ignoring removal notice for circuit {} from non-member {}",
+                circuit_id, member_node_id
+            );
+            return Ok(());
+        }
+
+        let notices = self
+            .removed_member_notices
+            .entry(circuit_id.to_string())
+            .or_insert_with(HashSet::new);
+        notices.insert(member_node_id.to_string());
+        if notices.is_superset(&other_members) {
+            self.removed_member_notices.remove(circuit_id);
+            info!(
+                "This node has been removed from circuit {}, deactivating the circuit",
+                circuit_id
+            );
+            self.deactivate_circuit(&stored_circuit)?;
+        }
+
+        Ok(())
+    }
+
     /// Locally remove a Circuit Proposal that has been committed. A message is sent to the
     /// circuit proposal members that the proposal is being removed locally. Once the proposal
     /// has been removed from the admin store, the peer refs created for this proposal are also
@@ -1306,7 +2409,7 @@ impl AdminServiceShared {
             .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
         {
             // send REMOVED_PROPOSAL message to all other members' admin services
-            if let Some(ref network_sender) = self.network_sender {
+            if self.network_sender.is_some() {
                 let mut removed_proposal = RemovedProposal::new();
                 removed_proposal.set_circuit_id(circuit_id.to_string());
                 let mut msg = AdminMessage::new();
@@ -1333,8 +2436,7 @@ impl AdminServiceShared {
                     .iter()
                 {
                     if !self.is_local_node(token.peer_id()) {
-                        network_sender
-                            .send(&admin_service_id(&token.id_as_string()), &envelope_bytes)?;
+                        self.send_to_admin_service(token, &envelope_bytes)?;
                     }
                 }
             }
@@ -1459,10 +2561,16 @@ impl AdminServiceShared {
         let mut pending_peers = vec![];
         let mut pending_members = vec![];
         let mut added_peers: Vec<PeerTokenPair> = vec![];
+        // members that are only reachable through relays are not peered with
+        let relayed_members = self.add_relayed_members(
+            members,
+            make_proto_relays(payload.get_circuit_create_request().get_circuit()),
+            &local_required_auth,
+        );
         for node in members {
             let peer_token_pair =
                 PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
-            if !self.is_local_node(&node.token) {
+            if !self.is_local_node(&node.token) && !relayed_members.contains(&node.node_id) {
                 debug!("Referencing node {:?}", &node.token);
                 let peer_ref = self
                     .peer_connector
@@ -1561,19 +2669,147 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    pub fn submit(&mut self, payload: CircuitManagementPayload) -> Result<(), ServiceError> {
-        debug!("Payload submitted: {:?}", payload);
-
-        let header = Message::parse_from_bytes(payload.get_header())?;
-        self.validate_circuit_management_payload(&payload, &header)
-            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
-        self.verify_signature(&payload)?;
+    /// Verify all members of the circuit to be updated are using a valid protocol version.
+    /// The members of an existing circuit are already peered, so only protocol agreement is
+    /// required before the update payload is moved into the `pending_circuit_payloads` list.
+    fn check_connected_peers_payload_update(
+        &mut self,
+        members: &[PeerNode],
+        local_required_auth: PeerAuthorizationToken,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let mut missing_protocol_ids = vec![];
+        let mut pending_members = vec![];
+        for node in members {
+            let peer_token_pair =
+                PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
+            if !self.is_local_node(&node.token)
+                && self.service_protocols.get(&peer_token_pair).is_none()
+            {
+                self.send_protocol_request(&peer_token_pair, &node.admin_service)?;
+                missing_protocol_ids.push(node.clone())
+            }
+            pending_members.push(peer_token_pair);
+        }
 
-        match header.get_action() {
-            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
-                let signer_public_key = header.get_requester();
-                let requester_node_id = header.get_requester_node_id();
-                self.validate_create_circuit(
+        if missing_protocol_ids.is_empty() {
+            self.pending_circuit_payloads.push_back(payload);
+        } else {
+            debug!(
+                "Members {:?} added; awaiting service protocol agreement before proceeding",
+                &missing_protocol_ids
+            );
+            self.pending_protocol_payloads.push(PendingPayload {
+                unpeered_ids: vec![],
+                missing_protocol_ids,
+                payload_type: PayloadType::Circuit(payload),
+                members: pending_members,
+                message_sender,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Verify all members of the circuit a node is being added to are connected and using a valid
+    /// protocol version. The existing members of the circuit are already peered, so a connection
+    /// is only established to the node being added. Once all members are peered and have agreed
+    /// on a protocol version, the payload is moved into the `pending_circuit_payloads` list.
+    fn check_connected_peers_payload_add_node(
+        &mut self,
+        members: &[PeerNode],
+        added_node_id: &str,
+        relayed_members: &HashSet<String>,
+        local_required_auth: PeerAuthorizationToken,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let mut missing_protocol_ids = vec![];
+        let mut pending_peers = vec![];
+        let mut pending_members = vec![];
+        for node in members {
+            let peer_token_pair =
+                PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
+            if !self.is_local_node(&node.token) && !relayed_members.contains(&node.node_id) {
+                if node.node_id == added_node_id {
+                    debug!("Referencing node {:?}", &node.token);
+                    let peer_ref = self
+                        .peer_connector
+                        .add_peer_ref(
+                            node.token.clone(),
+                            node.endpoints.to_vec(),
+                            local_required_auth.clone(),
+                        )
+                        .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                    self.add_peer_ref(peer_ref);
+
+                    // if we have a protocol the connection exists for the peer already
+                    if self.service_protocols.get(&peer_token_pair).is_none() {
+                        pending_peers.push(peer_token_pair.clone());
+                        missing_protocol_ids.push(node.clone())
+                    }
+                } else if self.service_protocols.get(&peer_token_pair).is_none() {
+                    self.send_protocol_request(&peer_token_pair, &node.admin_service)?;
+                    missing_protocol_ids.push(node.clone())
+                }
+            }
+            pending_members.push(peer_token_pair.clone());
+            self.token_to_peer.insert(
+                peer_token_pair,
+                PeerNodePair {
+                    peer_node: node.clone(),
+                    local_peer_token: local_required_auth.clone(),
+                },
+            );
+        }
+
+        if missing_protocol_ids.is_empty() {
+            self.pending_circuit_payloads.push_back(payload);
+        } else if pending_peers.is_empty() {
+            debug!(
+                "Members {:?} added; awaiting service protocol agreement before proceeding",
+                &missing_protocol_ids
+            );
+            self.pending_protocol_payloads.push(PendingPayload {
+                unpeered_ids: vec![],
+                missing_protocol_ids,
+                payload_type: PayloadType::Circuit(payload),
+                members: pending_members,
+                message_sender,
+            });
+        } else {
+            debug!(
+                "Members {:?} added; awaiting peering and service protocol agreement before \
+                proceeding",
+                &missing_protocol_ids
+            );
+            self.unpeered_payloads.push(PendingPayload {
+                unpeered_ids: pending_peers,
+                missing_protocol_ids,
+                payload_type: PayloadType::Circuit(payload),
+                members: pending_members,
+                message_sender,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn submit(&mut self, payload: CircuitManagementPayload) -> Result<(), ServiceError> {
+        debug!("Payload submitted: {:?}", payload);
+
+        let header = Message::parse_from_bytes(payload.get_header())?;
+        self.validate_circuit_management_payload(&payload, &header)
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+        self.verify_signature(&payload)?;
+
+        match header.get_action() {
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                self.validate_create_circuit(
                     payload.get_circuit_create_request().get_circuit(),
                     signer_public_key,
                     requester_node_id,
@@ -1645,6 +2881,119 @@ impl AdminServiceShared {
                     "local".to_string(),
                 )
             }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let update_roster_request = payload.get_circuit_update_roster_request();
+                let circuit_proposal = self
+                    .make_update_roster_circuit_proposal(
+                        update_roster_request,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_update_roster(
+                    update_roster_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_update_roster(
+                    payload,
+                    signer_public_key,
+                    requester_node_id,
+                    "local".to_string(),
+                )
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let add_node_request = payload.get_circuit_update_add_node();
+                let circuit_proposal = self
+                    .make_add_node_circuit_proposal(
+                        add_node_request,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_add_node(
+                    add_node_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_add_node(
+                    payload,
+                    signer_public_key,
+                    requester_node_id,
+                    "local".to_string(),
+                )
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let remove_node_request = payload.get_circuit_update_remove_node();
+                let circuit_proposal = self
+                    .make_remove_node_circuit_proposal(
+                        remove_node_request,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_remove_node(
+                    remove_node_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_remove_node(
+                    payload,
+                    signer_public_key,
+                    requester_node_id,
+                    "local".to_string(),
+                )
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let update_metadata_request =
+                    payload.get_circuit_update_application_metadata_request();
+                let circuit_proposal = self
+                    .make_update_application_metadata_circuit_proposal(
+                        update_metadata_request,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_update_application_metadata(
+                    update_metadata_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_update_application_metadata(
+                    payload,
+                    signer_public_key,
+                    requester_node_id,
+                    "local".to_string(),
+                )
+            }
             CircuitManagementPayload_Action::CIRCUIT_PURGE_REQUEST => {
                 let signer_public_key = header.get_requester();
                 let requester_node_id = header.get_requester_node_id();
@@ -1708,6 +3057,21 @@ impl AdminServiceShared {
         let mut pending_members = vec![];
         let mut members: Vec<PeerNode> = vec![];
 
+        // A node may only expire proposals on its own behalf
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        if payload.has_circuit_proposal_expire() {
+            let header: CircuitManagementPayload_Header =
+                Message::parse_from_bytes(payload.get_header())?;
+            if message_sender != admin_service_id(header.get_requester_node_id()) {
+                return Err(ServiceError::UnableToHandleMessage(Box::new(
+                    AdminSharedError::ValidationFailed(format!(
+                        "Expiry for proposal {} was not sent by the requesting node",
+                        payload.get_circuit_proposal_expire().get_circuit_id()
+                    )),
+                )));
+            }
+        }
+
         // Check if that payload is to create a circuit, in which case PeerRefs for the new
         // members must be added.
         if payload.has_circuit_create_request() {
@@ -1749,10 +3113,18 @@ impl AdminServiceShared {
                 )))
             })?;
 
+            // members that are only reachable through relays are not peered with
+            let relayed_members = self.add_relayed_members(
+                &peer_members,
+                make_proto_relays(payload.get_circuit_create_request().get_circuit()),
+                &local_required_auth,
+            );
             for node in &peer_members {
                 let peer_token_pair =
                     PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
-                if !self.is_local_node(peer_token_pair.peer_id()) {
+                if !self.is_local_node(peer_token_pair.peer_id())
+                    && !relayed_members.contains(&node.node_id)
+                {
                     debug!("Referencing node {:?}", &peer_token_pair);
                     let peer_ref = self
                         .peer_connector
@@ -1788,33 +3160,15 @@ impl AdminServiceShared {
                 );
             }
             members.extend(peer_members);
-        } else if payload.has_circuit_disband_request() {
-            // If a `CircuitDisbandRequest` is present in the payload, the members must be gathered
-            // from the admin store based on the provided circuit id.
-            // If the members list has already been updated, the payload was to create a
-            // new circuit.
-            if !members.is_empty() {
-                return Err(ServiceError::UnableToHandleMessage(Box::new(
-                    AdminSharedError::ValidationFailed(
-                        "Invalid payload; has two requests".to_string(),
-                    ),
-                )));
-            }
-            let circuit_id = payload.get_circuit_disband_request().get_circuit_id();
-            // If the proposed circuit is being disbanded, the circuit information must be
-            // gathered from the admin store, as the `CircuitDisbandRequest` only contains
-            // the `circuit_id`.
-            let circuit = self
-                .admin_store
-                .get_circuit(circuit_id)
-                .map_err(|err| {
-                    ServiceError::UnableToHandleMessage(Box::new(
-                        AdminSharedError::ValidationFailed(format!(
-                            "error occurred when trying to get circuit {}",
-                            err
-                        )),
-                    ))
-                })?
+        } else if payload.has_circuit_update_add_node() {
+            // If a node is being added to a circuit, the node being added must add PeerRefs for
+            // all existing members of the circuit, while the existing members only add a PeerRef
+            // for the node being added.
+            let add_node_request = payload.get_circuit_update_add_node();
+            let circuit_id = add_node_request.get_circuit_id();
+            let mut circuit = self
+                .get_proto_circuit(circuit_id)
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
                 .ok_or_else(|| {
                     ServiceError::UnableToHandleMessage(Box::new(
                         AdminSharedError::ValidationFailed(format!(
@@ -1823,6 +3177,10 @@ impl AdminServiceShared {
                         )),
                     ))
                 })?;
+            circuit
+                .mut_members()
+                .push(add_node_request.get_node().clone());
+            let is_joining = self.joining_circuits.contains_key(circuit_id);
 
             let local_required_auth = circuit
                 .get_node_token(&self.node_id)
@@ -1842,52 +3200,168 @@ impl AdminServiceShared {
                     ))
                 })?;
 
-            let tokens = circuit.list_nodes().map_err(|err| {
+            let peer_members = circuit.list_nodes().map_err(|err| {
                 ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
                     format!("Unable to get peer tokens for members: {}", err),
                 )))
             })?;
 
-            for node in tokens {
+            // members that are only reachable through relays are not peered with
+            let relayed_members = self.add_relayed_members(
+                &peer_members,
+                make_proto_relays(&circuit),
+                &local_required_auth,
+            );
+            for node in &peer_members {
                 let peer_token_pair =
                     PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
-                // Verify each disband member has an agreed upon protocol version with this node
-                // Otherwise, re-establish a peer connection
                 if !self.is_local_node(peer_token_pair.peer_id())
-                    && self.service_protocols.get(&peer_token_pair).is_none()
+                    && !relayed_members.contains(&node.node_id)
                 {
-                    pending_peers.push(peer_token_pair.clone());
-                    missing_protocol_ids.push(node.clone())
-                }
-            }
-        }
+                    if is_joining || node.node_id == add_node_request.get_node().get_node_id() {
+                        debug!("Referencing node {:?}", &peer_token_pair);
+                        let peer_ref = self
+                            .peer_connector
+                            .add_peer_ref(
+                                node.token.clone(),
+                                node.endpoints.to_vec(),
+                                local_required_auth.clone(),
+                            )
+                            .map_err(|err| {
+                                // remove all peer refs added for this proposal
+                                self.remove_peer_refs(added_peers.to_vec());
 
-        if missing_protocol_ids.is_empty() {
-            self.add_pending_consensus_proposal(proposal.id.clone(), (proposal.clone(), payload));
-            self.proposal_sender
-                .as_ref()
-                .ok_or(ServiceError::NotStarted)?
-                .send(ProposalUpdate::ProposalReceived(
-                    proposal,
-                    message_sender.as_bytes().into(),
-                ))
-                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
-        } else {
-            debug!(
-                "Members {:?} added; service protocol agreement before proceeding",
-                &missing_protocol_ids
-            );
+                                ServiceError::UnableToHandleMessage(Box::new(err))
+                            })?;
 
-            self.pending_protocol_payloads.push(PendingPayload {
-                unpeered_ids: pending_peers,
-                missing_protocol_ids,
-                payload_type: PayloadType::Consensus(proposal.id.clone(), (proposal, payload)),
-                members: pending_members,
-                message_sender,
-            });
-            Ok(())
-        }
-    }
+                        self.add_peer_ref(peer_ref);
+                        added_peers.push(peer_token_pair.clone());
+                    }
+
+                    // if we have a protocol the connection exists for the peer already
+                    if self.service_protocols.get(&peer_token_pair).is_none() {
+                        pending_peers.push(peer_token_pair.clone());
+                        missing_protocol_ids.push(node.clone())
+                    }
+                }
+                pending_members.push(peer_token_pair.clone());
+
+                self.token_to_peer.insert(
+                    peer_token_pair,
+                    PeerNodePair {
+                        peer_node: node.clone(),
+                        local_peer_token: local_required_auth.clone(),
+                    },
+                );
+            }
+            members.extend(peer_members);
+        } else if let Some(circuit_id) = get_existing_circuit_id(&payload) {
+            // If a `CircuitDisbandRequest` or a request to update a circuit is present in the
+            // payload, the members must be gathered from the admin store based on the provided
+            // circuit id.
+            // If the members list has already been updated, the payload was to create a
+            // new circuit.
+            if !members.is_empty() {
+                return Err(ServiceError::UnableToHandleMessage(Box::new(
+                    AdminSharedError::ValidationFailed(
+                        "Invalid payload; has two requests".to_string(),
+                    ),
+                )));
+            }
+            // If the proposed circuit is being disbanded or updated, the circuit information must
+            // be gathered from the admin store, as these requests only contain the `circuit_id`
+            // and the requested change.
+            let circuit = self
+                .admin_store
+                .get_circuit(circuit_id)
+                .map_err(|err| {
+                    ServiceError::UnableToHandleMessage(Box::new(
+                        AdminSharedError::ValidationFailed(format!(
+                            "error occurred when trying to get circuit {}",
+                            err
+                        )),
+                    ))
+                })?
+                .ok_or_else(|| {
+                    ServiceError::UnableToHandleMessage(Box::new(
+                        AdminSharedError::ValidationFailed(format!(
+                            "unable to get circuit {}",
+                            circuit_id
+                        )),
+                    ))
+                })?;
+
+            let local_required_auth = circuit
+                .get_node_token(&self.node_id)
+                .map_err(|err| {
+                    ServiceError::UnableToHandleMessage(Box::new(
+                        AdminSharedError::ValidationFailed(format!(
+                            "Unable to get local nodes token: {}",
+                            err
+                        )),
+                    ))
+                })?
+                .ok_or_else(|| {
+                    ServiceError::UnableToHandleMessage(Box::new(
+                        AdminSharedError::ValidationFailed(
+                            "Circuit does not have the local node".to_string(),
+                        ),
+                    ))
+                })?;
+
+            let tokens = circuit.list_nodes().map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get peer tokens for members: {}", err),
+                )))
+            })?;
+
+            for node in tokens {
+                // The node being removed from the circuit does not take part in the proposal
+                if payload.has_circuit_update_remove_node()
+                    && payload.get_circuit_update_remove_node().get_node_id() == node.node_id
+                {
+                    continue;
+                }
+
+                let peer_token_pair =
+                    PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
+                // Verify each member has an agreed upon protocol version with this node
+                // Otherwise, re-establish a peer connection
+                if !self.is_local_node(peer_token_pair.peer_id())
+                    && self.service_protocols.get(&peer_token_pair).is_none()
+                {
+                    pending_peers.push(peer_token_pair.clone());
+                    missing_protocol_ids.push(node.clone())
+                }
+            }
+        }
+
+        if missing_protocol_ids.is_empty() {
+            self.add_pending_consensus_proposal(proposal.id.clone(), (proposal.clone(), payload));
+            self.proposal_sender
+                .as_ref()
+                .ok_or(ServiceError::NotStarted)?
+                .send(ProposalUpdate::ProposalReceived(
+                    proposal,
+                    message_sender.as_bytes().into(),
+                ))
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+        } else {
+            debug!(
+                "Members {:?} added; service protocol agreement before proceeding",
+                &missing_protocol_ids
+            );
+
+            self.pending_protocol_payloads.push(PendingPayload {
+                unpeered_ids: pending_peers,
+                missing_protocol_ids,
+                payload_type: PayloadType::Consensus(proposal.id.clone(), (proposal, payload)),
+                members: pending_members,
+                message_sender,
+            });
+            Ok(())
+        }
+    }
 
     #[cfg(not(feature = "admin-service-event-subscriber-glob"))]
     pub fn get_events_since(
@@ -1895,6 +3369,9 @@ impl AdminServiceShared {
         since_event_id: &i64,
         circuit_management_type: &str,
     ) -> Result<Events, AdminSharedError> {
+        #[cfg(feature = "admin-service-event-retention")]
+        self.check_events_retained(*since_event_id)?;
+
         let events = self
             .event_store
             .list_events_by_management_type_since(
@@ -1913,6 +3390,9 @@ impl AdminServiceShared {
         since_event_id: &i64,
         circuit_management_type: &str,
     ) -> Result<Events, AdminSharedError> {
+        #[cfg(feature = "admin-service-event-retention")]
+        self.check_events_retained(*since_event_id)?;
+
         let events = if circuit_management_type == "*" {
             self.event_store
                 .list_events_since(*since_event_id)
@@ -1930,6 +3410,30 @@ impl AdminServiceShared {
         })
     }
 
+    /// Checks that the events after the given event ID have not been removed by compaction.
+    ///
+    /// A `since_event_id` of 0 requests all available events, and is always allowed.
+    #[cfg(feature = "admin-service-event-retention")]
+    fn check_events_retained(&self, since_event_id: i64) -> Result<(), AdminSharedError> {
+        if since_event_id <= 0 {
+            return Ok(());
+        }
+
+        match self
+            .event_store
+            .get_compacted_event_id()
+            .map_err(|err| AdminSharedError::UnableToAddSubscriber(err.to_string()))?
+        {
+            Some(compacted_event_id) if since_event_id < compacted_event_id => {
+                Err(AdminSharedError::EventsCompacted {
+                    since_event_id,
+                    compacted_event_id,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn add_subscriber(
         &mut self,
         circuit_management_type: String,
@@ -2253,6 +3757,23 @@ impl AdminServiceShared {
             .map_err(AdminSharedError::from)
     }
 
+    pub fn get_proposals_page(
+        &self,
+        filters: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = StoreProposal>>, AdminSharedError> {
+        self.admin_store
+            .list_proposals_page(filters, after, limit)
+            .map_err(AdminSharedError::from)
+    }
+
+    pub fn count_proposals(&self, filters: &[CircuitPredicate]) -> Result<u32, AdminSharedError> {
+        self.admin_store
+            .count_proposals(filters)
+            .map_err(AdminSharedError::from)
+    }
+
     pub fn remove_proposal(
         &mut self,
         circuit_id: &str,
@@ -2266,11 +3787,222 @@ impl AdminServiceShared {
         &mut self,
         circuit_proposal: CircuitProposal,
     ) -> Result<(), AdminSharedError> {
-        Ok(self
+        let proposal = StoreProposal::from_proto(circuit_proposal).map_err(|err| {
+            AdminSharedError::SplinterStateError(format!("Unable to add proposal: {}", err))
+        })?;
+
+        // The expiry is local to this node, so it is only recorded in the store and never
+        // included in the proposal that is agreed upon by the circuit members
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        let proposal = match self.proposal_expiry {
+            Some(expiry) => proposal
+                .builder()
+                .with_expires_at(SystemTime::now() + expiry)
+                .build()
+                .map_err(|err| {
+                    AdminSharedError::SplinterStateError(format!("Unable to add proposal: {}", err))
+                })?,
+            None => proposal,
+        };
+
+        Ok(self.admin_store.add_proposal(proposal)?)
+    }
+
+    /// Submits a request to expire each proposal that has passed its expiry time on this node.
+    ///
+    /// The expiry request is agreed upon by the proposal's members through consensus, like a
+    /// vote, so every member removes the proposal at the same point. Proposals whose changes are
+    /// currently pending in consensus, or whose expiry has already been submitted, are left alone
+    /// until the next check.
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    pub fn expire_proposals(&mut self) -> Result<(), AdminSharedError> {
+        let now = SystemTime::now();
+        let pending_circuit_id = self
+            .pending_changes
+            .as_ref()
+            .map(|context| context.circuit_proposal.get_circuit_id().to_string());
+
+        let submitted_expiries = self
+            .pending_circuit_payloads
+            .iter()
+            .chain(self.pending_protocol_payloads.iter().filter_map(|pending| {
+                match &pending.payload_type {
+                    PayloadType::Circuit(payload) => Some(payload),
+                    PayloadType::Consensus(_, _) => None,
+                }
+            }))
+            .filter(|payload| payload.has_circuit_proposal_expire())
+            .map(|payload| {
+                payload
+                    .get_circuit_proposal_expire()
+                    .get_circuit_id()
+                    .to_string()
+            })
+            .collect::<HashSet<_>>();
+
+        let expired = self
             .admin_store
-            .add_proposal(StoreProposal::from_proto(circuit_proposal).map_err(|err| {
-                AdminSharedError::SplinterStateError(format!("Unable to add proposal: {}", err))
-            })?)?)
+            .list_proposals(&[])?
+            .filter(|proposal| match proposal.expires_at() {
+                Some(expires_at) => expires_at <= now,
+                None => false,
+            })
+            .filter(|proposal| pending_circuit_id.as_deref() != Some(proposal.circuit_id()))
+            .filter(|proposal| !submitted_expiries.contains(proposal.circuit_id()))
+            .collect::<Vec<_>>();
+
+        for proposal in expired {
+            let payload = self.make_proposal_expire_payload(&proposal)?;
+
+            let local_required_auth = proposal
+                .circuit()
+                .get_node_token(&self.node_id)
+                .map_err(|err| {
+                    AdminSharedError::ValidationFailed(format!(
+                        "Unable to get local nodes token: {}",
+                        err
+                    ))
+                })?
+                .ok_or_else(|| {
+                    AdminSharedError::ValidationFailed(
+                        "Circuit does not have the local node".to_string(),
+                    )
+                })?;
+            let members = proposal.circuit().list_nodes().map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Unable to get peer tokens for members: {}",
+                    err
+                ))
+            })?;
+
+            // The expiry is proposed to the members the same way as a vote
+            self.check_connected_peers_payload_vote(
+                &members,
+                local_required_auth,
+                payload,
+                "local".to_string(),
+            )
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to submit expiry for proposal {}: {}",
+                    proposal.circuit_id(),
+                    err
+                ))
+            })?;
+
+            info!(
+                "circuit proposal for {} has expired on this node, requesting its removal",
+                proposal.circuit_id()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Builds a request to expire the given proposal, signed by this node.
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    fn make_proposal_expire_payload(
+        &self,
+        proposal: &StoreProposal,
+    ) -> Result<CircuitManagementPayload, AdminSharedError> {
+        let signer = self.expiry_signer.as_ref().ok_or_else(|| {
+            AdminSharedError::SplinterStateError(
+                "A signer is required to expire proposals".to_string(),
+            )
+        })?;
+
+        let mut expire_request = CircuitProposalExpire::new();
+        expire_request.set_circuit_id(proposal.circuit_id().to_string());
+        expire_request.set_circuit_hash(proposal.circuit_hash().to_string());
+
+        let expire_request_bytes = expire_request
+            .write_to_bytes()
+            .map_err(MarshallingError::from)?;
+        let payload_sha512 =
+            hash(MessageDigest::sha512(), &expire_request_bytes).map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to hash expiry request: {}",
+                    err
+                ))
+            })?;
+
+        let public_key = signer.public_key().map_err(|err| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get public key of expiry signer: {}",
+                err
+            ))
+        })?;
+
+        let mut header = CircuitManagementPayload_Header::new();
+        header.set_action(CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_EXPIRE);
+        header.set_requester(public_key.as_slice().to_vec());
+        header.set_payload_sha512(payload_sha512.to_vec());
+        header.set_requester_node_id(self.node_id.clone());
+        let header_bytes = header.write_to_bytes().map_err(MarshallingError::from)?;
+
+        let signature = signer.sign(&header_bytes).map_err(|err| {
+            AdminSharedError::SplinterStateError(format!("Unable to sign expiry request: {}", err))
+        })?;
+
+        let mut payload = CircuitManagementPayload::new();
+        payload.set_header(header_bytes);
+        payload.set_signature(signature.as_slice().to_vec());
+        payload.set_circuit_proposal_expire(expire_request);
+
+        Ok(payload)
+    }
+
+    /// Commits an agreed upon expiry of a proposal. The proposal is removed, the peer refs held
+    /// for it are released and a `ProposalExpired` event is sent to subscribers.
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    fn commit_proposal_expiry(
+        &mut self,
+        circuit_proposal: &CircuitProposal,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id();
+
+        if let Some(proposal) = self.remove_proposal(circuit_id)? {
+            match proposal.proposal_type() {
+                // Proposals to update a circuit use the peer refs of the existing circuit, so
+                // these must not be removed
+                ProposalType::UpdateRoster
+                | ProposalType::RemoveNode
+                | ProposalType::UpdateApplicationMetadata => (),
+                ProposalType::AddNode => {
+                    let peers =
+                        self.get_add_node_peer_tokens(circuit_proposal.get_circuit_proposal())?;
+                    self.peers_to_be_removed.push((Instant::now(), peers));
+                }
+                _ => {
+                    let peers = proposal
+                        .circuit()
+                        .list_tokens(&self.node_id)
+                        .map_err(|err| {
+                            AdminSharedError::SplinterStateError(format!(
+                                "Unable to remove peer refs for proposal {}: {}",
+                                circuit_id, err
+                            ))
+                        })?;
+                    self.peers_to_be_removed.push((Instant::now(), peers));
+                }
+            }
+        }
+
+        self.joining_circuits.remove(circuit_id);
+        self.update_metrics()?;
+
+        let mgmt_type = circuit_proposal
+            .get_circuit_proposal()
+            .circuit_management_type
+            .clone();
+        let event = messages::AdminServiceEvent::ProposalExpired(
+            messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                .map_err(AdminSharedError::InvalidMessageFormat)?,
+        );
+        self.send_event(&mgmt_type, event);
+
+        info!("circuit proposal for {} has expired", circuit_id);
+        Ok(())
     }
 
     pub fn update_proposal(
@@ -2313,6 +4045,7 @@ impl AdminServiceShared {
                     UninitializedCircuit {
                         circuit: Some(circuit),
                         ready_members: HashSet::new(),
+                        previous_roster: None,
                     },
                 );
             }
@@ -2332,19 +4065,32 @@ impl AdminServiceShared {
         // If the proposal has type `CircuitProposal_ProposalType::DISBAND`, the proposal is
         // intended to disband a circuit and the associated services will need to be stopped. In
         // this case, the next step is to `cleanup_disbanded_circuit_if_members_ready`.
-        if circuit_proposal_type == CircuitProposal_ProposalType::DISBAND {
-            self.cleanup_disbanded_circuit_if_members_ready(&circuit_id)
-        } else {
-            self.initialize_services_if_members_ready(&circuit_id)
+        // If the proposal has type `CircuitProposal_ProposalType::UPDATE_ROSTER`,
+        // `CircuitProposal_ProposalType::ADD_NODE` or `CircuitProposal_ProposalType::REMOVE_NODE`,
+        // the services added to the circuit need to be initialized and the services removed from
+        // the circuit need to be stopped. In this case, the next step is to
+        // `update_services_if_members_ready`.
+        match circuit_proposal_type {
+            CircuitProposal_ProposalType::DISBAND => {
+                self.cleanup_disbanded_circuit_if_members_ready(&circuit_id)
+            }
+            CircuitProposal_ProposalType::UPDATE_ROSTER
+            | CircuitProposal_ProposalType::ADD_NODE
+            | CircuitProposal_ProposalType::REMOVE_NODE => {
+                self.update_services_if_members_ready(&circuit_id)
+            }
+            _ => self.initialize_services_if_members_ready(&circuit_id),
         }
     }
 
-    /// A member may be ready to initialize a circuit or to disband a circuit. The proposal type
-    /// of the proposal associated with the `circuit_id` determines the operation the member
-    /// voted for. If the proposal type is `Create`, the vote submitted pertains to creating a
-    /// circuit so the services must be initialized if all members are now ready. If the proposal
-    /// type is disband, the vote submitted pertains to disbanding a circuit so the services
-    /// must be stopped if all members are now ready.
+    /// A member may be ready to initialize a circuit, to update a circuit or to disband a circuit.
+    /// The proposal type of the proposal associated with the `circuit_id` determines the
+    /// operation the member voted for. If the proposal type is `Create`, the vote submitted
+    /// pertains to creating a circuit so the services must be initialized if all members are now
+    /// ready. If the proposal type is `UpdateRoster`, `AddNode` or `RemoveNode`, the added
+    /// services must be initialized and the removed services stopped if all members are now
+    /// ready. If the proposal type is disband, the vote submitted pertains to disbanding a circuit
+    /// so the services must be stopped if all members are now ready.
     pub fn add_ready_member(
         &mut self,
         circuit_id: &str,
@@ -2374,6 +4120,7 @@ impl AdminServiceShared {
                 UninitializedCircuit {
                     circuit: None,
                     ready_members: HashSet::new(),
+                    previous_roster: None,
                 },
             );
         }
@@ -2388,6 +4135,9 @@ impl AdminServiceShared {
         // associated circuit proposal's type.
         match proposal_type {
             ProposalType::Disband => self.cleanup_disbanded_circuit_if_members_ready(circuit_id),
+            ProposalType::UpdateRoster | ProposalType::AddNode | ProposalType::RemoveNode => {
+                self.update_services_if_members_ready(circuit_id)
+            }
             _ => self.initialize_services_if_members_ready(circuit_id),
         }
     }
@@ -2438,25 +4188,96 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    fn validate_create_circuit(
-        &self,
-        circuit: &Circuit,
-        signer_public_key: &[u8],
-        requester_node_id: &str,
-        protocol: u32,
+    /// If all members of an updated circuit are ready, initialize the services that were added
+    /// to the circuit and stop the services that were removed from the circuit. Services whose
+    /// arguments were changed by the update are restarted with the updated arguments.
+    fn update_services_if_members_ready(
+        &mut self,
+        circuit_id: &str,
     ) -> Result<(), AdminSharedError> {
-        match protocol {
-            ADMIN_SERVICE_PROTOCOL_VERSION => {
-                // verify that the circuit version is supported
-                if circuit.get_circuit_version() > CIRCUIT_PROTOCOL_VERSION {
-                    return Err(AdminSharedError::ValidationFailed(format!(
-                        "Proposed circuit's schema version is unsupported: {}",
-                        circuit.get_circuit_version()
-                    )));
+        let ready = {
+            if let Some(uninitialized_circuit) = self.uninitialized_circuits.get(circuit_id) {
+                if let Some(ref circuit_proposal) = uninitialized_circuit.circuit {
+                    let all_members = circuit_proposal
+                        .get_circuit_proposal()
+                        .members
+                        .iter()
+                        .map(|node| node.node_id.clone())
+                        .collect::<HashSet<String>>();
+                    all_members.is_subset(&uninitialized_circuit.ready_members)
+                } else {
+                    false
                 }
+            } else {
+                false
             }
+        };
 
-            1 => {
+        if ready {
+            let uninitialized_circuit = self
+                .uninitialized_circuits
+                .remove(circuit_id)
+                .expect("Uninitialized circuit not set");
+            let circuit_proposal = uninitialized_circuit
+                .circuit
+                .expect("Uninitialized circuit's circuit proposal not set");
+            let previous_roster = uninitialized_circuit
+                .previous_roster
+                .expect("Updated circuit's previous roster not set");
+            let circuit = circuit_proposal.get_circuit_proposal();
+
+            // Stop the services that have been removed from the circuit or that have been changed
+            let removed_services = previous_roster
+                .iter()
+                .filter(|service| {
+                    !circuit
+                        .get_roster()
+                        .iter()
+                        .any(|updated| is_same_service(service, updated))
+                })
+                .cloned()
+                .collect::<Vec<StoreService>>();
+            self.retire_services(circuit_id, &removed_services)?;
+
+            // Start the services that have been added to the circuit or that have been changed
+            let mut added_services = circuit.clone();
+            added_services.set_roster(RepeatedField::from_vec(
+                circuit
+                    .get_roster()
+                    .iter()
+                    .filter(|service| {
+                        !previous_roster
+                            .iter()
+                            .any(|previous| is_same_service(previous, service))
+                    })
+                    .cloned()
+                    .collect(),
+            ));
+            self.initialize_services(&added_services)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_create_circuit(
+        &self,
+        circuit: &Circuit,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        match protocol {
+            ADMIN_SERVICE_PROTOCOL_VERSION => {
+                // verify that the circuit version is supported
+                if circuit.get_circuit_version() > CIRCUIT_PROTOCOL_VERSION {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Proposed circuit's schema version is unsupported: {}",
+                        circuit.get_circuit_version()
+                    )));
+                }
+            }
+
+            1 => {
                 // if using the previous version, display name cannot be set
                 if !circuit.get_display_name().is_empty() {
                     return Err(AdminSharedError::ValidationFailed(
@@ -2551,6 +4372,76 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Checks that the relay nodes of a circuit are only set for circuits with relayed routes,
+    /// are members of the circuit and do not relay messages in a loop.
+    fn validate_relays(
+        &self,
+        circuit: &Circuit,
+        members: &[String],
+    ) -> Result<(), AdminSharedError> {
+        let relays = circuit
+            .get_members()
+            .iter()
+            .filter(|member| !member.get_relay_node_id().is_empty())
+            .map(|member| (member.get_node_id(), member.get_relay_node_id()))
+            .collect::<HashMap<&str, &str>>();
+
+        if circuit.get_routes() != Circuit_RouteType::RELAYED_ROUTE {
+            if !relays.is_empty() {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Member relay nodes can only be set if route_type is RELAYED".to_string(),
+                ));
+            }
+            return Ok(());
+        }
+
+        if circuit.get_circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "route_type RELAYED is not support in circuit schema version {}",
+                circuit.get_circuit_version()
+            )));
+        }
+
+        if circuit.get_durability() == Circuit_DurabilityType::GUARANTEED_DELIVERY {
+            return Err(AdminSharedError::ValidationFailed(
+                "durability_type GUARANTEED_DELIVERY is not supported with route_type RELAYED"
+                    .to_string(),
+            ));
+        }
+
+        // relays forward admin messages over the connections between node IDs
+        if circuit.get_authorization_type() != Circuit_AuthorizationType::TRUST_AUTHORIZATION {
+            return Err(AdminSharedError::ValidationFailed(
+                "route_type RELAYED is only supported with authorization_type TRUST".to_string(),
+            ));
+        }
+
+        for (node_id, relay_node_id) in relays.iter() {
+            if !members.iter().any(|member| member == relay_node_id) {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Relay node {} of member {} is not a member of the circuit",
+                    relay_node_id, node_id
+                )));
+            }
+
+            // follow the relays of the relay to make sure messages cannot be relayed in a loop
+            let mut visited = vec![*node_id];
+            let mut current = Some(*relay_node_id);
+            while let Some(relay) = current {
+                if visited.contains(&relay) {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "The relay nodes of member {} form a loop",
+                        node_id
+                    )));
+                }
+                visited.push(relay);
+                current = relays.get(relay).copied();
+            }
+        }
+
+        Ok(())
+    }
+
     fn validate_circuit(&self, circuit: &Circuit) -> Result<(), AdminSharedError> {
         if circuit.get_authorization_type() == Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE {
             return Err(AdminSharedError::ValidationFailed(
@@ -2580,6 +4471,22 @@ impl AdminServiceShared {
             ));
         }
 
+        if circuit.get_durability() == Circuit_DurabilityType::GUARANTEED_DELIVERY {
+            if circuit.get_circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "durability_type GUARANTEED_DELIVERY is not support in circuit schema \
+                    version {}",
+                    circuit.get_circuit_version()
+                )));
+            }
+
+            if cfg!(not(feature = "circuit-durability")) {
+                return Err(AdminSharedError::ValidationFailed(
+                    "durability_type GUARANTEED_DELIVERY is not supported by this node".to_string(),
+                ));
+            }
+        }
+
         if circuit.get_routes() == Circuit_RouteType::UNSET_ROUTE_TYPE {
             return Err(AdminSharedError::ValidationFailed(
                 "route_type cannot be unset".to_string(),
@@ -2666,6 +4573,8 @@ impl AdminServiceShared {
             )));
         }
 
+        self.validate_relays(circuit, &members)?;
+
         if circuit.get_roster().is_empty() {
             return Err(AdminSharedError::ValidationFailed(
                 "The circuit must have services".to_string(),
@@ -2782,6 +4691,20 @@ impl AdminServiceShared {
             )));
         }
 
+        if circuit_proposal.proposal_type() == &ProposalType::AddNode
+            && circuit_proposal
+                .circuit()
+                .members()
+                .last()
+                .map(|member| member.node_id() == node_id)
+                .unwrap_or(false)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Received vote from node being added to the circuit: {}",
+                node_id
+            )));
+        }
+
         let voted_nodes: Vec<String> = circuit_proposal
             .votes()
             .iter()
@@ -2912,32 +4835,40 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    /// Validates a `CircuitPurgeRequest` using the following:
+    /// Validates a `CircuitUpdateRosterRequest` using the following:
     ///
-    /// - Validate the requester is authorized to propose a change on the requesting node
+    /// - Validate the protocol version used by the submitter node. Currently, updating a circuit's
+    ///   roster is only available to nodes using `ADMIN_SERVICE_PROTOCOL_VERSION` 2.
+    /// - Validate the requester is authorized to propose a change for the requesting node
     /// - Validate the signer's public key is authorized for the requesting node
-    /// - Validate the circuit being purged has a valid `circuit_status`.
-    ///   A circuit must have a `circuit_status` of `Disbanded` or `Abandoned` in order to be
-    ///   purged.
-    fn validate_purge_request(
+    /// - Validate a `CircuitProposal` with the same ID is not present
+    /// - Validate the circuit being updated has a valid `circuit_version` and `circuit_status`.
+    ///   A circuit must have a `circuit_version` of at least 2 and a `circuit_status` of `Active`
+    ///   in order to have its roster updated.
+    /// - Validate the request adds or removes at least one service, every service removed is in
+    ///   the circuit's current roster and no service is both added and removed
+    /// - Validate the updated circuit, including the services added to its roster
+    fn validate_update_roster(
         &self,
-        circuit_id: &str,
+        update_roster_request: &CircuitUpdateRosterRequest,
+        circuit: &Circuit,
         signer_public_key: &[u8],
         requester_node_id: &str,
+        protocol: u32,
     ) -> Result<(), AdminSharedError> {
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit-Update-Roster is not available for protocol version {}",
+                protocol
+            )));
+        }
+
         if requester_node_id.is_empty() {
             return Err(AdminSharedError::ValidationFailed(
                 "requester_node_id is empty".to_string(),
             ));
         }
 
-        if requester_node_id != self.node_id {
-            return Err(AdminSharedError::ValidationFailed(format!(
-                "Unable to purge circuit from node {}: request came from node {}",
-                self.node_id, requester_node_id
-            )));
-        }
-
         self.validate_key(signer_public_key)?;
 
         if !self
@@ -2955,16 +4886,22 @@ impl AdminServiceShared {
             .is_permitted(signer_public_key, PROPOSER_ROLE)
             .map_err(|_| {
                 AdminSharedError::ValidationFailed(format!(
-                    "{} is not permitted to propose change for node {}",
+                    "{} is not permitted to update roster for node {}",
                     to_hex(signer_public_key),
                     requester_node_id
                 ))
             })?;
 
-        // Verifying the circuit is `Disbanded` and able to be purged
+        if self.has_proposal(circuit.get_circuit_id())? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring duplicate proposal for circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
         let stored_circuit = self
             .admin_store
-            .get_circuit(circuit_id)
+            .get_circuit(circuit.get_circuit_id())
             .map_err(|err| {
                 AdminSharedError::ValidationFailed(format!(
                     "error occurred when trying to get circuit {}",
@@ -2973,49 +4910,97 @@ impl AdminServiceShared {
             })?
             .ok_or_else(|| {
                 AdminSharedError::ValidationFailed(format!(
-                    "Received purged request for a circuit that does not exist: circuit id {}",
-                    circuit_id
+                    "Received update roster request for a circuit that does not exist: \
+                     circuit id {}",
+                    circuit.get_circuit_id()
                 ))
             })?;
 
-        if stored_circuit.circuit_status() == &StoreCircuitStatus::Active {
+        if stored_circuit.circuit_status() != &StoreCircuitStatus::Active {
             return Err(AdminSharedError::ValidationFailed(format!(
-                "Attempting to purge a circuit that is still active: {}",
-                circuit_id
+                "Attempting to update the roster of an inactive circuit {}",
+                circuit.get_circuit_id()
             )));
         }
 
-        Ok(())
+        if stored_circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to update the roster of a circuit with schema version {}, must be {}",
+                stored_circuit.circuit_version(),
+                CIRCUIT_PROTOCOL_VERSION,
+            )));
+        }
+
+        if update_roster_request.get_add_services().is_empty()
+            && update_roster_request.get_remove_services().is_empty()
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Update roster request must add or remove a service: circuit id {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
+        for removed in update_roster_request.get_remove_services() {
+            if !stored_circuit
+                .roster()
+                .iter()
+                .any(|service| service.service_id() == removed.get_service_id())
+            {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Unable to remove service {}, it is not in the roster of circuit {}",
+                    removed.get_service_id(),
+                    circuit.get_circuit_id()
+                )));
+            }
+
+            if update_roster_request
+                .get_add_services()
+                .iter()
+                .any(|added| added.get_service_id() == removed.get_service_id())
+            {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Service {} cannot be both added to and removed from circuit {}",
+                    removed.get_service_id(),
+                    circuit.get_circuit_id()
+                )));
+            }
+        }
+
+        self.validate_circuit(circuit)
     }
 
-    /// Validate a `CircuitAbandon` payload by the following:
+    /// Validates a `CircuitUpdateAddNodeRequest` using the following:
     ///
+    /// - Validate the protocol version used by the submitter node. Currently, adding a node is
+    ///   only available to nodes using `ADMIN_SERVICE_PROTOCOL_VERSION` 2.
     /// - Validate the requester is authorized to propose a change for the requesting node
     /// - Validate the signer's public key is authorized for the requesting node
-    /// - Validate the circuit being abandoned has a valid `circuit_status`.
-    ///   A circuit must have a `circuit_status` of `Active` in order to be abandoned.
-    ///
-    /// Note: abandoning a circuit on protocol version 1 and circuit version 1 is allowed because
-    /// abandon does not require communication with other nodes.
-    fn validate_abandon_circuit(
+    /// - Validate a `CircuitProposal` with the same ID is not present
+    /// - Validate the circuit exists, is `Active` and the requesting node is a member
+    /// - Validate the node being added is not already a member of the circuit
+    /// - Validate each service in the request either runs on the node being added, or updates an
+    ///   existing service without moving it to another node
+    fn validate_add_node(
         &self,
-        circuit_id: &str,
+        add_node_request: &CircuitUpdateAddNodeRequest,
+        circuit: &Circuit,
         signer_public_key: &[u8],
         requester_node_id: &str,
+        protocol: u32,
     ) -> Result<(), AdminSharedError> {
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit-Update-Add-Node is not available for protocol version {}",
+                protocol
+            )));
+        }
+
         if requester_node_id.is_empty() {
             return Err(AdminSharedError::ValidationFailed(
                 "requester_node_id is empty".to_string(),
             ));
         }
 
-        if requester_node_id != self.node_id {
-            return Err(AdminSharedError::ValidationFailed(format!(
-                "Unable to abandon circuit from node {}: request came from node {}",
-                self.node_id, requester_node_id
-            )));
-        }
-
         self.validate_key(signer_public_key)?;
 
         if !self
@@ -3033,174 +5018,306 @@ impl AdminServiceShared {
             .is_permitted(signer_public_key, PROPOSER_ROLE)
             .map_err(|_| {
                 AdminSharedError::ValidationFailed(format!(
-                    "{} is not permitted to propose change for node {}",
+                    "{} is not permitted to add a node for node {}",
                     to_hex(signer_public_key),
                     requester_node_id
                 ))
             })?;
 
-        // Verifying the circuit is available in the admin store, `Active`, and able to be abandoned
-        let stored_circuit = self
-            .admin_store
-            .get_circuit(circuit_id)
-            .map_err(|err| {
-                AdminSharedError::ValidationFailed(format!(
-                    "error occurred when trying to get circuit {}",
-                    err
-                ))
-            })?
+        if self.has_proposal(circuit.get_circuit_id())? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring duplicate proposal for circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
+        let existing_circuit = self
+            .get_proto_circuit(circuit.get_circuit_id())?
             .ok_or_else(|| {
                 AdminSharedError::ValidationFailed(format!(
-                    "Received abandon request for a circuit that does not exist: circuit id {}",
-                    circuit_id
+                    "Received add node request for a circuit that does not exist: circuit id {}",
+                    circuit.get_circuit_id()
                 ))
             })?;
 
-        if stored_circuit.circuit_status() != &StoreCircuitStatus::Active {
+        if existing_circuit.get_circuit_status() != Circuit_CircuitStatus::ACTIVE {
             return Err(AdminSharedError::ValidationFailed(format!(
-                "Attempting to abandon a circuit that is not active: {}",
-                circuit_id
+                "Attempting to add a node to an inactive circuit {}",
+                circuit.get_circuit_id()
             )));
         }
 
-        Ok(())
-    }
+        if !existing_circuit
+            .get_members()
+            .iter()
+            .any(|member| member.get_node_id() == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id,
+                circuit.get_circuit_id()
+            )));
+        }
 
-    /// Validate a `ProposalRemoveRequest` payload by the following:
-    ///
-    /// - Validate the requester is authorized to propose a change for the requesting node
-    /// - Validate the signer's public key is authorized for the requesting node
-    /// - Validate the proposal being removed exists
-    ///
-    /// Note: removing a proposal on protocol version 1 and circuit version 1 is allowed because
-    /// abandon does not require communication with other nodes.
-    fn validate_remove_proposal(
-        &self,
-        circuit_id: &str,
-        signer_public_key: &[u8],
-        requester_node_id: &str,
-    ) -> Result<(), AdminSharedError> {
-        if requester_node_id.is_empty() {
+        let added_node_id = add_node_request.get_node().get_node_id();
+        if added_node_id.is_empty() {
             return Err(AdminSharedError::ValidationFailed(
-                "requester_node_id is empty".to_string(),
+                "Node being added to the circuit must have a node id".to_string(),
             ));
         }
 
-        if requester_node_id != self.node_id {
+        if existing_circuit
+            .get_members()
+            .iter()
+            .any(|member| member.get_node_id() == added_node_id)
+        {
             return Err(AdminSharedError::ValidationFailed(format!(
-                "Unable to remove proposal from node {}: request came from node {}",
-                self.node_id, requester_node_id
+                "Node {} is already a member of circuit {}",
+                added_node_id,
+                circuit.get_circuit_id()
             )));
         }
 
-        self.validate_key(signer_public_key)?;
+        for service in add_node_request.get_services() {
+            let existing_service = existing_circuit
+                .get_roster()
+                .iter()
+                .find(|existing| existing.get_service_id() == service.get_service_id());
 
-        if !self
-            .key_verifier
-            .is_permitted(requester_node_id, signer_public_key)?
-        {
-            return Err(AdminSharedError::ValidationFailed(format!(
-                "{} is not registered for the requester node {}",
-                to_hex(signer_public_key),
-                requester_node_id,
-            )));
-        }
+            let is_valid = match existing_service {
+                Some(existing) => service.get_allowed_nodes() == existing.get_allowed_nodes(),
+                None => service.get_allowed_nodes() == [added_node_id.to_string()],
+            };
+
+            if !is_valid {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Service {} must either run on the node being added or update an existing \
+                     service: circuit id {}",
+                    service.get_service_id(),
+                    circuit.get_circuit_id()
+                )));
+            }
+        }
+
+        self.validate_circuit(circuit)
+    }
+
+    /// Validates a `CircuitUpdateRemoveNodeRequest` using the following:
+    ///
+    /// - Validate the protocol version used by the submitter node. Currently, removing a node is
+    ///   only available to nodes using `ADMIN_SERVICE_PROTOCOL_VERSION` 2.
+    /// - Validate the requester is authorized to propose a change for the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate a `CircuitProposal` with the same ID is not present
+    /// - Validate the circuit exists, is `Active` and the requesting node is a member
+    /// - Validate the node being removed is a member of the circuit and is not the requesting node
+    /// - Validate at least two members remain in the circuit once the node is removed, so a single
+    ///   member cannot remove the only other member on its own
+    /// - Validate each service in the request updates a service that remains in the circuit,
+    ///   without moving it to another node
+    fn validate_remove_node(
+        &self,
+        remove_node_request: &CircuitUpdateRemoveNodeRequest,
+        circuit: &Circuit,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit-Update-Remove-Node is not available for protocol version {}",
+                protocol
+            )));
+        }
+
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
 
         self.key_permission_manager
             .is_permitted(signer_public_key, PROPOSER_ROLE)
             .map_err(|_| {
                 AdminSharedError::ValidationFailed(format!(
-                    "{} is not permitted to propose change for node {}",
+                    "{} is not permitted to remove a node for node {}",
                     to_hex(signer_public_key),
                     requester_node_id
                 ))
             })?;
 
-        if self.get_proposal(circuit_id)?.is_none() {
+        if self.has_proposal(circuit.get_circuit_id())? {
             return Err(AdminSharedError::ValidationFailed(format!(
-                "Attempting to remove proposal for circuit {} that does not exist",
-                &circuit_id,
+                "Ignoring duplicate proposal for circuit {}",
+                circuit.get_circuit_id()
             )));
         }
 
-        Ok(())
-    }
-
-    fn validate_circuit_management_payload(
-        &self,
-        payload: &CircuitManagementPayload,
-        header: &CircuitManagementPayload_Header,
-    ) -> Result<(), AdminSharedError> {
-        // Validate payload signature
-        if payload.get_signature().is_empty() {
-            return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload signature must be set".to_string(),
-            ));
-        };
-
-        // Validate the payload header
-        if payload.get_header().is_empty() {
-            return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload header must be set".to_string(),
-            ));
-        };
-
-        // Validate the header, requester field is set
-        if header.get_requester().is_empty() {
-            return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload must have a requester".to_string(),
-            ));
-        };
+        let stored_circuit = self
+            .admin_store
+            .get_circuit(circuit.get_circuit_id())
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received remove node request for a circuit that does not exist: \
+                     circuit id {}",
+                    circuit.get_circuit_id()
+                ))
+            })?;
 
-        self.validate_key(header.get_requester())?;
+        if stored_circuit.circuit_status() != &StoreCircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to remove a node from an inactive circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
 
-        // Validate the header, requester_node_id is set
-        if header.get_requester_node_id().is_empty() {
-            return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload must have a requester node id".to_string(),
-            ));
-        };
+        if !stored_circuit
+            .members()
+            .iter()
+            .any(|member| member.node_id() == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id,
+                circuit.get_circuit_id()
+            )));
+        }
 
-        Ok(())
-    }
+        let removed_node_id = remove_node_request.get_node_id();
+        if !stored_circuit
+            .members()
+            .iter()
+            .any(|member| member.node_id() == removed_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} is not a member of circuit {}",
+                removed_node_id,
+                circuit.get_circuit_id()
+            )));
+        }
 
-    fn check_approved(&self, proposal: &CircuitProposal) -> CircuitProposalStatus {
-        let mut received_votes = HashSet::new();
-        for vote in proposal.get_votes() {
-            if vote.get_vote() == CircuitProposalVote_Vote::REJECT {
-                return CircuitProposalStatus::Rejected;
-            }
-            received_votes.insert(vote.get_voter_node_id().to_string());
+        if removed_node_id == requester_node_id {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} cannot propose its own removal from circuit {}",
+                requester_node_id,
+                circuit.get_circuit_id()
+            )));
         }
 
-        let mut required_votes = proposal
-            .get_circuit_proposal()
-            .get_members()
-            .to_vec()
+        let remaining_members = stored_circuit
+            .members()
             .iter()
-            .map(|member| member.get_node_id().to_string())
-            .collect::<HashSet<String>>();
+            .filter(|member| member.node_id() != removed_node_id)
+            .count();
+        if remaining_members < 2 {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} cannot be removed, circuit {} must keep at least two members",
+                removed_node_id,
+                circuit.get_circuit_id()
+            )));
+        }
 
-        required_votes.remove(proposal.get_requester_node_id());
+        for service in remove_node_request.get_services() {
+            let is_valid = stored_circuit.roster().iter().any(|existing| {
+                existing.service_id() == service.get_service_id()
+                    && existing.node_id() != removed_node_id
+                    && service.get_allowed_nodes() == [existing.node_id().to_string()]
+            });
 
-        if required_votes == received_votes {
-            CircuitProposalStatus::Accepted
-        } else {
-            CircuitProposalStatus::Pending
+            if !is_valid {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Service {} must update a service that remains in circuit {}",
+                    service.get_service_id(),
+                    circuit.get_circuit_id()
+                )));
+            }
         }
+
+        self.validate_circuit(circuit)
     }
 
-    /// Makes the `CircuitProposal` associated with a `CircuitDisbandRequest` based on information
-    /// gathered from the currently active circuit that is specified in the disband request
-    fn make_disband_request_circuit_proposal(
+    /// Validates a `CircuitUpdateApplicationMetadataRequest` using the following:
+    ///
+    /// - Validate the protocol version used by the submitter node. Currently, updating the
+    ///   application metadata is only available to nodes using `ADMIN_SERVICE_PROTOCOL_VERSION` 2.
+    /// - Validate the requester is authorized to propose a change for the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate a `CircuitProposal` with the same ID is not present
+    /// - Validate the circuit exists, is `Active` and the requesting node is a member
+    /// - Validate the request changes the circuit's application metadata
+    fn validate_update_application_metadata(
         &self,
-        circuit_id: &str,
-        requester: &[u8],
+        update_metadata_request: &CircuitUpdateApplicationMetadataRequest,
+        circuit: &Circuit,
+        signer_public_key: &[u8],
         requester_node_id: &str,
-    ) -> Result<CircuitProposal, AdminSharedError> {
-        let store_circuit = self
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit-Update-Application-Metadata is not available for protocol version {}",
+                protocol
+            )));
+        }
+
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
+
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to update application metadata for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        if self.has_proposal(circuit.get_circuit_id())? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring duplicate proposal for circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
+        let stored_circuit = self
             .admin_store
-            .get_circuit(circuit_id)
+            .get_circuit(circuit.get_circuit_id())
             .map_err(|err| {
                 AdminSharedError::ValidationFailed(format!(
                     "error occurred when trying to get circuit {}",
@@ -3208,556 +5325,2684 @@ impl AdminServiceShared {
                 ))
             })?
             .ok_or_else(|| {
-                AdminSharedError::SplinterStateError(format!(
-                    "Unable to get circuit: {}",
-                    circuit_id
+                AdminSharedError::ValidationFailed(format!(
+                    "Received update application metadata request for a circuit that does not \
+                     exist: circuit id {}",
+                    circuit.get_circuit_id()
                 ))
             })?;
-        // Collecting the endpoints of the nodes apart of the circuit being disbanded
-        let circuit_members = store_circuit
+
+        if stored_circuit.circuit_status() != &StoreCircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to update the application metadata of an inactive circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
+        if !stored_circuit
             .members()
             .iter()
-            .map(|circuit_node| messages::SplinterNode {
-                node_id: circuit_node.node_id().to_string(),
-                endpoints: circuit_node.endpoints().to_vec(),
-                public_key: circuit_node
-                    .public_key()
-                    .clone()
-                    .map(|public_key| public_key.into_bytes()),
-            })
-            .collect::<Vec<messages::SplinterNode>>();
-        let mut create_circuit_builder = messages::CreateCircuitBuilder::new()
-            .with_circuit_id(circuit_id)
-            .with_roster(
-                store_circuit
-                    .roster()
-                    .iter()
-                    .map(|service| messages::SplinterService {
-                        service_id: service.service_id().into(),
-                        service_type: service.service_type().into(),
-                        allowed_nodes: vec![service.node_id().to_string()],
-                        arguments: service
-                            .arguments()
-                            .iter()
-                            .map(|(key, value)| (key.to_string(), value.to_string()))
-                            .collect(),
-                    })
-                    .collect::<Vec<messages::SplinterService>>()
-                    .as_ref(),
-            )
-            .with_members(circuit_members.as_ref())
-            .with_authorization_type(&messages::AuthorizationType::from(
-                store_circuit.authorization_type(),
-            ))
-            .with_persistence(&messages::PersistenceType::from(
-                store_circuit.persistence(),
-            ))
-            .with_durability(&messages::DurabilityType::from(store_circuit.durability()))
-            .with_routes(&messages::RouteType::from(store_circuit.routes()))
-            .with_circuit_management_type(store_circuit.circuit_management_type())
-            .with_circuit_version(store_circuit.circuit_version())
-            .with_circuit_status(&messages::CircuitStatus::Disbanded);
+            .any(|member| member.node_id() == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id,
+                circuit.get_circuit_id()
+            )));
+        }
 
-        if let Some(display_name) = store_circuit.display_name() {
-            create_circuit_builder = create_circuit_builder.with_display_name(display_name);
+        let current_metadata = stored_circuit
+            .application_metadata()
+            .as_deref()
+            .unwrap_or_default();
+        if update_metadata_request.get_application_metedata() == current_metadata {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Update application metadata request must change the application metadata: \
+                 circuit id {}",
+                circuit.get_circuit_id()
+            )));
         }
 
-        let proposed_circuit: Circuit = create_circuit_builder
-            .build()
+        self.validate_circuit(circuit)
+    }
+
+    /// Validates a `CircuitPurgeRequest` using the following:
+    ///
+    /// - Validate the requester is authorized to propose a change on the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate the circuit being purged has a valid `circuit_status`.
+    ///   A circuit must have a `circuit_status` of `Disbanded` or `Abandoned` in order to be
+    ///   purged.
+    fn validate_purge_request(
+        &self,
+        circuit_id: &str,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        if requester_node_id != self.node_id {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Unable to purge circuit from node {}: request came from node {}",
+                self.node_id, requester_node_id
+            )));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
+
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose change for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        // Verifying the circuit is `Disbanded` and able to be purged
+        let stored_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
             .map_err(|err| {
                 AdminSharedError::ValidationFailed(format!(
-                    "error occurred when trying to build circuit {}",
+                    "error occurred when trying to get circuit {}",
                     err
                 ))
             })?
-            .try_into()
-            .map_err(|err| {
+            .ok_or_else(|| {
                 AdminSharedError::ValidationFailed(format!(
-                    "error occurred when trying to create proto circuit {}",
-                    err
+                    "Received purged request for a circuit that does not exist: circuit id {}",
+                    circuit_id
                 ))
             })?;
 
-        let mut circuit_proposal = CircuitProposal::new();
-        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
-        circuit_proposal.set_circuit_id(circuit_id.to_string());
-        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
-        circuit_proposal.set_circuit_proposal(proposed_circuit);
-        circuit_proposal.set_requester(requester.to_vec());
-        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
+        if stored_circuit.circuit_status() == &StoreCircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to purge a circuit that is still active: {}",
+                circuit_id
+            )));
+        }
 
-        Ok(circuit_proposal)
+        Ok(())
     }
 
-    /// Makes a `Circuit` and `StoreCircuit` with an `Abandoned` `circuit_status` to be used to
-    /// update circuit state to reflect the abandoning change
-    fn make_abandoned_circuit(
+    /// Validate a `CircuitAbandon` payload by the following:
+    ///
+    /// - Validate the requester is authorized to propose a change for the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate the circuit being abandoned has a valid `circuit_status`.
+    ///   A circuit must have a `circuit_status` of `Active` in order to be abandoned.
+    ///
+    /// Note: abandoning a circuit on protocol version 1 and circuit version 1 is allowed because
+    /// abandon does not require communication with other nodes.
+    fn validate_abandon_circuit(
         &self,
-        store_circuit: &StoreCircuit,
-    ) -> Result<(Circuit, StoreCircuit), AdminSharedError> {
-        // Collecting the endpoints of the nodes apart of the circuit being abandoned
-        let circuit_members = store_circuit
-            .members()
-            .iter()
-            .map(|circuit_node| {
-                let mut node = SplinterNode::new();
-                node.set_node_id(circuit_node.node_id().to_string());
-                node.set_endpoints(RepeatedField::from_vec(circuit_node.endpoints().to_vec()));
-                {
-                    if let Some(public_key) = circuit_node.public_key() {
-                        node.set_public_key(public_key.clone().into_bytes());
-                    }
-                }
-                node
-            })
-            .collect::<Vec<SplinterNode>>();
+        circuit_id: &str,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
 
-        let services: Vec<SplinterService> = store_circuit
-            .roster()
-            .iter()
-            .map(|store_service| {
-                let mut service = SplinterService::new();
-                service.set_service_id(store_service.service_id().to_string());
-                service.set_service_type(store_service.service_type().to_string());
-                service.set_allowed_nodes(RepeatedField::from_vec(vec![store_service
-                    .node_id()
-                    .to_string()]));
-                service
-            })
-            .collect::<Vec<SplinterService>>();
-        let mut circuit = Circuit::new();
-        circuit.set_circuit_id(store_circuit.circuit_id().to_string());
-        circuit.set_roster(RepeatedField::from_vec(services));
-        circuit.set_members(RepeatedField::from_vec(circuit_members));
-        circuit.set_authorization_type(Circuit_AuthorizationType::from(
-            store_circuit.authorization_type(),
-        ));
-        circuit.set_persistence(Circuit_PersistenceType::from(store_circuit.persistence()));
-        circuit.set_durability(Circuit_DurabilityType::from(store_circuit.durability()));
-        circuit.set_routes(Circuit_RouteType::from(store_circuit.routes()));
-        circuit.set_circuit_management_type(store_circuit.circuit_management_type().to_string());
-        if let Some(display) = store_circuit.display_name() {
-            circuit.set_display_name(display.to_string());
+        if requester_node_id != self.node_id {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Unable to abandon circuit from node {}: request came from node {}",
+                self.node_id, requester_node_id
+            )));
         }
-        circuit.set_circuit_version(store_circuit.circuit_version());
-        circuit.set_circuit_status(Circuit_CircuitStatus::from(store_circuit.circuit_status()));
 
-        // Creating the `Abandoned` StoreCircuit
-        let mut store_circuit = StoreCircuitBuilder::new()
-            .with_circuit_id(store_circuit.circuit_id())
-            .with_roster(store_circuit.roster())
-            .with_members(store_circuit.members())
-            .with_authorization_type(store_circuit.authorization_type())
-            .with_persistence(store_circuit.persistence())
-            .with_durability(store_circuit.durability())
-            .with_routes(store_circuit.routes())
-            .with_circuit_management_type(store_circuit.circuit_management_type())
-            .with_circuit_version(store_circuit.circuit_version())
-            .with_circuit_status(&StoreCircuitStatus::Abandoned);
-        if let Some(display_name) = store_circuit.display_name() {
-            store_circuit = store_circuit.with_display_name(&display_name);
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
         }
 
-        Ok((
-            circuit,
-            store_circuit.build().map_err(|err| {
-                AdminSharedError::SplinterStateError(format!(
-                    "error occurred when trying to build circuit {}",
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose change for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        // Verifying the circuit is available in the admin store, `Active`, and able to be abandoned
+        let stored_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
                     err
                 ))
-            })?,
-        ))
-    }
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received abandon request for a circuit that does not exist: circuit id {}",
+                    circuit_id
+                ))
+            })?;
 
-    /// Initialize all services that this node should run on the created circuit using the
-    /// lifecycle dispatch. This may not include all services if they are not supported locally.
-    //  It is expected that some services will be started externally.
-    pub fn initialize_services(&mut self, circuit: &Circuit) -> Result<(), AdminSharedError> {
-        // Start all services the lifecycle dispatches can
-        for service in circuit.get_roster() {
-            if !service.allowed_nodes.contains(&self.node_id) {
-                continue;
-            }
+        if stored_circuit.circuit_status() != &StoreCircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to abandon a circuit that is not active: {}",
+                circuit_id
+            )));
+        }
 
-            let service_arguments: Vec<(String, String)> = service
-                .arguments
-                .iter()
-                .map(|arg| (arg.key.clone(), arg.value.clone()))
-                .collect();
+        Ok(())
+    }
 
-            for dispatch in &self.lifecycle_dispatch {
-                dispatch
-                    .add_service(
-                        &circuit.circuit_id,
-                        &service.service_id,
-                        &service.service_type,
-                        service_arguments.clone(),
-                    )
-                    .map_err(|err| {
-                        error!("{}", err);
-                        AdminSharedError::ServiceInitializationFailed {
-                            context: format!(
-                                "Unable to start service {} on circuit {}",
-                                service.service_id, circuit.circuit_id
-                            ),
-                            source: None,
-                        }
-                    })?;
-            }
+    /// Validate a `ProposalRemoveRequest` payload by the following:
+    ///
+    /// - Validate the requester is authorized to propose a change for the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate the proposal being removed exists
+    ///
+    /// Note: removing a proposal on protocol version 1 and circuit version 1 is allowed because
+    /// abandon does not require communication with other nodes.
+    fn validate_remove_proposal(
+        &self,
+        circuit_id: &str,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
         }
 
-        Ok(())
-    }
+        if requester_node_id != self.node_id {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Unable to remove proposal from node {}: request came from node {}",
+                self.node_id, requester_node_id
+            )));
+        }
 
-    /// Stops all services that this node was running on the disbanded or abandoned circuit using
-    /// the service lifecycle dispatch. This may not include all services if they are not supported
-    /// locally. It is expected that some services will be stopped externally.
-    pub fn stop_services(&mut self, circuit: &Circuit) -> Result<(), AdminSharedError> {
-        // Stop all services the lifecycle dispatches can
-        for service in circuit.get_roster() {
-            if !service.allowed_nodes.contains(&self.node_id) {
-                continue;
-            }
+        self.validate_key(signer_public_key)?;
 
-            for dispatch in &self.lifecycle_dispatch {
-                dispatch
-                    .retire_service(
-                        &circuit.circuit_id,
-                        &service.service_id,
-                        &service.service_type,
-                    )
-                    .map_err(|err| {
-                        error!("{}", err);
-                        AdminSharedError::ServiceShutdownFailed {
-                            context: format!(
-                                "Unable to shutdown service {} on circuit {}",
-                                service.service_id, circuit.circuit_id
-                            ),
-                            source: None,
-                        }
-                    })?;
-            }
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
         }
-        Ok(())
-    }
 
-    /// Purges all services that this node was running on the disbanded circuit using the service
-    /// lifecycle dispatch. Destroying a service will also remove the service's state LMDB files.
-    pub fn purge_services(
-        &mut self,
-        circuit_id: &str,
-        services: &[StoreService],
-    ) -> Result<(), AdminSharedError> {
-        for service in services {
-            if service.node_id() != self.node_id() {
-                continue;
-            }
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose change for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
 
-            for dispatch in &self.lifecycle_dispatch {
-                if let Err(err) =
-                    dispatch.purge_service(circuit_id, service.service_id(), service.service_type())
-                {
-                    error!(
-                        "Service {}::{} ({}) failed to purge: {}",
-                        circuit_id,
-                        service.service_id(),
-                        service.service_type(),
-                        err
-                    );
-                }
-            }
+        if self.get_proposal(circuit_id)?.is_none() {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to remove proposal for circuit {} that does not exist",
+                &circuit_id,
+            )));
         }
 
         Ok(())
     }
 
-    pub fn shutdown_all_services(&self) {
-        for dispatch in &self.lifecycle_dispatch {
-            if let Err(err) = dispatch.shutdown_all_services() {
-                error!("{}", err)
-            }
-        }
-    }
-
-    /// Verify all members are ready before cleaning up after the disbanded circuit, i.e. removing
-    /// peer refs, removing the circuit from the routing table, and shutting down the circuit's
-    /// associated services.
-    pub fn cleanup_disbanded_circuit_if_members_ready(
-        &mut self,
-        circuit_id: &str,
+    /// Validates a request to expire a proposal. The request must be for the current version of
+    /// the proposal and must come from a member of the proposed circuit. An expiry that this node
+    /// requested must be signed with one of this node's keys; the admin service checks that a
+    /// remote expiry was sent by the requesting node when the proposal is received.
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    fn validate_proposal_expire(
+        &self,
+        expire_request: &CircuitProposalExpire,
+        signer_public_key: &[u8],
+        circuit_proposal: &StoreProposal,
+        requester_node_id: &str,
     ) -> Result<(), AdminSharedError> {
-        let ready = {
-            if let Some(disbanded_circuit) = self.uninitialized_circuits.get(circuit_id) {
-                if let Some(ref circuit_proposal) = disbanded_circuit.circuit {
-                    let all_members = circuit_proposal
-                        .get_circuit_proposal()
-                        .members
-                        .iter()
-                        .map(|node| node.node_id.clone())
-                        .collect::<HashSet<String>>();
-                    all_members.is_subset(&disbanded_circuit.ready_members)
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        };
-
-        if ready {
-            let mut circuit_proposal = self
-                .uninitialized_circuits
-                .remove(circuit_id)
-                .expect("Pending disband circuit not set")
-                .circuit
-                .expect("Pending disband circuit's circuit proposal not set");
-            // send message about circuit acceptance
-            let circuit_proposal_proto =
-                messages::CircuitProposal::from_proto(circuit_proposal.clone())
-                    .map_err(AdminSharedError::InvalidMessageFormat)?;
-            let mgmt_type = circuit_proposal
-                .get_circuit_proposal()
-                .circuit_management_type
-                .clone();
-            let event = messages::AdminServiceEvent::CircuitDisbanded(circuit_proposal_proto);
-            self.send_event(&mgmt_type, event);
+        if expire_request.get_circuit_hash() != circuit_proposal.circuit_hash() {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Expiry is for a different version of the proposal for circuit {}",
+                circuit_proposal.circuit_id()
+            )));
+        }
 
-            // Circuit has been disbanded: all associated services will be shut
-            // down, the circuit removed from the routing table, and peer refs
-            // for this circuit will be removed.
-            self.stop_services(circuit_proposal.get_circuit_proposal())?;
-            // Removing the circuit from the routing table
-            self.routing_table_writer
-                .remove_circuit(circuit_proposal.get_circuit_id())
-                .map_err(|_| {
-                    AdminSharedError::SplinterStateError(format!(
-                        "Unable to remove circuit from routing table: {}",
-                        circuit_id
-                    ))
-                })?;
+        if !circuit_proposal
+            .circuit()
+            .members()
+            .iter()
+            .any(|member| member.node_id() == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} is not a member of the proposed circuit {} and cannot expire it",
+                requester_node_id,
+                circuit_proposal.circuit_id()
+            )));
+        }
 
-            let proposed_circuit = ProposedCircuit::from_proto(
-                circuit_proposal.take_circuit_proposal(),
-            )
-            .map_err(|err| {
-                AdminSharedError::SplinterStateError(format!(
-                    "Unable to get store proposed circuit from proto: {}",
-                    err
-                ))
-            })?;
-            // Removing the circuit's peer refs
-            self.remove_peer_refs(proposed_circuit.list_tokens(&self.node_id).map_err(|err| {
-                AdminSharedError::SplinterStateError(format!(
-                    "Unable to remove peer refs for proposal {}: {}",
-                    proposed_circuit.circuit_id(),
-                    err
+        if requester_node_id == self.node_id
+            && !self
+                .public_keys
+                .contains(&public_key::PublicKey::from_bytes(
+                    signer_public_key.to_vec(),
                 ))
-            })?);
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Expiry for the proposal for circuit {} was not signed by this node",
+                circuit_proposal.circuit_id()
+            )));
         }
 
         Ok(())
     }
 
-    /// Collect all circuits from the admin store, including `Disbanded` or `Abandoned` circuits
-    pub fn get_circuits(
+    fn validate_circuit_management_payload(
         &self,
-    ) -> Result<Box<dyn ExactSizeIterator<Item = StoreCircuit>>, AdminSharedError> {
-        let predicates = vec![
-            CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active),
-            CircuitPredicate::CircuitStatus(StoreCircuitStatus::Disbanded),
-            CircuitPredicate::CircuitStatus(StoreCircuitStatus::Abandoned),
-        ];
-        self.admin_store
-            .list_circuits(&predicates)
-            .map_err(AdminSharedError::from)
-    }
+        payload: &CircuitManagementPayload,
+        header: &CircuitManagementPayload_Header,
+    ) -> Result<(), AdminSharedError> {
+        // Validate payload signature
+        if payload.get_signature().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload signature must be set".to_string(),
+            ));
+        };
 
-    fn verify_signature(&self, payload: &CircuitManagementPayload) -> Result<bool, ServiceError> {
-        let header: CircuitManagementPayload_Header =
-            Message::parse_from_bytes(payload.get_header())?;
+        // Validate the payload header
+        if payload.get_header().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload header must be set".to_string(),
+            ));
+        };
 
-        let signature = payload.get_signature().to_vec();
-        let public_key = header.get_requester().to_vec();
+        // Validate the header, requester field is set
+        if header.get_requester().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload must have a requester".to_string(),
+            ));
+        };
 
-        self.signature_verifier
-            .verify(
-                payload.get_header(),
-                &Signature::new(signature),
-                &PublicKey::new(public_key),
-            )
-            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+        self.validate_key(header.get_requester())?;
+
+        // Validate the header, requester_node_id is set
+        if header.get_requester_node_id().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload must have a requester node id".to_string(),
+            ));
+        };
+
+        // Validate the request for the header's action is set
+        match header.get_action() {
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST
+                if !payload.has_circuit_update_roster_request() =>
+            {
+                Err(AdminSharedError::ValidationFailed(
+                    "CircuitManagementPayload must have a circuit update roster request"
+                        .to_string(),
+                ))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE
+                if !payload.has_circuit_update_add_node() =>
+            {
+                Err(AdminSharedError::ValidationFailed(
+                    "CircuitManagementPayload must have a circuit update add node request"
+                        .to_string(),
+                ))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE
+                if !payload.has_circuit_update_remove_node() =>
+            {
+                Err(AdminSharedError::ValidationFailed(
+                    "CircuitManagementPayload must have a circuit update remove node request"
+                        .to_string(),
+                ))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST
+                if !payload.has_circuit_update_application_metadata_request() =>
+            {
+                Err(AdminSharedError::ValidationFailed(
+                    "CircuitManagementPayload must have a circuit update application metadata \
+                     request"
+                        .to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
     }
-}
 
-// This should never return an error since we recieved a message from this service id
-pub fn get_peer_token_from_service_id(
-    service_id: &str,
-    local_node_id: &str,
-) -> Result<PeerTokenPair, InternalError> {
-    let mut iter = service_id.split("::");
+    fn check_approved(&self, proposal: &CircuitProposal) -> CircuitProposalStatus {
+        let mut received_votes = HashSet::new();
+        for vote in proposal.get_votes() {
+            if vote.get_vote() == CircuitProposalVote_Vote::REJECT {
+                return CircuitProposalStatus::Rejected;
+            }
+            received_votes.insert(vote.get_voter_node_id().to_string());
+        }
 
-    let admin_prefix = iter
-        .next()
-        .expect("str::split cannot return an empty iterator")
-        .to_string();
+        let mut required_votes = proposal
+            .get_circuit_proposal()
+            .get_members()
+            .to_vec()
+            .iter()
+            .map(|member| member.get_node_id().to_string())
+            .collect::<HashSet<String>>();
 
-    if admin_prefix.is_empty() {
-        return Err(InternalError::with_message(
-            "Empty admin_id argument detected".into(),
-        ));
-    }
+        required_votes.remove(proposal.get_requester_node_id());
 
-    let node_id = iter
-        .next()
-        .ok_or_else(|| InternalError::with_message("Missing node id for recipient".into()))?;
+        // Only the existing members of a circuit vote on adding a node to the circuit; the node
+        // being added is always the last member of the proposed circuit
+        if proposal.get_proposal_type() == CircuitProposal_ProposalType::ADD_NODE {
+            if let Some(added_node) = proposal.get_circuit_proposal().get_members().last() {
+                required_votes.remove(added_node.get_node_id());
+            }
+        }
 
-    if node_id.is_empty() {
-        return Err(InternalError::with_message("Empty node id provided".into()));
+        if required_votes == received_votes {
+            CircuitProposalStatus::Accepted
+        } else {
+            CircuitProposalStatus::Pending
+        }
     }
 
-    // If challenge authorization the admin id will be in the format
-    // admin::public_key::<public key string>.
-    if node_id == ADMIN_SERVICE_PUBLIC_KEY_PREFIX {
-        let public_key = iter
-            .next()
-            .ok_or_else(|| InternalError::with_message("Missing public key for recipient".into()))?
-            .to_string();
+    /// Makes the `CircuitProposal` associated with a `CircuitDisbandRequest` based on information
+    /// gathered from the currently active circuit that is specified in the disband request
+    fn make_disband_request_circuit_proposal(
+        &self,
+        circuit_id: &str,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let store_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get circuit: {}",
+                    circuit_id
+                ))
+            })?;
+        // Collecting the endpoints of the nodes apart of the circuit being disbanded
+        let circuit_members = store_circuit
+            .members()
+            .iter()
+            .map(|circuit_node| messages::SplinterNode {
+                node_id: circuit_node.node_id().to_string(),
+                endpoints: circuit_node.endpoints().to_vec(),
+                public_key: circuit_node
+                    .public_key()
+                    .clone()
+                    .map(|public_key| public_key.into_bytes()),
+                relay_node_id: circuit_node.relay_node_id().clone(),
+            })
+            .collect::<Vec<messages::SplinterNode>>();
+        let mut create_circuit_builder = messages::CreateCircuitBuilder::new()
+            .with_circuit_id(circuit_id)
+            .with_roster(
+                store_circuit
+                    .roster()
+                    .iter()
+                    .map(|service| messages::SplinterService {
+                        service_id: service.service_id().into(),
+                        service_type: service.service_type().into(),
+                        allowed_nodes: vec![service.node_id().to_string()],
+                        arguments: service
+                            .arguments()
+                            .iter()
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .collect(),
+                    })
+                    .collect::<Vec<messages::SplinterService>>()
+                    .as_ref(),
+            )
+            .with_members(circuit_members.as_ref())
+            .with_authorization_type(&messages::AuthorizationType::from(
+                store_circuit.authorization_type(),
+            ))
+            .with_persistence(&messages::PersistenceType::from(
+                store_circuit.persistence(),
+            ))
+            .with_durability(&messages::DurabilityType::from(store_circuit.durability()))
+            .with_routes(&messages::RouteType::from(store_circuit.routes()))
+            .with_circuit_management_type(store_circuit.circuit_management_type())
+            .with_circuit_version(store_circuit.circuit_version())
+            .with_circuit_status(&messages::CircuitStatus::Disbanded);
 
-        if public_key.is_empty() {
-            return Err(InternalError::with_message(
-                "Empty public key provided".into(),
-            ));
+        if let Some(display_name) = store_circuit.display_name() {
+            create_circuit_builder = create_circuit_builder.with_display_name(display_name);
         }
 
-        let second_public_key = iter.next().ok_or_else(|| {
-            InternalError::with_message("Missing local public key for recipient".into())
-        })?;
-
-        if second_public_key != ADMIN_SERVICE_PUBLIC_KEY_PREFIX {
-            return Err(InternalError::with_message(
-                "Missing local public key for recipient".into(),
-            ));
+        if let Some(application_metadata) = store_circuit.application_metadata() {
+            create_circuit_builder =
+                create_circuit_builder.with_application_metadata(application_metadata);
         }
 
-        let local_public_key = iter
-            .next()
-            .ok_or_else(|| {
-                InternalError::with_message("Missing local_public key for recipient".into())
+        let proposed_circuit: Circuit = create_circuit_builder
+            .build()
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to build circuit {}",
+                    err
+                ))
             })?
-            .to_string();
+            .try_into()
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to create proto circuit {}",
+                    err
+                ))
+            })?;
 
-        if local_public_key.is_empty() {
-            return Err(InternalError::with_message(
-                "Empty local public key provided".into(),
-            ));
-        }
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        circuit_proposal.set_circuit_id(circuit_id.to_string());
+        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+        circuit_proposal.set_circuit_proposal(proposed_circuit);
+        circuit_proposal.set_requester(requester.to_vec());
+        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
 
-        Ok(PeerTokenPair::new(
-            PeerAuthorizationToken::from_public_key(
-                &parse_hex(&public_key)
-                    .map_err(|err| InternalError::with_message(err.to_string()))?,
-            ),
-            PeerAuthorizationToken::from_public_key(
-                &parse_hex(&local_public_key)
-                    .map_err(|err| InternalError::with_message(err.to_string()))?,
-            ),
-        ))
-    } else {
-        Ok(PeerTokenPair::new(
-            PeerAuthorizationToken::from_peer_id(node_id),
-            PeerAuthorizationToken::from_peer_id(local_node_id),
-        ))
+        Ok(circuit_proposal)
     }
-}
 
-#[cfg(all(test, feature = "sqlite"))]
-mod tests {
-    use super::*;
+    /// Makes the `CircuitProposal` associated with a `CircuitUpdateRosterRequest` based on the
+    /// currently active circuit, with the requested services removed from and added to its roster
+    fn make_update_roster_circuit_proposal(
+        &self,
+        update_roster_request: &CircuitUpdateRosterRequest,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let circuit_id = update_roster_request.get_circuit_id();
+        let store_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received update roster request for a circuit that does not exist: \
+                     circuit id {}",
+                    circuit_id
+                ))
+            })?;
 
-    use std::sync::{Arc, Mutex};
+        let mut proposed_circuit = make_proto_circuit(&store_circuit)?;
+        let mut roster = proposed_circuit.take_roster().into_vec();
+        roster.retain(|service| {
+            !update_roster_request
+                .get_remove_services()
+                .iter()
+                .any(|removed| removed.get_service_id() == service.get_service_id())
+        });
+        roster.extend(update_roster_request.get_add_services().iter().cloned());
+        proposed_circuit.set_roster(RepeatedField::from_vec(roster));
+
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::UPDATE_ROSTER);
+        circuit_proposal.set_circuit_id(circuit_id.to_string());
+        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+        circuit_proposal.set_circuit_proposal(proposed_circuit);
+        circuit_proposal.set_requester(requester.to_vec());
+        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
+
+        Ok(circuit_proposal)
+    }
+
+    /// Makes the `CircuitProposal` associated with a `CircuitUpdateAddNodeRequest`, based on the
+    /// current definition of the circuit. The node being added is appended to the circuit's
+    /// members, the services in the request are added to or replace those in the roster, and the
+    /// circuit's schema version is set to the current version.
+    fn make_add_node_circuit_proposal(
+        &self,
+        add_node_request: &CircuitUpdateAddNodeRequest,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let circuit_id = add_node_request.get_circuit_id();
+        let mut proposed_circuit = self.get_proto_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received add node request for a circuit that does not exist: circuit id {}",
+                circuit_id
+            ))
+        })?;
+
+        proposed_circuit
+            .mut_members()
+            .push(add_node_request.get_node().clone());
+
+        let mut roster = proposed_circuit.take_roster().into_vec();
+        for service in add_node_request.get_services() {
+            match roster
+                .iter_mut()
+                .find(|existing| existing.get_service_id() == service.get_service_id())
+            {
+                Some(existing) => *existing = service.clone(),
+                None => roster.push(service.clone()),
+            }
+        }
+        proposed_circuit.set_roster(RepeatedField::from_vec(roster));
+        proposed_circuit.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
+
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::ADD_NODE);
+        circuit_proposal.set_circuit_id(circuit_id.to_string());
+        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+        circuit_proposal.set_circuit_proposal(proposed_circuit);
+        circuit_proposal.set_requester(requester.to_vec());
+        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
+
+        Ok(circuit_proposal)
+    }
+
+    /// Makes the `CircuitProposal` associated with a `CircuitUpdateRemoveNodeRequest`, based on
+    /// the currently active circuit. The node being removed is removed from the circuit's members,
+    /// the services that run on the node are removed from the roster, the services in the request
+    /// replace those in the roster, and the circuit's schema version is set to the current
+    /// version.
+    fn make_remove_node_circuit_proposal(
+        &self,
+        remove_node_request: &CircuitUpdateRemoveNodeRequest,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let circuit_id = remove_node_request.get_circuit_id();
+        let removed_node_id = remove_node_request.get_node_id();
+        let store_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received remove node request for a circuit that does not exist: \
+                     circuit id {}",
+                    circuit_id
+                ))
+            })?;
+
+        let mut proposed_circuit = make_proto_circuit(&store_circuit)?;
+        let mut members = proposed_circuit.take_members().into_vec();
+        members.retain(|member| member.get_node_id() != removed_node_id);
+        proposed_circuit.set_members(RepeatedField::from_vec(members));
+
+        let mut roster = proposed_circuit.take_roster().into_vec();
+        roster.retain(|service| {
+            !service
+                .get_allowed_nodes()
+                .iter()
+                .any(|node_id| node_id == removed_node_id)
+        });
+        for service in remove_node_request.get_services() {
+            if let Some(existing) = roster
+                .iter_mut()
+                .find(|existing| existing.get_service_id() == service.get_service_id())
+            {
+                *existing = service.clone();
+            }
+        }
+        proposed_circuit.set_roster(RepeatedField::from_vec(roster));
+        proposed_circuit.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
+
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::REMOVE_NODE);
+        circuit_proposal.set_circuit_id(circuit_id.to_string());
+        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+        circuit_proposal.set_circuit_proposal(proposed_circuit);
+        circuit_proposal.set_requester(requester.to_vec());
+        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
+
+        Ok(circuit_proposal)
+    }
+
+    fn make_update_application_metadata_circuit_proposal(
+        &self,
+        update_metadata_request: &CircuitUpdateApplicationMetadataRequest,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let circuit_id = update_metadata_request.get_circuit_id();
+        let store_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received update application metadata request for a circuit that does not \
+                     exist: circuit id {}",
+                    circuit_id
+                ))
+            })?;
+
+        let mut proposed_circuit = make_proto_circuit(&store_circuit)?;
+        proposed_circuit
+            .set_application_metadata(update_metadata_request.get_application_metedata().to_vec());
+
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal
+            .set_proposal_type(CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA);
+        circuit_proposal.set_circuit_id(circuit_id.to_string());
+        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+        circuit_proposal.set_circuit_proposal(proposed_circuit);
+        circuit_proposal.set_requester(requester.to_vec());
+        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
+
+        Ok(circuit_proposal)
+    }
+
+    /// Makes a `Circuit` and `StoreCircuit` with an `Abandoned` `circuit_status` to be used to
+    /// update circuit state to reflect the abandoning change
+    fn make_abandoned_circuit(
+        &self,
+        store_circuit: &StoreCircuit,
+    ) -> Result<(Circuit, StoreCircuit), AdminSharedError> {
+        // Collecting the endpoints of the nodes apart of the circuit being abandoned
+        let circuit_members = store_circuit
+            .members()
+            .iter()
+            .map(|circuit_node| {
+                let mut node = SplinterNode::new();
+                node.set_node_id(circuit_node.node_id().to_string());
+                node.set_endpoints(RepeatedField::from_vec(circuit_node.endpoints().to_vec()));
+                {
+                    if let Some(public_key) = circuit_node.public_key() {
+                        node.set_public_key(public_key.clone().into_bytes());
+                    }
+                }
+                node
+            })
+            .collect::<Vec<SplinterNode>>();
+
+        let services: Vec<SplinterService> = store_circuit
+            .roster()
+            .iter()
+            .map(|store_service| {
+                let mut service = SplinterService::new();
+                service.set_service_id(store_service.service_id().to_string());
+                service.set_service_type(store_service.service_type().to_string());
+                service.set_allowed_nodes(RepeatedField::from_vec(vec![store_service
+                    .node_id()
+                    .to_string()]));
+                service
+            })
+            .collect::<Vec<SplinterService>>();
+        let mut circuit = Circuit::new();
+        circuit.set_circuit_id(store_circuit.circuit_id().to_string());
+        circuit.set_roster(RepeatedField::from_vec(services));
+        circuit.set_members(RepeatedField::from_vec(circuit_members));
+        circuit.set_authorization_type(Circuit_AuthorizationType::from(
+            store_circuit.authorization_type(),
+        ));
+        circuit.set_persistence(Circuit_PersistenceType::from(store_circuit.persistence()));
+        circuit.set_durability(Circuit_DurabilityType::from(store_circuit.durability()));
+        circuit.set_routes(Circuit_RouteType::from(store_circuit.routes()));
+        circuit.set_circuit_management_type(store_circuit.circuit_management_type().to_string());
+        if let Some(display) = store_circuit.display_name() {
+            circuit.set_display_name(display.to_string());
+        }
+        if let Some(application_metadata) = store_circuit.application_metadata() {
+            circuit.set_application_metadata(application_metadata.to_vec());
+        }
+        circuit.set_circuit_version(store_circuit.circuit_version());
+        circuit.set_circuit_status(Circuit_CircuitStatus::from(store_circuit.circuit_status()));
+
+        // Creating the `Abandoned` StoreCircuit
+        let mut store_circuit = StoreCircuitBuilder::new()
+            .with_circuit_id(store_circuit.circuit_id())
+            .with_roster(store_circuit.roster())
+            .with_members(store_circuit.members())
+            .with_authorization_type(store_circuit.authorization_type())
+            .with_persistence(store_circuit.persistence())
+            .with_durability(store_circuit.durability())
+            .with_routes(store_circuit.routes())
+            .with_circuit_management_type(store_circuit.circuit_management_type())
+            .with_circuit_version(store_circuit.circuit_version())
+            .with_circuit_status(&StoreCircuitStatus::Abandoned);
+        if let Some(display_name) = store_circuit.display_name() {
+            store_circuit = store_circuit.with_display_name(&display_name);
+        }
+
+        Ok((
+            circuit,
+            store_circuit.build().map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "error occurred when trying to build circuit {}",
+                    err
+                ))
+            })?,
+        ))
+    }
+
+    /// Initialize all services that this node should run on the created circuit using the
+    /// lifecycle dispatch. This may not include all services if they are not supported locally.
+    //  It is expected that some services will be started externally.
+    pub fn initialize_services(&mut self, circuit: &Circuit) -> Result<(), AdminSharedError> {
+        // Start all services the lifecycle dispatches can
+        for service in circuit.get_roster() {
+            if !service.allowed_nodes.contains(&self.node_id) {
+                continue;
+            }
+
+            let service_arguments: Vec<(String, String)> = service
+                .arguments
+                .iter()
+                .map(|arg| (arg.key.clone(), arg.value.clone()))
+                .collect();
+
+            for dispatch in &self.lifecycle_dispatch {
+                dispatch
+                    .add_service(
+                        &circuit.circuit_id,
+                        &service.service_id,
+                        &service.service_type,
+                        service_arguments.clone(),
+                    )
+                    .map_err(|err| {
+                        error!("{}", err);
+                        AdminSharedError::ServiceInitializationFailed {
+                            context: format!(
+                                "Unable to start service {} on circuit {}",
+                                service.service_id, circuit.circuit_id
+                            ),
+                            source: None,
+                        }
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stops all services that this node was running on the disbanded or abandoned circuit using
+    /// the service lifecycle dispatch. This may not include all services if they are not supported
+    /// locally. It is expected that some services will be stopped externally.
+    pub fn stop_services(&mut self, circuit: &Circuit) -> Result<(), AdminSharedError> {
+        // Stop all services the lifecycle dispatches can
+        for service in circuit.get_roster() {
+            if !service.allowed_nodes.contains(&self.node_id) {
+                continue;
+            }
+
+            for dispatch in &self.lifecycle_dispatch {
+                dispatch
+                    .retire_service(
+                        &circuit.circuit_id,
+                        &service.service_id,
+                        &service.service_type,
+                    )
+                    .map_err(|err| {
+                        error!("{}", err);
+                        AdminSharedError::ServiceShutdownFailed {
+                            context: format!(
+                                "Unable to shutdown service {} on circuit {}",
+                                service.service_id, circuit.circuit_id
+                            ),
+                            source: None,
+                        }
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Purges all services that this node was running on the disbanded circuit using the service
+    /// lifecycle dispatch. Destroying a service will also remove the service's state LMDB files.
+    pub fn purge_services(
+        &mut self,
+        circuit_id: &str,
+        services: &[StoreService],
+    ) -> Result<(), AdminSharedError> {
+        for service in services {
+            if service.node_id() != self.node_id() {
+                continue;
+            }
+
+            for dispatch in &self.lifecycle_dispatch {
+                if let Err(err) =
+                    dispatch.purge_service(circuit_id, service.service_id(), service.service_type())
+                {
+                    error!(
+                        "Service {}::{} ({}) failed to purge: {}",
+                        circuit_id,
+                        service.service_id(),
+                        service.service_type(),
+                        err
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stops the given services that this node was running on the circuit using the service
+    /// lifecycle dispatch. This is used to stop the services that have been removed from a
+    /// circuit's roster.
+    fn retire_services(
+        &mut self,
+        circuit_id: &str,
+        services: &[StoreService],
+    ) -> Result<(), AdminSharedError> {
+        for service in services {
+            if service.node_id() != self.node_id() {
+                continue;
+            }
+
+            for dispatch in &self.lifecycle_dispatch {
+                dispatch
+                    .retire_service(circuit_id, service.service_id(), service.service_type())
+                    .map_err(|err| {
+                        error!("{}", err);
+                        AdminSharedError::ServiceShutdownFailed {
+                            context: format!(
+                                "Unable to shutdown service {} on circuit {}",
+                                service.service_id(),
+                                circuit_id
+                            ),
+                            source: None,
+                        }
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn shutdown_all_services(&self) {
+        for dispatch in &self.lifecycle_dispatch {
+            if let Err(err) = dispatch.shutdown_all_services() {
+                error!("{}", err)
+            }
+        }
+    }
+
+    /// Verify all members are ready before cleaning up after the disbanded circuit, i.e. removing
+    /// peer refs, removing the circuit from the routing table, and shutting down the circuit's
+    /// associated services.
+    pub fn cleanup_disbanded_circuit_if_members_ready(
+        &mut self,
+        circuit_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        let ready = {
+            if let Some(disbanded_circuit) = self.uninitialized_circuits.get(circuit_id) {
+                if let Some(ref circuit_proposal) = disbanded_circuit.circuit {
+                    let all_members = circuit_proposal
+                        .get_circuit_proposal()
+                        .members
+                        .iter()
+                        .map(|node| node.node_id.clone())
+                        .collect::<HashSet<String>>();
+                    all_members.is_subset(&disbanded_circuit.ready_members)
+                } else {
+                    false
+                }
+            } else {
+                false
+            }
+        };
+
+        if ready {
+            let mut circuit_proposal = self
+                .uninitialized_circuits
+                .remove(circuit_id)
+                .expect("Pending disband circuit not set")
+                .circuit
+                .expect("Pending disband circuit's circuit proposal not set");
+            // send message about circuit acceptance
+            let circuit_proposal_proto =
+                messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                    .map_err(AdminSharedError::InvalidMessageFormat)?;
+            let mgmt_type = circuit_proposal
+                .get_circuit_proposal()
+                .circuit_management_type
+                .clone();
+            let event = messages::AdminServiceEvent::CircuitDisbanded(circuit_proposal_proto);
+            self.send_event(&mgmt_type, event);
+
+            // Circuit has been disbanded: all associated services will be shut
+            // down, the circuit removed from the routing table, and peer refs
+            // for this circuit will be removed.
+            self.stop_services(circuit_proposal.get_circuit_proposal())?;
+            // Removing the circuit from the routing table
+            self.routing_table_writer
+                .remove_circuit(circuit_proposal.get_circuit_id())
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to remove circuit from routing table: {}",
+                        circuit_id
+                    ))
+                })?;
+
+            let proposed_circuit = ProposedCircuit::from_proto(
+                circuit_proposal.take_circuit_proposal(),
+            )
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get store proposed circuit from proto: {}",
+                    err
+                ))
+            })?;
+            // Removing the circuit's peer refs
+            self.remove_peer_refs(proposed_circuit.list_tokens(&self.node_id).map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to remove peer refs for proposal {}: {}",
+                    proposed_circuit.circuit_id(),
+                    err
+                ))
+            })?);
+        }
+
+        Ok(())
+    }
+
+    /// Collect all circuits from the admin store, including `Disbanded` or `Abandoned` circuits
+    pub fn get_circuits(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = StoreCircuit>>, AdminSharedError> {
+        let predicates = vec![
+            CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active),
+            CircuitPredicate::CircuitStatus(StoreCircuitStatus::Disbanded),
+            CircuitPredicate::CircuitStatus(StoreCircuitStatus::Abandoned),
+        ];
+        self.admin_store
+            .list_circuits(&predicates)
+            .map_err(AdminSharedError::from)
+    }
+
+    fn verify_signature(&self, payload: &CircuitManagementPayload) -> Result<bool, ServiceError> {
+        let header: CircuitManagementPayload_Header =
+            Message::parse_from_bytes(payload.get_header())?;
+
+        let signature = payload.get_signature().to_vec();
+        let public_key = header.get_requester().to_vec();
+
+        self.signature_verifier
+            .verify(
+                payload.get_header(),
+                &Signature::new(signature),
+                &PublicKey::new(public_key),
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+    }
+}
+
+/// Makes the routing table representation of a circuit
+fn make_routing_circuit(circuit: &StoreCircuit) -> routing::Circuit {
+    routing::Circuit::new(
+        circuit.circuit_id().to_string(),
+        circuit
+            .roster()
+            .iter()
+            .map(|service| {
+                routing::Service::new(
+                    service.service_id().to_string(),
+                    service.service_type().to_string(),
+                    service.node_id().to_string(),
+                    service.arguments().to_vec(),
+                )
+            })
+            .collect(),
+        circuit
+            .members()
+            .iter()
+            .map(|node| node.node_id().to_string())
+            .collect(),
+        circuit.authorization_type().into(),
+        circuit.durability().into(),
+    )
+    .with_relays(make_routing_relays(circuit))
+}
+
+/// Makes the routing table representation of the relay nodes of a circuit, keyed by the member
+/// they relay messages for. Only circuits with relayed routes have relays.
+pub(super) fn make_routing_relays(circuit: &StoreCircuit) -> BTreeMap<String, String> {
+    if circuit.routes() != &StoreRouteType::Relayed {
+        return BTreeMap::new();
+    }
+
+    circuit
+        .members()
+        .iter()
+        .filter_map(|node| {
+            node.relay_node_id()
+                .as_ref()
+                .map(|relay_node_id| (node.node_id().to_string(), relay_node_id.to_string()))
+        })
+        .collect()
+}
+
+/// Makes the relay nodes of a proposed circuit, keyed by the member they relay messages for. Only
+/// circuits with relayed routes have relays.
+fn make_proto_relays(circuit: &Circuit) -> BTreeMap<String, String> {
+    if circuit.get_routes() != Circuit_RouteType::RELAYED_ROUTE {
+        return BTreeMap::new();
+    }
+
+    circuit
+        .get_members()
+        .iter()
+        .filter(|node| !node.get_relay_node_id().is_empty())
+        .map(|node| {
+            (
+                node.get_node_id().to_string(),
+                node.get_relay_node_id().to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Makes the routing table representation of the members of a circuit
+fn make_routing_members(circuit: &Circuit) -> Vec<routing::CircuitNode> {
+    circuit
+        .get_members()
+        .iter()
+        .map(|node| {
+            routing::CircuitNode::new(
+                node.get_node_id().to_string(),
+                node.get_endpoints().to_vec(),
+                if node.get_public_key().is_empty() {
+                    None
+                } else {
+                    Some(public_key::PublicKey::from_bytes(
+                        node.get_public_key().to_vec(),
+                    ))
+                },
+            )
+        })
+        .collect()
+}
+
+/// Makes the protobuf representation of a circuit stored in the admin store
+fn make_proto_circuit(store_circuit: &StoreCircuit) -> Result<Circuit, AdminSharedError> {
+    let circuit_members = store_circuit
+        .members()
+        .iter()
+        .map(|circuit_node| messages::SplinterNode {
+            node_id: circuit_node.node_id().to_string(),
+            endpoints: circuit_node.endpoints().to_vec(),
+            public_key: circuit_node
+                .public_key()
+                .clone()
+                .map(|public_key| public_key.into_bytes()),
+            relay_node_id: circuit_node.relay_node_id().clone(),
+        })
+        .collect::<Vec<messages::SplinterNode>>();
+    let mut create_circuit_builder = messages::CreateCircuitBuilder::new()
+        .with_circuit_id(store_circuit.circuit_id())
+        .with_roster(
+            store_circuit
+                .roster()
+                .iter()
+                .map(|service| messages::SplinterService {
+                    service_id: service.service_id().into(),
+                    service_type: service.service_type().into(),
+                    allowed_nodes: vec![service.node_id().to_string()],
+                    arguments: service
+                        .arguments()
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                })
+                .collect::<Vec<messages::SplinterService>>()
+                .as_ref(),
+        )
+        .with_members(circuit_members.as_ref())
+        .with_authorization_type(&messages::AuthorizationType::from(
+            store_circuit.authorization_type(),
+        ))
+        .with_persistence(&messages::PersistenceType::from(
+            store_circuit.persistence(),
+        ))
+        .with_durability(&messages::DurabilityType::from(store_circuit.durability()))
+        .with_routes(&messages::RouteType::from(store_circuit.routes()))
+        .with_circuit_management_type(store_circuit.circuit_management_type())
+        .with_circuit_version(store_circuit.circuit_version())
+        .with_circuit_status(&messages::CircuitStatus::from(
+            store_circuit.circuit_status(),
+        ));
+
+    if let Some(display_name) = store_circuit.display_name() {
+        create_circuit_builder = create_circuit_builder.with_display_name(display_name);
+    }
+
+    if let Some(application_metadata) = store_circuit.application_metadata() {
+        create_circuit_builder =
+            create_circuit_builder.with_application_metadata(application_metadata);
+    }
+
+    create_circuit_builder
+        .build()
+        .map_err(|err| {
+            AdminSharedError::ValidationFailed(format!(
+                "error occurred when trying to build circuit {}",
+                err
+            ))
+        })?
+        .try_into()
+        .map_err(|err| {
+            AdminSharedError::ValidationFailed(format!(
+                "error occurred when trying to create proto circuit {}",
+                err
+            ))
+        })
+}
+
+/// Returns the ID of the existing circuit a payload's request applies to, if the payload contains
+/// a request to disband or update an existing circuit.
+fn get_existing_circuit_id(payload: &CircuitManagementPayload) -> Option<&str> {
+    if payload.has_circuit_disband_request() {
+        Some(payload.get_circuit_disband_request().get_circuit_id())
+    } else if payload.has_circuit_update_roster_request() {
+        Some(payload.get_circuit_update_roster_request().get_circuit_id())
+    } else if payload.has_circuit_update_remove_node() {
+        Some(payload.get_circuit_update_remove_node().get_circuit_id())
+    } else if payload.has_circuit_update_application_metadata_request() {
+        Some(
+            payload
+                .get_circuit_update_application_metadata_request()
+                .get_circuit_id(),
+        )
+    } else {
+        None
+    }
+}
+
+/// Returns true if the proposal type updates an existing circuit, rather than creating or
+/// disbanding a circuit.
+fn is_circuit_update(proposal_type: CircuitProposal_ProposalType) -> bool {
+    matches!(
+        proposal_type,
+        CircuitProposal_ProposalType::UPDATE_ROSTER
+            | CircuitProposal_ProposalType::ADD_NODE
+            | CircuitProposal_ProposalType::REMOVE_NODE
+    )
+}
+
+/// Returns true if the store service and the proto service have the same ID, run on the same
+/// node and have the same arguments.
+fn is_same_service(store_service: &StoreService, service: &SplinterService) -> bool {
+    store_service.service_id() == service.get_service_id()
+        && service
+            .get_allowed_nodes()
+            .iter()
+            .any(|node_id| node_id == store_service.node_id())
+        && store_service.arguments().len() == service.get_arguments().len()
+        && store_service
+            .arguments()
+            .iter()
+            .zip(service.get_arguments().iter())
+            .all(|((key, value), arg)| key == arg.get_key() && value == arg.get_value())
+}
+
+// This should never return an error since we recieved a message from this service id
+pub fn get_peer_token_from_service_id(
+    service_id: &str,
+    local_node_id: &str,
+) -> Result<PeerTokenPair, InternalError> {
+    let mut iter = service_id.split("::");
+
+    let admin_prefix = iter
+        .next()
+        .expect("str::split cannot return an empty iterator")
+        .to_string();
+
+    if admin_prefix.is_empty() {
+        return Err(InternalError::with_message(
+            "Empty admin_id argument detected".into(),
+        ));
+    }
+
+    let node_id = iter
+        .next()
+        .ok_or_else(|| InternalError::with_message("Missing node id for recipient".into()))?;
+
+    if node_id.is_empty() {
+        return Err(InternalError::with_message("Empty node id provided".into()));
+    }
+
+    // If challenge authorization the admin id will be in the format
+    // admin::public_key::<public key string>.
+    if node_id == ADMIN_SERVICE_PUBLIC_KEY_PREFIX {
+        let public_key = iter
+            .next()
+            .ok_or_else(|| InternalError::with_message("Missing public key for recipient".into()))?
+            .to_string();
+
+        if public_key.is_empty() {
+            return Err(InternalError::with_message(
+                "Empty public key provided".into(),
+            ));
+        }
+
+        let second_public_key = iter.next().ok_or_else(|| {
+            InternalError::with_message("Missing local public key for recipient".into())
+        })?;
+
+        if second_public_key != ADMIN_SERVICE_PUBLIC_KEY_PREFIX {
+            return Err(InternalError::with_message(
+                "Missing local public key for recipient".into(),
+            ));
+        }
+
+        let local_public_key = iter
+            .next()
+            .ok_or_else(|| {
+                InternalError::with_message("Missing local_public key for recipient".into())
+            })?
+            .to_string();
+
+        if local_public_key.is_empty() {
+            return Err(InternalError::with_message(
+                "Empty local public key provided".into(),
+            ));
+        }
+
+        Ok(PeerTokenPair::new(
+            PeerAuthorizationToken::from_public_key(
+                &parse_hex(&public_key)
+                    .map_err(|err| InternalError::with_message(err.to_string()))?,
+            ),
+            PeerAuthorizationToken::from_public_key(
+                &parse_hex(&local_public_key)
+                    .map_err(|err| InternalError::with_message(err.to_string()))?,
+            ),
+        ))
+    } else {
+        Ok(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id(node_id),
+            PeerAuthorizationToken::from_peer_id(local_node_id),
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+    use protobuf::RepeatedField;
+
+    use diesel::{
+        r2d2::{ConnectionManager as DieselConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::admin::service::AdminKeyVerifierError;
+    use crate::admin::store;
+    use crate::admin::store::diesel::DieselAdminServiceStore;
+    use crate::admin::store::CircuitNode;
+    use crate::circuit::routing::memory::RoutingTable;
+    use crate::keys::insecure::AllowAllKeyPermissionManager;
+    use crate::mesh::{Envelope, Mesh};
+    use crate::migrations::run_sqlite_migrations;
+    use crate::network::auth::AuthorizationManager;
+    use crate::network::connection_manager::authorizers::{Authorizers, InprocAuthorizer};
+    use crate::network::connection_manager::ConnectionManager;
+    use crate::orchestrator::{ServiceOrchestrator, ServiceOrchestratorBuilder};
+    use crate::peer::{PeerManager, PeerManagerConnector};
+    use crate::protocol::authorization::{
+        AuthorizationMessage, AuthorizationType, Authorized, ConnectRequest, ConnectResponse,
+        TrustRequest,
+    };
+    use crate::protocol::network::NetworkMessage;
+    use crate::protos::admin;
+    use crate::protos::admin::{
+        CircuitProposalVote_Vote, CircuitProposal_VoteRecord, SplinterNode, SplinterService,
+    };
+    use crate::protos::network;
+    use crate::protos::prelude::*;
+    use crate::service::instance::{ServiceMessageContext, ServiceSendError};
+    use crate::threading::lifecycle::ShutdownHandle;
+    use crate::transport::{
+        inproc::InprocTransport, ConnectError, Connection, DisconnectError, RecvError, SendError,
+        Transport,
+    };
+
+    const PUB_KEY: &[u8] = &[
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31, 32,
+    ];
+
+    /// Test that the CircuitManagementPayload is moved to the pending payloads when the peers are
+    /// fully authorized.
+    #[test]
+    fn test_protocol_agreement() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let mut other_listener = transport
+            .listen("inproc://otherplace:8000")
+            .expect("Unable to get listener");
+        let _test_listener = transport
+            .listen("inproc://someplace:8000")
+            .expect("Unable to get listener");
+        let _orchestator_listener = transport
+            .listen("inproc://orchestator")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://orchestator")
+            .expect("failed to create connection");
+        let orchestrator = ServiceOrchestratorBuilder::new()
+            .with_connection(orchestrator_connection)
+            .build()
+            .expect("failed to create orchestrator")
+            .run()
+            .expect("failed to start orchestrator");
+        let store = setup_admin_service_store();
+
+        let event_store = store.clone_boxed();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut shared = AdminServiceShared::new(
+            "my_peer_id".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+
+        let mut circuit = admin::Circuit::new();
+        circuit.set_circuit_id("01234-ABCDE".into());
+        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
+        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
+        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
+        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
+        circuit.set_circuit_management_type("test app auth handler".into());
+        circuit.set_comments("test circuit".into());
+        circuit.set_display_name("test_display".into());
+        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
+
+        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
+            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
+            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
+            splinter_node("my_peer_id", &["inproc://myplace:8000".to_string()]),
+        ]));
+        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
+            splinter_service("0123", "sabre"),
+            splinter_service("ABCD", "sabre"),
+        ]));
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+
+        let mut payload = admin::CircuitManagementPayload::new();
+
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        // start up thread for other node
+        std::thread::spawn(move || {
+            let mut mesh = Mesh::new(2, 2);
+            let conn = other_listener.accept().unwrap();
+            mesh.add(conn, "my_peer_id".to_string()).unwrap();
+
+            handle_auth(&mesh, "my_peer_id", "other-node");
+
+            mesh.signal_shutdown();
+            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        shared
+            .propose_circuit(payload, "test".to_string())
+            .expect("Proposal not accepted");
+
+        // None of the proposed members are peered
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected(&PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("other-node"),
+                PeerAuthorizationToken::from_peer_id("my_peer_id"),
+            ))
+            .expect("Unable to set peer to peered");
+
+        // Still waitin on 1 peer
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected(&PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("test-node"),
+                PeerAuthorizationToken::from_peer_id("my_peer_id"),
+            ))
+            .expect("Unable to set peer to peered");
+
+        // We're fully peered, but need to wait for protocol to be agreed on
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        shared
+            .on_protocol_agreement("admin::other-node", 1)
+            .expect("received unexpected error");
+
+        // Waiting on 1 node for protocol agreement
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        shared
+            .on_protocol_agreement("admin::test-node", 1)
+            .expect("received unexpected error");
+        // We're fully peered and agreed on protocol, so the pending payload is now available
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+        assert_eq!(1, shared.pending_circuit_payloads.len());
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Test that a member of a circuit with relayed routes that is only reachable through a hub
+    /// is not peered with, that the proposal only waits on the hub, and that messages for the
+    /// member are sent through the hub.
+    #[test]
+    fn test_relayed_member_not_peered() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let _hub_listener = transport
+            .listen("inproc://hubplace:8000")
+            .expect("Unable to get listener");
+        let _orchestator_listener = transport
+            .listen("inproc://orchestator")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://orchestator")
+            .expect("failed to create connection");
+        let orchestrator = ServiceOrchestratorBuilder::new()
+            .with_connection(orchestrator_connection)
+            .build()
+            .expect("failed to create orchestrator")
+            .run()
+            .expect("failed to start orchestrator");
+        let store = setup_admin_service_store();
+
+        let event_store = store.clone_boxed();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut shared = AdminServiceShared::new(
+            "my_peer_id".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+
+        let mut circuit = admin::Circuit::new();
+        circuit.set_circuit_id("01234-ABCDE".into());
+        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
+        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
+        circuit.set_routes(admin::Circuit_RouteType::RELAYED_ROUTE);
+        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
+        circuit.set_circuit_management_type("test app auth handler".into());
+        circuit.set_comments("test circuit".into());
+        circuit.set_display_name("test_display".into());
+        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
+
+        // both leaf nodes are only connected to the hub
+        let mut local_node = splinter_node("my_peer_id", &["inproc://myplace:8000".to_string()]);
+        local_node.set_relay_node_id("hub-node".into());
+        let mut other_node = splinter_node("other-node", &["inproc://otherplace:8000".to_string()]);
+        other_node.set_relay_node_id("hub-node".into());
+        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
+            splinter_node("hub-node", &["inproc://hubplace:8000".to_string()]),
+            other_node,
+            local_node,
+        ]));
+        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
+            splinter_service("0123", "sabre"),
+            splinter_service("ABCD", "sabre"),
+        ]));
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+
+        let mut payload = admin::CircuitManagementPayload::new();
+
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        shared
+            .propose_circuit(payload, "test".to_string())
+            .expect("Proposal not accepted");
+
+        let other_node_token = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("other-node"),
+            PeerAuthorizationToken::from_peer_id("my_peer_id"),
+        );
+        let hub_node_token = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("hub-node"),
+            PeerAuthorizationToken::from_peer_id("my_peer_id"),
+        );
+
+        // Only the hub is peered with
+        assert!(shared.peer_refs.contains_key(&hub_node_token));
+        assert!(!shared.peer_refs.contains_key(&other_node_token));
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(
+            vec![hub_node_token.clone()],
+            shared.unpeered_payloads[0].unpeered_ids
+        );
+
+        shared
+            .on_peer_connected(&hub_node_token)
+            .expect("Unable to set peer to peered");
+
+        // We're fully peered, but need to wait for protocol to be agreed on with the hub
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        shared
+            .on_protocol_agreement("admin::hub-node", 1)
+            .expect("received unexpected error");
+
+        // The relayed member does not need to agree on a protocol
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+        assert_eq!(1, shared.pending_circuit_payloads.len());
+
+        // Messages for the relayed member go through the hub
+        shared
+            .send_to_admin_service(&other_node_token, b"test")
+            .expect("Unable to send message");
+        assert_eq!(
+            vec![(
+                "admin::other-node".to_string(),
+                vec!["hub-node".to_string()],
+                b"test".to_vec()
+            )],
+            *service_sender
+                .relayed
+                .lock()
+                .expect("relayed lock poisoned")
+        );
+
+        // Messages for the hub are sent directly
+        shared
+            .send_to_admin_service(&hub_node_token, b"test")
+            .expect("Unable to send message");
+        assert_eq!(
+            Some(&("admin::hub-node".to_string(), b"test".to_vec())),
+            service_sender
+                .sent
+                .lock()
+                .expect("sent lock poisoned")
+                .last()
+        );
+
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Test that the CircuitManagementPayload message is dropped, if a node fails to match
+    /// protocol versions
+    #[test]
+    fn test_protocol_disagreement() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let _listener = transport
+            .listen("inproc://otherplace:8000")
+            .expect("Unable to get listener");
+        let _admin_listener = transport
+            .listen("inproc://admin-service")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://admin-service")
+            .expect("failed to create connection");
+        let orchestrator = ServiceOrchestratorBuilder::new()
+            .with_connection(orchestrator_connection)
+            .build()
+            .expect("failed to create orchestrator")
+            .run()
+            .expect("failed to start orchestrator");
+        let store = setup_admin_service_store();
+
+        let event_store = store.clone_boxed();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut shared = AdminServiceShared::new(
+            "test-node".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+
+        let mut circuit = admin::Circuit::new();
+        circuit.set_circuit_id("01234-ABCDE".into());
+        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
+        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
+        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
+        circuit.set_circuit_management_type("test app auth handler".into());
+        circuit.set_comments("test circuit".into());
+        circuit.set_display_name("test_display".into());
+        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
+        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
+
+        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
+            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
+            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
+        ]));
+        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
+            splinter_service("0123", "sabre"),
+            splinter_service("ABCD", "sabre"),
+        ]));
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+
+        let mut payload = admin::CircuitManagementPayload::new();
+
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        shared
+            .propose_circuit(payload, "local".to_string())
+            .expect("Proposal not accepted");
+
+        // None of the proposed members are peered
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected(&PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("other-node"),
+                PeerAuthorizationToken::from_peer_id("test-node"),
+            ))
+            .expect("Unable to set peer to peered");
+
+        assert_eq!(0, shared.unpeered_payloads.len());
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+        shared
+            .on_protocol_agreement("admin::other-node", 0)
+            .expect("received unexpected error");
+
+        // The message should be dropped
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+
+        // sent the service protocol request but not the payload
+        assert_eq!(
+            1,
+            service_sender
+                .sent
+                .lock()
+                .expect("Network sender lock poisoned")
+                .len()
+        );
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a valid circuit is validated correctly
+    fn test_validate_circuit_valid() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+
+        if let Err(err) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // Test that a valid circuit on version 2, would fail on protocol 1 because display_name is
+    // set. Protocol 1 should fail any circuit that has display name set, as display name is not
+    // included in the protobuf.
+    fn test_validate_invalid_protocol_display_name() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+
+        if let Ok(()) = admin_shared.validate_create_circuit(&circuit, PUB_KEY, "node_a", 1) {
+            panic!("Should have been invalid because display name is set");
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a circuit proposed with a key that is not permitted for the requesting node is
+    // invalid
+    fn test_validate_circuit_signer_not_permitted() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to requester node not being registered");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit is proposed by a signer key is not a valid public key the proposal is
+    // invalid
+    fn test_validate_circuit_signer_key_invalid() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+
+        let pub_key = (0u8..50).collect::<Vec<_>>();
+        // too short
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            &pub_key[0..10],
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to key being too short");
+        }
+        // too long
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            &pub_key,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to key being too long");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service in its roster with an allowed node that is not in
+    // members an error is returned
+    fn test_validate_circuit_bad_node() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut service_bad = SplinterService::new();
+        service_bad.set_service_id("0123".to_string());
+        service_bad.set_service_type("type_a".to_string());
+        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec!["node_bad".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service having an allowed node not in members");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service in its roster with too many allowed nodes
+    fn test_validate_circuit_too_many_allowed_nodes() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut service_bad = SplinterService::new();
+        service_bad.set_service_id("0123".to_string());
+        service_bad.set_service_type("type_a".to_string());
+        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec![
+            "node_b".to_string(),
+            "extra".to_string(),
+        ]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service having too many allowed nodes");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service with "" for a service id an error is returned
+    fn test_validate_circuit_empty_service_id() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut service_ = SplinterService::new();
+        service_.set_service_id("".to_string());
+        service_.set_service_type("type_a".to_string());
+        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service's id being empty");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service with an invalid service id an error is returned
+    fn test_validate_circuit_invalid_service_id() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut service_ = SplinterService::new();
+        service_.set_service_id("invalid_service_id".to_string());
+        service_.set_service_type("type_a".to_string());
+        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service's id being empty");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service with duplicate service ids an error is returned
+    fn test_validate_circuit_duplicate_service_id() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut service_a = SplinterService::new();
+        service_a.set_service_id("0123".to_string());
+        service_a.set_service_type("type_a".to_string());
+        service_a.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+
+        let mut service_a2 = SplinterService::new();
+        service_a2.set_service_id("0123".to_string());
+        service_a2.set_service_type("type_a".to_string());
+        service_a2.set_allowed_nodes(RepeatedField::from_vec(vec!["node_b".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_a, service_a2]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service's id being a duplicate");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit does not have any services in its roster an error is returned
+    fn test_validate_circuit_empty_roster() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+        circuit.set_roster(RepeatedField::from_vec(vec![]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to empty roster");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit does not have any nodes in its members an error is returned
+    fn test_validate_circuit_empty_members() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_members(RepeatedField::from_vec(vec![]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid empty members");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit does not have the local node in the member list an error is
+    // returned
+    fn test_validate_circuit_missing_local_node() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_b]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_a is not in members");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that the relay nodes of a circuit are validated
+    //
+    // 1. Check that relay nodes are rejected if the circuit does not use relayed routes
+    // 2. Check that a circuit with relayed routes and a member relay node is valid
+    // 3. Check that a relay node that is not a member of the circuit is rejected
+    // 4. Check that relay nodes that form a loop are rejected
+    fn test_validate_circuit_relays() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+        node_b.set_relay_node_id("node_a".to_string());
+
+        let mut circuit = setup_test_circuit();
+        circuit.set_members(RepeatedField::from_vec(vec![
+            node_a.clone(),
+            node_b.clone(),
+        ]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit does not use relayed routes");
+        }
+
+        circuit.set_routes(Circuit_RouteType::RELAYED_ROUTE);
+        if let Err(err) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        let mut unknown_relay = node_b.clone();
+        unknown_relay.set_relay_node_id("node_c".to_string());
+        circuit.set_members(RepeatedField::from_vec(vec![node_a.clone(), unknown_relay]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_c is not a member");
+        }
+
+        let mut looping_relay = node_a.clone();
+        looping_relay.set_relay_node_id("node_b".to_string());
+        circuit.set_members(RepeatedField::from_vec(vec![looping_relay, node_b.clone()]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the relay nodes form a loop");
+        }
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
+        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because relays require trust authorization");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a member with node id of "" an error is
+    // returned
+    fn test_validate_circuit_empty_node_id() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+
+        let mut node_ = SplinterNode::new();
+        node_.set_node_id("".to_string());
+        node_.set_endpoints(vec!["test://endpoint_:0".to_string()].into());
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_ is has an empty node id");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has duplicate members an error is returned
+    fn test_validate_circuit_duplicate_members() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+
+        let mut node_b2 = SplinterNode::new();
+        node_b2.set_node_id("node_b".to_string());
+        node_b2.set_endpoints(vec!["test://endpoint_b2:0".to_string()].into());
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_b2]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because there are duplicate members");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has an empty circuit id an error is returned
+    fn test_validate_circuit_empty_circuit_id() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_circuit_id("".to_string());
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit ID is empty");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has an invalid circuit id an error is returned
+    fn test_validate_circuit_invalid_circuit_id() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
 
-    use cylinder::{secp256k1::Secp256k1Context, Context};
-    use protobuf::RepeatedField;
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
-    use diesel::{
-        r2d2::{ConnectionManager as DieselConnectionManager, Pool},
-        sqlite::SqliteConnection,
-    };
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
-    use crate::admin::service::AdminKeyVerifierError;
-    use crate::admin::store;
-    use crate::admin::store::diesel::DieselAdminServiceStore;
-    use crate::admin::store::CircuitNode;
-    use crate::circuit::routing::memory::RoutingTable;
-    use crate::keys::insecure::AllowAllKeyPermissionManager;
-    use crate::mesh::{Envelope, Mesh};
-    use crate::migrations::run_sqlite_migrations;
-    use crate::network::auth::AuthorizationManager;
-    use crate::network::connection_manager::authorizers::{Authorizers, InprocAuthorizer};
-    use crate::network::connection_manager::ConnectionManager;
-    use crate::orchestrator::{ServiceOrchestrator, ServiceOrchestratorBuilder};
-    use crate::peer::{PeerManager, PeerManagerConnector};
-    use crate::protocol::authorization::{
-        AuthorizationMessage, AuthorizationType, Authorized, ConnectRequest, ConnectResponse,
-        TrustRequest,
-    };
-    use crate::protocol::network::NetworkMessage;
-    use crate::protos::admin;
-    use crate::protos::admin::{
-        CircuitProposalVote_Vote, CircuitProposal_VoteRecord, SplinterNode, SplinterService,
-    };
-    use crate::protos::network;
-    use crate::protos::prelude::*;
-    use crate::service::instance::{ServiceMessageContext, ServiceSendError};
-    use crate::threading::lifecycle::ShutdownHandle;
-    use crate::transport::{
-        inproc::InprocTransport, ConnectError, Connection, DisconnectError, RecvError, SendError,
-        Transport,
-    };
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-    const PUB_KEY: &[u8] = &[
-        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
-        25, 26, 27, 28, 29, 30, 31, 32,
-    ];
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
 
-    /// Test that the CircuitManagementPayload is moved to the pending payloads when the peers are
-    /// fully authorized.
-    #[test]
-    fn test_protocol_agreement() {
-        let mut transport = InprocTransport::default();
-        let mut orchestrator_transport = transport.clone();
+        circuit.set_circuit_id("invalid_circuit_id".to_string());
 
-        let mut other_listener = transport
-            .listen("inproc://otherplace:8000")
-            .expect("Unable to get listener");
-        let _test_listener = transport
-            .listen("inproc://someplace:8000")
-            .expect("Unable to get listener");
-        let _orchestator_listener = transport
-            .listen("inproc://orchestator")
-            .expect("Unable to get listener");
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit ID is invalid");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
-        let orchestrator_connection = orchestrator_transport
-            .connect("inproc://orchestator")
-            .expect("failed to create connection");
-        let orchestrator = ServiceOrchestratorBuilder::new()
-            .with_connection(orchestrator_connection)
-            .build()
-            .expect("failed to create orchestrator")
-            .run()
-            .expect("failed to start orchestrator");
+    #[test]
+    // test that if a circuit has a member with no endpoints an error is returned
+    fn test_validate_circuit_no_endpoints() {
         let store = setup_admin_service_store();
-
         let event_store = store.clone_boxed();
 
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let mut shared = AdminServiceShared::new(
-            "my_peer_id".into(),
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
             peer_connector,
@@ -3769,139 +8014,145 @@ mod tests {
             event_store,
             vec![],
         );
+        let mut circuit = setup_test_circuit();
 
-        let service_sender = MockServiceNetworkSender::new();
-        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        let mut circuit = admin::Circuit::new();
-        circuit.set_circuit_id("01234-ABCDE".into());
-        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
-        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
-        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
-        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
-        circuit.set_circuit_management_type("test app auth handler".into());
-        circuit.set_comments("test circuit".into());
-        circuit.set_display_name("test_display".into());
-        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec![].into());
 
-        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
-            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
-            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
-            splinter_node("my_peer_id", &["inproc://myplace:8000".to_string()]),
-        ]));
-        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
-            splinter_service("0123", "sabre"),
-            splinter_service("ABCD", "sabre"),
-        ]));
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
 
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because a member has no endpoints");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+    #[test]
+    // test that if a circuit has a member with an empty endpoint an error is returned
+    fn test_validate_circuit_empty_endpoint() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
 
-        let mut payload = admin::CircuitManagementPayload::new();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
-        payload.set_signature(Vec::new());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_create_request(request);
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
-        // start up thread for other node
-        std::thread::spawn(move || {
-            let mut mesh = Mesh::new(2, 2);
-            let conn = other_listener.accept().unwrap();
-            mesh.add(conn, "my_peer_id".to_string()).unwrap();
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-            handle_auth(&mesh, "my_peer_id", "other-node");
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
 
-            mesh.signal_shutdown();
-            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
-        });
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        shared
-            .propose_circuit(payload, "test".to_string())
-            .expect("Proposal not accepted");
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["".to_string()].into());
 
-        // None of the proposed members are peered
-        assert_eq!(1, shared.unpeered_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
 
-        // Set other-node to peered
-        shared
-            .on_peer_connected(&PeerTokenPair::new(
-                PeerAuthorizationToken::from_peer_id("other-node"),
-                PeerAuthorizationToken::from_peer_id("my_peer_id"),
-            ))
-            .expect("Unable to set peer to peered");
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because a member has an empty endpoint");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-        // Still waitin on 1 peer
-        assert_eq!(1, shared.unpeered_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+    #[test]
+    // test that if a circuit has a member with a duplicate endpoint an error is returned
+    fn test_validate_circuit_duplicate_endpoint() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
 
-        // Set other-node to peered
-        shared
-            .on_peer_connected(&PeerTokenPair::new(
-                PeerAuthorizationToken::from_peer_id("test-node"),
-                PeerAuthorizationToken::from_peer_id("my_peer_id"),
-            ))
-            .expect("Unable to set peer to peered");
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
 
-        // We're fully peered, but need to wait for protocol to be agreed on
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        shared
-            .on_protocol_agreement("admin::other-node", 1)
-            .expect("received unexpected error");
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        // Waiting on 1 node for protocol agreement
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
 
-        shared
-            .on_protocol_agreement("admin::test-node", 1)
-            .expect("received unexpected error");
-        // We're fully peered and agreed on protocol, so the pending payload is now available
-        assert_eq!(0, shared.pending_protocol_payloads.len());
-        assert_eq!(1, shared.pending_circuit_payloads.len());
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because a member has a duplicate endpoint");
+        }
         shutdown(mesh, cm, pm);
     }
 
-    /// Test that the CircuitManagementPayload message is dropped, if a node fails to match
-    /// protocol versions
     #[test]
-    fn test_protocol_disagreement() {
-        let mut transport = InprocTransport::default();
-        let mut orchestrator_transport = transport.clone();
-
-        let _listener = transport
-            .listen("inproc://otherplace:8000")
-            .expect("Unable to get listener");
-        let _admin_listener = transport
-            .listen("inproc://admin-service")
-            .expect("Unable to get listener");
-
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
-        let orchestrator_connection = orchestrator_transport
-            .connect("inproc://admin-service")
-            .expect("failed to create connection");
-        let orchestrator = ServiceOrchestratorBuilder::new()
-            .with_connection(orchestrator_connection)
-            .build()
-            .expect("failed to create orchestrator")
-            .run()
-            .expect("failed to start orchestrator");
+    // test that if a circuit does not have authorization set an error is returned
+    fn test_validate_circuit_no_authorization() {
         let store = setup_admin_service_store();
-
         let event_store = store.clone_boxed();
 
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let mut shared = AdminServiceShared::new(
-            "test-node".into(),
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
             peer_connector,
@@ -3913,84 +8164,66 @@ mod tests {
             event_store,
             vec![],
         );
+        let mut circuit = setup_test_circuit();
 
-        let service_sender = MockServiceNetworkSender::new();
-        shared.set_network_sender(Some(Box::new(service_sender.clone())));
-
-        let mut circuit = admin::Circuit::new();
-        circuit.set_circuit_id("01234-ABCDE".into());
-        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
-        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
-        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
-        circuit.set_circuit_management_type("test app auth handler".into());
-        circuit.set_comments("test circuit".into());
-        circuit.set_display_name("test_display".into());
-        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
-        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
-
-        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
-            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
-            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
-        ]));
-        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
-            splinter_service("0123", "sabre"),
-            splinter_service("ABCD", "sabre"),
-        ]));
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
-
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        circuit.set_authorization_type(Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE);
 
-        let mut payload = admin::CircuitManagementPayload::new();
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because authorization type is unset");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-        payload.set_signature(Vec::new());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_create_request(request);
+    #[test]
+    // test that if a circuit does not have persistence set an error is returned
+    fn test_validate_circuit_no_persitance() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
 
-        shared
-            .propose_circuit(payload, "local".to_string())
-            .expect("Proposal not accepted");
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
-        // None of the proposed members are peered
-        assert_eq!(1, shared.unpeered_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
-        // Set other-node to peered
-        shared
-            .on_peer_connected(&PeerTokenPair::new(
-                PeerAuthorizationToken::from_peer_id("other-node"),
-                PeerAuthorizationToken::from_peer_id("test-node"),
-            ))
-            .expect("Unable to set peer to peered");
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        assert_eq!(0, shared.unpeered_payloads.len());
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-        shared
-            .on_protocol_agreement("admin::other-node", 0)
-            .expect("received unexpected error");
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
 
-        // The message should be dropped
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-        assert_eq!(0, shared.pending_protocol_payloads.len());
+        circuit.set_persistence(Circuit_PersistenceType::UNSET_PERSISTENCE_TYPE);
 
-        // sent the service protocol request but not the payload
-        assert_eq!(
-            1,
-            service_sender
-                .sent
-                .lock()
-                .expect("Network sender lock poisoned")
-                .len()
-        );
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because persistence type is unset");
+        }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that a valid circuit is validated correctly
-    fn test_validate_circuit_valid() {
+    // test that if a circuit does not have durability set an error is returned
+    fn test_validate_circuit_unset_durability() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4015,25 +8248,24 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_test_circuit();
 
-        if let Err(err) = admin_shared.validate_create_circuit(
+        circuit.set_durability(Circuit_DurabilityType::UNSET_DURABILITY_TYPE);
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been valid: {}", err);
+            panic!("Should have been invalid because durabilty type is unset");
         }
-
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // Test that a valid circuit on version 2, would fail on protocol 1 because display_name is
-    // set. Protocol 1 should fail any circuit that has display name set, as display name is not
-    // included in the protobuf.
-    fn test_validate_invalid_protocol_display_name() {
+    // test that if a circuit does not have route type set an error is returned
+    fn test_validate_circuit_no_routes() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4058,19 +8290,24 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_test_circuit();
 
-        if let Ok(()) = admin_shared.validate_create_circuit(&circuit, PUB_KEY, "node_a", 1) {
-            panic!("Should have been invalid because display name is set");
-        }
+        circuit.set_routes(Circuit_RouteType::UNSET_ROUTE_TYPE);
 
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because route type is unset");
+        }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that a circuit proposed with a key that is not permitted for the requesting node is
-    // invalid
-    fn test_validate_circuit_signer_not_permitted() {
+    // test that if a circuit does not have circuit_management_type set an error is returned
+    fn test_validate_circuit_no_management_type() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4089,13 +8326,15 @@ mod tests {
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(MockAdminKeyVerifier::default()),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_circuit_management_type("".to_string());
 
         if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
@@ -4103,15 +8342,14 @@ mod tests {
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to requester node not being registered");
+            panic!("Should have been invalid because route type is unset");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit is proposed by a signer key is not a valid public key the proposal is
-    // invalid
-    fn test_validate_circuit_signer_key_invalid() {
+    // test that if a circuit has challenge auth set while circuit version 1 an error is returned
+    fn test_validate_circuit_challenge_auth_not_supported() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4136,37 +8374,68 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_v1_test_circuit();
+
+        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
 
-        let pub_key = (0u8..50).collect::<Vec<_>>();
-        // too short
         if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
-            &pub_key[0..10],
+            PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to key being too short");
+            panic!("Should have been invalid because cannot have challenge auth if version 1");
         }
-        // too long
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has challenge auth set and nodes do not have public keys, the circuit
+    // is invalid
+    fn test_validate_circuit_challenge_auth_no_public_keys() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
         if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
-            &pub_key,
+            PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to key being too long");
+            panic!("Should have been invalid because nodes do not have public keys set");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service in its roster with an allowed node that is not in
-    // members an error is returned
-    fn test_validate_circuit_bad_node() {
+    // test that a valid circuit proposal vote comes back as valid
+    fn test_validate_proposal_vote_valid() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
-
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
@@ -4188,32 +8457,28 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-
-        let mut service_bad = SplinterService::new();
-        service_bad.set_service_id("0123".to_string());
-        service_bad.set_service_type("type_a".to_string());
-        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec!["node_bad".to_string()]));
-
-        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Err(err) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to service having an allowed node not in members");
+            panic!("Should have been valid: {}", err);
         }
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "admin-service-proposal-expiry")]
     #[test]
-    // test that if a circuit has a service in its roster with too many allowed nodes
-    fn test_validate_circuit_too_many_allowed_nodes() {
+    // test that an expiry request is only valid if it is for the current version of the proposal
+    // and comes from a member of the proposed circuit
+    fn test_validate_proposal_expire() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
-
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
@@ -4235,32 +8500,48 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
+        let circuit = setup_test_circuit();
+        let proposal = StoreProposal::from_proto(setup_test_proposal(&circuit))
+            .expect("Unable to get proposal");
 
-        let mut service_bad = SplinterService::new();
-        service_bad.set_service_id("0123".to_string());
-        service_bad.set_service_type("type_a".to_string());
-        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec![
-            "node_b".to_string(),
-            "extra".to_string(),
-        ]));
+        let mut expire_request = CircuitProposalExpire::new();
+        expire_request.set_circuit_id(circuit.get_circuit_id().to_string());
+        expire_request.set_circuit_hash(sha256(&circuit).unwrap());
 
-        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+        if let Err(err) =
+            admin_shared.validate_proposal_expire(&expire_request, PUB_KEY, &proposal, "node_b")
+        {
+            panic!("Should have been valid: {}", err);
+        }
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid due to service having too many allowed nodes");
+        // the request comes from a node that is not a member of the proposed circuit
+        if let Ok(()) =
+            admin_shared.validate_proposal_expire(&expire_request, PUB_KEY, &proposal, "node_c")
+        {
+            panic!("Should have been invalid because the requester is not a member");
+        }
+
+        // the request comes from this node, but was not signed by one of its keys
+        if let Ok(()) =
+            admin_shared.validate_proposal_expire(&expire_request, PUB_KEY, &proposal, "node_a")
+        {
+            panic!("Should have been invalid because the key does not belong to this node");
+        }
+
+        // the request is for a different version of the proposal
+        expire_request.set_circuit_hash("old_hash".to_string());
+        if let Ok(()) =
+            admin_shared.validate_proposal_expire(&expire_request, PUB_KEY, &proposal, "node_b")
+        {
+            panic!("Should have been invalid because the circuit hash does not match");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service with "" for a service id an error is returned
-    fn test_validate_circuit_empty_service_id() {
+    // test that if the vote is from a key that is not permitted for the voting node the vote is
+    // invalid
+    fn test_validate_proposal_vote_not_permitted() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4279,35 +8560,30 @@ mod tests {
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(MockAdminKeyVerifier::new(false)),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-
-        let mut service_ = SplinterService::new();
-        service_.set_service_id("".to_string());
-        service_.set_service_type("type_a".to_string());
-        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
-
-        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to service's id being empty");
+            panic!("Should have been invalid because voting node is not registered");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service with an invalid service id an error is returned
-    fn test_validate_circuit_invalid_service_id() {
+    // test if the voter is the original requester node the vote is invalid
+    fn test_validate_proposal_vote_requester() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4332,29 +8608,24 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-
-        let mut service_ = SplinterService::new();
-        service_.set_service_id("invalid_service_id".to_string());
-        service_.set_service_type("type_a".to_string());
-        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
-
-        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
+            "node_b",
         ) {
-            panic!("Should have been invalid due to service's id being empty");
+            panic!("Should have been invalid because voter is the requester");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service with duplicate service ids an error is returned
-    fn test_validate_circuit_duplicate_service_id() {
+    // test if a voter has already voted on a proposal the new vote is invalid
+    fn test_validate_proposal_vote_duplicate_vote() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4379,34 +8650,32 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-
-        let mut service_a = SplinterService::new();
-        service_a.set_service_id("0123".to_string());
-        service_a.set_service_type("type_a".to_string());
-        service_a.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
 
-        let mut service_a2 = SplinterService::new();
-        service_a2.set_service_id("0123".to_string());
-        service_a2.set_service_type("type_a".to_string());
-        service_a2.set_allowed_nodes(RepeatedField::from_vec(vec!["node_b".to_string()]));
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+        vote_record.set_public_key(b"test_signer_a".to_vec());
+        vote_record.set_voter_node_id("node_a".to_string());
 
-        circuit.set_roster(RepeatedField::from_vec(vec![service_a, service_a2]));
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to service's id being a duplicate");
+            panic!("Should have been invalid because node as already submitted a vote");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit does not have any services in its roster an error is returned
-    fn test_validate_circuit_empty_roster() {
+    // test that if the circuit hash in the circuit proposal does not match the circuit hash on
+    // the vote, the vote is invalid
+    fn test_validate_proposal_vote_circuit_hash_mismatch() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4431,35 +8700,42 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-        circuit.set_roster(RepeatedField::from_vec(vec![]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        proposal.set_circuit_hash("bad_hash".to_string());
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to empty roster");
+            panic!("Should have been invalid because the circuit hash does not match");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit does not have any nodes in its members an error is returned
-    fn test_validate_circuit_empty_members() {
+    // test that the validate_circuit_management_payload method returns an error in case the
+    // signature is empty.
+    fn test_validate_circuit_management_payload_signature() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4472,37 +8748,53 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_members(RepeatedField::from_vec(vec![]));
+        let circuit = setup_test_circuit();
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid empty members");
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(PUB_KEY.into());
+        header.set_requester_node_id("node_b".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        // Asserting the payload will be deemed invalid as the signature is an empty vec.
+        if let Ok(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been invalid due to empty signature");
+        }
+
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        // Asserting the payload passed through validation.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit does not have the local node in the member list an error is
-    // returned
-    fn test_validate_circuit_missing_local_node() {
+    // test that the validate_circuit_management_payload method returns an error in case the
+    // header is empty.
+    fn test_validate_circuit_management_payload_header() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4515,41 +8807,54 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+        let circuit = setup_test_circuit();
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_b]));
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because node_a is not in members");
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(PUB_KEY.into());
+        header.set_requester_node_id("node_b".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_create_request(request);
+
+        // Asserting the payload will be deemed invalid as the header is empty.
+        match shared.validate_circuit_management_payload(&payload, &header) {
+            Err(err) => assert!(err
+                .to_string()
+                .contains("CircuitManagementPayload header must be set")),
+            _ => panic!("Should have been invalid because empty requester field"),
+        }
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload passed through validation, and failed at a further step.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a member with node id of "" an error is
-    // returned
-    fn test_validate_circuit_empty_node_id() {
+    // test that the validate_circuit_management_payload method returns an error in case the header
+    // requester field is empty.
+    fn test_validate_circuit_management_header_requester() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4562,48 +8867,56 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+        let circuit = setup_test_circuit();
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
 
-        let mut node_ = SplinterNode::new();
-        node_.set_node_id("".to_string());
-        node_.set_endpoints(vec!["test://endpoint_:0".to_string()].into());
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester_node_id("node_b".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_create_request(request);
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_]));
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload will be deemed invalid as the header is empty.
+        match shared.validate_circuit_management_payload(&payload, &header) {
+            Err(err) => assert!(err
+                .to_string()
+                .contains("CircuitManagementPayload must have a requester")),
+            _ => panic!("Should have been invalid because empty requester field"),
+        }
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because node_ is has an empty node id");
+        header.set_requester(PUB_KEY.into());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload passed through validation, and failed at a further step.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has duplicate members an error is returned
-    fn test_validate_circuit_duplicate_members() {
+    // test that the CircuitManagementPayload returns an error in case the header requester_node_id
+    // field is empty.
+    fn test_validate_circuit_management_header_requester_node_id() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4616,36 +8929,48 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+        let circuit = setup_test_circuit();
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
 
-        let mut node_b2 = SplinterNode::new();
-        node_b2.set_node_id("node_b".to_string());
-        node_b2.set_endpoints(vec!["test://endpoint_b2:0".to_string()].into());
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(PUB_KEY.into());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_create_request(request);
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_b2]));
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload will be deemed invalid as the header is empty.
+        match shared.validate_circuit_management_payload(&payload, &header) {
+            Err(err) => assert!(err
+                .to_string()
+                .contains("CircuitManagementPayload must have a requester node id")),
+            _ => panic!("Should have been invalid because empty requester field"),
+        }
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because there are duplicate members");
+        header.set_requester_node_id("node_b".to_string());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload passed through validation, and failed at a further step.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a circuit being disbanded is validated correctly
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be disbanded to the admin store. This is required by the
+    ///    `validate_disband_circuit` which verifies the circuit exists.
+    /// 3. Validate the call to `validate_disband_circuit` returns successfully
+    ///
+    /// This test verifies the `validate_disband_circuit` returns successfully when given a
+    /// valid request to disband an existing circuit.
     #[test]
-    // test that if a circuit has an empty circuit id an error is returned
-    fn test_validate_circuit_empty_circuit_id() {
+    fn test_validate_disband_circuit_valid() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4657,7 +8982,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4670,24 +8995,42 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_circuit_id("".to_string());
+        // Add the circuit to be disbanded
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Err(err) = shared.validate_disband_circuit(
+            &setup_test_circuit(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because the circuit ID is empty");
+            panic!("Should have been valid: {}", err);
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a circuit is unable to be disbanded when an invalid admin service protocol
+    /// version is used. Currently, the disband functionality is not available for
+    /// admin service protocol 1.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be disbanded to the admin store.
+    /// 3. Call `validate_disband_circuit` with a valid Circuit, valid requester info and protocol
+    ///    version 1.
+    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    ///
+    /// This test verifies the `validate_disband_circuit` returns an error when given
+    /// an admin service protocol that is not above 1.
     #[test]
-    // test that if a circuit has an invalid circuit id an error is returned
-    fn test_validate_circuit_invalid_circuit_id() {
+    fn test_validate_disband_circuit_invalid_protocol() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4699,7 +9042,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4712,24 +9055,40 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_circuit_id("invalid_circuit_id".to_string());
+        // Add the circuit to be disbanded
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because the circuit ID is invalid");
+        if let Ok(()) =
+            shared.validate_disband_circuit(&setup_v1_test_circuit(), PUB_KEY, "node_a", 1)
+        {
+            panic!(
+                "Should have been invalid because the admin service protocol schema version is 1"
+            );
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a circuit being disbanded is invalid if the circuit to be disbanded has an
+    /// invalid circuit version.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a v1 circuit to the admin store. This is required by the `validate_disband_circuit`,
+    ///    which verifies the circuit exists.
+    /// 3. Call `validate_disband_circuit` with a version 1 Circuit and valid requester info
+    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    ///
+    /// This test verifies the `validate_disband_circuit` returns an error when given
+    /// a version 1 circuit as disbanding functionality is not supported by version 1 circuits.
     #[test]
-    // test that if a circuit has a member with no endpoints an error is returned
-    fn test_validate_circuit_no_endpoints() {
+    fn test_validate_disband_circuit_invalid_circuit_version() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4754,32 +9113,41 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
-
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec![].into());
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
+        // Add the v1 circuit to be attempted to disbanded
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(1, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(()) = admin_shared.validate_disband_circuit(
+            &setup_v1_test_circuit(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because a member has no endpoints");
+            panic!(
+                "Should have been invalid because the circuit being disbanded is schema version 1"
+            );
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a circuit being disbanded is invalid if the circuit to be disbanded does not
+    /// exist in the admin store, indicating an invalid disband request.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Call `validate_disband_circuit` with a valid circuit and valid requester info
+    /// 3. Validate the call to `validate_disband_circuit` returns an error
+    ///
+    /// This test verifies the `validate_disband_circuit` returns an error when given
+    /// a circuit that does not already exist in the admin store.
     #[test]
-    // test that if a circuit has a member with an empty endpoint an error is returned
-    fn test_validate_circuit_empty_endpoint() {
+    fn test_validate_disband_circuit_no_circuit() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4804,32 +9172,31 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
-
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["".to_string()].into());
-
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(()) = admin_shared.validate_disband_circuit(
+            &setup_test_circuit(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because a member has an empty endpoint");
+            panic!("Should have been invalid because the circuit being disbanded does not exist");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
+    /// the node.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit to the admin store
+    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
+    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    ///
+    /// This test verifies the `validate_disband_circuit` returns an error when given a signer
+    /// key that is not permitted on the node.
     #[test]
-    // test that if a circuit has a member with a duplicate endpoint an error is returned
-    fn test_validate_circuit_duplicate_endpoint() {
+    fn test_validate_disband_circuit_not_permitted() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4848,38 +9215,47 @@ mod tests {
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(MockAdminKeyVerifier::new(false)),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
-
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
-
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
+        // Add the circuit to be disbanded
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(()) = admin_shared.validate_disband_circuit(
+            &setup_test_circuit(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because a member has a duplicate endpoint");
+            panic!("Should have been invalid because the requester is not authorized");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
+    /// the node.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
+    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
+    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    ///
+    /// This test verifies the `validate_disband_circuit` returns an error when the circuit
+    /// attempted to be disbanded already has a status of `Disbanded`. The disbanded action
+    /// is only valid for currently `Active` circuits.
     #[test]
-    // test that if a circuit does not have authorization set an error is returned
-    fn test_validate_circuit_no_authorization() {
+    fn test_validate_disband_circuit_already_disbanded() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4904,36 +9280,80 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_authorization_type(Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE);
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(()) = admin_shared.validate_disband_circuit(
+            &setup_test_circuit(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because authorization type is unset");
+            panic!("Should have been invalid due to circuit already being disbanded");
         }
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that the payload submitted via `propose_disband` is moved to the admin service's
+    /// payload lists as peers become fully peered, authorized and agree on a service protocol.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit to the admin store
+    /// 3. Create the `CircuitDisbandRequest` payload
+    /// 4. Start another thread to handle authorization for the peer node, "node_b"
+    /// 5. Set each node to peered. The `CircuitDisbandRequest` is only valid for circuits that
+    ///    have already been created, so nodes are assumed to be peered before the request is
+    ///    submitted.
+    /// 6. Submit the `CircuitDisbandRequest` payload
+    /// 7. Assert the payload is present in the "pending_protocol_payloads" list and that no
+    ///    payload is available in the `pending_circuit_payloads` list
+    /// 8. Set each node to agree on the protocol
+    /// 9. Assert the payload is present in the "pending_circuit_payloads" list and that no
+    ///    payload is available in the `pending_protocol_payloads` list
+    ///
+    /// This test verifies a payload submitted using `propose_disband` is moved to the admin
+    /// service's payload lists as expected when the circuit nodes have already been peered
+    /// and then agree on the admin service protocol.
     #[test]
-    // test that if a circuit does not have persistence set an error is returned
-    fn test_validate_circuit_no_persitance() {
+    fn test_disband_request() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let mut other_listener = transport
+            .listen("inproc://otherplace:8000")
+            .expect("Unable to get listener");
+        let _test_listener = transport
+            .listen("inproc://someplace:8000")
+            .expect("Unable to get listener");
+        let _orchestator_listener = transport
+            .listen("inproc://orchestator")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://orchestator")
+            .expect("failed to create connection");
+        let orchestrator = ServiceOrchestratorBuilder::new()
+            .with_connection(orchestrator_connection)
+            .build()
+            .expect("failed to create orchestrator")
+            .run()
+            .expect("failed to start orchestrator");
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
-        let orchestrator = setup_orchestrator();
-
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let mut shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4946,24 +9366,108 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_persistence(Circuit_PersistenceType::UNSET_PERSISTENCE_TYPE);
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because persistence type is unset");
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        for node in store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active)
+            .list_nodes()
+            .expect("Unable to get peer nodes from circuit")
+        {
+            shared.token_to_peer.insert(
+                PeerTokenPair::new(
+                    node.token.clone(),
+                    PeerAuthorizationToken::from_peer_id("node_a"),
+                ),
+                PeerNodePair {
+                    peer_node: node,
+                    local_peer_token: PeerAuthorizationToken::from_peer_id("node_a"),
+                },
+            );
         }
+
+        let mut request = admin::CircuitDisbandRequest::new();
+        request.set_circuit_id("01234-ABCDE".to_string());
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST);
+        header.set_requester(b"test_signer_a".to_vec());
+        header.set_requester_node_id("node_a".to_string());
+
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(b"test_signer_a".to_vec());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_disband_request(request);
+        // start up thread for other node
+        std::thread::spawn(move || {
+            let mut mesh = Mesh::new(2, 2);
+            let conn = other_listener.accept().unwrap();
+            mesh.add(conn, "my_peer_id".to_string()).unwrap();
+
+            handle_auth(&mesh, "my_peer_id", "node_b");
+
+            mesh.signal_shutdown();
+            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        // Set `node_b` to peered
+        shared
+            .on_peer_connected(&PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("node_b"),
+                PeerAuthorizationToken::from_peer_id("node_a"),
+            ))
+            .expect("Unable to set peer to peered");
+
+        shared
+            .propose_disband(
+                payload,
+                &b"test_signer_a".to_vec(),
+                "node_a",
+                "test".to_string(),
+            )
+            .expect("Proposal not accepted");
+
+        // We're fully peered, but need to wait for protocol to be agreed on
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set `node_b` to agree on the protocol
+        shared
+            .on_protocol_agreement("admin::node_b", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .expect("received unexpected error");
+
+        // Set `node_a` to agree on the protocol
+        shared
+            .on_protocol_agreement("admin::node_a", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .expect("received unexpected error");
+
+        // We're fully peered and agreed on protocol, so the pending payload is now available
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+        assert_eq!(1, shared.pending_circuit_payloads.len());
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a roster update is validated correctly
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Make the update roster proposal, adding a service for `node_b` and removing the
+    ///    existing `node_b` service
+    /// 4. Validate the call to `validate_update_roster` returns successfully
+    /// 5. Validate the proposed roster contains the added service and not the removed service
+    ///
+    /// This test verifies the `validate_update_roster` returns successfully when given a valid
+    /// request to update the roster of an existing circuit.
     #[test]
-    // test that if a circuit does not have durability set an error is returned
-    fn test_validate_circuit_unset_durability() {
+    fn test_validate_update_roster_valid() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4975,7 +9479,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4988,24 +9492,58 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_durability(Circuit_DurabilityType::UNSET_DURABILITY_TYPE);
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        let request = setup_update_roster_request(
+            vec![roster_service("EFGH", "node_b")],
+            vec![roster_service("ABCD", "node_b")],
+        );
+        let circuit_proposal = shared
+            .make_update_roster_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update roster proposal");
+
+        if let Err(err) = shared.validate_update_roster(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because durabilty type is unset");
+            panic!("Should have been valid: {}", err);
         }
+
+        assert_eq!(
+            circuit_proposal.get_proposal_type(),
+            CircuitProposal_ProposalType::UPDATE_ROSTER
+        );
+        let service_ids = circuit_proposal
+            .get_circuit_proposal()
+            .get_roster()
+            .iter()
+            .map(|service| service.get_service_id())
+            .collect::<Vec<&str>>();
+        assert_eq!(service_ids, vec!["0123", "EFGH"]);
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a roster update is invalid when an invalid admin service protocol version is
+    /// used. Updating a circuit's roster is not available for admin service protocol 1.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_update_roster` with a valid request, valid requester info and protocol
+    ///    version 1.
+    /// 4. Validate the call to `validate_update_roster` returns an error
     #[test]
-    // test that if a circuit does not have route type set an error is returned
-    fn test_validate_circuit_no_routes() {
+    fn test_validate_update_roster_invalid_protocol() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5017,7 +9555,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5030,24 +9568,41 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_routes(Circuit_RouteType::UNSET_ROUTE_TYPE);
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let request = setup_update_roster_request(vec![roster_service("EFGH", "node_b")], vec![]);
+        let circuit_proposal = shared
+            .make_update_roster_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update roster proposal");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(()) = shared.validate_update_roster(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
+            1,
         ) {
-            panic!("Should have been invalid because route type is unset");
+            panic!("Should have been invalid because the admin service protocol version is 1");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a roster update is invalid if it does not add or remove any services.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_update_roster` with an empty request and valid requester info
+    /// 4. Validate the call to `validate_update_roster` returns an error
     #[test]
-    // test that if a circuit does not have circuit_management_type set an error is returned
-    fn test_validate_circuit_no_management_type() {
+    fn test_validate_update_roster_no_changes() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5059,7 +9614,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5072,24 +9627,42 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_circuit_management_type("".to_string());
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        let request = setup_update_roster_request(vec![], vec![]);
+        let circuit_proposal = shared
+            .make_update_roster_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update roster proposal");
+
+        if let Ok(()) = shared.validate_update_roster(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because route type is unset");
+            panic!("Should have been invalid because no services are added or removed");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a roster update is invalid if a service being removed is not in the circuit's
+    /// roster.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_update_roster` with a request removing an unknown service
+    /// 4. Validate the call to `validate_update_roster` returns an error
     #[test]
-    // test that if a circuit has challenge auth set while circuit version 1 an error is returned
-    fn test_validate_circuit_challenge_auth_not_supported() {
+    fn test_validate_update_roster_remove_unknown_service() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5101,7 +9674,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5114,25 +9687,42 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_v1_test_circuit();
 
-        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        let request = setup_update_roster_request(vec![], vec![roster_service("EFGH", "node_b")]);
+        let circuit_proposal = shared
+            .make_update_roster_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update roster proposal");
+
+        if let Ok(()) = shared.validate_update_roster(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because cannot have challenge auth if version 1");
+            panic!("Should have been invalid because the removed service is not in the roster");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a roster update is invalid if a service being added is not allowed on a member
+    /// of the circuit.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_update_roster` with a request adding a service for an unknown node
+    /// 4. Validate the call to `validate_update_roster` returns an error
     #[test]
-    // test that if a circuit has challenge auth set and nodes do not have public keys, the circuit
-    // is invalid
-    fn test_validate_circuit_challenge_auth_no_public_keys() {
+    fn test_validate_update_roster_bad_node() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5144,7 +9734,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5157,25 +9747,44 @@ mod tests {
             event_store,
             vec![],
         );
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_authorization_type(Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION);
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let request = setup_update_roster_request(vec![roster_service("EFGH", "node_c")], vec![]);
+        let circuit_proposal = shared
+            .make_update_roster_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update roster proposal");
+
+        if let Ok(()) = shared.validate_update_roster(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because nodes do not have public keys set");
+            panic!("Should have been invalid because node_c is not a member of the circuit");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a roster update is invalid if the circuit to be updated is not `Active`.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
+    /// 3. Call `validate_update_roster` with a valid request and valid requester info
+    /// 4. Validate the call to `validate_update_roster` returns an error
     #[test]
-    // test that a valid circuit proposal vote comes back as valid
-    fn test_validate_proposal_vote_valid() {
+    fn test_validate_update_roster_inactive_circuit() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
+
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
@@ -5184,7 +9793,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5197,25 +9806,46 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
 
-        if let Err(err) = admin_shared.validate_circuit_vote(
-            &vote,
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let request = setup_update_roster_request(vec![roster_service("EFGH", "node_b")], vec![]);
+        let circuit_proposal = shared
+            .make_update_roster_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update roster proposal");
+
+        if let Ok(()) = shared.validate_update_roster(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been valid: {}", err);
+            panic!("Should have been invalid because the circuit is disbanded");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to add a node is validated correctly
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Make the add node proposal, adding `node_c` with a service for `node_c`
+    /// 4. Validate the call to `validate_add_node` returns successfully
+    /// 5. Validate the proposed circuit contains `node_c` as its last member, contains the added
+    ///    service and has been updated to the current circuit version
+    ///
+    /// This test verifies the `validate_add_node` returns successfully when given a valid request
+    /// to add a node to an existing circuit.
     #[test]
-    // test that if the vote is from a key that is not permitted for the voting node the vote is
-    // invalid
-    fn test_validate_proposal_vote_not_permitted() {
+    fn test_validate_add_node_valid() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5227,37 +9857,78 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(MockAdminKeyVerifier::default()),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION - 1, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let request = setup_add_node_request("node_c", vec![roster_service("EFGH", "node_c")]);
+        let circuit_proposal = shared
+            .make_add_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make add node proposal");
+
+        if let Err(err) = shared.validate_add_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because voting node is not registered");
+            panic!("Should have been valid: {}", err);
         }
+
+        assert_eq!(
+            circuit_proposal.get_proposal_type(),
+            CircuitProposal_ProposalType::ADD_NODE
+        );
+        let proposed_circuit = circuit_proposal.get_circuit_proposal();
+        let node_ids = proposed_circuit
+            .get_members()
+            .iter()
+            .map(|node| node.get_node_id())
+            .collect::<Vec<&str>>();
+        assert_eq!(node_ids, vec!["node_a", "node_b", "node_c"]);
+        let service_ids = proposed_circuit
+            .get_roster()
+            .iter()
+            .map(|service| service.get_service_id())
+            .collect::<Vec<&str>>();
+        assert_eq!(service_ids, vec!["0123", "ABCD", "EFGH"]);
+        assert_eq!(
+            proposed_circuit.get_circuit_version(),
+            CIRCUIT_PROTOCOL_VERSION
+        );
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to add a node is invalid when an invalid admin service protocol
+    /// version is used. Adding a node to a circuit is not available for admin service protocol 1.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_add_node` with a valid request, valid requester info and protocol
+    ///    version 1.
+    /// 4. Validate the call to `validate_add_node` returns an error
     #[test]
-    // test if the voter is the original requester node the vote is invalid
-    fn test_validate_proposal_vote_requester() {
+    fn test_validate_add_node_invalid_protocol() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5269,7 +9940,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5282,24 +9953,42 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let request = setup_add_node_request("node_c", vec![roster_service("EFGH", "node_c")]);
+        let circuit_proposal = shared
+            .make_add_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make add node proposal");
+
+        if let Ok(()) = shared.validate_add_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
-            "node_b",
+            "node_a",
+            1,
         ) {
-            panic!("Should have been invalid because voter is the requester");
+            panic!("Should have been invalid because the protocol version is 1");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to add a node is invalid if the node is already a member of the
+    /// circuit.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_add_node` with a request to add `node_b`, which is already a member
+    /// 4. Validate the call to `validate_add_node` returns an error
     #[test]
-    // test if a voter has already voted on a proposal the new vote is invalid
-    fn test_validate_proposal_vote_duplicate_vote() {
+    fn test_validate_add_node_existing_member() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5311,7 +10000,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5324,32 +10013,43 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let mut proposal = setup_test_proposal(&circuit);
 
-        let mut vote_record = CircuitProposal_VoteRecord::new();
-        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
-        vote_record.set_public_key(b"test_signer_a".to_vec());
-        vote_record.set_voter_node_id("node_a".to_string());
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
+        let request = setup_add_node_request("node_b", vec![roster_service("EFGH", "node_b")]);
+        let circuit_proposal = shared
+            .make_add_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make add node proposal");
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        if let Ok(()) = shared.validate_add_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because node as already submitted a vote");
+            panic!("Should have been invalid because node_b is already a member of the circuit");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to add a node is invalid if a new service is not allowed to run on
+    /// the node being added.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_add_node` with a request to add `node_c` that contains a new service
+    ///    for `node_b`
+    /// 4. Validate the call to `validate_add_node` returns an error
     #[test]
-    // test that if the circuit hash in the circuit proposal does not match the circuit hash on
-    // the vote, the vote is invalid
-    fn test_validate_proposal_vote_circuit_hash_mismatch() {
+    fn test_validate_add_node_invalid_service() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5361,7 +10061,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5374,37 +10074,49 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let mut proposal = setup_test_proposal(&circuit);
 
-        proposal.set_circuit_hash("bad_hash".to_string());
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        let request = setup_add_node_request("node_c", vec![roster_service("EFGH", "node_b")]);
+        let circuit_proposal = shared
+            .make_add_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make add node proposal");
+
+        if let Ok(()) = shared.validate_add_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because the circuit hash does not match");
+            panic!("Should have been invalid because the new service is not allowed on node_c");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to add a node is invalid if the circuit to be updated is not
+    /// `Active`.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
+    /// 3. Call `validate_add_node` with a valid request and valid requester info
+    /// 4. Validate the call to `validate_add_node` returns an error
     #[test]
-    // test that the validate_circuit_management_payload method returns an error in case the
-    // signature is empty.
-    fn test_validate_circuit_management_payload_signature() {
+    fn test_validate_add_node_inactive_circuit() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
@@ -5423,47 +10135,52 @@ mod tests {
             vec![],
         );
 
-        let circuit = setup_test_circuit();
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester(PUB_KEY.into());
-        header.set_requester_node_id("node_b".to_string());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(Vec::new());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_create_request(request);
+        let request = setup_add_node_request("node_c", vec![roster_service("EFGH", "node_c")]);
+        let circuit_proposal = shared
+            .make_add_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make add node proposal");
 
-        // Asserting the payload will be deemed invalid as the signature is an empty vec.
-        if let Ok(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been invalid due to empty signature");
+        if let Ok(()) = shared.validate_add_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit is disbanded");
         }
 
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        // Asserting the payload passed through validation.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
-        }
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to remove a node is validated correctly
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Make the remove node proposal, removing `node_b` from the circuit with three members
+    /// 4. Validate the call to `validate_remove_node` returns successfully
+    /// 5. Validate the proposed circuit no longer contains `node_b` or the service that ran on
+    ///    `node_b`
+    ///
+    /// This test verifies the `validate_remove_node` returns successfully when given a valid
+    /// request to remove a node from an existing circuit.
     #[test]
-    // test that the validate_circuit_management_payload method returns an error in case the
-    // header is empty.
-    fn test_validate_circuit_management_payload_header() {
+    fn test_validate_remove_node_valid() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
@@ -5482,48 +10199,68 @@ mod tests {
             vec![],
         );
 
-        let circuit = setup_test_circuit();
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        shared
+            .admin_store
+            .add_circuit(
+                three_member_store_circuit(),
+                three_member_store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester(PUB_KEY.into());
-        header.set_requester_node_id("node_b".to_string());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        payload.set_circuit_create_request(request);
+        let request = setup_remove_node_request("node_b", vec![roster_service("0123", "node_a")]);
+        let circuit_proposal = shared
+            .make_remove_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make remove node proposal");
 
-        // Asserting the payload will be deemed invalid as the header is empty.
-        match shared.validate_circuit_management_payload(&payload, &header) {
-            Err(err) => assert!(err
-                .to_string()
-                .contains("CircuitManagementPayload header must be set")),
-            _ => panic!("Should have been invalid because empty requester field"),
-        }
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload passed through validation, and failed at a further step.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
+        if let Err(err) = shared.validate_remove_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
         }
+
+        assert_eq!(
+            circuit_proposal.get_proposal_type(),
+            CircuitProposal_ProposalType::REMOVE_NODE
+        );
+        let proposed_circuit = circuit_proposal.get_circuit_proposal();
+        let node_ids = proposed_circuit
+            .get_members()
+            .iter()
+            .map(|node| node.get_node_id())
+            .collect::<Vec<&str>>();
+        assert_eq!(node_ids, vec!["node_a", "node_c"]);
+        let service_ids = proposed_circuit
+            .get_roster()
+            .iter()
+            .map(|service| service.get_service_id())
+            .collect::<Vec<&str>>();
+        assert_eq!(service_ids, vec!["0123", "WXYZ"]);
+
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to remove a node is invalid when an invalid admin service protocol
+    /// version is used. Removing a node from a circuit is not available for admin service
+    /// protocol 1.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_remove_node` with a valid request, valid requester info and protocol
+    ///    version 1.
+    /// 4. Validate the call to `validate_remove_node` returns an error
     #[test]
-    // test that the validate_circuit_management_payload method returns an error in case the header
-    // requester field is empty.
-    fn test_validate_circuit_management_header_requester() {
+    fn test_validate_remove_node_invalid_protocol() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
@@ -5542,50 +10279,48 @@ mod tests {
             vec![],
         );
 
-        let circuit = setup_test_circuit();
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester_node_id("node_b".to_string());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        payload.set_circuit_create_request(request);
+        let request = setup_remove_node_request("node_b", vec![]);
+        let circuit_proposal = shared
+            .make_remove_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make remove node proposal");
 
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload will be deemed invalid as the header is empty.
-        match shared.validate_circuit_management_payload(&payload, &header) {
-            Err(err) => assert!(err
-                .to_string()
-                .contains("CircuitManagementPayload must have a requester")),
-            _ => panic!("Should have been invalid because empty requester field"),
+        if let Ok(()) = shared.validate_remove_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
+            PUB_KEY,
+            "node_a",
+            1,
+        ) {
+            panic!("Should have been invalid because the protocol version is 1");
         }
 
-        header.set_requester(PUB_KEY.into());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload passed through validation, and failed at a further step.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
-        }
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a request to remove a node is invalid if the node is not a member of the
+    /// circuit.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_remove_node` with a request to remove `node_c`, which is not a member
+    /// 4. Validate the call to `validate_remove_node` returns an error
     #[test]
-    // test that the CircuitManagementPayload returns an error in case the header requester_node_id
-    // field is empty.
-    fn test_validate_circuit_management_header_requester_node_id() {
+    fn test_validate_remove_node_not_member() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
@@ -5604,47 +10339,41 @@ mod tests {
             vec![],
         );
 
-        let circuit = setup_test_circuit();
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester(PUB_KEY.into());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        payload.set_circuit_create_request(request);
+        let request = setup_remove_node_request("node_c", vec![]);
+        let circuit_proposal = shared
+            .make_remove_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make remove node proposal");
 
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload will be deemed invalid as the header is empty.
-        match shared.validate_circuit_management_payload(&payload, &header) {
-            Err(err) => assert!(err
-                .to_string()
-                .contains("CircuitManagementPayload must have a requester node id")),
-            _ => panic!("Should have been invalid because empty requester field"),
+        if let Ok(()) = shared.validate_remove_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_c is not a member of the circuit");
         }
 
-        header.set_requester_node_id("node_b".to_string());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload passed through validation, and failed at a further step.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
-        }
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit being disbanded is validated correctly
+    /// Tests that a request to remove a node is invalid if the node being removed is the
+    /// requesting node.
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Add the circuit to be disbanded to the admin store. This is required by the
-    ///    `validate_disband_circuit` which verifies the circuit exists.
-    /// 3. Validate the call to `validate_disband_circuit` returns successfully
-    ///
-    /// This test verifies the `validate_disband_circuit` returns successfully when given a
-    /// valid request to disband an existing circuit.
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_remove_node` with a request from `node_b` to remove `node_b`
+    /// 4. Validate the call to `validate_remove_node` returns an error
     #[test]
-    fn test_validate_disband_circuit_valid() {
+    fn test_validate_remove_node_requester() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5670,7 +10399,6 @@ mod tests {
             vec![],
         );
 
-        // Add the circuit to be disbanded
         shared
             .admin_store
             .add_circuit(
@@ -5679,32 +10407,34 @@ mod tests {
             )
             .expect("unable to add circuit to store");
 
-        if let Err(err) = shared.validate_disband_circuit(
-            &setup_test_circuit(),
+        let request = setup_remove_node_request("node_b", vec![]);
+        let circuit_proposal = shared
+            .make_remove_node_circuit_proposal(&request, PUB_KEY, "node_b")
+            .expect("unable to make remove node proposal");
+
+        if let Ok(()) = shared.validate_remove_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
-            "node_a",
+            "node_b",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been valid: {}", err);
+            panic!("Should have been invalid because node_b is the requesting node");
         }
 
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit is unable to be disbanded when an invalid admin service protocol
-    /// version is used. Currently, the disband functionality is not available for
-    /// admin service protocol 1.
+    /// Tests that a request to remove a node is invalid if fewer than two members would remain in
+    /// the circuit, as the removed node does not vote on its own removal.
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Add the circuit to be disbanded to the admin store.
-    /// 3. Call `validate_disband_circuit` with a valid Circuit, valid requester info and protocol
-    ///    version 1.
-    /// 4. Validate the call to `validate_disband_circuit` returns an error
-    ///
-    /// This test verifies the `validate_disband_circuit` returns an error when given
-    /// an admin service protocol that is not above 1.
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_remove_node` with a request from `node_a` to remove `node_b`, the only
+    ///    other member
+    /// 4. Validate the call to `validate_remove_node` returns an error
     #[test]
-    fn test_validate_disband_circuit_invalid_protocol() {
+    fn test_validate_remove_node_last_other_member() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5730,7 +10460,6 @@ mod tests {
             vec![],
         );
 
-        // Add the circuit to be disbanded
         shared
             .admin_store
             .add_circuit(
@@ -5739,30 +10468,33 @@ mod tests {
             )
             .expect("unable to add circuit to store");
 
-        if let Ok(()) =
-            shared.validate_disband_circuit(&setup_v1_test_circuit(), PUB_KEY, "node_a", 1)
-        {
-            panic!(
-                "Should have been invalid because the admin service protocol schema version is 1"
-            );
+        let request = setup_remove_node_request("node_b", vec![]);
+        let circuit_proposal = shared
+            .make_remove_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make remove node proposal");
+
+        if let Ok(()) = shared.validate_remove_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because only node_a would remain in the circuit");
         }
 
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit being disbanded is invalid if the circuit to be disbanded has an
-    /// invalid circuit version.
+    /// Tests that a request to remove a node is invalid if the circuit to be updated is not
+    /// `Active`.
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Add a v1 circuit to the admin store. This is required by the `validate_disband_circuit`,
-    ///    which verifies the circuit exists.
-    /// 3. Call `validate_disband_circuit` with a version 1 Circuit and valid requester info
-    /// 4. Validate the call to `validate_disband_circuit` returns an error
-    ///
-    /// This test verifies the `validate_disband_circuit` returns an error when given
-    /// a version 1 circuit as disbanding functionality is not supported by version 1 circuits.
+    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
+    /// 3. Call `validate_remove_node` with a valid request and valid requester info
+    /// 4. Validate the call to `validate_remove_node` returns an error
     #[test]
-    fn test_validate_disband_circuit_invalid_circuit_version() {
+    fn test_validate_remove_node_inactive_circuit() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5774,7 +10506,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5788,40 +10520,46 @@ mod tests {
             vec![],
         );
 
-        // Add the v1 circuit to be attempted to disbanded
-        admin_shared
+        shared
             .admin_store
             .add_circuit(
-                store_circuit(1, StoreCircuitStatus::Active),
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
                 store_circuit_nodes(),
             )
             .expect("unable to add circuit to store");
 
-        if let Ok(()) = admin_shared.validate_disband_circuit(
-            &setup_v1_test_circuit(),
+        let request = setup_remove_node_request("node_b", vec![]);
+        let circuit_proposal = shared
+            .make_remove_node_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make remove node proposal");
+
+        if let Ok(()) = shared.validate_remove_node(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!(
-                "Should have been invalid because the circuit being disbanded is schema version 1"
-            );
+            panic!("Should have been invalid because the circuit is disbanded");
         }
 
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit being disbanded is invalid if the circuit to be disbanded does not
-    /// exist in the admin store, indicating an invalid disband request.
+    /// Tests that a request to update the application metadata of a circuit is validated
+    /// correctly
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Call `validate_disband_circuit` with a valid circuit and valid requester info
-    /// 3. Validate the call to `validate_disband_circuit` returns an error
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Make the update application metadata proposal
+    /// 4. Validate the call to `validate_update_application_metadata` returns successfully
+    /// 5. Validate the proposed circuit contains the new application metadata and the circuit's
+    ///    existing members and services
     ///
-    /// This test verifies the `validate_disband_circuit` returns an error when given
-    /// a circuit that does not already exist in the admin store.
+    /// This test verifies the `validate_update_application_metadata` returns successfully when
+    /// given a valid request to update the application metadata of an existing circuit.
     #[test]
-    fn test_validate_disband_circuit_no_circuit() {
+    fn test_validate_update_application_metadata_valid() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5833,7 +10571,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5847,30 +10585,62 @@ mod tests {
             vec![],
         );
 
-        if let Ok(()) = admin_shared.validate_disband_circuit(
-            &setup_test_circuit(),
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let request = setup_update_application_metadata_request(b"new_metadata");
+        let circuit_proposal = shared
+            .make_update_application_metadata_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update application metadata proposal");
+
+        if let Err(err) = shared.validate_update_application_metadata(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because the circuit being disbanded does not exist");
+            panic!("Should have been valid: {}", err);
         }
 
+        assert_eq!(
+            circuit_proposal.get_proposal_type(),
+            CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA
+        );
+        let proposed_circuit = circuit_proposal.get_circuit_proposal();
+        assert_eq!(proposed_circuit.get_application_metadata(), b"new_metadata");
+        let node_ids = proposed_circuit
+            .get_members()
+            .iter()
+            .map(|node| node.get_node_id())
+            .collect::<Vec<&str>>();
+        assert_eq!(node_ids, vec!["node_a", "node_b"]);
+        let service_ids = proposed_circuit
+            .get_roster()
+            .iter()
+            .map(|service| service.get_service_id())
+            .collect::<Vec<&str>>();
+        assert_eq!(service_ids, vec!["0123", "ABCD"]);
+
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
-    /// the node.
+    /// Tests that a request to update the application metadata of a circuit is invalid when an
+    /// invalid admin service protocol version is used. Updating the application metadata is not
+    /// available for admin service protocol 1.
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Add a circuit to the admin store
-    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
-    /// 4. Validate the call to `validate_disband_circuit` returns an error
-    ///
-    /// This test verifies the `validate_disband_circuit` returns an error when given a signer
-    /// key that is not permitted on the node.
+    /// 2. Add the circuit to be updated to the admin store
+    /// 3. Call `validate_update_application_metadata` with a valid request, valid requester info
+    ///    and protocol version 1.
+    /// 4. Validate the call to `validate_update_application_metadata` returns an error
     #[test]
-    fn test_validate_disband_circuit_not_permitted() {
+    fn test_validate_update_application_metadata_invalid_protocol() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5882,22 +10652,21 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(MockAdminKeyVerifier::default()),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             event_store,
             vec![],
         );
 
-        // Add the circuit to be disbanded
-        admin_shared
+        shared
             .admin_store
             .add_circuit(
                 store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
@@ -5905,31 +10674,34 @@ mod tests {
             )
             .expect("unable to add circuit to store");
 
-        if let Ok(()) = admin_shared.validate_disband_circuit(
-            &setup_test_circuit(),
+        let request = setup_update_application_metadata_request(b"new_metadata");
+        let circuit_proposal = shared
+            .make_update_application_metadata_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update application metadata proposal");
+
+        if let Ok(()) = shared.validate_update_application_metadata(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
+            1,
         ) {
-            panic!("Should have been invalid because the requester is not authorized");
+            panic!("Should have been invalid because the protocol version is 1");
         }
 
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
-    /// the node.
-    ///
-    /// 1. Set up `AdminServiceShared`
-    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
-    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
-    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    /// Tests that a request to update the application metadata of a circuit is invalid if the
+    /// request does not change the circuit's application metadata.
     ///
-    /// This test verifies the `validate_disband_circuit` returns an error when the circuit
-    /// attempted to be disbanded already has a status of `Disbanded`. The disbanded action
-    /// is only valid for currently `Active` circuits.
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add the circuit to be updated, which has no application metadata, to the admin store
+    /// 3. Call `validate_update_application_metadata` with a request that sets empty
+    ///    application metadata
+    /// 4. Validate the call to `validate_update_application_metadata` returns an error
     #[test]
-    fn test_validate_disband_circuit_already_disbanded() {
+    fn test_validate_update_application_metadata_unchanged() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5941,7 +10713,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5955,79 +10727,54 @@ mod tests {
             vec![],
         );
 
-        admin_shared
+        shared
             .admin_store
             .add_circuit(
-                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
                 store_circuit_nodes(),
             )
             .expect("unable to add circuit to store");
 
-        if let Ok(()) = admin_shared.validate_disband_circuit(
-            &setup_test_circuit(),
+        let request = setup_update_application_metadata_request(b"");
+        let circuit_proposal = shared
+            .make_update_application_metadata_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update application metadata proposal");
+
+        if let Ok(()) = shared.validate_update_application_metadata(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to circuit already being disbanded");
+            panic!("Should have been invalid because the application metadata is unchanged");
         }
+
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that the payload submitted via `propose_disband` is moved to the admin service's
-    /// payload lists as peers become fully peered, authorized and agree on a service protocol.
+    /// Tests that a request to update the application metadata of a circuit is invalid if the
+    /// circuit to be updated is not `Active`.
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Add a circuit to the admin store
-    /// 3. Create the `CircuitDisbandRequest` payload
-    /// 4. Start another thread to handle authorization for the peer node, "node_b"
-    /// 5. Set each node to peered. The `CircuitDisbandRequest` is only valid for circuits that
-    ///    have already been created, so nodes are assumed to be peered before the request is
-    ///    submitted.
-    /// 6. Submit the `CircuitDisbandRequest` payload
-    /// 7. Assert the payload is present in the "pending_protocol_payloads" list and that no
-    ///    payload is available in the `pending_circuit_payloads` list
-    /// 8. Set each node to agree on the protocol
-    /// 9. Assert the payload is present in the "pending_circuit_payloads" list and that no
-    ///    payload is available in the `pending_protocol_payloads` list
-    ///
-    /// This test verifies a payload submitted using `propose_disband` is moved to the admin
-    /// service's payload lists as expected when the circuit nodes have already been peered
-    /// and then agree on the admin service protocol.
+    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
+    /// 3. Call `validate_update_application_metadata` with a valid request and valid requester
+    ///    info
+    /// 4. Validate the call to `validate_update_application_metadata` returns an error
     #[test]
-    fn test_disband_request() {
-        let mut transport = InprocTransport::default();
-        let mut orchestrator_transport = transport.clone();
-
-        let mut other_listener = transport
-            .listen("inproc://otherplace:8000")
-            .expect("Unable to get listener");
-        let _test_listener = transport
-            .listen("inproc://someplace:8000")
-            .expect("Unable to get listener");
-        let _orchestator_listener = transport
-            .listen("inproc://orchestator")
-            .expect("Unable to get listener");
-
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
-        let orchestrator_connection = orchestrator_transport
-            .connect("inproc://orchestator")
-            .expect("failed to create connection");
-        let orchestrator = ServiceOrchestratorBuilder::new()
-            .with_connection(orchestrator_connection)
-            .build()
-            .expect("failed to create orchestrator")
-            .run()
-            .expect("failed to start orchestrator");
+    fn test_validate_update_application_metadata_inactive_circuit() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let mut shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -6041,91 +10788,29 @@ mod tests {
             vec![],
         );
 
-        let service_sender = MockServiceNetworkSender::new();
-        shared.set_network_sender(Some(Box::new(service_sender.clone())));
-
         shared
             .admin_store
             .add_circuit(
-                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
                 store_circuit_nodes(),
             )
             .expect("unable to add circuit to store");
 
-        for node in store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active)
-            .list_nodes()
-            .expect("Unable to get peer nodes from circuit")
-        {
-            shared.token_to_peer.insert(
-                PeerTokenPair::new(
-                    node.token.clone(),
-                    PeerAuthorizationToken::from_peer_id("node_a"),
-                ),
-                PeerNodePair {
-                    peer_node: node,
-                    local_peer_token: PeerAuthorizationToken::from_peer_id("node_a"),
-                },
-            );
-        }
-
-        let mut request = admin::CircuitDisbandRequest::new();
-        request.set_circuit_id("01234-ABCDE".to_string());
-
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST);
-        header.set_requester(b"test_signer_a".to_vec());
-        header.set_requester_node_id("node_a".to_string());
-
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(b"test_signer_a".to_vec());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_disband_request(request);
-        // start up thread for other node
-        std::thread::spawn(move || {
-            let mut mesh = Mesh::new(2, 2);
-            let conn = other_listener.accept().unwrap();
-            mesh.add(conn, "my_peer_id".to_string()).unwrap();
-
-            handle_auth(&mesh, "my_peer_id", "node_b");
-
-            mesh.signal_shutdown();
-            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
-        });
-
-        // Set `node_b` to peered
-        shared
-            .on_peer_connected(&PeerTokenPair::new(
-                PeerAuthorizationToken::from_peer_id("node_b"),
-                PeerAuthorizationToken::from_peer_id("node_a"),
-            ))
-            .expect("Unable to set peer to peered");
-
-        shared
-            .propose_disband(
-                payload,
-                &b"test_signer_a".to_vec(),
-                "node_a",
-                "test".to_string(),
-            )
-            .expect("Proposal not accepted");
-
-        // We're fully peered, but need to wait for protocol to be agreed on
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-
-        // Set `node_b` to agree on the protocol
-        shared
-            .on_protocol_agreement("admin::node_b", ADMIN_SERVICE_PROTOCOL_VERSION)
-            .expect("received unexpected error");
+        let request = setup_update_application_metadata_request(b"new_metadata");
+        let circuit_proposal = shared
+            .make_update_application_metadata_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make update application metadata proposal");
 
-        // Set `node_a` to agree on the protocol
-        shared
-            .on_protocol_agreement("admin::node_a", ADMIN_SERVICE_PROTOCOL_VERSION)
-            .expect("received unexpected error");
+        if let Ok(()) = shared.validate_update_application_metadata(
+            &request,
+            circuit_proposal.get_circuit_proposal(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit is disbanded");
+        }
 
-        // We're fully peered and agreed on protocol, so the pending payload is now available
-        assert_eq!(0, shared.pending_protocol_payloads.len());
-        assert_eq!(1, shared.pending_circuit_payloads.len());
         shutdown(mesh, cm, pm);
     }
 
@@ -6781,6 +11466,78 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a node that was removed from a circuit only deactivates the circuit once every
+    /// other member has reported the removal.
+    ///
+    /// 1. Set up `AdminServiceShared` for `node_c`
+    /// 2. Add an `Active` circuit with the members `node_a`, `node_b` and `node_c` to the admin
+    ///    store
+    /// 3. Add removal notices from `node_a` and from `node_d`, which is not a member
+    /// 4. Validate the circuit is still `Active`
+    /// 5. Add a removal notice from `node_b`
+    /// 6. Validate the circuit now has a `circuit_status` of `Abandoned`
+    #[test]
+    fn test_removed_member_notice() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut admin_shared = AdminServiceShared::new(
+            "node_c".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                three_member_store_circuit(),
+                three_member_store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        admin_shared
+            .add_removed_member_notice("01234-ABCDE", "node_a")
+            .expect("unable to add removal notice from node_a");
+        admin_shared
+            .add_removed_member_notice("01234-ABCDE", "node_d")
+            .expect("unable to add removal notice from node_d");
+
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .unwrap();
+        assert_eq!(&StoreCircuitStatus::Active, circuit.circuit_status());
+
+        admin_shared
+            .add_removed_member_notice("01234-ABCDE", "node_b")
+            .expect("unable to add removal notice from node_b");
+
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .unwrap();
+        assert_eq!(&StoreCircuitStatus::Abandoned, circuit.circuit_status());
+
+        shutdown(mesh, cm, pm);
+    }
+
     /// Tests that a request to remove a circuit proposal is validated correctly
     ///
     /// 1. Set up `AdminServiceShared`
@@ -7260,6 +12017,63 @@ mod tests {
         service
     }
 
+    fn roster_service(service_id: &str, node_id: &str) -> admin::SplinterService {
+        let mut service = admin::SplinterService::new();
+        service.set_service_id(service_id.into());
+        service.set_service_type("type_a".into());
+        service.set_allowed_nodes(RepeatedField::from_vec(vec![node_id.into()]));
+        service
+    }
+
+    fn setup_update_roster_request(
+        add_services: Vec<admin::SplinterService>,
+        remove_services: Vec<admin::SplinterService>,
+    ) -> CircuitUpdateRosterRequest {
+        let mut request = CircuitUpdateRosterRequest::new();
+        request.set_circuit_id("01234-ABCDE".into());
+        request.set_add_services(RepeatedField::from_vec(add_services));
+        request.set_remove_services(RepeatedField::from_vec(remove_services));
+        request
+    }
+
+    fn setup_add_node_request(
+        node_id: &str,
+        services: Vec<admin::SplinterService>,
+    ) -> CircuitUpdateAddNodeRequest {
+        let mut node = admin::SplinterNode::new();
+        node.set_node_id(node_id.into());
+        node.set_endpoints(RepeatedField::from_vec(vec![format!(
+            "test://endpoint_{}:0",
+            node_id
+        )]));
+
+        let mut request = CircuitUpdateAddNodeRequest::new();
+        request.set_circuit_id("01234-ABCDE".into());
+        request.set_node(node);
+        request.set_services(RepeatedField::from_vec(services));
+        request
+    }
+
+    fn setup_remove_node_request(
+        node_id: &str,
+        services: Vec<admin::SplinterService>,
+    ) -> CircuitUpdateRemoveNodeRequest {
+        let mut request = CircuitUpdateRemoveNodeRequest::new();
+        request.set_circuit_id("01234-ABCDE".into());
+        request.set_node_id(node_id.into());
+        request.set_services(RepeatedField::from_vec(services));
+        request
+    }
+
+    fn setup_update_application_metadata_request(
+        application_metadata: &[u8],
+    ) -> CircuitUpdateApplicationMetadataRequest {
+        let mut request = CircuitUpdateApplicationMetadataRequest::new();
+        request.set_circuit_id("01234-ABCDE".into());
+        request.set_application_metedata(application_metadata.to_vec());
+        request
+    }
+
     fn store_circuit(version: i32, status: StoreCircuitStatus) -> StoreCircuit {
         let nodes = store_circuit_nodes();
         store::CircuitBuilder::new()
@@ -7306,6 +12120,55 @@ mod tests {
         ]
     }
 
+    fn three_member_store_circuit() -> StoreCircuit {
+        let nodes = three_member_store_circuit_nodes();
+        store::CircuitBuilder::new()
+            .with_circuit_id("01234-ABCDE")
+            .with_roster(&vec![
+                store::ServiceBuilder::new()
+                    .with_service_id("0123")
+                    .with_service_type("type_a")
+                    .with_node_id("node_a")
+                    .build()
+                    .expect("unable to build admin store Service"),
+                store::ServiceBuilder::new()
+                    .with_service_id("ABCD")
+                    .with_service_type("type_a")
+                    .with_node_id("node_b")
+                    .build()
+                    .expect("unable to build admin store Service"),
+                store::ServiceBuilder::new()
+                    .with_service_id("WXYZ")
+                    .with_service_type("type_a")
+                    .with_node_id("node_c")
+                    .build()
+                    .expect("unable to build admin store Service"),
+            ])
+            .with_members(&nodes)
+            .with_authorization_type(&store::AuthorizationType::Trust)
+            .with_persistence(&store::PersistenceType::Any)
+            .with_durability(&store::DurabilityType::NoDurability)
+            .with_routes(&store::RouteType::Any)
+            .with_circuit_management_type("test_circuit")
+            .with_display_name("test_display")
+            .with_circuit_version(CIRCUIT_PROTOCOL_VERSION)
+            .with_circuit_status(&StoreCircuitStatus::Active)
+            .build()
+            .expect("unable to build store Circuit")
+    }
+
+    fn three_member_store_circuit_nodes() -> Vec<CircuitNode> {
+        let mut nodes = store_circuit_nodes();
+        nodes.push(
+            store::CircuitNodeBuilder::new()
+                .with_node_id("node_c")
+                .with_endpoints(&vec!["test://endpoint_c:0".to_string()])
+                .build()
+                .expect("unable to build store CircuitNode"),
+        );
+        nodes
+    }
+
     struct MockConnectingTransport {
         connection_results: VecDeque<Result<Box<dyn Connection>, ConnectError>>,
     }
@@ -7425,6 +12288,7 @@ mod tests {
         pub sent: Arc<Mutex<Vec<(String, Vec<u8>)>>>,
         pub sent_and_awaited: Arc<Mutex<Vec<(String, Vec<u8>)>>>,
         pub replied: Arc<Mutex<Vec<(ServiceMessageContext, Vec<u8>)>>>,
+        pub relayed: Arc<Mutex<Vec<(String, Vec<String>, Vec<u8>)>>>,
     }
 
     impl MockServiceNetworkSender {
@@ -7433,6 +12297,7 @@ mod tests {
                 sent: Arc::new(Mutex::new(vec![])),
                 sent_and_awaited: Arc::new(Mutex::new(vec![])),
                 replied: Arc::new(Mutex::new(vec![])),
+                relayed: Arc::new(Mutex::new(vec![])),
             }
         }
     }
@@ -7486,6 +12351,20 @@ mod tests {
                 .push((recipient.to_string(), message.to_vec()));
             Ok(())
         }
+
+        fn send_with_relay_route(
+            &self,
+            recipient: &str,
+            message: &[u8],
+            relay_route: &[String],
+        ) -> Result<(), ServiceSendError> {
+            self.relayed.lock().expect("relayed lock poisoned").push((
+                recipient.to_string(),
+                relay_route.to_vec(),
+                message.to_vec(),
+            ));
+            Ok(())
+        }
     }
 
     struct MockAdminKeyVerifier(bool);
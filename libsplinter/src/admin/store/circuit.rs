@@ -39,6 +39,7 @@ pub struct Circuit {
     display_name: Option<String>,
     circuit_version: i32,
     circuit_status: CircuitStatus,
+    application_metadata: Option<Vec<u8>>,
 }
 
 impl Circuit {
@@ -96,6 +97,11 @@ impl Circuit {
     pub fn circuit_status(&self) -> &CircuitStatus {
         &self.circuit_status
     }
+
+    /// Returns the application metadata for the circuit
+    pub fn application_metadata(&self) -> &Option<Vec<u8>> {
+        &self.application_metadata
+    }
 }
 
 impl TryFrom<&admin::Circuit> for Circuit {
@@ -146,11 +152,84 @@ impl TryFrom<&admin::Circuit> for Circuit {
         if !proto.get_display_name().is_empty() {
             builder = builder.with_display_name(proto.get_display_name());
         }
+        if !proto.get_application_metadata().is_empty() {
+            builder = builder.with_application_metadata(proto.get_application_metadata());
+        }
 
         builder.build()
     }
 }
 
+impl From<&Circuit> for admin::Circuit {
+    fn from(circuit: &Circuit) -> Self {
+        let mut proto = admin::Circuit::new();
+
+        proto.set_circuit_id(circuit.id.clone());
+        proto.set_roster(protobuf::RepeatedField::from_vec(
+            circuit
+                .roster
+                .iter()
+                .map(|service| {
+                    let mut proto_service = admin::SplinterService::new();
+                    proto_service.set_service_id(service.service_id().into());
+                    proto_service.set_service_type(service.service_type().into());
+                    proto_service.set_allowed_nodes(protobuf::RepeatedField::from_vec(vec![
+                        service.node_id().into(),
+                    ]));
+                    proto_service.set_arguments(protobuf::RepeatedField::from_vec(
+                        service
+                            .arguments()
+                            .iter()
+                            .map(|(key, value)| {
+                                let mut argument = admin::SplinterService_Argument::new();
+                                argument.set_key(key.clone());
+                                argument.set_value(value.clone());
+                                argument
+                            })
+                            .collect(),
+                    ));
+                    proto_service
+                })
+                .collect(),
+        ));
+        proto.set_members(protobuf::RepeatedField::from_vec(
+            circuit
+                .members
+                .iter()
+                .map(|node| {
+                    let mut proto_node = admin::SplinterNode::new();
+                    proto_node.set_node_id(node.node_id().into());
+                    proto_node.set_endpoints(node.endpoints().to_vec().into());
+                    if let Some(public_key) = node.public_key() {
+                        proto_node.set_public_key(public_key.as_slice().to_vec());
+                    }
+                    if let Some(relay_node_id) = node.relay_node_id() {
+                        proto_node.set_relay_node_id(relay_node_id.clone());
+                    }
+                    proto_node
+                })
+                .collect(),
+        ));
+        proto.set_authorization_type((&circuit.authorization_type).into());
+        proto.set_persistence((&circuit.persistence).into());
+        proto.set_durability((&circuit.durability).into());
+        proto.set_routes((&circuit.routes).into());
+        proto.set_circuit_management_type(circuit.circuit_management_type.clone());
+        proto.set_circuit_version(circuit.circuit_version);
+        proto.set_circuit_status((&circuit.circuit_status).into());
+
+        if let Some(display_name) = &circuit.display_name {
+            proto.set_display_name(display_name.clone());
+        }
+
+        if let Some(application_metadata) = &circuit.application_metadata {
+            proto.set_application_metadata(application_metadata.clone());
+        }
+
+        proto
+    }
+}
+
 /// What type of authorization the circuit requires
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AuthorizationType {
@@ -239,12 +318,14 @@ impl From<&PersistenceType> for admin::Circuit_PersistenceType {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DurabilityType {
     NoDurability,
+    GuaranteedDelivery,
 }
 
 impl From<&messages::DurabilityType> for DurabilityType {
     fn from(message_enum: &messages::DurabilityType) -> Self {
         match *message_enum {
             messages::DurabilityType::NoDurability => DurabilityType::NoDurability,
+            messages::DurabilityType::GuaranteedDelivery => DurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -255,6 +336,9 @@ impl TryFrom<&admin::Circuit_DurabilityType> for DurabilityType {
     fn try_from(proto: &admin::Circuit_DurabilityType) -> Result<Self, Self::Error> {
         match *proto {
             admin::Circuit_DurabilityType::NO_DURABILITY => Ok(DurabilityType::NoDurability),
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                Ok(DurabilityType::GuaranteedDelivery)
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => Err(
                 InvalidStateError::with_message("DurabilityType is unset".to_string()),
             ),
@@ -266,6 +350,9 @@ impl From<&DurabilityType> for admin::Circuit_DurabilityType {
     fn from(durability: &DurabilityType) -> Self {
         match *durability {
             DurabilityType::NoDurability => admin::Circuit_DurabilityType::NO_DURABILITY,
+            DurabilityType::GuaranteedDelivery => {
+                admin::Circuit_DurabilityType::GUARANTEED_DELIVERY
+            }
         }
     }
 }
@@ -274,6 +361,7 @@ impl From<&DurabilityType> for admin::Circuit_DurabilityType {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RouteType {
     Any,
+    Relayed,
 }
 
 impl Default for RouteType {
@@ -286,6 +374,7 @@ impl From<&messages::RouteType> for RouteType {
     fn from(message_enum: &messages::RouteType) -> Self {
         match *message_enum {
             messages::RouteType::Any => RouteType::Any,
+            messages::RouteType::Relayed => RouteType::Relayed,
         }
     }
 }
@@ -296,6 +385,7 @@ impl TryFrom<&admin::Circuit_RouteType> for RouteType {
     fn try_from(proto: &admin::Circuit_RouteType) -> Result<Self, Self::Error> {
         match *proto {
             admin::Circuit_RouteType::ANY_ROUTE => Ok(RouteType::Any),
+            admin::Circuit_RouteType::RELAYED_ROUTE => Ok(RouteType::Relayed),
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => Err(InvalidStateError::with_message(
                 "RouteType is unset".to_string(),
             )),
@@ -307,6 +397,7 @@ impl From<&RouteType> for admin::Circuit_RouteType {
     fn from(route: &RouteType) -> Self {
         match *route {
             RouteType::Any => admin::Circuit_RouteType::ANY_ROUTE,
+            RouteType::Relayed => admin::Circuit_RouteType::RELAYED_ROUTE,
         }
     }
 }
@@ -375,6 +466,7 @@ pub struct CircuitBuilder {
     display_name: Option<String>,
     circuit_version: Option<i32>,
     circuit_status: Option<CircuitStatus>,
+    application_metadata: Option<Vec<u8>>,
 }
 
 impl CircuitBuilder {
@@ -438,6 +530,11 @@ impl CircuitBuilder {
         self.circuit_status.clone()
     }
 
+    /// Returns the application metadata in the builder
+    pub fn application_metadata(&self) -> Option<Vec<u8>> {
+        self.application_metadata.clone()
+    }
+
     /// Sets the circuit ID
     ///
     /// # Arguments
@@ -553,6 +650,16 @@ impl CircuitBuilder {
         self
     }
 
+    /// Sets the application metadata for the circuit
+    ///
+    /// # Arguments
+    ///
+    ///  * `application_metadata` - Opaque bytes that can be used by applications
+    pub fn with_application_metadata(mut self, application_metadata: &[u8]) -> CircuitBuilder {
+        self.application_metadata = Some(application_metadata.into());
+        self
+    }
+
     /// Builds a `Circuit`
     ///
     /// Returns an error if the circuit ID, roster, members or circuit management
@@ -602,6 +709,8 @@ impl CircuitBuilder {
 
         let circuit_status = self.circuit_status.unwrap_or_default();
 
+        let application_metadata = self.application_metadata;
+
         let circuit = Circuit {
             id: circuit_id,
             roster,
@@ -614,6 +723,7 @@ impl CircuitBuilder {
             display_name,
             circuit_version,
             circuit_status,
+            application_metadata,
         };
 
         Ok(circuit)
@@ -634,6 +744,7 @@ impl From<ProposedCircuit> for Circuit {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status().clone(),
+            application_metadata: circuit.application_metadata().clone(),
         }
     }
 }
@@ -646,3 +757,12 @@ impl From<&AuthorizationType> for routing::AuthorizationType {
         }
     }
 }
+
+impl From<&DurabilityType> for routing::DurabilityType {
+    fn from(durability: &DurabilityType) -> Self {
+        match durability {
+            DurabilityType::NoDurability => routing::DurabilityType::NoDurability,
+            DurabilityType::GuaranteedDelivery => routing::DurabilityType::GuaranteedDelivery,
+        }
+    }
+}
@@ -24,6 +24,7 @@ pub struct CircuitNode {
     id: String,
     endpoints: Vec<String>,
     public_key: Option<PublicKey>,
+    relay_node_id: Option<String>,
 }
 
 impl CircuitNode {
@@ -41,6 +42,11 @@ impl CircuitNode {
     pub fn public_key(&self) -> &Option<PublicKey> {
         &self.public_key
     }
+
+    /// Returns the ID of the node that relays messages for this node, if any
+    pub fn relay_node_id(&self) -> &Option<String> {
+        &self.relay_node_id
+    }
 }
 
 impl From<&ProposedNode> for CircuitNode {
@@ -49,6 +55,7 @@ impl From<&ProposedNode> for CircuitNode {
             id: proposed_node.node_id().into(),
             endpoints: proposed_node.endpoints().to_vec(),
             public_key: proposed_node.public_key().clone(),
+            relay_node_id: proposed_node.relay_node_id().clone(),
         }
     }
 }
@@ -59,6 +66,7 @@ impl From<ProposedNode> for CircuitNode {
             id: node.node_id().into(),
             endpoints: node.endpoints().to_vec(),
             public_key: node.public_key().clone(),
+            relay_node_id: node.relay_node_id().clone(),
         }
     }
 }
@@ -69,6 +77,7 @@ pub struct CircuitNodeBuilder {
     node_id: Option<String>,
     endpoints: Option<Vec<String>>,
     public_key: Option<PublicKey>,
+    relay_node_id: Option<String>,
 }
 
 impl CircuitNodeBuilder {
@@ -92,6 +101,11 @@ impl CircuitNodeBuilder {
         self.public_key.clone()
     }
 
+    /// Returns the ID of the node that relays messages for the node
+    pub fn relay_node_id(&self) -> Option<String> {
+        self.relay_node_id.clone()
+    }
+
     /// Sets the node ID
     ///
     /// # Arguments
@@ -122,6 +136,16 @@ impl CircuitNodeBuilder {
         self
     }
 
+    /// Sets the relay node
+    ///
+    /// # Arguments
+    ///
+    ///  * `relay_node_id` - The ID of the circuit member that relays messages for the node
+    pub fn with_relay_node_id(mut self, relay_node_id: &str) -> CircuitNodeBuilder {
+        self.relay_node_id = Some(relay_node_id.into());
+        self
+    }
+
     /// Builds the `CircuitNode`
     ///
     /// Returns an error if the node ID or endpoints are not set
@@ -142,6 +166,7 @@ impl CircuitNodeBuilder {
             id: node_id,
             endpoints,
             public_key: self.public_key,
+            relay_node_id: self.relay_node_id,
         };
 
         Ok(node)
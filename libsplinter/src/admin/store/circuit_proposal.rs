@@ -15,6 +15,7 @@
 //! Structs for building circuit proposals
 
 use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::admin::messages::{self, is_valid_circuit_id};
 use crate::error::InvalidStateError;
@@ -33,6 +34,7 @@ pub struct CircuitProposal {
     votes: Vec<VoteRecord>,
     requester: PublicKey,
     requester_node_id: String,
+    expires_at: Option<SystemTime>,
 }
 
 impl CircuitProposal {
@@ -71,15 +73,26 @@ impl CircuitProposal {
         &self.requester_node_id
     }
 
+    /// Returns the time after which this node requests that the proposal be expired, if the
+    /// proposal expires
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
     pub fn builder(&self) -> CircuitProposalBuilder {
-        CircuitProposalBuilder::new()
+        let builder = CircuitProposalBuilder::new()
             .with_proposal_type(self.proposal_type())
             .with_circuit_id(self.circuit_id())
             .with_circuit_hash(self.circuit_hash())
             .with_circuit(self.circuit())
             .with_votes(self.votes())
             .with_requester(self.requester())
-            .with_requester_node_id(self.requester_node_id())
+            .with_requester_node_id(self.requester_node_id());
+
+        match self.expires_at {
+            Some(expires_at) => builder.with_expires_at(expires_at),
+            None => builder,
+        }
     }
 
     pub fn from_proto(mut proto: admin::CircuitProposal) -> Result<Self, InvalidStateError> {
@@ -89,6 +102,9 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Disband,
+            admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA => {
+                ProposalType::UpdateApplicationMetadata
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `proposal type`".to_string(),
@@ -110,6 +126,7 @@ impl CircuitProposal {
             votes,
             requester: PublicKey::from_bytes(proto.take_requester()),
             requester_node_id: proto.take_requester_node_id(),
+            expires_at: None,
         })
     }
 
@@ -120,6 +137,9 @@ impl CircuitProposal {
             ProposalType::AddNode => admin::CircuitProposal_ProposalType::ADD_NODE,
             ProposalType::RemoveNode => admin::CircuitProposal_ProposalType::REMOVE_NODE,
             ProposalType::Disband => admin::CircuitProposal_ProposalType::DISBAND,
+            ProposalType::UpdateApplicationMetadata => {
+                admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA
+            }
         };
 
         let votes = self
@@ -153,6 +173,7 @@ pub struct CircuitProposalBuilder {
     votes: Option<Vec<VoteRecord>>,
     requester: Option<PublicKey>,
     requester_node_id: Option<String>,
+    expires_at: Option<SystemTime>,
 }
 
 impl CircuitProposalBuilder {
@@ -196,6 +217,11 @@ impl CircuitProposalBuilder {
         self.requester_node_id.clone()
     }
 
+    /// Returns the time after which this node requests that the proposal be expired
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
     /// Set the proposal type of the circuit proposal
     ///
     /// # Arguments
@@ -268,6 +294,18 @@ impl CircuitProposalBuilder {
         self
     }
 
+    /// Sets the time after which this node requests that the proposal be expired. The expiry time
+    /// is local to this node and is not part of the proposal that is shared with the other
+    /// members.
+    ///
+    /// # Arguments
+    ///
+    ///  * `expires_at` - The time the proposal expires
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> CircuitProposalBuilder {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
     /// Builds a `CircuitProposal`
     ///
     /// Returns an error if the circuit ID, circuit, circuit hash, requester, or requester node id
@@ -327,6 +365,7 @@ impl CircuitProposalBuilder {
             votes,
             requester,
             requester_node_id,
+            expires_at: self.expires_at,
         })
     }
 }
@@ -335,7 +374,7 @@ impl TryFrom<&messages::CircuitProposal> for CircuitProposal {
     type Error = InvalidStateError;
 
     fn try_from(admin_proposal: &messages::CircuitProposal) -> Result<Self, Self::Error> {
-        CircuitProposalBuilder::new()
+        let builder = CircuitProposalBuilder::new()
             .with_proposal_type(&ProposalType::from(&admin_proposal.proposal_type))
             .with_circuit_id(&admin_proposal.circuit_id)
             .with_circuit_hash(&admin_proposal.circuit_hash)
@@ -348,8 +387,14 @@ impl TryFrom<&messages::CircuitProposal> for CircuitProposal {
                     .collect::<Vec<VoteRecord>>(),
             )
             .with_requester(&PublicKey::from_bytes(admin_proposal.requester.clone()))
-            .with_requester_node_id(&admin_proposal.requester_node_id)
-            .build()
+            .with_requester_node_id(&admin_proposal.requester_node_id);
+
+        match admin_proposal.expires_at {
+            Some(expires_at) => builder
+                .with_expires_at(UNIX_EPOCH + Duration::from_secs(expires_at))
+                .build(),
+            None => builder.build(),
+        }
     }
 }
 
@@ -499,6 +544,7 @@ pub enum ProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    UpdateApplicationMetadata,
 }
 
 impl From<&messages::ProposalType> for ProposalType {
@@ -509,6 +555,9 @@ impl From<&messages::ProposalType> for ProposalType {
             messages::ProposalType::AddNode => ProposalType::AddNode,
             messages::ProposalType::RemoveNode => ProposalType::RemoveNode,
             messages::ProposalType::Disband => ProposalType::Disband,
+            messages::ProposalType::UpdateApplicationMetadata => {
+                ProposalType::UpdateApplicationMetadata
+            }
         }
     }
 }
@@ -525,6 +574,9 @@ impl TryFrom<&admin::CircuitProposal_ProposalType> for ProposalType {
             admin::CircuitProposal_ProposalType::ADD_NODE => Ok(ProposalType::AddNode),
             admin::CircuitProposal_ProposalType::REMOVE_NODE => Ok(ProposalType::RemoveNode),
             admin::CircuitProposal_ProposalType::DISBAND => Ok(ProposalType::Disband),
+            admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA => {
+                Ok(ProposalType::UpdateApplicationMetadata)
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => Err(
                 InvalidStateError::with_message("ProposalType is unset".to_string()),
             ),
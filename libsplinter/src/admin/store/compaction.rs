@@ -0,0 +1,87 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Periodically removes admin service events that fall outside of the retention policy.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::{AdminServiceStore, EventRetention};
+
+/// Compacts the admin service event log on a fixed interval.
+pub struct EventLogCompactor {
+    shutdown_tx: Option<Sender<()>>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl EventLogCompactor {
+    /// Starts a thread that compacts the event log once immediately, and then again every
+    /// `interval`.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store that holds the admin service events
+    /// * `retention` - The policy used to decide which events are removed
+    /// * `interval` - How long to wait between compactions
+    pub fn start(
+        store: Box<dyn AdminServiceStore>,
+        retention: EventRetention,
+        interval: Duration,
+    ) -> Result<Self, InternalError> {
+        let (shutdown_tx, shutdown_rx) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("EventLogCompactor".into())
+            .spawn(move || loop {
+                match store.compact_events(&retention) {
+                    Ok(0) => (),
+                    Ok(removed) => debug!("Compacted {} admin service event(s)", removed),
+                    Err(err) => error!("Unable to compact admin service events: {}", err),
+                }
+
+                match shutdown_rx.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                        debug!("Exiting admin service event log compactor");
+                        break;
+                    }
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(EventLogCompactor {
+            shutdown_tx: Some(shutdown_tx),
+            join_handle,
+        })
+    }
+}
+
+impl ShutdownHandle for EventLogCompactor {
+    fn signal_shutdown(&mut self) {
+        // Dropping the sender also stops the thread, so a failed send can be ignored
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|_| {
+            InternalError::with_message("Unable to join event log compactor thread".to_string())
+        })
+    }
+}
@@ -30,6 +30,8 @@ use std::sync::{Arc, RwLock};
 use diesel::r2d2::{ConnectionManager, Pool};
 
 use crate::admin::messages;
+#[cfg(feature = "admin-service-event-retention")]
+use crate::admin::store::EventRetention;
 use crate::admin::store::{
     error::AdminServiceStoreError, AdminServiceStore, Circuit, CircuitNode, CircuitPredicate,
     CircuitProposal, Service, ServiceId,
@@ -40,9 +42,13 @@ use crate::store::pool::ConnectionPool;
 use operations::add_circuit::AdminServiceStoreAddCircuitOperation as _;
 use operations::add_event::AdminServiceStoreAddEventOperation as _;
 use operations::add_proposal::AdminServiceStoreAddProposalOperation as _;
+#[cfg(feature = "admin-service-event-retention")]
+use operations::compact_events::AdminServiceStoreCompactEventsOperation as _;
 use operations::count_circuits::AdminServiceStoreCountCircuitsOperation as _;
 use operations::count_proposals::AdminServiceStoreCountProposalsOperation as _;
 use operations::get_circuit::AdminServiceStoreFetchCircuitOperation as _;
+#[cfg(feature = "admin-service-event-retention")]
+use operations::get_compacted_event_id::AdminServiceStoreGetCompactedEventIdOperation as _;
 use operations::get_node::AdminServiceStoreFetchNodeOperation as _;
 use operations::get_proposal::AdminServiceStoreFetchProposalOperation as _;
 use operations::get_service::AdminServiceStoreFetchServiceOperation as _;
@@ -141,8 +147,20 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
         &self,
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
-        self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_proposals(predicates))
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals(predicates, None, None)
+        })
+    }
+
+    fn list_proposals_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals(predicates, after, Some(limit))
+        })
     }
 
     fn count_proposals(
@@ -182,8 +200,20 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
         &self,
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
-        self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(predicates))
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits(predicates, None, None)
+        })
+    }
+
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits(predicates, after, Some(limit))
+        })
     }
 
     fn count_circuits(
@@ -252,6 +282,18 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
         })
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    fn compact_events(&self, retention: &EventRetention) -> Result<usize, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).compact_events(retention))
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    fn get_compacted_event_id(&self) -> Result<Option<i64>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_compacted_event_id())
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -287,8 +329,20 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
         &self,
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
-        self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_proposals(predicates))
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals(predicates, None, None)
+        })
+    }
+
+    fn list_proposals_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals(predicates, after, Some(limit))
+        })
     }
 
     fn count_proposals(
@@ -328,8 +382,20 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
         &self,
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
-        self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(predicates))
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits(predicates, None, None)
+        })
+    }
+
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits(predicates, after, Some(limit))
+        })
     }
 
     fn count_circuits(
@@ -398,6 +464,18 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
         })
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    fn compact_events(&self, retention: &EventRetention) -> Result<usize, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).compact_events(retention))
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    fn get_compacted_event_id(&self) -> Result<Option<i64>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_compacted_event_id())
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -418,6 +496,8 @@ pub mod tests {
     use crate::migrations::run_sqlite_migrations;
     use crate::public_key::PublicKey;
 
+    use std::time::{Duration, UNIX_EPOCH};
+
     use diesel::{
         r2d2::{ConnectionManager, Pool},
         sqlite::SqliteConnection,
@@ -532,6 +612,96 @@ pub mod tests {
         assert_eq!(proposals.len(), 2);
     }
 
+    /// Verify that list_proposals applies the requester and vote predicates
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal with a vote from bubba-node-000 and a proposal with no votes, both
+    ///    requested by acme-node-000
+    /// 4. List proposals with requester predicates, validate the correct proposals are returned
+    /// 5. List proposals with a voted by predicate, validate only the voted on proposal is
+    ///    returned
+    /// 6. List proposals with awaiting vote predicates, validate that only members who have not
+    ///    voted and did not request the proposal are considered to be awaited
+    /// 7. List proposals with service and display name predicates, validate the correct proposals
+    ///    are returned
+    #[test]
+    fn test_list_proposals_with_requester_and_vote_predicates() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        let extra_proposal = create_extra_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+        store
+            .add_proposal(extra_proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let proposals = store
+            .list_proposals(&vec![CircuitPredicate::RequesterNodeIdEq(
+                "acme-node-000".to_string(),
+            )])
+            .expect("Unable to list proposals with requester predicate");
+        assert_eq!(proposals.len(), 2);
+
+        let proposals = store
+            .list_proposals(&vec![CircuitPredicate::RequesterNodeIdEq(
+                "bubba-node-000".to_string(),
+            )])
+            .expect("Unable to list proposals with requester predicate");
+        assert_eq!(proposals.len(), 0);
+
+        let mut proposals = store
+            .list_proposals(&vec![CircuitPredicate::VotedBy(
+                "bubba-node-000".to_string(),
+            )])
+            .expect("Unable to list proposals with voted by predicate");
+        assert_eq!(proposals.next(), Some(proposal.clone()));
+        assert_eq!(proposals.next(), None);
+
+        let mut proposals = store
+            .list_proposals(&vec![CircuitPredicate::AwaitingVoteFrom(
+                "gumbo-node-000".to_string(),
+            )])
+            .expect("Unable to list proposals with awaiting vote predicate");
+        assert_eq!(proposals.next(), Some(extra_proposal.clone()));
+        assert_eq!(proposals.next(), None);
+
+        // bubba-node-000 has already voted and acme-node-000 is the requester
+        for node_id in &["bubba-node-000", "acme-node-000"] {
+            let proposals = store
+                .list_proposals(&vec![CircuitPredicate::AwaitingVoteFrom(
+                    node_id.to_string(),
+                )])
+                .expect("Unable to list proposals with awaiting vote predicate");
+            assert_eq!(proposals.len(), 0);
+        }
+
+        let proposals = store
+            .list_proposals(&vec![
+                CircuitPredicate::ServiceTypeEq("scabbard".to_string()),
+                CircuitPredicate::ServiceArgumentEq(
+                    "peer_services".to_string(),
+                    "[\"a000\"]".to_string(),
+                ),
+            ])
+            .expect("Unable to list proposals with service predicates");
+        assert_eq!(proposals.len(), 2);
+
+        let mut proposals = store
+            .list_proposals(&vec![
+                CircuitPredicate::DisplayNameContains("display".to_string()),
+                CircuitPredicate::CircuitVersionEq(3),
+            ])
+            .expect("Unable to list proposals with display name and version predicates");
+        assert_eq!(proposals.next(), Some(proposal));
+        assert_eq!(proposals.next(), None);
+    }
+
     /// Verify that count_proposals works correctly
     ///
     /// 1. Run sqlite migrations
@@ -698,6 +868,66 @@ pub mod tests {
         assert_eq!(updated_proposal, fetched_proposal);
     }
 
+    /// Verify that a proposal's expiry is stored and is kept when the proposal is updated
+    /// without one
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal with an expiry to the store
+    /// 4. Validate the fetched proposal has the same expiry
+    /// 5. Update the proposal with a vote and no expiry
+    /// 6. Validate the fetched proposal has the vote and still has the original expiry
+    #[test]
+    fn test_proposal_expiry() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let expires_at = UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let proposal = create_proposal()
+            .builder()
+            .with_expires_at(expires_at)
+            .build()
+            .expect("Unable to build proposal");
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(fetched_proposal.expires_at(), Some(expires_at));
+
+        let updated_proposal = create_proposal()
+            .builder()
+            .with_votes(&vec![VoteRecordBuilder::new()
+                .with_public_key(&PublicKey::from_bytes(
+                    parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                        .unwrap(),
+                ))
+                .with_vote(&Vote::Accept)
+                .with_voter_node_id("bubba-node-000")
+                .build()
+                .expect("Unable to build vote record")])
+            .build()
+            .expect("Unable to build updated proposal");
+
+        store
+            .update_proposal(updated_proposal.clone())
+            .expect("Unable to update proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(fetched_proposal.votes(), updated_proposal.votes());
+        assert_eq!(fetched_proposal.expires_at(), Some(expires_at));
+    }
+
     /// Verify that a proposal can be upgraded to a circuit
     ///
     /// 1. Run sqlite migrations
@@ -915,6 +1145,262 @@ pub mod tests {
         assert_eq!(circuits.len(), 2);
     }
 
+    /// Verify that list_circuits applies the service, display name and version predicates
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two circuits, only one of which has a display name and a circuit version of 3
+    /// 4. List circuits with service type and service argument predicates, validate both circuits
+    ///    are returned and that mismatching values return no circuits
+    /// 5. List circuits with a display name substring predicate, validate only the circuit with a
+    ///    matching display name is returned and that `_` is not treated as a wildcard
+    /// 6. List circuits with a circuit version predicate, validate only the matching circuit is
+    ///    returned
+    /// 7. List circuits with a proposal-only predicate, validate it does not filter circuits
+    #[test]
+    fn test_list_circuits_with_service_and_display_predicates() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        let extra_circuit = create_extra_circuit("WBKLF-CCCCC");
+
+        store
+            .add_circuit(circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(extra_circuit.clone(), create_extra_nodes())
+            .expect("Unable to add circuit");
+
+        let circuits = store
+            .list_circuits(&vec![CircuitPredicate::ServiceTypeEq(
+                "scabbard".to_string(),
+            )])
+            .expect("Unable to list circuits with service type predicate");
+        assert_eq!(circuits.len(), 2);
+
+        let circuits = store
+            .list_circuits(&vec![CircuitPredicate::ServiceTypeEq(
+                "sawtooth".to_string(),
+            )])
+            .expect("Unable to list circuits with service type predicate");
+        assert_eq!(circuits.len(), 0);
+
+        let circuits = store
+            .list_circuits(&vec![CircuitPredicate::ServiceArgumentEq(
+                "peer_services".to_string(),
+                "[\"a001\"]".to_string(),
+            )])
+            .expect("Unable to list circuits with service argument predicate");
+        assert_eq!(circuits.len(), 2);
+
+        let circuits = store
+            .list_circuits(&vec![CircuitPredicate::ServiceArgumentEq(
+                "peer_services".to_string(),
+                "[\"a002\"]".to_string(),
+            )])
+            .expect("Unable to list circuits with service argument predicate");
+        assert_eq!(circuits.len(), 0);
+
+        let mut circuits = store
+            .list_circuits(&vec![CircuitPredicate::DisplayNameContains(
+                "_disp".to_string(),
+            )])
+            .expect("Unable to list circuits with display name predicate");
+        assert_eq!(circuits.next(), Some(circuit.clone()));
+        assert_eq!(circuits.next(), None);
+
+        let circuits = store
+            .list_circuits(&vec![CircuitPredicate::DisplayNameContains(
+                "test_d_splay".to_string(),
+            )])
+            .expect("Unable to list circuits with display name predicate");
+        assert_eq!(circuits.len(), 0);
+
+        let mut circuits = store
+            .list_circuits(&vec![CircuitPredicate::CircuitVersionEq(3)])
+            .expect("Unable to list circuits with circuit version predicate");
+        assert_eq!(circuits.next(), Some(circuit.clone()));
+        assert_eq!(circuits.next(), None);
+
+        let circuits = store
+            .list_circuits(&vec![CircuitPredicate::VotedBy(
+                "bubba-node-000".to_string(),
+            )])
+            .expect("Unable to list circuits with proposal-only predicate");
+        assert_eq!(circuits.len(), 2);
+    }
+
+    /// Verifies that the display name predicate ignores case when listing circuits and proposals
+    /// in a SQLite-backed store.
+    #[test]
+    fn test_display_name_predicate_ignores_case() {
+        let pool = create_connection_pool_and_migrate();
+        check_display_name_predicate_ignores_case(&DieselAdminServiceStore::new(pool));
+    }
+
+    /// Verifies that the display name predicate ignores case when listing circuits and proposals
+    /// in a PostgreSQL-backed store.
+    ///
+    /// This test requires a PostgreSQL database, whose URL is given by the
+    /// `SPLINTER_TEST_POSTGRES_URL` environment variable; the admin service tables in the database
+    /// are cleared by the test. Run it with `cargo test -- --ignored`.
+    #[cfg(feature = "postgres")]
+    #[test]
+    #[ignore]
+    fn test_display_name_predicate_ignores_case_postgres() {
+        let pool = create_postgres_connection_pool_and_migrate();
+        check_display_name_predicate_ignores_case(&DieselAdminServiceStore::new(pool));
+    }
+
+    /// Adds a circuit and a proposal, both with the display name "test_display", to the `store`,
+    /// and verifies that they are listed with display name predicates that differ only in case,
+    /// and that a display name that is not contained in theirs matches neither of them.
+    fn check_display_name_predicate_ignores_case(store: &dyn AdminServiceStore) {
+        store
+            .add_circuit(
+                create_circuit("WBKLF-BBBBB", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+        store
+            .add_proposal(create_proposal())
+            .expect("Unable to add proposal");
+
+        for display_name in &["test_display", "TEST_DISP", "Display"] {
+            let predicates = vec![CircuitPredicate::DisplayNameContains(
+                display_name.to_string(),
+            )];
+            assert_eq!(
+                store
+                    .list_circuits(&predicates)
+                    .expect("Unable to list circuits with display name predicate")
+                    .len(),
+                1,
+                "circuit not listed for display name {}",
+                display_name
+            );
+            assert_eq!(
+                store
+                    .list_proposals(&predicates)
+                    .expect("Unable to list proposals with display name predicate")
+                    .len(),
+                1,
+                "proposal not listed for display name {}",
+                display_name
+            );
+        }
+
+        let predicates = vec![CircuitPredicate::DisplayNameContains(
+            "TEST-DISPLAY".to_string(),
+        )];
+        assert_eq!(
+            store
+                .list_circuits(&predicates)
+                .expect("Unable to list circuits with display name predicate")
+                .len(),
+            0
+        );
+        assert_eq!(
+            store
+                .list_proposals(&predicates)
+                .expect("Unable to list proposals with display name predicate")
+                .len(),
+            0
+        );
+    }
+
+    /// Verify that list_circuits_page returns circuits in pages
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add three active circuits and a disbanded circuit
+    /// 4. List the first page of two circuits, validate the two highest circuit IDs are returned
+    ///    in descending order
+    /// 5. List the page after the last circuit on the first page, validate only the remaining
+    ///    active circuit is returned
+    /// 6. List the page after a circuit ID that is not in the store, validate the circuits that
+    ///    sort after it are returned
+    #[test]
+    fn test_list_circuits_page() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit_a = create_circuit("WBKLF-AAAAA", CircuitStatus::Active);
+        let circuit_b = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        let circuit_c = create_circuit("WBKLF-CCCCC", CircuitStatus::Active);
+        let disbanded_circuit = create_circuit("WBKLF-DDDDD", CircuitStatus::Disbanded);
+
+        for circuit in &[&circuit_a, &circuit_b, &circuit_c, &disbanded_circuit] {
+            store
+                .add_circuit((*circuit).clone(), create_nodes())
+                .expect("Unable to add circuit");
+        }
+
+        let circuits = store
+            .list_circuits_page(&[], None, 2)
+            .expect("Unable to list circuits page")
+            .collect::<Vec<_>>();
+        assert_eq!(circuits, vec![circuit_c.clone(), circuit_b.clone()]);
+
+        let circuits = store
+            .list_circuits_page(&[], Some("WBKLF-BBBBB"), 2)
+            .expect("Unable to list circuits page")
+            .collect::<Vec<_>>();
+        assert_eq!(circuits, vec![circuit_a.clone()]);
+
+        let circuits = store
+            .list_circuits_page(&[], Some("WBKLF-BZZZZ"), 2)
+            .expect("Unable to list circuits page")
+            .collect::<Vec<_>>();
+        assert_eq!(circuits, vec![circuit_b, circuit_a]);
+    }
+
+    /// Verify that list_proposals_page returns proposals in pages
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two proposals
+    /// 4. List the first page of one proposal, validate the proposal with the highest circuit ID
+    ///    is returned
+    /// 5. List the page after it, validate the other proposal is returned
+    /// 6. List the page after the last proposal, validate no proposals are returned
+    #[test]
+    fn test_list_proposals_page() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        let extra_proposal = create_extra_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+        store
+            .add_proposal(extra_proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let proposals = store
+            .list_proposals_page(&[], None, 1)
+            .expect("Unable to list proposals page")
+            .collect::<Vec<_>>();
+        assert_eq!(proposals, vec![proposal]);
+
+        let proposals = store
+            .list_proposals_page(&[], Some("WBKLF-BBBBB"), 1)
+            .expect("Unable to list proposals page")
+            .collect::<Vec<_>>();
+        assert_eq!(proposals, vec![extra_proposal]);
+
+        let proposals = store
+            .list_proposals_page(&[], Some("WBKLF-AAAAA"), 1)
+            .expect("Unable to list proposals page");
+        assert_eq!(proposals.len(), 0);
+    }
+
     /// Verify that count_circuits works correctly
     ///
     /// 1. Run sqlite migrations
@@ -1399,6 +1885,133 @@ pub mod tests {
         );
     }
 
+    /// Verify that events beyond the maximum count are compacted
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Add three events to the store
+    /// 4. Compact the events, keeping at most 1 event
+    /// 5. Validate that 2 events were removed, only the most recent event is returned and the
+    ///    compacted event ID is 2
+    /// 6. Compact the events again and validate nothing else is removed
+    #[cfg(feature = "admin-service-event-retention")]
+    #[test]
+    fn test_compact_events_by_count() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        assert_eq!(
+            store
+                .get_compacted_event_id()
+                .expect("Unable to get compacted event ID"),
+            None
+        );
+
+        store
+            .add_event(create_proposal_submitted_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_circuit_ready_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_proposal_vote_messages_event("test"))
+            .expect("Unable to add event");
+
+        let retention = EventRetention::new(None, Some(1));
+        assert_eq!(
+            store
+                .compact_events(&retention)
+                .expect("Unable to compact events"),
+            2
+        );
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events_since(0)
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(events, vec![create_proposal_vote_event(3, "test")]);
+        assert_eq!(
+            store
+                .get_compacted_event_id()
+                .expect("Unable to get compacted event ID"),
+            Some(2)
+        );
+
+        assert_eq!(
+            store
+                .compact_events(&retention)
+                .expect("Unable to compact events"),
+            0
+        );
+        assert_eq!(
+            store
+                .get_compacted_event_id()
+                .expect("Unable to get compacted event ID"),
+            Some(2)
+        );
+    }
+
+    /// Verify that events older than the maximum age are compacted
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Add three events to the store and set the creation time of the first two events to an
+    ///    hour ago
+    /// 4. Compact the events, keeping events at most a minute old
+    /// 5. Validate that 2 events were removed, only the most recent event is returned and the
+    ///    compacted event ID is 2
+    #[cfg(feature = "admin-service-event-retention")]
+    #[test]
+    fn test_compact_events_by_age() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        use super::schema::admin_service_event;
+        use diesel::prelude::*;
+
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool.clone());
+        store
+            .add_event(create_proposal_submitted_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_circuit_ready_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_proposal_vote_messages_event("test"))
+            .expect("Unable to add event");
+
+        let an_hour_ago = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time is before the epoch")
+            .as_secs() as i64
+            - 3600;
+        diesel::update(admin_service_event::table.filter(admin_service_event::id.le(2)))
+            .set(admin_service_event::created_at.eq(an_hour_ago))
+            .execute(&*pool.get().expect("Unable to get connection"))
+            .expect("Unable to update event creation time");
+
+        let retention = EventRetention::new(Some(Duration::from_secs(60)), None);
+        assert_eq!(
+            store
+                .compact_events(&retention)
+                .expect("Unable to compact events"),
+            2
+        );
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events_since(0)
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(events, vec![create_proposal_vote_event(3, "test")]);
+        assert_eq!(
+            store
+                .get_compacted_event_id()
+                .expect("Unable to get compacted event ID"),
+            Some(2)
+        );
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection ensures that the same DB is used for all operations.
@@ -1415,6 +2028,37 @@ pub mod tests {
         pool
     }
 
+    /// Creates a connection pool for the PostgreSQL database at the URL given by the
+    /// `SPLINTER_TEST_POSTGRES_URL` environment variable, runs migrations, and removes any
+    /// circuits and proposals left in the admin service tables.
+    #[cfg(feature = "postgres")]
+    fn create_postgres_connection_pool_and_migrate(
+    ) -> Pool<ConnectionManager<diesel::pg::PgConnection>> {
+        use diesel::connection::SimpleConnection;
+
+        use crate::migrations::run_postgres_migrations;
+
+        let url = std::env::var("SPLINTER_TEST_POSTGRES_URL")
+            .expect("SPLINTER_TEST_POSTGRES_URL must be set to run PostgreSQL tests");
+        let connection_manager = ConnectionManager::<diesel::pg::PgConnection>::new(url);
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        let conn = pool.get().expect("Failed to get connection for migrations");
+        run_postgres_migrations(&*conn).expect("Failed to run migrations");
+
+        conn.batch_execute(
+            "TRUNCATE circuit_proposal, proposed_circuit, vote_record, proposed_node, \
+             proposed_node_endpoint, proposed_service, proposed_service_argument, circuit, \
+             circuit_member, node_endpoint, service, service_argument CASCADE",
+        )
+        .expect("Failed to clear admin service tables");
+
+        pool
+    }
+
     fn create_proposal() -> CircuitProposal {
         CircuitProposalBuilder::default()
             .with_proposal_type(&ProposalType::Create)
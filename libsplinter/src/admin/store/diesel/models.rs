@@ -18,6 +18,7 @@
 
 use std::convert::TryFrom;
 use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use diesel::{
     backend::Backend,
@@ -58,6 +59,7 @@ pub struct CircuitProposalModel {
     pub circuit_hash: String,
     pub requester: Vec<u8>,
     pub requester_node_id: String,
+    pub expires_at: Option<i64>,
 }
 
 impl From<&CircuitProposal> for CircuitProposalModel {
@@ -68,10 +70,25 @@ impl From<&CircuitProposal> for CircuitProposalModel {
             circuit_hash: proposal.circuit_hash().into(),
             requester: proposal.requester().as_slice().to_vec(),
             requester_node_id: proposal.requester_node_id().into(),
+            expires_at: proposal.expires_at().map(to_epoch_secs),
         }
     }
 }
 
+/// Converts a `SystemTime` to the number of seconds since the Unix epoch, as stored in the
+/// database
+pub fn to_epoch_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+        .unwrap_or(0)
+}
+
+/// Converts a number of seconds since the Unix epoch, as stored in the database, to a
+/// `SystemTime`
+pub fn from_epoch_secs(secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(u64::try_from(secs).unwrap_or(0))
+}
+
 /// Database model representation of a `ProposedCircuit`
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable, QueryableByName)]
 #[table_name = "proposed_circuit"]
@@ -169,6 +186,7 @@ pub struct ProposedNodeModel {
     pub node_id: String,
     pub position: i32,
     pub public_key: Option<Vec<u8>>,
+    pub relay_node_id: Option<String>,
 }
 
 impl TryFrom<&ProposedCircuit> for Vec<ProposedNodeModel> {
@@ -192,6 +210,7 @@ impl TryFrom<&ProposedCircuit> for Vec<ProposedNodeModel> {
                         .public_key()
                         .clone()
                         .map(|public_key| public_key.into_bytes()),
+                    relay_node_id: node.relay_node_id().clone(),
                 })
             })
             .collect::<Result<Vec<ProposedNodeModel>, AdminServiceStoreError>>()
@@ -418,6 +437,7 @@ pub struct CircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub application_metadata: Option<Vec<u8>>,
 }
 
 impl From<&Circuit> for CircuitModel {
@@ -432,6 +452,7 @@ impl From<&Circuit> for CircuitModel {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: CircuitStatusModel::from(circuit.circuit_status()),
+            application_metadata: circuit.application_metadata().clone(),
         }
     }
 }
@@ -446,6 +467,7 @@ pub struct CircuitMemberModel {
     pub node_id: String,
     pub position: i32,
     pub public_key: Option<Vec<u8>>,
+    pub relay_node_id: Option<String>,
 }
 
 impl TryFrom<&Circuit> for Vec<CircuitMemberModel> {
@@ -469,6 +491,7 @@ impl TryFrom<&Circuit> for Vec<CircuitMemberModel> {
                         .public_key()
                         .clone()
                         .map(|public_key| public_key.into_bytes()),
+                    relay_node_id: node.relay_node_id().clone(),
                 })
             })
             .collect::<Result<Vec<CircuitMemberModel>, AdminServiceStoreError>>()
@@ -492,6 +515,7 @@ pub struct AdminServiceEventModel {
     pub id: i64,
     pub event_type: String,
     pub data: Option<Vec<u8>>,
+    pub created_at: i64,
 }
 
 #[derive(AsChangeset, Insertable, PartialEq, Debug)]
@@ -499,6 +523,7 @@ pub struct AdminServiceEventModel {
 pub struct NewAdminServiceEventModel<'a> {
     pub event_type: &'a str,
     pub data: Option<&'a [u8]>,
+    pub created_at: i64,
 }
 
 /// Database model representation of a `CircuitProposal` from an `AdminServiceEvent`
@@ -823,31 +848,36 @@ impl AdminEventProposedServiceArgumentModel {
 
 impl<'a> From<&'a messages::AdminServiceEvent> for NewAdminServiceEventModel<'a> {
     fn from(event: &'a messages::AdminServiceEvent) -> Self {
-        match event {
-            messages::AdminServiceEvent::ProposalSubmitted(_) => NewAdminServiceEventModel {
-                event_type: "ProposalSubmitted",
-                data: None,
-            },
-            messages::AdminServiceEvent::ProposalVote((_, data)) => NewAdminServiceEventModel {
-                event_type: "ProposalVote",
-                data: Some(data),
-            },
-            messages::AdminServiceEvent::ProposalAccepted((_, data)) => NewAdminServiceEventModel {
-                event_type: "ProposalAccepted",
-                data: Some(data),
-            },
-            messages::AdminServiceEvent::ProposalRejected((_, data)) => NewAdminServiceEventModel {
-                event_type: "ProposalRejected",
-                data: Some(data),
-            },
-            messages::AdminServiceEvent::CircuitReady(_) => NewAdminServiceEventModel {
-                event_type: "CircuitReady",
-                data: None,
-            },
-            messages::AdminServiceEvent::CircuitDisbanded(_) => NewAdminServiceEventModel {
-                event_type: "CircuitDisbanded",
-                data: None,
-            },
+        let (event_type, data) = match event {
+            messages::AdminServiceEvent::ProposalSubmitted(_) => ("ProposalSubmitted", None),
+            messages::AdminServiceEvent::ProposalVote((_, data)) => {
+                ("ProposalVote", Some(data.as_slice()))
+            }
+            messages::AdminServiceEvent::ProposalAccepted((_, data)) => {
+                ("ProposalAccepted", Some(data.as_slice()))
+            }
+            messages::AdminServiceEvent::ProposalRejected((_, data)) => {
+                ("ProposalRejected", Some(data.as_slice()))
+            }
+            messages::AdminServiceEvent::CircuitReady(_) => ("CircuitReady", None),
+            messages::AdminServiceEvent::CircuitDisbanded(_) => ("CircuitDisbanded", None),
+            messages::AdminServiceEvent::CircuitApplicationMetadataUpdated(_) => {
+                ("CircuitApplicationMetadataUpdated", None)
+            }
+            messages::AdminServiceEvent::ProposalExpired(_) => ("ProposalExpired", None),
+        };
+
+        // The time the event was added, in seconds since the epoch, used to remove events that
+        // are older than the event retention period
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+
+        NewAdminServiceEventModel {
+            event_type,
+            data,
+            created_at,
         }
     }
 }
@@ -895,6 +925,18 @@ impl TryFrom<(AdminServiceEventModel, CircuitProposal)> for AdminServiceEvent {
                 .with_proposal(&proposal)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
+            ("CircuitApplicationMetadataUpdated", None) => AdminServiceEventBuilder::new()
+                .with_event_id(event_model.id)
+                .with_event_type(&EventType::CircuitApplicationMetadataUpdated)
+                .with_proposal(&proposal)
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError),
+            ("ProposalExpired", None) => AdminServiceEventBuilder::new()
+                .with_event_id(event_model.id)
+                .with_event_type(&EventType::ProposalExpired)
+                .with_proposal(&proposal)
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message(
                     "Unable to convert AdminServiceEventModel to AdminServiceEvent".into(),
@@ -947,6 +989,7 @@ impl TryFrom<String> for ProposalType {
             "AddNode" => Ok(ProposalType::AddNode),
             "RemoveNode" => Ok(ProposalType::RemoveNode),
             "Disband" => Ok(ProposalType::Disband),
+            "UpdateApplicationMetadata" => Ok(ProposalType::UpdateApplicationMetadata),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message("Unable to convert string to ProposalType".into()),
             )),
@@ -962,6 +1005,7 @@ impl From<&ProposalType> for String {
             ProposalType::AddNode => String::from("AddNode"),
             ProposalType::RemoveNode => String::from("RemoveNode"),
             ProposalType::Disband => String::from("Disband"),
+            ProposalType::UpdateApplicationMetadata => String::from("UpdateApplicationMetadata"),
         }
     }
 }
@@ -974,6 +1018,9 @@ impl From<&messages::ProposalType> for String {
             messages::ProposalType::AddNode => String::from("AddNode"),
             messages::ProposalType::RemoveNode => String::from("RemoveNode"),
             messages::ProposalType::Disband => String::from("Disband"),
+            messages::ProposalType::UpdateApplicationMetadata => {
+                String::from("UpdateApplicationMetadata")
+            }
         }
     }
 }
@@ -1046,6 +1093,7 @@ impl TryFrom<String> for DurabilityType {
     fn try_from(variant: String) -> Result<Self, Self::Error> {
         match variant.as_ref() {
             "NoDurability" => Ok(DurabilityType::NoDurability),
+            "GuaranteedDelivery" => Ok(DurabilityType::GuaranteedDelivery),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message(
                     "Unable to convert string to DurabilityType".into(),
@@ -1059,6 +1107,7 @@ impl From<&DurabilityType> for String {
     fn from(variant: &DurabilityType) -> Self {
         match variant {
             DurabilityType::NoDurability => String::from("NoDurability"),
+            DurabilityType::GuaranteedDelivery => String::from("GuaranteedDelivery"),
         }
     }
 }
@@ -1067,6 +1116,7 @@ impl From<&messages::DurabilityType> for String {
     fn from(variant: &messages::DurabilityType) -> Self {
         match variant {
             messages::DurabilityType::NoDurability => String::from("NoDurability"),
+            messages::DurabilityType::GuaranteedDelivery => String::from("GuaranteedDelivery"),
         }
     }
 }
@@ -1076,6 +1126,7 @@ impl TryFrom<String> for RouteType {
     fn try_from(variant: String) -> Result<Self, Self::Error> {
         match variant.as_ref() {
             "Any" => Ok(RouteType::Any),
+            "Relayed" => Ok(RouteType::Relayed),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message("Unable to convert string to RouteType".into()),
             )),
@@ -1087,6 +1138,7 @@ impl From<&RouteType> for String {
     fn from(variant: &RouteType) -> Self {
         match variant {
             RouteType::Any => String::from("Any"),
+            RouteType::Relayed => String::from("Relayed"),
         }
     }
 }
@@ -1095,6 +1147,7 @@ impl From<&messages::RouteType> for String {
     fn from(variant: &messages::RouteType) -> Self {
         match variant {
             messages::RouteType::Any => String::from("Any"),
+            messages::RouteType::Relayed => String::from("Relayed"),
         }
     }
 }
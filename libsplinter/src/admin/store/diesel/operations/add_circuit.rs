@@ -88,6 +88,7 @@ impl<'a> AdminServiceStoreAddCircuitOperation
                             .public_key()
                             .clone()
                             .map(|public_key| public_key.into_bytes()),
+                        relay_node_id: node.relay_node_id().clone(),
                     })
                 })
                 .collect::<Result<Vec<CircuitMemberModel>, AdminServiceStoreError>>()?;
@@ -187,6 +188,7 @@ impl<'a> AdminServiceStoreAddCircuitOperation
                             .public_key()
                             .clone()
                             .map(|public_key| public_key.into_bytes()),
+                        relay_node_id: node.relay_node_id().clone(),
                     })
                 })
                 .collect::<Result<Vec<CircuitMemberModel>, AdminServiceStoreError>>()?;
@@ -0,0 +1,212 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "compact events" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use diesel::{
+    dsl::{delete, insert_into, max},
+    prelude::*,
+};
+
+use super::AdminServiceStoreOperations;
+
+use crate::admin::store::{
+    diesel::schema::{
+        admin_event_circuit_proposal, admin_event_proposed_circuit, admin_event_proposed_node,
+        admin_event_proposed_node_endpoint, admin_event_proposed_service,
+        admin_event_proposed_service_argument, admin_event_vote_record, admin_service_event,
+        admin_service_event_compaction,
+    },
+    AdminServiceStoreError, EventRetention,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreCompactEventsOperation {
+    fn compact_events(&self, retention: &EventRetention) -> Result<usize, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreCompactEventsOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn compact_events(&self, retention: &EventRetention) -> Result<usize, AdminServiceStoreError> {
+        self.conn.transaction::<usize, _, _>(|| {
+            // The most recent event that is not one of the `max_count` most recent events
+            let count_cutoff = match retention.max_count() {
+                Some(max_count) => admin_service_event::table
+                    .select(admin_service_event::id)
+                    .order(admin_service_event::id.desc())
+                    .offset(i64::try_from(max_count).unwrap_or(i64::MAX))
+                    .first::<i64>(self.conn)
+                    .optional()?,
+                None => None,
+            };
+            // The most recent event that was added before the `max_age` cutoff
+            let age_cutoff = match retention.max_age() {
+                Some(max_age) => admin_service_event::table
+                    .filter(admin_service_event::created_at.lt(created_before(max_age)))
+                    .select(max(admin_service_event::id))
+                    .first::<Option<i64>>(self.conn)?,
+                None => None,
+            };
+
+            let compacted_event_id = match count_cutoff.max(age_cutoff) {
+                Some(compacted_event_id) => compacted_event_id,
+                None => return Ok(0),
+            };
+
+            delete(
+                admin_event_vote_record::table
+                    .filter(admin_event_vote_record::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_service_argument::table
+                    .filter(admin_event_proposed_service_argument::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_service::table
+                    .filter(admin_event_proposed_service::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_node_endpoint::table
+                    .filter(admin_event_proposed_node_endpoint::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_node::table
+                    .filter(admin_event_proposed_node::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_circuit::table
+                    .filter(admin_event_proposed_circuit::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_circuit_proposal::table
+                    .filter(admin_event_circuit_proposal::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            let removed = delete(
+                admin_service_event::table.filter(admin_service_event::id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+
+            // Only the most recent compacted event ID is kept
+            delete(admin_service_event_compaction::table).execute(self.conn)?;
+            insert_into(admin_service_event_compaction::table)
+                .values(admin_service_event_compaction::compacted_event_id.eq(compacted_event_id))
+                .execute(self.conn)?;
+
+            Ok(removed)
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreCompactEventsOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn compact_events(&self, retention: &EventRetention) -> Result<usize, AdminServiceStoreError> {
+        self.conn.transaction::<usize, _, _>(|| {
+            // The most recent event that is not one of the `max_count` most recent events
+            let count_cutoff = match retention.max_count() {
+                Some(max_count) => admin_service_event::table
+                    .select(admin_service_event::id)
+                    .order(admin_service_event::id.desc())
+                    .offset(i64::try_from(max_count).unwrap_or(i64::MAX))
+                    .first::<i64>(self.conn)
+                    .optional()?,
+                None => None,
+            };
+            // The most recent event that was added before the `max_age` cutoff
+            let age_cutoff = match retention.max_age() {
+                Some(max_age) => admin_service_event::table
+                    .filter(admin_service_event::created_at.lt(created_before(max_age)))
+                    .select(max(admin_service_event::id))
+                    .first::<Option<i64>>(self.conn)?,
+                None => None,
+            };
+
+            let compacted_event_id = match count_cutoff.max(age_cutoff) {
+                Some(compacted_event_id) => compacted_event_id,
+                None => return Ok(0),
+            };
+
+            delete(
+                admin_event_vote_record::table
+                    .filter(admin_event_vote_record::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_service_argument::table
+                    .filter(admin_event_proposed_service_argument::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_service::table
+                    .filter(admin_event_proposed_service::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_node_endpoint::table
+                    .filter(admin_event_proposed_node_endpoint::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_node::table
+                    .filter(admin_event_proposed_node::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_proposed_circuit::table
+                    .filter(admin_event_proposed_circuit::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            delete(
+                admin_event_circuit_proposal::table
+                    .filter(admin_event_circuit_proposal::event_id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+            let removed = delete(
+                admin_service_event::table.filter(admin_service_event::id.le(compacted_event_id)),
+            )
+            .execute(self.conn)?;
+
+            // Only the most recent compacted event ID is kept
+            delete(admin_service_event_compaction::table).execute(self.conn)?;
+            insert_into(admin_service_event_compaction::table)
+                .values(admin_service_event_compaction::compacted_event_id.eq(compacted_event_id))
+                .execute(self.conn)?;
+
+            Ok(removed)
+        })
+    }
+}
+
+/// Returns the time, in seconds since the epoch, that events must have been added before to be
+/// older than `max_age`
+fn created_before(max_age: Duration) -> i64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    i64::try_from(now.saturating_sub(max_age.as_secs())).unwrap_or(i64::MAX)
+}
@@ -31,7 +31,7 @@ use crate::admin::store::{
 };
 use crate::error::InternalError;
 
-use super::AdminServiceStoreOperations;
+use super::{apply_circuit_predicate_filters, AdminServiceStoreOperations};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreCountCircuitsOperation {
     fn count_circuits(
@@ -103,6 +103,8 @@ where
                 );
             }
 
+            query = apply_circuit_predicate_filters(query, predicates);
+
             let count = query.select(count_star()).first::<i64>(self.conn)?;
 
             u32::try_from(count).map_err(|_| {
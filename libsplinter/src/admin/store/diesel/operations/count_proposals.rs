@@ -28,7 +28,7 @@ use crate::admin::store::{
 };
 use crate::error::InternalError;
 
-use super::AdminServiceStoreOperations;
+use super::{apply_proposed_circuit_predicate_filters, AdminServiceStoreOperations};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreCountProposalsOperation {
     fn count_proposals(
@@ -87,6 +87,8 @@ where
                     .filter(proposed_circuit::circuit_management_type.eq_any(management_types));
             }
 
+            query = apply_proposed_circuit_predicate_filters(query, predicates);
+
             let count = query.select(count_star()).first::<i64>(self.conn)?;
 
             u32::try_from(count).map_err(|_| {
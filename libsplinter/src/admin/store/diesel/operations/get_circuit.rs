@@ -43,7 +43,8 @@ where
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<Integer, C::Backend>,
     i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
-    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    CircuitMemberModel:
+        diesel::Queryable<(Text, Text, Integer, Nullable<Binary>, Nullable<Text>), C::Backend>,
 {
     fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
         self.conn.transaction::<Option<Circuit>, _, _>(|| {
@@ -110,6 +111,10 @@ where
                             builder.with_public_key(&PublicKey::from_bytes(public_key.to_vec()));
                     }
 
+                    if let Some(relay_node_id) = &member.relay_node_id {
+                        builder = builder.with_relay_node_id(relay_node_id);
+                    }
+
                     builder.build()
                 })
                 .collect::<Result<Vec<CircuitNode>, InvalidStateError>>()
@@ -132,6 +137,10 @@ where
                 builder = builder.with_display_name(&display_name);
             }
 
+            if let Some(application_metadata) = circuit.application_metadata {
+                builder = builder.with_application_metadata(&application_metadata);
+            }
+
             Ok(Some(
                 builder
                     .build()
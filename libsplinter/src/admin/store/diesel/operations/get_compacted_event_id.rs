@@ -0,0 +1,49 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "get compacted event ID" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::max, prelude::*};
+
+use super::AdminServiceStoreOperations;
+
+use crate::admin::store::{diesel::schema::admin_service_event_compaction, AdminServiceStoreError};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreGetCompactedEventIdOperation {
+    fn get_compacted_event_id(&self) -> Result<Option<i64>, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreGetCompactedEventIdOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn get_compacted_event_id(&self) -> Result<Option<i64>, AdminServiceStoreError> {
+        admin_service_event_compaction::table
+            .select(max(admin_service_event_compaction::compacted_event_id))
+            .first::<Option<i64>>(self.conn)
+            .map_err(AdminServiceStoreError::from)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreGetCompactedEventIdOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn get_compacted_event_id(&self) -> Result<Option<i64>, AdminServiceStoreError> {
+        admin_service_event_compaction::table
+            .select(max(admin_service_event_compaction::compacted_event_id))
+            .first::<Option<i64>>(self.conn)
+            .map_err(AdminServiceStoreError::from)
+    }
+}
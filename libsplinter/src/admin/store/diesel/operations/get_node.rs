@@ -37,7 +37,8 @@ where
     String: diesel::deserialize::FromSql<Text, C::Backend>,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<Integer, C::Backend>,
-    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    CircuitMemberModel:
+        diesel::Queryable<(Text, Text, Integer, Nullable<Binary>, Nullable<Text>), C::Backend>,
 {
     fn get_node(&self, node_id: &str) -> Result<Option<CircuitNode>, AdminServiceStoreError> {
         self.conn.transaction::<Option<CircuitNode>, _, _>(|| {
@@ -16,7 +16,7 @@
 
 use diesel::{
     prelude::*,
-    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
 };
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -25,7 +25,7 @@ use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
+            from_epoch_secs, CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
             ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
@@ -53,7 +53,8 @@ where
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
-    CircuitProposalModel: diesel::Queryable<(Text, Text, Text, Binary, Text), C::Backend>,
+    CircuitProposalModel:
+        diesel::Queryable<(Text, Text, Text, Binary, Text, Nullable<BigInt>), C::Backend>,
     ProposedCircuitModel: diesel::Queryable<
         (
             Text,
@@ -71,7 +72,8 @@ where
         C::Backend,
     >,
     VoteRecordModel: diesel::Queryable<(Text, Binary, Text, Text, Integer), C::Backend>,
-    ProposedNodeModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    ProposedNodeModel:
+        diesel::Queryable<(Text, Text, Integer, Nullable<Binary>, Nullable<Text>), C::Backend>,
 {
     fn get_proposal(
         &self,
@@ -148,6 +150,10 @@ where
                             builder.with_public_key(&PublicKey::from_bytes(public_key.to_vec()))
                     }
 
+                    if let Some(relay_node_id) = &node.relay_node_id {
+                        builder = builder.with_relay_node_id(relay_node_id)
+                    }
+
                     builder
                         .build()
                         .map_err(AdminServiceStoreError::InvalidStateError)
@@ -264,15 +270,21 @@ where
             let native_proposed_circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
+            let mut proposal_builder = CircuitProposalBuilder::new()
+                .with_proposal_type(&ProposalType::try_from(proposal.proposal_type)?)
+                .with_circuit_id(&proposal.circuit_id)
+                .with_circuit_hash(&proposal.circuit_hash)
+                .with_circuit(&native_proposed_circuit)
+                .with_votes(&vote_record)
+                .with_requester(&PublicKey::from_bytes(proposal.requester.to_vec()))
+                .with_requester_node_id(&proposal.requester_node_id);
+
+            if let Some(expires_at) = proposal.expires_at {
+                proposal_builder = proposal_builder.with_expires_at(from_epoch_secs(expires_at));
+            }
+
             Ok(Some(
-                CircuitProposalBuilder::new()
-                    .with_proposal_type(&ProposalType::try_from(proposal.proposal_type)?)
-                    .with_circuit_id(&proposal.circuit_id)
-                    .with_circuit_hash(&proposal.circuit_hash)
-                    .with_circuit(&native_proposed_circuit)
-                    .with_votes(&vote_record)
-                    .with_requester(&PublicKey::from_bytes(proposal.requester.to_vec()))
-                    .with_requester_node_id(&proposal.requester_node_id)
+                proposal_builder
                     .build()
                     .map_err(AdminServiceStoreError::InvalidStateError)?,
             ))
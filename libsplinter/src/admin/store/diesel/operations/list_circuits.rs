@@ -35,12 +35,14 @@ use crate::admin::store::{
 use crate::error::InvalidStateError;
 use crate::public_key::PublicKey;
 
-use super::AdminServiceStoreOperations;
+use super::{apply_circuit_predicate_filters, AdminServiceStoreOperations};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreListCircuitsOperation {
     fn list_circuits(
         &self,
         predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
 }
 
@@ -51,11 +53,14 @@ where
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<Integer, C::Backend>,
     i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
-    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    CircuitMemberModel:
+        diesel::Queryable<(Text, Text, Integer, Nullable<Binary>, Nullable<Text>), C::Backend>,
 {
     fn list_circuits(
         &self,
         predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
         // Collect the management types included in the list of `CircuitPredicates`
         let management_types: Vec<String> = predicates
@@ -112,6 +117,18 @@ where
                     );
                 }
 
+                query = apply_circuit_predicate_filters(query, predicates);
+
+                // Circuits are listed in descending order, so the page following `after` starts
+                // with the next lower circuit ID
+                if let Some(after) = after {
+                    query = query.filter(circuit::circuit_id.lt(after));
+                }
+
+                if let Some(limit) = limit {
+                    query = query.limit(limit as i64);
+                }
+
                 let circuits: Vec<CircuitModel> = query
                     .order(circuit::circuit_id.desc())
                     .load::<CircuitModel>(self.conn)?;
@@ -258,6 +275,10 @@ where
                     if let Some(display_name) = &model.display_name {
                         circuit_builder = circuit_builder.with_display_name(display_name);
                     }
+                    if let Some(application_metadata) = &model.application_metadata {
+                        circuit_builder =
+                            circuit_builder.with_application_metadata(application_metadata);
+                    }
                     if let Some(members) = circuit_members.get_mut(&model.circuit_id) {
                         members.sort_by_key(|node| node.position);
 
@@ -277,6 +298,10 @@ where
                                     ));
                                 }
 
+                                if let Some(relay_node_id) = &member.relay_node_id {
+                                    builder = builder.with_relay_node_id(relay_node_id);
+                                }
+
                                 builder.build()
                             })
                             .collect::<Result<Vec<CircuitNode>, InvalidStateError>>()
@@ -47,7 +47,8 @@ where
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<Integer, C::Backend>,
     NodeEndpointModel: diesel::Queryable<(Text, Text), C::Backend>,
-    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    CircuitMemberModel:
+        diesel::Queryable<(Text, Text, Integer, Nullable<Binary>, Nullable<Text>), C::Backend>,
 {
     fn list_nodes(
         &self,
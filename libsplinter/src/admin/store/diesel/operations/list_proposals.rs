@@ -20,13 +20,13 @@ use std::convert::TryFrom;
 use diesel::{
     dsl::exists,
     prelude::*,
-    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
 };
 
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
+            from_epoch_secs, CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
             ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
@@ -42,12 +42,14 @@ use crate::admin::store::{
 use crate::error::InvalidStateError;
 use crate::public_key::PublicKey;
 
-use super::AdminServiceStoreOperations;
+use super::{apply_proposed_circuit_predicate_filters, AdminServiceStoreOperations};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreListProposalsOperation {
     fn list_proposals(
         &self,
         predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
 }
 
@@ -57,7 +59,8 @@ where
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
-    CircuitProposalModel: diesel::Queryable<(Text, Text, Text, Binary, Text), C::Backend>,
+    CircuitProposalModel:
+        diesel::Queryable<(Text, Text, Text, Binary, Text, Nullable<BigInt>), C::Backend>,
     ProposedCircuitModel: diesel::Queryable<
         (
             Text,
@@ -75,11 +78,14 @@ where
         C::Backend,
     >,
     VoteRecordModel: diesel::Queryable<(Text, Binary, Text, Text, Integer), C::Backend>,
-    ProposedNodeModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+    ProposedNodeModel:
+        diesel::Queryable<(Text, Text, Integer, Nullable<Binary>, Nullable<Text>), C::Backend>,
 {
     fn list_proposals(
         &self,
         predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
         // Collect the management types included in the list of `CircuitPredicates`
         let management_types: Vec<String> = predicates
@@ -123,6 +129,18 @@ where
                         .filter(proposed_circuit::circuit_management_type.eq_any(management_types));
                 }
 
+                query = apply_proposed_circuit_predicate_filters(query, predicates);
+
+                // Proposals are listed in descending order, so the page following `after` starts
+                // with the next lower circuit ID
+                if let Some(after) = after {
+                    query = query.filter(proposed_circuit::circuit_id.lt(after));
+                }
+
+                if let Some(limit) = limit {
+                    query = query.limit(limit as i64);
+                }
+
                 // Collects proposed circuits which match the circuit predicates
                 let proposed_circuits: Vec<ProposedCircuitModel> = query
                     .order(proposed_circuit::circuit_id.desc())
@@ -161,7 +179,7 @@ where
                                 )
                             })?;
 
-                        let proposal_builder = CircuitProposalBuilder::new()
+                        let mut proposal_builder = CircuitProposalBuilder::new()
                             .with_proposal_type(&ProposalType::try_from(
                                 proposal.proposal_type.to_string(),
                             )?)
@@ -169,6 +187,10 @@ where
                             .with_circuit_hash(&proposal.circuit_hash)
                             .with_requester(&PublicKey::from_bytes(proposal.requester.to_vec()))
                             .with_requester_node_id(&proposal.requester_node_id);
+                        if let Some(expires_at) = proposal.expires_at {
+                            proposal_builder =
+                                proposal_builder.with_expires_at(from_epoch_secs(expires_at));
+                        }
                         let mut proposed_circuit_builder = ProposedCircuitBuilder::new()
                             .with_circuit_id(&proposed_circuit.circuit_id)
                             .with_authorization_type(&AuthorizationType::try_from(
@@ -316,6 +338,10 @@ where
                                 .with_public_key(&PublicKey::from_bytes(public_key.to_vec()))
                         }
 
+                        if let Some(relay_node_id) = &node.relay_node_id {
+                            proposed_node = proposed_node.with_relay_node_id(relay_node_id)
+                        }
+
                         proposed_nodes.insert(
                             (node.circuit_id, node.node_id),
                             IndexedNodeBuilder {
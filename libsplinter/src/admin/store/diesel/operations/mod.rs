@@ -17,9 +17,13 @@
 pub(super) mod add_circuit;
 pub(super) mod add_event;
 pub(super) mod add_proposal;
+#[cfg(feature = "admin-service-event-retention")]
+pub(super) mod compact_events;
 pub(super) mod count_circuits;
 pub(super) mod count_proposals;
 pub(super) mod get_circuit;
+#[cfg(feature = "admin-service-event-retention")]
+pub(super) mod get_compacted_event_id;
 pub(super) mod get_node;
 pub(super) mod get_proposal;
 pub(super) mod get_service;
@@ -36,6 +40,22 @@ pub(super) mod update_circuit;
 pub(super) mod update_proposal;
 pub(super) mod upgrade;
 
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+};
+
+use crate::admin::store::diesel::schema::{
+    circuit, circuit_proposal, proposed_circuit, proposed_node, proposed_service,
+    proposed_service_argument, service, service_argument, vote_record,
+};
+use crate::admin::store::CircuitPredicate;
+
+// SQLite's `LIKE` ignores the case of ASCII characters, but PostgreSQL's does not, so both sides
+// are lowercased to match the same display names on either backend
+sql_function!(fn lower(value: Nullable<Text>) -> Nullable<Text>);
+
 pub struct AdminServiceStoreOperations<'a, C> {
     conn: &'a C,
 }
@@ -45,3 +65,198 @@ impl<'a, C: diesel::Connection> AdminServiceStoreOperations<'a, C> {
         AdminServiceStoreOperations { conn }
     }
 }
+
+type BoxedCircuitQuery<'a, DB> = diesel::query_builder::BoxedSelectStatement<
+    'a,
+    (
+        Text,
+        Text,
+        Text,
+        Text,
+        Text,
+        Text,
+        Nullable<Text>,
+        Integer,
+        SmallInt,
+        Nullable<Binary>,
+    ),
+    circuit::table,
+    DB,
+>;
+
+type BoxedProposedCircuitQuery<'a, DB> = diesel::query_builder::BoxedSelectStatement<
+    'a,
+    (
+        Text,
+        Text,
+        Text,
+        Text,
+        Text,
+        Text,
+        Nullable<Binary>,
+        Nullable<Text>,
+        Nullable<Text>,
+        Integer,
+        SmallInt,
+    ),
+    proposed_circuit::table,
+    DB,
+>;
+
+/// Takes a query of type `circuit::table.into_boxed().select(circuit::all_columns)` and updates
+/// the query for the provided service, display name and version predicates.
+///
+/// The management type, member and status predicates are handled by the callers; the
+/// proposal-only predicates are ignored.
+fn apply_circuit_predicate_filters<'a, DB: 'a>(
+    query: BoxedCircuitQuery<'a, DB>,
+    predicates: &'a [CircuitPredicate],
+) -> BoxedCircuitQuery<'a, DB>
+where
+    DB: diesel::backend::Backend,
+{
+    let mut query = query;
+    for predicate in predicates {
+        match predicate {
+            CircuitPredicate::ServiceTypeEq(service_type) => {
+                query = query.filter(exists(
+                    service::table.filter(
+                        service::circuit_id
+                            .eq(circuit::circuit_id)
+                            .and(service::service_type.eq(service_type)),
+                    ),
+                ));
+            }
+            CircuitPredicate::ServiceArgumentEq(key, value) => {
+                query = query.filter(exists(
+                    service_argument::table.filter(
+                        service_argument::circuit_id
+                            .eq(circuit::circuit_id)
+                            .and(service_argument::key.eq(key))
+                            .and(service_argument::value.eq(value)),
+                    ),
+                ));
+            }
+            CircuitPredicate::DisplayNameContains(display_name) => {
+                query = query.filter(
+                    lower(circuit::display_name)
+                        .like(lower(contains_pattern(display_name)))
+                        .escape('\\'),
+                );
+            }
+            CircuitPredicate::CircuitVersionEq(version) => {
+                query = query.filter(circuit::circuit_version.eq(*version));
+            }
+            _ => (),
+        }
+    }
+
+    query
+}
+
+/// Takes a query of type
+/// `proposed_circuit::table.into_boxed().select(proposed_circuit::all_columns)` and updates the
+/// query for the provided service, display name, version, requester and vote predicates.
+///
+/// The management type and member predicates are handled by the callers; the status predicate is
+/// ignored.
+fn apply_proposed_circuit_predicate_filters<'a, DB: 'a>(
+    query: BoxedProposedCircuitQuery<'a, DB>,
+    predicates: &'a [CircuitPredicate],
+) -> BoxedProposedCircuitQuery<'a, DB>
+where
+    DB: diesel::backend::Backend,
+{
+    let mut query = query;
+    for predicate in predicates {
+        match predicate {
+            CircuitPredicate::ServiceTypeEq(service_type) => {
+                query = query.filter(exists(
+                    proposed_service::table.filter(
+                        proposed_service::circuit_id
+                            .eq(proposed_circuit::circuit_id)
+                            .and(proposed_service::service_type.eq(service_type)),
+                    ),
+                ));
+            }
+            CircuitPredicate::ServiceArgumentEq(key, value) => {
+                query = query.filter(exists(
+                    proposed_service_argument::table.filter(
+                        proposed_service_argument::circuit_id
+                            .eq(proposed_circuit::circuit_id)
+                            .and(proposed_service_argument::key.eq(key))
+                            .and(proposed_service_argument::value.eq(value)),
+                    ),
+                ));
+            }
+            CircuitPredicate::DisplayNameContains(display_name) => {
+                query = query.filter(
+                    lower(proposed_circuit::display_name)
+                        .like(lower(contains_pattern(display_name)))
+                        .escape('\\'),
+                );
+            }
+            CircuitPredicate::CircuitVersionEq(version) => {
+                query = query.filter(proposed_circuit::circuit_version.eq(*version));
+            }
+            CircuitPredicate::RequesterNodeIdEq(node_id) => {
+                query = query.filter(exists(
+                    circuit_proposal::table.filter(
+                        circuit_proposal::circuit_id
+                            .eq(proposed_circuit::circuit_id)
+                            .and(circuit_proposal::requester_node_id.eq(node_id)),
+                    ),
+                ));
+            }
+            CircuitPredicate::VotedBy(node_id) => {
+                query = query.filter(exists(
+                    vote_record::table.filter(
+                        vote_record::circuit_id
+                            .eq(proposed_circuit::circuit_id)
+                            .and(vote_record::voter_node_id.eq(node_id)),
+                    ),
+                ));
+            }
+            CircuitPredicate::AwaitingVoteFrom(node_id) => {
+                // only return proposals where the node is a member, did not request the proposal
+                // and has not voted yet
+                query = query.filter(
+                    exists(
+                        proposed_node::table.filter(
+                            proposed_node::circuit_id
+                                .eq(proposed_circuit::circuit_id)
+                                .and(proposed_node::node_id.eq(node_id)),
+                        ),
+                    )
+                    .and(exists(
+                        circuit_proposal::table.filter(
+                            circuit_proposal::circuit_id
+                                .eq(proposed_circuit::circuit_id)
+                                .and(circuit_proposal::requester_node_id.ne(node_id)),
+                        ),
+                    ))
+                    .and(not(exists(
+                        vote_record::table.filter(
+                            vote_record::circuit_id
+                                .eq(proposed_circuit::circuit_id)
+                                .and(vote_record::voter_node_id.eq(node_id)),
+                        ),
+                    ))),
+                );
+            }
+            _ => (),
+        }
+    }
+
+    query
+}
+
+/// Creates a `LIKE` pattern, using `\` as the escape character, that matches any value containing
+/// the given string.
+fn contains_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 //! Provides the "update circuit" operation for the `DieselAdminServiceStore`.
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
 use diesel::{
@@ -23,8 +24,10 @@ use diesel::{
 use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
-        models::{CircuitMemberModel, CircuitModel, ServiceArgumentModel, ServiceModel},
-        schema::{circuit, circuit_member, service, service_argument},
+        models::{
+            CircuitMemberModel, CircuitModel, NodeEndpointModel, ServiceArgumentModel, ServiceModel,
+        },
+        schema::{circuit, circuit_member, node_endpoint, service, service_argument},
     },
     error::AdminServiceStoreError,
     Circuit,
@@ -61,7 +64,10 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
+                    circuit::display_name.eq(circuit_model.display_name),
+                    circuit::circuit_version.eq(circuit_model.circuit_version),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::application_metadata.eq(circuit_model.application_metadata),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -89,6 +95,37 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
+            // Members added to the circuit may not have associated `node_endpoint` entries yet,
+            // so insert the endpoints of any member that does not
+            for (node_id, endpoints) in circuit
+                .members()
+                .iter()
+                .map(|node| {
+                    (
+                        node.node_id().into(),
+                        node.endpoints()
+                            .iter()
+                            .map(|endpoint| NodeEndpointModel {
+                                node_id: node.node_id().into(),
+                                endpoint: endpoint.into(),
+                            })
+                            .collect::<Vec<NodeEndpointModel>>(),
+                    )
+                })
+                .collect::<HashMap<String, Vec<NodeEndpointModel>>>()
+                .into_iter()
+            {
+                if let Some(0) = node_endpoint::table
+                    .filter(node_endpoint::node_id.eq(&node_id))
+                    .count()
+                    .first(self.conn)
+                    .optional()?
+                {
+                    insert_into(node_endpoint::table)
+                        .values(endpoints)
+                        .execute(self.conn)?;
+                }
+            }
             Ok(())
         })
     }
@@ -120,7 +157,10 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
+                    circuit::display_name.eq(circuit_model.display_name),
+                    circuit::circuit_version.eq(circuit_model.circuit_version),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::application_metadata.eq(circuit_model.application_metadata),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -148,6 +188,37 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
+            // Members added to the circuit may not have associated `node_endpoint` entries yet,
+            // so insert the endpoints of any member that does not
+            for (node_id, endpoints) in circuit
+                .members()
+                .iter()
+                .map(|node| {
+                    (
+                        node.node_id().into(),
+                        node.endpoints()
+                            .iter()
+                            .map(|endpoint| NodeEndpointModel {
+                                node_id: node.node_id().into(),
+                                endpoint: endpoint.into(),
+                            })
+                            .collect::<Vec<NodeEndpointModel>>(),
+                    )
+                })
+                .collect::<HashMap<String, Vec<NodeEndpointModel>>>()
+                .into_iter()
+            {
+                if let Some(0) = node_endpoint::table
+                    .filter(node_endpoint::node_id.eq(&node_id))
+                    .count()
+                    .first(self.conn)
+                    .optional()?
+                {
+                    insert_into(node_endpoint::table)
+                        .values(endpoints)
+                        .execute(self.conn)?;
+                }
+            }
             Ok(())
         })
     }
@@ -69,6 +69,13 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     circuit_proposal::requester_node_id.eq(proposal_model.requester_node_id),
                 ))
                 .execute(self.conn)?;
+            // The expiry is only set when a node adds the proposal, so an update without an
+            // expiry keeps the existing one
+            if let Some(expires_at) = proposal_model.expires_at {
+                update(circuit_proposal::table.find(proposal.circuit_id()))
+                    .set(circuit_proposal::expires_at.eq(expires_at))
+                    .execute(self.conn)?;
+            }
             // Update existing `ProposedCircuit`
             let proposed_circuit_model = ProposedCircuitModel::from(proposal.circuit());
             update(proposed_circuit::table.find(proposal.circuit_id()))
@@ -170,6 +177,13 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     circuit_proposal::requester_node_id.eq(proposal_model.requester_node_id),
                 ))
                 .execute(self.conn)?;
+            // The expiry is only set when a node adds the proposal, so an update without an
+            // expiry keeps the existing one
+            if let Some(expires_at) = proposal_model.expires_at {
+                update(circuit_proposal::table.find(proposal.circuit_id()))
+                    .set(circuit_proposal::expires_at.eq(expires_at))
+                    .execute(self.conn)?;
+            }
             // Update existing `ProposedCircuit`
             let proposed_circuit_model = ProposedCircuitModel::from(proposal.circuit());
             update(proposed_circuit::table.find(proposal.circuit_id()))
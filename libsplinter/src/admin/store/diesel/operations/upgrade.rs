@@ -74,6 +74,10 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 builder = builder.with_display_name(display_name);
             }
 
+            if let Some(application_metadata) = proposed_circuit.application_metadata() {
+                builder = builder.with_application_metadata(application_metadata);
+            }
+
             let circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
@@ -136,6 +140,10 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 builder = builder.with_display_name(display_name);
             }
 
+            if let Some(application_metadata) = proposed_circuit.application_metadata() {
+                builder = builder.with_application_metadata(application_metadata);
+            }
+
             let circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
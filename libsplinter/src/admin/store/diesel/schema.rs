@@ -19,6 +19,7 @@ table! {
         circuit_hash -> Text,
         requester -> Binary,
         requester_node_id -> Text,
+        expires_at -> Nullable<Int8>,
     }
 }
 
@@ -54,6 +55,7 @@ table! {
         node_id -> Text,
         position -> Integer,
         public_key -> Nullable<Binary>,
+        relay_node_id -> Nullable<Text>,
     }
 }
 
@@ -117,6 +119,7 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        application_metadata -> Nullable<Binary>,
     }
 }
 
@@ -126,6 +129,7 @@ table! {
         node_id -> Text,
         position -> Integer,
         public_key -> Nullable<Binary>,
+        relay_node_id -> Nullable<Text>,
     }
 }
 
@@ -159,6 +163,13 @@ table! {
         id -> Int8,
         event_type -> Text,
         data -> Nullable<Binary>,
+        created_at -> Int8,
+    }
+}
+
+table! {
+    admin_service_event_compaction (compacted_event_id) {
+        compacted_event_id -> Int8,
     }
 }
 
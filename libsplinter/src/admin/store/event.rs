@@ -15,6 +15,8 @@
 //! Structs for events associated with the admin store
 
 use std::convert::TryFrom;
+#[cfg(feature = "admin-service-event-retention")]
+use std::time::Duration;
 
 use super::CircuitProposal;
 use crate::admin::service::messages;
@@ -40,6 +42,8 @@ pub enum EventType {
     ProposalRejected { requester: PublicKey },
     CircuitReady,
     CircuitDisbanded,
+    CircuitApplicationMetadataUpdated,
+    ProposalExpired,
 }
 
 impl AdminServiceEvent {
@@ -132,6 +136,46 @@ impl AdminServiceEventBuilder {
     }
 }
 
+/// The policy used to decide which `AdminServiceEvent`s are removed when the event log is
+/// compacted
+///
+/// Events are removed if they are older than the maximum age or if they are not one of the most
+/// recent events, up to the maximum count. If neither limit is set, no events are removed.
+#[cfg(feature = "admin-service-event-retention")]
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct EventRetention {
+    max_age: Option<Duration>,
+    max_count: Option<u64>,
+}
+
+#[cfg(feature = "admin-service-event-retention")]
+impl EventRetention {
+    /// Creates a new `EventRetention`
+    ///
+    /// # Arguments
+    ///
+    /// * `max_age` - How long events are kept after they are added
+    /// * `max_count` - The number of most recent events that are kept
+    pub fn new(max_age: Option<Duration>, max_count: Option<u64>) -> Self {
+        EventRetention { max_age, max_count }
+    }
+
+    /// Returns how long events are kept after they are added, if limited
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Returns the number of most recent events that are kept, if limited
+    pub fn max_count(&self) -> Option<u64> {
+        self.max_count
+    }
+
+    /// Returns true if this policy removes events
+    pub fn is_limited(&self) -> bool {
+        self.max_age.is_some() || self.max_count.is_some()
+    }
+}
+
 impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
     type Error = InvalidStateError;
 
@@ -176,6 +220,18 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                 event_type: EventType::CircuitDisbanded,
                 proposal,
             }),
+            messages::AdminServiceEvent::CircuitApplicationMetadataUpdated(_) => {
+                Ok(AdminServiceEvent {
+                    event_id,
+                    event_type: EventType::CircuitApplicationMetadataUpdated,
+                    proposal,
+                })
+            }
+            messages::AdminServiceEvent::ProposalExpired(_) => Ok(AdminServiceEvent {
+                event_id,
+                event_type: EventType::ProposalExpired,
+                proposal,
+            }),
         }
     }
 }
@@ -30,6 +30,8 @@
 mod circuit;
 mod circuit_node;
 mod circuit_proposal;
+#[cfg(feature = "admin-service-event-retention")]
+mod compaction;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 pub mod diesel;
 pub mod error;
@@ -53,7 +55,11 @@ pub use self::circuit_node::{CircuitNode, CircuitNodeBuilder};
 pub use self::circuit_proposal::{
     CircuitProposal, CircuitProposalBuilder, ProposalType, Vote, VoteRecord, VoteRecordBuilder,
 };
+#[cfg(feature = "admin-service-event-retention")]
+pub use self::compaction::EventLogCompactor;
 use self::error::AdminServiceStoreError;
+#[cfg(feature = "admin-service-event-retention")]
+pub use self::event::EventRetention;
 pub use self::event::{AdminServiceEvent, AdminServiceEventBuilder, EventType};
 pub use self::proposed_circuit::{ProposedCircuit, ProposedCircuitBuilder};
 pub use self::proposed_node::{ProposedNode, ProposedNodeBuilder};
@@ -126,10 +132,27 @@ impl PartialOrd for ServiceId {
 }
 
 /// Predicate for filtering the lists of circuits and circuit proposals
+///
+/// The `RequesterNodeIdEq`, `VotedBy` and `AwaitingVoteFrom` predicates only apply to circuit
+/// proposals; every circuit matches them.
 pub enum CircuitPredicate {
     ManagementTypeEq(String),
     MembersInclude(Vec<String>),
     CircuitStatus(CircuitStatus),
+    /// The circuit has a service of the given type
+    ServiceTypeEq(String),
+    /// The circuit has a service with the given argument key and value
+    ServiceArgumentEq(String, String),
+    /// The circuit's display name contains the given string, ignoring case
+    DisplayNameContains(String),
+    CircuitVersionEq(i32),
+    /// The proposal was requested by the given node
+    RequesterNodeIdEq(String),
+    /// The given node has voted on the proposal
+    VotedBy(String),
+    /// The given node is a member of the proposed circuit, did not request the proposal and has
+    /// not voted on it yet
+    AwaitingVoteFrom(String),
 }
 
 impl CircuitPredicate {
@@ -152,6 +175,27 @@ impl CircuitPredicate {
                 true
             }
             CircuitPredicate::CircuitStatus(status) => circuit.circuit_status() == status,
+            CircuitPredicate::ServiceTypeEq(service_type) => circuit
+                .roster()
+                .iter()
+                .any(|service| service.service_type() == service_type),
+            CircuitPredicate::ServiceArgumentEq(key, value) => {
+                circuit.roster().iter().any(|service| {
+                    service
+                        .arguments()
+                        .iter()
+                        .any(|(arg_key, arg_value)| arg_key == key && arg_value == value)
+                })
+            }
+            CircuitPredicate::DisplayNameContains(display_name) => circuit
+                .display_name()
+                .as_ref()
+                .map(|name| name.to_lowercase().contains(&display_name.to_lowercase()))
+                .unwrap_or(false),
+            CircuitPredicate::CircuitVersionEq(version) => circuit.circuit_version() == *version,
+            CircuitPredicate::RequesterNodeIdEq(_)
+            | CircuitPredicate::VotedBy(_)
+            | CircuitPredicate::AwaitingVoteFrom(_) => true,
         }
     }
 
@@ -177,6 +221,45 @@ impl CircuitPredicate {
             CircuitPredicate::CircuitStatus(status) => {
                 proposal.circuit().circuit_status() == status
             }
+            CircuitPredicate::ServiceTypeEq(service_type) => proposal
+                .circuit()
+                .roster()
+                .iter()
+                .any(|service| service.service_type() == service_type),
+            CircuitPredicate::ServiceArgumentEq(key, value) => {
+                proposal.circuit().roster().iter().any(|service| {
+                    service
+                        .arguments()
+                        .iter()
+                        .any(|(arg_key, arg_value)| arg_key == key && arg_value == value)
+                })
+            }
+            CircuitPredicate::DisplayNameContains(display_name) => proposal
+                .circuit()
+                .display_name()
+                .as_ref()
+                .map(|name| name.to_lowercase().contains(&display_name.to_lowercase()))
+                .unwrap_or(false),
+            CircuitPredicate::CircuitVersionEq(version) => {
+                proposal.circuit().circuit_version() == *version
+            }
+            CircuitPredicate::RequesterNodeIdEq(node_id) => proposal.requester_node_id() == node_id,
+            CircuitPredicate::VotedBy(node_id) => proposal
+                .votes()
+                .iter()
+                .any(|vote| vote.voter_node_id() == node_id),
+            CircuitPredicate::AwaitingVoteFrom(node_id) => {
+                proposal.requester_node_id() != node_id
+                    && proposal
+                        .circuit()
+                        .members()
+                        .iter()
+                        .any(|node| node.node_id() == node_id)
+                    && !proposal
+                        .votes()
+                        .iter()
+                        .any(|vote| vote.voter_node_id() == node_id)
+            }
         }
     }
 }
@@ -201,6 +284,8 @@ pub trait AdminServiceStore: Send + Sync {
     ///
     ///  * `proposal` - The proposal with the updated information
     ///
+    ///  If the updated proposal does not have an expiry, the existing expiry is kept.
+    ///
     ///  Returns an error if a `CircuitProposal` with the same ID does not exist
     fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError>;
 
@@ -226,12 +311,29 @@ pub trait AdminServiceStore: Send + Sync {
     /// List circuit proposals from the store
     ///
     /// The proposals returned can be filtered by provided `CircuitPredicate`. This enables
-    /// filtering by management type and members.
+    /// filtering by management type, members, services, display name, version, requester and votes.
     fn list_proposals(
         &self,
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
 
+    /// List a page of circuit proposals from the store
+    ///
+    /// Proposals are returned in descending order of circuit ID, starting with the first proposal
+    /// that follows the circuit ID `after`, if provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicates` - A list of predicates to filter the proposals by
+    /// * `after` - The circuit ID of the last proposal on the previous page
+    /// * `limit` - The maximum number of proposals to return
+    fn list_proposals_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
+
     /// Returns the count of proposals in the store
     ///
     /// # Arguments
@@ -290,6 +392,23 @@ pub trait AdminServiceStore: Send + Sync {
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
 
+    /// List a page of circuits from the store
+    ///
+    /// Circuits are returned in descending order of circuit ID, starting with the first circuit
+    /// that follows the circuit ID `after`, if provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicates` - A list of predicates to filter the circuits by
+    /// * `after` - The circuit ID of the last circuit on the previous page
+    /// * `limit` - The maximum number of circuits to return
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
+
     /// Returns the count of circuits in the store
     ///
     /// # Arguments
@@ -373,6 +492,23 @@ pub trait AdminServiceStore: Send + Sync {
         start: i64,
     ) -> Result<EventIter, AdminServiceStoreError>;
 
+    /// Remove the `AdminServiceEvent`s that fall outside of the given retention policy. Returns
+    /// the number of events removed.
+    ///
+    /// Events are always removed from the oldest event forward, so after compaction every event
+    /// with an ID up to and including the compacted event ID has been removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `retention` - the policy used to decide which events are removed
+    #[cfg(feature = "admin-service-event-retention")]
+    fn compact_events(&self, retention: &EventRetention) -> Result<usize, AdminServiceStoreError>;
+
+    /// Returns the ID of the most recent `AdminServiceEvent` removed by compaction, if any events
+    /// have been removed. Events with this ID or lower are no longer available.
+    #[cfg(feature = "admin-service-event-retention")]
+    fn get_compacted_event_id(&self) -> Result<Option<i64>, AdminServiceStoreError>;
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore>;
 }
 
@@ -133,6 +133,9 @@ impl ProposedCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                DurabilityType::GuaranteedDelivery
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `durability type`".to_string(),
@@ -142,6 +145,7 @@ impl ProposedCircuit {
 
         let routes = match proto.get_routes() {
             admin::Circuit_RouteType::ANY_ROUTE => RouteType::Any,
+            admin::Circuit_RouteType::RELAYED_ROUTE => RouteType::Relayed,
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `route type`".to_string(),
@@ -264,10 +268,14 @@ impl ProposedCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            DurabilityType::GuaranteedDelivery => {
+                circuit.set_durability(admin::Circuit_DurabilityType::GUARANTEED_DELIVERY);
+            }
         };
 
         match self.routes {
             RouteType::Any => circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE),
+            RouteType::Relayed => circuit.set_routes(admin::Circuit_RouteType::RELAYED_ROUTE),
         };
 
         // If the circuit version is equal to the `CIRCUIT_PROTOCOL_VERSION`, the `circuit_status`
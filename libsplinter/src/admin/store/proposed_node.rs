@@ -25,6 +25,7 @@ pub struct ProposedNode {
     node_id: String,
     endpoints: Vec<String>,
     public_key: Option<PublicKey>,
+    relay_node_id: Option<String>,
 }
 
 impl ProposedNode {
@@ -43,6 +44,11 @@ impl ProposedNode {
         &self.public_key
     }
 
+    /// Returns the ID of the node that relays messages for the proposed node, if any
+    pub fn relay_node_id(&self) -> &Option<String> {
+        &self.relay_node_id
+    }
+
     pub fn into_proto(self) -> admin::SplinterNode {
         let mut proto = admin::SplinterNode::new();
 
@@ -53,6 +59,10 @@ impl ProposedNode {
             proto.set_public_key(public_key.into_bytes());
         }
 
+        if let Some(relay_node_id) = self.relay_node_id {
+            proto.set_relay_node_id(relay_node_id);
+        }
+
         proto
     }
 
@@ -66,10 +76,20 @@ impl ProposedNode {
             }
         };
 
+        let relay_node_id = {
+            let relay_node_id = proto.take_relay_node_id();
+            if relay_node_id.is_empty() {
+                None
+            } else {
+                Some(relay_node_id)
+            }
+        };
+
         Self {
             node_id: proto.take_node_id(),
             endpoints: proto.take_endpoints().into(),
             public_key,
+            relay_node_id,
         }
     }
 }
@@ -80,6 +100,7 @@ pub struct ProposedNodeBuilder {
     node_id: Option<String>,
     endpoints: Option<Vec<String>>,
     public_key: Option<PublicKey>,
+    relay_node_id: Option<String>,
 }
 
 impl ProposedNodeBuilder {
@@ -103,6 +124,11 @@ impl ProposedNodeBuilder {
         self.public_key.clone()
     }
 
+    /// Returns the ID of the node that relays messages for the node
+    pub fn relay_node_id(&self) -> Option<String> {
+        self.relay_node_id.clone()
+    }
+
     /// Sets the node ID
     ///
     /// # Arguments
@@ -133,6 +159,16 @@ impl ProposedNodeBuilder {
         self
     }
 
+    /// Sets the relay node
+    ///
+    /// # Arguments
+    ///
+    ///  * `relay_node_id` - The ID of the circuit member that relays messages for the node
+    pub fn with_relay_node_id(mut self, relay_node_id: &str) -> ProposedNodeBuilder {
+        self.relay_node_id = Some(relay_node_id.into());
+        self
+    }
+
     /// Builds the `ProposedNode`
     ///
     /// Returns an error if the node ID or endpoints are not set
@@ -151,6 +187,7 @@ impl ProposedNodeBuilder {
             node_id,
             endpoints,
             public_key: self.public_key,
+            relay_node_id: self.relay_node_id,
         };
 
         Ok(node)
@@ -163,6 +200,7 @@ impl From<&messages::SplinterNode> for ProposedNode {
             node_id: admin_node.node_id.to_string(),
             endpoints: admin_node.endpoints.to_vec(),
             public_key: admin_node.public_key.clone().map(PublicKey::from_bytes),
+            relay_node_id: admin_node.relay_node_id.clone(),
         }
     }
 }
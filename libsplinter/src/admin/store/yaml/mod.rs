@@ -26,6 +26,8 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+#[cfg(feature = "admin-service-event-retention")]
+use super::EventRetention;
 use super::{AdminServiceEvent, EventIter};
 use super::{
     AdminServiceStore, AdminServiceStoreError, AuthorizationType, Circuit, CircuitBuilder,
@@ -537,11 +539,21 @@ impl AdminServiceStore for YamlAdminServiceStore {
                 ))
             })?;
 
-            if state
+            if let Some(existing_expiry) = state
                 .proposal_state
                 .proposals
-                .contains_key(proposal.circuit_id())
+                .get(proposal.circuit_id())
+                .map(|existing| existing.expires_at())
             {
+                // An update without an expiry keeps the existing expiry
+                let proposal = match (proposal.expires_at(), existing_expiry) {
+                    (None, Some(expires_at)) => proposal
+                        .builder()
+                        .with_expires_at(expires_at)
+                        .build()
+                        .map_err(AdminServiceStoreError::InvalidStateError)?,
+                    _ => proposal,
+                };
                 state
                     .proposal_state
                     .proposals
@@ -625,7 +637,7 @@ impl AdminServiceStore for YamlAdminServiceStore {
     /// List circuit proposals from the underlying storage
     ///
     /// The proposals returned can be filtered by provided CircuitPredicate. This enables
-    /// filtering by management type and members.
+    /// filtering by management type, members, services, display name, version, requester and votes.
     fn list_proposals(
         &self,
         predicates: &[CircuitPredicate],
@@ -653,6 +665,27 @@ impl AdminServiceStore for YamlAdminServiceStore {
         Ok(Box::new(proposals.into_iter()))
     }
 
+    fn list_proposals_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        let mut proposals = self
+            .list_proposals(predicates)?
+            .filter(|proposal| {
+                after
+                    .map(|after| proposal.circuit_id() < after)
+                    .unwrap_or(true)
+            })
+            .collect::<Vec<CircuitProposal>>();
+
+        proposals.sort_by(|a, b| b.circuit_id().cmp(a.circuit_id()));
+        proposals.truncate(limit);
+
+        Ok(Box::new(proposals.into_iter()))
+    }
+
     /// Returns the count of proposals in the store
     ///
     /// # Arguments
@@ -766,23 +799,47 @@ impl AdminServiceStore for YamlAdminServiceStore {
                 ))
             })?;
 
-            if state
+            let previous_circuit = state
                 .circuit_state
                 .circuits
-                .contains_key(circuit.circuit_id())
-            {
-                state
-                    .circuit_state
-                    .circuits
-                    .insert(circuit.circuit_id().to_string(), circuit);
-            } else {
-                return Err(AdminServiceStoreError::InvalidStateError(
-                    InvalidStateError::with_message(format!(
-                        "A circuit with ID {} does not exist",
-                        circuit.circuit_id()
-                    )),
-                ));
+                .remove(circuit.circuit_id())
+                .ok_or_else(|| {
+                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
+                        format!("A circuit with ID {} does not exist", circuit.circuit_id()),
+                    ))
+                })?;
+
+            // Replace the services of the previous circuit definition
+            for service in previous_circuit.roster() {
+                let service_id = ServiceId::new(
+                    service.service_id().to_string(),
+                    circuit.circuit_id().to_string(),
+                );
+                state.service_directory.remove(&service_id);
             }
+
+            for service in circuit.roster() {
+                let service_id = ServiceId::new(
+                    service.service_id().to_string(),
+                    circuit.circuit_id().to_string(),
+                );
+                state.service_directory.insert(service_id, service.clone());
+            }
+
+            // Members added to the circuit may not be known yet
+            for node in circuit.members() {
+                if !state.circuit_state.nodes.contains_key(node.node_id()) {
+                    state
+                        .circuit_state
+                        .nodes
+                        .insert(node.node_id().to_string(), node.clone());
+                }
+            }
+
+            state
+                .circuit_state
+                .circuits
+                .insert(circuit.circuit_id().to_string(), circuit);
         }
 
         self.write_circuit_state().map_err(|err| {
@@ -859,7 +916,7 @@ impl AdminServiceStore for YamlAdminServiceStore {
     /// List all circuits from the underlying storage
     ///
     /// The proposals returned can be filtered by provided CircuitPredicate. This enables
-    /// filtering by management type and members.
+    /// filtering by management type, members, services, display name, version, requester and votes.
     fn list_circuits(
         &self,
         predicates: &[CircuitPredicate],
@@ -887,6 +944,27 @@ impl AdminServiceStore for YamlAdminServiceStore {
         Ok(Box::new(circuits.into_iter()))
     }
 
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let mut circuits = self
+            .list_circuits(predicates)?
+            .filter(|circuit| {
+                after
+                    .map(|after| circuit.circuit_id() < after)
+                    .unwrap_or(true)
+            })
+            .collect::<Vec<Circuit>>();
+
+        circuits.sort_by(|a, b| b.circuit_id().cmp(a.circuit_id()));
+        circuits.truncate(limit);
+
+        Ok(Box::new(circuits.into_iter()))
+    }
+
     /// Returns the count of circuits in the store
     ///
     /// # Arguments
@@ -1115,6 +1193,18 @@ impl AdminServiceStore for YamlAdminServiceStore {
         unimplemented!()
     }
 
+    /// The YAML store does not store events, so there are never any events to remove
+    #[cfg(feature = "admin-service-event-retention")]
+    fn compact_events(&self, _retention: &EventRetention) -> Result<usize, AdminServiceStoreError> {
+        Ok(0)
+    }
+
+    /// The YAML store does not store events, so no events have been removed
+    #[cfg(feature = "admin-service-event-retention")]
+    fn get_compacted_event_id(&self) -> Result<Option<i64>, AdminServiceStoreError> {
+        Ok(None)
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -1139,6 +1229,8 @@ struct YamlCircuit {
     circuit_version: i32,
     #[serde(default = "default_circuit_status")]
     circuit_status: YamlCircuitStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    application_metadata: Option<String>,
 }
 
 impl TryFrom<YamlCircuit> for Circuit {
@@ -1179,6 +1271,16 @@ impl TryFrom<YamlCircuit> for Circuit {
             builder = builder.with_display_name(display_name);
         }
 
+        if let Some(application_metadata) = circuit.application_metadata {
+            builder = builder.with_application_metadata(&parse_hex(&application_metadata).map_err(
+                |_| {
+                    InvalidStateError::with_message(
+                        "Circuit application metadata is not valid hex".to_string(),
+                    )
+                },
+            )?)
+        }
+
         builder.build()
     }
 }
@@ -1205,6 +1307,10 @@ impl From<Circuit> for YamlCircuit {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status().clone().into(),
+            application_metadata: circuit
+                .application_metadata()
+                .as_ref()
+                .map(|app_metadata| to_hex(app_metadata)),
         }
     }
 }
@@ -1407,6 +1513,7 @@ pub enum YamlProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    UpdateApplicationMetadata,
 }
 
 impl From<YamlProposalType> for ProposalType {
@@ -1417,6 +1524,7 @@ impl From<YamlProposalType> for ProposalType {
             YamlProposalType::AddNode => ProposalType::AddNode,
             YamlProposalType::RemoveNode => ProposalType::RemoveNode,
             YamlProposalType::Disband => ProposalType::Disband,
+            YamlProposalType::UpdateApplicationMetadata => ProposalType::UpdateApplicationMetadata,
         }
     }
 }
@@ -1429,6 +1537,7 @@ impl From<ProposalType> for YamlProposalType {
             ProposalType::AddNode => YamlProposalType::AddNode,
             ProposalType::RemoveNode => YamlProposalType::RemoveNode,
             ProposalType::Disband => YamlProposalType::Disband,
+            ProposalType::UpdateApplicationMetadata => YamlProposalType::UpdateApplicationMetadata,
         }
     }
 }
@@ -1700,12 +1809,14 @@ impl From<YamlPersistenceType> for PersistenceType {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum YamlDurabilityType {
     NoDurability,
+    GuaranteedDelivery,
 }
 
 impl From<DurabilityType> for YamlDurabilityType {
     fn from(durability_type: DurabilityType) -> Self {
         match durability_type {
             DurabilityType::NoDurability => YamlDurabilityType::NoDurability,
+            DurabilityType::GuaranteedDelivery => YamlDurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -1714,6 +1825,7 @@ impl From<YamlDurabilityType> for DurabilityType {
     fn from(yaml_durability_type: YamlDurabilityType) -> Self {
         match yaml_durability_type {
             YamlDurabilityType::NoDurability => DurabilityType::NoDurability,
+            YamlDurabilityType::GuaranteedDelivery => DurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -1722,12 +1834,14 @@ impl From<YamlDurabilityType> for DurabilityType {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum YamlRouteType {
     Any,
+    Relayed,
 }
 
 impl From<RouteType> for YamlRouteType {
     fn from(route_type: RouteType) -> Self {
         match route_type {
             RouteType::Any => YamlRouteType::Any,
+            RouteType::Relayed => YamlRouteType::Relayed,
         }
     }
 }
@@ -1736,6 +1850,7 @@ impl From<YamlRouteType> for RouteType {
     fn from(yaml_route_type: YamlRouteType) -> Self {
         match yaml_route_type {
             YamlRouteType::Any => RouteType::Any,
+            YamlRouteType::Relayed => RouteType::Relayed,
         }
     }
 }
@@ -54,17 +54,25 @@ pub(crate) fn validate_claims(
     secret_manager: &Arc<dyn SecretManager>,
     validation: &Validation,
 ) -> AuthorizationResult {
-    let secret = match secret_manager.secret() {
-        Ok(secret) => secret,
+    let key_ring = match secret_manager.key_ring() {
+        Ok(key_ring) => key_ring,
         Err(err) => {
             debug!("Failed to fetch secret {}", err);
             return AuthorizationResult::Failed;
         }
     };
 
+    let key = match key_ring.key_for_token(token) {
+        Some(key) => key,
+        None => {
+            debug!("Invalid token: signing key is unknown or has expired");
+            return AuthorizationResult::Unauthorized;
+        }
+    };
+
     match decode::<Claims>(
         token,
-        &DecodingKey::from_secret(secret.as_ref()),
+        &DecodingKey::from_secret(key.secret().as_ref()),
         validation,
     ) {
         Ok(claims) => AuthorizationResult::Authorized(claims.claims),
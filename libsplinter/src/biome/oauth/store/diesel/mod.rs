@@ -31,6 +31,7 @@ use super::{
 
 use operations::{
     add_session::OAuthUserSessionStoreAddSession as _,
+    count_users::OAuthUserSessionStoreCountUsers as _,
     get_session::OAuthUserSessionStoreGetSession as _, get_user::OAuthUserSessionStoreGetUser as _,
     list_users::OAuthUserSessionStoreListUsers as _,
     remove_session::OAuthUserSessionStoreRemoveSession as _,
@@ -112,7 +113,23 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::sqlite::Sqlit
 
     fn list_users(&self) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
         self.connection_pool.execute_read(|connection| {
-            OAuthUserSessionStoreOperations::new(connection).list_users()
+            OAuthUserSessionStoreOperations::new(connection).list_users(None, None)
+        })
+    }
+
+    fn list_users_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            OAuthUserSessionStoreOperations::new(connection).list_users(after, Some(limit))
+        })
+    }
+
+    fn count_users(&self) -> Result<usize, OAuthUserSessionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            OAuthUserSessionStoreOperations::new(connection).count_users()
         })
     }
 
@@ -169,7 +186,23 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::pg::PgConnect
 
     fn list_users(&self) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
         self.connection_pool.execute_read(|connection| {
-            OAuthUserSessionStoreOperations::new(connection).list_users()
+            OAuthUserSessionStoreOperations::new(connection).list_users(None, None)
+        })
+    }
+
+    fn list_users_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            OAuthUserSessionStoreOperations::new(connection).list_users(after, Some(limit))
+        })
+    }
+
+    fn count_users(&self) -> Result<usize, OAuthUserSessionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            OAuthUserSessionStoreOperations::new(connection).count_users()
         })
     }
 
@@ -606,6 +639,55 @@ pub mod tests {
         assert_eq!(users.len(), 2);
     }
 
+    /// Verify that a SQLite-backed `DieselOAuthUserSessionStore` correctly supports listing pages
+    /// of OAuth users and counting them.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create a `DieselOAuthUserSessionStore`.
+    /// 3. Add OAuth user sessions for three different subjects.
+    /// 4. Verify that `count_users` returns 3.
+    /// 5. Verify that the first page of two users contains the first two subjects, in order.
+    /// 6. Verify that the page after the second subject contains only the last subject.
+    #[test]
+    fn sqlite_list_users_page() {
+        let pool = create_connection_pool_and_migrate();
+
+        let oauth_user_session_store = DieselOAuthUserSessionStore::new(pool);
+
+        for (i, subject) in ["subject-c", "subject-a", "subject-b"].iter().enumerate() {
+            let session = InsertableOAuthUserSessionBuilder::new()
+                .with_splinter_access_token(format!("splinter_access_token{}", i))
+                .with_subject(subject.to_string())
+                .with_oauth_access_token(format!("oauth_access_token{}", i))
+                .build()
+                .expect("Unable to build session");
+            oauth_user_session_store
+                .add_session(session)
+                .expect("Unable to add session");
+        }
+
+        assert_eq!(
+            oauth_user_session_store
+                .count_users()
+                .expect("Unable to count users"),
+            3
+        );
+
+        let subjects = oauth_user_session_store
+            .list_users_page(None, 2)
+            .expect("Unable to list first page")
+            .map(|user| user.subject().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(subjects, vec!["subject-a", "subject-b"]);
+
+        let subjects = oauth_user_session_store
+            .list_users_page(Some("subject-b"), 2)
+            .expect("Unable to list second page")
+            .map(|user| user.subject().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(subjects, vec!["subject-c"]);
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
@@ -22,7 +22,13 @@ use crate::biome::oauth::store::{
 use super::OAuthUserSessionStoreOperations;
 
 pub trait OAuthUserSessionStoreListUsers {
-    fn list_users(&self) -> Result<OAuthUserIter, OAuthUserSessionStoreError>;
+    /// Lists the OAuth users. If a limit is given, the users are ordered by subject, starting after
+    /// the subject `after`, and at most `limit` users are returned.
+    fn list_users(
+        &self,
+        after: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<OAuthUserIter, OAuthUserSessionStoreError>;
 }
 
 impl<'a, C> OAuthUserSessionStoreListUsers for OAuthUserSessionStoreOperations<'a, C>
@@ -30,9 +36,21 @@ where
     C: diesel::Connection,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
-    fn list_users(&self) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
+    fn list_users(
+        &self,
+        after: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
+        let mut query = oauth_users::table.into_boxed();
+        if let Some(limit) = limit {
+            if let Some(after) = after {
+                query = query.filter(oauth_users::subject.gt(after));
+            }
+            query = query.order(oauth_users::subject).limit(limit as i64);
+        }
+
         Ok(OAuthUserIter::new(
-            oauth_users::table
+            query
                 .load::<OAuthUserModel>(self.conn)?
                 .into_iter()
                 .map(OAuthUser::from)
@@ -16,6 +16,7 @@
 //! operations implemented for a diesel backend
 
 pub(super) mod add_session;
+pub(super) mod count_users;
 pub(super) mod get_session;
 pub(super) mod get_user;
 pub(super) mod list_users;
@@ -393,6 +393,31 @@ pub trait OAuthUserSessionStore: Send + Sync {
     /// Returns the list of OAuth users, including the Biome user ID if it exists
     fn list_users(&self) -> Result<OAuthUserIter, OAuthUserSessionStoreError>;
 
+    /// Returns a page of OAuth users, in ascending order of subject identifier
+    ///
+    /// The page starts with the first user whose subject follows `after`, if provided, and
+    /// contains at most `limit` users.
+    fn list_users_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<OAuthUserIter, OAuthUserSessionStoreError> {
+        let mut users = self
+            .list_users()?
+            .filter(|user| after.map(|after| user.subject() > after).unwrap_or(true))
+            .collect::<Vec<_>>();
+
+        users.sort_by(|a, b| a.subject().cmp(b.subject()));
+        users.truncate(limit);
+
+        Ok(OAuthUserIter::new(users))
+    }
+
+    /// Returns the number of OAuth users
+    fn count_users(&self) -> Result<usize, OAuthUserSessionStoreError> {
+        Ok(self.list_users()?.len())
+    }
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn OAuthUserSessionStore>;
 }
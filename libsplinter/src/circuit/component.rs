@@ -154,7 +154,7 @@ mod tests {
 
     use crate::circuit::routing::AuthorizationType;
     use crate::circuit::routing::{
-        memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
+        memory::RoutingTable, Circuit, CircuitNode, DurabilityType, RoutingTableWriter, Service,
     };
 
     #[test]
@@ -371,6 +371,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         (circuit, vec![node_123, node_345])
@@ -0,0 +1,34 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Guaranteed delivery of direct messages on circuits.
+//!
+//! When a circuit has the `GuaranteedDelivery` durability type, the
+//! [`CircuitDirectMessageHandler`] stores each direct message it sends to another node in the
+//! outbox of a [`DurabilityStore`] and gives it a message ID, if the sending service did not
+//! provide one. The receiving node acknowledges each message with a `CircuitDirectMessageAck`
+//! and uses the message ID to drop messages it has already delivered. Once a message is
+//! acknowledged, the [`CircuitDirectMessageAckHandler`] removes it from the outbox.
+//!
+//! Messages that have not been acknowledged are resent by the [`OutboxResender`] each time the
+//! `PeerManager` reconnects to the node they were sent to.
+//!
+//! [`CircuitDirectMessageHandler`]: crate::circuit::handlers::CircuitDirectMessageHandler
+//! [`CircuitDirectMessageAckHandler`]: crate::circuit::handlers::CircuitDirectMessageAckHandler
+//! [`DurabilityStore`]: store::DurabilityStore
+
+mod resender;
+pub mod store;
+
+pub use resender::OutboxResender;
@@ -0,0 +1,219 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resends unacknowledged circuit messages when peers reconnect.
+
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::circuit::routing::{DurabilityType, RoutingTableReader, RoutingTableReaderError};
+use crate::error::InternalError;
+use crate::network::dispatch::{MessageSender, PeerId};
+use crate::peer::{PeerManagerConnector, PeerManagerNotification, PeerTokenPair, SubscriberId};
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::store::DurabilityStore;
+
+/// How long the IDs of received messages are kept to drop duplicates. A message that is resent
+/// after this time is delivered again.
+const RECEIVED_MESSAGE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// How often the IDs of received messages older than `RECEIVED_MESSAGE_TTL` are pruned
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Resends the messages in the outbox for a node each time the `PeerManager` reconnects to it.
+///
+/// The resender also periodically prunes the IDs of received messages that are older than
+/// seven days.
+pub struct OutboxResender {
+    peer_connector: PeerManagerConnector,
+    subscriber_id: Option<SubscriberId>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl OutboxResender {
+    /// Starts a thread that listens for `PeerManager` notifications and resends unacknowledged
+    /// messages when a peer connects.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the local node
+    /// * `store` - The store that holds the outbox
+    /// * `routing_table` - Used to look up the node a connected peer belongs to
+    /// * `sender` - Used to resend messages
+    /// * `peer_connector` - Used to subscribe to `PeerManager` notifications
+    pub fn start(
+        node_id: &str,
+        store: Box<dyn DurabilityStore>,
+        routing_table: Box<dyn RoutingTableReader>,
+        sender: Box<dyn MessageSender<PeerId>>,
+        peer_connector: PeerManagerConnector,
+    ) -> Result<Self, InternalError> {
+        let (notification_tx, notification_rx) = channel();
+        let subscriber_id = peer_connector
+            .subscribe_sender(notification_tx)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let resender = Resender {
+            node_id: node_id.to_string(),
+            store,
+            routing_table,
+            sender,
+        };
+
+        let join_handle = thread::Builder::new()
+            .name("OutboxResender".into())
+            .spawn(move || {
+                let mut last_pruned: Option<Instant> = None;
+                loop {
+                    if last_pruned
+                        .map(|last_pruned| last_pruned.elapsed() >= PRUNE_INTERVAL)
+                        .unwrap_or(true)
+                    {
+                        resender.prune_received_messages();
+                        last_pruned = Some(Instant::now());
+                    }
+
+                    match notification_rx.recv_timeout(PRUNE_INTERVAL) {
+                        Ok(PeerManagerNotification::Connected { peer }) => resender.resend(peer),
+                        Ok(PeerManagerNotification::Disconnected { .. }) => (),
+                        Err(RecvTimeoutError::Timeout) => (),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            debug!("Outbox resender stopped receiving peer manager notifications");
+                            break;
+                        }
+                    }
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(OutboxResender {
+            peer_connector,
+            subscriber_id: Some(subscriber_id),
+            join_handle,
+        })
+    }
+}
+
+impl ShutdownHandle for OutboxResender {
+    fn signal_shutdown(&mut self) {
+        // Unsubscribing drops the notification sender, which stops the thread
+        if let Some(subscriber_id) = self.subscriber_id.take() {
+            if let Err(err) = self.peer_connector.unsubscribe(subscriber_id) {
+                warn!(
+                    "Unable to unsubscribe from peer manager notifications: {}",
+                    err
+                );
+            }
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|_| {
+            InternalError::with_message("Unable to join outbox resender thread".to_string())
+        })
+    }
+}
+
+struct Resender {
+    node_id: String,
+    store: Box<dyn DurabilityStore>,
+    routing_table: Box<dyn RoutingTableReader>,
+    sender: Box<dyn MessageSender<PeerId>>,
+}
+
+impl Resender {
+    fn resend(&self, peer: PeerTokenPair) {
+        let node_id = match self.find_node(&peer) {
+            Ok(Some(node_id)) => node_id,
+            Ok(None) => return,
+            Err(err) => {
+                error!("Unable to look up node for peer {}: {}", peer, err);
+                return;
+            }
+        };
+
+        let messages = match self.store.list_outbox_messages(&node_id) {
+            Ok(messages) => messages,
+            Err(err) => {
+                error!("Unable to list outbox messages for {}: {}", node_id, err);
+                return;
+            }
+        };
+
+        if !messages.is_empty() {
+            debug!(
+                "Resending {} unacknowledged message(s) to {}",
+                messages.len(),
+                node_id
+            );
+        }
+
+        for message in messages {
+            if self
+                .sender
+                .send(peer.clone().into(), message.payload().to_vec())
+                .is_err()
+            {
+                // The remaining messages are resent on the next reconnect
+                warn!("Unable to resend messages to {}", node_id);
+                break;
+            }
+        }
+    }
+
+    fn prune_received_messages(&self) {
+        let received_before = match SystemTime::now().checked_sub(RECEIVED_MESSAGE_TTL) {
+            Some(received_before) => received_before,
+            None => return,
+        };
+
+        match self.store.remove_received_messages(received_before) {
+            Ok(0) => (),
+            Ok(count) => debug!("Pruned {} received message ID(s)", count),
+            Err(err) => error!("Unable to prune received messages: {}", err),
+        }
+    }
+
+    /// Returns the ID of the node the peer belongs to, if the node is a member of a circuit with
+    /// guaranteed delivery
+    fn find_node(&self, peer: &PeerTokenPair) -> Result<Option<String>, RoutingTableReaderError> {
+        for (_, circuit) in self.routing_table.list_circuits()? {
+            if circuit.durability() != &DurabilityType::GuaranteedDelivery {
+                continue;
+            }
+
+            let local_peer_id = match self.routing_table.get_node(&self.node_id)? {
+                Some(node) => node.get_peer_auth_token(circuit.authorization_type())?,
+                None => continue,
+            };
+
+            for member in circuit.members() {
+                if member == &self.node_id {
+                    continue;
+                }
+
+                if let Some(node) = self.routing_table.get_node(member)? {
+                    let peer_id = node.get_peer_auth_token(circuit.authorization_type())?;
+                    if &PeerTokenPair::new(peer_id, local_peer_id.clone()) == peer {
+                        return Ok(Some(member.to_string()));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
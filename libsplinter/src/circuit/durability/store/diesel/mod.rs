@@ -0,0 +1,382 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database backed [DurabilityStore](super::DurabilityStore) implementation, powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::store::pool::ConnectionPool;
+
+use super::{DurabilityStore, DurabilityStoreError, OutboxMessage};
+
+use operations::add_outbox_message::DurabilityStoreAddOutboxMessageOperation as _;
+use operations::add_received_message::DurabilityStoreAddReceivedMessageOperation as _;
+use operations::has_received_message::DurabilityStoreHasReceivedMessageOperation as _;
+use operations::list_outbox_messages::DurabilityStoreListOutboxMessagesOperation as _;
+use operations::remove_outbox_message::DurabilityStoreRemoveOutboxMessageOperation as _;
+use operations::remove_received_messages::DurabilityStoreRemoveReceivedMessagesOperation as _;
+use operations::DurabilityStoreOperations;
+
+/// A database-backed DurabilityStore, powered by [`Diesel`](https://crates.io/crates/diesel).
+pub struct DieselDurabilityStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselDurabilityStore<C> {
+    /// Creates a new `DieselDurabilityStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselDurabilityStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselDurabilityStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselDurabilityStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl DurabilityStore for DieselDurabilityStore<diesel::pg::PgConnection> {
+    fn add_outbox_message(&self, message: OutboxMessage) -> Result<(), DurabilityStoreError> {
+        self.connection_pool
+            .execute_write(|conn| DurabilityStoreOperations::new(conn).add_outbox_message(message))
+    }
+
+    fn remove_outbox_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+        node_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            DurabilityStoreOperations::new(conn)
+                .remove_outbox_message(circuit_id, message_id, node_id)
+        })
+    }
+
+    fn list_outbox_messages(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<OutboxMessage>, DurabilityStoreError> {
+        self.connection_pool
+            .execute_read(|conn| DurabilityStoreOperations::new(conn).list_outbox_messages(node_id))
+    }
+
+    fn add_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            DurabilityStoreOperations::new(conn).add_received_message(circuit_id, message_id)
+        })
+    }
+
+    fn has_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<bool, DurabilityStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            DurabilityStoreOperations::new(conn).has_received_message(circuit_id, message_id)
+        })
+    }
+
+    fn remove_received_messages(
+        &self,
+        received_before: SystemTime,
+    ) -> Result<usize, DurabilityStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            DurabilityStoreOperations::new(conn).remove_received_messages(received_before)
+        })
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DurabilityStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl DurabilityStore for DieselDurabilityStore<diesel::sqlite::SqliteConnection> {
+    fn add_outbox_message(&self, message: OutboxMessage) -> Result<(), DurabilityStoreError> {
+        self.connection_pool
+            .execute_write(|conn| DurabilityStoreOperations::new(conn).add_outbox_message(message))
+    }
+
+    fn remove_outbox_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+        node_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            DurabilityStoreOperations::new(conn)
+                .remove_outbox_message(circuit_id, message_id, node_id)
+        })
+    }
+
+    fn list_outbox_messages(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<OutboxMessage>, DurabilityStoreError> {
+        self.connection_pool
+            .execute_read(|conn| DurabilityStoreOperations::new(conn).list_outbox_messages(node_id))
+    }
+
+    fn add_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            DurabilityStoreOperations::new(conn).add_received_message(circuit_id, message_id)
+        })
+    }
+
+    fn has_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<bool, DurabilityStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            DurabilityStoreOperations::new(conn).has_received_message(circuit_id, message_id)
+        })
+    }
+
+    fn remove_received_messages(
+        &self,
+        received_before: SystemTime,
+    ) -> Result<usize, DurabilityStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            DurabilityStoreOperations::new(conn).remove_received_messages(received_before)
+        })
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DurabilityStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that messages can be added to and removed from the outbox.
+    ///
+    /// 1. Add two messages for one node and one message for another node
+    /// 2. Validate the messages for the first node are listed in the order they were added
+    /// 3. Add the first message again and validate it is not duplicated
+    /// 4. Remove the first message as acknowledged by the second node and validate it is still
+    ///    listed
+    /// 5. Remove the first message and validate only the second message is listed
+    /// 6. Remove the first message again and validate no error is returned
+    #[test]
+    fn test_outbox() {
+        let store = DieselDurabilityStore::new(create_connection_pool_and_migrate());
+
+        let message_1 = OutboxMessage::new(
+            "message-1".into(),
+            "abcde-01234".into(),
+            "node-1".into(),
+            b"one".to_vec(),
+        );
+        let message_2 = OutboxMessage::new(
+            "message-2".into(),
+            "abcde-01234".into(),
+            "node-1".into(),
+            b"two".to_vec(),
+        );
+        let message_3 = OutboxMessage::new(
+            "message-3".into(),
+            "abcde-01234".into(),
+            "node-2".into(),
+            b"three".to_vec(),
+        );
+
+        store
+            .add_outbox_message(message_1.clone())
+            .expect("Unable to add message");
+        store
+            .add_outbox_message(message_2.clone())
+            .expect("Unable to add message");
+        store
+            .add_outbox_message(message_3.clone())
+            .expect("Unable to add message");
+
+        assert_eq!(
+            store
+                .list_outbox_messages("node-1")
+                .expect("Unable to list messages"),
+            vec![message_1.clone(), message_2.clone()]
+        );
+
+        store
+            .add_outbox_message(message_1)
+            .expect("Unable to add message");
+        assert_eq!(
+            store
+                .list_outbox_messages("node-1")
+                .expect("Unable to list messages")
+                .len(),
+            2
+        );
+
+        store
+            .remove_outbox_message("abcde-01234", "message-1", "node-2")
+            .expect("Unable to remove message");
+        assert_eq!(
+            store
+                .list_outbox_messages("node-1")
+                .expect("Unable to list messages")
+                .len(),
+            2
+        );
+
+        store
+            .remove_outbox_message("abcde-01234", "message-1", "node-1")
+            .expect("Unable to remove message");
+        assert_eq!(
+            store
+                .list_outbox_messages("node-1")
+                .expect("Unable to list messages"),
+            vec![message_2]
+        );
+
+        store
+            .remove_outbox_message("abcde-01234", "message-1", "node-1")
+            .expect("Unable to remove message");
+        assert_eq!(
+            store
+                .list_outbox_messages("node-2")
+                .expect("Unable to list messages"),
+            vec![message_3]
+        );
+    }
+
+    /// Verify that received messages are recorded per circuit.
+    ///
+    /// 1. Validate a message has not been received
+    /// 2. Add the message twice and validate it has been received
+    /// 3. Validate a message with the same ID on another circuit has not been received
+    #[test]
+    fn test_received_messages() {
+        let store = DieselDurabilityStore::new(create_connection_pool_and_migrate());
+
+        assert!(!store
+            .has_received_message("abcde-01234", "message-1")
+            .expect("Unable to check message"));
+
+        store
+            .add_received_message("abcde-01234", "message-1")
+            .expect("Unable to add message");
+        store
+            .add_received_message("abcde-01234", "message-1")
+            .expect("Unable to add message");
+
+        assert!(store
+            .has_received_message("abcde-01234", "message-1")
+            .expect("Unable to check message"));
+        assert!(!store
+            .has_received_message("fghij-56789", "message-1")
+            .expect("Unable to check message"));
+    }
+
+    /// Verify that the records of received messages are removed once they are older than the
+    /// given time.
+    ///
+    /// 1. Add a received message
+    /// 2. Remove the messages received before the Unix epoch and validate the message is kept
+    /// 3. Remove the messages received before a minute from now and validate the message is
+    ///    removed
+    #[test]
+    fn test_remove_received_messages() {
+        let store = DieselDurabilityStore::new(create_connection_pool_and_migrate());
+
+        store
+            .add_received_message("abcde-01234", "message-1")
+            .expect("Unable to add message");
+
+        assert_eq!(
+            store
+                .remove_received_messages(SystemTime::UNIX_EPOCH)
+                .expect("Unable to remove messages"),
+            0
+        );
+        assert!(store
+            .has_received_message("abcde-01234", "message-1")
+            .expect("Unable to check message"));
+
+        assert_eq!(
+            store
+                .remove_received_messages(SystemTime::now() + std::time::Duration::from_secs(60))
+                .expect("Unable to remove messages"),
+            1
+        );
+        assert!(!store
+            .has_received_message("abcde-01234", "message-1")
+            .expect("Unable to check message"));
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::circuit::durability::store::OutboxMessage;
+
+use super::schema::{circuit_outbox_message, circuit_received_message};
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "circuit_outbox_message"]
+#[primary_key(circuit_id, message_id)]
+pub struct OutboxMessageModel {
+    pub circuit_id: String,
+    pub message_id: String,
+    pub node_id: String,
+    pub position: i64,
+    pub payload: Vec<u8>,
+}
+
+impl From<OutboxMessageModel> for OutboxMessage {
+    fn from(model: OutboxMessageModel) -> Self {
+        OutboxMessage::new(
+            model.message_id,
+            model.circuit_id,
+            model.node_id,
+            model.payload,
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "circuit_received_message"]
+#[primary_key(circuit_id, message_id)]
+pub struct ReceivedMessageModel {
+    pub circuit_id: String,
+    pub message_id: String,
+    /// The time the message was received, in seconds since the Unix epoch
+    pub received_at: i64,
+}
@@ -0,0 +1,104 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add outbox message" operation for the `DieselDurabilityStore`.
+
+use diesel::{dsl::insert_into, dsl::max, prelude::*};
+
+use crate::circuit::durability::store::{
+    diesel::{models::OutboxMessageModel, schema::circuit_outbox_message},
+    DurabilityStoreError, OutboxMessage,
+};
+
+use super::DurabilityStoreOperations;
+
+pub(in crate::circuit::durability::store::diesel) trait DurabilityStoreAddOutboxMessageOperation {
+    fn add_outbox_message(&self, message: OutboxMessage) -> Result<(), DurabilityStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurabilityStoreAddOutboxMessageOperation
+    for DurabilityStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_outbox_message(&self, message: OutboxMessage) -> Result<(), DurabilityStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // A message that is already in the outbox is not added again, so it keeps its place
+            if circuit_outbox_message::table
+                .filter(circuit_outbox_message::circuit_id.eq(message.circuit_id()))
+                .filter(circuit_outbox_message::message_id.eq(message.message_id()))
+                .first::<OutboxMessageModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Ok(());
+            }
+
+            let position = circuit_outbox_message::table
+                .select(max(circuit_outbox_message::position))
+                .first::<Option<i64>>(self.conn)?
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            insert_into(circuit_outbox_message::table)
+                .values(OutboxMessageModel {
+                    circuit_id: message.circuit_id().to_string(),
+                    message_id: message.message_id().to_string(),
+                    node_id: message.node_id().to_string(),
+                    position,
+                    payload: message.payload().to_vec(),
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurabilityStoreAddOutboxMessageOperation
+    for DurabilityStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_outbox_message(&self, message: OutboxMessage) -> Result<(), DurabilityStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // A message that is already in the outbox is not added again, so it keeps its place
+            if circuit_outbox_message::table
+                .filter(circuit_outbox_message::circuit_id.eq(message.circuit_id()))
+                .filter(circuit_outbox_message::message_id.eq(message.message_id()))
+                .first::<OutboxMessageModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Ok(());
+            }
+
+            let position = circuit_outbox_message::table
+                .select(max(circuit_outbox_message::position))
+                .first::<Option<i64>>(self.conn)?
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            insert_into(circuit_outbox_message::table)
+                .values(OutboxMessageModel {
+                    circuit_id: message.circuit_id().to_string(),
+                    message_id: message.message_id().to_string(),
+                    node_id: message.node_id().to_string(),
+                    position,
+                    payload: message.payload().to_vec(),
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
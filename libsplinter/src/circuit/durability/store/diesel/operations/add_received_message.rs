@@ -0,0 +1,100 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add received message" operation for the `DieselDurabilityStore`.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::circuit::durability::store::{
+    diesel::{models::ReceivedMessageModel, schema::circuit_received_message},
+    DurabilityStoreError,
+};
+
+use super::{to_seconds, DurabilityStoreOperations};
+
+pub(in crate::circuit::durability::store::diesel) trait DurabilityStoreAddReceivedMessageOperation {
+    fn add_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<(), DurabilityStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurabilityStoreAddReceivedMessageOperation
+    for DurabilityStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            if circuit_received_message::table
+                .filter(circuit_received_message::circuit_id.eq(circuit_id))
+                .filter(circuit_received_message::message_id.eq(message_id))
+                .first::<ReceivedMessageModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Ok(());
+            }
+
+            insert_into(circuit_received_message::table)
+                .values(ReceivedMessageModel {
+                    circuit_id: circuit_id.to_string(),
+                    message_id: message_id.to_string(),
+                    received_at: to_seconds(SystemTime::now())?,
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurabilityStoreAddReceivedMessageOperation
+    for DurabilityStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            if circuit_received_message::table
+                .filter(circuit_received_message::circuit_id.eq(circuit_id))
+                .filter(circuit_received_message::message_id.eq(message_id))
+                .first::<ReceivedMessageModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Ok(());
+            }
+
+            insert_into(circuit_received_message::table)
+                .values(ReceivedMessageModel {
+                    circuit_id: circuit_id.to_string(),
+                    message_id: message_id.to_string(),
+                    received_at: to_seconds(SystemTime::now())?,
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
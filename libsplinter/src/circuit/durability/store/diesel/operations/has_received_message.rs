@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "has received message" operation for the `DieselDurabilityStore`.
+
+use diesel::prelude::*;
+
+use crate::circuit::durability::store::{
+    diesel::{models::ReceivedMessageModel, schema::circuit_received_message},
+    DurabilityStoreError,
+};
+
+use super::DurabilityStoreOperations;
+
+pub(in crate::circuit::durability::store::diesel) trait DurabilityStoreHasReceivedMessageOperation {
+    fn has_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<bool, DurabilityStoreError>;
+}
+
+impl<'a, C> DurabilityStoreHasReceivedMessageOperation for DurabilityStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn has_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<bool, DurabilityStoreError> {
+        Ok(circuit_received_message::table
+            .filter(circuit_received_message::circuit_id.eq(circuit_id))
+            .filter(circuit_received_message::message_id.eq(message_id))
+            .first::<ReceivedMessageModel>(self.conn)
+            .optional()?
+            .is_some())
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list outbox messages" operation for the `DieselDurabilityStore`.
+
+use diesel::prelude::*;
+
+use crate::circuit::durability::store::{
+    diesel::{models::OutboxMessageModel, schema::circuit_outbox_message},
+    DurabilityStoreError, OutboxMessage,
+};
+
+use super::DurabilityStoreOperations;
+
+pub(in crate::circuit::durability::store::diesel) trait DurabilityStoreListOutboxMessagesOperation {
+    fn list_outbox_messages(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<OutboxMessage>, DurabilityStoreError>;
+}
+
+impl<'a, C> DurabilityStoreListOutboxMessagesOperation for DurabilityStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<diesel::sql_types::Binary, C::Backend>,
+{
+    fn list_outbox_messages(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<OutboxMessage>, DurabilityStoreError> {
+        Ok(circuit_outbox_message::table
+            .filter(circuit_outbox_message::node_id.eq(node_id))
+            .order(circuit_outbox_message::position.asc())
+            .load::<OutboxMessageModel>(self.conn)?
+            .into_iter()
+            .map(OutboxMessage::from)
+            .collect())
+    }
+}
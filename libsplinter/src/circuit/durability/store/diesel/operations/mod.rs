@@ -0,0 +1,44 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides database operations for the `DieselDurabilityStore`.
+
+pub(super) mod add_outbox_message;
+pub(super) mod add_received_message;
+pub(super) mod has_received_message;
+pub(super) mod list_outbox_messages;
+pub(super) mod remove_outbox_message;
+pub(super) mod remove_received_messages;
+
+use std::time::SystemTime;
+
+use crate::circuit::durability::store::DurabilityStoreError;
+use crate::error::InternalError;
+
+pub struct DurabilityStoreOperations<'a, C> {
+    conn: &'a C,
+}
+
+impl<'a, C: diesel::Connection> DurabilityStoreOperations<'a, C> {
+    pub fn new(conn: &'a C) -> Self {
+        DurabilityStoreOperations { conn }
+    }
+}
+
+/// Converts a time to the number of seconds since the Unix epoch, as stored in the database
+fn to_seconds(time: SystemTime) -> Result<i64, DurabilityStoreError> {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .map_err(|err| DurabilityStoreError::Internal(InternalError::from_source(Box::new(err))))
+}
@@ -0,0 +1,77 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove outbox message" operation for the `DieselDurabilityStore`.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::circuit::durability::store::{
+    diesel::schema::circuit_outbox_message, DurabilityStoreError,
+};
+
+use super::DurabilityStoreOperations;
+
+pub(in crate::circuit::durability::store::diesel) trait DurabilityStoreRemoveOutboxMessageOperation
+{
+    fn remove_outbox_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+        node_id: &str,
+    ) -> Result<(), DurabilityStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurabilityStoreRemoveOutboxMessageOperation
+    for DurabilityStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_outbox_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+        node_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        delete(
+            circuit_outbox_message::table
+                .filter(circuit_outbox_message::circuit_id.eq(circuit_id))
+                .filter(circuit_outbox_message::message_id.eq(message_id))
+                .filter(circuit_outbox_message::node_id.eq(node_id)),
+        )
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurabilityStoreRemoveOutboxMessageOperation
+    for DurabilityStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_outbox_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+        node_id: &str,
+    ) -> Result<(), DurabilityStoreError> {
+        delete(
+            circuit_outbox_message::table
+                .filter(circuit_outbox_message::circuit_id.eq(circuit_id))
+                .filter(circuit_outbox_message::message_id.eq(message_id))
+                .filter(circuit_outbox_message::node_id.eq(node_id)),
+        )
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+}
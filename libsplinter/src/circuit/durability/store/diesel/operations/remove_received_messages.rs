@@ -0,0 +1,69 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove received messages" operation for the `DieselDurabilityStore`.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::circuit::durability::store::{
+    diesel::schema::circuit_received_message, DurabilityStoreError,
+};
+
+use super::{to_seconds, DurabilityStoreOperations};
+
+pub(in crate::circuit::durability::store::diesel) trait DurabilityStoreRemoveReceivedMessagesOperation
+{
+    fn remove_received_messages(
+        &self,
+        received_before: SystemTime,
+    ) -> Result<usize, DurabilityStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurabilityStoreRemoveReceivedMessagesOperation
+    for DurabilityStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_received_messages(
+        &self,
+        received_before: SystemTime,
+    ) -> Result<usize, DurabilityStoreError> {
+        let received_before = to_seconds(received_before)?;
+
+        Ok(delete(
+            circuit_received_message::table
+                .filter(circuit_received_message::received_at.lt(received_before)),
+        )
+        .execute(self.conn)?)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurabilityStoreRemoveReceivedMessagesOperation
+    for DurabilityStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_received_messages(
+        &self,
+        received_before: SystemTime,
+    ) -> Result<usize, DurabilityStoreError> {
+        let received_before = to_seconds(received_before)?;
+
+        Ok(delete(
+            circuit_received_message::table
+                .filter(circuit_received_message::received_at.lt(received_before)),
+        )
+        .execute(self.conn)?)
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Errors for the DurabilityStore trait
+
+use std::error::Error;
+use std::fmt;
+
+use crate::error::{InternalError, ResourceTemporarilyUnavailableError};
+
+/// Represents DurabilityStore errors
+#[derive(Debug)]
+pub enum DurabilityStoreError {
+    Internal(InternalError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl fmt::Display for DurabilityStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurabilityStoreError::Internal(err) => err.fmt(f),
+            DurabilityStoreError::ResourceTemporarilyUnavailable(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for DurabilityStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DurabilityStoreError::Internal(err) => Some(err),
+            DurabilityStoreError::ResourceTemporarilyUnavailable(err) => Some(err),
+        }
+    }
+}
+
+impl From<InternalError> for DurabilityStoreError {
+    fn from(err: InternalError) -> Self {
+        DurabilityStoreError::Internal(err)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for DurabilityStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        DurabilityStoreError::Internal(InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for DurabilityStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        DurabilityStoreError::ResourceTemporarilyUnavailable(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for guaranteed delivery of circuit messages.
+//!
+//! A node stores each direct message it sends to another node on a durable circuit in an outbox
+//! until the receiving node acknowledges it. The receiving node records the ID of each message it
+//! has delivered, so messages that are resent are only delivered once. The IDs are kept until
+//! they are pruned with [`DurabilityStore::remove_received_messages`].
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+use std::time::SystemTime;
+
+pub use error::DurabilityStoreError;
+
+/// A direct message that has been sent to another node but not yet acknowledged
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutboxMessage {
+    message_id: String,
+    circuit_id: String,
+    node_id: String,
+    payload: Vec<u8>,
+}
+
+impl OutboxMessage {
+    /// Creates a new `OutboxMessage`
+    ///
+    /// # Arguments
+    ///
+    /// * `message_id` - The unique ID of the message
+    /// * `circuit_id` - The circuit the message was sent on
+    /// * `node_id` - The ID of the node the message was sent to
+    /// * `payload` - The bytes of the network message that was sent
+    pub fn new(message_id: String, circuit_id: String, node_id: String, payload: Vec<u8>) -> Self {
+        OutboxMessage {
+            message_id,
+            circuit_id,
+            node_id,
+            payload,
+        }
+    }
+
+    /// Returns the ID of the message
+    pub fn message_id(&self) -> &str {
+        &self.message_id
+    }
+
+    /// Returns the ID of the circuit the message was sent on
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the ID of the node the message was sent to
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Returns the bytes of the network message
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+pub trait DurabilityStore: Send + Sync {
+    /// Adds a message to the outbox
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message that was sent
+    fn add_outbox_message(&self, message: OutboxMessage) -> Result<(), DurabilityStoreError>;
+
+    /// Removes a message from the outbox once it has been acknowledged by the node it was sent
+    /// to. Removing a message that is not in the outbox, or that was sent to another node, is not
+    /// an error, as a message may be acknowledged more than once.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit the message was sent on
+    /// * `message_id` - The ID of the message
+    /// * `node_id` - The ID of the node that acknowledged the message
+    fn remove_outbox_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+        node_id: &str,
+    ) -> Result<(), DurabilityStoreError>;
+
+    /// Lists the messages in the outbox for a node, in the order they were added
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node the messages were sent to
+    fn list_outbox_messages(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<OutboxMessage>, DurabilityStoreError>;
+
+    /// Records that a message has been delivered. Recording a message more than once is not an
+    /// error.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit the message was received on
+    /// * `message_id` - The ID of the message
+    fn add_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<(), DurabilityStoreError>;
+
+    /// Returns true if the message has already been delivered
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit the message was received on
+    /// * `message_id` - The ID of the message
+    fn has_received_message(
+        &self,
+        circuit_id: &str,
+        message_id: &str,
+    ) -> Result<bool, DurabilityStoreError>;
+
+    /// Removes the records of the messages that were received before the given time, and returns
+    /// the number of records removed. A message that is resent after its record is removed is
+    /// delivered again.
+    ///
+    /// # Arguments
+    ///
+    /// * `received_before` - The time before which records are removed
+    fn remove_received_messages(
+        &self,
+        received_before: SystemTime,
+    ) -> Result<usize, DurabilityStoreError>;
+
+    fn clone_boxed(&self) -> Box<dyn DurabilityStore>;
+}
+
+impl Clone for Box<dyn DurabilityStore> {
+    fn clone(&self) -> Box<dyn DurabilityStore> {
+        self.clone_boxed()
+    }
+}
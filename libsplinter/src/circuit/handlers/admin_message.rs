@@ -146,6 +146,23 @@ impl AdminDirectMessageHandler {
             .map_err(|err| DispatchError::HandleError(err.to_string()))?;
 
         let response = if circuit.is_some() {
+            // If the message still has relays to pass through, forward it to the next relay. The
+            // last relay on the route sends it on to the recipient's node.
+            if let Some(next_hop) = msg.get_relay_route().first() {
+                let mut relayed_msg = msg.clone();
+                relayed_msg.mut_relay_route().remove(0);
+                let network_msg_bytes = create_message(
+                    relayed_msg.write_to_bytes().map_err(DispatchError::from)?,
+                    CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                )?;
+                let target_node: PeerId = PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id(next_hop),
+                    PeerAuthorizationToken::from_peer_id(&self.node_id),
+                )
+                .into();
+                return Ok((network_msg_bytes, target_node));
+            }
+
             let mut iter = recipient.split("::");
 
             let admin_prefix = iter
@@ -309,7 +326,7 @@ mod tests {
 
     use crate::circuit::routing::AuthorizationType;
     use crate::circuit::routing::{
-        memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
+        memory::RoutingTable, Circuit, CircuitNode, DurabilityType, RoutingTableWriter, Service,
     };
     use crate::network::dispatch::Dispatcher;
     use crate::peer::PeerAuthorizationToken;
@@ -350,6 +367,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -437,6 +455,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -524,6 +543,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -628,6 +648,61 @@ mod tests {
         )
     }
 
+    /// Send a message to an admin service via the admin circuit that must pass through a relay.
+    /// Expect that the message is sent to the relay node, with the relay removed from the route.
+    #[test]
+    fn test_send_admin_direct_message_via_relay() {
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+
+        let handler = AdminDirectMessageHandler::new("1234".into(), reader, vec![]);
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = AdminDirectMessage::new();
+        direct_message.set_circuit("admin".into());
+        direct_message.set_sender("admin::1234".into());
+        direct_message.set_recipient("admin::5678".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("random_corr_id".into());
+        direct_message.set_relay_route(vec!["hub".to_string()].into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        assert!(dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("admin::1234"),
+                    PeerAuthorizationToken::from_peer_id("1234"),
+                )
+                .into(),
+                &CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                direct_bytes
+            )
+            .is_ok());
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("hub"),
+                PeerAuthorizationToken::from_peer_id("1234"),
+            ),
+            CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+            |msg: AdminDirectMessage| {
+                assert_eq!(msg.get_circuit(), "admin");
+                assert_eq!(msg.get_sender(), "admin::1234");
+                assert_eq!(msg.get_recipient(), "admin::5678");
+                assert_eq!(msg.get_payload(), b"test");
+                assert_eq!(msg.get_correlation_id(), "random_corr_id");
+                assert!(msg.get_relay_route().is_empty());
+            },
+        )
+    }
+
     /// Send a message to an admin service via the admin circuit using a public key. Expect that
     /// the message is sent to the appropriate node that hosts the target admin service.
     #[test]
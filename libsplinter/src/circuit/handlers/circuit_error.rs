@@ -152,7 +152,7 @@ mod tests {
 
     use crate::circuit::routing::AuthorizationType;
     use crate::circuit::routing::{
-        memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
+        memory::RoutingTable, Circuit, CircuitNode, DurabilityType, RoutingTableWriter, Service,
     };
     use crate::network::dispatch::Dispatcher;
     use crate::peer::PeerAuthorizationToken;
@@ -193,6 +193,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -298,6 +299,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
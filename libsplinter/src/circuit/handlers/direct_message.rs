@@ -13,9 +13,16 @@
 // limitations under the License.
 
 use crate::circuit::handlers::create_message;
-use crate::circuit::routing::{RoutingTableReader, ServiceId as RoutingServiceId};
+use crate::circuit::routing::{Circuit, RoutingTableReader, ServiceId as RoutingServiceId};
+#[cfg(feature = "circuit-durability")]
+use crate::circuit::{
+    durability::store::{DurabilityStore, OutboxMessage},
+    routing::DurabilityType,
+};
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::peer::PeerTokenPair;
+#[cfg(feature = "circuit-durability")]
+use crate::protos::circuit::CircuitDirectMessageAck;
 use crate::protos::circuit::{
     CircuitDirectMessage, CircuitError, CircuitError_Error, CircuitMessageType,
 };
@@ -29,6 +36,8 @@ use crate::{
 };
 
 use protobuf::Message;
+#[cfg(feature = "circuit-durability")]
+use uuid::Uuid;
 
 // Implements a handler that handles CircuitDirectMessage
 pub struct CircuitDirectMessageHandler {
@@ -36,6 +45,8 @@ pub struct CircuitDirectMessageHandler {
     routing_table: Box<dyn RoutingTableReader>,
     #[cfg(feature = "service-message-handler-dispatch")]
     service_dispatcher: ServiceDispatcher,
+    #[cfg(feature = "circuit-durability")]
+    durability_store: Option<Box<dyn DurabilityStore>>,
 }
 
 impl Handler for CircuitDirectMessageHandler {
@@ -71,6 +82,66 @@ impl Handler for CircuitDirectMessageHandler {
             }
         );
 
+        #[cfg(feature = "circuit-durability")]
+        {
+            if let Some(store) = &self.durability_store {
+                return self.handle_durable(msg, context, sender, &**store);
+            }
+        }
+
+        self.route(
+            msg,
+            context.message_bytes().to_vec(),
+            context.source_peer_id(),
+            sender,
+        )
+    }
+}
+
+impl CircuitDirectMessageHandler {
+    pub fn new(
+        node_id: String,
+        routing_table: Box<dyn RoutingTableReader>,
+        #[cfg(feature = "service-message-handler-dispatch")] service_dispatcher: ServiceDispatcher,
+    ) -> Self {
+        CircuitDirectMessageHandler {
+            node_id,
+            routing_table,
+            #[cfg(feature = "service-message-handler-dispatch")]
+            service_dispatcher,
+            #[cfg(feature = "circuit-durability")]
+            durability_store: None,
+        }
+    }
+
+    /// Enables guaranteed delivery for circuits with the `GuaranteedDelivery` durability type.
+    ///
+    /// # Arguments
+    ///
+    /// * `durability_store` - Stores the outbox of unacknowledged messages and the IDs of the
+    ///   messages that have been delivered
+    #[cfg(feature = "circuit-durability")]
+    pub fn with_durability_store(mut self, durability_store: Box<dyn DurabilityStore>) -> Self {
+        self.durability_store = Some(durability_store);
+        self
+    }
+
+    /// Routes the direct message to the recipient service, or to the node the recipient service
+    /// is connected to, or returns an error message to the source peer.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The direct message
+    /// * `msg_bytes` - The serialized direct message
+    /// * `source_peer_id` - The peer the message was received from
+    /// * `sender` - Used to send the message
+    fn route(
+        &self,
+        msg: CircuitDirectMessage,
+        msg_bytes: Vec<u8>,
+        source_peer_id: &PeerId,
+        sender: &dyn MessageSender<PeerId>,
+    ) -> Result<(), DispatchError> {
         let circuit_name = msg.get_circuit();
         let msg_sender = msg.get_sender();
         let recipient = msg.get_recipient();
@@ -129,7 +200,7 @@ impl Handler for CircuitDirectMessageHandler {
                     let msg_bytes = error_message.write_to_bytes()?;
                     let network_msg_bytes =
                         create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
-                    (network_msg_bytes, context.source_peer_id().clone())
+                    (network_msg_bytes, source_peer_id.clone())
                 } else if circuit
                     .roster()
                     .iter()
@@ -142,12 +213,33 @@ impl Handler for CircuitDirectMessageHandler {
                         .map_err(|err| DispatchError::HandleError(err.to_string()))?
                     {
                         let node_id = service.node_id().to_string();
-                        let msg_bytes = context.message_bytes().to_vec();
                         let network_msg_bytes =
                             create_message(msg_bytes, CircuitMessageType::CIRCUIT_DIRECT_MESSAGE)?;
                         // If the service is on this node send message to the service, otherwise
-                        // send the message to the node the service is connected to
+                        // send the message to the node the service is connected to, or the next
+                        // relay node on the way to it
                         if node_id != self.node_id {
+                            let (network_msg_bytes, node_id) = if circuit.is_relayed() {
+                                match self.relay(
+                                    &circuit,
+                                    msg.clone(),
+                                    &node_id,
+                                    source_peer_id,
+                                    sender,
+                                )? {
+                                    Some((msg_bytes, next_node_id)) => (
+                                        create_message(
+                                            msg_bytes,
+                                            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                                        )?,
+                                        next_node_id,
+                                    ),
+                                    None => return Ok(()),
+                                }
+                            } else {
+                                (network_msg_bytes, node_id)
+                            };
+
                             let node_peer_id: PeerId = {
                                 let peer_id = self
                                     .routing_table
@@ -209,7 +301,7 @@ impl Handler for CircuitDirectMessageHandler {
                         let msg_bytes = error_message.write_to_bytes()?;
                         let network_msg_bytes =
                             create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
-                        (network_msg_bytes, context.source_peer_id().clone())
+                        (network_msg_bytes, source_peer_id.clone())
                     }
                 } else {
                     // if the recipient is not allowed on the circuit, send circuit error
@@ -227,7 +319,7 @@ impl Handler for CircuitDirectMessageHandler {
                     let msg_bytes = error_message.write_to_bytes()?;
                     let network_msg_bytes =
                         create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
-                    (network_msg_bytes, context.source_peer_id().clone())
+                    (network_msg_bytes, source_peer_id.clone())
                 }
             } else {
                 // if the circuit does not exist, send circuit error
@@ -242,7 +334,7 @@ impl Handler for CircuitDirectMessageHandler {
                 let msg_bytes = error_message.write_to_bytes()?;
                 let network_msg_bytes =
                     create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
-                (network_msg_bytes, context.source_peer_id().clone())
+                (network_msg_bytes, source_peer_id.clone())
             }
         };
 
@@ -254,39 +346,291 @@ impl Handler for CircuitDirectMessageHandler {
             })?;
         Ok(())
     }
+
+    /// Determines the node a direct message on a circuit with relayed routes must be sent to
+    /// next and updates the message's time-to-live.
+    ///
+    /// A message is only relayed if this node is on the route between the sender's node and the
+    /// target node and the message was received from the previous node on that route. Returns
+    /// the serialized direct message and the next node ID, or `None` if the message was dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit the message was sent on
+    /// * `msg` - The direct message
+    /// * `target_node_id` - The ID of the node the recipient service is connected to
+    /// * `source_peer_id` - The peer the message was received from
+    /// * `sender` - Used to send an error message if the message's time-to-live has expired
+    fn relay(
+        &self,
+        circuit: &Circuit,
+        mut msg: CircuitDirectMessage,
+        target_node_id: &str,
+        source_peer_id: &PeerId,
+        sender: &dyn MessageSender<PeerId>,
+    ) -> Result<Option<(Vec<u8>, String)>, DispatchError> {
+        let sender_node_id = circuit
+            .roster()
+            .iter()
+            .find(|service| service.service_id() == msg.get_sender())
+            .map(|service| service.node_id().to_string())
+            .ok_or_else(|| {
+                DispatchError::HandleError(format!(
+                    "Sender {} is not in circuit {}",
+                    msg.get_sender(),
+                    circuit.circuit_id()
+                ))
+            })?;
+
+        let route = circuit.route(&sender_node_id, target_node_id);
+        let position = match route.iter().position(|node_id| node_id == &self.node_id) {
+            Some(position) if position + 1 < route.len() => position,
+            _ => {
+                warn!(
+                    "Dropping message on {} from {}: not a relay for {} => {}",
+                    circuit.circuit_id(),
+                    source_peer_id,
+                    sender_node_id,
+                    target_node_id
+                );
+                return Ok(None);
+            }
+        };
+
+        if position == 0 {
+            // the message originated from a service on this node
+            msg.set_ttl(circuit.members().len() as u32);
+        } else {
+            // only relay messages received from the previous node on the route
+            let previous_peer_token = self
+                .routing_table
+                .get_node(&route[position - 1])
+                .map_err(|err| DispatchError::HandleError(err.to_string()))?
+                .ok_or_else(|| {
+                    DispatchError::HandleError(format!(
+                        "Node {} not in routing table",
+                        route[position - 1]
+                    ))
+                })?
+                .get_peer_auth_token(circuit.authorization_type())
+                .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+
+            if source_peer_id.peer_id() != &previous_peer_token {
+                warn!(
+                    "Dropping message on {} from {}: expected message from {}",
+                    circuit.circuit_id(),
+                    source_peer_id,
+                    route[position - 1]
+                );
+                return Ok(None);
+            }
+
+            if msg.get_ttl() == 0 {
+                let mut error_message = CircuitError::new();
+                error_message.set_correlation_id(msg.get_correlation_id().to_string());
+                error_message.set_service_id(msg.get_sender().into());
+                error_message.set_circuit_name(msg.get_circuit().into());
+                error_message.set_error(CircuitError_Error::ERROR_TTL_EXPIRED);
+                error_message.set_error_message(format!(
+                    "Time-to-live expired before message reached recipient: {}",
+                    msg.get_recipient()
+                ));
+
+                let msg_bytes = error_message.write_to_bytes()?;
+                let network_msg_bytes =
+                    create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
+                sender
+                    .send(source_peer_id.clone(), network_msg_bytes)
+                    .map_err(|(recipient, payload)| {
+                        DispatchError::NetworkSendError((recipient.into(), payload))
+                    })?;
+                return Ok(None);
+            }
+
+            msg.set_ttl(msg.get_ttl() - 1);
+        }
+
+        Ok(Some((msg.write_to_bytes()?, route[position + 1].clone())))
+    }
+}
+
+/// The direction of a direct message sent between nodes on a circuit with guaranteed delivery
+#[cfg(feature = "circuit-durability")]
+enum DurableDirection {
+    /// The message is from a local service to a service on the given node
+    Outgoing(String),
+    /// The message is from a service on another node to a local service
+    Incoming,
 }
 
+#[cfg(feature = "circuit-durability")]
 impl CircuitDirectMessageHandler {
-    pub fn new(
-        node_id: String,
-        routing_table: Box<dyn RoutingTableReader>,
-        #[cfg(feature = "service-message-handler-dispatch")] service_dispatcher: ServiceDispatcher,
-    ) -> Self {
-        CircuitDirectMessageHandler {
-            node_id,
-            routing_table,
-            #[cfg(feature = "service-message-handler-dispatch")]
-            service_dispatcher,
+    /// Handles a direct message when guaranteed delivery is enabled.
+    ///
+    /// Outgoing messages on circuits with guaranteed delivery are given a message ID, if they do
+    /// not have one, and are added to the outbox before they are sent. If the message cannot be
+    /// sent, it is resent when the node reconnects. Incoming messages are acknowledged and are
+    /// only delivered if they have not been delivered before.
+    fn handle_durable(
+        &self,
+        mut msg: CircuitDirectMessage,
+        context: &MessageContext<PeerId, CircuitMessageType>,
+        sender: &dyn MessageSender<PeerId>,
+        store: &dyn DurabilityStore,
+    ) -> Result<(), DispatchError> {
+        let circuit_name = msg.get_circuit().to_string();
+
+        match self.durable_direction(&msg)? {
+            Some(DurableDirection::Outgoing(node_id)) => {
+                if msg.get_message_id().is_empty() {
+                    msg.set_message_id(Uuid::new_v4().to_string());
+                }
+
+                let msg_bytes = msg.write_to_bytes()?;
+                let network_msg_bytes = create_message(
+                    msg_bytes.clone(),
+                    CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                )?;
+
+                store
+                    .add_outbox_message(OutboxMessage::new(
+                        msg.get_message_id().to_string(),
+                        circuit_name,
+                        node_id,
+                        network_msg_bytes,
+                    ))
+                    .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+
+                match self.route(msg, msg_bytes, context.source_peer_id(), sender) {
+                    Err(DispatchError::NetworkSendError((recipient, _))) => {
+                        debug!(
+                            "Unable to send message to {}, it will be resent on reconnect",
+                            recipient
+                        );
+                        Ok(())
+                    }
+                    res => res,
+                }
+            }
+            Some(DurableDirection::Incoming) if !msg.get_message_id().is_empty() => {
+                let message_id = msg.get_message_id().to_string();
+
+                if store
+                    .has_received_message(&circuit_name, &message_id)
+                    .map_err(|err| DispatchError::HandleError(err.to_string()))?
+                {
+                    debug!("Dropping duplicate message {}", message_id);
+                } else {
+                    self.route(
+                        msg,
+                        context.message_bytes().to_vec(),
+                        context.source_peer_id(),
+                        sender,
+                    )?;
+
+                    store
+                        .add_received_message(&circuit_name, &message_id)
+                        .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+                }
+
+                let mut ack = CircuitDirectMessageAck::new();
+                ack.set_circuit(circuit_name);
+                ack.set_message_id(message_id);
+                let ack_bytes = create_message(
+                    ack.write_to_bytes()?,
+                    CircuitMessageType::CIRCUIT_DIRECT_MESSAGE_ACK,
+                )?;
+
+                sender
+                    .send(context.source_peer_id().clone(), ack_bytes)
+                    .map_err(|(recipient, payload)| {
+                        DispatchError::NetworkSendError((recipient.into(), payload))
+                    })
+            }
+            _ => self.route(
+                msg,
+                context.message_bytes().to_vec(),
+                context.source_peer_id(),
+                sender,
+            ),
         }
     }
+
+    /// Returns the direction of the message if it is sent between nodes on a circuit with
+    /// guaranteed delivery
+    fn durable_direction(
+        &self,
+        msg: &CircuitDirectMessage,
+    ) -> Result<Option<DurableDirection>, DispatchError> {
+        match self
+            .routing_table
+            .get_circuit(msg.get_circuit())
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            Some(circuit) if circuit.durability() == &DurabilityType::GuaranteedDelivery => (),
+            _ => return Ok(None),
+        }
+
+        let sender_node = self.service_node_id(msg.get_circuit(), msg.get_sender())?;
+        let recipient_node = self.service_node_id(msg.get_circuit(), msg.get_recipient())?;
+
+        match (sender_node, recipient_node) {
+            (Some(sender_node), Some(recipient_node))
+                if sender_node == self.node_id && recipient_node != self.node_id =>
+            {
+                Ok(Some(DurableDirection::Outgoing(recipient_node)))
+            }
+            (Some(sender_node), Some(recipient_node))
+                if sender_node != self.node_id && recipient_node == self.node_id =>
+            {
+                Ok(Some(DurableDirection::Incoming))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn service_node_id(
+        &self,
+        circuit_name: &str,
+        service_id: &str,
+    ) -> Result<Option<String>, DispatchError> {
+        Ok(self
+            .routing_table
+            .get_service(&RoutingServiceId::new(
+                circuit_name.to_string(),
+                service_id.to_string(),
+            ))
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+            .map(|service| service.node_id().to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use std::collections::VecDeque;
+    use std::collections::{BTreeMap, VecDeque};
     use std::sync::{Arc, Mutex};
 
     use crate::circuit::routing::AuthorizationType;
     use crate::circuit::routing::{
-        memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
+        memory::RoutingTable, Circuit, CircuitNode, DurabilityType, RoutingTableWriter, Service,
     };
     use crate::network::dispatch::Dispatcher;
     use crate::peer::PeerAuthorizationToken;
     use crate::protos::circuit::CircuitMessage;
     use crate::protos::network::NetworkMessage;
 
+    #[cfg(all(feature = "circuit-durability", feature = "sqlite"))]
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    #[cfg(all(feature = "circuit-durability", feature = "sqlite"))]
+    use crate::circuit::durability::store::diesel::DieselDurabilityStore;
+    #[cfg(all(feature = "circuit-durability", feature = "sqlite"))]
+    use crate::migrations::run_sqlite_migrations;
     #[cfg(feature = "service-message-handler-dispatch")]
     use crate::runtime::service::{
         NetworkMessageSenderFactory, RoutingTableServiceTypeResolver,
@@ -341,6 +685,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -444,6 +789,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -537,6 +883,7 @@ mod tests {
             vec![service_abc.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -631,6 +978,7 @@ mod tests {
             vec![service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -786,6 +1134,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         writer
@@ -844,6 +1193,112 @@ mod tests {
         )
     }
 
+    // Adds a circuit with relayed routes, where service a0001 is connected to node 345 and
+    // service b0001 is connected to node 678, which can only be reached through node 123
+    fn add_relayed_circuit(writer: &mut dyn RoutingTableWriter) {
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()], None);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()], None);
+        let node_678 = CircuitNode::new("678".to_string(), vec!["123.0.0.1:2".to_string()], None);
+
+        let mut service_def = Service::new(
+            "a0001".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+        let service_ghi = Service::new(
+            "b0001".to_string(),
+            "test".to_string(),
+            "678".to_string(),
+            vec![],
+        );
+
+        service_def.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("def_network"),
+            PeerAuthorizationToken::from_peer_id("345"),
+        ));
+
+        let mut relays = BTreeMap::new();
+        relays.insert("678".to_string(), "123".to_string());
+
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![service_def, service_ghi],
+            vec!["123".into(), "345".into(), "678".into()],
+            AuthorizationType::Trust,
+            DurabilityType::NoDurability,
+        )
+        .with_relays(relays);
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345, node_678],
+            )
+            .expect("Unable to add circuits");
+    }
+
+    // Adds a circuit with guaranteed delivery, where service a0001 is connected to node 345 and
+    // service b0001 is connected to node 123
+    #[cfg(all(feature = "circuit-durability", feature = "sqlite"))]
+    fn add_durable_circuit(writer: &mut dyn RoutingTableWriter) {
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()], None);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()], None);
+
+        let mut service_abc = Service::new(
+            "b0001".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let mut service_def = Service::new(
+            "a0001".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+
+        service_abc.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("abc_network"),
+            PeerAuthorizationToken::from_peer_id("123"),
+        ));
+        service_def.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("def_network"),
+            PeerAuthorizationToken::from_peer_id("345"),
+        ));
+
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![service_abc, service_def],
+            vec!["123".into(), "345".into()],
+            AuthorizationType::Trust,
+            DurabilityType::GuaranteedDelivery,
+        );
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+    }
+
+    #[cfg(all(feature = "circuit-durability", feature = "sqlite"))]
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+
     fn assert_network_message<M: protobuf::Message, F: Fn(M)>(
         message: Vec<u8>,
         recipient: PeerTokenPair,
@@ -899,6 +1354,350 @@ mod tests {
         )
     }
 
+    // Test that a direct message sent by a local service on a circuit with relayed routes is sent
+    // to the relay node of the recipient's node, with its time-to-live set
+    #[test]
+    fn test_circuit_direct_message_handler_relay_origin() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        add_relayed_circuit(&mut *writer);
+
+        let handler = CircuitDirectMessageHandler::new(
+            "345".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        );
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"test".to_vec());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("def_network"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("123"),
+                PeerAuthorizationToken::from_peer_id("345"),
+            ),
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_sender(), "a0001");
+                assert_eq!(msg.get_recipient(), "b0001");
+                assert_eq!(msg.get_payload().to_vec(), b"test".to_vec());
+                assert_eq!(msg.get_ttl(), 3);
+            },
+        )
+    }
+
+    // Test that a relay node forwards a direct message received from the previous node on the
+    // route to the next node, decrementing the message's time-to-live
+    #[test]
+    fn test_circuit_direct_message_handler_relay_forward() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        add_relayed_circuit(&mut *writer);
+
+        let handler = CircuitDirectMessageHandler::new(
+            "123".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        );
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_ttl(3);
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("345"),
+                    PeerAuthorizationToken::from_peer_id("123"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("678"),
+                PeerAuthorizationToken::from_peer_id("123"),
+            ),
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_sender(), "a0001");
+                assert_eq!(msg.get_recipient(), "b0001");
+                assert_eq!(msg.get_ttl(), 2);
+            },
+        )
+    }
+
+    // Test that a relay node does not forward messages it should not relay
+    //
+    // 1. Check that a message received from a node that is not the previous node on the route is
+    //    dropped
+    // 2. Check that a message whose time-to-live has expired is dropped and an error is returned
+    //    to the node it was received from
+    #[test]
+    fn test_circuit_direct_message_handler_relay_rejected() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        add_relayed_circuit(&mut *writer);
+
+        let handler = CircuitDirectMessageHandler::new(
+            "123".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        );
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_ttl(3);
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("678"),
+                    PeerAuthorizationToken::from_peer_id("123"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        assert!(mock_sender.next_outbound().is_none());
+
+        direct_message.set_ttl(0);
+        let node_peer_id = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("345"),
+            PeerAuthorizationToken::from_peer_id("123"),
+        );
+
+        dispatcher
+            .dispatch(
+                node_peer_id.clone().into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            node_peer_id,
+            CircuitMessageType::CIRCUIT_ERROR_MESSAGE,
+            |msg: CircuitError| {
+                assert_eq!(msg.get_service_id(), "a0001");
+                assert_eq!(msg.get_error(), CircuitError_Error::ERROR_TTL_EXPIRED);
+            },
+        );
+        assert!(mock_sender.next_outbound().is_none());
+    }
+
+    // Test that a direct message sent by a local service on a circuit with guaranteed delivery is
+    // given a message ID, added to the outbox and sent to the node the recipient service is
+    // connected to
+    #[cfg(all(feature = "circuit-durability", feature = "sqlite"))]
+    #[test]
+    fn test_circuit_direct_message_handler_durable_outgoing() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let store = DieselDurabilityStore::new(create_connection_pool_and_migrate());
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        add_durable_circuit(&mut *writer);
+
+        let handler = CircuitDirectMessageHandler::new(
+            "345".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        )
+        .with_durability_store(Box::new(store.clone()));
+
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"test".to_vec());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("def_network"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+
+        let outbox = store
+            .list_outbox_messages("123")
+            .expect("Unable to list outbox");
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].circuit_id(), "Alpha-00000");
+        assert_eq!(outbox[0].payload(), &message[..]);
+
+        let message_id = outbox[0].message_id().to_string();
+        assert!(!message_id.is_empty());
+
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("123"),
+                PeerAuthorizationToken::from_peer_id("345"),
+            ),
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_sender(), "a0001");
+                assert_eq!(msg.get_recipient(), "b0001");
+                assert_eq!(msg.get_payload().to_vec(), b"test".to_vec());
+                assert_eq!(msg.get_message_id(), message_id);
+            },
+        )
+    }
+
+    // Test that a direct message received from another node on a circuit with guaranteed
+    // delivery is delivered to the local service once and is acknowledged each time it is
+    // received
+    #[cfg(all(feature = "circuit-durability", feature = "sqlite"))]
+    #[test]
+    fn test_circuit_direct_message_handler_durable_incoming() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let store = DieselDurabilityStore::new(create_connection_pool_and_migrate());
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        add_durable_circuit(&mut *writer);
+
+        let handler = CircuitDirectMessageHandler::new(
+            "123".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        )
+        .with_durability_store(Box::new(store.clone()));
+
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_message_id("message-1".into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        let node_peer_id = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("345"),
+            PeerAuthorizationToken::from_peer_id("123"),
+        );
+
+        // dispatch the message twice, as if it was resent after a reconnect
+        for _ in 0..2 {
+            dispatcher
+                .dispatch(
+                    node_peer_id.clone().into(),
+                    &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                    direct_bytes.clone(),
+                )
+                .unwrap();
+        }
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("abc_network"),
+                PeerAuthorizationToken::from_peer_id("123"),
+            ),
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_recipient(), "b0001");
+                assert_eq!(msg.get_message_id(), "message-1");
+            },
+        );
+
+        for _ in 0..2 {
+            let (id, message) = mock_sender.next_outbound().expect("No ack was sent");
+            assert_network_message(
+                message,
+                id.into(),
+                node_peer_id.clone(),
+                CircuitMessageType::CIRCUIT_DIRECT_MESSAGE_ACK,
+                |msg: CircuitDirectMessageAck| {
+                    assert_eq!(msg.get_circuit(), "Alpha-00000");
+                    assert_eq!(msg.get_message_id(), "message-1");
+                },
+            );
+        }
+
+        assert!(mock_sender.next_outbound().is_none());
+        assert!(store
+            .has_received_message("Alpha-00000", "message-1")
+            .expect("Unable to check received message"));
+    }
+
     #[derive(Clone)]
     struct MockSender {
         outbound: Arc<Mutex<VecDeque<(PeerId, Vec<u8>)>>>,
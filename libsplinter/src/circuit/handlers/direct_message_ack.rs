@@ -0,0 +1,299 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::circuit::durability::store::DurabilityStore;
+use crate::circuit::routing::RoutingTableReader;
+use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
+use crate::peer::PeerTokenPair;
+use crate::protos::circuit::{CircuitDirectMessageAck, CircuitMessageType};
+
+// Implements a handler that removes acknowledged messages from the outbox. A message is only
+// removed if the ack was sent by the node the message was sent to.
+pub struct CircuitDirectMessageAckHandler {
+    node_id: String,
+    routing_table: Box<dyn RoutingTableReader>,
+    store: Box<dyn DurabilityStore>,
+}
+
+impl Handler for CircuitDirectMessageAckHandler {
+    type Source = PeerId;
+    type MessageType = CircuitMessageType;
+    type Message = CircuitDirectMessageAck;
+
+    fn match_type(&self) -> Self::MessageType {
+        CircuitMessageType::CIRCUIT_DIRECT_MESSAGE_ACK
+    }
+
+    fn handle(
+        &self,
+        msg: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        _: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        debug!(
+            "Handle Circuit Direct Message Ack {} on {} from {}",
+            msg.get_message_id(),
+            msg.get_circuit(),
+            context.source_peer_id()
+        );
+
+        let node_id = match self.source_node_id(msg.get_circuit(), context.source_peer_id())? {
+            Some(node_id) => node_id,
+            None => {
+                warn!(
+                    "Ignoring ack for message {} on {} from {}, peer is not a member of the \
+                     circuit",
+                    msg.get_message_id(),
+                    msg.get_circuit(),
+                    context.source_peer_id()
+                );
+                return Ok(());
+            }
+        };
+
+        self.store
+            .remove_outbox_message(msg.get_circuit(), msg.get_message_id(), &node_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))
+    }
+}
+
+impl CircuitDirectMessageAckHandler {
+    pub fn new(
+        node_id: String,
+        routing_table: Box<dyn RoutingTableReader>,
+        store: Box<dyn DurabilityStore>,
+    ) -> Self {
+        CircuitDirectMessageAckHandler {
+            node_id,
+            routing_table,
+            store,
+        }
+    }
+
+    /// Returns the ID of the circuit member the peer belongs to, if any
+    fn source_node_id(
+        &self,
+        circuit_name: &str,
+        source_peer_id: &PeerTokenPair,
+    ) -> Result<Option<String>, DispatchError> {
+        let circuit = match self
+            .routing_table
+            .get_circuit(circuit_name)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            Some(circuit) => circuit,
+            None => return Ok(None),
+        };
+
+        let local_peer_id = match self
+            .routing_table
+            .get_node(&self.node_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            Some(node) => node
+                .get_peer_auth_token(circuit.authorization_type())
+                .map_err(|err| DispatchError::HandleError(err.to_string()))?,
+            None => return Ok(None),
+        };
+
+        for member in circuit.members() {
+            if member == &self.node_id {
+                continue;
+            }
+
+            if let Some(node) = self
+                .routing_table
+                .get_node(member)
+                .map_err(|err| DispatchError::HandleError(err.to_string()))?
+            {
+                let peer_id = node
+                    .get_peer_auth_token(circuit.authorization_type())
+                    .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+                if &PeerTokenPair::new(peer_id, local_peer_id.clone()) == source_peer_id {
+                    return Ok(Some(member.to_string()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use protobuf::Message;
+
+    use crate::circuit::durability::store::{diesel::DieselDurabilityStore, OutboxMessage};
+    use crate::circuit::routing::{
+        memory::RoutingTable, AuthorizationType, Circuit, CircuitNode, DurabilityType,
+        RoutingTableWriter,
+    };
+    use crate::migrations::run_sqlite_migrations;
+    use crate::network::dispatch::Dispatcher;
+    use crate::peer::{PeerAuthorizationToken, PeerTokenPair};
+
+    // Test that an acknowledged message is removed from the outbox and that other messages are
+    // left in the outbox
+    #[test]
+    fn test_circuit_direct_message_ack_handler() {
+        let store = DieselDurabilityStore::new(create_connection_pool_and_migrate());
+        let mut dispatcher = Dispatcher::new(Box::new(MockSender));
+        let routing_table = create_routing_table();
+
+        for message_id in &["message-1", "message-2"] {
+            store
+                .add_outbox_message(OutboxMessage::new(
+                    message_id.to_string(),
+                    "Alpha-00000".into(),
+                    "123".into(),
+                    b"test".to_vec(),
+                ))
+                .expect("Unable to add message");
+        }
+
+        dispatcher.set_handler(Box::new(CircuitDirectMessageAckHandler::new(
+            "345".into(),
+            Box::new(routing_table),
+            Box::new(store.clone()),
+        )));
+
+        let mut ack = CircuitDirectMessageAck::new();
+        ack.set_circuit("Alpha-00000".into());
+        ack.set_message_id("message-1".into());
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("123"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE_ACK,
+                ack.write_to_bytes().expect("Unable to serialize ack"),
+            )
+            .expect("Unable to dispatch ack");
+
+        let messages = store
+            .list_outbox_messages("123")
+            .expect("Unable to list messages");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].message_id(), "message-2");
+    }
+
+    // Test that an ack from a node other than the one the message was sent to does not remove
+    // the message from the outbox
+    #[test]
+    fn test_circuit_direct_message_ack_handler_wrong_node() {
+        let store = DieselDurabilityStore::new(create_connection_pool_and_migrate());
+        let mut dispatcher = Dispatcher::new(Box::new(MockSender));
+        let routing_table = create_routing_table();
+
+        store
+            .add_outbox_message(OutboxMessage::new(
+                "message-1".into(),
+                "Alpha-00000".into(),
+                "123".into(),
+                b"test".to_vec(),
+            ))
+            .expect("Unable to add message");
+
+        dispatcher.set_handler(Box::new(CircuitDirectMessageAckHandler::new(
+            "345".into(),
+            Box::new(routing_table),
+            Box::new(store.clone()),
+        )));
+
+        let mut ack = CircuitDirectMessageAck::new();
+        ack.set_circuit("Alpha-00000".into());
+        ack.set_message_id("message-1".into());
+
+        for peer_id in &["678", "unknown"] {
+            dispatcher
+                .dispatch(
+                    PeerTokenPair::new(
+                        PeerAuthorizationToken::from_peer_id(peer_id),
+                        PeerAuthorizationToken::from_peer_id("345"),
+                    )
+                    .into(),
+                    &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE_ACK,
+                    ack.write_to_bytes().expect("Unable to serialize ack"),
+                )
+                .expect("Unable to dispatch ack");
+        }
+
+        assert_eq!(
+            store
+                .list_outbox_messages("123")
+                .expect("Unable to list messages")
+                .len(),
+            1
+        );
+    }
+
+    /// Creates a routing table with a circuit between the local node, `345`, and the nodes `123`
+    /// and `678`
+    fn create_routing_table() -> RoutingTable {
+        let table = RoutingTable::default();
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let nodes = ["123", "345", "678"]
+            .iter()
+            .enumerate()
+            .map(|(i, node_id)| {
+                CircuitNode::new(node_id.to_string(), vec![format!("127.0.0.1:{}", i)], None)
+            })
+            .collect();
+
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![],
+            vec!["123".into(), "345".into(), "678".into()],
+            AuthorizationType::Trust,
+            DurabilityType::GuaranteedDelivery,
+        );
+
+        writer
+            .add_circuit(circuit.circuit_id().into(), circuit, nodes)
+            .expect("Unable to add circuit");
+
+        table
+    }
+
+    struct MockSender;
+
+    impl MessageSender<PeerId> for MockSender {
+        fn send(&self, _: PeerId, _: Vec<u8>) -> Result<(), (PeerId, Vec<u8>)> {
+            Ok(())
+        }
+    }
+
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -16,6 +16,8 @@ mod admin_message;
 mod circuit_error;
 mod circuit_message;
 mod direct_message;
+#[cfg(feature = "circuit-durability")]
+mod direct_message_ack;
 mod service_handlers;
 
 use protobuf::Message;
@@ -27,6 +29,8 @@ pub use self::admin_message::AdminDirectMessageHandler;
 pub use self::circuit_error::CircuitErrorHandler;
 pub use self::circuit_message::CircuitMessageHandler;
 pub use self::direct_message::CircuitDirectMessageHandler;
+#[cfg(feature = "circuit-durability")]
+pub use self::direct_message_ack::CircuitDirectMessageAckHandler;
 pub use self::service_handlers::ServiceConnectRequestHandler;
 pub use self::service_handlers::ServiceDisconnectRequestHandler;
 
@@ -289,7 +289,7 @@ mod tests {
 
     use crate::circuit::routing::AuthorizationType;
     use crate::circuit::routing::{
-        memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
+        memory::RoutingTable, Circuit, CircuitNode, DurabilityType, RoutingTableWriter, Service,
     };
     use crate::network::dispatch::Dispatcher;
     use crate::peer::PeerAuthorizationToken;
@@ -793,6 +793,7 @@ mod tests {
             vec![service_abc.clone(), service_def.clone()],
             vec!["123".into(), "345".into()],
             AuthorizationType::Trust,
+            DurabilityType::NoDurability,
         );
 
         (circuit, vec![node_123, node_345])
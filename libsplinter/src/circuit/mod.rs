@@ -16,6 +16,8 @@
 
 #[cfg(feature = "service-network")]
 pub mod component;
+#[cfg(feature = "circuit-durability")]
+pub mod durability;
 pub mod handlers;
 pub mod routing;
 #[cfg(feature = "circuit-template")]
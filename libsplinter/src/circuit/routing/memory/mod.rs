@@ -26,7 +26,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
 
 use super::error::RoutingTableReaderError;
-use super::AuthorizationType;
+use super::{AuthorizationType, DurabilityType};
 use super::{
     Circuit, CircuitIter, CircuitNode, CircuitNodeIter, RoutingTableReader, RoutingTableWriter,
     Service, ServiceId,
@@ -179,6 +179,7 @@ impl RoutingTableReader for RoutingTable {
                 vec![],
                 vec![],
                 AuthorizationType::Trust,
+                DurabilityType::NoDurability,
             )))
         } else {
             Ok(self
@@ -396,7 +397,7 @@ impl RoutingTableWriter for RoutingTable {
 mod test {
     use super::*;
 
-    use crate::circuit::routing::AuthorizationType;
+    use crate::circuit::routing::{AuthorizationType, DurabilityType};
 
     // Test the routing table read and write operations for circuits
     //
@@ -448,12 +449,16 @@ mod test {
             roster: circuit_roster0.clone(),
             members: circuit_members0.clone(),
             authorization_type: AuthorizationType::Trust,
+            durability: DurabilityType::NoDurability,
+            relays: BTreeMap::new(),
         };
         let circuit1 = Circuit {
             circuit_id: "345-def".to_string(),
             roster: circuit_roster1.clone(),
             members: circuit_members1.clone(),
             authorization_type: AuthorizationType::Trust,
+            durability: DurabilityType::NoDurability,
+            relays: BTreeMap::new(),
         };
 
         let mut expected_nodes = BTreeMap::new();
@@ -606,6 +611,8 @@ mod test {
             roster: vec![service0.clone(), service1.clone()],
             members: vec![node0.node_id.clone(), node1.node_id.clone()],
             authorization_type: AuthorizationType::Trust,
+            durability: DurabilityType::NoDurability,
+            relays: BTreeMap::new(),
         };
         let service_id0 = ServiceId::new(
             "012-abc".to_string(),
@@ -37,6 +37,7 @@ mod error;
 pub mod memory;
 
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt;
 
 pub use self::error::RoutingTableReaderError;
@@ -190,6 +191,8 @@ pub struct Circuit {
     roster: Vec<Service>,
     members: Vec<String>,
     authorization_type: AuthorizationType,
+    durability: DurabilityType,
+    relays: BTreeMap<String, String>,
 }
 
 impl Circuit {
@@ -201,20 +204,37 @@ impl Circuit {
     /// * `roster` - The list of services in the circuit
     /// * `members` - The list of node IDs for the members of a circuit
     /// * `authorization_type` - The authorization type used for the circuit
+    /// * `durability` - The durability type used for messages sent on the circuit
     pub fn new(
         circuit_id: String,
         roster: Vec<Service>,
         members: Vec<String>,
         authorization_type: AuthorizationType,
+        durability: DurabilityType,
     ) -> Self {
         Circuit {
             circuit_id,
             roster,
             members,
             authorization_type,
+            durability,
+            relays: BTreeMap::new(),
         }
     }
 
+    /// Sets the relay nodes of a circuit that uses relayed routes.
+    ///
+    /// Each entry maps the ID of a member node to the ID of the member that relays messages to
+    /// and from it. Members without a relay are expected to be directly connected to each other.
+    ///
+    /// # Arguments
+    ///
+    /// * `relays` - The relay node ID for each member node that has one
+    pub fn with_relays(mut self, relays: BTreeMap<String, String>) -> Self {
+        self.relays = relays;
+        self
+    }
+
     /// Returns the ID of the circuit
     pub fn circuit_id(&self) -> &str {
         &self.circuit_id
@@ -234,6 +254,78 @@ impl Circuit {
     pub fn authorization_type(&self) -> &AuthorizationType {
         &self.authorization_type
     }
+
+    /// Returns the durability type
+    pub fn durability(&self) -> &DurabilityType {
+        &self.durability
+    }
+
+    /// Returns the relay node IDs, keyed by the member node they relay messages for
+    pub fn relays(&self) -> &BTreeMap<String, String> {
+        &self.relays
+    }
+
+    /// Returns true if messages on the circuit may be forwarded through relay nodes
+    pub fn is_relayed(&self) -> bool {
+        !self.relays.is_empty()
+    }
+
+    /// Returns the ID of the node a message must be sent to next, in order to reach the target
+    /// node from the local node.
+    ///
+    /// # Arguments
+    ///
+    /// * `local_node_id` - The ID of the node sending the message
+    /// * `target_node_id` - The ID of the node the message is for
+    pub fn next_hop(&self, local_node_id: &str, target_node_id: &str) -> Option<String> {
+        self.route(local_node_id, target_node_id)
+            .get(1)
+            .map(|node_id| node_id.to_string())
+    }
+
+    /// Returns the list of node IDs a message passes through from the source node to the target
+    /// node, including both the source and the target.
+    ///
+    /// Nodes form a tree through their relays; a message travels up from the source until it
+    /// reaches a relay the target is behind and then travels down to the target. Nodes without a
+    /// relay are connected directly.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_node_id` - The ID of the node the message originates from
+    /// * `target_node_id` - The ID of the node the message is for
+    pub fn route(&self, source_node_id: &str, target_node_id: &str) -> Vec<String> {
+        let source_chain = self.relay_chain(source_node_id);
+        let target_chain = self.relay_chain(target_node_id);
+
+        let mut route = vec![];
+        for node_id in source_chain.iter() {
+            route.push(node_id.to_string());
+            if let Some(index) = target_chain.iter().position(|id| id == node_id) {
+                route.extend(target_chain[..index].iter().rev().map(|id| id.to_string()));
+                return route;
+            }
+        }
+
+        // no relay in common, the top-most relays are connected directly
+        route.extend(target_chain.iter().rev().map(|id| id.to_string()));
+        route
+    }
+
+    /// Returns the node followed by its relay, the relay's relay and so on.
+    fn relay_chain<'a>(&'a self, node_id: &'a str) -> Vec<&'a str> {
+        let mut chain = vec![node_id];
+        let mut current = node_id;
+        while let Some(relay) = self.relays.get(current) {
+            // relays are validated to be acyclic, but guard against looping regardless
+            if chain.contains(&relay.as_str()) || chain.len() > self.members.len() {
+                break;
+            }
+            chain.push(relay);
+            current = relay;
+        }
+        chain
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -242,6 +334,15 @@ pub enum AuthorizationType {
     Challenge,
 }
 
+/// How messages sent between the nodes of a circuit are delivered
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DurabilityType {
+    /// Messages are dropped if the connection to the receiving node is not available
+    NoDurability,
+    /// Messages are stored until the receiving node acknowledges them
+    GuaranteedDelivery,
+}
+
 /// The routing table representation of a node
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CircuitNode {
@@ -429,3 +530,76 @@ impl fmt::Display for ServiceId {
 }
 
 impl Eq for ServiceId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that routes are computed through the relay nodes of a circuit:
+    //
+    //   hub_a --- hub_b
+    //     |         |
+    //   node_a    node_b
+    //     |
+    //   node_c
+    //
+    // 1. Check that a node without a relay is routed to directly
+    // 2. Check that a message from a node behind a relay is sent up to its relay
+    // 3. Check that a message to a node behind a relay is sent down through the relays
+    // 4. Check that a message between two different relays passes through both relays
+    #[test]
+    fn test_circuit_relay_routes() {
+        let mut relays = BTreeMap::new();
+        relays.insert("node_a".to_string(), "hub_a".to_string());
+        relays.insert("node_b".to_string(), "hub_b".to_string());
+        relays.insert("node_c".to_string(), "node_a".to_string());
+
+        let circuit = Circuit::new(
+            "abcde-12345".to_string(),
+            vec![],
+            vec![
+                "hub_a".to_string(),
+                "hub_b".to_string(),
+                "node_a".to_string(),
+                "node_b".to_string(),
+                "node_c".to_string(),
+            ],
+            AuthorizationType::Trust,
+            DurabilityType::NoDurability,
+        )
+        .with_relays(relays);
+
+        assert!(circuit.is_relayed());
+
+        assert_eq!(circuit.route("hub_a", "hub_b"), vec!["hub_a", "hub_b"]);
+        assert_eq!(
+            circuit.next_hop("hub_a", "hub_b"),
+            Some("hub_b".to_string())
+        );
+
+        assert_eq!(circuit.route("node_a", "hub_a"), vec!["node_a", "hub_a"]);
+        assert_eq!(
+            circuit.route("node_c", "hub_a"),
+            vec!["node_c", "node_a", "hub_a"]
+        );
+
+        assert_eq!(
+            circuit.route("hub_a", "node_c"),
+            vec!["hub_a", "node_a", "node_c"]
+        );
+        assert_eq!(
+            circuit.next_hop("hub_a", "node_c"),
+            Some("node_a".to_string())
+        );
+
+        assert_eq!(
+            circuit.route("node_c", "node_b"),
+            vec!["node_c", "node_a", "hub_a", "hub_b", "node_b"]
+        );
+        assert_eq!(
+            circuit.next_hop("hub_a", "node_b"),
+            Some("hub_b".to_string())
+        );
+        assert_eq!(circuit.next_hop("node_b", "node_b"), None);
+    }
+}
@@ -17,6 +17,8 @@ use std::sync::mpsc::SendError;
 
 use protobuf::error::ProtobufError;
 
+use crate::error::InternalError;
+
 use super::{PeerId, ProposalId, ProposalUpdate};
 
 #[derive(Debug)]
@@ -138,3 +140,10 @@ impl From<ConsensusSendError> for ConsensusEngineError {
         ConsensusEngineError(Box::new(err))
     }
 }
+
+impl From<InternalError> for ConsensusEngineError {
+    fn from(err: InternalError) -> Self {
+        // `InternalError` is not `Send`, so only its message is kept
+        ConsensusEngineError(Box::<dyn Error + Send + Sync>::from(err.to_string()))
+    }
+}
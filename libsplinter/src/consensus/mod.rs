@@ -15,6 +15,8 @@
 //! The API that defines interactions between consensus and a Splinter service.
 
 pub mod error;
+#[cfg(feature = "consensus-raft")]
+pub mod raft;
 pub mod two_phase;
 
 use std::convert::{TryFrom, TryInto};
@@ -175,6 +177,30 @@ pub trait ProposalManager: Send {
 
     /// Consensus has rejected the given proposal.
     fn reject_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError>;
+
+    /// Returns the data of a proposal known to this manager, for consensus algorithms that
+    /// replicate or persist the data along with the proposal.
+    ///
+    /// The default implementation returns `None`, since most managers share the data of their
+    /// proposals themselves.
+    fn get_proposal_data(&self, _id: &ProposalId) -> Result<Option<Vec<u8>>, ProposalManagerError> {
+        Ok(None)
+    }
+
+    /// Provides the data of a proposal that was replicated or persisted by consensus, so that the
+    /// proposal can be checked even if the manager never received it from the peer that created
+    /// it, or lost it when it was restarted. The data should be ignored if the manager already has
+    /// the proposal.
+    ///
+    /// The default implementation does nothing, since most managers share the data of their
+    /// proposals themselves.
+    fn add_proposal_data(
+        &self,
+        _proposal: &Proposal,
+        _data: Vec<u8>,
+    ) -> Result<(), ProposalManagerError> {
+        Ok(())
+    }
 }
 
 /// Messages the `ProposalManager` sends to consensus
@@ -308,6 +334,8 @@ pub mod tests {
         next_proposal_valid: Arc<AtomicBool>,
         return_proposal: Arc<AtomicBool>,
         consensus_data: Option<Vec<u8>>,
+        require_proposal_data: Arc<AtomicBool>,
+        added_proposal_data: Arc<Mutex<Vec<(ProposalId, Vec<u8>)>>>,
     }
 
     impl Clone for MockProposalManager {
@@ -323,6 +351,8 @@ pub mod tests {
                 next_proposal_valid: self.next_proposal_valid.clone(),
                 return_proposal: self.return_proposal.clone(),
                 consensus_data: self.consensus_data.clone(),
+                require_proposal_data: self.require_proposal_data.clone(),
+                added_proposal_data: self.added_proposal_data.clone(),
             }
         }
     }
@@ -338,6 +368,8 @@ pub mod tests {
                 next_proposal_valid: Arc::new(AtomicBool::new(true)),
                 return_proposal: Arc::new(AtomicBool::new(true)),
                 consensus_data: None,
+                require_proposal_data: Arc::new(AtomicBool::new(false)),
+                added_proposal_data: Arc::new(Mutex::new(vec![])),
             }
         }
 
@@ -354,6 +386,17 @@ pub mod tests {
             self.consensus_data = data;
         }
 
+        /// If set, proposals are only known to the manager once their data has been added.
+        pub fn set_require_proposal_data(&self, require: bool) {
+            self.require_proposal_data.store(require, Ordering::Relaxed);
+        }
+
+        pub fn added_proposal_data(&self) -> MutexGuard<Vec<(ProposalId, Vec<u8>)>> {
+            self.added_proposal_data
+                .lock()
+                .expect("failed to get added proposal data")
+        }
+
         pub fn accepted_proposals(&self) -> MutexGuard<Vec<(ProposalId, Vec<u8>)>> {
             self.accepted_proposals
                 .lock()
@@ -406,6 +449,15 @@ pub mod tests {
         }
 
         fn check_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError> {
+            if self.require_proposal_data.load(Ordering::Relaxed)
+                && !self
+                    .added_proposal_data()
+                    .iter()
+                    .any(|(added_id, _)| added_id == id)
+            {
+                return Err(ProposalManagerError::UnknownProposal(id.clone()));
+            }
+
             if self.next_proposal_valid.load(Ordering::Relaxed) {
                 self.update_sender
                     .send(ProposalUpdate::ProposalValid(id.clone()))
@@ -438,6 +490,22 @@ pub mod tests {
                 .push(id.clone());
             Ok(())
         }
+
+        fn get_proposal_data(
+            &self,
+            id: &ProposalId,
+        ) -> Result<Option<Vec<u8>>, ProposalManagerError> {
+            Ok(Some(id.clone().into()))
+        }
+
+        fn add_proposal_data(
+            &self,
+            proposal: &Proposal,
+            data: Vec<u8>,
+        ) -> Result<(), ProposalManagerError> {
+            self.added_proposal_data().push((proposal.id.clone(), data));
+            Ok(())
+        }
     }
 
     #[derive(Clone)]
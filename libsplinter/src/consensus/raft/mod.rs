@@ -0,0 +1,1377 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A crash-fault-tolerant consensus algorithm based on Raft, implemented as a `ConsensusEngine`
+//!
+//! The nodes elect a leader, which appends proposals to a log that it replicates to the other
+//! nodes. Once an entry has been stored by a majority of the nodes it is committed, and every node
+//! applies the committed entries in log order: the entry's proposal is checked by the proposal
+//! manager, then accepted if it is valid or rejected if it is not. Since every node applies the
+//! same entries in the same order, all nodes reach the same result. Unlike two-phase commit, a
+//! minority of the nodes may be offline without stopping the network from making progress.
+//!
+//! Each entry carries the data of its proposal, as provided by the proposal manager of the node
+//! that created it. Before an entry is applied, the data is given to the proposal manager, so a
+//! node that was offline when a proposal was created can still apply it once it catches up.
+//!
+//! Any node may create proposals. A follower forwards the proposals it creates to the leader,
+//! which appends them to the log. A node only asks the proposal manager for a proposal once it
+//! has applied every entry in its log, so that proposals are built on the latest state.
+//!
+//! The current term, the vote for that term, the index of the last applied entry and the log are
+//! saved in a [`RaftStore`], so a node that restarts keeps its place in the network.
+//!
+//! # Known limitations
+//!
+//! - The log is never compacted and there are no snapshots; a node that falls behind is sent all
+//!   of the entries it is missing.
+//! - The members of the network are fixed to the peers provided in the startup state.
+//! - If the proposal manager does not provide the data of its proposals, the data must be made
+//!   available to the other nodes' proposal managers by other means before an entry can be
+//!   applied.
+
+mod store;
+
+use std::cmp::min;
+use std::collections::{HashMap, HashSet};
+use std::convert::{TryFrom, TryInto};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use protobuf::error::ProtobufError;
+use protobuf::{Message, RepeatedField};
+use rand::Rng;
+
+use crate::consensus::{
+    ConsensusEngine, ConsensusEngineError, ConsensusMessage, ConsensusNetworkSender, PeerId,
+    Proposal, ProposalId, ProposalManager, ProposalManagerError, ProposalUpdate, StartupState,
+};
+use crate::protos::raft::{
+    AppendEntries, AppendEntriesResponse, RaftLogEntry as RaftLogEntryProto, RaftMessage,
+    RaftMessage_Type, RequestVote, RequestVoteResponse,
+};
+
+pub use self::store::{HardState, MemoryRaftStore, RaftLogEntry, RaftStore};
+
+const MESSAGE_RECV_TIMEOUT_MILLIS: u64 = 100;
+const PROPOSAL_RECV_TIMEOUT_MILLIS: u64 = 100;
+const DEFAULT_ELECTION_TIMEOUT_MILLIS: u64 = 2000;
+const DEFAULT_HEARTBEAT_INTERVAL_MILLIS: u64 = 500;
+/// The maximum number of entries sent to a follower in a single message
+const MAX_ENTRIES_PER_MESSAGE: usize = 64;
+
+#[derive(Debug, PartialEq)]
+enum Role {
+    Follower,
+    Candidate,
+    Leader,
+}
+
+/// A proposal created by this node that has not yet been appended to the log
+struct OwnProposal {
+    proposal: Proposal,
+    data: Vec<u8>,
+    /// The leader the proposal was last forwarded to, and when
+    forwarded: Option<(PeerId, Instant)>,
+}
+
+pub struct RaftEngine {
+    id: PeerId,
+    peers: HashSet<PeerId>,
+    store: Box<dyn RaftStore>,
+    role: Role,
+    hard_state: HardState,
+    log: Vec<RaftLogEntry>,
+    commit_index: u64,
+    leader_id: Option<PeerId>,
+    votes_received: HashSet<PeerId>,
+    next_index: HashMap<PeerId, u64>,
+    match_index: HashMap<PeerId, u64>,
+    election_timeout: Duration,
+    heartbeat_interval: Duration,
+    election_deadline: Instant,
+    next_heartbeat: Instant,
+    /// The proposal of the entry that is being applied, which is waiting to be checked
+    applying: Option<ProposalId>,
+    awaiting_proposal: bool,
+    own_proposal: Option<OwnProposal>,
+}
+
+impl RaftEngine {
+    /// Creates a new engine that saves its state in the given store, using the default election
+    /// timeout (2 seconds) and heartbeat interval (500 milliseconds).
+    pub fn new(store: Box<dyn RaftStore>) -> Self {
+        Self::new_with_timeouts(
+            store,
+            Duration::from_millis(DEFAULT_ELECTION_TIMEOUT_MILLIS),
+            Duration::from_millis(DEFAULT_HEARTBEAT_INTERVAL_MILLIS),
+        )
+    }
+
+    /// Creates a new engine with the given election timeout and heartbeat interval.
+    ///
+    /// A follower starts an election if it has not heard from a leader for a random duration
+    /// between the election timeout and twice the election timeout. The heartbeat interval should
+    /// be well below the election timeout.
+    pub fn new_with_timeouts(
+        store: Box<dyn RaftStore>,
+        election_timeout: Duration,
+        heartbeat_interval: Duration,
+    ) -> Self {
+        RaftEngine {
+            id: PeerId::default(),
+            peers: HashSet::new(),
+            store,
+            role: Role::Follower,
+            hard_state: HardState::default(),
+            log: Vec::new(),
+            commit_index: 0,
+            leader_id: None,
+            votes_received: HashSet::new(),
+            next_index: HashMap::new(),
+            match_index: HashMap::new(),
+            election_timeout,
+            heartbeat_interval,
+            election_deadline: Instant::now(),
+            next_heartbeat: Instant::now(),
+            applying: None,
+            awaiting_proposal: false,
+            own_proposal: None,
+        }
+    }
+
+    fn last_index(&self) -> u64 {
+        self.log.last().map(|entry| entry.index).unwrap_or(0)
+    }
+
+    fn last_term(&self) -> u64 {
+        self.log.last().map(|entry| entry.term).unwrap_or(0)
+    }
+
+    /// Returns the term of the entry at the given index; index 0 is the start of the log.
+    fn term_at(&self, index: u64) -> Option<u64> {
+        if index == 0 {
+            Some(0)
+        } else {
+            self.log.get(index as usize - 1).map(|entry| entry.term)
+        }
+    }
+
+    /// The number of nodes, including this one, that make up a majority of the network
+    fn quorum(&self) -> usize {
+        (self.peers.len() + 1) / 2 + 1
+    }
+
+    fn reset_election_deadline(&mut self) {
+        let jitter = rand::thread_rng().gen_range(0..=self.election_timeout.as_millis() as u64);
+        self.election_deadline =
+            Instant::now() + self.election_timeout + Duration::from_millis(jitter);
+    }
+
+    fn save_hard_state(&self) -> Result<(), ConsensusEngineError> {
+        Ok(self.store.set_hard_state(&self.hard_state)?)
+    }
+
+    /// Adds the entries to the end of the log, both in memory and in the store.
+    fn add_entries(&mut self, entries: Vec<RaftLogEntry>) -> Result<(), ConsensusEngineError> {
+        self.store.append_entries(&entries)?;
+
+        if let Some(own_proposal) = &self.own_proposal {
+            let logged = entries.iter().any(|entry| match &entry.proposal {
+                Some(proposal) => proposal.id == own_proposal.proposal.id,
+                None => false,
+            });
+            if logged {
+                self.own_proposal = None;
+            }
+        }
+
+        self.log.extend(entries);
+
+        Ok(())
+    }
+
+    fn become_follower(
+        &mut self,
+        term: u64,
+        leader_id: Option<PeerId>,
+    ) -> Result<(), ConsensusEngineError> {
+        if term > self.hard_state.term {
+            self.hard_state.term = term;
+            self.hard_state.voted_for = None;
+            self.save_hard_state()?;
+        }
+
+        if self.role != Role::Follower {
+            debug!("Becoming follower in term {}", term);
+        }
+
+        self.role = Role::Follower;
+        self.leader_id = leader_id;
+        self.votes_received.clear();
+        self.reset_election_deadline();
+
+        Ok(())
+    }
+
+    fn start_election(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        self.hard_state.term += 1;
+        self.hard_state.voted_for = Some(self.id.clone());
+        self.save_hard_state()?;
+
+        info!("Starting election for term {}", self.hard_state.term);
+
+        self.role = Role::Candidate;
+        self.leader_id = None;
+        self.votes_received.clear();
+        self.votes_received.insert(self.id.clone());
+        self.reset_election_deadline();
+
+        if self.votes_received.len() >= self.quorum() {
+            return self.become_leader(network_sender);
+        }
+
+        let mut request = RequestVote::new();
+        request.set_last_log_index(self.last_index());
+        request.set_last_log_term(self.last_term());
+
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::REQUEST_VOTE);
+        msg.set_term(self.hard_state.term);
+        msg.set_request_vote(request);
+
+        network_sender.broadcast(msg.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn become_leader(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        info!("Elected leader for term {}", self.hard_state.term);
+
+        self.role = Role::Leader;
+        self.leader_id = Some(self.id.clone());
+
+        let next_index = self.last_index() + 1;
+        self.next_index = self
+            .peers
+            .iter()
+            .map(|peer_id| (peer_id.clone(), next_index))
+            .collect();
+        self.match_index = self
+            .peers
+            .iter()
+            .map(|peer_id| (peer_id.clone(), 0))
+            .collect();
+
+        // A leader only commits entries from its own term by counting replicas, so an empty entry
+        // is appended to commit any entries that were left uncommitted by previous leaders
+        self.append_to_log(None, vec![])?;
+        self.send_append_entries_to_all(network_sender);
+
+        Ok(())
+    }
+
+    /// Appends a new entry for the current term to the leader's log.
+    fn append_to_log(
+        &mut self,
+        proposal: Option<Proposal>,
+        proposal_data: Vec<u8>,
+    ) -> Result<(), ConsensusEngineError> {
+        let entry = RaftLogEntry {
+            index: self.last_index() + 1,
+            term: self.hard_state.term,
+            proposal,
+            proposal_data,
+        };
+        self.add_entries(vec![entry])?;
+        self.advance_commit_index();
+
+        Ok(())
+    }
+
+    /// Commits the latest entry from the current term that has been stored by a majority of the
+    /// nodes, along with all entries before it.
+    fn advance_commit_index(&mut self) {
+        for index in (self.commit_index + 1..=self.last_index()).rev() {
+            if self.term_at(index) != Some(self.hard_state.term) {
+                break;
+            }
+
+            let replicas = 1 + self
+                .match_index
+                .values()
+                .filter(|match_index| **match_index >= index)
+                .count();
+
+            if replicas >= self.quorum() {
+                debug!("Committed entries up to index {}", index);
+                self.commit_index = index;
+                break;
+            }
+        }
+    }
+
+    fn send_append_entries(
+        &self,
+        peer_id: &PeerId,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let next_index = self
+            .next_index
+            .get(peer_id)
+            .copied()
+            .unwrap_or_else(|| self.last_index() + 1);
+        let prev_log_index = next_index - 1;
+
+        let entries = self
+            .log
+            .iter()
+            .skip(prev_log_index as usize)
+            .take(MAX_ENTRIES_PER_MESSAGE)
+            .map(entry_to_proto)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut request = AppendEntries::new();
+        request.set_prev_log_index(prev_log_index);
+        request.set_prev_log_term(self.term_at(prev_log_index).unwrap_or(0));
+        request.set_entries(RepeatedField::from_vec(entries));
+        request.set_leader_commit(self.commit_index);
+
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::APPEND_ENTRIES);
+        msg.set_term(self.hard_state.term);
+        msg.set_append_entries(request);
+
+        network_sender.send_to(peer_id, msg.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn send_append_entries_to_all(&mut self, network_sender: &dyn ConsensusNetworkSender) {
+        for peer_id in &self.peers {
+            // A peer that cannot be reached must not stop the others from being updated
+            if let Err(err) = self.send_append_entries(peer_id, network_sender) {
+                debug!("Unable to send append entries to {}: {}", peer_id, err);
+            }
+        }
+
+        self.next_heartbeat = Instant::now() + self.heartbeat_interval;
+    }
+
+    fn handle_consensus_msg(
+        &mut self,
+        consensus_msg: ConsensusMessage,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let mut raft_msg: RaftMessage = Message::parse_from_bytes(&consensus_msg.message)?;
+        let origin_id = consensus_msg.origin_id;
+
+        if !self.peers.contains(&origin_id) {
+            warn!("Ignoring Raft message from unknown peer {}", origin_id);
+            return Ok(());
+        }
+
+        let term = raft_msg.get_term();
+        if term > self.hard_state.term {
+            debug!(
+                "Received message for newer term {} from {}",
+                term, origin_id
+            );
+            self.become_follower(term, None)?;
+        }
+
+        match raft_msg.get_message_type() {
+            RaftMessage_Type::REQUEST_VOTE => self.handle_request_vote(
+                origin_id,
+                term,
+                raft_msg.get_request_vote(),
+                network_sender,
+            ),
+            RaftMessage_Type::REQUEST_VOTE_RESPONSE => self.handle_request_vote_response(
+                origin_id,
+                term,
+                raft_msg.get_request_vote_response(),
+                network_sender,
+            ),
+            RaftMessage_Type::APPEND_ENTRIES => self.handle_append_entries(
+                origin_id,
+                term,
+                raft_msg.take_append_entries(),
+                network_sender,
+            ),
+            RaftMessage_Type::APPEND_ENTRIES_RESPONSE => self.handle_append_entries_response(
+                origin_id,
+                term,
+                raft_msg.get_append_entries_response(),
+                network_sender,
+            ),
+            RaftMessage_Type::FORWARD_PROPOSAL => self.handle_forwarded_proposal(
+                origin_id,
+                raft_msg.get_forwarded_proposal(),
+                raft_msg.take_forwarded_proposal_data(),
+                network_sender,
+            ),
+            RaftMessage_Type::UNSET_TYPE => {
+                warn!(
+                    "Ignoring improperly specified Raft message from {}",
+                    origin_id
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn handle_request_vote(
+        &mut self,
+        candidate_id: PeerId,
+        term: u64,
+        request: &RequestVote,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        // A candidate may only win if its log contains every entry that may have been committed
+        let log_up_to_date = (request.get_last_log_term(), request.get_last_log_index())
+            >= (self.last_term(), self.last_index());
+        let can_vote = match &self.hard_state.voted_for {
+            Some(voted_for) => voted_for == &candidate_id,
+            None => true,
+        };
+
+        let vote_granted = term == self.hard_state.term && can_vote && log_up_to_date;
+
+        if vote_granted {
+            debug!("Voting for {} in term {}", candidate_id, term);
+            self.hard_state.voted_for = Some(candidate_id.clone());
+            self.save_hard_state()?;
+            self.reset_election_deadline();
+        } else {
+            debug!("Denying vote for {} in term {}", candidate_id, term);
+        }
+
+        let mut response = RequestVoteResponse::new();
+        response.set_vote_granted(vote_granted);
+
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::REQUEST_VOTE_RESPONSE);
+        msg.set_term(self.hard_state.term);
+        msg.set_request_vote_response(response);
+
+        network_sender.send_to(&candidate_id, msg.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn handle_request_vote_response(
+        &mut self,
+        peer_id: PeerId,
+        term: u64,
+        response: &RequestVoteResponse,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.role != Role::Candidate || term != self.hard_state.term {
+            return Ok(());
+        }
+
+        if response.get_vote_granted() {
+            debug!("Received vote from {} in term {}", peer_id, term);
+            self.votes_received.insert(peer_id);
+
+            if self.votes_received.len() >= self.quorum() {
+                self.become_leader(network_sender)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_append_entries(
+        &mut self,
+        leader_id: PeerId,
+        term: u64,
+        request: AppendEntries,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if term < self.hard_state.term {
+            return self.send_append_entries_response(
+                &leader_id,
+                false,
+                self.last_index(),
+                network_sender,
+            );
+        }
+
+        if self.role == Role::Leader {
+            error!(
+                "Received append entries from {} while leader for the same term {}",
+                leader_id, term
+            );
+            return Ok(());
+        }
+
+        if self.leader_id.as_ref() != Some(&leader_id) {
+            info!("Following leader {} for term {}", leader_id, term);
+        }
+        self.become_follower(term, Some(leader_id.clone()))?;
+
+        let prev_log_index = request.get_prev_log_index();
+        if self.term_at(prev_log_index) != Some(request.get_prev_log_term()) {
+            // The logs do not match at the previous entry, so the leader must try an earlier one
+            let hint = min(self.last_index(), prev_log_index.saturating_sub(1));
+            return self.send_append_entries_response(&leader_id, false, hint, network_sender);
+        }
+
+        let mut new_entries = vec![];
+        for entry_proto in request.get_entries() {
+            let entry = entry_from_proto(entry_proto)?;
+
+            if entry.index <= self.last_index() {
+                if self.term_at(entry.index) == Some(entry.term) {
+                    continue;
+                }
+
+                if entry.index <= self.hard_state.applied_index {
+                    error!(
+                        "Leader {} sent an entry that conflicts with applied entry {}",
+                        leader_id, entry.index
+                    );
+                    return Ok(());
+                }
+
+                // Remove the conflicting entry and all entries that follow it
+                debug!("Removing entries from index {}", entry.index);
+                self.store.truncate_entries(entry.index)?;
+                self.log.truncate(entry.index as usize - 1);
+            }
+
+            new_entries.push(entry);
+        }
+
+        if !new_entries.is_empty() {
+            self.add_entries(new_entries)?;
+        }
+
+        let last_new_index = prev_log_index + request.get_entries().len() as u64;
+        if request.get_leader_commit() > self.commit_index {
+            self.commit_index = min(request.get_leader_commit(), last_new_index);
+        }
+
+        self.send_append_entries_response(&leader_id, true, last_new_index, network_sender)
+    }
+
+    fn send_append_entries_response(
+        &self,
+        leader_id: &PeerId,
+        success: bool,
+        match_index: u64,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let mut response = AppendEntriesResponse::new();
+        response.set_success(success);
+        response.set_match_index(match_index);
+
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::APPEND_ENTRIES_RESPONSE);
+        msg.set_term(self.hard_state.term);
+        msg.set_append_entries_response(response);
+
+        network_sender.send_to(leader_id, msg.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn handle_append_entries_response(
+        &mut self,
+        peer_id: PeerId,
+        term: u64,
+        response: &AppendEntriesResponse,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.role != Role::Leader || term != self.hard_state.term {
+            return Ok(());
+        }
+
+        let next_index = if response.get_success() {
+            let match_index = response.get_match_index();
+            if match_index > self.match_index.get(&peer_id).copied().unwrap_or(0) {
+                self.match_index.insert(peer_id.clone(), match_index);
+                self.advance_commit_index();
+            }
+            match_index + 1
+        } else {
+            let next_index = self
+                .next_index
+                .get(&peer_id)
+                .copied()
+                .unwrap_or_else(|| self.last_index() + 1);
+            min(next_index - 1, response.get_match_index() + 1).max(1)
+        };
+        self.next_index.insert(peer_id.clone(), next_index);
+
+        // Keep sending entries until the follower has all of them
+        if !response.get_success() || next_index <= self.last_index() {
+            self.send_append_entries(&peer_id, network_sender)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_forwarded_proposal(
+        &mut self,
+        peer_id: PeerId,
+        proposal_bytes: &[u8],
+        proposal_data: Vec<u8>,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.role != Role::Leader {
+            debug!("Ignoring proposal forwarded by {}; not the leader", peer_id);
+            return Ok(());
+        }
+
+        let proposal = Proposal::try_from(proposal_bytes)?;
+
+        // The same proposal may be forwarded again if the follower did not see it appended
+        let already_logged = self.log.iter().any(|entry| match &entry.proposal {
+            Some(logged) => logged.id == proposal.id,
+            None => false,
+        });
+        if already_logged {
+            debug!("Proposal {} is already in the log; ignoring", proposal.id);
+            return Ok(());
+        }
+
+        debug!(
+            "Appending proposal {} forwarded by {}",
+            proposal.id, peer_id
+        );
+        self.append_to_log(Some(proposal), proposal_data)?;
+        self.send_append_entries_to_all(network_sender);
+
+        Ok(())
+    }
+
+    fn handle_proposal_update(
+        &mut self,
+        update: ProposalUpdate,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        match update {
+            ProposalUpdate::ProposalCreated(None) => {
+                self.awaiting_proposal = false;
+            }
+            ProposalUpdate::ProposalCreated(Some(proposal)) => {
+                debug!("Proposal created: {}", proposal.id);
+                self.awaiting_proposal = false;
+                let data = proposal_manager
+                    .get_proposal_data(&proposal.id)?
+                    .unwrap_or_default();
+                self.own_proposal = Some(OwnProposal {
+                    proposal,
+                    data,
+                    forwarded: None,
+                });
+                self.handle_own_proposal(network_sender)?;
+            }
+            ProposalUpdate::ProposalReceived(proposal, peer_id) => {
+                // The entry for this proposal is applied once it has been committed
+                debug!("Proposal received from {}: {}", peer_id, proposal.id);
+            }
+            ProposalUpdate::ProposalValid(proposal_id) => {
+                if self.applying.as_ref() == Some(&proposal_id) {
+                    debug!("Accepting proposal {}", proposal_id);
+                    proposal_manager.accept_proposal(&proposal_id, None)?;
+                    self.finish_applying()?;
+                } else {
+                    warn!("Got valid message for unknown proposal: {}", proposal_id);
+                }
+            }
+            ProposalUpdate::ProposalInvalid(proposal_id) => {
+                if self.applying.as_ref() == Some(&proposal_id) {
+                    debug!("Rejecting proposal {}", proposal_id);
+                    proposal_manager.reject_proposal(&proposal_id)?;
+                    self.finish_applying()?;
+                } else {
+                    warn!("Got invalid message for unknown proposal: {}", proposal_id);
+                }
+            }
+            ProposalUpdate::ProposalAccepted(proposal_id) => {
+                info!("proposal accepted: {}", proposal_id);
+            }
+            ProposalUpdate::ProposalAcceptFailed(proposal_id, err) => {
+                error!(
+                    "failed to accept proposal {} due to error: {}",
+                    proposal_id, err
+                );
+            }
+            other => {
+                debug!("ignoring update: {:?}", other);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets a proposal created by this node into the log: the leader appends it directly, while a
+    /// follower forwards it to the leader. A follower forwards the proposal again if the leader
+    /// changes or has not appended it within an election timeout.
+    fn handle_own_proposal(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.role == Role::Leader {
+            if let Some(own_proposal) = self.own_proposal.take() {
+                debug!("Appending proposal {}", own_proposal.proposal.id);
+                self.append_to_log(Some(own_proposal.proposal), own_proposal.data)?;
+                self.send_append_entries_to_all(network_sender);
+            }
+            return Ok(());
+        }
+
+        let leader_id = match &self.leader_id {
+            Some(leader_id) => leader_id.clone(),
+            None => return Ok(()),
+        };
+
+        let election_timeout = self.election_timeout;
+        if let Some(own_proposal) = &mut self.own_proposal {
+            let should_forward = match &own_proposal.forwarded {
+                Some((forwarded_to, forwarded_at)) => {
+                    forwarded_to != &leader_id || forwarded_at.elapsed() > election_timeout
+                }
+                None => true,
+            };
+
+            if should_forward {
+                debug!(
+                    "Forwarding proposal {} to leader {}",
+                    own_proposal.proposal.id, leader_id
+                );
+
+                let mut msg = RaftMessage::new();
+                msg.set_message_type(RaftMessage_Type::FORWARD_PROPOSAL);
+                msg.set_term(self.hard_state.term);
+                msg.set_forwarded_proposal(own_proposal.proposal.clone().try_into()?);
+                msg.set_forwarded_proposal_data(own_proposal.data.clone());
+
+                own_proposal.forwarded = Some((leader_id.clone(), Instant::now()));
+                network_sender.send_to(&leader_id, msg.write_to_bytes()?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Starts applying the committed entries in order. An entry with a proposal is applied once
+    /// the proposal manager has been given the proposal's data and has checked it.
+    fn apply_committed_entries(
+        &mut self,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        while self.applying.is_none() && self.hard_state.applied_index < self.commit_index {
+            let entry = self.log[self.hard_state.applied_index as usize].clone();
+
+            match entry.proposal {
+                Some(proposal) => {
+                    if !entry.proposal_data.is_empty() {
+                        proposal_manager.add_proposal_data(&proposal, entry.proposal_data)?;
+                    }
+
+                    match proposal_manager.check_proposal(&proposal.id) {
+                        Ok(()) => {
+                            debug!("Checking committed proposal {}", proposal.id);
+                            self.applying = Some(proposal.id);
+                        }
+                        Err(ProposalManagerError::UnknownProposal(_)) => {
+                            // The entry has no data and the proposal manager has not received the
+                            // proposal by other means yet, try again later
+                            trace!("Waiting to receive proposal {}", proposal.id);
+                            break;
+                        }
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+                None => {
+                    self.hard_state.applied_index = entry.index;
+                    self.save_hard_state()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish_applying(&mut self) -> Result<(), ConsensusEngineError> {
+        self.applying = None;
+        self.hard_state.applied_index += 1;
+        self.save_hard_state()
+    }
+
+    /// If every entry in the log has been applied and a leader is known, ask the proposal manager
+    /// for a new proposal.
+    fn get_next_proposal(&mut self, proposal_manager: &dyn ProposalManager) {
+        if self.awaiting_proposal
+            || self.own_proposal.is_some()
+            || self.applying.is_some()
+            || self.leader_id.is_none()
+            || self.hard_state.applied_index < self.last_index()
+        {
+            return;
+        }
+
+        match proposal_manager.create_proposal(None, vec![]) {
+            Ok(()) => self.awaiting_proposal = true,
+            Err(err) => error!("Error while creating proposal: {}", err),
+        }
+    }
+
+    /// Sends heartbeats if this node is the leader, or starts an election if the leader has not
+    /// been heard from in time.
+    fn handle_timers(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let now = Instant::now();
+        match self.role {
+            Role::Leader => {
+                if now >= self.next_heartbeat {
+                    self.send_append_entries_to_all(network_sender);
+                }
+            }
+            Role::Follower | Role::Candidate => {
+                if now >= self.election_deadline {
+                    self.start_election(network_sender)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn entry_to_proto(entry: &RaftLogEntry) -> Result<RaftLogEntryProto, ProtobufError> {
+    let mut proto = RaftLogEntryProto::new();
+    proto.set_index(entry.index);
+    proto.set_term(entry.term);
+    if let Some(proposal) = &entry.proposal {
+        proto.set_proposal(proposal.clone().try_into()?);
+    }
+    proto.set_proposal_data(entry.proposal_data.clone());
+    Ok(proto)
+}
+
+fn entry_from_proto(proto: &RaftLogEntryProto) -> Result<RaftLogEntry, ProtobufError> {
+    let proposal = if proto.get_proposal().is_empty() {
+        None
+    } else {
+        Some(Proposal::try_from(proto.get_proposal())?)
+    };
+
+    Ok(RaftLogEntry {
+        index: proto.get_index(),
+        term: proto.get_term(),
+        proposal,
+        proposal_data: proto.get_proposal_data().to_vec(),
+    })
+}
+
+impl ConsensusEngine for RaftEngine {
+    fn name(&self) -> &str {
+        "raft"
+    }
+
+    fn version(&self) -> &str {
+        "0.1"
+    }
+
+    fn additional_protocols(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    fn run(
+        &mut self,
+        consensus_messages: Receiver<ConsensusMessage>,
+        proposal_updates: Receiver<ProposalUpdate>,
+        network_sender: Box<dyn ConsensusNetworkSender>,
+        proposal_manager: Box<dyn ProposalManager>,
+        startup_state: StartupState,
+    ) -> Result<(), ConsensusEngineError> {
+        let message_timeout = Duration::from_millis(MESSAGE_RECV_TIMEOUT_MILLIS);
+        let proposal_timeout = Duration::from_millis(PROPOSAL_RECV_TIMEOUT_MILLIS);
+
+        self.id = startup_state.id;
+
+        for id in startup_state.peer_ids {
+            if id != self.id {
+                self.peers.insert(id);
+            }
+        }
+
+        self.hard_state = self.store.get_hard_state()?;
+        self.log = self.store.list_entries()?;
+        // Every applied entry has been committed
+        self.commit_index = self.hard_state.applied_index;
+        self.reset_election_deadline();
+
+        loop {
+            if let Err(err) = self.handle_timers(&*network_sender) {
+                error!("Failed to handle Raft timers: {}", err);
+            }
+
+            if let Err(err) = self.apply_committed_entries(&*proposal_manager) {
+                error!("Failed to apply committed entries: {}", err);
+            }
+
+            if let Err(err) = self.handle_own_proposal(&*network_sender) {
+                error!("Failed to forward proposal to leader: {}", err);
+            }
+
+            self.get_next_proposal(&*proposal_manager);
+
+            // Get and handle a consensus message if there is one
+            match consensus_messages.recv_timeout(message_timeout) {
+                Ok(consensus_message) => {
+                    if let Err(err) = self.handle_consensus_msg(consensus_message, &*network_sender)
+                    {
+                        error!("error while handling consensus message: {}", err);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    info!("consensus message receiver disconnected");
+                    break;
+                }
+            }
+
+            // Get and handle a proposal update if there is one
+            match proposal_updates.recv_timeout(proposal_timeout) {
+                Ok(ProposalUpdate::Shutdown) => {
+                    info!("received shutdown");
+                    break;
+                }
+                Ok(update) => {
+                    if let Err(err) =
+                        self.handle_proposal_update(update, &*network_sender, &*proposal_manager)
+                    {
+                        error!("error while handling proposal update: {}", err);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    info!("proposal update receiver disconnected");
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{channel, Sender};
+    use std::thread::JoinHandle;
+
+    use crate::consensus::tests::{MockConsensusNetworkSender, MockProposalManager};
+
+    const LONG_TIMEOUT_MILLIS: u64 = 60000;
+    const SHORT_TIMEOUT_MILLIS: u64 = 200;
+    const WAIT_LIMIT_MILLIS: u64 = 5000;
+
+    struct TestEngine {
+        consensus_msg_tx: Sender<ConsensusMessage>,
+        update_tx: Sender<ProposalUpdate>,
+        thread: JoinHandle<()>,
+    }
+
+    impl TestEngine {
+        fn send(&self, origin: u8, msg: RaftMessage) {
+            self.consensus_msg_tx
+                .send(ConsensusMessage::new(
+                    msg.write_to_bytes().expect("failed to write message"),
+                    vec![origin].into(),
+                ))
+                .expect("failed to send message");
+        }
+
+        fn shutdown(self) {
+            self.update_tx
+                .send(ProposalUpdate::Shutdown)
+                .expect("failed to send shutdown");
+            self.thread.join().expect("failed to join engine thread");
+        }
+    }
+
+    /// Runs an engine with ID `id` and the given peers in a separate thread.
+    fn start_engine(
+        id: u8,
+        peers: &[u8],
+        election_timeout_millis: u64,
+        store: MemoryRaftStore,
+        return_proposal: bool,
+    ) -> (TestEngine, MockProposalManager, MockConsensusNetworkSender) {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(return_proposal);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![id].into(),
+            peer_ids: peers.iter().map(|peer| vec![*peer].into()).collect(),
+            last_proposal: None,
+        };
+
+        let mut engine = RaftEngine::new_with_timeouts(
+            Box::new(store),
+            Duration::from_millis(election_timeout_millis),
+            Duration::from_millis(election_timeout_millis / 4),
+        );
+        let engine_manager = manager.clone();
+        let engine_network = network.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(engine_network),
+                    Box::new(engine_manager),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        (
+            TestEngine {
+                consensus_msg_tx,
+                update_tx,
+                thread,
+            },
+            manager,
+            network,
+        )
+    }
+
+    /// Waits until the condition is met, panicking if it is not met in time.
+    fn wait_for<F: Fn() -> bool>(condition: F) {
+        let deadline = Instant::now() + Duration::from_millis(WAIT_LIMIT_MILLIS);
+        while !condition() {
+            if Instant::now() > deadline {
+                panic!("condition was not met in time");
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Returns the messages of the given type that were sent to the given peer.
+    fn sent_to(
+        network: &MockConsensusNetworkSender,
+        peer: u8,
+        message_type: RaftMessage_Type,
+    ) -> Vec<RaftMessage> {
+        let peer_id: PeerId = vec![peer].into();
+        network
+            .sent_messages()
+            .iter()
+            .filter(|(_, sent_to)| sent_to == &peer_id)
+            .map(|(bytes, _)| {
+                Message::parse_from_bytes(bytes).expect("failed to parse sent message")
+            })
+            .filter(|msg: &RaftMessage| msg.get_message_type() == message_type)
+            .collect()
+    }
+
+    fn request_vote(term: u64, last_log_index: u64, last_log_term: u64) -> RaftMessage {
+        let mut request = RequestVote::new();
+        request.set_last_log_index(last_log_index);
+        request.set_last_log_term(last_log_term);
+
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::REQUEST_VOTE);
+        msg.set_term(term);
+        msg.set_request_vote(request);
+        msg
+    }
+
+    fn append_entries(
+        term: u64,
+        prev_log_index: u64,
+        prev_log_term: u64,
+        entries: &[RaftLogEntry],
+        leader_commit: u64,
+    ) -> RaftMessage {
+        let mut request = AppendEntries::new();
+        request.set_prev_log_index(prev_log_index);
+        request.set_prev_log_term(prev_log_term);
+        request.set_entries(RepeatedField::from_vec(
+            entries
+                .iter()
+                .map(|entry| entry_to_proto(entry).expect("failed to convert entry"))
+                .collect(),
+        ));
+        request.set_leader_commit(leader_commit);
+
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::APPEND_ENTRIES);
+        msg.set_term(term);
+        msg.set_append_entries(request);
+        msg
+    }
+
+    fn append_entries_response(term: u64, match_index: u64) -> RaftMessage {
+        let mut response = AppendEntriesResponse::new();
+        response.set_success(true);
+        response.set_match_index(match_index);
+
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::APPEND_ENTRIES_RESPONSE);
+        msg.set_term(term);
+        msg.set_append_entries_response(response);
+        msg
+    }
+
+    /// Verify that the engine properly shuts down when it receives the Shutdown update.
+    #[test]
+    fn test_shutdown() {
+        let (engine, _, _) = start_engine(
+            0,
+            &[1, 2],
+            LONG_TIMEOUT_MILLIS,
+            MemoryRaftStore::new(),
+            true,
+        );
+
+        engine.shutdown();
+    }
+
+    /// Verify that a node without peers elects itself and commits its own proposals, and that its
+    /// state is saved in the store.
+    #[test]
+    fn test_single_node() {
+        let store = MemoryRaftStore::new();
+        let (engine, manager, _) = start_engine(0, &[], SHORT_TIMEOUT_MILLIS, store.clone(), true);
+
+        wait_for(|| !manager.accepted_proposals().is_empty());
+        engine.shutdown();
+
+        assert_eq!(manager.accepted_proposals()[0].0, vec![1].into());
+
+        let hard_state = store.get_hard_state().expect("failed to get hard state");
+        assert_eq!(hard_state.term, 1);
+        assert_eq!(hard_state.voted_for, Some(vec![0].into()));
+        assert!(hard_state.applied_index >= 2);
+
+        let entries = store.list_entries().expect("failed to list entries");
+        assert_eq!(entries[0].proposal, None);
+        assert_eq!(
+            entries[1].proposal.as_ref().map(|proposal| &proposal.id),
+            Some(&vec![1].into())
+        );
+        assert_eq!(entries[1].proposal_data, vec![1]);
+    }
+
+    /// Verify that a node votes for at most one candidate per term, and only for candidates whose
+    /// log is up to date.
+    #[test]
+    fn test_vote() {
+        let store = MemoryRaftStore::new();
+        store
+            .append_entries(&[RaftLogEntry {
+                index: 1,
+                term: 1,
+                proposal: None,
+                proposal_data: vec![],
+            }])
+            .expect("failed to append entry");
+        store
+            .set_hard_state(&HardState {
+                term: 1,
+                voted_for: None,
+                applied_index: 0,
+            })
+            .expect("failed to set hard state");
+
+        let (engine, _, network) =
+            start_engine(0, &[1, 2, 3], LONG_TIMEOUT_MILLIS, store.clone(), false);
+
+        // Peer 1's log is missing the entry, so it does not get the vote
+        engine.send(1, request_vote(2, 0, 0));
+        wait_for(|| !sent_to(&network, 1, RaftMessage_Type::REQUEST_VOTE_RESPONSE).is_empty());
+        let response = &sent_to(&network, 1, RaftMessage_Type::REQUEST_VOTE_RESPONSE)[0];
+        assert_eq!(response.get_term(), 2);
+        assert!(!response.get_request_vote_response().get_vote_granted());
+
+        // Peer 2's log is up to date, so it gets the vote
+        engine.send(2, request_vote(2, 1, 1));
+        wait_for(|| !sent_to(&network, 2, RaftMessage_Type::REQUEST_VOTE_RESPONSE).is_empty());
+        let response = &sent_to(&network, 2, RaftMessage_Type::REQUEST_VOTE_RESPONSE)[0];
+        assert!(response.get_request_vote_response().get_vote_granted());
+
+        // The vote for this term has already been given to peer 2
+        engine.send(3, request_vote(2, 1, 1));
+        wait_for(|| !sent_to(&network, 3, RaftMessage_Type::REQUEST_VOTE_RESPONSE).is_empty());
+        let response = &sent_to(&network, 3, RaftMessage_Type::REQUEST_VOTE_RESPONSE)[0];
+        assert!(!response.get_request_vote_response().get_vote_granted());
+
+        engine.shutdown();
+
+        let hard_state = store.get_hard_state().expect("failed to get hard state");
+        assert_eq!(hard_state.term, 2);
+        assert_eq!(hard_state.voted_for, Some(vec![2].into()));
+    }
+
+    /// Verify that a node becomes leader once a majority votes for it, and that it commits
+    /// proposals once they have been replicated to a majority.
+    ///
+    /// 1. Wait for the node to start an election and send a vote from peer 1
+    /// 2. Wait for the no-op entry to be sent to peer 1 and report it as replicated
+    /// 3. Wait for the node's proposal to be sent to peer 1 and report it as replicated
+    /// 4. Verify that the proposal is accepted
+    #[test]
+    fn test_leader() {
+        let (engine, manager, network) = start_engine(
+            0,
+            &[1, 2],
+            SHORT_TIMEOUT_MILLIS,
+            MemoryRaftStore::new(),
+            true,
+        );
+
+        wait_for(|| !network.broadcast_messages().is_empty());
+        let request: RaftMessage = Message::parse_from_bytes(&network.broadcast_messages()[0])
+            .expect("failed to parse request");
+        assert_eq!(request.get_message_type(), RaftMessage_Type::REQUEST_VOTE);
+        let term = request.get_term();
+
+        let mut response = RequestVoteResponse::new();
+        response.set_vote_granted(true);
+        let mut msg = RaftMessage::new();
+        msg.set_message_type(RaftMessage_Type::REQUEST_VOTE_RESPONSE);
+        msg.set_term(term);
+        msg.set_request_vote_response(response);
+        engine.send(1, msg);
+
+        let entry_sent = |index: u64| {
+            sent_to(&network, 1, RaftMessage_Type::APPEND_ENTRIES)
+                .iter()
+                .any(|msg| {
+                    msg.get_append_entries()
+                        .get_entries()
+                        .iter()
+                        .any(|entry| entry.get_index() == index)
+                })
+        };
+
+        wait_for(|| entry_sent(1));
+        engine.send(1, append_entries_response(term, 1));
+
+        wait_for(|| entry_sent(2));
+        assert!(manager.accepted_proposals().is_empty());
+        engine.send(1, append_entries_response(term, 2));
+
+        wait_for(|| !manager.accepted_proposals().is_empty());
+        engine.shutdown();
+
+        assert_eq!(manager.accepted_proposals()[0].0, vec![1].into());
+    }
+
+    /// Verify that a follower appends the entries sent by the leader, applies them once they are
+    /// committed, and rejects entries that do not follow its log.
+    #[test]
+    fn test_follower() {
+        let store = MemoryRaftStore::new();
+        let (engine, manager, network) =
+            start_engine(0, &[1, 2], LONG_TIMEOUT_MILLIS, store.clone(), false);
+
+        let mut proposal = Proposal::default();
+        proposal.id = vec![5].into();
+        let entry = RaftLogEntry {
+            index: 1,
+            term: 1,
+            proposal: Some(proposal),
+            proposal_data: vec![],
+        };
+
+        engine.send(1, append_entries(1, 0, 0, &[entry.clone()], 1));
+        wait_for(|| !sent_to(&network, 1, RaftMessage_Type::APPEND_ENTRIES_RESPONSE).is_empty());
+        let response = &sent_to(&network, 1, RaftMessage_Type::APPEND_ENTRIES_RESPONSE)[0];
+        assert!(response.get_append_entries_response().get_success());
+        assert_eq!(response.get_append_entries_response().get_match_index(), 1);
+
+        wait_for(|| !manager.accepted_proposals().is_empty());
+        assert_eq!(manager.accepted_proposals()[0].0, vec![5].into());
+
+        // The follower does not have an entry at index 3
+        engine.send(1, append_entries(1, 3, 1, &[], 1));
+        wait_for(|| sent_to(&network, 1, RaftMessage_Type::APPEND_ENTRIES_RESPONSE).len() == 2);
+        let response = &sent_to(&network, 1, RaftMessage_Type::APPEND_ENTRIES_RESPONSE)[1];
+        assert!(!response.get_append_entries_response().get_success());
+        assert_eq!(response.get_append_entries_response().get_match_index(), 1);
+
+        engine.shutdown();
+
+        assert_eq!(
+            store.list_entries().expect("failed to list entries"),
+            vec![entry]
+        );
+        assert_eq!(
+            store
+                .get_hard_state()
+                .expect("failed to get hard state")
+                .applied_index,
+            1
+        );
+    }
+
+    /// Verify that a follower that never received a proposal from the node that created it
+    /// applies the proposal using the data replicated in the log entry.
+    #[test]
+    fn test_follower_proposal_data() {
+        let (engine, manager, _) = start_engine(
+            0,
+            &[1, 2],
+            LONG_TIMEOUT_MILLIS,
+            MemoryRaftStore::new(),
+            false,
+        );
+        manager.set_require_proposal_data(true);
+
+        let mut proposal = Proposal::default();
+        proposal.id = vec![5].into();
+        let entry = RaftLogEntry {
+            index: 1,
+            term: 1,
+            proposal: Some(proposal),
+            proposal_data: vec![5, 5],
+        };
+
+        engine.send(1, append_entries(1, 0, 0, &[entry], 1));
+
+        wait_for(|| !manager.accepted_proposals().is_empty());
+        engine.shutdown();
+
+        assert_eq!(manager.accepted_proposals()[0].0, vec![5].into());
+        assert_eq!(
+            manager.added_proposal_data()[0],
+            (vec![5].into(), vec![5, 5])
+        );
+    }
+}
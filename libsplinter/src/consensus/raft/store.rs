@@ -0,0 +1,136 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistent state of the Raft consensus engine
+
+use std::sync::{Arc, Mutex};
+
+use crate::consensus::{PeerId, Proposal};
+use crate::error::InternalError;
+
+/// The state of a Raft node that must survive a restart.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HardState {
+    /// The latest term this node has seen
+    pub term: u64,
+    /// The peer this node voted for in the current term, if any
+    pub voted_for: Option<PeerId>,
+    /// The index of the last log entry that was applied by the proposal manager
+    pub applied_index: u64,
+}
+
+/// An entry in the replicated log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaftLogEntry {
+    /// The position of the entry in the log, starting at 1
+    pub index: u64,
+    /// The term in which the entry was appended by the leader
+    pub term: u64,
+    /// The proposal to apply; `None` for the no-op entry a new leader appends
+    pub proposal: Option<Proposal>,
+    /// The data of the proposal, as provided by the proposal manager of the node that created it;
+    /// empty if that manager does not provide proposal data
+    pub proposal_data: Vec<u8>,
+}
+
+/// Stores the hard state and the log of a Raft node.
+pub trait RaftStore: Send + Sync {
+    /// Returns the hard state, or the default hard state if none has been saved.
+    fn get_hard_state(&self) -> Result<HardState, InternalError>;
+
+    /// Saves the hard state, replacing the existing hard state.
+    fn set_hard_state(&self, hard_state: &HardState) -> Result<(), InternalError>;
+
+    /// Returns all entries in the log, ordered by index.
+    fn list_entries(&self) -> Result<Vec<RaftLogEntry>, InternalError>;
+
+    /// Adds the given entries to the end of the log.
+    fn append_entries(&self, entries: &[RaftLogEntry]) -> Result<(), InternalError>;
+
+    /// Removes all entries with an index greater than or equal to the given index.
+    fn truncate_entries(&self, from_index: u64) -> Result<(), InternalError>;
+
+    fn clone_boxed(&self) -> Box<dyn RaftStore>;
+}
+
+impl Clone for Box<dyn RaftStore> {
+    fn clone(&self) -> Self {
+        (*self).clone_boxed()
+    }
+}
+
+/// A [RaftStore] that keeps its state in memory.
+///
+/// Clones of this store share the same state. The state does not survive a restart of the
+/// process, so this store is intended for testing.
+#[derive(Clone, Default)]
+pub struct MemoryRaftStore {
+    inner: Arc<Mutex<(HardState, Vec<RaftLogEntry>)>>,
+}
+
+impl MemoryRaftStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RaftStore for MemoryRaftStore {
+    fn get_hard_state(&self) -> Result<HardState, InternalError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Raft store lock was poisoned".into()))?
+            .0
+            .clone())
+    }
+
+    fn set_hard_state(&self, hard_state: &HardState) -> Result<(), InternalError> {
+        self.inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Raft store lock was poisoned".into()))?
+            .0 = hard_state.clone();
+        Ok(())
+    }
+
+    fn list_entries(&self) -> Result<Vec<RaftLogEntry>, InternalError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Raft store lock was poisoned".into()))?
+            .1
+            .clone())
+    }
+
+    fn append_entries(&self, entries: &[RaftLogEntry]) -> Result<(), InternalError> {
+        self.inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Raft store lock was poisoned".into()))?
+            .1
+            .extend_from_slice(entries);
+        Ok(())
+    }
+
+    fn truncate_entries(&self, from_index: u64) -> Result<(), InternalError> {
+        self.inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Raft store lock was poisoned".into()))?
+            .1
+            .retain(|entry| entry.index < from_index);
+        Ok(())
+    }
+
+    fn clone_boxed(&self) -> Box<dyn RaftStore> {
+        Box::new(self.clone())
+    }
+}
@@ -263,6 +263,10 @@ impl TwoPhaseEngine {
                     consensus_msg.origin_id
                 ),
             },
+            TwoPhaseMessage_Type::DECISION_REQUEST => warn!(
+                "Ignoring decision request from {}; not supported by this version",
+                consensus_msg.origin_id
+            ),
             TwoPhaseMessage_Type::UNSET_TYPE => warn!(
                 "Ignoring improperly specified two-phase message from {}",
                 consensus_msg.origin_id
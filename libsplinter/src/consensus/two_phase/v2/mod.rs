@@ -20,11 +20,27 @@
 //!
 //! # Known limitations of this 2PC implementation
 //!
-//! A limitation of this implementation is that it is not fully resilient to crashes; for instance,
-//! if the coordinator commits a proposal but crashes before it is able to send the `APPLY` message
-//! to the other nodes, the network will be out of sync because the coordinator does not know to
-//! send the message when it restarts. This limitation will be solved by re-implementing 2PC as a
-//! stateless algorithm.
+//! The engine records the proposal it is evaluating, along with the proposal's data as provided by
+//! the proposal manager, and the decisions the coordinator makes in a [TwoPhaseStore]. When the
+//! engine restarts with a proposal still in flight, the coordinator applies the proposal if it had
+//! decided to apply it and re-sends the recorded result of that proposal (or rejects it if no
+//! result was recorded). A participant gives the proposal
+//! and its data back to the proposal manager, which checks it again, and requests the result from
+//! the coordinator with a `DECISION_REQUEST` message. Participants also request the result if they
+//! do not receive it within the coordinator timeout. This recovery only works if a durable store
+//! is provided with [TwoPhaseEngine::new_with_store]; the store used by [TwoPhaseEngine::new]
+//! keeps its state in memory.
+//!
+//! Once a proposal has been applied, the decisions for the proposals before it are removed from
+//! the store. A node does not verify a proposal until it has the result of the proposal it is
+//! evaluating, so every node has the results of the earlier proposals by then.
+//!
+//! Recovery has the following limitations:
+//!
+//! * The proposal manager must provide the data of its proposals with
+//!   [ProposalManager::get_proposal_data] for a node to apply a recovered proposal.
+//! * A node that stops after applying a proposal but before removing it from the store is given
+//!   the proposal again when it restarts, so the proposal manager must not apply a proposal twice.
 //!
 //! # Differences from previous version
 //!
@@ -38,9 +54,10 @@
 //!   which proposals are evaluated and is responsible for determining when to accept them, it is
 //!   the only node that can reliably produce proposals that are based on the most current state.
 
+mod store;
 mod timing;
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::mpsc::{Receiver, RecvTimeoutError};
 use std::time::Duration;
 
@@ -48,13 +65,14 @@ use protobuf::Message;
 
 use crate::consensus::{
     ConsensusEngine, ConsensusEngineError, ConsensusMessage, ConsensusNetworkSender, PeerId,
-    ProposalId, ProposalManager, ProposalUpdate, StartupState,
+    Proposal, ProposalId, ProposalManager, ProposalManagerError, ProposalUpdate, StartupState,
 };
 use crate::protos::two_phase::{
     TwoPhaseMessage, TwoPhaseMessage_ProposalResult, TwoPhaseMessage_ProposalVerificationResponse,
     TwoPhaseMessage_Type,
 };
 
+pub use self::store::{InFlightProposal, MemoryTwoPhaseStore, TwoPhaseDecision, TwoPhaseStore};
 use self::timing::Timeout;
 
 const MESSAGE_RECV_TIMEOUT_MILLIS: u64 = 100;
@@ -82,25 +100,34 @@ impl State {
             Self::EvaluatingProposal(tpc_proposal) if tpc_proposal.proposal_id() == proposal_id,
         )
     }
+
+    pub fn is_evaluating_recovered_proposal(&self) -> bool {
+        matches!(self, Self::EvaluatingProposal(tpc_proposal) if tpc_proposal.recovered)
+    }
 }
 
 /// Contains information about a proposal that two phase consensus needs to keep track of
 #[derive(Debug)]
 struct TwoPhaseProposal {
-    proposal_id: ProposalId,
+    proposal: Proposal,
+    proposal_data: Vec<u8>,
     peers_verified: HashSet<PeerId>,
+    /// Whether the proposal was recovered from the store when this node restarted
+    recovered: bool,
 }
 
 impl TwoPhaseProposal {
-    fn new(proposal_id: ProposalId) -> Self {
+    fn new(proposal: Proposal, proposal_data: Vec<u8>) -> Self {
         TwoPhaseProposal {
-            proposal_id,
+            proposal,
+            proposal_data,
             peers_verified: HashSet::new(),
+            recovered: false,
         }
     }
 
     fn proposal_id(&self) -> &ProposalId {
-        &self.proposal_id
+        &self.proposal.id
     }
 
     fn peers_verified(&self) -> &HashSet<PeerId> {
@@ -110,6 +137,34 @@ impl TwoPhaseProposal {
     fn add_verified_peer(&mut self, id: PeerId) {
         self.peers_verified.insert(id);
     }
+
+    fn to_in_flight_proposal(&self) -> InFlightProposal {
+        InFlightProposal {
+            proposal: self.proposal.clone(),
+            proposal_data: self.proposal_data.clone(),
+            peers_verified: self.peers_verified.clone(),
+        }
+    }
+}
+
+impl From<InFlightProposal> for TwoPhaseProposal {
+    fn from(in_flight: InFlightProposal) -> Self {
+        TwoPhaseProposal {
+            proposal: in_flight.proposal,
+            proposal_data: in_flight.proposal_data,
+            peers_verified: in_flight.peers_verified,
+            recovered: true,
+        }
+    }
+}
+
+impl From<TwoPhaseDecision> for TwoPhaseMessage_ProposalResult {
+    fn from(decision: TwoPhaseDecision) -> Self {
+        match decision {
+            TwoPhaseDecision::Apply => TwoPhaseMessage_ProposalResult::APPLY,
+            TwoPhaseDecision::Reject => TwoPhaseMessage_ProposalResult::REJECT,
+        }
+    }
 }
 
 pub struct TwoPhaseEngine {
@@ -117,19 +172,36 @@ pub struct TwoPhaseEngine {
     verifiers: HashSet<PeerId>,
     state: State,
     coordinator_timeout: Timeout,
-    proposals_received: HashSet<ProposalId>,
+    proposals_received: HashMap<ProposalId, Proposal>,
     verification_request_backlog: VecDeque<ProposalId>,
+    store: Box<dyn TwoPhaseStore>,
+    decision_request_timeout: Timeout,
 }
 
 impl TwoPhaseEngine {
+    /// Creates a new engine that keeps its state in memory.
     pub fn new(coordinator_timeout_duration: Duration) -> Self {
+        Self::new_with_store(
+            coordinator_timeout_duration,
+            Box::new(MemoryTwoPhaseStore::new()),
+        )
+    }
+
+    /// Creates a new engine that records its state in the given store, so it can recover a
+    /// proposal that was in flight when it was stopped.
+    pub fn new_with_store(
+        coordinator_timeout_duration: Duration,
+        store: Box<dyn TwoPhaseStore>,
+    ) -> Self {
         TwoPhaseEngine {
             id: PeerId::default(),
             verifiers: HashSet::new(),
             state: State::Idle,
             coordinator_timeout: Timeout::new(coordinator_timeout_duration),
-            proposals_received: HashSet::new(),
+            proposals_received: HashMap::new(),
             verification_request_backlog: VecDeque::new(),
+            store,
+            decision_request_timeout: Timeout::new(coordinator_timeout_duration),
         }
     }
 
@@ -168,10 +240,10 @@ impl TwoPhaseEngine {
                     self.verification_request_backlog.push_back(proposal_id);
                 } else {
                     // Try to get the proposal from the backlog
-                    if self.proposals_received.remove(&proposal_id) {
+                    if let Some(proposal) = self.proposals_received.remove(&proposal_id) {
                         debug!("Checking proposal {}", proposal_id);
                         proposal_manager.check_proposal(&proposal_id)?;
-                        self.state = State::EvaluatingProposal(TwoPhaseProposal::new(proposal_id));
+                        self.start_evaluation(proposal, proposal_manager)?;
                     } else {
                         debug!(
                             "Proposal not yet received, backlogging verification request: \
@@ -200,6 +272,8 @@ impl TwoPhaseEngine {
                         // Already checked state above in self.state.is_evaluating_proposal_with_id
                         if let State::EvaluatingProposal(tpc_proposal) = &mut self.state {
                             tpc_proposal.add_verified_peer(consensus_msg.origin_id);
+                            self.store
+                                .set_in_flight_proposal(&tpc_proposal.to_in_flight_proposal())?;
 
                             if tpc_proposal.peers_verified() == &self.verifiers {
                                 debug!(
@@ -240,7 +314,7 @@ impl TwoPhaseEngine {
                     if self.state.is_evaluating_proposal_with_id(&proposal_id) {
                         debug!("Accepting proposal {}", proposal_id);
                         proposal_manager.accept_proposal(&proposal_id, None)?;
-                        self.state = State::Idle;
+                        self.complete_evaluation(&proposal_id, TwoPhaseDecision::Apply)?;
                     } else {
                         warn!(
                             "Received unexpected apply result for proposal {}",
@@ -254,7 +328,7 @@ impl TwoPhaseEngine {
 
                     // Only update state if this was the currently evaluating proposal
                     if self.state.is_evaluating_proposal_with_id(&proposal_id) {
-                        self.state = State::Idle;
+                        self.complete_evaluation(&proposal_id, TwoPhaseDecision::Reject)?;
                     }
                 }
                 TwoPhaseMessage_ProposalResult::UNSET_RESULT => warn!(
@@ -262,6 +336,42 @@ impl TwoPhaseEngine {
                     consensus_msg.origin_id
                 ),
             },
+            TwoPhaseMessage_Type::DECISION_REQUEST => {
+                if !self.is_coordinator() {
+                    warn!(
+                        "Received decision request for proposal {}, but this node is not the \
+                         coordinator",
+                        proposal_id
+                    );
+                    return Ok(());
+                }
+
+                let decision = match self.store.get_decision(&proposal_id)? {
+                    Some(decision) => decision,
+                    None if self.state.is_evaluating_proposal_with_id(&proposal_id) => {
+                        debug!(
+                            "Ignoring decision request for proposal still being evaluated: {}",
+                            proposal_id
+                        );
+                        return Ok(());
+                    }
+                    // A proposal that has no decision and is not being evaluated was never
+                    // applied by the coordinator
+                    None => TwoPhaseDecision::Reject,
+                };
+
+                debug!(
+                    "Sending result of proposal {} to peer {}",
+                    proposal_id, consensus_msg.origin_id
+                );
+
+                let mut result = TwoPhaseMessage::new();
+                result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+                result.set_proposal_id(proposal_id.into());
+                result.set_proposal_result(decision.into());
+
+                network_sender.send_to(&consensus_msg.origin_id, result.write_to_bytes()?)?;
+            }
             TwoPhaseMessage_Type::UNSET_TYPE => warn!(
                 "Ignoring improperly specified two-phase message from {}",
                 consensus_msg.origin_id
@@ -292,7 +402,7 @@ impl TwoPhaseEngine {
             }
             ProposalUpdate::ProposalCreated(Some(proposal)) => {
                 debug!("Proposal created, starting coordination: {}", proposal.id);
-                self.start_coordination(proposal.id, network_sender, proposal_manager)?;
+                self.start_coordination(proposal, network_sender, proposal_manager)?;
             }
             ProposalUpdate::ProposalReceived(_, peer_id) if &peer_id != self.coordinator_id() => {
                 warn!(
@@ -302,7 +412,8 @@ impl TwoPhaseEngine {
             }
             ProposalUpdate::ProposalReceived(proposal, _) => {
                 debug!("Proposal received: {}", proposal.id);
-                self.proposals_received.insert(proposal.id);
+                self.proposals_received
+                    .insert(proposal.id.clone(), proposal);
             }
             ProposalUpdate::ProposalValid(proposal_id) => match &mut self.state {
                 State::EvaluatingProposal(tpc_proposal)
@@ -312,6 +423,8 @@ impl TwoPhaseEngine {
 
                     if is_coordinator {
                         tpc_proposal.add_verified_peer(self.id.clone());
+                        self.store
+                            .set_in_flight_proposal(&tpc_proposal.to_in_flight_proposal())?;
 
                         debug!("Requesting verification of proposal {}", proposal_id);
 
@@ -321,6 +434,9 @@ impl TwoPhaseEngine {
                         request.set_proposal_id(proposal_id.into());
 
                         network_sender.broadcast(request.write_to_bytes()?)?;
+                    } else if tpc_proposal.recovered {
+                        // The coordinator has already decided the result of a recovered proposal
+                        debug!("Recovered proposal prepared: {}", proposal_id);
                     } else {
                         debug!("Sending verified response for proposal {}", proposal_id);
 
@@ -342,7 +458,12 @@ impl TwoPhaseEngine {
                 if self.state.is_evaluating_proposal_with_id(&proposal_id) {
                     debug!("Proposal invalid: {}", proposal_id);
 
-                    if is_coordinator {
+                    if self.state.is_evaluating_recovered_proposal() {
+                        error!(
+                            "Recovered proposal is invalid and cannot be applied: {}",
+                            proposal_id
+                        );
+                    } else if is_coordinator {
                         debug!("Rejecting proposal {}", proposal_id);
                         self.complete_coordination(
                             proposal_id,
@@ -387,23 +508,20 @@ impl TwoPhaseEngine {
 
     fn start_coordination(
         &mut self,
-        proposal_id: ProposalId,
+        proposal: Proposal,
         network_sender: &dyn ConsensusNetworkSender,
         proposal_manager: &dyn ProposalManager,
     ) -> Result<(), ConsensusEngineError> {
-        debug!("Checking proposal {}", proposal_id);
-        match proposal_manager.check_proposal(&proposal_id) {
-            Ok(_) => {
-                self.state = State::EvaluatingProposal(TwoPhaseProposal::new(proposal_id));
-                self.coordinator_timeout.start();
-            }
+        debug!("Checking proposal {}", proposal.id);
+        match proposal_manager.check_proposal(&proposal.id) {
+            Ok(_) => self.start_evaluation(proposal, proposal_manager)?,
             Err(err) => {
                 debug!(
                     "Rejecting proposal {}; failed to check proposal due to err: {}",
-                    proposal_id, err
+                    proposal.id, err
                 );
                 self.complete_coordination(
-                    proposal_id,
+                    proposal.id,
                     TwoPhaseMessage_ProposalResult::REJECT,
                     network_sender,
                     proposal_manager,
@@ -420,13 +538,9 @@ impl TwoPhaseEngine {
         network_sender: &dyn ConsensusNetworkSender,
         proposal_manager: &dyn ProposalManager,
     ) -> Result<(), ConsensusEngineError> {
-        match proposal_result {
-            TwoPhaseMessage_ProposalResult::APPLY => {
-                proposal_manager.accept_proposal(&proposal_id, None)?;
-            }
-            TwoPhaseMessage_ProposalResult::REJECT => {
-                proposal_manager.reject_proposal(&proposal_id)?;
-            }
+        let decision = match proposal_result {
+            TwoPhaseMessage_ProposalResult::APPLY => TwoPhaseDecision::Apply,
+            TwoPhaseMessage_ProposalResult::REJECT => TwoPhaseDecision::Reject,
             TwoPhaseMessage_ProposalResult::UNSET_RESULT => {
                 warn!(
                     "Unset proposal result when completing proposal {}",
@@ -434,8 +548,17 @@ impl TwoPhaseEngine {
                 );
                 return Ok(());
             }
+        };
+
+        // Record the decision before applying it, so participants can always be told the result
+        self.record_decision(&proposal_id, decision)?;
+
+        match decision {
+            TwoPhaseDecision::Apply => proposal_manager.accept_proposal(&proposal_id, None)?,
+            TwoPhaseDecision::Reject => proposal_manager.reject_proposal(&proposal_id)?,
         }
 
+        self.store.remove_in_flight_proposal()?;
         self.state = State::Idle;
         self.coordinator_timeout.stop();
 
@@ -449,6 +572,184 @@ impl TwoPhaseEngine {
         Ok(())
     }
 
+    /// Starts evaluating the given proposal, recording it and its data as in flight so the
+    /// evaluation can be recovered if this node is stopped.
+    fn start_evaluation(
+        &mut self,
+        proposal: Proposal,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let proposal_data = proposal_manager
+            .get_proposal_data(&proposal.id)?
+            .unwrap_or_default();
+        let tpc_proposal = TwoPhaseProposal::new(proposal, proposal_data);
+        self.store
+            .set_in_flight_proposal(&tpc_proposal.to_in_flight_proposal())?;
+        self.state = State::EvaluatingProposal(tpc_proposal);
+
+        if self.is_coordinator() {
+            self.coordinator_timeout.start();
+        } else {
+            self.decision_request_timeout.start();
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the evaluation of a proposal as a participant, once the coordinator's decision
+    /// has been applied.
+    fn complete_evaluation(
+        &mut self,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), ConsensusEngineError> {
+        self.record_decision(proposal_id, decision)?;
+        self.store.remove_in_flight_proposal()?;
+        self.state = State::Idle;
+        self.decision_request_timeout.stop();
+
+        Ok(())
+    }
+
+    /// Records the decision for the given proposal. If the proposal is applied, the decisions for
+    /// all other proposals are removed: every verifier approved this proposal, which they only do
+    /// once they have the results of the proposals before it, so those results will not be
+    /// requested again.
+    fn record_decision(
+        &self,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), ConsensusEngineError> {
+        self.store.add_decision(proposal_id, decision)?;
+        if decision == TwoPhaseDecision::Apply {
+            self.store.remove_decisions_except(proposal_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Asks the coordinator for the result of the given proposal.
+    fn request_decision(
+        &self,
+        proposal_id: ProposalId,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::DECISION_REQUEST);
+        request.set_proposal_id(proposal_id.into());
+
+        network_sender.send_to(self.coordinator_id(), request.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    /// If this node is a participant and it has not received the result of the proposal it is
+    /// evaluating in time, ask the coordinator for the result.
+    fn request_decision_if_timed_out(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if let State::EvaluatingProposal(ref tpc_proposal) = self.state {
+            if !self.is_coordinator() && self.decision_request_timeout.check_expired() {
+                warn!(
+                    "Result of proposal not received; requesting it from the coordinator: {}",
+                    tpc_proposal.proposal_id()
+                );
+                let proposal_id = tpc_proposal.proposal_id().clone();
+                self.decision_request_timeout.start();
+                self.request_decision(proposal_id, network_sender)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recovers the proposal that was in flight when this node was last stopped, if any.
+    ///
+    /// The coordinator applies the proposal if it had decided to apply it, then re-sends the
+    /// recorded result of the proposal, rejecting it if no result was recorded. A participant requests the result from the coordinator, unless it had already
+    /// received it, after giving the proposal back to the proposal manager to be checked again.
+    fn recover_in_flight_proposal(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let in_flight = match self.store.get_in_flight_proposal()? {
+            Some(in_flight) => in_flight,
+            None => return Ok(()),
+        };
+        let proposal_id = in_flight.proposal.id.clone();
+
+        if self.is_coordinator() {
+            let decision = match self.store.get_decision(&proposal_id)? {
+                Some(TwoPhaseDecision::Apply) => {
+                    // The decision is recorded before the proposal is applied, so this node may
+                    // have stopped before applying it; the in-flight proposal is kept until it
+                    // has been applied, so that it is applied when this node restarts again if it
+                    // fails now
+                    info!("Applying recovered proposal {}", proposal_id);
+                    prepare_recovered_proposal(&in_flight, proposal_manager)?;
+                    proposal_manager.accept_proposal(&proposal_id, None)?;
+                    TwoPhaseDecision::Apply
+                }
+                Some(TwoPhaseDecision::Reject) => TwoPhaseDecision::Reject,
+                None => {
+                    warn!(
+                        "Proposal was interrupted before it was completed; rejecting: {}",
+                        proposal_id
+                    );
+                    self.record_decision(&proposal_id, TwoPhaseDecision::Reject)?;
+                    if let Err(err) = proposal_manager.reject_proposal(&proposal_id) {
+                        warn!(
+                            "Failed to reject recovered proposal {}: {}",
+                            proposal_id, err
+                        );
+                    }
+                    TwoPhaseDecision::Reject
+                }
+            };
+            self.store.remove_in_flight_proposal()?;
+
+            info!("Sending result of recovered proposal {}", proposal_id);
+
+            let mut result = TwoPhaseMessage::new();
+            result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+            result.set_proposal_id(proposal_id.into());
+            result.set_proposal_result(decision.into());
+
+            // Participants that miss this message will request the result themselves
+            if let Err(err) = network_sender.broadcast(result.write_to_bytes()?) {
+                warn!("Failed to send result of recovered proposal: {}", err);
+            }
+        } else if self.store.get_decision(&proposal_id)?.is_some() {
+            self.store.remove_in_flight_proposal()?;
+        } else {
+            // The proposal manager lost the proposal when this node was stopped, so it must be
+            // checked again before its result can be applied
+            if let Err(err) = prepare_recovered_proposal(&in_flight, proposal_manager) {
+                warn!(
+                    "Failed to prepare recovered proposal {}: {}",
+                    proposal_id, err
+                );
+            }
+
+            info!(
+                "Requesting result of recovered proposal from the coordinator: {}",
+                proposal_id
+            );
+            self.state = State::EvaluatingProposal(in_flight.into());
+            self.decision_request_timeout.start();
+
+            // If the coordinator is not reachable yet, the request will be sent again once the
+            // timeout expires
+            if let Err(err) = self.request_decision(proposal_id, network_sender) {
+                warn!("Failed to request result of recovered proposal: {}", err);
+            }
+        }
+
+        Ok(())
+    }
+
     /// If the coordinator timeout has expired, abort the current proposal.
     fn abort_proposal_if_timed_out(
         &mut self,
@@ -484,14 +785,14 @@ impl TwoPhaseEngine {
             if let Some(idx) = self
                 .verification_request_backlog
                 .iter()
-                .position(|proposal_id| self.proposals_received.contains(proposal_id))
+                .position(|proposal_id| self.proposals_received.contains_key(proposal_id))
             {
                 let proposal_id = self.verification_request_backlog.remove(idx).unwrap();
-                self.proposals_received.remove(&proposal_id);
+                let proposal = self.proposals_received.remove(&proposal_id).unwrap();
 
                 debug!("Checking proposal from backlog: {}", proposal_id);
                 proposal_manager.check_proposal(&proposal_id)?;
-                self.state = State::EvaluatingProposal(TwoPhaseProposal::new(proposal_id));
+                self.start_evaluation(proposal, proposal_manager)?;
             }
         }
 
@@ -509,6 +810,18 @@ impl TwoPhaseEngine {
     }
 }
 
+/// Gives a recovered proposal and its data back to the proposal manager and has the manager check
+/// it, so the manager is ready to accept the proposal.
+fn prepare_recovered_proposal(
+    in_flight: &InFlightProposal,
+    proposal_manager: &dyn ProposalManager,
+) -> Result<(), ProposalManagerError> {
+    if !in_flight.proposal_data.is_empty() {
+        proposal_manager.add_proposal_data(&in_flight.proposal, in_flight.proposal_data.clone())?;
+    }
+    proposal_manager.check_proposal(&in_flight.proposal.id)
+}
+
 impl ConsensusEngine for TwoPhaseEngine {
     fn name(&self) -> &str {
         "two-phase"
@@ -540,12 +853,20 @@ impl ConsensusEngine for TwoPhaseEngine {
             self.verifiers.insert(id);
         }
 
+        if let Err(err) = self.recover_in_flight_proposal(&*network_sender, &*proposal_manager) {
+            error!("Failed to recover in-flight proposal: {}", err);
+        }
+
         loop {
             if let Err(err) = self.abort_proposal_if_timed_out(&*network_sender, &*proposal_manager)
             {
                 error!("Failed to abort timed-out proposal: {}", err);
             }
 
+            if let Err(err) = self.request_decision_if_timed_out(&*network_sender) {
+                error!("Failed to request proposal result: {}", err);
+            }
+
             if let Err(err) = self.handle_backlogged_verification_request(&*proposal_manager) {
                 error!("Failed to handle backlogged verification request: {}", err);
             }
@@ -603,7 +924,6 @@ pub mod tests {
     use std::sync::mpsc::channel;
 
     use crate::consensus::tests::{MockConsensusNetworkSender, MockProposalManager};
-    use crate::consensus::Proposal;
 
     const COORDINATOR_TIMEOUT_MILLIS: u64 = 5000;
 
@@ -651,8 +971,12 @@ pub mod tests {
             verifiers: peer_ids_hashset.clone(),
             state: State::Idle,
             coordinator_timeout: Timeout::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS)),
-            proposals_received: HashSet::new(),
+            proposals_received: HashMap::new(),
             verification_request_backlog: VecDeque::new(),
+            store: Box::new(MemoryTwoPhaseStore::new()),
+            decision_request_timeout: Timeout::new(Duration::from_millis(
+                COORDINATOR_TIMEOUT_MILLIS,
+            )),
         };
         assert_eq!(coordinator.coordinator_id(), &peer_ids[0]);
         assert!(coordinator.is_coordinator());
@@ -662,8 +986,12 @@ pub mod tests {
             verifiers: peer_ids_hashset,
             state: State::Idle,
             coordinator_timeout: Timeout::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS)),
-            proposals_received: HashSet::new(),
+            proposals_received: HashMap::new(),
             verification_request_backlog: VecDeque::new(),
+            store: Box::new(MemoryTwoPhaseStore::new()),
+            decision_request_timeout: Timeout::new(Duration::from_millis(
+                COORDINATOR_TIMEOUT_MILLIS,
+            )),
         };
         assert_eq!(other_node.coordinator_id(), &peer_ids[0]);
         assert!(!other_node.is_coordinator());
@@ -1065,4 +1393,342 @@ pub mod tests {
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
     }
+
+    /// Test that a coordinator which was stopped while a proposal was in flight rejects the
+    /// proposal when it restarts, and answers decision requests for it.
+    #[test]
+    fn test_coordinator_recovery() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        let store = MemoryTwoPhaseStore::new();
+        store
+            .set_in_flight_proposal(&InFlightProposal {
+                proposal: Proposal {
+                    id: vec![1].into(),
+                    ..Default::default()
+                },
+                proposal_data: vec![1],
+                peers_verified: HashSet::from_iter(vec![vec![0].into()]),
+            })
+            .expect("failed to set in-flight proposal");
+
+        let mut engine = TwoPhaseEngine::new_with_store(
+            Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS),
+            Box::new(store.clone()),
+        );
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Verify the Reject message is sent for the interrupted proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::REJECT
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        assert_eq!(
+            store
+                .get_decision(&vec![1].into())
+                .expect("failed to get decision"),
+            Some(TwoPhaseDecision::Reject)
+        );
+        assert_eq!(
+            store
+                .get_in_flight_proposal()
+                .expect("failed to get in-flight proposal"),
+            None
+        );
+
+        // Receive a decision request for the proposal
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::DECISION_REQUEST);
+        request.set_proposal_id(vec![1]);
+        let message_bytes = request
+            .write_to_bytes()
+            .expect("failed to write request to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![1].into()))
+            .expect("failed to send decision request");
+
+        // Verify the Reject message is sent to the requesting peer
+        loop {
+            if let Some((msg, peer_id)) = network.sent_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(peer_id, &vec![1].into());
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::REJECT
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test that a coordinator which was stopped after recording its decision to apply a proposal,
+    /// but before applying the proposal, gives the proposal and its data back to the proposal
+    /// manager and applies it when it restarts, before sending the result to the participants.
+    #[test]
+    fn test_coordinator_recovery_before_apply() {
+        let (update_tx, update_rx) = channel();
+        let (_consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        manager.set_require_proposal_data(true);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        // The state of the store between `record_decision` and `accept_proposal`
+        let store = MemoryTwoPhaseStore::new();
+        store
+            .set_in_flight_proposal(&InFlightProposal {
+                proposal: Proposal {
+                    id: vec![1].into(),
+                    ..Default::default()
+                },
+                proposal_data: vec![1],
+                peers_verified: HashSet::from_iter(vec![
+                    vec![0].into(),
+                    vec![1].into(),
+                    vec![2].into(),
+                ]),
+            })
+            .expect("failed to set in-flight proposal");
+        store
+            .add_decision(&vec![1].into(), TwoPhaseDecision::Apply)
+            .expect("failed to add decision");
+
+        let mut engine = TwoPhaseEngine::new_with_store(
+            Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS),
+            Box::new(store.clone()),
+        );
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Verify the Apply message is sent for the recovered proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::APPLY
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+
+        // The proposal was applied by the coordinator before the result was sent
+        assert_eq!(
+            manager.added_proposal_data().as_slice(),
+            &[(vec![1].into(), vec![1])]
+        );
+        assert_eq!(manager.accepted_proposals().len(), 1);
+        assert_eq!(manager.accepted_proposals()[0].0, vec![1].into());
+        assert_eq!(
+            store
+                .get_decision(&vec![1].into())
+                .expect("failed to get decision"),
+            Some(TwoPhaseDecision::Apply)
+        );
+        assert_eq!(
+            store
+                .get_in_flight_proposal()
+                .expect("failed to get in-flight proposal"),
+            None
+        );
+    }
+
+    /// Test that a participant which was stopped while a proposal was in flight gives the proposal
+    /// and its data back to the proposal manager, requests the result of the proposal from the
+    /// coordinator when it restarts, and applies it. Applying the proposal removes the decisions
+    /// for earlier proposals.
+    #[test]
+    fn test_participant_recovery() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![1].into(),
+            peer_ids: vec![vec![0].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        let store = MemoryTwoPhaseStore::new();
+        store
+            .set_in_flight_proposal(&InFlightProposal {
+                proposal: Proposal {
+                    id: vec![1].into(),
+                    ..Default::default()
+                },
+                proposal_data: vec![1],
+                peers_verified: HashSet::new(),
+            })
+            .expect("failed to set in-flight proposal");
+        store
+            .add_decision(&vec![0].into(), TwoPhaseDecision::Apply)
+            .expect("failed to add decision");
+
+        let mut engine = TwoPhaseEngine::new_with_store(
+            Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS),
+            Box::new(store.clone()),
+        );
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Check that the decision request is sent to the coordinator
+        loop {
+            if let Some((msg, peer_id)) = network.sent_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(peer_id, &vec![0].into());
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::DECISION_REQUEST
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Receive the Apply result
+        let mut result = TwoPhaseMessage::new();
+        result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+        result.set_proposal_id(vec![1]);
+        result.set_proposal_result(TwoPhaseMessage_ProposalResult::APPLY);
+        let message_bytes = result
+            .write_to_bytes()
+            .expect("failed to write apply result to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
+            .expect("failed to send apply result");
+
+        // Verify the proposal was accepted
+        loop {
+            if let Some((id, _)) = manager.accepted_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+
+        assert_eq!(
+            manager.added_proposal_data().as_slice(),
+            &[(vec![1].into(), vec![1])]
+        );
+        // The participant did not respond to the check of the recovered proposal
+        assert!(network
+            .sent_messages()
+            .iter()
+            .all(|(msg, _)| TwoPhaseMessage::parse_from_bytes(msg)
+                .expect("failed to parse message")
+                .get_message_type()
+                == TwoPhaseMessage_Type::DECISION_REQUEST));
+        assert_eq!(
+            store
+                .get_decision(&vec![0].into())
+                .expect("failed to get decision"),
+            None
+        );
+        assert_eq!(
+            store
+                .get_decision(&vec![1].into())
+                .expect("failed to get decision"),
+            Some(TwoPhaseDecision::Apply)
+        );
+        assert_eq!(
+            store
+                .get_in_flight_proposal()
+                .expect("failed to get in-flight proposal"),
+            None
+        );
+    }
 }
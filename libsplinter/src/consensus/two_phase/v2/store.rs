@@ -0,0 +1,163 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistent state of the two-phase commit consensus engine
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use crate::consensus::{PeerId, Proposal, ProposalId};
+use crate::error::InternalError;
+
+/// The result of a proposal, as decided by the coordinator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TwoPhaseDecision {
+    Apply,
+    Reject,
+}
+
+/// A proposal that was being evaluated and has not yet been completed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InFlightProposal {
+    pub proposal: Proposal,
+    /// The data of the proposal, as provided by the proposal manager; empty if the manager does
+    /// not provide proposal data
+    pub proposal_data: Vec<u8>,
+    /// The verifiers that have approved the proposal; only tracked by the coordinator
+    pub peers_verified: HashSet<PeerId>,
+}
+
+/// Stores the in-flight proposal and the decisions made for proposals by a two-phase commit node.
+pub trait TwoPhaseStore: Send + Sync {
+    /// Returns the in-flight proposal, if there is one.
+    fn get_in_flight_proposal(&self) -> Result<Option<InFlightProposal>, InternalError>;
+
+    /// Saves the in-flight proposal, replacing the existing one.
+    fn set_in_flight_proposal(&self, proposal: &InFlightProposal) -> Result<(), InternalError>;
+
+    /// Removes the in-flight proposal, if there is one.
+    fn remove_in_flight_proposal(&self) -> Result<(), InternalError>;
+
+    /// Records the decision for the given proposal.
+    fn add_decision(
+        &self,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), InternalError>;
+
+    /// Returns the decision for the given proposal, if one has been recorded.
+    fn get_decision(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<TwoPhaseDecision>, InternalError>;
+
+    /// Removes the decisions for all proposals other than the given one.
+    fn remove_decisions_except(&self, proposal_id: &ProposalId) -> Result<(), InternalError>;
+
+    fn clone_boxed(&self) -> Box<dyn TwoPhaseStore>;
+}
+
+impl Clone for Box<dyn TwoPhaseStore> {
+    fn clone(&self) -> Self {
+        (*self).clone_boxed()
+    }
+}
+
+#[derive(Default)]
+struct MemoryTwoPhaseState {
+    in_flight_proposal: Option<InFlightProposal>,
+    decisions: HashMap<ProposalId, TwoPhaseDecision>,
+}
+
+/// A [TwoPhaseStore] that keeps its state in memory.
+///
+/// Clones of this store share the same state. The state does not survive a restart of the
+/// process, so an engine using this store cannot recover proposals that were interrupted by a
+/// restart.
+#[derive(Clone, Default)]
+pub struct MemoryTwoPhaseStore {
+    inner: Arc<Mutex<MemoryTwoPhaseState>>,
+}
+
+impl MemoryTwoPhaseStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TwoPhaseStore for MemoryTwoPhaseStore {
+    fn get_in_flight_proposal(&self) -> Result<Option<InFlightProposal>, InternalError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Two-phase store lock was poisoned".into()))?
+            .in_flight_proposal
+            .clone())
+    }
+
+    fn set_in_flight_proposal(&self, proposal: &InFlightProposal) -> Result<(), InternalError> {
+        self.inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Two-phase store lock was poisoned".into()))?
+            .in_flight_proposal = Some(proposal.clone());
+        Ok(())
+    }
+
+    fn remove_in_flight_proposal(&self) -> Result<(), InternalError> {
+        self.inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Two-phase store lock was poisoned".into()))?
+            .in_flight_proposal = None;
+        Ok(())
+    }
+
+    fn add_decision(
+        &self,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), InternalError> {
+        self.inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Two-phase store lock was poisoned".into()))?
+            .decisions
+            .insert(proposal_id.clone(), decision);
+        Ok(())
+    }
+
+    fn get_decision(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<TwoPhaseDecision>, InternalError> {
+        Ok(self
+            .inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Two-phase store lock was poisoned".into()))?
+            .decisions
+            .get(proposal_id)
+            .copied())
+    }
+
+    fn remove_decisions_except(&self, proposal_id: &ProposalId) -> Result<(), InternalError> {
+        self.inner
+            .lock()
+            .map_err(|_| InternalError::with_message("Two-phase store lock was poisoned".into()))?
+            .decisions
+            .retain(|id, _| id == proposal_id);
+        Ok(())
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TwoPhaseStore> {
+        Box::new(self.clone())
+    }
+}
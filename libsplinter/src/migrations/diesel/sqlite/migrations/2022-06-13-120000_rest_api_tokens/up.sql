@@ -0,0 +1,32 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+
+CREATE TABLE IF NOT EXISTS rest_api_tokens (
+    id                        TEXT PRIMARY KEY,
+    name                      TEXT NOT NULL,
+    owner                     TEXT NOT NULL,
+    owner_type                TEXT NOT NULL,
+    token_hash                TEXT NOT NULL UNIQUE,
+    created_at                BIGINT NOT NULL,
+    expires_at                BIGINT
+);
+
+CREATE TABLE IF NOT EXISTS rest_api_token_roles (
+    token_id                  TEXT NOT NULL,
+    role_id                   TEXT NOT NULL,
+    PRIMARY KEY (token_id, role_id),
+    FOREIGN KEY (token_id) REFERENCES rest_api_tokens(id) ON DELETE CASCADE
+);
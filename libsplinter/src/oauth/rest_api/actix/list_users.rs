@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::actix_web::{web, HttpResponse};
+use crate::actix_web::{web, Error, HttpRequest, HttpResponse};
 use crate::biome::oauth::store::OAuthUserSessionStore;
 use crate::oauth::rest_api::{
     resources::list_users::{ListOAuthUserResponse, OAuthUserResponse, PagingQuery},
@@ -20,10 +20,10 @@ use crate::oauth::rest_api::{
 };
 use crate::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
-    paging::get_response_paging_info,
+    paging::{get_response_cursor_paging_info, get_response_paging_info, Cursor},
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
-use futures::future::IntoFuture;
+use futures::{future::IntoFuture, Future};
 
 const OAUTH_USER_READ_PROTOCOL_MIN: u32 = 1;
 
@@ -36,91 +36,103 @@ pub fn make_oauth_list_users_resource(
     #[cfg(feature = "authorization")]
     {
         resource.add_method(Method::Get, OAUTH_USER_READ_PERMISSION, move |req, _| {
-            let web::Query(paging_query): web::Query<PagingQuery> =
-                match web::Query::from_query(req.query_string()) {
-                    Ok(paging_query) => paging_query,
-                    Err(_) => {
-                        return Box::new(
-                            HttpResponse::BadRequest()
-                                .json(ErrorResponse::bad_request("Invalid query"))
-                                .into_future(),
-                        )
-                    }
-                };
-            let link = format!("{}?", req.uri().path());
-            Box::new(match oauth_user_session_store.list_users() {
-                Ok(users) => {
-                    let total = users.len();
-                    let oauth_users = users
-                        .skip(paging_query.offset)
-                        .take(paging_query.limit)
-                        .collect::<Vec<_>>();
-
-                    HttpResponse::Ok()
-                        .json(ListOAuthUserResponse {
-                            data: oauth_users.iter().map(OAuthUserResponse::from).collect(),
-                            paging: get_response_paging_info(
-                                Some(paging_query.limit),
-                                Some(paging_query.offset),
-                                &link,
-                                total,
-                            ),
-                        })
-                        .into_future()
-                }
-                Err(err) => {
-                    error!("Unable to remove user session: {}", err);
-                    HttpResponse::InternalServerError()
-                        .json(ErrorResponse::internal_error())
-                        .into_future()
-                }
-            })
+            list_users(req, &*oauth_user_session_store)
         })
     }
     #[cfg(not(feature = "authorization"))]
     {
         resource.add_method(Method::Get, move |req, _| {
-            let web::Query(paging_query): web::Query<PagingQuery> =
-                match web::Query::from_query(req.query_string()) {
-                    Ok(paging_query) => paging_query,
-                    Err(_) => {
-                        return Box::new(
-                            HttpResponse::BadRequest()
-                                .json(ErrorResponse::bad_request("Invalid query"))
-                                .into_future(),
-                        )
-                    }
-                };
-            let link = format!("{}?", req.uri().path());
-
-            Box::new(match oauth_user_session_store.list_users() {
-                Ok(users) => {
-                    let total = users.total();
-                    let oauth_users = users
-                        .skip(paging_query.offset)
-                        .take(paging_query.limit)
-                        .collect::<Vec<_>>();
-                    Ok(HttpResponse::Ok().json(ListOAuthUserResponse {
-                        data: oauth_users.iter().map(OAuthUserResponse::from).collect(),
-                        paging: get_response_paging_info(
-                            Some(paging_query.limit),
-                            Some(paging_query.offset),
-                            &link,
-                            total,
-                        ),
-                    }))
-                }
-                Err(err) => {
-                    error!("Unable to remove user session: {}", err);
-                    HttpResponse::InternalServerError()
-                        .json(ErrorResponse::internal_error())
-                        .into_future()
-                }
-            })
+            list_users(req, &*oauth_user_session_store)
         })
     }
 }
 
+fn list_users(
+    req: HttpRequest,
+    oauth_user_session_store: &dyn OAuthUserSessionStore,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let web::Query(paging_query): web::Query<PagingQuery> =
+        match web::Query::from_query(req.query_string()) {
+            Ok(paging_query) => paging_query,
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid query"))
+                        .into_future(),
+                )
+            }
+        };
+
+    let cursor = match paging_query
+        .cursor
+        .as_deref()
+        .map(Cursor::decode)
+        .transpose()
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Invalid cursor value passed: {}",
+                        err
+                    )))
+                    .into_future(),
+            )
+        }
+    };
+
+    let link = format!("{}?", req.uri().path());
+    let limit = paging_query.limit;
+
+    // An explicit offset without a cursor is served as before, so that existing offset links keep
+    // working; otherwise the page is fetched from the store starting after the cursor
+    let result = match (paging_query.offset, cursor.as_ref()) {
+        (Some(offset), None) => oauth_user_session_store.list_users().map(|users| {
+            let total = users.len();
+            let users = users.skip(offset).take(limit).collect::<Vec<_>>();
+            let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+            (users, paging)
+        }),
+        _ => oauth_user_session_store.count_users().and_then(|total| {
+            // One more user than the limit is fetched to find out whether there is a next page
+            let users = oauth_user_session_store
+                .list_users_page(cursor.as_ref().map(Cursor::key), limit.saturating_add(1))?
+                .collect::<Vec<_>>();
+            let (users, has_more) = Cursor::split_page(users, limit);
+            let next_cursor = Cursor::following(
+                cursor.as_ref(),
+                limit,
+                has_more,
+                users.last().map(|user| user.subject()),
+            );
+            let paging = get_response_cursor_paging_info(
+                limit,
+                cursor.as_ref(),
+                next_cursor.as_ref(),
+                &link,
+                total,
+            );
+            Ok((users, paging))
+        }),
+    };
+
+    Box::new(match result {
+        Ok((users, paging)) => HttpResponse::Ok()
+            .json(ListOAuthUserResponse {
+                data: users.iter().map(OAuthUserResponse::from).collect(),
+                paging,
+            })
+            .into_future(),
+        Err(err) => {
+            error!("Unable to list OAuth users: {}", err);
+            HttpResponse::InternalServerError()
+                .json(ErrorResponse::internal_error())
+                .into_future()
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,7 +232,7 @@ mod tests {
     /// the request.
     /// 1. Adds 101 OAuth user sessions to the store
     /// 2. Perform a GET against /oauth/users
-    /// 3. Verify that 100 elements are initially returned, with a valid next URL
+    /// 3. Verify that 100 elements are initially returned, with a next URL holding a cursor
     /// 4. Perform a GET request against the next URL
     /// 5. Verify the final element is returned
     #[test]
@@ -264,10 +276,14 @@ mod tests {
 
         let paging = resp.paging;
 
-        assert_eq!(
-            paging,
-            create_test_paging_response(0, 100, 100, 0, 100, 101, "/oauth/users?")
+        // the next page is referenced by a cursor after the last user on this page
+        let mut expected_paging =
+            create_test_paging_response(0, 100, 100, 0, 100, 101, "/oauth/users?");
+        expected_paging.next = format!(
+            "/oauth/users?limit=100&cursor={}",
+            Cursor::new(100, "subject_98").encode()
         );
+        assert_eq!(paging, expected_paging);
 
         let next_link: String = paging.next;
 
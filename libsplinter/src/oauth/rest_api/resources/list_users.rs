@@ -15,7 +15,7 @@
 //! Defines OAuthUsers returned by the `OAuthResourceProvider`.
 
 use crate::biome::oauth::store::OAuthUser;
-use crate::rest_api::paging::{Paging, DEFAULT_LIMIT, DEFAULT_OFFSET};
+use crate::rest_api::paging::{Paging, DEFAULT_LIMIT};
 
 #[derive(Serialize)]
 pub(crate) struct ListOAuthUserResponse<'a> {
@@ -42,14 +42,12 @@ impl<'a> From<&'a OAuthUser> for OAuthUserResponse<'a> {
 pub struct PagingQuery {
     #[serde(default = "default_limit")]
     pub limit: usize,
-    #[serde(default = "default_offset")]
-    pub offset: usize,
+    #[serde(default)]
+    pub offset: Option<usize>,
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 fn default_limit() -> usize {
     DEFAULT_LIMIT
 }
-
-fn default_offset() -> usize {
-    DEFAULT_OFFSET
-}
@@ -20,7 +20,7 @@ use crate::collections::BiHashMap;
 
 use super::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
-    PeerRefRemoveError, PeerUnknownAddError,
+    PeerRefRemoveError, PeerUnknownAddError, PeerUpdateError,
 };
 use super::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
 use super::{EndpointPeerRef, PeerRef};
@@ -144,6 +144,44 @@ impl PeerManagerConnector {
             .map_err(|err| PeerUnknownAddError::ReceiveError(format!("{:?}", err)))?
     }
 
+    /// Requests that the endpoints of a peer are replaced, for example because the peer's
+    /// endpoints changed in the registry.
+    ///
+    /// Every reference to the peer is updated, regardless of the local authorization it requires.
+    /// If the peer is not referenced, nothing is changed. A connected peer keeps its current
+    /// connection; the new endpoints are used the next time it has to be reconnected.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` -  The unique PeerAuthorizationToken for the peer.
+    /// * `endpoints` -  The new list of endpoints associated with the peer, in order of
+    ///   preference.
+    pub fn update_peer_endpoints(
+        &self,
+        peer_id: PeerAuthorizationToken,
+        endpoints: Vec<String>,
+    ) -> Result<(), PeerUpdateError> {
+        let (sender, recv) = channel();
+
+        let message = PeerManagerMessage::Request(PeerManagerRequest::UpdatePeerEndpoints {
+            peer_id,
+            endpoints,
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerUpdateError(
+                    "unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerUpdateError(format!("{:?}", err)))?
+    }
+
     /// Requests the list of currently connected peers.
     ///
     /// Returns the list of peer IDs.
@@ -0,0 +1,70 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A registry subscriber that keeps the endpoints of peers up to date.
+
+use crate::hex::parse_hex;
+use crate::registry::{RegistryEvent, RegistryEventSubscriber, RegistrySubscriberError};
+
+use super::{PeerAuthorizationToken, PeerManagerConnector};
+
+/// Updates the endpoints of the `PeerManager`'s peers when their nodes change in the registry.
+///
+/// When a node is added to or updated in the registry, the endpoints of the peer with the node's
+/// ID, and of the peers identified by any of the node's keys, are replaced with the node's
+/// endpoints. This allows peers to be reconnected at their new endpoints without restarting the
+/// node. Removed nodes are ignored, since the peers may still be referenced by circuits.
+pub struct PeerEndpointUpdater {
+    peer_connector: PeerManagerConnector,
+}
+
+impl PeerEndpointUpdater {
+    /// Constructs a new `PeerEndpointUpdater` that updates the peers of the `PeerManager` with
+    /// the given connector.
+    pub fn new(peer_connector: PeerManagerConnector) -> Self {
+        Self { peer_connector }
+    }
+}
+
+impl RegistryEventSubscriber for PeerEndpointUpdater {
+    fn handle_event(&self, event: &RegistryEvent) -> Result<(), RegistrySubscriberError> {
+        let node = match event {
+            RegistryEvent::NodeAdded(node) | RegistryEvent::NodeUpdated(node) => node,
+            RegistryEvent::NodeRemoved(_) => return Ok(()),
+        };
+
+        let peer_ids = std::iter::once(PeerAuthorizationToken::from_peer_id(node.identity()))
+            .chain(node.keys().iter().filter_map(|key| match parse_hex(key) {
+                Ok(public_key) => Some(PeerAuthorizationToken::from_public_key(&public_key)),
+                Err(_) => {
+                    warn!("Ignoring invalid key {} of node {}", key, node.identity());
+                    None
+                }
+            }));
+
+        for peer_id in peer_ids {
+            self.peer_connector
+                .update_peer_endpoints(peer_id, node.endpoints().to_vec())
+                .map_err(|err| {
+                    RegistrySubscriberError::UnableToHandleEvent(format!(
+                        "Unable to update endpoints of node {}: {}",
+                        node.identity(),
+                        err
+                    ))
+                })?;
+        }
+
+        Ok(())
+    }
+}
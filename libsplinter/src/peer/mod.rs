@@ -24,6 +24,8 @@
 
 mod builder;
 mod connector;
+#[cfg(feature = "registry-notifications")]
+mod endpoint_updater;
 mod error;
 pub mod interconnect;
 mod notification;
@@ -50,9 +52,11 @@ use crate::threading::pacemaker;
 pub use self::builder::PeerManagerBuilder;
 use self::connector::PeerRemover;
 pub use self::connector::{PeerLookup, PeerManagerConnector};
+#[cfg(feature = "registry-notifications")]
+pub use self::endpoint_updater::PeerEndpointUpdater;
 use self::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
-    PeerRefRemoveError, PeerUnknownAddError,
+    PeerRefRemoveError, PeerUnknownAddError, PeerUpdateError,
 };
 pub use self::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
 use self::notification::{Subscriber, SubscriberMap};
@@ -104,6 +108,11 @@ pub(crate) enum PeerManagerRequest {
         connection_id: String,
         sender: Sender<Result<(), PeerRefRemoveError>>,
     },
+    UpdatePeerEndpoints {
+        peer_id: PeerAuthorizationToken,
+        endpoints: Vec<String>,
+        sender: Sender<Result<(), PeerUpdateError>>,
+    },
     ListPeers {
         sender: Sender<Result<Vec<PeerAuthorizationToken>, PeerListError>>,
     },
@@ -453,6 +462,18 @@ fn handle_request(
                 warn!("Connector dropped before receiving result of removing peer");
             }
         }
+        PeerManagerRequest::UpdatePeerEndpoints {
+            peer_id,
+            endpoints,
+            sender,
+        } => {
+            if sender
+                .send(update_peer_endpoints(peer_id, endpoints, connector, peers))
+                .is_err()
+            {
+                warn!("Connector dropped before receiving result of updating peer endpoints");
+            }
+        }
         PeerManagerRequest::ListPeers { sender } => {
             if sender.send(Ok(peers.peer_ids())).is_err() {
                 warn!("Connector dropped before receiving result of list peers");
@@ -695,6 +716,70 @@ fn add_peer(
     Ok(peer_ref)
 }
 
+// Replace the endpoints of every peer with the given peer ID. Connected peers keep their current
+// connection until it is lost; peers that are not connected and whose active endpoint was removed
+// are reconnected using the new endpoints right away.
+fn update_peer_endpoints(
+    peer_id: PeerAuthorizationToken,
+    endpoints: Vec<String>,
+    connector: Connector,
+    peers: &mut PeerMap,
+) -> Result<(), PeerUpdateError> {
+    if endpoints.is_empty() {
+        return Err(PeerUpdateError(format!(
+            "no endpoints provided for peer {}",
+            peer_id
+        )));
+    }
+
+    for mut peer_metadata in peers.get_by_authorization_token(&peer_id) {
+        if peer_metadata.endpoints == endpoints {
+            continue;
+        }
+
+        info!("Updating peer {} to endpoints {:?}", peer_id, endpoints);
+        peer_metadata.endpoints = endpoints.clone();
+
+        if peer_metadata.status != PeerStatus::Connected
+            && !endpoints.contains(&peer_metadata.active_endpoint)
+        {
+            if let Err(err) = connector
+                .remove_connection(&peer_metadata.active_endpoint, &peer_metadata.connection_id)
+            {
+                error!("Unable to clean up old connection: {}", err);
+            }
+
+            for endpoint in endpoints.iter() {
+                match connector.request_connection(
+                    endpoint,
+                    &peer_metadata.connection_id,
+                    Some(peer_id.clone().into()),
+                    Some(peer_metadata.required_local_auth.clone().into()),
+                ) {
+                    Ok(()) => {
+                        peer_metadata.active_endpoint = endpoint.to_string();
+                        break;
+                    }
+                    // If request_connection errored we will retry in the future
+                    Err(err) => {
+                        log_connect_request_err(err, &peer_id, endpoint);
+                    }
+                }
+            }
+
+            if !endpoints.contains(&peer_metadata.active_endpoint) {
+                peer_metadata.active_endpoint = endpoints[0].to_string();
+            }
+            peer_metadata.status = PeerStatus::Pending;
+            peer_metadata.last_connection_attempt = Instant::now();
+        }
+
+        peers.update_peer(peer_metadata)?;
+    }
+
+    Ok(())
+}
+
 // Request a connection, the resulting connection will be treated as an InboundConnection
 fn add_unidentified(
     endpoint: String,
@@ -2693,6 +2778,87 @@ pub mod tests {
         mesh1.wait_for_shutdown().expect("Unable to shutdown mesh");
     }
 
+    // Test that a peer that could not be reached at its endpoint is connected at its new endpoint
+    // after its endpoints are updated.
+    //
+    // 1. add test_peer with an endpoint that nothing is listening on
+    // 2. update test_peer's endpoints to the endpoint that is being listened on
+    // 3. verify that a Connected notification is received for test_peer
+    #[test]
+    fn test_peer_manager_update_peer_endpoints() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(tx)
+            .expect("Unable to get subscriber");
+        let _peer_ref = peer_connector
+            .add_peer_ref(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec!["inproc://unreachable".to_string()],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .expect("Unable to add peer");
+
+        peer_connector
+            .update_peer_endpoints(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec!["inproc://test".to_string()],
+            )
+            .expect("Unable to update peer endpoints");
+
+        // timeout after 60 seconds
+        let timeout = Duration::from_secs(60);
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: PeerTokenPair::new(
+                        PeerAuthorizationToken::from_peer_id("test_peer"),
+                        PeerAuthorizationToken::from_peer_id("my_id"),
+                    )
+                }
+        );
+
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
     // Test that the PeerManager can be started and stopped
     #[test]
     fn test_peer_manager_shutdown() {
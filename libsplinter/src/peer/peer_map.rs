@@ -208,6 +208,19 @@ impl PeerMap {
                 }
             }
 
+            // Remove the peer from any endpoints it no longer has
+            for endpoint in peer_entry.get().endpoints.iter() {
+                if peer_metadata.endpoints.contains(endpoint) {
+                    continue;
+                }
+                if let Some(peer_tokens) = self.endpoints.get_mut(endpoint) {
+                    peer_tokens.remove(&peer_token_pair);
+                    if peer_tokens.is_empty() {
+                        self.endpoints.remove(endpoint);
+                    }
+                }
+            }
+
             if peer_metadata.connection_id != peer_entry.get().connection_id {
                 self.removed_connection_ids
                     .insert(peer_entry.get().connection_id.to_string(), peer_token_pair);
@@ -250,6 +263,19 @@ impl PeerMap {
         self.peers.get(peer_id)
     }
 
+    /// Returns the metadatas for the peers with the provided peer ID, regardless of the required
+    /// local authorization
+    pub fn get_by_authorization_token(
+        &self,
+        peer_id: &PeerAuthorizationToken,
+    ) -> Vec<PeerMetadata> {
+        self.peers
+            .values()
+            .filter(|meta| &meta.id == peer_id)
+            .cloned()
+            .collect()
+    }
+
     /// Returns the metadata for a peer from the provided connection ID
     pub fn get_by_connection_id(&self, connection_id: &str) -> Option<&PeerMetadata> {
         self.peers
@@ -654,4 +680,45 @@ pub mod tests {
             PeerStatus::Disconnected { retry_attempts: 5 }
         );
     }
+
+    // Test that endpoints removed from a peer by an update no longer refer to the peer
+    //  1. Insert test_peer with endpoints test_endpoint1 and test_endpoint2
+    //  2. Update test_peer to only have the endpoint new_endpoint
+    //  3. Check that test_peer can be found by new_endpoint, but not by the old endpoints
+    #[test]
+    fn test_update_peer_removes_old_endpoints() {
+        let mut peer_map = PeerMap::new(10);
+
+        peer_map.insert(
+            PeerAuthorizationToken::from_peer_id("test_peer"),
+            "connection_id".to_string(),
+            vec!["test_endpoint1".to_string(), "test_endpoint2".to_string()],
+            "test_endpoint1".to_string(),
+            PeerStatus::Pending,
+            PeerAuthorizationToken::from_peer_id("my_id"),
+            vec![],
+        );
+
+        let mut peer_metadata = peer_map
+            .get_by_authorization_token(&PeerAuthorizationToken::from_peer_id("test_peer"))
+            .pop()
+            .expect("Unable to retrieve peer metadata");
+        peer_metadata.endpoints = vec!["new_endpoint".to_string()];
+        peer_metadata.active_endpoint = "new_endpoint".to_string();
+
+        peer_map
+            .update_peer(peer_metadata)
+            .expect("Unable to update endpoints");
+
+        assert!(peer_map.contains_endpoint("new_endpoint"));
+        assert!(!peer_map.contains_endpoint("test_endpoint1"));
+        assert!(!peer_map.contains_endpoint("test_endpoint2"));
+        assert_eq!(
+            peer_map
+                .get_peer_from_endpoint("new_endpoint")
+                .expect("Unable to get peer by endpoint")[0]
+                .id,
+            PeerAuthorizationToken::from_peer_id("test_peer")
+        );
+    }
 }
@@ -30,6 +30,8 @@ use diesel::r2d2::{ConnectionManager, Pool};
 
 use crate::store::pool::ConnectionPool;
 
+#[cfg(feature = "registry-notifications")]
+use super::{notification::RegistrySubscribers, RegistryEvent, RegistryEventSubscriber};
 use super::{
     MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
@@ -44,8 +46,13 @@ use operations::update_node::RegistryUpdateNodeOperation as _;
 use operations::RegistryOperations;
 
 /// A database-backed registry, powered by [`Diesel`](https://crates.io/crates/diesel).
+///
+/// Subscribers added to the registry are notified of the changes made through the registry or
+/// any of its clones; changes made to the database by other processes are not observed.
 pub struct DieselRegistry<C: diesel::Connection + 'static> {
     connection_pool: ConnectionPool<C>,
+    #[cfg(feature = "registry-notifications")]
+    subscribers: RegistrySubscribers,
 }
 
 impl<C: diesel::Connection> DieselRegistry<C> {
@@ -57,6 +64,8 @@ impl<C: diesel::Connection> DieselRegistry<C> {
     pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
         DieselRegistry {
             connection_pool: connection_pool.into(),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: RegistrySubscribers::default(),
         }
     }
 
@@ -73,6 +82,8 @@ impl<C: diesel::Connection> DieselRegistry<C> {
     ) -> Self {
         Self {
             connection_pool: connection_pool.into(),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: RegistrySubscribers::default(),
         }
     }
 }
@@ -82,6 +93,8 @@ impl Clone for DieselRegistry<diesel::pg::PgConnection> {
     fn clone(&self) -> Self {
         Self {
             connection_pool: self.connection_pool.clone(),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: self.subscribers.clone(),
         }
     }
 }
@@ -91,6 +104,8 @@ impl Clone for DieselRegistry<diesel::sqlite::SqliteConnection> {
     fn clone(&self) -> Self {
         Self {
             connection_pool: self.connection_pool.clone(),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: self.subscribers.clone(),
         }
     }
 }
@@ -107,7 +122,20 @@ where
     ) -> Result<NodeIter<'a>, RegistryError> {
         self.connection_pool.execute_read(|conn| {
             RegistryOperations::new(conn)
-                .list_nodes(predicates)
+                .list_nodes(predicates, None, None)
+                .map(|nodes| Box::new(nodes.into_iter()) as NodeIter<'a>)
+        })
+    }
+
+    fn list_nodes_page<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        self.connection_pool.execute_read(|conn| {
+            RegistryOperations::new(conn)
+                .list_nodes(predicates, after, Some(limit))
                 .map(|nodes| Box::new(nodes.into_iter()) as NodeIter<'a>)
         })
     }
@@ -126,41 +154,100 @@ where
         self.connection_pool
             .execute_read(|conn| RegistryOperations::new(conn).has_node(identity))
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        self.subscribers.add(subscriber);
+        Ok(())
+    }
 }
 
 #[cfg(feature = "postgres")]
 impl RegistryWriter for DieselRegistry<diesel::pg::PgConnection> {
     fn add_node(&self, node: Node) -> Result<(), RegistryError> {
+        #[cfg(feature = "registry-notifications")]
+        let event = RegistryEvent::NodeAdded(node.clone());
+
         self.connection_pool
-            .execute_write(|conn| RegistryOperations::new(conn).add_node(node))
+            .execute_write(|conn| RegistryOperations::new(conn).add_node(node))?;
+
+        #[cfg(feature = "registry-notifications")]
+        self.subscribers.notify(&[event]);
+
+        Ok(())
     }
 
     fn update_node(&self, node: Node) -> Result<(), RegistryError> {
+        #[cfg(feature = "registry-notifications")]
+        let event = RegistryEvent::NodeUpdated(node.clone());
+
         self.connection_pool
-            .execute_write(|conn| RegistryOperations::new(conn).update_node(node))
+            .execute_write(|conn| RegistryOperations::new(conn).update_node(node))?;
+
+        #[cfg(feature = "registry-notifications")]
+        self.subscribers.notify(&[event]);
+
+        Ok(())
     }
 
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
-        self.connection_pool
-            .execute_write(|conn| RegistryOperations::new(conn).delete_node(identity))
+        let deleted = self
+            .connection_pool
+            .execute_write(|conn| RegistryOperations::new(conn).delete_node(identity))?;
+
+        #[cfg(feature = "registry-notifications")]
+        if let Some(node) = &deleted {
+            self.subscribers
+                .notify(&[RegistryEvent::NodeRemoved(node.clone())]);
+        }
+
+        Ok(deleted)
     }
 }
 
 #[cfg(feature = "sqlite")]
 impl RegistryWriter for DieselRegistry<diesel::sqlite::SqliteConnection> {
     fn add_node(&self, node: Node) -> Result<(), RegistryError> {
+        #[cfg(feature = "registry-notifications")]
+        let event = RegistryEvent::NodeAdded(node.clone());
+
         self.connection_pool
-            .execute_write(|conn| RegistryOperations::new(conn).add_node(node))
+            .execute_write(|conn| RegistryOperations::new(conn).add_node(node))?;
+
+        #[cfg(feature = "registry-notifications")]
+        self.subscribers.notify(&[event]);
+
+        Ok(())
     }
 
     fn update_node(&self, node: Node) -> Result<(), RegistryError> {
+        #[cfg(feature = "registry-notifications")]
+        let event = RegistryEvent::NodeUpdated(node.clone());
+
         self.connection_pool
-            .execute_write(|conn| RegistryOperations::new(conn).update_node(node))
+            .execute_write(|conn| RegistryOperations::new(conn).update_node(node))?;
+
+        #[cfg(feature = "registry-notifications")]
+        self.subscribers.notify(&[event]);
+
+        Ok(())
     }
 
     fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
-        self.connection_pool
-            .execute_write(|conn| RegistryOperations::new(conn).delete_node(identity))
+        let deleted = self
+            .connection_pool
+            .execute_write(|conn| RegistryOperations::new(conn).delete_node(identity))?;
+
+        #[cfg(feature = "registry-notifications")]
+        if let Some(node) = &deleted {
+            self.subscribers
+                .notify(&[RegistryEvent::NodeRemoved(node.clone())]);
+        }
+
+        Ok(deleted)
     }
 }
 
@@ -204,8 +291,12 @@ where
 pub mod tests {
     use super::*;
 
+    #[cfg(feature = "postgres")]
+    use crate::migrations::run_postgres_migrations;
     use crate::migrations::run_sqlite_migrations;
 
+    #[cfg(feature = "postgres")]
+    use diesel::{pg::PgConnection, RunQueryDsl};
     use diesel::{
         r2d2::{ConnectionManager, Pool},
         sqlite::SqliteConnection,
@@ -281,6 +372,54 @@ pub mod tests {
         assert!(registry.update_node(node).is_err());
     }
 
+    /// Verifies that subscribers are notified of the changes made through the registry and its
+    /// clones, and that failed writes do not produce events
+    ///
+    /// 1. Setup sqlite database
+    /// 2. Add a subscriber to a clone of the registry
+    /// 3. Add, update, and delete node 1 and verify that the matching events are received
+    /// 4. Attempt to delete a node that does not exist and verify that no event is received
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn test_subscriber_notified() {
+        let pool = create_connection_pool_and_migrate();
+        let registry = DieselRegistry::new(pool);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        registry
+            .clone()
+            .add_subscriber(Box::new(ChannelSubscriber(tx)))
+            .expect("Unable to add subscriber");
+
+        registry
+            .add_node(get_node_1())
+            .expect("Unable to insert node");
+
+        let mut node = get_node_1();
+        node.display_name = "Changed Name".to_string();
+        registry
+            .update_node(node.clone())
+            .expect("Unable to update node 1");
+
+        registry
+            .delete_node(&node.identity)
+            .expect("Unable to delete node 1");
+
+        assert!(registry
+            .delete_node("Node-not-found")
+            .expect("Unable to delete missing node")
+            .is_none());
+
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![
+                RegistryEvent::NodeAdded(get_node_1()),
+                RegistryEvent::NodeUpdated(node.clone()),
+                RegistryEvent::NodeRemoved(node),
+            ]
+        );
+    }
+
     ///  Test that a new node can be inserted into the registry and fetched
     ///
     /// 1. Setup sqlite database
@@ -355,6 +494,40 @@ pub mod tests {
         assert_eq!(nodes[1], get_node_2());
     }
 
+    /// Verifies that list_nodes_page returns pages of nodes ordered by identity.
+    ///
+    /// 1. Setup sqlite database
+    /// 2. Insert nodes 3, 1 and 2
+    /// 3. Validate that the first page of two nodes contains nodes 1 and 2
+    /// 4. Validate that the page after node 2 contains only node 3
+    /// 5. Validate that the page after node 3 is empty
+    #[test]
+    fn test_list_nodes_page() {
+        let pool = create_connection_pool_and_migrate();
+        let registry = DieselRegistry::new(pool);
+
+        for node in vec![get_node_3(), get_node_1(), get_node_2()] {
+            registry.add_node(node).expect("Unable to insert node");
+        }
+
+        let nodes = registry
+            .list_nodes_page(&[], None, 2)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+        assert_eq!(nodes, vec![get_node_1(), get_node_2()]);
+
+        let nodes = registry
+            .list_nodes_page(&[], Some("Node-456"), 2)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+        assert_eq!(nodes, vec![get_node_3()]);
+
+        let nodes = registry
+            .list_nodes_page(&[], Some("Node-789"), 2)
+            .expect("Failed to retrieve nodes");
+        assert_eq!(nodes.len(), 0);
+    }
+
     /// Verifies that list_nodes returns an empty list when there are no nodes in the registry.
     ///
     /// 1. Setup sqlite database
@@ -726,6 +899,141 @@ pub mod tests {
         assert_eq!(count, 2);
     }
 
+    /// Verifies that the `In`, `Exists`, `NotExists`, `Prefix`, numeric, `HasKey` and
+    /// `HasEndpoint` predicates filter nodes correctly in a SQLite-backed registry.
+    #[test]
+    fn test_list_nodes_expanded_predicates() {
+        let pool = create_connection_pool_and_migrate();
+        check_expanded_predicates(&DieselRegistry::new(pool));
+    }
+
+    /// Verifies that the expanded predicates filter nodes correctly in a PostgreSQL-backed
+    /// registry.
+    ///
+    /// This test requires a PostgreSQL database, whose URL is given by the
+    /// `SPLINTER_TEST_POSTGRES_URL` environment variable; the registry tables in the database are
+    /// cleared by the test. Run it with `cargo test -- --ignored`.
+    #[cfg(feature = "postgres")]
+    #[test]
+    #[ignore]
+    fn test_list_nodes_expanded_predicates_postgres() {
+        let pool = create_postgres_connection_pool_and_migrate();
+        check_expanded_predicates(&DieselRegistry::new(pool));
+    }
+
+    /// Adds three nodes to the `registry`, two with a numeric "capacity" metadata value, and
+    /// verifies that listing and counting the nodes with each of the expanded predicates returns
+    /// the expected nodes.
+    fn check_expanded_predicates(registry: &dyn RwRegistry) {
+        let node_1 = Node::builder("Node-123")
+            .with_endpoint("tcps://12.0.0.123:8431")
+            .with_display_name("Bitwise IO - Node 1")
+            .with_key("abcd")
+            .with_metadata("company", "Bitwise IO")
+            .with_metadata("capacity", "9")
+            .build()
+            .expect("Failed to build node1");
+        let node_2 = Node::builder("Node-456")
+            .with_endpoint("tcps://12.0.0.123:8434")
+            .with_display_name("Cargill - Node 1")
+            .with_key("0123")
+            .with_metadata("company", "Cargill")
+            .with_metadata("capacity", "10")
+            .build()
+            .expect("Failed to build node2");
+
+        registry.add_node(node_1).expect("Unable to insert node");
+        registry.add_node(node_2).expect("Unable to insert node");
+        registry
+            .add_node(get_node_3())
+            .expect("Unable to insert node");
+
+        let check = |predicates: Vec<MetadataPredicate>, expected: &[&str]| {
+            let mut identities = registry
+                .list_nodes(&predicates)
+                .expect("Failed to retrieve nodes")
+                .map(|node| node.identity().to_string())
+                .collect::<Vec<_>>();
+            identities.sort();
+            assert_eq!(identities, expected);
+
+            let count = registry
+                .count_nodes(&predicates)
+                .expect("Failed to count nodes");
+            assert_eq!(count as usize, expected.len());
+        };
+
+        check(
+            vec![MetadataPredicate::In(
+                "company".into(),
+                vec!["Bitwise IO".into(), "Other".into()],
+            )],
+            &["Node-123"],
+        );
+        check(vec![MetadataPredicate::In("company".into(), vec![])], &[]);
+        check(
+            vec![MetadataPredicate::Exists("capacity".into())],
+            &["Node-123", "Node-456"],
+        );
+        check(
+            vec![MetadataPredicate::NotExists("capacity".into())],
+            &["Node-789"],
+        );
+        check(
+            vec![MetadataPredicate::Prefix("company".into(), "Car".into())],
+            &["Node-456", "Node-789"],
+        );
+        check(
+            vec![MetadataPredicate::Prefix("company".into(), "car".into())],
+            &[],
+        );
+        // "10" is less than "9" as a string, but not as a number
+        check(
+            vec![MetadataPredicate::Lt("capacity".into(), "9".into())],
+            &["Node-456"],
+        );
+        check(
+            vec![MetadataPredicate::NumGt("capacity".into(), 9.5)],
+            &["Node-456"],
+        );
+        check(
+            vec![MetadataPredicate::NumLe("capacity".into(), 10.0)],
+            &["Node-123", "Node-456"],
+        );
+        check(
+            vec![
+                MetadataPredicate::NumGe("capacity".into(), 9.0),
+                MetadataPredicate::Prefix("company".into(), "Bit".into()),
+            ],
+            &["Node-123"],
+        );
+        check(
+            vec![MetadataPredicate::HasKey("0123".into())],
+            &["Node-456"],
+        );
+        check(
+            vec![MetadataPredicate::HasEndpoint(
+                "tcps://12.0.0.123:8435".into(),
+            )],
+            &["Node-789"],
+        );
+
+        // A page is limited after the numeric predicates are applied
+        let predicates = vec![MetadataPredicate::NumGe("capacity".into(), 0.0)];
+        let page = registry
+            .list_nodes_page(&predicates, None, 1)
+            .expect("Failed to retrieve page")
+            .map(|node| node.identity().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(page, vec!["Node-123"]);
+        let page = registry
+            .list_nodes_page(&predicates, Some("Node-123"), 1)
+            .expect("Failed to retrieve page")
+            .map(|node| node.identity().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(page, vec!["Node-456"]);
+    }
+
     fn get_node_1() -> Node {
         Node::builder("Node-123")
             .with_endpoint("tcps://12.0.0.123:8431")
@@ -759,6 +1067,21 @@ pub mod tests {
             .expect("Failed to build node3")
     }
 
+    #[cfg(feature = "registry-notifications")]
+    struct ChannelSubscriber(std::sync::mpsc::Sender<RegistryEvent>);
+
+    #[cfg(feature = "registry-notifications")]
+    impl RegistryEventSubscriber for ChannelSubscriber {
+        fn handle_event(
+            &self,
+            event: &RegistryEvent,
+        ) -> Result<(), crate::registry::RegistrySubscriberError> {
+            self.0
+                .send(event.clone())
+                .map_err(|_| crate::registry::RegistrySubscriberError::Unsubscribe)
+        }
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection ensures that the same DB is used for all operations.
@@ -774,4 +1097,36 @@ pub mod tests {
 
         pool
     }
+
+    /// Creates a connection pool for the PostgreSQL database at the URL given by the
+    /// `SPLINTER_TEST_POSTGRES_URL` environment variable, runs migrations, and removes any nodes
+    /// left in the registry tables.
+    #[cfg(feature = "postgres")]
+    fn create_postgres_connection_pool_and_migrate() -> Pool<ConnectionManager<PgConnection>> {
+        let url = std::env::var("SPLINTER_TEST_POSTGRES_URL")
+            .expect("SPLINTER_TEST_POSTGRES_URL must be set to run PostgreSQL tests");
+        let connection_manager = ConnectionManager::<PgConnection>::new(url);
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        let conn = pool.get().expect("Failed to get connection for migrations");
+        run_postgres_migrations(&*conn).expect("Failed to run migrations");
+
+        diesel::delete(schema::splinter_nodes_metadata::table)
+            .execute(&*conn)
+            .expect("Failed to clear node metadata");
+        diesel::delete(schema::splinter_nodes_keys::table)
+            .execute(&*conn)
+            .expect("Failed to clear node keys");
+        diesel::delete(schema::splinter_nodes_endpoints::table)
+            .execute(&*conn)
+            .expect("Failed to clear node endpoints");
+        diesel::delete(schema::splinter_nodes::table)
+            .execute(&*conn)
+            .expect("Failed to clear nodes");
+
+        pool
+    }
 }
@@ -20,7 +20,7 @@ use diesel::{dsl::count_star, prelude::*};
 use crate::error::InternalError;
 use crate::registry::{diesel::schema::splinter_nodes, MetadataPredicate, RegistryError};
 
-use super::{apply_predicate_filters, RegistryOperations};
+use super::{apply_predicate_filters, list_nodes::RegistryListNodesOperation, RegistryOperations};
 
 pub(in crate::registry::diesel) trait RegistryCountNodesOperation {
     fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError>;
@@ -30,9 +30,19 @@ impl<'a, C> RegistryCountNodesOperation for RegistryOperations<'a, C>
 where
     C: diesel::Connection,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
     fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
-        if predicates.is_empty() {
+        if predicates.iter().any(MetadataPredicate::is_numeric) {
+            // Numeric predicates can only be applied to the loaded nodes, so count those
+            let count = self.list_nodes(predicates, None, None)?.len();
+
+            Ok(u32::try_from(count).map_err(|_| {
+                RegistryError::InternalError(InternalError::with_message(
+                    "The number of nodes is larger than the max u32".to_string(),
+                ))
+            })?)
+        } else if predicates.is_empty() {
             // No predicates were specified, just count all nodes
             let count = splinter_nodes::table
                 .count()
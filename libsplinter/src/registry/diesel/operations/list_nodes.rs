@@ -30,7 +30,14 @@ use crate::registry::{
 use super::{apply_predicate_filters, RegistryOperations};
 
 pub(in crate::registry::diesel) trait RegistryListNodesOperation {
-    fn list_nodes(&self, predicates: &[MetadataPredicate]) -> Result<Vec<Node>, RegistryError>;
+    /// Lists the nodes that match the predicates. If a limit is given, the nodes are ordered by
+    /// identity, starting after the identity `after`, and at most `limit` nodes are returned.
+    fn list_nodes(
+        &self,
+        predicates: &[MetadataPredicate],
+        after: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Node>, RegistryError>;
 }
 
 impl<'a, C> RegistryListNodesOperation for RegistryOperations<'a, C>
@@ -38,9 +45,21 @@ where
     C: diesel::Connection,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
 {
-    fn list_nodes(&self, predicates: &[MetadataPredicate]) -> Result<Vec<Node>, RegistryError> {
-        self.conn.transaction::<_, _, _>(|| {
-            let nodes: Vec<NodesModel> = if predicates.is_empty() {
+    fn list_nodes(
+        &self,
+        predicates: &[MetadataPredicate],
+        after: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Node>, RegistryError> {
+        // Numeric predicates are applied to the loaded nodes, so the limit can only be applied
+        // after they are
+        let numeric_predicates = predicates
+            .iter()
+            .filter(|predicate| predicate.is_numeric())
+            .collect::<Vec<_>>();
+
+        let mut nodes = self.conn.transaction::<_, RegistryError, _>(|| {
+            let nodes: Vec<NodesModel> = if predicates.is_empty() && limit.is_none() {
                 // No predicates were specified, just get all nodes
                 splinter_nodes::table.load(self.conn)?
             } else {
@@ -50,6 +69,18 @@ where
                     .select(splinter_nodes::all_columns);
 
                 query = apply_predicate_filters(query, predicates);
+
+                // A page of nodes is ordered by identity and starts after the given identity
+                if let Some(limit) = limit {
+                    if let Some(after) = after {
+                        query = query.filter(splinter_nodes::identity.gt(after));
+                    }
+                    query = query.order(splinter_nodes::identity);
+                    if numeric_predicates.is_empty() {
+                        query = query.limit(limit as i64);
+                    }
+                }
+
                 query.load(self.conn)?
             };
 
@@ -106,6 +137,19 @@ where
                     })
                     .collect::<Result<Vec<_>, _>>()
             }
-        })
+        })?;
+
+        if !numeric_predicates.is_empty() {
+            nodes.retain(|node| {
+                numeric_predicates
+                    .iter()
+                    .all(|predicate| predicate.apply(node))
+            });
+            if let Some(limit) = limit {
+                nodes.truncate(limit);
+            }
+        }
+
+        Ok(nodes)
     }
 }
@@ -23,13 +23,18 @@ pub(super) mod list_nodes;
 pub(super) mod update_node;
 
 use diesel::{
-    dsl::{exists, not},
+    dsl::{exists, not, sql},
     prelude::*,
+    sql_types::{Bool, Integer, Text},
 };
 
-use crate::registry::diesel::schema::{splinter_nodes, splinter_nodes_metadata};
+use crate::registry::diesel::schema::{
+    splinter_nodes, splinter_nodes_endpoints, splinter_nodes_keys, splinter_nodes_metadata,
+};
 use crate::registry::MetadataPredicate;
 
+sql_function!(fn substr(value: Text, start: Integer, length: Integer) -> Text);
+
 pub struct RegistryOperations<'a, C> {
     conn: &'a C,
 }
@@ -49,6 +54,9 @@ type BoxedNodeQuery<'a, DB> = diesel::query_builder::BoxedSelectStatement<
 
 /// Takes a query of type `splinter_nodes::table.into_boxed().select(splinter_nodes::all_columns)`
 /// and updates the query for any provided predicates
+///
+/// Numeric predicates are not applied, because metadata values can't be compared as numbers in
+/// the same way by every backend; these must be applied to the loaded nodes instead.
 fn apply_predicate_filters<'a, DB: 'a>(
     query: BoxedNodeQuery<'a, DB>,
     predicates: &'a [MetadataPredicate],
@@ -133,6 +141,87 @@ where
                     ),
                 ));
             }
+
+            MetadataPredicate::In(key, vals) => {
+                if vals.is_empty() {
+                    // No value can match an empty list; this also avoids an empty `IN` statement
+                    query = query.filter(sql::<Bool>("1 = 0"));
+                } else {
+                    query = query.filter(exists(
+                        splinter_nodes_metadata::table.filter(
+                            splinter_nodes_metadata::identity
+                                .eq(splinter_nodes::identity)
+                                .and(splinter_nodes_metadata::key.eq(key))
+                                .and(splinter_nodes_metadata::value.eq_any(vals)),
+                        ),
+                    ));
+                }
+            }
+
+            MetadataPredicate::Exists(key) => {
+                query = query.filter(exists(
+                    splinter_nodes_metadata::table.filter(
+                        splinter_nodes_metadata::identity
+                            .eq(splinter_nodes::identity)
+                            .and(splinter_nodes_metadata::key.eq(key)),
+                    ),
+                ));
+            }
+
+            MetadataPredicate::NotExists(key) => {
+                query = query.filter(not(exists(
+                    splinter_nodes_metadata::table.filter(
+                        splinter_nodes_metadata::identity
+                            .eq(splinter_nodes::identity)
+                            .and(splinter_nodes_metadata::key.eq(key)),
+                    ),
+                )));
+            }
+
+            MetadataPredicate::Prefix(key, prefix) => {
+                // `LIKE` is case-insensitive in SQLite, so compare the leading characters instead
+                query = query.filter(exists(
+                    splinter_nodes_metadata::table.filter(
+                        splinter_nodes_metadata::identity
+                            .eq(splinter_nodes::identity)
+                            .and(splinter_nodes_metadata::key.eq(key))
+                            .and(
+                                substr(
+                                    splinter_nodes_metadata::value,
+                                    1,
+                                    prefix.chars().count() as i32,
+                                )
+                                .eq(prefix),
+                            ),
+                    ),
+                ));
+            }
+
+            MetadataPredicate::HasKey(key) => {
+                query = query.filter(exists(
+                    splinter_nodes_keys::table.filter(
+                        splinter_nodes_keys::identity
+                            .eq(splinter_nodes::identity)
+                            .and(splinter_nodes_keys::key.eq(key)),
+                    ),
+                ));
+            }
+
+            MetadataPredicate::HasEndpoint(endpoint) => {
+                query = query.filter(exists(
+                    splinter_nodes_endpoints::table.filter(
+                        splinter_nodes_endpoints::identity
+                            .eq(splinter_nodes::identity)
+                            .and(splinter_nodes_endpoints::endpoint.eq(endpoint)),
+                    ),
+                ));
+            }
+
+            MetadataPredicate::NumEq(..)
+            | MetadataPredicate::NumGt(..)
+            | MetadataPredicate::NumGe(..)
+            | MetadataPredicate::NumLt(..)
+            | MetadataPredicate::NumLe(..) => (),
         }
     }
 
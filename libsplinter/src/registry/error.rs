@@ -74,6 +74,31 @@ impl From<InternalError> for RegistryError {
     }
 }
 
+/// Returned by a registry event subscriber when it cannot handle an event
+#[cfg(feature = "registry-notifications")]
+#[derive(Debug)]
+pub enum RegistrySubscriberError {
+    /// The event could not be handled, but the subscriber should continue to receive events
+    UnableToHandleEvent(String),
+    /// The subscriber no longer wants to receive events and should be removed
+    Unsubscribe,
+}
+
+#[cfg(feature = "registry-notifications")]
+impl Error for RegistrySubscriberError {}
+
+#[cfg(feature = "registry-notifications")]
+impl fmt::Display for RegistrySubscriberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistrySubscriberError::UnableToHandleEvent(msg) => {
+                write!(f, "Unable to handle event: {}", msg)
+            }
+            RegistrySubscriberError::Unsubscribe => f.write_str("Unsubscribe"),
+        }
+    }
+}
+
 /// Represents the reason that a node was found to be invalid
 #[derive(Debug)]
 pub enum InvalidNodeError {
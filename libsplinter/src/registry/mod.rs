@@ -29,6 +29,8 @@ pub mod client;
 #[cfg(feature = "diesel")]
 mod diesel;
 mod error;
+#[cfg(feature = "registry-notifications")]
+mod notification;
 #[cfg(feature = "rest-api-actix-web-1")]
 mod rest_api;
 mod unified;
@@ -37,10 +39,19 @@ mod yaml;
 use std::collections::HashMap;
 use std::iter::ExactSizeIterator;
 
+#[cfg(feature = "registry-notifications")]
+use crate::error::InvalidStateError;
+
 #[cfg(feature = "diesel")]
 pub use self::diesel::DieselRegistry;
+#[cfg(feature = "registry-notifications")]
+pub use error::RegistrySubscriberError;
 pub use error::{InvalidNodeError, RegistryError};
+#[cfg(feature = "registry-notifications")]
+pub use notification::{RegistryEvent, RegistryEventSubscriber};
 pub use unified::UnifiedRegistry;
+#[cfg(feature = "registry-remote-signature")]
+pub use yaml::RemoteYamlSignatureVerification;
 pub use yaml::{LocalYamlRegistry, YamlNode};
 #[cfg(feature = "registry-remote")]
 pub use yaml::{RemoteYamlRegistry, RemoteYamlShutdownHandle};
@@ -177,15 +188,17 @@ impl NodeBuilder {
     }
 }
 
-/// A predicate on a key/value pair in a Node's metadata table.
+/// A predicate on a key/value pair in a Node's metadata table, or on a node's keys or endpoints.
 ///
-/// Each variant is an operator, and supplies a tuple representing a key/value pair. It is applied
-/// by the comparison operator on the value found at the given key (the first item in the tuple)
-/// against the predicate's value (the second item in the tuple).
+/// Most variants are an operator, and supply a tuple representing a key/value pair. They are
+/// applied by the operator on the value found at the given key (the first item in the tuple)
+/// against the predicate's value (the second item in the tuple). The comparison operators compare
+/// the values as strings, while the numeric operators compare them as numbers.
 ///
 /// If the item is missing in a node's metadata table, the predicate returns false (with the
-/// exception of the `Ne` variant).
-#[derive(Clone)]
+/// exception of the `Ne` and `NotExists` variants). The numeric operators also return false if the
+/// node's value is not a number.
+#[derive(Clone, Debug, PartialEq)]
 pub enum MetadataPredicate {
     /// Applies the `==` operator.
     Eq(String, String),
@@ -199,6 +212,28 @@ pub enum MetadataPredicate {
     Lt(String, String),
     /// Applies the `<=` operator.
     Le(String, String),
+    /// Matches if the value is equal to any of the given values.
+    In(String, Vec<String>),
+    /// Matches if the metadata table contains the given key.
+    Exists(String),
+    /// Matches if the metadata table does not contain the given key.
+    NotExists(String),
+    /// Matches if the value starts with the given prefix.
+    Prefix(String, String),
+    /// Applies the `==` operator to the value as a number.
+    NumEq(String, f64),
+    /// Applies the `>` operator to the value as a number.
+    NumGt(String, f64),
+    /// Applies the `>=` operator to the value as a number.
+    NumGe(String, f64),
+    /// Applies the `<` operator to the value as a number.
+    NumLt(String, f64),
+    /// Applies the `<=` operator to the value as a number.
+    NumLe(String, f64),
+    /// Matches if the node has the given public key.
+    HasKey(String),
+    /// Matches if the node has the given endpoint.
+    HasEndpoint(String),
 }
 
 impl MetadataPredicate {
@@ -224,9 +259,57 @@ impl MetadataPredicate {
             MetadataPredicate::Le(key, val) => {
                 node.metadata.get(key).map(|v| v <= val).unwrap_or(false)
             }
+            MetadataPredicate::In(key, vals) => node
+                .metadata
+                .get(key)
+                .map(|v| vals.contains(v))
+                .unwrap_or(false),
+            MetadataPredicate::Exists(key) => node.metadata.contains_key(key),
+            MetadataPredicate::NotExists(key) => !node.metadata.contains_key(key),
+            MetadataPredicate::Prefix(key, prefix) => node
+                .metadata
+                .get(key)
+                .map(|v| v.starts_with(prefix.as_str()))
+                .unwrap_or(false),
+            MetadataPredicate::NumEq(key, val) => Self::numeric_value(node, key)
+                .map(|v| v == *val)
+                .unwrap_or(false),
+            MetadataPredicate::NumGt(key, val) => Self::numeric_value(node, key)
+                .map(|v| v > *val)
+                .unwrap_or(false),
+            MetadataPredicate::NumGe(key, val) => Self::numeric_value(node, key)
+                .map(|v| v >= *val)
+                .unwrap_or(false),
+            MetadataPredicate::NumLt(key, val) => Self::numeric_value(node, key)
+                .map(|v| v < *val)
+                .unwrap_or(false),
+            MetadataPredicate::NumLe(key, val) => Self::numeric_value(node, key)
+                .map(|v| v <= *val)
+                .unwrap_or(false),
+            MetadataPredicate::HasKey(key) => node.has_key(key),
+            MetadataPredicate::HasEndpoint(endpoint) => node.endpoints.contains(endpoint),
         }
     }
 
+    /// Returns true if this predicate compares metadata values as numbers.
+    pub(in crate::registry) fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            MetadataPredicate::NumEq(..)
+                | MetadataPredicate::NumGt(..)
+                | MetadataPredicate::NumGe(..)
+                | MetadataPredicate::NumLt(..)
+                | MetadataPredicate::NumLe(..)
+        )
+    }
+
+    /// Returns the node's value for the given metadata key as a number, if it is one.
+    fn numeric_value(node: &Node, key: &str) -> Option<f64> {
+        node.metadata
+            .get(key)
+            .and_then(|v| v.trim().parse::<f64>().ok())
+    }
+
     /// Returns the `Eq` predicate for the given key and value
     pub fn eq<S: Into<String>>(key: S, value: S) -> MetadataPredicate {
         MetadataPredicate::Eq(key.into(), value.into())
@@ -264,6 +347,33 @@ pub trait RegistryReader: Send + Sync {
     /// no predicates (i.e. return all).
     fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError>;
 
+    /// Returns an iterator over a page of the nodes in the registry, in ascending order of
+    /// identity.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicates` - A list of predicates to be applied to the resulting list, as for
+    /// `list_nodes`.
+    /// * `after` - If provided, the page starts with the first node whose identity follows this
+    /// identity.
+    /// * `limit` - The maximum number of nodes to return.
+    fn list_nodes_page<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        let mut nodes = self
+            .list_nodes(predicates)?
+            .filter(|node| after.map(|after| node.identity() > after).unwrap_or(true))
+            .collect::<Vec<_>>();
+
+        nodes.sort_by(|a, b| a.identity().cmp(b.identity()));
+        nodes.truncate(limit);
+
+        Ok(Box::new(nodes.into_iter()))
+    }
+
     /// Returns the node with the given identity, if it exists in the registry.
     ///
     /// # Arguments
@@ -279,6 +389,26 @@ pub trait RegistryReader: Send + Sync {
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         self.get_node(identity).map(|opt| opt.is_some())
     }
+
+    /// Adds a subscriber that will be notified each time a node is added to, updated in, or
+    /// removed from the registry.
+    ///
+    /// Subscribers are only notified of changes that the registry observes: changes made through
+    /// the registry itself (or any of its clones), or changes it finds when refreshing from its
+    /// backing source. Registries that do not support notifications return an error.
+    ///
+    /// # Arguments
+    ///
+    ///  * `subscriber` - The subscriber to notify of registry events.
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(
+        &self,
+        _subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        Err(RegistryError::InvalidStateError(
+            InvalidStateError::with_message("Registry does not support event subscriptions".into()),
+        ))
+    }
 }
 
 /// Defines registry write capabilities.
@@ -341,6 +471,15 @@ where
         (**self).count_nodes(predicates)
     }
 
+    fn list_nodes_page<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        (**self).list_nodes_page(predicates, after, limit)
+    }
+
     fn get_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
         (**self).get_node(identity)
     }
@@ -348,6 +487,14 @@ where
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         (**self).has_node(identity)
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        (**self).add_subscriber(subscriber)
+    }
 }
 
 impl<NW> RegistryWriter for Box<NW>
@@ -596,6 +743,53 @@ mod tests {
         assert!(MetadataPredicate::Le("key".into(), "6".into()).apply(&node));
         assert!(MetadataPredicate::Le("key".into(), "5".into()).apply(&node));
         assert!(!MetadataPredicate::Le("key".into(), "4".into()).apply(&node));
+
+        assert!(MetadataPredicate::In("key".into(), vec!["4".into(), "5".into()]).apply(&node));
+        assert!(!MetadataPredicate::In("key".into(), vec!["4".into(), "6".into()]).apply(&node));
+        assert!(!MetadataPredicate::In("other".into(), vec!["5".into()]).apply(&node));
+
+        assert!(MetadataPredicate::Exists("key".into()).apply(&node));
+        assert!(!MetadataPredicate::Exists("other".into()).apply(&node));
+
+        assert!(MetadataPredicate::NotExists("other".into()).apply(&node));
+        assert!(!MetadataPredicate::NotExists("key".into()).apply(&node));
+
+        assert!(MetadataPredicate::HasKey("key".into()).apply(&node));
+        assert!(!MetadataPredicate::HasKey("other".into()).apply(&node));
+
+        assert!(MetadataPredicate::HasEndpoint("endpoint".into()).apply(&node));
+        assert!(!MetadataPredicate::HasEndpoint("other".into()).apply(&node));
+    }
+
+    /// Verify that the prefix and numeric `MetadataPredicate` variants compare values correctly,
+    /// and that the numeric variants don't match values that aren't numbers.
+    #[test]
+    fn metadata_predicates_prefix_and_numeric() {
+        let node = Node::builder("identity")
+            .with_endpoint("endpoint")
+            .with_key("key")
+            .with_metadata("region", "us-east-1".into())
+            .with_metadata("capacity", "10".into())
+            .build()
+            .expect("Failed to build node");
+
+        assert!(MetadataPredicate::Prefix("region".into(), "us-".into()).apply(&node));
+        assert!(MetadataPredicate::Prefix("region".into(), "".into()).apply(&node));
+        assert!(!MetadataPredicate::Prefix("region".into(), "eu-".into()).apply(&node));
+        assert!(!MetadataPredicate::Prefix("other".into(), "us-".into()).apply(&node));
+
+        // "10" is less than "9" as a string, but not as a number
+        assert!(MetadataPredicate::Lt("capacity".into(), "9".into()).apply(&node));
+        assert!(MetadataPredicate::NumGt("capacity".into(), 9.0).apply(&node));
+        assert!(!MetadataPredicate::NumLt("capacity".into(), 9.0).apply(&node));
+
+        assert!(MetadataPredicate::NumEq("capacity".into(), 10.0).apply(&node));
+        assert!(MetadataPredicate::NumGe("capacity".into(), 10.0).apply(&node));
+        assert!(MetadataPredicate::NumLe("capacity".into(), 10.0).apply(&node));
+        assert!(!MetadataPredicate::NumGt("capacity".into(), 10.0).apply(&node));
+
+        assert!(!MetadataPredicate::NumGt("region".into(), 0.0).apply(&node));
+        assert!(!MetadataPredicate::NumLt("other".into(), 100.0).apply(&node));
     }
 
     /// Verify that the `validate_nodes` method properly validates nodes based on the following
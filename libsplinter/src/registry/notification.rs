@@ -0,0 +1,254 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notifications of changes to the nodes in a registry.
+//!
+//! Registries that support notifications accept a [`RegistryEventSubscriber`] through
+//! [`RegistryReader::add_subscriber`], and notify it with a [`RegistryEvent`] each time a node is
+//! added, updated, or removed.
+//!
+//! [`RegistryEvent`]: enum.RegistryEvent.html
+//! [`RegistryEventSubscriber`]: trait.RegistryEventSubscriber.html
+//! [`RegistryReader::add_subscriber`]: trait.RegistryReader.html#method.add_subscriber
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use super::{error::RegistrySubscriberError, Node};
+
+/// A change to a node in a registry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RegistryEvent {
+    /// A node was added to the registry; contains the new node.
+    NodeAdded(Node),
+    /// A node in the registry was changed; contains the updated node.
+    NodeUpdated(Node),
+    /// A node was removed from the registry; contains the node as it was before it was removed.
+    NodeRemoved(Node),
+}
+
+impl RegistryEvent {
+    /// The node that the event applies to.
+    pub fn node(&self) -> &Node {
+        match self {
+            RegistryEvent::NodeAdded(node)
+            | RegistryEvent::NodeUpdated(node)
+            | RegistryEvent::NodeRemoved(node) => node,
+        }
+    }
+}
+
+/// Receives the events of a registry it has been added to.
+pub trait RegistryEventSubscriber: Send {
+    /// Handle a single registry event.
+    ///
+    /// Returning `RegistrySubscriberError::Unsubscribe` removes the subscriber from the registry;
+    /// any other error is logged and the subscriber will continue to receive events.
+    fn handle_event(&self, event: &RegistryEvent) -> Result<(), RegistrySubscriberError>;
+}
+
+/// The subscribers of a registry.
+///
+/// The list is shared by all clones, so that a subscriber added through any clone of a registry
+/// is notified of changes made through any other clone.
+///
+/// Events are queued and delivered in order by whichever thread is currently notifying the
+/// subscribers. The subscribers are called without holding the internal lock, so a subscriber
+/// may read from the registry (which may in turn produce more events) while handling an event.
+#[derive(Clone, Default)]
+pub(in crate::registry) struct RegistrySubscribers {
+    inner: Arc<Mutex<SubscribersInner>>,
+}
+
+#[derive(Default)]
+struct SubscribersInner {
+    subscribers: Vec<Box<dyn RegistryEventSubscriber>>,
+    queue: VecDeque<RegistryEvent>,
+    notifying: bool,
+}
+
+impl RegistrySubscribers {
+    /// Add a subscriber to the list.
+    pub fn add(&self, subscriber: Box<dyn RegistryEventSubscriber>) {
+        match self.inner.lock() {
+            Ok(mut inner) => inner.subscribers.push(subscriber),
+            Err(_) => error!("Registry subscribers lock poisoned; unable to add subscriber"),
+        }
+    }
+
+    /// Send the given events, in order, to all subscribers.
+    ///
+    /// This must not be called while holding any of the registry's internal locks, since
+    /// subscribers may read from the registry when handling an event.
+    pub fn notify(&self, events: &[RegistryEvent]) {
+        if events.is_empty() {
+            return;
+        }
+
+        let mut inner = match self.inner.lock() {
+            Ok(inner) => inner,
+            Err(_) => {
+                error!("Registry subscribers lock poisoned; unable to send registry events");
+                return;
+            }
+        };
+
+        inner.queue.extend(events.iter().cloned());
+
+        // The events will be delivered by the thread that is already notifying the subscribers
+        if inner.notifying {
+            return;
+        }
+        inner.notifying = true;
+
+        while let Some(event) = inner.queue.pop_front() {
+            let mut subscribers = std::mem::take(&mut inner.subscribers);
+            drop(inner);
+
+            subscribers.retain(|subscriber| match subscriber.handle_event(&event) {
+                Ok(()) => true,
+                Err(RegistrySubscriberError::Unsubscribe) => false,
+                Err(RegistrySubscriberError::UnableToHandleEvent(msg)) => {
+                    error!("Unable to send registry event: {}", msg);
+                    true
+                }
+            });
+
+            inner = match self.inner.lock() {
+                Ok(inner) => inner,
+                Err(_) => {
+                    error!("Registry subscribers lock poisoned; unable to send registry events");
+                    return;
+                }
+            };
+
+            // Keep any subscribers that were added while the event was being delivered
+            subscribers.append(&mut inner.subscribers);
+            inner.subscribers = subscribers;
+        }
+
+        inner.notifying = false;
+    }
+}
+
+/// Compute the events that describe the change from the `previous` set of nodes to the `current`
+/// set of nodes.
+pub(in crate::registry) fn diff_nodes(previous: &[Node], current: &[Node]) -> Vec<RegistryEvent> {
+    let previous_by_identity = previous
+        .iter()
+        .map(|node| (node.identity.as_str(), node))
+        .collect::<HashMap<_, _>>();
+    let current_by_identity = current
+        .iter()
+        .map(|node| (node.identity.as_str(), node))
+        .collect::<HashMap<_, _>>();
+
+    let mut events = current
+        .iter()
+        .filter_map(
+            |node| match previous_by_identity.get(node.identity.as_str()) {
+                None => Some(RegistryEvent::NodeAdded(node.clone())),
+                Some(previous_node) if *previous_node != node => {
+                    Some(RegistryEvent::NodeUpdated(node.clone()))
+                }
+                Some(_) => None,
+            },
+        )
+        .collect::<Vec<_>>();
+
+    events.extend(
+        previous
+            .iter()
+            .filter(|node| !current_by_identity.contains_key(node.identity.as_str()))
+            .map(|node| RegistryEvent::NodeRemoved(node.clone())),
+    );
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{channel, Sender};
+
+    /// Verify that `diff_nodes` reports added, updated, and removed nodes, and ignores nodes that
+    /// have not changed.
+    #[test]
+    fn diff_nodes_changes() {
+        let unchanged = new_node("unchanged", "tcps://localhost:8000");
+        let updated = new_node("updated", "tcps://localhost:8001");
+        let updated_new = new_node("updated", "tcps://localhost:8011");
+        let removed = new_node("removed", "tcps://localhost:8002");
+        let added = new_node("added", "tcps://localhost:8003");
+
+        let events = diff_nodes(
+            &[unchanged.clone(), updated, removed.clone()],
+            &[unchanged, updated_new.clone(), added.clone()],
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                RegistryEvent::NodeUpdated(updated_new),
+                RegistryEvent::NodeAdded(added),
+                RegistryEvent::NodeRemoved(removed),
+            ]
+        );
+    }
+
+    /// Verify that subscribers receive events until they unsubscribe.
+    #[test]
+    fn subscribers_notify_and_unsubscribe() {
+        let subscribers = RegistrySubscribers::default();
+
+        let (tx, rx) = channel();
+        subscribers.add(Box::new(ChannelSubscriber(tx)));
+
+        let node = new_node("node", "tcps://localhost:8000");
+        subscribers.notify(&[RegistryEvent::NodeAdded(node.clone())]);
+        assert_eq!(
+            rx.try_recv().expect("Event not received"),
+            RegistryEvent::NodeAdded(node.clone())
+        );
+
+        // Dropping the receiver causes the subscriber to unsubscribe on the next event
+        drop(rx);
+        subscribers.notify(&[RegistryEvent::NodeRemoved(node)]);
+        assert!(subscribers
+            .inner
+            .lock()
+            .expect("lock poisoned")
+            .subscribers
+            .is_empty());
+    }
+
+    fn new_node(identity: &str, endpoint: &str) -> Node {
+        Node::builder(identity)
+            .with_endpoint(endpoint)
+            .with_key("abcd")
+            .build()
+            .expect("Failed to build node")
+    }
+
+    struct ChannelSubscriber(Sender<RegistryEvent>);
+
+    impl RegistryEventSubscriber for ChannelSubscriber {
+        fn handle_event(&self, event: &RegistryEvent) -> Result<(), RegistrySubscriberError> {
+            self.0
+                .send(event.clone())
+                .map_err(|_| RegistrySubscriberError::Unsubscribe)
+        }
+    }
+}
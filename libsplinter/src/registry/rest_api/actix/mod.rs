@@ -14,3 +14,5 @@
 
 pub(super) mod nodes;
 pub(super) mod nodes_identity;
+#[cfg(feature = "registry-notifications")]
+pub(super) mod ws_nodes;
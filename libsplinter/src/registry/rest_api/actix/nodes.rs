@@ -32,13 +32,24 @@ use crate::registry::{
 };
 use crate::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
-    paging::{get_response_paging_info, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    paging::{get_response_cursor_paging_info, get_response_paging_info, Cursor, DEFAULT_LIMIT},
     percent_encode_filter_query, ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
 const REGISTRY_LIST_NODES_MIN: u32 = 1;
 
-type Filter = HashMap<String, (String, String)>;
+/// The `filter` query parameter, which maps a metadata key to an operator and its arguments.
+///
+/// The comparison operators (`=`, `!=`, `>`, `>=`, `<` and `<=`) and `prefix` take a string,
+/// `in` takes a list of strings, `exists` and `not_exists` take nothing, and the numeric
+/// operators (`num=`, `num>`, `num>=`, `num<` and `num<=`) take a number. The reserved keys
+/// `$key` and `$endpoint` match a node's public keys and endpoints with the `=` operator.
+type Filter = HashMap<String, Vec<serde_json::Value>>;
+
+/// Reserved filter key for matching a node's public keys
+const FILTER_KEY: &str = "$key";
+/// Reserved filter key for matching a node's endpoints
+const FILTER_ENDPOINT: &str = "$endpoint";
 
 pub fn make_nodes_resource(registry: Box<dyn RwRegistry>) -> Resource {
     let registry1 = registry.clone();
@@ -84,7 +95,7 @@ fn list_nodes(
 
     let offset = match query.get("offset") {
         Some(value) => match value.parse::<usize>() {
-            Ok(val) => val,
+            Ok(val) => Some(val),
             Err(err) => {
                 return Box::new(
                     HttpResponse::BadRequest()
@@ -96,7 +107,24 @@ fn list_nodes(
                 )
             }
         },
-        None => DEFAULT_OFFSET,
+        None => None,
+    };
+
+    let cursor = match query.get("cursor") {
+        Some(value) => match Cursor::decode(value) {
+            Ok(cursor) => Some(cursor),
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid cursor value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => None,
     };
 
     let limit = match query.get("limit") {
@@ -153,11 +181,7 @@ fn list_nodes(
     };
 
     Box::new(query_list_nodes(
-        registry,
-        link,
-        predicates,
-        Some(offset),
-        Some(limit),
+        registry, link, predicates, offset, cursor, limit,
     ))
 }
 
@@ -166,31 +190,54 @@ fn query_list_nodes(
     link: String,
     filters: Vec<MetadataPredicate>,
     offset: Option<usize>,
-    limit: Option<usize>,
+    cursor: Option<Cursor>,
+    limit: usize,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
     web::block(move || {
+        // An explicit offset without a cursor is served as before, so that existing offset links
+        // keep working; otherwise the page is fetched from the registry starting after the cursor
+        if let (Some(offset), None) = (offset, cursor.as_ref()) {
+            let nodes = registry
+                .list_nodes(&filters)
+                .map_err(RegistryRestApiError::from)?;
+
+            let total = nodes.len();
+            let nodes = nodes.skip(offset).take(limit).collect::<Vec<_>>();
+            let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+            return Ok((nodes, paging));
+        }
+
+        let total = registry
+            .count_nodes(&filters)
+            .map_err(RegistryRestApiError::from)? as usize;
         let nodes = registry
-            .list_nodes(&filters)
-            .map_err(RegistryRestApiError::from)?;
-        let offset_value = offset.unwrap_or(0);
-        let total = nodes.len();
-        let limit_value = limit.unwrap_or(total as usize);
-
-        let nodes = nodes
-            .skip(offset_value)
-            .take(limit_value)
+            .list_nodes_page(&filters, cursor.as_ref().map(Cursor::key), limit)
+            .map_err(RegistryRestApiError::from)?
             .collect::<Vec<_>>();
 
-        Ok((nodes, link, limit, offset, total as usize))
+        let next_cursor = Cursor::following(
+            cursor.as_ref(),
+            limit,
+            total,
+            nodes.last().map(|node| node.identity()),
+        );
+        let paging = get_response_cursor_paging_info(
+            limit,
+            cursor.as_ref(),
+            next_cursor.as_ref(),
+            &link,
+            total,
+        );
+
+        Ok((nodes, paging))
     })
     .then(
         |res: Result<_, BlockingError<RegistryRestApiError>>| match res {
-            Ok((nodes, link, limit, offset, total_count)) => {
-                Ok(HttpResponse::Ok().json(ListNodesResponse {
-                    data: nodes.iter().map(NodeResponse::from).collect(),
-                    paging: get_response_paging_info(limit, offset, &link, total_count),
-                }))
-            }
+            Ok((nodes, paging)) => Ok(HttpResponse::Ok().json(ListNodesResponse {
+                data: nodes.iter().map(NodeResponse::from).collect(),
+                paging,
+            })),
             Err(err) => {
                 error!("Unable to list nodes: {}", err);
                 Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
@@ -203,20 +250,139 @@ fn to_predicates(filters: Option<Filter>) -> Result<Vec<MetadataPredicate>, Stri
     match filters {
         Some(filters) => filters
             .into_iter()
-            .map(|(key, (operator, value))| match operator.as_str() {
-                "=" => Ok(MetadataPredicate::Eq(key, value)),
-                ">" => Ok(MetadataPredicate::Gt(key, value)),
-                "<" => Ok(MetadataPredicate::Lt(key, value)),
-                ">=" => Ok(MetadataPredicate::Ge(key, value)),
-                "<=" => Ok(MetadataPredicate::Le(key, value)),
-                "!=" => Ok(MetadataPredicate::Ne(key, value)),
-                _ => Err(format!("{} is not a valid operator", operator)),
-            })
+            .map(|(key, expression)| to_predicate(key, expression))
             .collect(),
         None => Ok(vec![]),
     }
 }
 
+fn to_predicate(
+    key: String,
+    expression: Vec<serde_json::Value>,
+) -> Result<MetadataPredicate, String> {
+    let mut expression = expression.into_iter();
+    let operator = match expression.next() {
+        Some(serde_json::Value::String(operator)) => operator,
+        _ => return Err(format!("filter for {} must start with an operator", key)),
+    };
+    let args = expression.collect::<Vec<_>>();
+
+    match (key.as_str(), operator.as_str()) {
+        (FILTER_KEY, "=") => Ok(MetadataPredicate::HasKey(string_arg(
+            &key, &operator, &args,
+        )?)),
+        (FILTER_ENDPOINT, "=") => Ok(MetadataPredicate::HasEndpoint(string_arg(
+            &key, &operator, &args,
+        )?)),
+        (FILTER_KEY, _) | (FILTER_ENDPOINT, _) => {
+            Err(format!("{} is not a valid operator for {}", operator, key))
+        }
+        (_, "=") => Ok(MetadataPredicate::Eq(
+            key.clone(),
+            string_arg(&key, &operator, &args)?,
+        )),
+        (_, ">") => Ok(MetadataPredicate::Gt(
+            key.clone(),
+            string_arg(&key, &operator, &args)?,
+        )),
+        (_, "<") => Ok(MetadataPredicate::Lt(
+            key.clone(),
+            string_arg(&key, &operator, &args)?,
+        )),
+        (_, ">=") => Ok(MetadataPredicate::Ge(
+            key.clone(),
+            string_arg(&key, &operator, &args)?,
+        )),
+        (_, "<=") => Ok(MetadataPredicate::Le(
+            key.clone(),
+            string_arg(&key, &operator, &args)?,
+        )),
+        (_, "!=") => Ok(MetadataPredicate::Ne(
+            key.clone(),
+            string_arg(&key, &operator, &args)?,
+        )),
+        (_, "prefix") => Ok(MetadataPredicate::Prefix(
+            key.clone(),
+            string_arg(&key, &operator, &args)?,
+        )),
+        (_, "in") => Ok(MetadataPredicate::In(
+            key.clone(),
+            string_list_arg(&key, &operator, &args)?,
+        )),
+        (_, "exists") if args.is_empty() => Ok(MetadataPredicate::Exists(key)),
+        (_, "not_exists") if args.is_empty() => Ok(MetadataPredicate::NotExists(key)),
+        (_, "exists") | (_, "not_exists") => {
+            Err(format!("{} for {} does not take a value", operator, key))
+        }
+        (_, "num=") => Ok(MetadataPredicate::NumEq(
+            key.clone(),
+            number_arg(&key, &operator, &args)?,
+        )),
+        (_, "num>") => Ok(MetadataPredicate::NumGt(
+            key.clone(),
+            number_arg(&key, &operator, &args)?,
+        )),
+        (_, "num>=") => Ok(MetadataPredicate::NumGe(
+            key.clone(),
+            number_arg(&key, &operator, &args)?,
+        )),
+        (_, "num<") => Ok(MetadataPredicate::NumLt(
+            key.clone(),
+            number_arg(&key, &operator, &args)?,
+        )),
+        (_, "num<=") => Ok(MetadataPredicate::NumLe(
+            key.clone(),
+            number_arg(&key, &operator, &args)?,
+        )),
+        _ => Err(format!("{} is not a valid operator", operator)),
+    }
+}
+
+fn string_arg(key: &str, operator: &str, args: &[serde_json::Value]) -> Result<String, String> {
+    match args {
+        [serde_json::Value::String(value)] => Ok(value.clone()),
+        _ => Err(format!(
+            "{} for {} requires a single string value",
+            operator, key
+        )),
+    }
+}
+
+fn string_list_arg(
+    key: &str,
+    operator: &str,
+    args: &[serde_json::Value],
+) -> Result<Vec<String>, String> {
+    match args {
+        [serde_json::Value::Array(values)] => values
+            .iter()
+            .map(|value| match value {
+                serde_json::Value::String(value) => Ok(value.clone()),
+                _ => Err(format!(
+                    "{} for {} requires a list of strings",
+                    operator, key
+                )),
+            })
+            .collect(),
+        _ => Err(format!(
+            "{} for {} requires a list of strings",
+            operator, key
+        )),
+    }
+}
+
+fn number_arg(key: &str, operator: &str, args: &[serde_json::Value]) -> Result<f64, String> {
+    let value = match args {
+        [serde_json::Value::Number(value)] => value.as_f64(),
+        [serde_json::Value::String(value)] => value.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+
+    value
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("{} for {} requires a numeric value", operator, key))
+}
+
 fn add_node(
     payload: web::Payload,
     registry: web::Data<Box<dyn RwRegistry>>,
@@ -343,6 +509,72 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[test]
+    /// Tests that GET /registry/nodes?limit=1 returns the first node with a link to the next
+    /// page, and that following that link returns the second node.
+    fn test_list_nodes_with_cursor() {
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_nodes_resource(Box::new(MemRegistry::new(vec![get_node_2(), get_node_1()]))),
+        ]);
+
+        let url = Url::parse(&format!("http://{}/registry/nodes?limit=1", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            body.get("data").expect("No data field in response"),
+            &to_value(vec![NodeResponse::from(&get_node_1())])
+                .expect("Failed to convert node1 to JsonValue")
+        );
+
+        let next_link = format!(
+            "/registry/nodes?limit=1&cursor={}",
+            Cursor::new(1, "Node-123").encode()
+        );
+        let mut expected_paging = create_test_paging_response(0, 1, 1, 0, 1, 2, "/registry/nodes?");
+        expected_paging.next = next_link.clone();
+        assert_eq!(
+            body.get("paging").expect("no paging field in response"),
+            &to_value(expected_paging).expect("failed to convert expected paging")
+        );
+
+        let url =
+            Url::parse(&format!("http://{}{}", bind_url, next_link)).expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            body.get("data").expect("No data field in response"),
+            &to_value(vec![NodeResponse::from(&get_node_2())])
+                .expect("Failed to convert node2 to JsonValue")
+        );
+
+        let mut expected_paging = create_test_paging_response(1, 1, 1, 0, 1, 2, "/registry/nodes?");
+        expected_paging.current = next_link;
+        assert_eq!(
+            body.get("paging").expect("no paging field in response"),
+            &to_value(expected_paging).expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     #[test]
     /// Tests a GET /registry/nodes request with filters returns the expected node.
     fn test_list_nodes_with_filters_ok() {
@@ -417,6 +649,92 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[test]
+    /// Tests that GET /registry/nodes supports the `in` operator and the reserved `$key`
+    /// filter key, and that both are applied together.
+    fn test_list_nodes_with_extended_filters_ok() {
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_nodes_resource(Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()]))),
+        ]);
+
+        let filter = percent_encode_filter_query(
+            "{\"company\":[\"in\",[\"Bitwise IO\",\"Cargill\"]],\"$key\":[\"=\",\"abcd\"]}",
+        );
+        let url = Url::parse(&format!(
+            "http://{}/registry/nodes?filter={}",
+            bind_url, filter
+        ))
+        .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let nodes: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            nodes.get("data").expect("no data field in response"),
+            &to_value(vec![NodeResponse::from(&get_node_2())])
+                .expect("failed to convert expected data"),
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests that filter expressions are converted into the expected predicates, and that
+    /// malformed expressions are rejected.
+    fn test_to_predicates() {
+        let parse = |filter: &str| {
+            to_predicates(Some(
+                serde_json::from_str(filter).expect("Failed to parse filter"),
+            ))
+        };
+
+        assert_eq!(
+            parse("{\"company\":[\"prefix\",\"Bit\"]}"),
+            Ok(vec![MetadataPredicate::Prefix(
+                "company".into(),
+                "Bit".into()
+            )])
+        );
+        assert_eq!(
+            parse("{\"company\":[\"exists\"]}"),
+            Ok(vec![MetadataPredicate::Exists("company".into())])
+        );
+        assert_eq!(
+            parse("{\"company\":[\"not_exists\"]}"),
+            Ok(vec![MetadataPredicate::NotExists("company".into())])
+        );
+        assert_eq!(
+            parse("{\"rank\":[\"num>=\",10]}"),
+            Ok(vec![MetadataPredicate::NumGe("rank".into(), 10.0)])
+        );
+        assert_eq!(
+            parse("{\"rank\":[\"num<\",\"2.5\"]}"),
+            Ok(vec![MetadataPredicate::NumLt("rank".into(), 2.5)])
+        );
+        assert_eq!(
+            parse("{\"$endpoint\":[\"=\",\"12.0.0.123:8431\"]}"),
+            Ok(vec![MetadataPredicate::HasEndpoint(
+                "12.0.0.123:8431".into()
+            )])
+        );
+
+        assert!(parse("{\"company\":[]}").is_err());
+        assert!(parse("{\"company\":[\"=\"]}").is_err());
+        assert!(parse("{\"company\":[\"=\",1]}").is_err());
+        assert!(parse("{\"company\":[\"in\",\"Cargill\"]}").is_err());
+        assert!(parse("{\"company\":[\"exists\",\"Cargill\"]}").is_err());
+        assert!(parse("{\"rank\":[\"num>\",\"ten\"]}").is_err());
+        assert!(parse("{\"$key\":[\"!=\",\"abcd\"]}").is_err());
+    }
+
     #[test]
     /// Test the POST /registry/nodes route for adding a node to the registry.
     fn test_add_node() {
@@ -44,11 +44,19 @@ const REGISTRY_WRITE_PERMISSION: Permission = Permission::Check {
 /// * `GET /registry/nodes/{identity}` - Fetch a specific node in the registry
 /// * `PUT /registry/nodes/{identity}` - Replace a node in the registry
 /// * `DELETE /registry/nodes/{identity}` - Delete a node from the registry
+/// * `GET /ws/registry/nodes` - Subscribe to changes to the nodes in the registry (requires the
+///   `registry-notifications` feature)
 impl RestResourceProvider for dyn RwRegistry {
     fn resources(&self) -> Vec<Resource> {
-        vec![
+        #[allow(unused_mut)]
+        let mut resources = vec![
             actix::nodes_identity::make_nodes_identity_resource(self.clone_box()),
             actix::nodes::make_nodes_resource(self.clone_box()),
-        ]
+        ];
+
+        #[cfg(feature = "registry-notifications")]
+        resources.push(actix::ws_nodes::make_ws_nodes_resource(self.clone_box()));
+
+        resources
     }
 }
@@ -0,0 +1,60 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::registry::{Node, RegistryEvent};
+
+/// A registry event, as sent over the websocket
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegistryEventResponse {
+    pub event_type: &'static str,
+    pub node: RegistryEventNode,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegistryEventNode {
+    pub identity: String,
+    pub endpoints: Vec<String>,
+    pub display_name: String,
+    pub keys: Vec<String>,
+    pub metadata: HashMap<String, String>,
+}
+
+impl From<&RegistryEvent> for RegistryEventResponse {
+    fn from(event: &RegistryEvent) -> Self {
+        let event_type = match event {
+            RegistryEvent::NodeAdded(_) => "node_added",
+            RegistryEvent::NodeUpdated(_) => "node_updated",
+            RegistryEvent::NodeRemoved(_) => "node_removed",
+        };
+
+        Self {
+            event_type,
+            node: RegistryEventNode::from(event.node()),
+        }
+    }
+}
+
+impl From<&Node> for RegistryEventNode {
+    fn from(node: &Node) -> Self {
+        Self {
+            identity: node.identity.clone(),
+            endpoints: node.endpoints.clone(),
+            display_name: node.display_name.clone(),
+            keys: node.keys.clone(),
+            metadata: node.metadata.clone(),
+        }
+    }
+}
@@ -22,7 +22,17 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+#[cfg(feature = "registry-notifications")]
+use std::sync::{Mutex, Weak};
 
+#[cfg(feature = "registry-notifications")]
+use crate::error::InternalError;
+
+#[cfg(feature = "registry-notifications")]
+use super::{
+    notification::RegistrySubscribers, RegistryEvent, RegistryEventSubscriber,
+    RegistrySubscriberError,
+};
 use super::{
     MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
@@ -61,6 +71,16 @@ use super::{
 /// If the same metadata key is set for the node in different registires, the value for that key
 /// from the highest-precedence registry will be used.
 ///
+/// # Notifications
+///
+/// When the first subscriber is added, the `UnifiedRegistry` subscribes to all of its source
+/// registries; sources that do not support subscriptions are ignored. Events from the sources are
+/// translated to the unified view before they are sent to subscribers: each event contains the
+/// node as it is returned by [`get_node`], an added node that is already provided by another
+/// source is reported as updated, and a removed node that is still provided by another source is
+/// reported as updated rather than removed.
+///
+/// [`get_node`]: ../trait.RegistryReader.html#tymethod.get_node
 /// [`RegistryReader`]: ../trait.RegistryReader.html
 /// [`RegistryWriter`]: ../trait.RegistryWriter.html
 /// [`RwRegistry`]: ../trait.RwRegistry.html
@@ -72,6 +92,11 @@ use super::{
 pub struct UnifiedRegistry {
     internal_source: Arc<dyn RwRegistry>,
     external_sources: Vec<Arc<dyn RegistryReader>>,
+    #[cfg(feature = "registry-notifications")]
+    subscribers: RegistrySubscribers,
+    /// Whether or not the registry has subscribed to its sources
+    #[cfg(feature = "registry-notifications")]
+    subscribed_to_sources: Arc<Mutex<bool>>,
 }
 
 impl UnifiedRegistry {
@@ -84,9 +109,81 @@ impl UnifiedRegistry {
         Self {
             internal_source: internal_source.into(),
             external_sources: external_sources.into_iter().map(Arc::from).collect(),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: RegistrySubscribers::default(),
+            #[cfg(feature = "registry-notifications")]
+            subscribed_to_sources: Arc::new(Mutex::new(false)),
         }
     }
 
+    /// Subscribes to all source registries, if this has not already been done, so that their
+    /// events are forwarded to the unified registry's subscribers.
+    #[cfg(feature = "registry-notifications")]
+    fn subscribe_to_sources(&self) -> Result<(), RegistryError> {
+        let mut subscribed_to_sources = self.subscribed_to_sources.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "Unified registry's subscription lock poisoned".into(),
+            ))
+        })?;
+
+        if *subscribed_to_sources {
+            return Ok(());
+        }
+
+        let results = std::iter::once(self.internal_source.add_subscriber(self.forwarder(None)))
+            .chain(
+                self.external_sources
+                    .iter()
+                    .enumerate()
+                    .map(|(index, source)| source.add_subscriber(self.forwarder(Some(index)))),
+            );
+
+        for result in results {
+            if let Err(err) = result {
+                debug!("Unable to subscribe to source registry: {}", err);
+            }
+        }
+
+        *subscribed_to_sources = true;
+
+        Ok(())
+    }
+
+    /// Creates a subscriber that forwards the events of the given source to this registry's
+    /// subscribers; `None` is the internal source, and `Some` is the index of an external source.
+    #[cfg(feature = "registry-notifications")]
+    fn forwarder(&self, source: Option<usize>) -> Box<dyn RegistryEventSubscriber> {
+        Box::new(SourceEventForwarder {
+            source,
+            internal_source: Arc::downgrade(&self.internal_source),
+            external_sources: self.external_sources.iter().map(Arc::downgrade).collect(),
+            subscribers: self.subscribers.clone(),
+        })
+    }
+
+    /// Determines whether or not the node exists in any source other than the given one.
+    #[cfg(feature = "registry-notifications")]
+    fn has_node_in_other_sources(&self, identity: &str, source: Option<usize>) -> bool {
+        let has_node = |result: Result<bool, RegistryError>| {
+            result.unwrap_or_else(|err| {
+                debug!(
+                    "Failed to check for existence of node in source registry: {}",
+                    err
+                );
+                false
+            })
+        };
+
+        (source.is_some() && has_node(self.internal_source.has_node(identity)))
+            || self
+                .external_sources
+                .iter()
+                .enumerate()
+                .any(|(index, registry)| {
+                    Some(index) != source && has_node(registry.has_node(identity))
+                })
+    }
+
     /// Gets all nodes from all sources (in ascending order of precedence) without deduplication.
     fn all_nodes<'a>(&'a self) -> Box<dyn Iterator<Item = Node> + 'a> {
         Box::new(
@@ -176,6 +273,16 @@ impl RegistryReader for UnifiedRegistry {
             }))
     }
 
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        self.subscribe_to_sources()?;
+        self.subscribers.add(subscriber);
+        Ok(())
+    }
+
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         Ok(self
             .internal_source
@@ -227,6 +334,78 @@ impl RwRegistry for UnifiedRegistry {
     }
 }
 
+/// Forwards the events of one of a `UnifiedRegistry`'s sources to the unified registry's
+/// subscribers.
+///
+/// Only weak references to the sources are held, since the sources hold the forwarder; once the
+/// unified registry and all of its clones are dropped, the forwarder unsubscribes.
+#[cfg(feature = "registry-notifications")]
+struct SourceEventForwarder {
+    source: Option<usize>,
+    internal_source: Weak<dyn RwRegistry>,
+    external_sources: Vec<Weak<dyn RegistryReader>>,
+    subscribers: RegistrySubscribers,
+}
+
+#[cfg(feature = "registry-notifications")]
+impl SourceEventForwarder {
+    /// Reconstructs the unified registry from the sources, if it still exists.
+    fn unified_registry(&self) -> Option<UnifiedRegistry> {
+        Some(UnifiedRegistry {
+            internal_source: self.internal_source.upgrade()?,
+            external_sources: self
+                .external_sources
+                .iter()
+                .map(Weak::upgrade)
+                .collect::<Option<_>>()?,
+            subscribers: self.subscribers.clone(),
+            subscribed_to_sources: Arc::new(Mutex::new(true)),
+        })
+    }
+}
+
+#[cfg(feature = "registry-notifications")]
+impl RegistryEventSubscriber for SourceEventForwarder {
+    fn handle_event(&self, event: &RegistryEvent) -> Result<(), RegistrySubscriberError> {
+        let registry = self
+            .unified_registry()
+            .ok_or(RegistrySubscriberError::Unsubscribe)?;
+
+        let identity = event.node().identity();
+        let in_other_sources = registry.has_node_in_other_sources(identity, self.source);
+
+        let unified_event = match event {
+            RegistryEvent::NodeRemoved(node) if !in_other_sources => {
+                RegistryEvent::NodeRemoved(node.clone())
+            }
+            _ => {
+                let node = match registry.get_node(identity) {
+                    Ok(Some(node)) => node,
+                    // The node has been removed again since the event was sent
+                    Ok(None) => return Ok(()),
+                    Err(err) => {
+                        return Err(RegistrySubscriberError::UnableToHandleEvent(format!(
+                            "unable to get node {} from unified registry: {}",
+                            identity, err
+                        )))
+                    }
+                };
+
+                match event {
+                    RegistryEvent::NodeAdded(_) if !in_other_sources => {
+                        RegistryEvent::NodeAdded(node)
+                    }
+                    _ => RegistryEvent::NodeUpdated(node),
+                }
+            }
+        };
+
+        self.subscribers.notify(&[unified_event]);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -659,6 +838,92 @@ mod test {
             .expect("Unable to check writeable for node1"));
     }
 
+    /// Verify that the events of the source registries are translated to the unified view:
+    ///
+    /// 1. Adding a node that is not in any other source is reported as added
+    /// 2. Adding a node that is already in an external source is reported as updated, with the
+    ///    merged node
+    /// 3. Removing a node that is still in an external source is reported as updated, with the
+    ///    external source's node
+    /// 4. Removing a node that is not in any other source is reported as removed
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn subscriber_notified_with_unified_view() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("subscriber_notified_with_unified_view")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let path = |name: &str| {
+            temp_dir
+                .path()
+                .join(name)
+                .to_str()
+                .expect("Failed to get path")
+                .to_string()
+        };
+
+        let external_node = new_node("node1", "endpoint1", &[("meta_a", "val_a")]);
+        let external = crate::registry::LocalYamlRegistry::new(&path("external.yaml"))
+            .expect("Failed to create external registry");
+        external
+            .add_node(external_node.clone())
+            .expect("Unable to add node to external registry");
+
+        let internal = crate::registry::LocalYamlRegistry::new(&path("internal.yaml"))
+            .expect("Failed to create internal registry");
+
+        let unified = UnifiedRegistry::new(Box::new(internal), vec![Box::new(external)]);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        unified
+            .add_subscriber(Box::new(ChannelSubscriber(tx)))
+            .expect("Unable to add subscriber");
+
+        let node2 = new_node("node2", "endpoint2", &[]);
+        unified
+            .add_node(node2.clone())
+            .expect("Unable to add node2");
+
+        let internal_node = new_node("node1", "endpoint3", &[("meta_b", "val_b")]);
+        unified
+            .add_node(internal_node)
+            .expect("Unable to add node1");
+        let merged_node = new_node(
+            "node1",
+            "endpoint3",
+            &[("meta_a", "val_a"), ("meta_b", "val_b")],
+        );
+
+        unified
+            .delete_node("node1")
+            .expect("Unable to delete node1");
+        unified
+            .delete_node("node2")
+            .expect("Unable to delete node2");
+
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![
+                RegistryEvent::NodeAdded(node2.clone()),
+                RegistryEvent::NodeUpdated(merged_node),
+                RegistryEvent::NodeUpdated(external_node),
+                RegistryEvent::NodeRemoved(node2),
+            ]
+        );
+    }
+
+    #[cfg(feature = "registry-notifications")]
+    struct ChannelSubscriber(std::sync::mpsc::Sender<RegistryEvent>);
+
+    #[cfg(feature = "registry-notifications")]
+    impl RegistryEventSubscriber for ChannelSubscriber {
+        fn handle_event(&self, event: &RegistryEvent) -> Result<(), RegistrySubscriberError> {
+            self.0
+                .send(event.clone())
+                .map_err(|_| RegistrySubscriberError::Unsubscribe)
+        }
+    }
+
     #[derive(Clone, Default)]
     struct MemRegistry {
         nodes: Arc<Mutex<HashMap<String, Node>>>,
@@ -31,6 +31,11 @@ use crate::registry::{
     error::InvalidNodeError, validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError,
     RegistryReader, RegistryWriter, RwRegistry,
 };
+#[cfg(feature = "registry-notifications")]
+use crate::registry::{
+    notification::{diff_nodes, RegistrySubscribers},
+    RegistryEvent, RegistryEventSubscriber,
+};
 
 use crate::error::{InternalError, InvalidStateError};
 
@@ -51,10 +56,15 @@ use super::YamlNode;
 /// file already exists, the registry will attempt to load, parse, and validate it. If the backing
 /// file does not already exist, the registry will attempt to create it.
 ///
+/// Subscribers added to the registry are notified of changes made through the registry, as well
+/// as of changes found when the cache is refreshed from a modified backing file.
+///
 /// [`Node`]: struct.Node.html
 #[derive(Clone)]
 pub struct LocalYamlRegistry {
     internal: Arc<Mutex<Internal>>,
+    #[cfg(feature = "registry-notifications")]
+    subscribers: RegistrySubscribers,
 }
 
 impl LocalYamlRegistry {
@@ -69,32 +79,49 @@ impl LocalYamlRegistry {
     pub fn new(file_path: &str) -> Result<LocalYamlRegistry, RegistryError> {
         Ok(LocalYamlRegistry {
             internal: Arc::new(Mutex::new(Internal::new(file_path)?)),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: RegistrySubscribers::default(),
         })
     }
 
     /// Get all nodes in the registry.
     pub(super) fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
-        Ok(self
-            .internal
-            .lock()
-            .map_err(|_| {
-                RegistryError::InternalError(InternalError::with_message(
-                    "YAML registry's internal lock poisoned".into(),
-                ))
-            })?
-            .get_nodes())
+        let mut internal = self.internal.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "YAML registry's internal lock poisoned".into(),
+            ))
+        })?;
+
+        let nodes = internal.get_nodes();
+
+        #[cfg(feature = "registry-notifications")]
+        {
+            let events = internal.take_pending_events();
+            drop(internal);
+            self.subscribers.notify(&events);
+        }
+
+        Ok(nodes)
     }
 
     /// Write the given list of nodes to the backing YAML file.
     pub(super) fn write_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
-        self.internal
-            .lock()
-            .map_err(|_| {
-                RegistryError::InternalError(InternalError::with_message(
-                    "YAML registry's internal lock poisoned".into(),
-                ))
-            })?
-            .write_nodes(nodes)
+        let mut internal = self.internal.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "YAML registry's internal lock poisoned".into(),
+            ))
+        })?;
+
+        let result = internal.write_nodes(nodes);
+
+        #[cfg(feature = "registry-notifications")]
+        {
+            let events = internal.take_pending_events();
+            drop(internal);
+            self.subscribers.notify(&events);
+        }
+
+        result
     }
 }
 
@@ -130,6 +157,15 @@ impl RegistryReader for LocalYamlRegistry {
             .iter()
             .any(|node| node.identity == identity))
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        self.subscribers.add(subscriber);
+        Ok(())
+    }
 }
 
 impl RegistryWriter for LocalYamlRegistry {
@@ -191,6 +227,9 @@ struct Internal {
     file_path: String,
     cached_nodes: Vec<Node>,
     last_read: SystemTime,
+    /// Events for changes to the cache that have not yet been sent to subscribers
+    #[cfg(feature = "registry-notifications")]
+    pending_events: Vec<RegistryEvent>,
 }
 
 impl Internal {
@@ -199,6 +238,8 @@ impl Internal {
             file_path: file_path.into(),
             cached_nodes: vec![],
             last_read: SystemTime::UNIX_EPOCH,
+            #[cfg(feature = "registry-notifications")]
+            pending_events: vec![],
         };
 
         // If file already exists, read it; otherwise initialize it.
@@ -208,9 +249,29 @@ impl Internal {
             internal.write_nodes(vec![])?;
         }
 
+        // The initial contents are not a change that subscribers need to be notified of
+        #[cfg(feature = "registry-notifications")]
+        internal.pending_events.clear();
+
         Ok(internal)
     }
 
+    /// Take the events for the changes to the cache since this was last called.
+    #[cfg(feature = "registry-notifications")]
+    fn take_pending_events(&mut self) -> Vec<RegistryEvent> {
+        std::mem::take(&mut self.pending_events)
+    }
+
+    /// Replace the cached nodes, recording the changes for subscribers.
+    fn set_cached_nodes(&mut self, nodes: Vec<Node>) {
+        #[cfg(feature = "registry-notifications")]
+        self.pending_events
+            .extend(diff_nodes(&self.cached_nodes, &nodes));
+
+        self.cached_nodes = nodes;
+        self.last_read = SystemTime::now();
+    }
+
     /// Get the internal list of nodes. If the backing file has been modified since the last read,
     /// attempt to refresh the cache.
     fn get_nodes(&mut self) -> Vec<Node> {
@@ -289,8 +350,7 @@ impl Internal {
             nodes
         };
 
-        self.cached_nodes = nodes;
-        self.last_read = SystemTime::now();
+        self.set_cached_nodes(nodes);
 
         Ok(())
     }
@@ -336,8 +396,7 @@ impl Internal {
             ))
         })?;
 
-        self.cached_nodes = nodes;
-        self.last_read = SystemTime::now();
+        self.set_cached_nodes(nodes);
 
         Ok(())
     }
@@ -348,6 +407,8 @@ mod test {
     use super::*;
 
     use std::fs::{remove_file, File};
+    #[cfg(feature = "registry-notifications")]
+    use std::sync::mpsc::{channel, Sender};
 
     use tempfile::Builder;
 
@@ -1340,6 +1401,57 @@ mod test {
         assert_eq!(nodes, vec![get_node_1()]);
     }
 
+    ///
+    /// Verifies that subscribers are notified of the changes made through the registry, and of
+    /// the changes found when the YAML file is modified directly.
+    ///
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn test_subscriber_notified() {
+        let temp_dir = Builder::new()
+            .prefix("test_subscriber_notified")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let path = temp_dir
+            .path()
+            .join("registry.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        write_to_file(&[get_node_3()], &path);
+
+        let registry = LocalYamlRegistry::new(&path).expect("Failed to create LocalYamlRegistry");
+
+        let (tx, rx) = channel();
+        registry
+            .add_subscriber(Box::new(ChannelSubscriber(tx)))
+            .expect("Failed to add subscriber");
+
+        registry.add_node(get_node_1()).expect("Failed to add node");
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![RegistryEvent::NodeAdded(get_node_1())]
+        );
+
+        // Allow some time before writing the file to make sure the read time is earlier than the
+        // write time; the sytem clock may not be very precise.
+        std::thread::sleep(std::time::Duration::from_secs(1));
+
+        write_to_file(&[get_node_2(), get_node_3()], &path);
+
+        registry
+            .get_nodes()
+            .expect("Failed to get nodes from updated file");
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            vec![
+                RegistryEvent::NodeAdded(get_node_2()),
+                RegistryEvent::NodeRemoved(get_node_1()),
+            ]
+        );
+    }
+
     ///
     /// Verifies that if the YAML file is removed, the registry will still return nodes using its
     /// in-memory cache.
@@ -1405,6 +1517,21 @@ mod test {
             .expect("Failed to build node3")
     }
 
+    #[cfg(feature = "registry-notifications")]
+    struct ChannelSubscriber(Sender<RegistryEvent>);
+
+    #[cfg(feature = "registry-notifications")]
+    impl RegistryEventSubscriber for ChannelSubscriber {
+        fn handle_event(
+            &self,
+            event: &RegistryEvent,
+        ) -> Result<(), crate::registry::RegistrySubscriberError> {
+            self.0
+                .send(event.clone())
+                .map_err(|_| crate::registry::RegistrySubscriberError::Unsubscribe)
+        }
+    }
+
     fn write_to_file(data: &[Node], file_path: &str) {
         let yaml_data: Vec<YamlNode> = data
             .iter()
@@ -26,6 +26,8 @@ pub use crate::registry::error::InvalidNodeError;
 use super::Node;
 
 pub use local::LocalYamlRegistry;
+#[cfg(feature = "registry-remote-signature")]
+pub use remote::RemoteYamlSignatureVerification;
 #[cfg(feature = "registry-remote")]
 pub use remote::{RemoteYamlRegistry, RemoteYamlShutdownHandle};
 
@@ -29,14 +29,23 @@ use std::sync::{
 use std::thread;
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "registry-remote-signature")]
+use cylinder::{PublicKey, Signature, Verifier};
 use openssl::hash::{hash, MessageDigest};
 
 use crate::error::{InternalError, InvalidStateError};
+#[cfg(feature = "registry-remote-signature")]
+use crate::hex::parse_hex;
 use crate::hex::to_hex;
 use crate::registry::{
     error::InvalidNodeError, validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError,
     RegistryReader,
 };
+#[cfg(feature = "registry-notifications")]
+use crate::registry::{
+    notification::{diff_nodes, RegistrySubscribers},
+    RegistryEvent, RegistryEventSubscriber,
+};
 use crate::threading::lifecycle::ShutdownHandle;
 
 use super::{LocalYamlRegistry, YamlNode};
@@ -71,12 +80,29 @@ use super::{LocalYamlRegistry, YamlNode};
 /// and the previously cached registry values will continue to be used. The next time the registry
 /// is read, it will try again to refresh the cache.
 ///
+/// If the registry is constructed with [`new_signed`], every fetched file must have a valid
+/// detached signature from one of the configured publisher keys before it is cached. The
+/// signature is fetched from the registry file's URL with `.sig` appended. It must contain two
+/// lines: the file's version, which is a decimal integer that the publisher increases with every
+/// update (a Unix timestamp of the publication time works), and the hex-encoded signature of the
+/// version line (including its newline) followed by the file's bytes. A file whose version is
+/// older than that of the cached file is rejected, so a previously signed file can't be replayed
+/// to roll the registry back; the accepted version is saved alongside the cache so this holds
+/// across restarts. Files without a valid signature or with an older version are rejected like
+/// any other invalid file, so the last verified contents continue to be used.
+///
+/// Subscribers added to the registry are notified of the changes found each time the cache is
+/// successfully refreshed, whether the refresh is automatic or forced.
+///
 /// [`Node`]: struct.Node.html
 /// [`RegistryReader`]: trait.RegistryReader.html
 /// [`constructor`]: struct.RemoteYamlRegistry.html#method.new
+/// [`new_signed`]: struct.RemoteYamlRegistry.html#method.new_signed
 pub struct RemoteYamlRegistry {
     internal: Arc<Mutex<Internal>>,
     shutdown_handle: Option<RemoteYamlShutdownHandle>,
+    #[cfg(feature = "registry-notifications")]
+    subscribers: RegistrySubscribers,
 }
 
 impl RemoteYamlRegistry {
@@ -98,11 +124,63 @@ impl RemoteYamlRegistry {
         automatic_refresh_period: Option<Duration>,
         forced_refresh_period: Option<Duration>,
     ) -> Result<Self, RegistryError> {
-        let internal = Arc::new(Mutex::new(Internal::new(
-            url,
-            cache_dir,
-            forced_refresh_period,
-        )?));
+        let internal = Internal::new(url, cache_dir, forced_refresh_period)?;
+
+        Self::start(internal, automatic_refresh_period)
+    }
+
+    /// Construct a new `RemoteYamlRegistry` that only accepts registry files signed by one of the
+    /// given publishers.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the registry's backing YAML file.
+    /// * `cache_dir` - Directory that the local registry cache will be stored in.
+    /// * `automatic_refresh_period` - Amount of time between attempts to automatically fetch and
+    ///   cache the remote YAML file in the background. If `None`, background refreshes will be
+    ///   disabled. The automatic refresh occurs with a tolerance of +/- 1 second.
+    /// * `forced_refresh_period` - Amount of time since the last successful cache refresh before
+    ///   attempting to refresh on every read operation. If `None`, forced refreshes will be
+    ///   disabled.
+    /// * `signature_verification` - The publisher keys, and the verifier used to check a fetched
+    ///   file's signature against them.
+    #[cfg(feature = "registry-remote-signature")]
+    pub fn new_signed(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        signature_verification: RemoteYamlSignatureVerification,
+    ) -> Result<Self, RegistryError> {
+        let mut internal = Internal::new(url, cache_dir, forced_refresh_period)?;
+        internal.enable_signature_verification(signature_verification, cache_dir)?;
+
+        Self::start(internal, automatic_refresh_period)
+    }
+
+    /// Attempt to populate the cache of the given `internal` state immediately, and start the
+    /// automatic refresh thread if an `automatic_refresh_period` is provided.
+    fn start(
+        mut internal: Internal,
+        automatic_refresh_period: Option<Duration>,
+    ) -> Result<Self, RegistryError> {
+        // If initial fetch/cache fails, it will be re-attempted on the next registry read, so just
+        // log a message
+        if let Err(err) = internal.refresh_cache() {
+            warn!(
+                "Couldn't initialize cache on startup of remote registry '{}': {}",
+                internal.url, err
+            );
+        }
+
+        // There can't be any subscribers yet, so the changes from the initial refresh are dropped
+        #[cfg(feature = "registry-notifications")]
+        internal.take_pending_events();
+        #[cfg(feature = "registry-notifications")]
+        let subscribers = RegistrySubscribers::default();
+
+        let url = internal.url.clone();
+        let internal = Arc::new(Mutex::new(internal));
 
         let (running, join_handle) = {
             if let Some(refresh_period) = automatic_refresh_period {
@@ -111,6 +189,8 @@ impl RemoteYamlRegistry {
                 let thread_internal = internal.clone();
                 let thread_url = url.to_string();
                 let thread_running = running.clone();
+                #[cfg(feature = "registry-notifications")]
+                let thread_subscribers = subscribers.clone();
                 let join_handle = thread::Builder::new()
                     .name(format!("Remote Registry Automatic Refresh: {}", url))
                     .spawn(move || {
@@ -119,6 +199,8 @@ impl RemoteYamlRegistry {
                             thread_internal,
                             &thread_url,
                             thread_running,
+                            #[cfg(feature = "registry-notifications")]
+                            thread_subscribers,
                         )
                     })
                     .map_err(|err| {
@@ -144,6 +226,8 @@ impl RemoteYamlRegistry {
         Ok(Self {
             internal,
             shutdown_handle: Some(shutdown_handle),
+            #[cfg(feature = "registry-notifications")]
+            subscribers,
         })
     }
 
@@ -153,14 +237,23 @@ impl RemoteYamlRegistry {
 
     /// Acquire the lock for the internal cache and get the nodes from it.
     fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
-        self.internal
-            .lock()
-            .map_err(|_| {
-                RegistryError::InternalError(InternalError::with_message(
-                    "Internal lock poisoned".into(),
-                ))
-            })?
-            .get_nodes()
+        let mut internal = self.internal.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "Internal lock poisoned".into(),
+            ))
+        })?;
+
+        let nodes = internal.get_nodes();
+
+        // A forced refresh may have changed the cache
+        #[cfg(feature = "registry-notifications")]
+        {
+            let events = internal.take_pending_events();
+            drop(internal);
+            self.subscribers.notify(&events);
+        }
+
+        nodes
     }
 }
 
@@ -188,6 +281,84 @@ impl RegistryReader for RemoteYamlRegistry {
             .filter(move |node| predicates.iter().all(|predicate| predicate.apply(node)))
             .count() as u32)
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(
+        &self,
+        subscriber: Box<dyn RegistryEventSubscriber>,
+    ) -> Result<(), RegistryError> {
+        self.subscribers.add(subscriber);
+        Ok(())
+    }
+}
+
+/// The publisher keys that a signed remote registry file is verified against.
+///
+/// A registry file is accepted if its detached signature is valid for any one of the publisher
+/// keys.
+#[cfg(feature = "registry-remote-signature")]
+pub struct RemoteYamlSignatureVerification {
+    verifier: Box<dyn Verifier>,
+    publisher_keys: Vec<PublicKey>,
+}
+
+#[cfg(feature = "registry-remote-signature")]
+impl RemoteYamlSignatureVerification {
+    /// Construct a new `RemoteYamlSignatureVerification`.
+    ///
+    /// # Arguments
+    ///
+    /// * `verifier` - The verifier used to check signatures; this must match the algorithm of the
+    ///   publisher keys.
+    /// * `publisher_keys` - The public keys of the trusted registry publishers.
+    pub fn new(verifier: Box<dyn Verifier>, publisher_keys: Vec<PublicKey>) -> Self {
+        Self {
+            verifier,
+            publisher_keys,
+        }
+    }
+
+    /// Fetch the detached signature for the registry file at the given URL and check that it is a
+    /// valid signature of its version and `registry_bytes` for one of the publisher keys. Returns
+    /// the signed version of the file.
+    fn verify(&self, url: &str, registry_bytes: &[u8]) -> Result<u64, RegistryError> {
+        let signature_url = format!("{}.sig", url);
+        let signature_bytes = fetch_bytes_from_remote(&signature_url)?;
+        let (version, signature) = std::str::from_utf8(&signature_bytes)
+            .ok()
+            .and_then(|contents| {
+                let mut lines = contents.lines().map(str::trim);
+                let version = lines.next()?.parse::<u64>().ok()?;
+                let signature = parse_hex(lines.next()?).ok()?;
+                Some((version, Signature::new(signature)))
+            })
+            .ok_or_else(|| {
+                RegistryError::InvalidStateError(InvalidStateError::with_message(format!(
+                    "Remote registry signature at {} must contain a version and a hex-encoded \
+                     signature",
+                    signature_url
+                )))
+            })?;
+
+        let mut signed_bytes = format!("{}\n", version).into_bytes();
+        signed_bytes.extend_from_slice(registry_bytes);
+
+        if self.publisher_keys.iter().any(|public_key| {
+            matches!(
+                self.verifier.verify(&signed_bytes, &signature, public_key),
+                Ok(true)
+            )
+        }) {
+            Ok(version)
+        } else {
+            Err(RegistryError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "Remote registry file from {} is not signed by a trusted publisher",
+                    url
+                )),
+            ))
+        }
+    }
 }
 
 /// Holds the internal state of the remote registry.
@@ -197,10 +368,21 @@ struct Internal {
     last_refresh_successful: bool,
     forced_refresh_period: Option<Duration>,
     next_forced_refresh: Option<Instant>,
+    #[cfg(feature = "registry-remote-signature")]
+    signature_verification: Option<RemoteYamlSignatureVerification>,
+    /// The file that the version of the last accepted signed registry file is saved to
+    #[cfg(feature = "registry-remote-signature")]
+    version_file: Option<String>,
+    /// The version of the last accepted signed registry file; older files are rejected
+    #[cfg(feature = "registry-remote-signature")]
+    accepted_version: Option<u64>,
+    /// Events for changes to the cache that have not yet been sent to subscribers
+    #[cfg(feature = "registry-notifications")]
+    pending_events: Vec<RegistryEvent>,
 }
 
 impl Internal {
-    /// Initialize the internal cache and attempt to populate it immediately.
+    /// Initialize the internal cache.
     fn new(
         url: &str,
         cache_dir: &str,
@@ -210,32 +392,155 @@ impl Internal {
 
         let cache = LocalYamlRegistry::new(&compute_cache_filename(&url, cache_dir)?)?;
 
-        let mut internal = Self {
+        Ok(Self {
             url,
             cache,
             last_refresh_successful: false,
             forced_refresh_period,
             next_forced_refresh: None,
+            #[cfg(feature = "registry-remote-signature")]
+            signature_verification: None,
+            #[cfg(feature = "registry-remote-signature")]
+            version_file: None,
+            #[cfg(feature = "registry-remote-signature")]
+            accepted_version: None,
+            #[cfg(feature = "registry-notifications")]
+            pending_events: vec![],
+        })
+    }
+
+    /// Require fetched files to be signed, and load the version of the last accepted file from
+    /// the cache directory.
+    #[cfg(feature = "registry-remote-signature")]
+    fn enable_signature_verification(
+        &mut self,
+        signature_verification: RemoteYamlSignatureVerification,
+        cache_dir: &str,
+    ) -> Result<(), RegistryError> {
+        let version_file = compute_version_filename(&self.url, cache_dir)?;
+
+        self.accepted_version = match std::fs::read_to_string(&version_file) {
+            Ok(contents) => Some(contents.trim().parse::<u64>().map_err(|err| {
+                RegistryError::InternalError(InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!(
+                        "Failed to parse remote registry version file {}",
+                        version_file
+                    ),
+                ))
+            })?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(RegistryError::InternalError(
+                    InternalError::from_source_with_message(
+                        Box::new(err),
+                        format!(
+                            "Failed to read remote registry version file {}",
+                            version_file
+                        ),
+                    ),
+                ))
+            }
         };
+        self.version_file = Some(version_file);
+        self.signature_verification = Some(signature_verification);
 
-        // If initial fetch/cache fails, it will be re-attempted on the next registry read, so just
-        // log a message
-        if let Err(err) = internal.refresh_cache() {
-            warn!(
-                "Couldn't initialize cache on startup of remote registry '{}': {}",
-                internal.url, err
-            );
+        Ok(())
+    }
+
+    /// Fetch the remote registry file, verify its signature if required, and parse and validate
+    /// its nodes. Returns the nodes along with the file's signed version, if it is signed.
+    fn fetch_nodes(&self) -> Result<(Vec<Node>, Option<u64>), RegistryError> {
+        let bytes = fetch_bytes_from_remote(&self.url)?;
+
+        #[cfg(feature = "registry-remote-signature")]
+        let version = match &self.signature_verification {
+            Some(signature_verification) => {
+                let version = signature_verification
+                    .verify(&self.url, &bytes)
+                    .and_then(|version| match self.accepted_version {
+                        Some(accepted_version) if version < accepted_version => {
+                            Err(RegistryError::InvalidStateError(
+                                InvalidStateError::with_message(format!(
+                                    "Remote registry file from {} has version {}, which is older \
+                                     than the cached version {}",
+                                    self.url, version, accepted_version
+                                )),
+                            ))
+                        }
+                        _ => Ok(version),
+                    })
+                    .map_err(|err| {
+                        counter!("splinter.registry.remote.rejected_signatures", 1,
+                            "url" => self.url.clone()
+                        );
+                        err
+                    })?;
+                Some(version)
+            }
+            None => None,
+        };
+        #[cfg(not(feature = "registry-remote-signature"))]
+        let version = None;
+
+        Ok((parse_nodes(&bytes)?, version))
+    }
+
+    /// Save the version of the signed registry file that was just cached, so that older files are
+    /// rejected from now on.
+    #[cfg(feature = "registry-remote-signature")]
+    fn update_version(&mut self, version: u64) -> Result<(), RegistryError> {
+        if let Some(version_file) = &self.version_file {
+            std::fs::write(version_file, version.to_string()).map_err(|err| {
+                RegistryError::InternalError(InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!(
+                        "Failed to write remote registry version file {}",
+                        version_file
+                    ),
+                ))
+            })?;
         }
+        self.accepted_version = Some(version);
+
+        Ok(())
+    }
+
+    /// Write the fetched nodes to the cache, recording the changes for subscribers.
+    fn update_cache(&mut self, nodes: Vec<Node>) -> Result<(), RegistryError> {
+        #[cfg(feature = "registry-notifications")]
+        let events = diff_nodes(&self.cache.get_nodes()?, &nodes);
+
+        self.cache.write_nodes(nodes)?;
+
+        #[cfg(feature = "registry-notifications")]
+        self.pending_events.extend(events);
+
+        Ok(())
+    }
 
-        Ok(internal)
+    /// Take the events for the changes to the cache since this was last called.
+    #[cfg(feature = "registry-notifications")]
+    fn take_pending_events(&mut self) -> Vec<RegistryEvent> {
+        std::mem::take(&mut self.pending_events)
     }
 
     /// Attempt to refresh the internal cache and update state accordingly.
     fn refresh_cache(&mut self) -> Result<(), RegistryError> {
-        fetch_nodes_from_remote(&self.url)
-            .and_then(|nodes| self.cache.write_nodes(nodes))
+        self.fetch_nodes()
+            .and_then(|(nodes, _version)| {
+                self.update_cache(nodes)?;
+                #[cfg(feature = "registry-remote-signature")]
+                if let Some(version) = _version {
+                    self.update_version(version)?;
+                }
+                Ok(())
+            })
             .map_err(|err| {
                 self.last_refresh_successful = false;
+                counter!("splinter.registry.remote.failed_refreshes", 1,
+                    "url" => self.url.clone()
+                );
                 err
             })
             .and_then(|_| {
@@ -312,8 +617,19 @@ fn compute_cache_filename(url: &str, cache_dir: &str) -> Result<String, Registry
         .to_string())
 }
 
-/// Fetch, parse, and validate the YAML registry file at the given URL.
-fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
+// The version of the last accepted signed file is saved next to the cache, so that it is used
+// across restarts as well.
+#[cfg(feature = "registry-remote-signature")]
+fn compute_version_filename(url: &str, cache_dir: &str) -> Result<String, RegistryError> {
+    Ok(Path::new(&compute_cache_filename(url, cache_dir)?)
+        .with_extension("version")
+        .to_str()
+        .expect("path built from &str cannot be invalid")
+        .to_string())
+}
+
+/// Fetch the file at the given URL.
+fn fetch_bytes_from_remote(url: &str) -> Result<Vec<u8>, RegistryError> {
     let bytes = reqwest::blocking::get(url)
         .and_then(|response| response.error_for_status())
         .map_err(|err| {
@@ -329,7 +645,13 @@ fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
                 "Failed to get bytes from remote registry file HTTP response".into(),
             ))
         })?;
-    let yaml_nodes: Vec<YamlNode> = serde_yaml::from_slice(&bytes).map_err(|_| {
+
+    Ok(bytes.to_vec())
+}
+
+/// Parse and validate the bytes of a YAML registry file.
+fn parse_nodes(bytes: &[u8]) -> Result<Vec<Node>, RegistryError> {
+    let yaml_nodes: Vec<YamlNode> = serde_yaml::from_slice(bytes).map_err(|_| {
         RegistryError::InternalError(InternalError::with_message(
             "Failed to deserialize remote registry file: Not a valid YAML sequence of nodes".into(),
         ))
@@ -354,12 +676,13 @@ fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
 }
 
 /// Infinitely loop, attempting to refresh the `internal` cache every `refresh_period`, until no
-/// longer `running`.
+/// longer `running`. Any changes to the cache are sent to the `subscribers`.
 fn automatic_refresh_loop(
     refresh_period: Duration,
     internal: Arc<Mutex<Internal>>,
     url: &str,
     running: Arc<AtomicBool>,
+    #[cfg(feature = "registry-notifications")] subscribers: RegistrySubscribers,
 ) {
     loop {
         // Wait the `refresh_period`, checking for shutdown every second
@@ -400,6 +723,13 @@ fn automatic_refresh_loop(
                 }
             }
         }
+
+        #[cfg(feature = "registry-notifications")]
+        {
+            let events = internal.take_pending_events();
+            drop(internal);
+            subscribers.notify(&events);
+        }
     }
 }
 
@@ -436,6 +766,8 @@ mod tests {
     use std::fs::File;
 
     use actix_web::HttpResponse;
+    #[cfg(feature = "registry-remote-signature")]
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
     use futures::future::IntoFuture;
     use tempfile::{Builder, TempDir};
 
@@ -1006,6 +1338,57 @@ mod tests {
         test_config.shutdown();
     }
 
+    /// Verifies that subscribers are notified of the changes found by an automatic refresh, and
+    /// are not notified of the initial contents of the registry.
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn auto_refresh_notifies_subscribers() {
+        let test_config =
+            TestConfig::setup("auto_refresh_notifies_subscribers", Some(mock_registry()));
+
+        let refresh_period = Duration::from_secs(1);
+        let mut remote_registry = RemoteYamlRegistry::new(
+            test_config.url(),
+            test_config.path(),
+            Some(refresh_period),
+            None,
+        )
+        .expect("Failed to create registry");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        remote_registry
+            .add_subscriber(Box::new(ChannelSubscriber(tx)))
+            .expect("Failed to add subscriber");
+
+        let mut updated_registry = mock_registry();
+        updated_registry.pop();
+        updated_registry[0].endpoints = vec!["tcps://12.0.0.123:8441".into()];
+        test_config.update_registry(Some(updated_registry.clone()));
+
+        // Wait twice as long as the auto refresh period to be sure it has a chance to refresh
+        let timeout = refresh_period * 2;
+        let events = vec![
+            rx.recv_timeout(timeout).expect("First event not received"),
+            rx.recv_timeout(timeout).expect("Second event not received"),
+        ];
+        assert_eq!(
+            events,
+            vec![
+                RegistryEvent::NodeUpdated(updated_registry[0].clone()),
+                RegistryEvent::NodeRemoved(mock_registry()[2].clone()),
+            ]
+        );
+
+        let mut shutdown_handle = remote_registry
+            .take_shutdown_handle()
+            .expect("Unable to get shutdown handle");
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Unable to shutdown remote registry");
+        test_config.shutdown();
+    }
+
     /// Verifies that when forced refresh feature is disabled, the registry is not refreshed on
     /// read.
     #[test]
@@ -1148,7 +1531,199 @@ mod tests {
 
     // Restart, remote file not available
 
+    /// Verifies that a signed registry accepts a remote file whose detached signature is valid for
+    /// one of the publisher keys.
+    #[cfg(feature = "registry-remote-signature")]
+    #[test]
+    fn signed_registry_valid_signature() {
+        let test_config =
+            TestConfig::setup("signed_registry_valid_signature", Some(mock_registry()));
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        test_config.update_signature(Some(sign_registry(&*signer, 1, &mock_registry())));
+
+        let mut remote_registry = RemoteYamlRegistry::new_signed(
+            test_config.url(),
+            test_config.path(),
+            None,
+            None,
+            RemoteYamlSignatureVerification::new(
+                context.new_verifier(),
+                vec![signer.public_key().expect("Failed to get public key")],
+            ),
+        )
+        .expect("Failed to create registry");
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        let mut shutdown_handle = remote_registry
+            .take_shutdown_handle()
+            .expect("Unable to get shutdown handle");
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Unable to shutdown remote registry");
+        test_config.shutdown();
+    }
+
+    /// Verifies that a signed registry rejects a remote file whose signature doesn't match its
+    /// contents, or isn't from a trusted publisher, and keeps the last verified contents.
+    ///
+    /// 1. Start a signed registry with a validly signed file and verify its contents
+    /// 2. Update the remote file without updating its signature; restart the registry and verify
+    ///    that the previous contents are still used
+    /// 3. Sign the updated file with an untrusted key; restart the registry and verify that the
+    ///    previous contents are still used
+    #[cfg(feature = "registry-remote-signature")]
+    #[test]
+    fn signed_registry_invalid_signature() {
+        let test_config =
+            TestConfig::setup("signed_registry_invalid_signature", Some(mock_registry()));
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let untrusted_signer = context.new_signer(context.new_random_private_key());
+        let publisher_key = signer.public_key().expect("Failed to get public key");
+
+        test_config.update_signature(Some(sign_registry(&*signer, 1, &mock_registry())));
+
+        let new_signed_registry = || {
+            RemoteYamlRegistry::new_signed(
+                test_config.url(),
+                test_config.path(),
+                None,
+                None,
+                RemoteYamlSignatureVerification::new(
+                    context.new_verifier(),
+                    vec![publisher_key.clone()],
+                ),
+            )
+            .expect("Failed to create registry")
+        };
+
+        let shutdown = |mut remote_registry: RemoteYamlRegistry| {
+            let mut shutdown_handle = remote_registry
+                .take_shutdown_handle()
+                .expect("Unable to get shutdown handle");
+            shutdown_handle.signal_shutdown();
+            shutdown_handle
+                .wait_for_shutdown()
+                .expect("Unable to shutdown remote registry");
+        };
+
+        let remote_registry = new_signed_registry();
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+        shutdown(remote_registry);
+
+        // Update the remote file, but keep the old signature
+        test_config.update_registry(Some(vec![]));
+
+        let remote_registry = new_signed_registry();
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+        shutdown(remote_registry);
+
+        // Sign the update with a key that isn't trusted
+        test_config.update_signature(Some(sign_registry(&*untrusted_signer, 2, &[])));
+
+        let remote_registry = new_signed_registry();
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+        shutdown(remote_registry);
+
+        test_config.shutdown();
+    }
+
+    /// Verifies that a signed registry rejects a validly signed remote file whose version is older
+    /// than the cached one, including after a restart, and accepts newer versions.
+    ///
+    /// 1. Start a signed registry with version 1 of the file and verify its contents
+    /// 2. Publish version 2 with different contents; restart the registry and verify that the new
+    ///    contents are used
+    /// 3. Replay version 1; restart the registry and verify that version 2's contents are still
+    ///    used
+    /// 4. Publish version 3 with the original contents; restart the registry and verify that the
+    ///    original contents are used
+    #[cfg(feature = "registry-remote-signature")]
+    #[test]
+    fn signed_registry_older_version() {
+        let test_config = TestConfig::setup("signed_registry_older_version", Some(mock_registry()));
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let publisher_key = signer.public_key().expect("Failed to get public key");
+
+        let new_signed_registry = || {
+            RemoteYamlRegistry::new_signed(
+                test_config.url(),
+                test_config.path(),
+                None,
+                None,
+                RemoteYamlSignatureVerification::new(
+                    context.new_verifier(),
+                    vec![publisher_key.clone()],
+                ),
+            )
+            .expect("Failed to create registry")
+        };
+
+        let shutdown = |mut remote_registry: RemoteYamlRegistry| {
+            let mut shutdown_handle = remote_registry
+                .take_shutdown_handle()
+                .expect("Unable to get shutdown handle");
+            shutdown_handle.signal_shutdown();
+            shutdown_handle
+                .wait_for_shutdown()
+                .expect("Unable to shutdown remote registry");
+        };
+
+        test_config.update_signature(Some(sign_registry(&*signer, 1, &mock_registry())));
+
+        let remote_registry = new_signed_registry();
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+        shutdown(remote_registry);
+
+        // Publish a newer version
+        let updated_registry = mock_registry()[..1].to_vec();
+        test_config.update_registry(Some(updated_registry.clone()));
+        test_config.update_signature(Some(sign_registry(&*signer, 2, &updated_registry)));
+
+        let remote_registry = new_signed_registry();
+        verify_internal_cache(&test_config, &remote_registry, updated_registry.clone());
+        shutdown(remote_registry);
+
+        // Replay the older, validly signed version
+        test_config.update_registry(Some(mock_registry()));
+        test_config.update_signature(Some(sign_registry(&*signer, 1, &mock_registry())));
+
+        let remote_registry = new_signed_registry();
+        verify_internal_cache(&test_config, &remote_registry, updated_registry);
+        shutdown(remote_registry);
+
+        // Publish the original contents as a newer version
+        test_config.update_signature(Some(sign_registry(&*signer, 3, &mock_registry())));
+
+        let remote_registry = new_signed_registry();
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+        shutdown(remote_registry);
+
+        test_config.shutdown();
+    }
+
     /// Creates a mock registry.
+    #[cfg(feature = "registry-notifications")]
+    struct ChannelSubscriber(std::sync::mpsc::Sender<RegistryEvent>);
+
+    #[cfg(feature = "registry-notifications")]
+    impl RegistryEventSubscriber for ChannelSubscriber {
+        fn handle_event(
+            &self,
+            event: &RegistryEvent,
+        ) -> Result<(), crate::registry::RegistrySubscriberError> {
+            self.0
+                .send(event.clone())
+                .map_err(|_| crate::registry::RegistrySubscriberError::Unsubscribe)
+        }
+    }
+
     fn mock_registry() -> Vec<Node> {
         vec![
             Node::builder("Node-123")
@@ -1178,6 +1753,24 @@ mod tests {
         ]
     }
 
+    /// Returns the contents of the signature file for the given `version` of the registry file
+    /// that serves the given `nodes`.
+    #[cfg(feature = "registry-remote-signature")]
+    fn sign_registry(signer: &dyn Signer, version: u64, nodes: &[Node]) -> String {
+        let yaml_registry: Vec<YamlNode> = nodes
+            .iter()
+            .map(|node| YamlNode::from(node.clone()))
+            .collect();
+        let mut bytes = format!("{}\n", version).into_bytes();
+        bytes
+            .extend(serde_yaml::to_vec(&yaml_registry).expect("Failed to serialize registry file"));
+        let signature = signer
+            .sign(&bytes)
+            .expect("Failed to sign registry file")
+            .as_hex();
+        format!("{}\n{}", version, signature)
+    }
+
     /// Verifies that the retrieved nodes and the backing file of the `remote_registry` match the
     /// contents of the `expected_registry`.
     fn verify_internal_cache(
@@ -1210,6 +1803,8 @@ mod tests {
         _temp_dir: TempDir,
         temp_dir_path: String,
         registry: Arc<Mutex<Option<Vec<Node>>>>,
+        #[cfg(feature = "registry-remote-signature")]
+        signature: Arc<Mutex<Option<String>>>,
         registry_url: String,
         rest_api_shutdown_handle: RestApiShutdownHandle,
         rest_api_join_handle: std::thread::JoinHandle<()>,
@@ -1231,14 +1826,17 @@ mod tests {
                 .to_string();
 
             let registry = Arc::new(Mutex::new(registry));
+            let signature = Arc::new(Mutex::new(None));
 
             let (rest_api_shutdown_handle, rest_api_join_handle, registry_url) =
-                serve_registry(registry.clone());
+                serve_registry(registry.clone(), signature.clone());
 
             Self {
                 _temp_dir: temp_dir,
                 temp_dir_path,
                 registry,
+                #[cfg(feature = "registry-remote-signature")]
+                signature,
                 registry_url,
                 rest_api_shutdown_handle,
                 rest_api_join_handle,
@@ -1261,6 +1859,13 @@ mod tests {
             *self.registry.lock().expect("Registry lock poisonsed") = registry;
         }
 
+        /// Updates the detached `signature` served up by the REST API; if `signature` is `None`,
+        /// the signature file won't be available.
+        #[cfg(feature = "registry-remote-signature")]
+        fn update_signature(&self, signature: Option<String>) {
+            *self.signature.lock().expect("Signature lock poisoned") = signature;
+        }
+
         /// Shuts down the REST API; this should be called at the end of every test that uses
         /// `TestConfig`.
         fn shutdown(self) {
@@ -1274,11 +1879,36 @@ mod tests {
     }
 
     /// Wraps `run_rest_api_on_open_port`, serving up the given `registry` as a registry YAML file
-    /// that can be fetched at the returned URL. If `registry` is `None`, the registry file will not
-    /// be available.
+    /// that can be fetched at the returned URL, and the given `signature` alongside it. If
+    /// `registry` or `signature` is `None`, the corresponding file will not be available.
     fn serve_registry(
         registry: Arc<Mutex<Option<Vec<Node>>>>,
+        signature: Arc<Mutex<Option<String>>>,
     ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        let mut signature_resource = Resource::build("/registry.yaml.sig");
+        #[cfg(feature = "authorization")]
+        {
+            signature_resource = signature_resource.add_method(
+                Method::Get,
+                Permission::AllowUnauthenticated,
+                move |_, _| {
+                    Box::new(match &*signature.lock().expect("Signature lock poisoned") {
+                        Some(signature) => HttpResponse::Ok().body(signature.clone()).into_future(),
+                        None => HttpResponse::NotFound().finish().into_future(),
+                    })
+                },
+            )
+        }
+        #[cfg(not(feature = "authorization"))]
+        {
+            signature_resource = signature_resource.add_method(Method::Get, move |_, _| {
+                Box::new(match &*signature.lock().expect("Signature lock poisoned") {
+                    Some(signature) => HttpResponse::Ok().body(signature.clone()).into_future(),
+                    None => HttpResponse::NotFound().finish().into_future(),
+                })
+            })
+        }
+
         let mut resource = Resource::build("/registry.yaml");
         #[cfg(feature = "authorization")]
         {
@@ -1324,7 +1954,7 @@ mod tests {
                 })
             })
         }
-        let (shutdown, join, url) = run_rest_api_on_open_port(vec![resource]);
+        let (shutdown, join, url) = run_rest_api_on_open_port(vec![resource, signature_resource]);
 
         (shutdown, join, format!("http://{}/registry.yaml", url))
     }
@@ -18,6 +18,8 @@ use std::thread;
 use actix_web::{middleware, App, HttpServer};
 use futures::Future;
 
+#[cfg(feature = "rest-api-audit")]
+use crate::rest_api::auth::audit::store::AuditStore;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::{
     routes::AuthorizationResourceProvider, AuthorizationHandler, PermissionMap,
@@ -57,6 +59,8 @@ pub struct RestApi {
     pub(super) identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     pub(super) authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-audit")]
+    pub(super) audit_store: Option<Box<dyn AuditStore>>,
 }
 
 impl RestApi {
@@ -86,6 +90,11 @@ impl RestApi {
             #[cfg(feature = "authorization")]
             self.authorization_handlers.to_owned(),
         );
+        #[cfg(feature = "rest-api-audit")]
+        let authorization = match self.audit_store.to_owned() {
+            Some(audit_store) => authorization.with_audit_store(audit_store),
+            None => authorization,
+        };
 
         #[cfg(feature = "rest-api-cors")]
         let cors = match &allow_list {
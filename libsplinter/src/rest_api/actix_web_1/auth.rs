@@ -22,6 +22,10 @@ use crate::biome::credentials::rest_api::BiomeCredentialsRestResourceProvider;
 use crate::biome::OAuthUserSessionStore;
 #[cfg(all(feature = "oauth", feature = "biome-profile"))]
 use crate::biome::UserProfileStore;
+#[cfg(all(feature = "rest-api-tokens", feature = "authorization-handler-rbac"))]
+use crate::rest_api::auth::authorization::rbac::store::RoleBasedAuthorizationStore;
+#[cfg(feature = "rest-api-tokens")]
+use crate::rest_api::auth::token::store::ApiTokenStore;
 #[cfg(feature = "oauth")]
 use crate::rest_api::OAuthConfig;
 use crate::rest_api::{auth::identity::IdentityProvider, RequestError};
@@ -54,6 +58,16 @@ pub enum AuthConfig {
         #[cfg(feature = "biome-profile")]
         user_profile_store: Box<dyn UserProfileStore>,
     },
+    /// API token authentication
+    #[cfg(feature = "rest-api-tokens")]
+    ApiToken {
+        /// The store of the API tokens issued by the Splinter REST API
+        api_token_store: Box<dyn ApiTokenStore>,
+        /// The RBAC store that the roles of the clients creating API tokens are looked up in; a
+        /// client may only grant a token the roles that it holds
+        #[cfg(feature = "authorization-handler-rbac")]
+        role_based_authorization_store: Option<Box<dyn RoleBasedAuthorizationStore>>,
+    },
     /// A custom authentication method
     Custom {
         /// REST API resources that would allow a client to receive some authentication credentials
@@ -20,10 +20,16 @@ use std::sync::Mutex;
 use crate::error::InvalidStateError;
 #[cfg(feature = "oauth")]
 use crate::oauth::{GithubOAuthClientBuilder, OpenIdOAuthClientBuilder};
+#[cfg(feature = "rest-api-audit")]
+use crate::rest_api::auth::audit::{store::AuditStore, AuditResourceProvider};
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::AuthorizationHandler;
 #[cfg(feature = "cylinder-jwt")]
 use crate::rest_api::auth::identity::cylinder::CylinderKeyIdentityProvider;
+#[cfg(feature = "rest-api-tokens")]
+use crate::rest_api::auth::{
+    identity::token::ApiTokenIdentityProvider, token::ApiTokenResourceProvider,
+};
 #[cfg(feature = "oauth")]
 use crate::rest_api::{
     auth::identity::oauth::OAuthUserIdentityProvider, OAuthConfig, OAuthResourceProvider,
@@ -31,7 +37,12 @@ use crate::rest_api::{
 use crate::rest_api::{auth::identity::IdentityProvider, BindConfig, RestApiServerError};
 
 use super::AuthConfig;
-#[cfg(any(feature = "biome-credentials", feature = "oauth"))]
+#[cfg(any(
+    feature = "biome-credentials",
+    feature = "oauth",
+    feature = "rest-api-audit",
+    feature = "rest-api-tokens"
+))]
 use super::RestResourceProvider;
 use super::{Resource, RestApi};
 
@@ -45,6 +56,8 @@ pub struct RestApiBuilder {
     auth_configs: Vec<AuthConfig>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-audit")]
+    audit_store: Option<Box<dyn AuditStore>>,
 }
 
 impl RestApiBuilder {
@@ -110,6 +123,14 @@ impl RestApiBuilder {
         self
     }
 
+    /// Sets the store that requests are recorded in; this also adds the `/audit` endpoint for
+    /// listing the recorded requests
+    #[cfg(feature = "rest-api-audit")]
+    pub fn with_audit_store(mut self, audit_store: Box<dyn AuditStore>) -> Self {
+        self.audit_store = Some(audit_store);
+        self
+    }
+
     // Allowing unused_mut because self must be mutable if feature `auth` is enabled
     #[allow(unused_mut)]
     pub fn build(mut self) -> Result<RestApi, RestApiServerError> {
@@ -240,6 +261,25 @@ impl RestApiBuilder {
                         );
                         oauth_configured = true;
                     }
+                    #[cfg(feature = "rest-api-tokens")]
+                    AuthConfig::ApiToken {
+                        api_token_store,
+                        #[cfg(feature = "authorization-handler-rbac")]
+                        role_based_authorization_store,
+                    } => {
+                        identity_providers.push(Box::new(ApiTokenIdentityProvider::new(
+                            api_token_store.clone(),
+                        )));
+                        let resource_provider = ApiTokenResourceProvider::new(api_token_store);
+                        #[cfg(feature = "authorization-handler-rbac")]
+                        let resource_provider = match role_based_authorization_store {
+                            Some(store) => {
+                                resource_provider.with_role_based_authorization_store(store)
+                            }
+                            None => resource_provider,
+                        };
+                        self.resources.append(&mut resource_provider.resources());
+                    }
                     AuthConfig::Custom {
                         mut resources,
                         identity_provider,
@@ -253,6 +293,12 @@ impl RestApiBuilder {
             identity_providers
         };
 
+        #[cfg(feature = "rest-api-audit")]
+        if let Some(audit_store) = &self.audit_store {
+            self.resources
+                .append(&mut AuditResourceProvider::new(audit_store.clone()).resources());
+        }
+
         Ok(RestApi {
             bind,
             resources: self.resources,
@@ -261,6 +307,8 @@ impl RestApiBuilder {
             identity_providers,
             #[cfg(feature = "authorization")]
             authorization_handlers: self.authorization_handlers,
+            #[cfg(feature = "rest-api-audit")]
+            audit_store: self.audit_store,
         })
     }
 }
@@ -352,6 +400,8 @@ mod test {
                 identity_providers: vec![],
                 #[cfg(feature = "authorization")]
                 authorization_handlers: vec![],
+                #[cfg(feature = "rest-api-audit")]
+                audit_store: None,
             })
         }
     }
@@ -104,6 +104,7 @@ pub type HandlerFunction = Box<
 struct ResourceMethod {
     method: Method,
     permission: Permission,
+    scoped: bool,
     handler: Arc<HandlerFunction>,
 }
 
@@ -171,6 +172,33 @@ impl Resource {
         self.methods.push(ResourceMethod {
             method,
             permission,
+            scoped: false,
+            handler: Arc::new(Box::new(handle)),
+        });
+        self
+    }
+
+    /// Adds a method whose handler checks the resources that a request accesses itself.
+    ///
+    /// A client that only has the permission for some resources is allowed to make the request.
+    /// In that case, the handler is given a
+    /// [`ResourceAuthorization`](crate::rest_api::auth::authorization::ResourceAuthorization) in
+    /// the request's extensions, which it must use to check each resource before accessing it.
+    #[cfg(feature = "authorization")]
+    pub fn add_scoped_method<F>(mut self, method: Method, permission: Permission, handle: F) -> Self
+    where
+        F: Fn(
+                HttpRequest,
+                web::Payload,
+            ) -> Box<dyn Future<Item = HttpResponse, Error = ActixError>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.methods.push(ResourceMethod {
+            method,
+            permission,
+            scoped: true,
             handler: Arc::new(Box::new(handle)),
         });
         self
@@ -258,6 +286,7 @@ impl Resource {
              ResourceMethod {
                  method,
                  permission,
+                 scoped,
                  handler,
              }| {
                 let guards = request_guards.clone();
@@ -273,7 +302,11 @@ impl Resource {
                     }
                     (handler)(r, p)
                 };
-                permission_map.add_permission(method.clone(), &route, permission);
+                if scoped {
+                    permission_map.add_scoped_permission(method.clone(), &route, permission);
+                } else {
+                    permission_map.add_permission(method.clone(), &route, permission);
+                }
                 resource.route(match method {
                     Method::Get => web::get().to_async(func),
                     Method::Post => web::post().to_async(func),
@@ -14,7 +14,21 @@
 
 //! Authorization middleware for the Actix REST API
 
+use std::cell::RefCell;
+use std::rc::Rc;
+#[cfg(feature = "rest-api-audit")]
+use std::time::SystemTime;
+
+#[cfg(feature = "rest-api-audit")]
+use actix_http::h1;
+
 use actix_web::dev::*;
+#[cfg(feature = "rest-api-audit")]
+use actix_web::{
+    error::PayloadError,
+    http::StatusCode,
+    web::{self, BytesMut},
+};
 use actix_web::{
     http::{
         header::{self, HeaderValue},
@@ -22,6 +36,8 @@ use actix_web::{
     },
     Error as ActixError, HttpMessage, HttpResponse,
 };
+#[cfg(feature = "rest-api-audit")]
+use futures::{future, Stream};
 use futures::{
     future::{ok, FutureResult},
     Future, IntoFuture, Poll,
@@ -31,9 +47,20 @@ use crate::rest_api::ErrorResponse;
 #[cfg(feature = "authorization")]
 use crate::rest_api::Method;
 
+#[cfg(feature = "rest-api-audit")]
+use super::audit::{request_digest, store::AuditStore, AuditDecision, AuditEntryBuilder};
+#[cfg(feature = "rest-api-audit")]
+use super::authorization::Permission;
 #[cfg(feature = "authorization")]
 use super::authorization::{AuthorizationHandler, PermissionMap};
 use super::{authorize, identity::IdentityProvider, AuthorizationResult};
+#[cfg(feature = "rest-api-audit")]
+use super::{get_identity, identity::Identity};
+
+/// The largest request body, in bytes, that is read in order to record the request's digest;
+/// requests with larger bodies are rejected before they are authorized
+#[cfg(feature = "rest-api-audit")]
+const MAX_AUDITED_BODY_SIZE: usize = 4 * 1024 * 1024;
 
 /// Wrapper for the authorization middleware
 #[derive(Clone)]
@@ -41,6 +68,8 @@ pub struct Authorization {
     identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-audit")]
+    audit_store: Option<Box<dyn AuditStore>>,
 }
 
 impl Authorization {
@@ -54,8 +83,18 @@ impl Authorization {
             identity_providers,
             #[cfg(feature = "authorization")]
             authorization_handlers,
+            #[cfg(feature = "rest-api-audit")]
+            audit_store: None,
         }
     }
+
+    /// Records the requests that modify state, and all requests that are denied, in the given
+    /// audit store
+    #[cfg(feature = "rest-api-audit")]
+    pub fn with_audit_store(mut self, audit_store: Box<dyn AuditStore>) -> Self {
+        self.audit_store = Some(audit_store);
+        self
+    }
 }
 
 impl<S, B> Transform<S> for Authorization
@@ -76,7 +115,9 @@ where
             identity_providers: self.identity_providers.clone(),
             #[cfg(feature = "authorization")]
             authorization_handlers: self.authorization_handlers.clone(),
-            service,
+            #[cfg(feature = "rest-api-audit")]
+            audit_store: self.audit_store.clone(),
+            service: Rc::new(RefCell::new(service)),
         })
     }
 }
@@ -86,7 +127,23 @@ pub struct AuthorizationMiddleware<S> {
     identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
-    service: S,
+    #[cfg(feature = "rest-api-audit")]
+    audit_store: Option<Box<dyn AuditStore>>,
+    // Shared so that a request can be passed on to the service once its body has been read
+    service: Rc<RefCell<S>>,
+}
+
+impl<S> Clone for AuthorizationMiddleware<S> {
+    fn clone(&self) -> Self {
+        Self {
+            identity_providers: self.identity_providers.clone(),
+            #[cfg(feature = "authorization")]
+            authorization_handlers: self.authorization_handlers.clone(),
+            #[cfg(feature = "rest-api-audit")]
+            audit_store: self.audit_store.clone(),
+            service: self.service.clone(),
+        }
+    }
 }
 
 impl<S, B> Service for AuthorizationMiddleware<S>
@@ -101,12 +158,12 @@ where
     type Future = Box<dyn Future<Item = Self::Response, Error = Self::Error>>;
 
     fn poll_ready(&mut self) -> Poll<(), Self::Error> {
-        self.service.poll_ready()
+        self.service.borrow_mut().poll_ready()
     }
 
     fn call(&mut self, req: ServiceRequest) -> Self::Future {
         if req.method() == ActixMethod::OPTIONS {
-            return Box::new(self.service.call(req).and_then(|mut res| {
+            return Box::new(self.service.borrow_mut().call(req).and_then(|mut res| {
                 res.headers_mut().insert(
                     header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
                     HeaderValue::from_static("true"),
@@ -116,6 +173,66 @@ where
             }));
         }
 
+        // The body is part of the request's digest, so it is read before the request is
+        // authorized, then restored for the service
+        #[cfg(feature = "rest-api-audit")]
+        if self.audit_store.is_some() {
+            let content_length = req
+                .headers()
+                .get(header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<usize>().ok());
+            if content_length.unwrap_or(0) > MAX_AUDITED_BODY_SIZE {
+                return payload_too_large(req);
+            }
+
+            let middleware = self.clone();
+            let mut req = req;
+            return Box::new(
+                req.take_payload()
+                    .fold(BytesMut::new(), |mut body, chunk| {
+                        if body.len() + chunk.len() > MAX_AUDITED_BODY_SIZE {
+                            return Err(PayloadError::Overflow);
+                        }
+                        body.extend_from_slice(&chunk);
+                        Ok(body)
+                    })
+                    .then(move |res| match res {
+                        Ok(body) => {
+                            let body = body.freeze();
+                            let (_, mut payload) = h1::Payload::create(true);
+                            payload.unread_data(body.clone());
+                            req.set_payload(payload.into());
+
+                            middleware.authorize_request(req, &body)
+                        }
+                        Err(PayloadError::Overflow) => payload_too_large(req),
+                        Err(err) => Box::new(Err(ActixError::from(err)).into_future()),
+                    }),
+            );
+        }
+
+        self.authorize_request(
+            req,
+            #[cfg(feature = "rest-api-audit")]
+            &[],
+        )
+    }
+}
+
+impl<S, B> AuthorizationMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>,
+    S::Future: 'static,
+    B: 'static,
+{
+    /// Authorizes the request and, if it is allowed, passes it on to the service; the request is
+    /// recorded in the audit store, if there is one, with the digest of the given body
+    fn authorize_request(
+        &self,
+        req: ServiceRequest,
+        #[cfg(feature = "rest-api-audit")] body: &[u8],
+    ) -> Box<dyn Future<Item = ServiceResponse<B>, Error = ActixError>> {
         #[cfg(feature = "authorization")]
         let method = match *req.method() {
             ActixMethod::GET => Method::Get,
@@ -175,6 +292,21 @@ where
             }
         };
 
+        #[cfg(feature = "rest-api-audit")]
+        let audit_recorder = self.audit_store.as_ref().map(|audit_store| AuditRecorder {
+            audit_store: audit_store.clone(),
+            timestamp: SystemTime::now(),
+            permission_id: match permission_map.get_permission(&method, req.path()) {
+                Some(Permission::Check { permission_id, .. }) => Some(permission_id.to_string()),
+                _ => None,
+            },
+            method: req.method().to_string(),
+            route: req.path().to_string(),
+            request_digest: request_digest(req.method().as_str(), &req.uri().to_string(), body),
+        });
+        #[cfg(feature = "rest-api-audit")]
+        let mut audit_identity = None;
+
         match authorize(
             #[cfg(feature = "authorization")]
             &method,
@@ -188,8 +320,26 @@ where
         ) {
             AuthorizationResult::Authorized(identity) => {
                 debug!("Authenticated user {:?}", identity);
+                #[cfg(feature = "rest-api-audit")]
+                {
+                    audit_identity = Some(identity.clone());
+                }
                 req.extensions_mut().insert(identity);
             }
+            #[cfg(feature = "authorization")]
+            AuthorizationResult::AuthorizedForResources(resource_authorization) => {
+                debug!(
+                    "Authenticated user {:?} for some resources",
+                    resource_authorization.identity()
+                );
+                #[cfg(feature = "rest-api-audit")]
+                {
+                    audit_identity = Some(resource_authorization.identity().clone());
+                }
+                req.extensions_mut()
+                    .insert(resource_authorization.identity().clone());
+                req.extensions_mut().insert(resource_authorization);
+            }
             #[cfg(any(
                 feature = "authorization",
                 feature = "biome-credentials",
@@ -197,36 +347,164 @@ where
             ))]
             AuthorizationResult::NoAuthorizationNecessary => {}
             AuthorizationResult::Unauthorized => {
-                return Box::new(
-                    req.into_response(
-                        HttpResponse::Unauthorized()
-                            .json(ErrorResponse::unauthorized())
-                            .into_body(),
+                // The client may have been identified but not permitted to make the request; the
+                // identity providers are checked again, since the result of the authorization does
+                // not include the identity
+                #[cfg(feature = "rest-api-audit")]
+                let recorded = audit_recorder.map(|audit_recorder| {
+                    audit_recorder.record(
+                        get_identity(auth_header, &self.identity_providers),
+                        AuditDecision::Denied,
+                        StatusCode::UNAUTHORIZED.as_u16(),
                     )
-                    .into_future(),
-                )
+                });
+
+                let response = req.into_response(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_body(),
+                );
+
+                #[cfg(feature = "rest-api-audit")]
+                if let Some(recorded) = recorded {
+                    return Box::new(recorded.then(move |_| Ok::<_, ActixError>(response)));
+                }
+
+                return Box::new(response.into_future());
             }
             #[cfg(feature = "authorization")]
             AuthorizationResult::UnknownEndpoint => {
-                return Box::new(
-                    req.into_response(
-                        HttpResponse::NotFound()
-                            .json(ErrorResponse::not_found("endpoint not found"))
-                            .into_body(),
+                #[cfg(feature = "rest-api-audit")]
+                let recorded = audit_recorder.map(|audit_recorder| {
+                    audit_recorder.record(
+                        get_identity(auth_header, &self.identity_providers),
+                        AuditDecision::Denied,
+                        StatusCode::NOT_FOUND.as_u16(),
                     )
-                    .into_future(),
-                )
+                });
+
+                let response = req.into_response(
+                    HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found("endpoint not found"))
+                        .into_body(),
+                );
+
+                #[cfg(feature = "rest-api-audit")]
+                if let Some(recorded) = recorded {
+                    return Box::new(recorded.then(move |_| Ok::<_, ActixError>(response)));
+                }
+
+                return Box::new(response.into_future());
             }
         }
 
-        Box::new(self.service.call(req).and_then(|mut res| {
+        let response = self.service.borrow_mut().call(req).and_then(|mut res| {
             res.headers_mut().insert(
                 header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
                 HeaderValue::from_static("true"),
             );
 
             res
-        }))
+        });
+
+        // Requests that only read state are not recorded when they are allowed
+        #[cfg(feature = "rest-api-audit")]
+        if let Some(audit_recorder) =
+            audit_recorder.filter(|_| !matches!(method, Method::Get | Method::Head))
+        {
+            return Box::new(response.then(move |res| {
+                let status = match &res {
+                    Ok(res) => res.status(),
+                    Err(err) => err.as_response_error().error_response().status(),
+                };
+                // The handler of a scoped endpoint denies requests for the resources that the
+                // client is not authorized for
+                let decision = if status == StatusCode::UNAUTHORIZED {
+                    AuditDecision::Denied
+                } else {
+                    AuditDecision::Allowed
+                };
+                audit_recorder
+                    .record(audit_identity, decision, status.as_u16())
+                    .then(move |_| res)
+            }));
+        }
+
+        Box::new(response)
+    }
+}
+
+/// Rejects a request whose body is too large to be read for the audit log
+#[cfg(feature = "rest-api-audit")]
+fn payload_too_large<B>(
+    req: ServiceRequest,
+) -> Box<dyn Future<Item = ServiceResponse<B>, Error = ActixError>>
+where
+    B: 'static,
+{
+    Box::new(
+        req.into_response(
+            HttpResponse::PayloadTooLarge()
+                .json(ErrorResponse::payload_too_large(&format!(
+                    "Request body must not be larger than {} bytes",
+                    MAX_AUDITED_BODY_SIZE
+                )))
+                .into_body(),
+        )
+        .into_future(),
+    )
+}
+
+/// The details of a request that are recorded in the audit log once the outcome of the request is
+/// known
+#[cfg(feature = "rest-api-audit")]
+struct AuditRecorder {
+    audit_store: Box<dyn AuditStore>,
+    timestamp: SystemTime,
+    permission_id: Option<String>,
+    method: String,
+    route: String,
+    request_digest: String,
+}
+
+#[cfg(feature = "rest-api-audit")]
+impl AuditRecorder {
+    /// Adds an entry for the request to the audit store on the blocking thread pool, so that the
+    /// write does not hold up the worker; a failure to record the entry is logged, but does not
+    /// change the response to the request
+    fn record(
+        self,
+        identity: Option<Identity>,
+        decision: AuditDecision,
+        status: u16,
+    ) -> Box<dyn Future<Item = (), Error = ()>> {
+        let mut builder = AuditEntryBuilder::new()
+            .with_timestamp(self.timestamp)
+            .with_method(self.method)
+            .with_route(self.route)
+            .with_decision(decision)
+            .with_status(status)
+            .with_request_digest(self.request_digest);
+        if let Some(identity) = identity {
+            builder = builder.with_identity(identity);
+        }
+        if let Some(permission_id) = self.permission_id {
+            builder = builder.with_permission_id(permission_id);
+        }
+
+        match builder.build() {
+            Ok(entry) => {
+                let audit_store = self.audit_store;
+                Box::new(
+                    web::block(move || audit_store.add_entry(entry))
+                        .map_err(|err| error!("Unable to record audit entry: {}", err)),
+                )
+            }
+            Err(err) => {
+                error!("Unable to build audit entry: {}", err);
+                Box::new(future::ok(()))
+            }
+        }
     }
 }
 
@@ -235,8 +513,12 @@ mod tests {
     use super::*;
 
     use actix_web::{http::StatusCode, test, web, App, HttpRequest};
+    #[cfg(feature = "rest-api-audit")]
+    use tempfile::TempDir;
 
     use crate::error::InternalError;
+    #[cfg(feature = "rest-api-audit")]
+    use crate::rest_api::auth::audit::store::FileAuditStore;
     #[cfg(feature = "authorization")]
     use crate::rest_api::auth::authorization::Permission;
     use crate::rest_api::auth::{identity::Identity, AuthorizationHeader};
@@ -370,6 +652,145 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    /// Verifies that the authorization middleware records requests that modify state and requests
+    /// that are denied in the audit store, but not allowed requests that only read state.
+    ///
+    /// 1. Make an authorized `GET` request, an authorized `POST` request with a body, an
+    ///    unauthorized `POST` request and a `POST` request to an unknown endpoint
+    /// 2. Verify that the body of the authorized `POST` request was still passed to its handler
+    /// 3. Verify that only the `POST` requests were recorded, with the client's identity, the
+    ///    status of the response and the digest of the body for the authorized request, as denied
+    ///    for the unauthorized request and as denied with a `404` for the unknown endpoint
+    #[cfg(feature = "rest-api-audit")]
+    #[test]
+    fn auth_middleware_audit() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let audit_store = FileAuditStore::new(temp_dir.path().join("audit.log"));
+
+        let auth_middleware =
+            Authorization::new(vec![Box::new(AlwaysAcceptIdentityProvider)], vec![])
+                .with_audit_store(Box::new(audit_store.clone()));
+
+        let mut permission_map = PermissionMap::default();
+        permission_map.add_permission(Method::Get, "/", Permission::AllowAuthenticated);
+        permission_map.add_permission(Method::Post, "/", Permission::AllowAuthenticated);
+
+        let mut service = test::init_service(
+            App::new()
+                .wrap(auth_middleware)
+                .data(permission_map)
+                .service(
+                    web::resource("/")
+                        .route(web::get().to(|| HttpResponse::Ok()))
+                        .route(web::post().to(|body: String| {
+                            if body == "payload" {
+                                HttpResponse::Accepted()
+                            } else {
+                                HttpResponse::BadRequest()
+                            }
+                        })),
+                ),
+        );
+
+        let req = test::TestRequest::with_uri("/")
+            .header("Authorization", "test")
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::with_uri("/?value=1")
+            .method(ActixMethod::POST)
+            .header("Authorization", "test")
+            .set_payload("payload")
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let req = test::TestRequest::with_uri("/")
+            .method(ActixMethod::POST)
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::with_uri("/unknown")
+            .method(ActixMethod::POST)
+            .header("Authorization", "test")
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let entries = audit_store
+            .list_entries(None, None)
+            .expect("Unable to list entries")
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(
+            entries[0].identity(),
+            Some(&Identity::Custom("identity".into()))
+        );
+        assert_eq!(entries[0].method(), "POST");
+        assert_eq!(entries[0].route(), "/");
+        assert_eq!(entries[0].decision(), AuditDecision::Allowed);
+        assert_eq!(entries[0].status(), 202);
+        assert_eq!(
+            entries[0].request_digest(),
+            request_digest("POST", "/?value=1", b"payload")
+        );
+
+        assert_eq!(entries[1].identity(), None);
+        assert_eq!(entries[1].decision(), AuditDecision::Denied);
+        assert_eq!(entries[1].status(), 401);
+
+        assert_eq!(
+            entries[2].identity(),
+            Some(&Identity::Custom("identity".into()))
+        );
+        assert_eq!(entries[2].route(), "/unknown");
+        assert_eq!(entries[2].decision(), AuditDecision::Denied);
+        assert_eq!(entries[2].status(), 404);
+    }
+
+    /// Verifies that the authorization middleware rejects a request whose body is larger than can
+    /// be read for the audit log with a `413 Payload Too Large` response, without recording it or
+    /// passing it on to the service.
+    #[cfg(feature = "rest-api-audit")]
+    #[test]
+    fn auth_middleware_audit_body_too_large() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let audit_store = FileAuditStore::new(temp_dir.path().join("audit.log"));
+
+        let auth_middleware =
+            Authorization::new(vec![Box::new(AlwaysAcceptIdentityProvider)], vec![])
+                .with_audit_store(Box::new(audit_store.clone()));
+
+        let mut permission_map = PermissionMap::default();
+        permission_map.add_permission(Method::Post, "/", Permission::AllowAuthenticated);
+
+        let mut service = test::init_service(
+            App::new()
+                .wrap(auth_middleware)
+                .data(permission_map)
+                .route("/", web::post().to(|| HttpResponse::Accepted())),
+        );
+
+        let req = test::TestRequest::with_uri("/")
+            .method(ActixMethod::POST)
+            .header("Authorization", "test")
+            .set_payload(vec![0u8; MAX_AUDITED_BODY_SIZE + 1])
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        assert_eq!(
+            audit_store
+                .list_entries(None, None)
+                .expect("Unable to list entries")
+                .count(),
+            0
+        );
+    }
+
     /// An identity provider that always returns `Ok(Some(_))`
     #[derive(Clone)]
     struct AlwaysAcceptIdentityProvider;
@@ -0,0 +1,290 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An audit log of the requests made to the REST API
+//!
+//! The authorization middleware records an [`AuditEntry`] for every request that modifies state
+//! (any method other than `GET` or `HEAD`) and for every request that is denied, whatever its
+//! method. Entries are written to an [`AuditStore`](store::AuditStore).
+
+mod routes;
+pub mod store;
+
+use std::fmt;
+use std::time::SystemTime;
+
+use openssl::sha::Sha256;
+
+use crate::error::InvalidStateError;
+use crate::hex::to_hex;
+use crate::rest_api::auth::identity::Identity;
+
+pub use routes::AuditResourceProvider;
+
+/// Whether a request was allowed or denied by the authorization middleware
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AuditDecision {
+    Allowed,
+    Denied,
+}
+
+impl fmt::Display for AuditDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditDecision::Allowed => f.write_str("allowed"),
+            AuditDecision::Denied => f.write_str("denied"),
+        }
+    }
+}
+
+/// A record of a single request to the REST API
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    timestamp: SystemTime,
+    identity: Option<Identity>,
+    permission_id: Option<String>,
+    method: String,
+    route: String,
+    decision: AuditDecision,
+    status: u16,
+    request_digest: String,
+}
+
+impl AuditEntry {
+    /// Returns the time the request was received
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Returns the identity of the client that made the request, if it could be determined
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
+    /// Returns the ID of the permission that was checked for the request, if the endpoint
+    /// requires one
+    pub fn permission_id(&self) -> Option<&str> {
+        self.permission_id.as_deref()
+    }
+
+    /// Returns the HTTP method of the request
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the path of the request, without the query string
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// Returns whether the request was allowed or denied
+    pub fn decision(&self) -> AuditDecision {
+        self.decision
+    }
+
+    /// Returns the HTTP status code of the response
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the digest of the request; see [`request_digest`]
+    pub fn request_digest(&self) -> &str {
+        &self.request_digest
+    }
+}
+
+/// Builds a new `AuditEntry`
+#[derive(Default)]
+pub struct AuditEntryBuilder {
+    timestamp: Option<SystemTime>,
+    identity: Option<Identity>,
+    permission_id: Option<String>,
+    method: Option<String>,
+    route: Option<String>,
+    decision: Option<AuditDecision>,
+    status: Option<u16>,
+    request_digest: Option<String>,
+}
+
+impl AuditEntryBuilder {
+    /// Creates a new `AuditEntryBuilder`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the time the request was received
+    ///
+    /// If not provided, the current time is used.
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the identity of the client that made the request
+    pub fn with_identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Sets the ID of the permission that was checked for the request
+    pub fn with_permission_id(mut self, permission_id: String) -> Self {
+        self.permission_id = Some(permission_id);
+        self
+    }
+
+    /// Sets the HTTP method of the request
+    ///
+    /// This field is required.
+    pub fn with_method(mut self, method: String) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Sets the path of the request
+    ///
+    /// This field is required.
+    pub fn with_route(mut self, route: String) -> Self {
+        self.route = Some(route);
+        self
+    }
+
+    /// Sets whether the request was allowed or denied
+    ///
+    /// This field is required.
+    pub fn with_decision(mut self, decision: AuditDecision) -> Self {
+        self.decision = Some(decision);
+        self
+    }
+
+    /// Sets the HTTP status code of the response
+    ///
+    /// This field is required.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Sets the digest of the request
+    ///
+    /// This field is required.
+    pub fn with_request_digest(mut self, request_digest: String) -> Self {
+        self.request_digest = Some(request_digest);
+        self
+    }
+
+    /// Builds the `AuditEntry`
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if any of the required fields were not set.
+    pub fn build(self) -> Result<AuditEntry, InvalidStateError> {
+        let method = self.method.ok_or_else(|| missing_field("method"))?;
+        let route = self.route.ok_or_else(|| missing_field("route"))?;
+        let decision = self.decision.ok_or_else(|| missing_field("decision"))?;
+        let status = self.status.ok_or_else(|| missing_field("status"))?;
+        let request_digest = self
+            .request_digest
+            .ok_or_else(|| missing_field("request_digest"))?;
+
+        Ok(AuditEntry {
+            timestamp: self.timestamp.unwrap_or_else(SystemTime::now),
+            identity: self.identity,
+            permission_id: self.permission_id,
+            method,
+            route,
+            decision,
+            status,
+            request_digest,
+        })
+    }
+}
+
+fn missing_field(field: &str) -> InvalidStateError {
+    InvalidStateError::with_message(format!(
+        "Unable to build AuditEntry: Missing required field: {}",
+        field
+    ))
+}
+
+/// Computes the digest of a request, which is the hex-encoded SHA-256 hash of the request's
+/// method, URI, including the query string, and body
+///
+/// The digest allows a request to be matched against the logs of a client or proxy without
+/// storing query parameters or bodies, which may contain sensitive values, in the audit log.
+pub fn request_digest(method: &str, uri: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{} {}\n", method, uri).as_bytes());
+    hasher.update(body);
+    to_hex(&hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the request digest depends on the method, path, query string and body of a
+    /// request.
+    #[test]
+    fn test_request_digest() {
+        let digest = request_digest("POST", "/authorization/maintenance?enabled=true", b"");
+        assert_eq!(digest.len(), 64);
+        assert_eq!(
+            digest,
+            request_digest("POST", "/authorization/maintenance?enabled=true", b"")
+        );
+        assert_ne!(
+            digest,
+            request_digest("POST", "/authorization/maintenance?enabled=false", b"")
+        );
+        assert_ne!(
+            digest,
+            request_digest("DELETE", "/authorization/maintenance?enabled=true", b"")
+        );
+
+        let digest = request_digest("POST", "/admin/submit", b"payload-1");
+        assert_eq!(
+            digest,
+            request_digest("POST", "/admin/submit", b"payload-1")
+        );
+        assert_ne!(
+            digest,
+            request_digest("POST", "/admin/submit", b"payload-2")
+        );
+        assert_ne!(digest, request_digest("POST", "/admin/submit", b""));
+    }
+
+    /// Verify that an entry cannot be built without the required fields.
+    #[test]
+    fn test_build_missing_fields() {
+        assert!(AuditEntryBuilder::new()
+            .with_method("POST".into())
+            .with_route("/admin/submit".into())
+            .with_decision(AuditDecision::Allowed)
+            .with_request_digest(request_digest("POST", "/admin/submit", b""))
+            .build()
+            .is_err());
+
+        let entry = AuditEntryBuilder::new()
+            .with_method("POST".into())
+            .with_route("/admin/submit".into())
+            .with_decision(AuditDecision::Allowed)
+            .with_status(202)
+            .with_request_digest(request_digest("POST", "/admin/submit", b""))
+            .build()
+            .expect("Unable to build entry");
+        assert_eq!(entry.identity(), None);
+        assert_eq!(entry.permission_id(), None);
+        assert_eq!(entry.status(), 202);
+    }
+}
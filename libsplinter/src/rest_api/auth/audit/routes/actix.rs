@@ -0,0 +1,362 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoint:
+//!
+//! * `GET /audit` for listing the entries of the audit log
+
+use std::time::{Duration, SystemTime};
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use crate::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    auth::audit::store::{AuditStore, AuditStoreError},
+    paging::{get_response_cursor_paging_info, get_response_paging_info, Cursor},
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+use super::{
+    resources::{AuditEntryResponse, ListAuditEntriesQuery, ListAuditEntriesResponse},
+    AUDIT_READ_PERMISSION,
+};
+
+const AUDIT_MIN: u32 = 1;
+
+pub fn make_audit_resource(audit_store: Box<dyn AuditStore>) -> Resource {
+    Resource::build("/audit")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            AUDIT_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, AUDIT_READ_PERMISSION, move |r, _| {
+            list_audit_entries(r, audit_store.clone())
+        })
+}
+
+fn list_audit_entries(
+    req: HttpRequest,
+    audit_store: Box<dyn AuditStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query = match web::Query::<ListAuditEntriesQuery>::from_query(req.query_string()) {
+        Ok(query) => query.into_inner(),
+        Err(_) => return bad_request("Invalid query"),
+    };
+
+    if query.limit == 0 {
+        return bad_request("Invalid limit: must be greater than 0");
+    }
+    if let (Some(since), Some(until)) = (query.since, query.until) {
+        if since > until {
+            return bad_request("Invalid time range: since must not be after until");
+        }
+    }
+
+    let since = match query.since.map(to_system_time).transpose() {
+        Ok(since) => since,
+        Err(msg) => return bad_request(msg),
+    };
+    let until = match query.until.map(to_system_time).transpose() {
+        Ok(until) => until,
+        Err(msg) => return bad_request(msg),
+    };
+    let cursor = match query.cursor.as_deref().map(Cursor::decode).transpose() {
+        Ok(cursor) => cursor,
+        Err(err) => return bad_request(&format!("Invalid cursor: {}", err)),
+    };
+
+    // Keep the time range in the paging links
+    let mut link = format!("{}?", req.uri().path());
+    if let Some(since) = query.since {
+        link.push_str(&format!("since={}&", since));
+    }
+    if let Some(until) = query.until {
+        link.push_str(&format!("until={}&", until));
+    }
+
+    let limit = query.limit;
+    let offset = query.offset;
+
+    Box::new(
+        web::block(move || {
+            let total = audit_store.count_entries(since, until)?;
+
+            // An explicit offset without a cursor is served as before, so that existing offset
+            // links keep working; only the entries up to the end of the page are read
+            if let (Some(offset), None) = (offset, cursor.as_ref()) {
+                let entries = audit_store
+                    .list_entries_page(since, until, None, offset.saturating_add(limit))?
+                    .into_entries()
+                    .into_iter()
+                    .skip(offset)
+                    .collect::<Vec<_>>();
+                let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+                return Ok((entries, paging));
+            }
+
+            // One more entry than the limit is fetched to find out whether there is a next page
+            let mut page = audit_store.list_entries_page(
+                since,
+                until,
+                cursor.as_ref().map(Cursor::key),
+                limit.saturating_add(1),
+            )?;
+            let has_more = page.truncate(limit);
+
+            let next_cursor = Cursor::following(cursor.as_ref(), limit, has_more, page.last_key());
+            let paging = get_response_cursor_paging_info(
+                limit,
+                cursor.as_ref(),
+                next_cursor.as_ref(),
+                &link,
+                total,
+            );
+
+            Ok((page.into_entries(), paging))
+        })
+        .then(|res: Result<_, BlockingError<AuditStoreError>>| match res {
+            Ok((entries, paging)) => Ok(HttpResponse::Ok().json(ListAuditEntriesResponse {
+                data: entries.iter().map(AuditEntryResponse::from).collect(),
+                paging,
+            })),
+            Err(BlockingError::Error(AuditStoreError::InvalidArgument(err))) => Ok(
+                HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+                    "Invalid cursor: {}",
+                    err
+                ))),
+            ),
+            Err(err) => {
+                error!("Unable to list audit entries: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
+    )
+}
+
+fn to_system_time(seconds: u64) -> Result<SystemTime, &'static str> {
+    SystemTime::UNIX_EPOCH
+        .checked_add(Duration::from_secs(seconds))
+        .ok_or("Invalid time range: time is out of range")
+}
+
+fn bad_request(msg: &str) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::BadRequest()
+            .json(ErrorResponse::bad_request(msg))
+            .into_future(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::{blocking::Client, StatusCode, Url};
+    use tempfile::TempDir;
+
+    use crate::rest_api::actix_web_1::{RestApiBuilder, RestApiShutdownHandle};
+    use crate::rest_api::auth::audit::{
+        request_digest, store::FileAuditStore, AuditDecision, AuditEntryBuilder,
+    };
+    use crate::rest_api::auth::identity::Identity;
+
+    /// Verifies that `GET /audit` lists the entries of the audit log with time range filters and
+    /// paging.
+    ///
+    /// 1. Add three entries, recorded one minute apart, to a file audit store
+    /// 2. Run the REST API with the audit endpoint
+    /// 3. Verify that all entries are listed, oldest first, without any query parameters
+    /// 4. Verify that only the second entry is listed with `since` and `until` set to the time it
+    ///    was recorded and the time the third entry was recorded
+    /// 5. Verify that the paging information is correct with a limit and offset
+    /// 6. Verify that the first page with a limit links to the next page with a cursor, and that
+    ///    the next page has the remaining entry
+    /// 7. Verify that an invalid cursor and a time range that ends before it starts are rejected
+    #[test]
+    fn list_entries() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = FileAuditStore::new(temp_dir.path().join("audit.log"));
+
+        let start = 1_650_000_000;
+        for i in 0..3 {
+            store
+                .add_entry(
+                    AuditEntryBuilder::new()
+                        .with_timestamp(
+                            SystemTime::UNIX_EPOCH + Duration::from_secs(start + 60 * i),
+                        )
+                        .with_identity(Identity::Key(format!("key-{}", i)))
+                        .with_permission_id("circuit.write".into())
+                        .with_method("POST".into())
+                        .with_route("/admin/submit".into())
+                        .with_decision(AuditDecision::Allowed)
+                        .with_status(202)
+                        .with_request_digest(request_digest("POST", "/admin/submit", b""))
+                        .build()
+                        .expect("Unable to build entry"),
+                )
+                .expect("Unable to add entry");
+        }
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_audit_resource(Box::new(store))]);
+
+        let url = Url::parse(&format!("http://{}/audit", bind_url)).expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .get(url.clone())
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp
+            .json::<serde_json::Value>()
+            .expect("Failed to parse response body");
+        let data = body["data"].as_array().expect("Missing data");
+        assert_eq!(data.len(), 3);
+        assert_eq!(
+            data[0],
+            json!({
+                "timestamp": start,
+                "identity": { "identity_type": "key", "identity": "key-0" },
+                "permission_id": "circuit.write",
+                "method": "POST",
+                "route": "/admin/submit",
+                "decision": "allowed",
+                "status": 202,
+                "request_digest": request_digest("POST", "/admin/submit", b""),
+            })
+        );
+        assert_eq!(data[2]["timestamp"], json!(start + 120));
+
+        let resp = Client::new()
+            .get(url.clone())
+            .query(&[("since", start + 60), ("until", start + 120)])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp
+            .json::<serde_json::Value>()
+            .expect("Failed to parse response body");
+        let data = body["data"].as_array().expect("Missing data");
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["identity"]["identity"], json!("key-1"));
+
+        let resp = Client::new()
+            .get(url.clone())
+            .query(&[("limit", 2), ("offset", 2)])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp
+            .json::<serde_json::Value>()
+            .expect("Failed to parse response body");
+        assert_eq!(body["data"].as_array().expect("Missing data").len(), 1);
+        assert_eq!(body["paging"]["total"], json!(3));
+        assert_eq!(body["paging"]["offset"], json!(2));
+        assert_eq!(body["paging"]["limit"], json!(2));
+
+        let resp = Client::new()
+            .get(url.clone())
+            .query(&[("limit", 2)])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp
+            .json::<serde_json::Value>()
+            .expect("Failed to parse response body");
+        let data = body["data"].as_array().expect("Missing data");
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[1]["identity"]["identity"], json!("key-1"));
+        let next = body["paging"]["next"].as_str().expect("Missing next link");
+        let cursor = next
+            .split("cursor=")
+            .nth(1)
+            .expect("Missing cursor in next link")
+            .to_string();
+
+        let resp = Client::new()
+            .get(url.clone())
+            .query(&[("limit", "2"), ("cursor", &cursor)])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp
+            .json::<serde_json::Value>()
+            .expect("Failed to parse response body");
+        let data = body["data"].as_array().expect("Missing data");
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0]["identity"]["identity"], json!("key-2"));
+        assert_eq!(body["paging"]["offset"], json!(2));
+        assert_eq!(body["paging"]["total"], json!(3));
+
+        let resp = Client::new()
+            .get(url.clone())
+            .query(&[("cursor", "zzzz")])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = Client::new()
+            .get(url.clone())
+            .query(&[("cursor", Cursor::new(2, "not-a-key").encode())])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = Client::new()
+            .get(url)
+            .query(&[("since", start + 60), ("until", start)])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = crate::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .build_insecure()
+            .expect("Failed to build REST API")
+            .run_insecure();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+}
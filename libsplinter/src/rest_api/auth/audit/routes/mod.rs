@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API endpoints for the audit log
+
+mod actix;
+mod resources;
+
+use crate::rest_api::actix_web_1::{Resource, RestResourceProvider};
+use crate::rest_api::auth::authorization::Permission;
+
+use super::store::AuditStore;
+
+const AUDIT_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "audit.read",
+    permission_display_name: "Audit log read",
+    permission_description: "Allows the client to read the REST API audit log",
+};
+
+/// REST Resource Provider for the audit log
+///
+/// Provides the following endpoint as a REST API resource:
+///
+/// * `GET /audit` - List the entries of the audit log
+pub struct AuditResourceProvider {
+    audit_store: Box<dyn AuditStore>,
+}
+
+impl AuditResourceProvider {
+    /// Constructs a new resource provider with the given store.
+    pub fn new(audit_store: Box<dyn AuditStore>) -> Self {
+        Self { audit_store }
+    }
+}
+
+impl RestResourceProvider for AuditResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![actix::make_audit_resource(self.audit_store.clone())]
+    }
+}
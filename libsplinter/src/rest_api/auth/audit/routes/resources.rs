@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides resources for the audit log's REST API endpoint
+
+use std::time::SystemTime;
+
+use crate::rest_api::{
+    auth::{
+        audit::{AuditDecision, AuditEntry},
+        identity::Identity,
+    },
+    paging::{Paging, DEFAULT_LIMIT},
+};
+
+/// The query parameters of `GET /audit`; times are in seconds since the Unix epoch
+#[derive(Deserialize)]
+pub struct ListAuditEntriesQuery {
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListAuditEntriesResponse<'a> {
+    pub data: Vec<AuditEntryResponse<'a>>,
+    pub paging: Paging,
+}
+
+/// An entry of the audit log; the timestamp is in seconds since the Unix epoch
+#[derive(Debug, Serialize)]
+pub struct AuditEntryResponse<'a> {
+    pub timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<IdentityResponse<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_id: Option<&'a str>,
+    pub method: &'a str,
+    pub route: &'a str,
+    pub decision: &'a str,
+    pub status: u16,
+    pub request_digest: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "identity_type", content = "identity")]
+#[serde(rename_all = "lowercase")]
+pub enum IdentityResponse<'a> {
+    Custom(&'a str),
+    Key(&'a str),
+    User(&'a str),
+}
+
+impl<'a> From<&'a AuditEntry> for AuditEntryResponse<'a> {
+    fn from(entry: &'a AuditEntry) -> Self {
+        Self {
+            timestamp: entry
+                .timestamp()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|timestamp| timestamp.as_secs())
+                .unwrap_or(0),
+            identity: entry.identity().map(IdentityResponse::from),
+            permission_id: entry.permission_id(),
+            method: entry.method(),
+            route: entry.route(),
+            decision: match entry.decision() {
+                AuditDecision::Allowed => "allowed",
+                AuditDecision::Denied => "denied",
+            },
+            status: entry.status(),
+            request_digest: entry.request_digest(),
+        }
+    }
+}
+
+impl<'a> From<&'a Identity> for IdentityResponse<'a> {
+    fn from(identity: &'a Identity) -> Self {
+        match identity {
+            Identity::Custom(custom) => IdentityResponse::Custom(custom),
+            Identity::Key(key) => IdentityResponse::Key(key),
+            Identity::User(user) => IdentityResponse::User(user),
+        }
+    }
+}
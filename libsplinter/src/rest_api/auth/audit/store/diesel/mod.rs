@@ -0,0 +1,361 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database backed [AuditStore](super::AuditStore) implementation, powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::error::InvalidArgumentError;
+use crate::rest_api::auth::audit::AuditEntry;
+use crate::store::pool::ConnectionPool;
+
+use super::{AuditEntryPage, AuditStore, AuditStoreError};
+
+use operations::add_entry::AuditStoreAddEntryOperation as _;
+use operations::count_entries::AuditStoreCountEntriesOperation as _;
+use operations::list_entries::AuditStoreListEntriesOperation as _;
+use operations::AuditStoreOperations;
+
+/// A database-backed AuditStore, powered by [`Diesel`](https://crates.io/crates/diesel).
+pub struct DieselAuditStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselAuditStore<C> {
+    /// Creates a new `DieselAuditStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselAuditStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselAuditStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselAuditStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl AuditStore for DieselAuditStore<diesel::pg::PgConnection> {
+    fn add_entry(&self, entry: AuditEntry) -> Result<(), AuditStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AuditStoreOperations::new(conn).add_entry(entry))
+    }
+
+    fn list_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AuditStoreError> {
+        let entries = self.connection_pool.execute_read(|conn| {
+            AuditStoreOperations::new(conn).list_entries(since, until, None, None)
+        })?;
+
+        Ok(Box::new(
+            entries
+                .into_iter()
+                .map(|(_, entry)| entry)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    fn list_entries_page(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<AuditEntryPage, AuditStoreError> {
+        let after = after.map(parse_key).transpose()?;
+        let entries = self.connection_pool.execute_read(|conn| {
+            AuditStoreOperations::new(conn).list_entries(since, until, after, Some(limit))
+        })?;
+
+        Ok(into_page(entries))
+    }
+
+    fn count_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<usize, AuditStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AuditStoreOperations::new(conn).count_entries(since, until))
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl AuditStore for DieselAuditStore<diesel::sqlite::SqliteConnection> {
+    fn add_entry(&self, entry: AuditEntry) -> Result<(), AuditStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AuditStoreOperations::new(conn).add_entry(entry))
+    }
+
+    fn list_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AuditStoreError> {
+        let entries = self.connection_pool.execute_read(|conn| {
+            AuditStoreOperations::new(conn).list_entries(since, until, None, None)
+        })?;
+
+        Ok(Box::new(
+            entries
+                .into_iter()
+                .map(|(_, entry)| entry)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    fn list_entries_page(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<AuditEntryPage, AuditStoreError> {
+        let after = after.map(parse_key).transpose()?;
+        let entries = self.connection_pool.execute_read(|conn| {
+            AuditStoreOperations::new(conn).list_entries(since, until, after, Some(limit))
+        })?;
+
+        Ok(into_page(entries))
+    }
+
+    fn count_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<usize, AuditStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AuditStoreOperations::new(conn).count_entries(since, until))
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(self.clone())
+    }
+}
+
+/// Parses the key of an entry, which is the entry's ID
+fn parse_key(key: &str) -> Result<i64, AuditStoreError> {
+    key.parse::<i64>().map_err(|_| {
+        AuditStoreError::from(InvalidArgumentError::new(
+            "after",
+            "not a valid audit entry key",
+        ))
+    })
+}
+
+fn into_page(entries: Vec<(i64, AuditEntry)>) -> AuditEntryPage {
+    AuditEntryPage::new(
+        entries
+            .into_iter()
+            .map(|(id, entry)| (id.to_string(), entry))
+            .collect(),
+    )
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::auth::audit::{request_digest, AuditDecision, AuditEntryBuilder};
+    use crate::rest_api::auth::identity::Identity;
+
+    /// Verify that entries can be added and listed with time range filters.
+    ///
+    /// 1. Add an allowed entry with every field, then a denied entry without an identity or
+    ///    permission one minute later
+    /// 2. Validate both entries are listed, oldest first, without filters
+    /// 3. Validate only the second entry is listed with a `since` filter at its timestamp
+    /// 4. Validate only the first entry is listed with an `until` filter at the second entry's
+    ///    timestamp
+    #[test]
+    fn test_add_and_list_entries() {
+        let store = DieselAuditStore::new(create_connection_pool_and_migrate());
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let allowed = AuditEntryBuilder::new()
+            .with_timestamp(start)
+            .with_identity(Identity::User("user-1".into()))
+            .with_permission_id("authorization.maintenance.write".into())
+            .with_method("POST".into())
+            .with_route("/authorization/maintenance".into())
+            .with_decision(AuditDecision::Allowed)
+            .with_status(200)
+            .with_request_digest(request_digest(
+                "POST",
+                "/authorization/maintenance?enabled=true",
+                b"",
+            ))
+            .build()
+            .expect("Unable to build entry");
+        let denied = AuditEntryBuilder::new()
+            .with_timestamp(start + Duration::from_secs(60))
+            .with_method("DELETE".into())
+            .with_route("/authorization/roles/admin".into())
+            .with_decision(AuditDecision::Denied)
+            .with_status(401)
+            .with_request_digest(request_digest("DELETE", "/authorization/roles/admin", b""))
+            .build()
+            .expect("Unable to build entry");
+
+        store
+            .add_entry(allowed.clone())
+            .expect("Unable to add entry");
+        store
+            .add_entry(denied.clone())
+            .expect("Unable to add entry");
+
+        assert_eq!(
+            store
+                .list_entries(None, None)
+                .expect("Unable to list entries")
+                .collect::<Vec<_>>(),
+            vec![allowed.clone(), denied.clone()]
+        );
+        assert_eq!(
+            store
+                .list_entries(Some(denied.timestamp()), None)
+                .expect("Unable to list entries")
+                .collect::<Vec<_>>(),
+            vec![denied.clone()]
+        );
+        assert_eq!(
+            store
+                .list_entries(None, Some(denied.timestamp()))
+                .expect("Unable to list entries")
+                .collect::<Vec<_>>(),
+            vec![allowed]
+        );
+    }
+
+    /// Verify that entries can be listed a page at a time and counted.
+    ///
+    /// 1. Add five entries recorded a minute apart
+    /// 2. Validate the entries in the time range are counted
+    /// 3. Validate the entries in the time range are listed two at a time, starting after the key
+    ///    of the previous page, until an empty page without a key is returned
+    /// 4. Validate an invalid key is rejected
+    #[test]
+    fn test_list_entries_page() {
+        let store = DieselAuditStore::new(create_connection_pool_and_migrate());
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let entries = (0..5)
+            .map(|i| {
+                AuditEntryBuilder::new()
+                    .with_timestamp(start + Duration::from_secs(60 * i))
+                    .with_method("POST".into())
+                    .with_route("/admin/submit".into())
+                    .with_decision(AuditDecision::Allowed)
+                    .with_status(202)
+                    .with_request_digest(request_digest("POST", "/admin/submit", b""))
+                    .build()
+                    .expect("Unable to build entry")
+            })
+            .collect::<Vec<_>>();
+        for entry in entries.iter().cloned() {
+            store.add_entry(entry).expect("Unable to add entry");
+        }
+
+        let since = Some(start + Duration::from_secs(60));
+        assert_eq!(
+            store
+                .count_entries(since, None)
+                .expect("Unable to count entries"),
+            4
+        );
+
+        let page = store
+            .list_entries_page(since, None, None, 2)
+            .expect("Unable to list entries");
+        assert_eq!(page.entries(), &entries[1..3]);
+
+        let page = store
+            .list_entries_page(since, None, page.last_key(), 2)
+            .expect("Unable to list entries");
+        assert_eq!(page.entries(), &entries[3..5]);
+
+        let page = store
+            .list_entries_page(since, None, page.last_key(), 2)
+            .expect("Unable to list entries");
+        assert!(page.entries().is_empty());
+        assert_eq!(page.last_key(), None);
+
+        assert!(matches!(
+            store.list_entries_page(since, None, Some("invalid"), 2),
+            Err(AuditStoreError::InvalidArgument(_))
+        ));
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::rest_api::auth::audit::{
+    store::AuditStoreError, AuditDecision, AuditEntry, AuditEntryBuilder,
+};
+use crate::rest_api::auth::identity::Identity;
+
+use super::schema::rest_api_audit_log;
+
+#[derive(Debug, PartialEq, Identifiable, Queryable)]
+#[table_name = "rest_api_audit_log"]
+#[primary_key(id)]
+pub struct AuditEntryModel {
+    pub id: i64,
+    // The time the request was received, in seconds since the Unix epoch
+    pub timestamp: i64,
+    pub identity: Option<String>,
+    pub identity_type: Option<String>,
+    pub permission_id: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub decision: String,
+    pub status: i32,
+    pub request_digest: String,
+}
+
+#[derive(Debug, PartialEq, Insertable)]
+#[table_name = "rest_api_audit_log"]
+pub struct NewAuditEntryModel {
+    pub timestamp: i64,
+    pub identity: Option<String>,
+    pub identity_type: Option<String>,
+    pub permission_id: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub decision: String,
+    pub status: i32,
+    pub request_digest: String,
+}
+
+impl TryFrom<AuditEntry> for NewAuditEntryModel {
+    type Error = AuditStoreError;
+
+    fn try_from(entry: AuditEntry) -> Result<Self, Self::Error> {
+        let (identity, identity_type) = match entry.identity() {
+            Some(Identity::Custom(custom)) => (Some(custom.clone()), Some("custom".to_string())),
+            Some(Identity::Key(key)) => (Some(key.clone()), Some("key".to_string())),
+            Some(Identity::User(user)) => (Some(user.clone()), Some("user".to_string())),
+            None => (None, None),
+        };
+
+        Ok(NewAuditEntryModel {
+            timestamp: to_seconds(entry.timestamp())?,
+            identity,
+            identity_type,
+            permission_id: entry.permission_id().map(String::from),
+            method: entry.method().to_string(),
+            route: entry.route().to_string(),
+            decision: entry.decision().to_string(),
+            status: i32::from(entry.status()),
+            request_digest: entry.request_digest().to_string(),
+        })
+    }
+}
+
+impl TryFrom<AuditEntryModel> for AuditEntry {
+    type Error = AuditStoreError;
+
+    fn try_from(model: AuditEntryModel) -> Result<Self, Self::Error> {
+        let decision = match model.decision.as_str() {
+            "allowed" => AuditDecision::Allowed,
+            "denied" => AuditDecision::Denied,
+            decision => {
+                return Err(AuditStoreError::Internal(InternalError::with_message(
+                    format!("Invalid audit entry decision: {}", decision),
+                )))
+            }
+        };
+        let status = u16::try_from(model.status).map_err(|_| {
+            AuditStoreError::Internal(InternalError::with_message(format!(
+                "Invalid audit entry status: {}",
+                model.status
+            )))
+        })?;
+
+        let mut builder = AuditEntryBuilder::new()
+            .with_timestamp(from_seconds(model.timestamp))
+            .with_method(model.method)
+            .with_route(model.route)
+            .with_decision(decision)
+            .with_status(status)
+            .with_request_digest(model.request_digest);
+
+        let identity = match (model.identity, model.identity_type.as_deref()) {
+            (Some(identity), Some("custom")) => Some(Identity::Custom(identity)),
+            (Some(identity), Some("key")) => Some(Identity::Key(identity)),
+            (Some(identity), Some("user")) => Some(Identity::User(identity)),
+            (None, None) => None,
+            (_, identity_type) => {
+                return Err(AuditStoreError::Internal(InternalError::with_message(
+                    format!("Invalid audit entry identity type: {:?}", identity_type),
+                )))
+            }
+        };
+        if let Some(identity) = identity {
+            builder = builder.with_identity(identity);
+        }
+        if let Some(permission_id) = model.permission_id {
+            builder = builder.with_permission_id(permission_id);
+        }
+
+        builder
+            .build()
+            .map_err(|err| AuditStoreError::Internal(InternalError::from_source(Box::new(err))))
+    }
+}
+
+pub fn to_seconds(time: SystemTime) -> Result<i64, AuditStoreError> {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            AuditStoreError::Internal(InternalError::with_message(
+                "Audit entry time is out of range".to_string(),
+            ))
+        })
+}
+
+fn from_seconds(seconds: i64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64)
+}
@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::rest_api::auth::audit::{
+    store::{
+        diesel::{models::NewAuditEntryModel, schema::rest_api_audit_log},
+        AuditStoreError,
+    },
+    AuditEntry,
+};
+
+use super::AuditStoreOperations;
+
+pub(in crate::rest_api::auth::audit::store::diesel) trait AuditStoreAddEntryOperation {
+    fn add_entry(&self, entry: AuditEntry) -> Result<(), AuditStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AuditStoreAddEntryOperation for AuditStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_entry(&self, entry: AuditEntry) -> Result<(), AuditStoreError> {
+        insert_into(rest_api_audit_log::table)
+            .values(NewAuditEntryModel::try_from(entry)?)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AuditStoreAddEntryOperation
+    for AuditStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_entry(&self, entry: AuditEntry) -> Result<(), AuditStoreError> {
+        insert_into(rest_api_audit_log::table)
+            .values(NewAuditEntryModel::try_from(entry)?)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
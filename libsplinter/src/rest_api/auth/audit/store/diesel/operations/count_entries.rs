@@ -0,0 +1,63 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::SystemTime;
+
+use diesel::{dsl::count_star, prelude::*};
+
+use crate::error::InternalError;
+use crate::rest_api::auth::audit::store::{
+    diesel::{models::to_seconds, schema::rest_api_audit_log},
+    AuditStoreError,
+};
+
+use super::AuditStoreOperations;
+
+pub(in crate::rest_api::auth::audit::store::diesel) trait AuditStoreCountEntriesOperation {
+    fn count_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<usize, AuditStoreError>;
+}
+
+impl<'a, C> AuditStoreCountEntriesOperation for AuditStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn count_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<usize, AuditStoreError> {
+        let mut query = rest_api_audit_log::table.select(count_star()).into_boxed();
+        if let Some(since) = since {
+            query = query.filter(rest_api_audit_log::timestamp.ge(to_seconds(since)?));
+        }
+        if let Some(until) = until {
+            query = query.filter(rest_api_audit_log::timestamp.lt(to_seconds(until)?));
+        }
+
+        let count = query.first::<i64>(self.conn)?;
+
+        usize::try_from(count).map_err(|_| {
+            AuditStoreError::Internal(InternalError::with_message(format!(
+                "Invalid audit entry count: {}",
+                count
+            )))
+        })
+    }
+}
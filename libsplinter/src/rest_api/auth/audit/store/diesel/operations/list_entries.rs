@@ -0,0 +1,83 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::SystemTime;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::audit::{
+    store::{
+        diesel::{
+            models::{to_seconds, AuditEntryModel},
+            schema::rest_api_audit_log,
+        },
+        AuditStoreError,
+    },
+    AuditEntry,
+};
+
+use super::AuditStoreOperations;
+
+pub(in crate::rest_api::auth::audit::store::diesel) trait AuditStoreListEntriesOperation {
+    /// Lists the entries recorded in the time range, from oldest to newest, with the ID of each
+    /// entry; the list starts with the entry following the `after` ID and has at most `limit`
+    /// entries, if these are provided.
+    fn list_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+        after: Option<i64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<(i64, AuditEntry)>, AuditStoreError>;
+}
+
+impl<'a, C> AuditStoreListEntriesOperation for AuditStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
+{
+    fn list_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+        after: Option<i64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<(i64, AuditEntry)>, AuditStoreError> {
+        let mut query = rest_api_audit_log::table.into_boxed();
+        if let Some(since) = since {
+            query = query.filter(rest_api_audit_log::timestamp.ge(to_seconds(since)?));
+        }
+        if let Some(until) = until {
+            query = query.filter(rest_api_audit_log::timestamp.lt(to_seconds(until)?));
+        }
+        if let Some(after) = after {
+            query = query.filter(rest_api_audit_log::id.gt(after));
+        }
+        if let Some(limit) = limit {
+            query = query.limit(i64::try_from(limit).unwrap_or(i64::MAX));
+        }
+
+        // Entries are inserted in the order they are recorded, so the ID orders entries that
+        // were recorded within the same second
+        query
+            .order(rest_api_audit_log::id)
+            .load::<AuditEntryModel>(self.conn)?
+            .into_iter()
+            .map(|model| Ok((model.id, AuditEntry::try_from(model)?)))
+            .collect()
+    }
+}
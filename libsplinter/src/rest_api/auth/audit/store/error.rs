@@ -0,0 +1,76 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Errors for the AuditStore trait
+
+use std::error::Error;
+use std::fmt;
+
+use crate::error::{InternalError, InvalidArgumentError, ResourceTemporarilyUnavailableError};
+
+/// Represents AuditStore errors
+#[derive(Debug)]
+pub enum AuditStoreError {
+    Internal(InternalError),
+    InvalidArgument(InvalidArgumentError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl fmt::Display for AuditStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditStoreError::Internal(err) => err.fmt(f),
+            AuditStoreError::InvalidArgument(err) => err.fmt(f),
+            AuditStoreError::ResourceTemporarilyUnavailable(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for AuditStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AuditStoreError::Internal(err) => Some(err),
+            AuditStoreError::InvalidArgument(err) => Some(err),
+            AuditStoreError::ResourceTemporarilyUnavailable(err) => Some(err),
+        }
+    }
+}
+
+impl From<InternalError> for AuditStoreError {
+    fn from(err: InternalError) -> Self {
+        AuditStoreError::Internal(err)
+    }
+}
+
+impl From<InvalidArgumentError> for AuditStoreError {
+    fn from(err: InvalidArgumentError) -> Self {
+        AuditStoreError::InvalidArgument(err)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for AuditStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        AuditStoreError::Internal(InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for AuditStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        AuditStoreError::ResourceTemporarilyUnavailable(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
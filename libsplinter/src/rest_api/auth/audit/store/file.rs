@@ -0,0 +1,437 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An [AuditStore](super::AuditStore) that appends entries to a file
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use crate::error::{InternalError, InvalidArgumentError};
+use crate::rest_api::auth::audit::{AuditDecision, AuditEntry, AuditEntryBuilder};
+use crate::rest_api::auth::identity::Identity;
+
+use super::{AuditEntryPage, AuditStore, AuditStoreError};
+
+/// An AuditStore that appends entries to a file, one JSON object per line
+///
+/// The file is created if it does not exist and is only readable by its owner. Entries are never
+/// removed from the file; it may be rotated by an external tool while the REST API is running.
+///
+/// The key of an entry, used to list pages of entries, is the position of its line in the file, so
+/// keys are no longer valid once the file has been rotated.
+#[derive(Clone)]
+pub struct FileAuditStore {
+    path: PathBuf,
+    // Serializes writes, so that entries from concurrent requests are not interleaved
+    lock: Arc<Mutex<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonAuditEntry {
+    // The time the request was received, in seconds since the Unix epoch
+    timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    identity: Option<JsonIdentity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    permission_id: Option<String>,
+    method: String,
+    route: String,
+    decision: JsonDecision,
+    status: u16,
+    request_digest: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "identity_type", content = "identity")]
+#[serde(rename_all = "lowercase")]
+enum JsonIdentity {
+    Custom(String),
+    Key(String),
+    User(String),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JsonDecision {
+    Allowed,
+    Denied,
+}
+
+impl FileAuditStore {
+    /// Creates a new `FileAuditStore`
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the audit log file
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileAuditStore {
+            path: path.into(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Reads the entries of the file in order, calling `f` with the position of each entry's line
+    /// in the file and the entry, until `f` returns `false`; the entries at or before the `after`
+    /// position are skipped without being parsed. Only one entry is held in memory at a time.
+    fn for_each_entry<F>(&self, after: Option<usize>, mut f: F) -> Result<(), AuditStoreError>
+    where
+        F: FnMut(usize, AuditEntry) -> bool,
+    {
+        let _guard = self.lock.lock().map_err(|_| {
+            InternalError::with_message("File audit store's lock poisoned".to_string())
+        })?;
+
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            // Nothing has been recorded yet
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => {
+                return Err(AuditStoreError::from(
+                    InternalError::from_source_with_message(
+                        Box::new(err),
+                        format!("Unable to open audit log file {}", self.path.display()),
+                    ),
+                ))
+            }
+        };
+
+        for (position, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Unable to read audit log file {}", self.path.display()),
+                )
+            })?;
+            if line.trim().is_empty() || after.map(|after| position <= after).unwrap_or(false) {
+                continue;
+            }
+
+            let entry = serde_json::from_str::<JsonAuditEntry>(&line)
+                .map_err(|err| {
+                    InternalError::from_source_with_message(
+                        Box::new(err),
+                        format!("Unable to parse audit log file {}", self.path.display()),
+                    )
+                })
+                .and_then(entry_from_json)?;
+
+            if !f(position, entry) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_in_range(entry: &AuditEntry, since: Option<SystemTime>, until: Option<SystemTime>) -> bool {
+    since
+        .map(|since| entry.timestamp() >= since)
+        .unwrap_or(true)
+        && until.map(|until| entry.timestamp() < until).unwrap_or(true)
+}
+
+impl AuditStore for FileAuditStore {
+    fn add_entry(&self, entry: AuditEntry) -> Result<(), AuditStoreError> {
+        let mut line = serde_json::to_vec(&JsonAuditEntry::from(entry)).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "Unable to serialize audit entry".to_string(),
+            )
+        })?;
+        line.push(b'\n');
+
+        let _guard = self.lock.lock().map_err(|_| {
+            InternalError::with_message("File audit store's lock poisoned".to_string())
+        })?;
+
+        OpenOptions::new()
+            .append(true)
+            .create(true)
+            .mode(0o600)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&line))
+            .map_err(|err| {
+                AuditStoreError::from(InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Unable to write audit log file {}", self.path.display()),
+                ))
+            })
+    }
+
+    fn list_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AuditStoreError> {
+        let mut entries = vec![];
+        self.for_each_entry(None, |_, entry| {
+            if is_in_range(&entry, since, until) {
+                entries.push(entry);
+            }
+            true
+        })?;
+
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn list_entries_page(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<AuditEntryPage, AuditStoreError> {
+        let after = after
+            .map(|after| {
+                after.parse::<usize>().map_err(|_| {
+                    AuditStoreError::from(InvalidArgumentError::new(
+                        "after",
+                        "not a valid audit entry key",
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let mut entries = vec![];
+        if limit > 0 {
+            self.for_each_entry(after, |position, entry| {
+                if is_in_range(&entry, since, until) {
+                    entries.push((position.to_string(), entry));
+                }
+                entries.len() < limit
+            })?;
+        }
+
+        Ok(AuditEntryPage::new(entries))
+    }
+
+    fn count_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<usize, AuditStoreError> {
+        let mut count = 0;
+        self.for_each_entry(None, |_, entry| {
+            if is_in_range(&entry, since, until) {
+                count += 1;
+            }
+            true
+        })?;
+
+        Ok(count)
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(self.clone())
+    }
+}
+
+impl From<AuditEntry> for JsonAuditEntry {
+    fn from(entry: AuditEntry) -> Self {
+        JsonAuditEntry {
+            timestamp: entry
+                .timestamp
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|timestamp| timestamp.as_secs())
+                .unwrap_or(0),
+            identity: entry.identity.map(|identity| match identity {
+                Identity::Custom(custom) => JsonIdentity::Custom(custom),
+                Identity::Key(key) => JsonIdentity::Key(key),
+                Identity::User(user) => JsonIdentity::User(user),
+            }),
+            permission_id: entry.permission_id,
+            method: entry.method,
+            route: entry.route,
+            decision: match entry.decision {
+                AuditDecision::Allowed => JsonDecision::Allowed,
+                AuditDecision::Denied => JsonDecision::Denied,
+            },
+            status: entry.status,
+            request_digest: entry.request_digest,
+        }
+    }
+}
+
+fn entry_from_json(entry: JsonAuditEntry) -> Result<AuditEntry, InternalError> {
+    let mut builder = AuditEntryBuilder::new()
+        .with_timestamp(SystemTime::UNIX_EPOCH + Duration::from_secs(entry.timestamp))
+        .with_method(entry.method)
+        .with_route(entry.route)
+        .with_decision(match entry.decision {
+            JsonDecision::Allowed => AuditDecision::Allowed,
+            JsonDecision::Denied => AuditDecision::Denied,
+        })
+        .with_status(entry.status)
+        .with_request_digest(entry.request_digest);
+
+    if let Some(identity) = entry.identity {
+        builder = builder.with_identity(match identity {
+            JsonIdentity::Custom(custom) => Identity::Custom(custom),
+            JsonIdentity::Key(key) => Identity::Key(key),
+            JsonIdentity::User(user) => Identity::User(user),
+        });
+    }
+    if let Some(permission_id) = entry.permission_id {
+        builder = builder.with_permission_id(permission_id);
+    }
+
+    builder
+        .build()
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    use tempfile::TempDir;
+
+    use crate::rest_api::auth::audit::request_digest;
+
+    /// Verify that entries are appended to the file and can be listed with time range filters.
+    ///
+    /// 1. Validate no entries are listed before the file exists
+    /// 2. Add three entries recorded a minute apart and validate the file is only readable by its
+    ///    owner
+    /// 3. Validate all entries are listed, oldest first, without filters
+    /// 4. Validate the `since` filter includes entries recorded at that time and the `until`
+    ///    filter excludes them
+    /// 5. Validate a second store for the same file lists the same entries
+    #[test]
+    fn test_add_and_list_entries() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("audit.log");
+        let store = FileAuditStore::new(&path);
+
+        assert_eq!(
+            store
+                .list_entries(None, None)
+                .expect("Unable to list entries")
+                .len(),
+            0
+        );
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let entries = (0..3)
+            .map(|i| entry(start + Duration::from_secs(60 * i)))
+            .collect::<Vec<_>>();
+        for entry in entries.iter().cloned() {
+            store.add_entry(entry).expect("Unable to add entry");
+        }
+
+        let mode = fs::metadata(&path)
+            .expect("Unable to read metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        assert_eq!(
+            store
+                .list_entries(None, None)
+                .expect("Unable to list entries")
+                .collect::<Vec<_>>(),
+            entries
+        );
+        assert_eq!(
+            store
+                .list_entries(
+                    Some(start + Duration::from_secs(60)),
+                    Some(start + Duration::from_secs(120))
+                )
+                .expect("Unable to list entries")
+                .collect::<Vec<_>>(),
+            vec![entries[1].clone()]
+        );
+
+        assert_eq!(
+            FileAuditStore::new(&path)
+                .list_entries(None, None)
+                .expect("Unable to list entries")
+                .collect::<Vec<_>>(),
+            entries
+        );
+    }
+
+    /// Verify that entries can be listed a page at a time and counted.
+    ///
+    /// 1. Add five entries recorded a minute apart
+    /// 2. Validate the entries in the time range are counted
+    /// 3. Validate the entries in the time range are listed two at a time, starting after the key
+    ///    of the previous page, until an empty page without a key is returned
+    /// 4. Validate an invalid key is rejected
+    #[test]
+    fn test_list_entries_page() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store = FileAuditStore::new(temp_dir.path().join("audit.log"));
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let entries = (0..5)
+            .map(|i| entry(start + Duration::from_secs(60 * i)))
+            .collect::<Vec<_>>();
+        for entry in entries.iter().cloned() {
+            store.add_entry(entry).expect("Unable to add entry");
+        }
+
+        let since = Some(start + Duration::from_secs(60));
+        assert_eq!(
+            store
+                .count_entries(since, None)
+                .expect("Unable to count entries"),
+            4
+        );
+
+        let page = store
+            .list_entries_page(since, None, None, 2)
+            .expect("Unable to list entries");
+        assert_eq!(page.entries(), &entries[1..3]);
+
+        let page = store
+            .list_entries_page(since, None, page.last_key(), 2)
+            .expect("Unable to list entries");
+        assert_eq!(page.entries(), &entries[3..5]);
+
+        let page = store
+            .list_entries_page(since, None, page.last_key(), 2)
+            .expect("Unable to list entries");
+        assert!(page.entries().is_empty());
+        assert_eq!(page.last_key(), None);
+
+        assert!(matches!(
+            store.list_entries_page(since, None, Some("invalid"), 2),
+            Err(AuditStoreError::InvalidArgument(_))
+        ));
+    }
+
+    fn entry(timestamp: SystemTime) -> AuditEntry {
+        AuditEntryBuilder::new()
+            .with_timestamp(timestamp)
+            .with_identity(Identity::Key("key".into()))
+            .with_permission_id("circuit.write".into())
+            .with_method("POST".into())
+            .with_route("/admin/submit".into())
+            .with_decision(AuditDecision::Allowed)
+            .with_status(202)
+            .with_request_digest(request_digest("POST", "/admin/submit", b""))
+            .build()
+            .expect("Unable to build entry")
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for the REST API audit log
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+mod file;
+
+use std::time::SystemTime;
+
+use super::AuditEntry;
+
+pub use error::AuditStoreError;
+pub use file::FileAuditStore;
+
+/// Defines methods for recording and reading audit log entries
+pub trait AuditStore: Send + Sync {
+    /// Adds an entry to the audit log
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The entry to add
+    fn add_entry(&self, entry: AuditEntry) -> Result<(), AuditStoreError>;
+
+    /// Returns the entries in the audit log from oldest to newest
+    ///
+    /// All matching entries are loaded at once; use
+    /// [`list_entries_page`](AuditStore::list_entries_page) to list a large audit log.
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - If provided, only entries recorded at or after this time are returned
+    /// * `until` - If provided, only entries recorded before this time are returned
+    fn list_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = AuditEntry>>, AuditStoreError>;
+
+    /// Returns a page of the entries in the audit log from oldest to newest
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - If provided, only entries recorded at or after this time are returned
+    /// * `until` - If provided, only entries recorded before this time are returned
+    /// * `after` - If provided, the page starts with the entry following the entry with this key;
+    ///   see [`AuditEntryPage::last_key`]
+    /// * `limit` - The maximum number of entries on the page
+    fn list_entries_page(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<AuditEntryPage, AuditStoreError>;
+
+    /// Returns the number of entries in the audit log
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - If provided, only entries recorded at or after this time are counted
+    /// * `until` - If provided, only entries recorded before this time are counted
+    fn count_entries(
+        &self,
+        since: Option<SystemTime>,
+        until: Option<SystemTime>,
+    ) -> Result<usize, AuditStoreError>;
+
+    fn clone_box(&self) -> Box<dyn AuditStore>;
+}
+
+impl Clone for Box<dyn AuditStore> {
+    fn clone(&self) -> Box<dyn AuditStore> {
+        self.clone_box()
+    }
+}
+
+/// A page of the entries in the audit log, listed by [`AuditStore::list_entries_page`]
+#[derive(Debug, PartialEq)]
+pub struct AuditEntryPage {
+    entries: Vec<AuditEntry>,
+    keys: Vec<String>,
+}
+
+impl AuditEntryPage {
+    /// Creates a new `AuditEntryPage`
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The entries on the page, from oldest to newest, each with its key
+    pub fn new(entries: Vec<(String, AuditEntry)>) -> Self {
+        let (keys, entries) = entries.into_iter().unzip();
+        Self { entries, keys }
+    }
+
+    /// Returns the entries on the page, from oldest to newest
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    /// Returns the key of the last entry on the page; the following page is listed by passing it
+    /// as the `after` argument of [`AuditStore::list_entries_page`]
+    pub fn last_key(&self) -> Option<&str> {
+        self.keys.last().map(String::as_str)
+    }
+
+    /// Shortens the page to its first `len` entries, returning whether any entries were removed
+    pub fn truncate(&mut self, len: usize) -> bool {
+        let truncated = self.entries.len() > len;
+        self.entries.truncate(len);
+        self.keys.truncate(len);
+        truncated
+    }
+
+    /// Converts the page into its entries
+    pub fn into_entries(self) -> Vec<AuditEntry> {
+        self.entries
+    }
+}
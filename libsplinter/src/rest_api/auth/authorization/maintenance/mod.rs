@@ -15,11 +15,10 @@
 //! An authorization handler that allows write permissions to be temporarily revoked
 
 mod routes;
+pub mod store;
 
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
-};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 
 use crate::error::InternalError;
 #[cfg(feature = "authorization-handler-rbac")]
@@ -30,6 +29,8 @@ use crate::rest_api::auth::identity::Identity;
 
 use super::{AuthorizationHandler, AuthorizationHandlerResult};
 
+use store::{MaintenanceState, MaintenanceStateBuilder, MaintenanceStore};
+
 /// An authorization handler that allows write permissions to be temporarily revoked
 ///
 /// For the purposes of this authorization handler, a write permission is any permission whose ID
@@ -41,10 +42,17 @@ use super::{AuthorizationHandler, AuthorizationHandlerResult};
 /// on whether or not maintenance mode is enabled. If maintenance mode is enabled, checks for
 /// non-read permission will always result in a [`AuthorizationHandlerResult::Deny`] result; if
 /// disabled, all permission checks will always result in a [`AuthorizationHandlerResult::Continue`]
-/// result.
+/// result. Maintenance mode is no longer enabled once the expiration time it was enabled with, if
+/// any, has passed. If the state of maintenance mode can't be read, non-read permission checks
+/// result in [`AuthorizationHandlerResult::Deny`].
+///
+/// If the handler has a [`MaintenanceStore`], the state of maintenance mode is kept in the store,
+/// so it is preserved when the handler is restarted. Otherwise, the state is kept in memory and
+/// maintenance mode starts out disabled.
 #[derive(Clone, Default)]
 pub struct MaintenanceModeAuthorizationHandler {
-    maintenance_mode: Arc<AtomicBool>,
+    maintenance_state: Arc<RwLock<Option<MaintenanceState>>>,
+    maintenance_store: Option<Box<dyn MaintenanceStore>>,
     #[cfg(feature = "authorization-handler-rbac")]
     rbac_store: Option<Box<dyn RoleBasedAuthorizationStore>>,
 }
@@ -63,15 +71,58 @@ impl MaintenanceModeAuthorizationHandler {
         }
     }
 
+    /// Sets the store that the state of maintenance mode is kept in
+    pub fn with_maintenance_store(mut self, maintenance_store: Box<dyn MaintenanceStore>) -> Self {
+        self.maintenance_store = Some(maintenance_store);
+        self
+    }
+
+    /// Returns the current state of maintenance mode, or `None` if it has never been set
+    pub fn maintenance_state(&self) -> Result<Option<MaintenanceState>, InternalError> {
+        match &self.maintenance_store {
+            Some(store) => store
+                .get_state()
+                .map_err(|err| InternalError::from_source(Box::new(err))),
+            None => Ok(self
+                .maintenance_state
+                .read()
+                .map_err(|_| {
+                    InternalError::with_message("Maintenance state lock poisoned".to_string())
+                })?
+                .clone()),
+        }
+    }
+
     /// Returns whether or not maintenance mode is enabled
-    pub fn is_maintenance_mode_enabled(&self) -> bool {
-        self.maintenance_mode.load(Ordering::Relaxed)
+    pub fn is_maintenance_mode_enabled(&self) -> Result<bool, InternalError> {
+        Ok(self
+            .maintenance_state()?
+            .map(|state| state.is_enabled_at(SystemTime::now()))
+            .unwrap_or(false))
     }
 
-    /// Sets whether or not maintenance mode is enabled
-    pub fn set_maintenance_mode(&self, maintenance_mode: bool) {
-        self.maintenance_mode
-            .store(maintenance_mode, Ordering::Relaxed);
+    /// Sets whether or not maintenance mode is enabled, without recording who set it or why
+    pub fn set_maintenance_mode(&self, maintenance_mode: bool) -> Result<(), InternalError> {
+        let state = MaintenanceStateBuilder::new()
+            .with_enabled(maintenance_mode)
+            .build()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        self.set_maintenance_state(state)
+    }
+
+    /// Replaces the state of maintenance mode
+    pub fn set_maintenance_state(&self, state: MaintenanceState) -> Result<(), InternalError> {
+        match &self.maintenance_store {
+            Some(store) => store
+                .set_state(state)
+                .map_err(|err| InternalError::from_source(Box::new(err))),
+            None => {
+                *self.maintenance_state.write().map_err(|_| {
+                    InternalError::with_message("Maintenance state lock poisoned".to_string())
+                })? = Some(state);
+                Ok(())
+            }
+        }
     }
 }
 
@@ -82,7 +133,21 @@ impl AuthorizationHandler for MaintenanceModeAuthorizationHandler {
         #[allow(unused_variables)] identity: &Identity,
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError> {
-        if !permission_id.ends_with(".read") && self.maintenance_mode.load(Ordering::Relaxed) {
+        if permission_id.ends_with(".read") {
+            return Ok(AuthorizationHandlerResult::Continue);
+        }
+
+        // If the state of maintenance mode can't be read, it may be enabled, so the permission is
+        // denied rather than passed on to the next handler
+        let is_maintenance_mode_enabled = match self.is_maintenance_mode_enabled() {
+            Ok(enabled) => enabled,
+            Err(err) => {
+                error!("Unable to check if maintenance mode is enabled: {}", err);
+                return Ok(AuthorizationHandlerResult::Deny);
+            }
+        };
+
+        if is_maintenance_mode_enabled {
             // Check if the client has the "admin" role, in which case they're not denied permission
             #[cfg(feature = "authorization-handler-rbac")]
             {
@@ -120,6 +185,19 @@ impl AuthorizationHandler for MaintenanceModeAuthorizationHandler {
 mod tests {
     use super::*;
 
+    use std::time::Duration;
+
+    #[cfg(feature = "sqlite")]
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    #[cfg(feature = "sqlite")]
+    use crate::migrations::run_sqlite_migrations;
+    #[cfg(feature = "sqlite")]
+    use crate::rest_api::auth::authorization::maintenance::store::diesel::DieselMaintenanceStore;
+    use crate::rest_api::auth::authorization::maintenance::store::MaintenanceStoreError;
     use crate::rest_api::auth::authorization::rbac::store::{
         Assignment, AssignmentBuilder, Role, RoleBasedAuthorizationStore,
         RoleBasedAuthorizationStoreError,
@@ -141,15 +219,27 @@ mod tests {
     #[test]
     fn auth_handler_read_permissions() {
         let handler = MaintenanceModeAuthorizationHandler::default();
-        assert_eq!(handler.is_maintenance_mode_enabled(), false);
+        assert_eq!(
+            handler
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            false
+        );
 
         assert!(matches!(
             handler.has_permission(&Identity::Custom("identity".into()), "permission.read"),
             Ok(AuthorizationHandlerResult::Continue)
         ));
 
-        handler.set_maintenance_mode(true);
-        assert_eq!(handler.is_maintenance_mode_enabled(), true);
+        handler
+            .set_maintenance_mode(true)
+            .expect("Unable to set maintenance mode");
+        assert_eq!(
+            handler
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            true
+        );
 
         assert!(matches!(
             handler.has_permission(&Identity::Custom("identity".into()), "permission.read"),
@@ -168,21 +258,40 @@ mod tests {
     #[test]
     fn auth_handler_non_read_permissions() {
         let handler = MaintenanceModeAuthorizationHandler::default();
-        assert_eq!(handler.is_maintenance_mode_enabled(), false);
+        assert_eq!(
+            handler
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            false
+        );
         assert!(matches!(
             handler.has_permission(&Identity::Custom("identity".into()), "permission"),
             Ok(AuthorizationHandlerResult::Continue)
         ));
 
-        handler.set_maintenance_mode(true);
-        assert_eq!(handler.is_maintenance_mode_enabled(), true);
+        handler
+            .set_maintenance_mode(true)
+            .expect("Unable to set maintenance mode");
+        assert_eq!(
+            handler
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            true
+        );
         assert!(matches!(
             handler.has_permission(&Identity::Custom("identity".into()), "permission"),
             Ok(AuthorizationHandlerResult::Deny)
         ));
 
-        handler.set_maintenance_mode(false);
-        assert_eq!(handler.is_maintenance_mode_enabled(), false);
+        handler
+            .set_maintenance_mode(false)
+            .expect("Unable to set maintenance mode");
+        assert_eq!(
+            handler
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            false
+        );
         assert!(matches!(
             handler.has_permission(&Identity::Custom("identity".into()), "permission"),
             Ok(AuthorizationHandlerResult::Continue)
@@ -207,8 +316,15 @@ mod tests {
             MockRoleBasedAuthorizationStore,
         )));
 
-        handler.set_maintenance_mode(true);
-        assert_eq!(handler.is_maintenance_mode_enabled(), true);
+        handler
+            .set_maintenance_mode(true)
+            .expect("Unable to set maintenance mode");
+        assert_eq!(
+            handler
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            true
+        );
 
         assert!(matches!(
             handler.has_permission(&Identity::User(ADMIN_USER_IDENTITY.into()), "permission"),
@@ -229,6 +345,153 @@ mod tests {
         ));
     }
 
+    /// Verifies that maintenance mode is no longer enabled once its expiration time has passed.
+    ///
+    /// 1. Enable maintenance mode with an expiration time in the future and verify that a `Deny`
+    ///    result is returned by `has_permission`
+    /// 2. Enable maintenance mode with an expiration time in the past and verify that maintenance
+    ///    mode is disabled and a `Continue` result is returned by `has_permission`
+    #[test]
+    fn auth_handler_expired_maintenance_mode() {
+        let handler = MaintenanceModeAuthorizationHandler::default();
+        let now = SystemTime::now();
+
+        handler
+            .set_maintenance_state(
+                MaintenanceStateBuilder::new()
+                    .with_enabled(true)
+                    .with_set_at(now)
+                    .with_expires_at(now + Duration::from_secs(3600))
+                    .build()
+                    .expect("Unable to build state"),
+            )
+            .expect("Unable to set maintenance state");
+        assert!(matches!(
+            handler.has_permission(&Identity::Custom("identity".into()), "permission"),
+            Ok(AuthorizationHandlerResult::Deny)
+        ));
+
+        handler
+            .set_maintenance_state(
+                MaintenanceStateBuilder::new()
+                    .with_enabled(true)
+                    .with_set_at(now - Duration::from_secs(7200))
+                    .with_expires_at(now - Duration::from_secs(3600))
+                    .build()
+                    .expect("Unable to build state"),
+            )
+            .expect("Unable to set maintenance state");
+        assert_eq!(
+            handler
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            false
+        );
+        assert!(matches!(
+            handler.has_permission(&Identity::Custom("identity".into()), "permission"),
+            Ok(AuthorizationHandlerResult::Continue)
+        ));
+    }
+
+    /// Verifies that the maintenance mode authorization handler denies non-read permissions when
+    /// the state of maintenance mode can't be read from the store, and still passes on read
+    /// permissions.
+    #[test]
+    fn auth_handler_unreadable_state() {
+        let handler = MaintenanceModeAuthorizationHandler::default()
+            .with_maintenance_store(Box::new(FailingMaintenanceStore));
+
+        assert!(matches!(
+            handler.has_permission(&Identity::Custom("identity".into()), "permission"),
+            Ok(AuthorizationHandlerResult::Deny)
+        ));
+        assert!(matches!(
+            handler.has_permission(&Identity::Custom("identity".into()), "permission.read"),
+            Ok(AuthorizationHandlerResult::Continue)
+        ));
+    }
+
+    /// Verifies that the state of maintenance mode is kept in the maintenance store, so that it
+    /// is preserved when the handler is recreated.
+    ///
+    /// 1. Create a `MaintenanceModeAuthorizationHandler` with a SQLite-backed store
+    /// 2. Enable maintenance mode with an identity and a reason
+    /// 3. Create a new handler with the same store and verify that maintenance mode is still
+    ///    enabled, with the same identity and reason
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn auth_handler_persistent_state() {
+        let store = create_maintenance_store();
+
+        let handler = MaintenanceModeAuthorizationHandler::default()
+            .with_maintenance_store(Box::new(store.clone()));
+        handler
+            .set_maintenance_state(
+                MaintenanceStateBuilder::new()
+                    .with_enabled(true)
+                    .with_set_by(Identity::User("user-1".into()))
+                    .with_reason("Upgrading the database".into())
+                    .build()
+                    .expect("Unable to build state"),
+            )
+            .expect("Unable to set maintenance state");
+
+        let restarted =
+            MaintenanceModeAuthorizationHandler::default().with_maintenance_store(Box::new(store));
+        assert_eq!(
+            restarted
+                .is_maintenance_mode_enabled()
+                .expect("Unable to check maintenance mode"),
+            true
+        );
+        let state = restarted
+            .maintenance_state()
+            .expect("Unable to get maintenance state")
+            .expect("Maintenance state not set");
+        assert_eq!(state.set_by(), Some(&Identity::User("user-1".into())));
+        assert_eq!(state.reason(), Some("Upgrading the database"));
+        assert!(matches!(
+            restarted.has_permission(&Identity::Custom("identity".into()), "permission"),
+            Ok(AuthorizationHandlerResult::Deny)
+        ));
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn create_maintenance_store() -> DieselMaintenanceStore<SqliteConnection> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        DieselMaintenanceStore::new(pool)
+    }
+
+    /// A maintenance store that is never able to read or write the state
+    #[derive(Clone)]
+    struct FailingMaintenanceStore;
+
+    impl MaintenanceStore for FailingMaintenanceStore {
+        fn get_state(&self) -> Result<Option<MaintenanceState>, MaintenanceStoreError> {
+            Err(MaintenanceStoreError::Internal(
+                InternalError::with_message("failed".into()),
+            ))
+        }
+
+        fn set_state(&self, _state: MaintenanceState) -> Result<(), MaintenanceStoreError> {
+            Err(MaintenanceStoreError::Internal(
+                InternalError::with_message("failed".into()),
+            ))
+        }
+
+        fn clone_box(&self) -> Box<dyn MaintenanceStore> {
+            Box::new(self.clone())
+        }
+    }
+
     #[derive(Clone)]
     struct MockRoleBasedAuthorizationStore;
 
@@ -16,19 +16,29 @@
 //!
 //! * `GET /authorization/maintenance` for checking if maintenance mode is enabled
 //! * `POST /authorization/maintenance` for enabling/disabling maintenance mode
+//!
+//! The `GET` endpoint responds with `true` or `false` as plain text, unless the request accepts
+//! `application/json`, in which case the full state of maintenance mode is returned.
+
+use std::time::{Duration, SystemTime};
 
-use actix_web::{web, Error, HttpRequest, HttpResponse};
+use actix_web::{http::header, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, Future};
 
 use crate::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
-    auth::authorization::maintenance::MaintenanceModeAuthorizationHandler,
+    auth::{
+        authorization::maintenance::{
+            store::MaintenanceStateBuilder, MaintenanceModeAuthorizationHandler,
+        },
+        identity::Identity,
+    },
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
 use super::{
-    resources::PostMaintenanceModeQuery, AUTHORIZATION_MAINTENANCE_READ_PERMISSION,
-    AUTHORIZATION_MAINTENANCE_WRITE_PERMISSION,
+    resources::{MaintenanceModeResponse, PostMaintenanceModeQuery},
+    AUTHORIZATION_MAINTENANCE_READ_PERMISSION, AUTHORIZATION_MAINTENANCE_WRITE_PERMISSION,
 };
 
 const AUTHORIZATION_MAINTENANCE_MIN: u32 = 1;
@@ -43,7 +53,7 @@ pub fn make_maintenance_resource(auth_handler: MaintenanceModeAuthorizationHandl
         .add_method(
             Method::Get,
             AUTHORIZATION_MAINTENANCE_READ_PERMISSION,
-            move |_, _| get_maintenance_mode(auth_handler.clone()),
+            move |r, _| get_maintenance_mode(r, auth_handler.clone()),
         )
         .add_method(
             Method::Post,
@@ -53,12 +63,37 @@ pub fn make_maintenance_resource(auth_handler: MaintenanceModeAuthorizationHandl
 }
 
 fn get_maintenance_mode(
+    req: HttpRequest,
     auth_handler: MaintenanceModeAuthorizationHandler,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let accepts_json = req
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .map(|accept| accept.contains("application/json"))
+        .unwrap_or(false);
+
     Box::new(
-        HttpResponse::Ok()
-            .body(auth_handler.is_maintenance_mode_enabled().to_string())
-            .into_future(),
+        web::block(move || {
+            // The error is converted to a string, since it must be sent between threads
+            auth_handler
+                .maintenance_state()
+                .map_err(|err| err.to_string())
+        })
+        .then(move |res| match res {
+            Ok(state) => {
+                let response = MaintenanceModeResponse::new(state.as_ref(), SystemTime::now());
+                if accepts_json {
+                    Ok(HttpResponse::Ok().json(response))
+                } else {
+                    Ok(HttpResponse::Ok().body(response.enabled.to_string()))
+                }
+            }
+            Err(err) => {
+                error!("Unable to get maintenance state: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
     )
 }
 
@@ -66,16 +101,64 @@ fn post_maintenance_mode(
     req: HttpRequest,
     auth_handler: MaintenanceModeAuthorizationHandler,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query = match web::Query::<PostMaintenanceModeQuery>::from_query(req.query_string()) {
+        Ok(query) => query.into_inner(),
+        Err(_) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            )
+        }
+    };
+
+    let now = SystemTime::now();
+    let mut builder = MaintenanceStateBuilder::new()
+        .with_enabled(query.enabled)
+        .with_set_at(now);
+    if let Some(identity) = req.extensions().get::<Identity>() {
+        builder = builder.with_set_by(identity.clone());
+    }
+    if let Some(reason) = query.reason {
+        builder = builder.with_reason(reason);
+    }
+    if let Some(expires_in) = query.expires_in {
+        match now.checked_add(Duration::from_secs(expires_in)) {
+            Some(expires_at) => builder = builder.with_expires_at(expires_at),
+            None => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid expires_in"))
+                        .into_future(),
+                )
+            }
+        }
+    }
+
+    let state = match builder.build() {
+        Ok(state) => state,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&err.to_string()))
+                    .into_future(),
+            )
+        }
+    };
+
     Box::new(
-        match web::Query::<PostMaintenanceModeQuery>::from_query(req.query_string()) {
-            Ok(query) => {
-                auth_handler.set_maintenance_mode(query.enabled);
-                HttpResponse::Ok().finish().into_future()
+        web::block(move || {
+            auth_handler
+                .set_maintenance_state(state)
+                .map_err(|err| err.to_string())
+        })
+        .then(|res| match res {
+            Ok(()) => Ok(HttpResponse::Ok().finish()),
+            Err(err) => {
+                error!("Unable to set maintenance state: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
             }
-            _ => HttpResponse::BadRequest()
-                .json(ErrorResponse::bad_request("Invalid query"))
-                .into_future(),
-        },
+        }),
     )
 }
 
@@ -173,7 +256,9 @@ mod tests {
         let (shutdown_handle, join_handle, bind_url) =
             run_rest_api_on_open_port(vec![make_maintenance_resource(handler.clone())]);
 
-        assert!(!handler.is_maintenance_mode_enabled());
+        assert!(!handler
+            .is_maintenance_mode_enabled()
+            .expect("Unable to check maintenance mode"));
 
         let url = Url::parse(&format!("http://{}/authorization/maintenance", bind_url))
             .expect("Failed to parse URL");
@@ -186,7 +271,9 @@ mod tests {
             .send()
             .expect("Failed to perform request");
         assert_eq!(resp.status(), StatusCode::OK);
-        assert!(!handler.is_maintenance_mode_enabled());
+        assert!(!handler
+            .is_maintenance_mode_enabled()
+            .expect("Unable to check maintenance mode"));
 
         // Enable
         let resp = Client::new()
@@ -196,7 +283,9 @@ mod tests {
             .send()
             .expect("Failed to perform request");
         assert_eq!(resp.status(), StatusCode::OK);
-        assert!(handler.is_maintenance_mode_enabled());
+        assert!(handler
+            .is_maintenance_mode_enabled()
+            .expect("Unable to check maintenance mode"));
 
         // Enable (idempotent)
         let resp = Client::new()
@@ -206,7 +295,86 @@ mod tests {
             .send()
             .expect("Failed to perform request");
         assert_eq!(resp.status(), StatusCode::OK);
-        assert!(handler.is_maintenance_mode_enabled());
+        assert!(handler
+            .is_maintenance_mode_enabled()
+            .expect("Unable to check maintenance mode"));
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Verifies that the `GET /authorization/maintenance` endpoint returns the full state of
+    /// maintenance mode when the request accepts JSON.
+    ///
+    /// 1. Run the REST API with the maintenance mode endpoints
+    /// 2. Verify that `GET /authorization/maintenance` returns a disabled state with no details
+    /// 3. Enable maintenance mode with a reason and an expiration time using
+    ///    `POST /authorization/maintenance`
+    /// 4. Verify that `GET /authorization/maintenance` returns the enabled state with the reason
+    ///    and an expiration time one hour after the time it was set
+    /// 5. Verify that `POST /authorization/maintenance` rejects an expiration time when disabling
+    ///    maintenance mode
+    #[test]
+    fn get_json_state() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_maintenance_resource(
+                MaintenanceModeAuthorizationHandler::default(),
+            )]);
+
+        let url = Url::parse(&format!("http://{}/authorization/maintenance", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .get(url.clone())
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .header("Accept", "application/json")
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.json::<serde_json::Value>()
+                .expect("Failed to parse response body"),
+            json!({ "enabled": false })
+        );
+
+        let resp = Client::new()
+            .post(url.clone())
+            .query(&[
+                ("enabled", "true"),
+                ("reason", "Upgrading the database"),
+                ("expires_in", "3600"),
+            ])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = Client::new()
+            .get(url.clone())
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .header("Accept", "application/json")
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let state = resp
+            .json::<serde_json::Value>()
+            .expect("Failed to parse response body");
+        assert_eq!(state["enabled"], json!(true));
+        assert_eq!(state["reason"], json!("Upgrading the database"));
+        // The REST API is run without authentication, so there is no identity to record
+        assert!(state.get("set_by").is_none());
+        let set_at = state["set_at"].as_u64().expect("Missing set_at");
+        assert_eq!(state["expires_at"], json!(set_at + 3600));
+
+        let resp = Client::new()
+            .post(url)
+            .query(&[("enabled", "false"), ("expires_in", "3600")])
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
         shutdown_handle
             .shutdown()
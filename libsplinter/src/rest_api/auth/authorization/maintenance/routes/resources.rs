@@ -15,7 +15,78 @@
 //! This module provides resources for the maintenance mode authorization handler's REST API
 //! endpoints
 
+use std::time::SystemTime;
+
+use crate::rest_api::auth::{
+    authorization::maintenance::store::MaintenanceState, identity::Identity,
+};
+
 #[derive(Deserialize)]
 pub struct PostMaintenanceModeQuery {
     pub enabled: bool,
+    pub reason: Option<String>,
+    /// The number of seconds after which maintenance mode is automatically disabled
+    pub expires_in: Option<u64>,
+}
+
+/// The state of maintenance mode; times are in seconds since the Unix epoch
+#[derive(Debug, Serialize)]
+pub struct MaintenanceModeResponse<'a> {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_by: Option<SetByResponse<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "identity_type", content = "identity")]
+#[serde(rename_all = "lowercase")]
+pub enum SetByResponse<'a> {
+    Custom(&'a str),
+    Key(&'a str),
+    User(&'a str),
+}
+
+impl<'a> MaintenanceModeResponse<'a> {
+    /// Creates the response for the given state, where `now` is used to determine whether or not
+    /// maintenance mode has expired
+    pub fn new(state: Option<&'a MaintenanceState>, now: SystemTime) -> Self {
+        match state {
+            Some(state) => Self {
+                enabled: state.is_enabled_at(now),
+                set_by: state.set_by().map(SetByResponse::from),
+                set_at: Some(to_seconds(state.set_at())),
+                reason: state.reason(),
+                expires_at: state.expires_at().map(to_seconds),
+            },
+            None => Self {
+                enabled: false,
+                set_by: None,
+                set_at: None,
+                reason: None,
+                expires_at: None,
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a Identity> for SetByResponse<'a> {
+    fn from(identity: &'a Identity) -> Self {
+        match identity {
+            Identity::Custom(custom) => SetByResponse::Custom(custom),
+            Identity::Key(key) => SetByResponse::Key(key),
+            Identity::User(user) => SetByResponse::User(user),
+        }
+    }
+}
+
+fn to_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
 }
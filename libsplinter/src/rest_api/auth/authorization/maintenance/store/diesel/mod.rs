@@ -0,0 +1,180 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database backed [MaintenanceStore](super::MaintenanceStore) implementation, powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::store::pool::ConnectionPool;
+
+use super::{MaintenanceState, MaintenanceStore, MaintenanceStoreError};
+
+use operations::get_state::MaintenanceStoreGetStateOperation as _;
+use operations::set_state::MaintenanceStoreSetStateOperation as _;
+use operations::MaintenanceStoreOperations;
+
+/// A database-backed MaintenanceStore, powered by [`Diesel`](https://crates.io/crates/diesel).
+pub struct DieselMaintenanceStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselMaintenanceStore<C> {
+    /// Creates a new `DieselMaintenanceStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselMaintenanceStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselMaintenanceStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselMaintenanceStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl MaintenanceStore for DieselMaintenanceStore<diesel::pg::PgConnection> {
+    fn get_state(&self) -> Result<Option<MaintenanceState>, MaintenanceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| MaintenanceStoreOperations::new(conn).get_state())
+    }
+
+    fn set_state(&self, state: MaintenanceState) -> Result<(), MaintenanceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| MaintenanceStoreOperations::new(conn).set_state(state))
+    }
+
+    fn clone_box(&self) -> Box<dyn MaintenanceStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl MaintenanceStore for DieselMaintenanceStore<diesel::sqlite::SqliteConnection> {
+    fn get_state(&self) -> Result<Option<MaintenanceState>, MaintenanceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| MaintenanceStoreOperations::new(conn).get_state())
+    }
+
+    fn set_state(&self, state: MaintenanceState) -> Result<(), MaintenanceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| MaintenanceStoreOperations::new(conn).set_state(state))
+    }
+
+    fn clone_box(&self) -> Box<dyn MaintenanceStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, SystemTime};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::auth::authorization::maintenance::store::MaintenanceStateBuilder;
+    use crate::rest_api::auth::identity::Identity;
+
+    /// Verify that the state of maintenance mode can be set and replaced.
+    ///
+    /// 1. Validate no state is returned before one has been set
+    /// 2. Set a state with every field and validate it is returned unchanged
+    /// 3. Replace the state with one that only has the required fields and validate only the new
+    ///    state is returned
+    #[test]
+    fn test_set_and_get_state() {
+        let store = DieselMaintenanceStore::new(create_connection_pool_and_migrate());
+
+        assert_eq!(store.get_state().expect("Unable to get state"), None);
+
+        let set_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let enabled = MaintenanceStateBuilder::new()
+            .with_enabled(true)
+            .with_set_by(Identity::User("user-1".into()))
+            .with_set_at(set_at)
+            .with_reason("Upgrading the database".into())
+            .with_expires_at(set_at + Duration::from_secs(3600))
+            .build()
+            .expect("Unable to build state");
+        store
+            .set_state(enabled.clone())
+            .expect("Unable to set state");
+        assert_eq!(
+            store.get_state().expect("Unable to get state"),
+            Some(enabled)
+        );
+
+        let disabled = MaintenanceStateBuilder::new()
+            .with_enabled(false)
+            .with_set_at(set_at + Duration::from_secs(60))
+            .build()
+            .expect("Unable to build state");
+        store
+            .set_state(disabled.clone())
+            .expect("Unable to set state");
+        assert_eq!(
+            store.get_state().expect("Unable to get state"),
+            Some(disabled)
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::rest_api::auth::authorization::maintenance::store::{
+    MaintenanceState, MaintenanceStateBuilder, MaintenanceStoreError,
+};
+use crate::rest_api::auth::identity::Identity;
+
+use super::schema::authorization_maintenance_mode;
+
+/// The ID of the only row of the `authorization_maintenance_mode` table
+pub const MAINTENANCE_STATE_ID: i32 = 0;
+
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "authorization_maintenance_mode"]
+#[primary_key(id)]
+pub struct MaintenanceStateModel {
+    pub id: i32,
+    pub enabled: bool,
+    pub set_by: Option<String>,
+    pub set_by_type: Option<String>,
+    // Times are stored in seconds since the Unix epoch
+    pub set_at: i64,
+    pub reason: Option<String>,
+    pub expires_at: Option<i64>,
+}
+
+impl TryFrom<MaintenanceState> for MaintenanceStateModel {
+    type Error = MaintenanceStoreError;
+
+    fn try_from(state: MaintenanceState) -> Result<Self, Self::Error> {
+        let (set_by, set_by_type) = match state.set_by() {
+            Some(Identity::Custom(custom)) => (Some(custom.clone()), Some("custom".to_string())),
+            Some(Identity::Key(key)) => (Some(key.clone()), Some("key".to_string())),
+            Some(Identity::User(user)) => (Some(user.clone()), Some("user".to_string())),
+            None => (None, None),
+        };
+
+        Ok(MaintenanceStateModel {
+            id: MAINTENANCE_STATE_ID,
+            enabled: state.enabled(),
+            set_by,
+            set_by_type,
+            set_at: to_seconds(state.set_at())?,
+            reason: state.reason().map(String::from),
+            expires_at: state.expires_at().map(to_seconds).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<MaintenanceStateModel> for MaintenanceState {
+    type Error = MaintenanceStoreError;
+
+    fn try_from(model: MaintenanceStateModel) -> Result<Self, Self::Error> {
+        let mut builder = MaintenanceStateBuilder::new()
+            .with_enabled(model.enabled)
+            .with_set_at(from_seconds(model.set_at));
+
+        let set_by = match (model.set_by, model.set_by_type.as_deref()) {
+            (Some(set_by), Some("custom")) => Some(Identity::Custom(set_by)),
+            (Some(set_by), Some("key")) => Some(Identity::Key(set_by)),
+            (Some(set_by), Some("user")) => Some(Identity::User(set_by)),
+            (None, None) => None,
+            (_, set_by_type) => {
+                return Err(MaintenanceStoreError::Internal(
+                    InternalError::with_message(format!(
+                        "Invalid maintenance state identity type: {:?}",
+                        set_by_type
+                    )),
+                ))
+            }
+        };
+        if let Some(set_by) = set_by {
+            builder = builder.with_set_by(set_by);
+        }
+        if let Some(reason) = model.reason {
+            builder = builder.with_reason(reason);
+        }
+        if let Some(expires_at) = model.expires_at {
+            builder = builder.with_expires_at(from_seconds(expires_at));
+        }
+
+        builder.build().map_err(|err| {
+            MaintenanceStoreError::Internal(InternalError::from_source(Box::new(err)))
+        })
+    }
+}
+
+fn to_seconds(time: SystemTime) -> Result<i64, MaintenanceStoreError> {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            MaintenanceStoreError::Internal(InternalError::with_message(
+                "Maintenance state time is out of range".to_string(),
+            ))
+        })
+}
+
+fn from_seconds(seconds: i64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64)
+}
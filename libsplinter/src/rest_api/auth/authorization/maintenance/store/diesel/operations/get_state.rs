@@ -0,0 +1,50 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::authorization::maintenance::store::{
+    diesel::{
+        models::{MaintenanceStateModel, MAINTENANCE_STATE_ID},
+        schema::authorization_maintenance_mode,
+    },
+    MaintenanceState, MaintenanceStoreError,
+};
+
+use super::MaintenanceStoreOperations;
+
+pub(in crate::rest_api::auth::authorization::maintenance::store::diesel) trait MaintenanceStoreGetStateOperation
+{
+    fn get_state(&self) -> Result<Option<MaintenanceState>, MaintenanceStoreError>;
+}
+
+impl<'a, C> MaintenanceStoreGetStateOperation for MaintenanceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+{
+    fn get_state(&self) -> Result<Option<MaintenanceState>, MaintenanceStoreError> {
+        authorization_maintenance_mode::table
+            .find(MAINTENANCE_STATE_ID)
+            .first::<MaintenanceStateModel>(self.conn)
+            .optional()?
+            .map(MaintenanceState::try_from)
+            .transpose()
+    }
+}
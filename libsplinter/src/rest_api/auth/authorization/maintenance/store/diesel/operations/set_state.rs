@@ -0,0 +1,72 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::rest_api::auth::authorization::maintenance::store::{
+    diesel::{
+        models::{MaintenanceStateModel, MAINTENANCE_STATE_ID},
+        schema::authorization_maintenance_mode,
+    },
+    MaintenanceState, MaintenanceStoreError,
+};
+
+use super::MaintenanceStoreOperations;
+
+pub(in crate::rest_api::auth::authorization::maintenance::store::diesel) trait MaintenanceStoreSetStateOperation
+{
+    fn set_state(&self, state: MaintenanceState) -> Result<(), MaintenanceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> MaintenanceStoreSetStateOperation
+    for MaintenanceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_state(&self, state: MaintenanceState) -> Result<(), MaintenanceStoreError> {
+        let model = MaintenanceStateModel::try_from(state)?;
+
+        self.conn.transaction::<(), _, _>(|| {
+            diesel::delete(authorization_maintenance_mode::table.find(MAINTENANCE_STATE_ID))
+                .execute(self.conn)?;
+
+            insert_into(authorization_maintenance_mode::table)
+                .values(model)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> MaintenanceStoreSetStateOperation
+    for MaintenanceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_state(&self, state: MaintenanceState) -> Result<(), MaintenanceStoreError> {
+        let model = MaintenanceStateModel::try_from(state)?;
+
+        self.conn.transaction::<(), _, _>(|| {
+            diesel::delete(authorization_maintenance_mode::table.find(MAINTENANCE_STATE_ID))
+                .execute(self.conn)?;
+
+            insert_into(authorization_maintenance_mode::table)
+                .values(model)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
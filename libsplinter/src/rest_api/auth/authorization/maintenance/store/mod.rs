@@ -0,0 +1,200 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for the state of maintenance mode.
+//!
+//! Keeping the state in a database means that maintenance mode stays enabled when the Splinter
+//! daemon is restarted, rather than silently allowing write operations again.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+use std::time::SystemTime;
+
+use crate::error::InvalidStateError;
+use crate::rest_api::auth::identity::Identity;
+
+pub use error::MaintenanceStoreError;
+
+/// The state of maintenance mode, as last set by a client
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaintenanceState {
+    enabled: bool,
+    set_by: Option<Identity>,
+    set_at: SystemTime,
+    reason: Option<String>,
+    expires_at: Option<SystemTime>,
+}
+
+impl MaintenanceState {
+    /// Returns whether or not maintenance mode was enabled, regardless of whether or not it has
+    /// expired since
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns whether or not maintenance mode is enabled at the given time; maintenance mode is
+    /// no longer enabled once its expiration time has passed
+    pub fn is_enabled_at(&self, time: SystemTime) -> bool {
+        self.enabled
+            && self
+                .expires_at
+                .map(|expires_at| expires_at > time)
+                .unwrap_or(true)
+    }
+
+    /// Returns the identity of the client that set the state, if it is known
+    pub fn set_by(&self) -> Option<&Identity> {
+        self.set_by.as_ref()
+    }
+
+    /// Returns the time the state was set
+    pub fn set_at(&self) -> SystemTime {
+        self.set_at
+    }
+
+    /// Returns the reason given for setting the state, if any
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Returns the time maintenance mode is automatically disabled, if any
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    pub fn into_builder(self) -> MaintenanceStateBuilder {
+        MaintenanceStateBuilder {
+            enabled: Some(self.enabled),
+            set_by: self.set_by,
+            set_at: Some(self.set_at),
+            reason: self.reason,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+/// Builds a new `MaintenanceState`
+#[derive(Default)]
+pub struct MaintenanceStateBuilder {
+    enabled: Option<bool>,
+    set_by: Option<Identity>,
+    set_at: Option<SystemTime>,
+    reason: Option<String>,
+    expires_at: Option<SystemTime>,
+}
+
+impl MaintenanceStateBuilder {
+    /// Creates a new `MaintenanceStateBuilder`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether or not maintenance mode is enabled
+    ///
+    /// This field is required.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Sets the identity of the client that set the state
+    pub fn with_set_by(mut self, set_by: Identity) -> Self {
+        self.set_by = Some(set_by);
+        self
+    }
+
+    /// Sets the time the state was set
+    ///
+    /// If not provided, the current time is used.
+    pub fn with_set_at(mut self, set_at: SystemTime) -> Self {
+        self.set_at = Some(set_at);
+        self
+    }
+
+    /// Sets the reason for setting the state
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Sets the time maintenance mode is automatically disabled
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Builds the `MaintenanceState`
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if `enabled` was not set, or if an expiration time was set
+    /// for a state that does not enable maintenance mode or is not after the time the state was
+    /// set.
+    pub fn build(self) -> Result<MaintenanceState, InvalidStateError> {
+        let enabled = self.enabled.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "Unable to build MaintenanceState: Missing required field: enabled".to_string(),
+            )
+        })?;
+        let set_at = self.set_at.unwrap_or_else(SystemTime::now);
+
+        if let Some(expires_at) = self.expires_at {
+            if !enabled {
+                return Err(InvalidStateError::with_message(
+                    "Unable to build MaintenanceState: An expiration time may only be set when \
+                     enabling maintenance mode"
+                        .to_string(),
+                ));
+            }
+            if expires_at <= set_at {
+                return Err(InvalidStateError::with_message(
+                    "Unable to build MaintenanceState: The expiration time must be after the time \
+                     the state was set"
+                        .to_string(),
+                ));
+            }
+        }
+
+        Ok(MaintenanceState {
+            enabled,
+            set_by: self.set_by,
+            set_at,
+            reason: self.reason,
+            expires_at: self.expires_at,
+        })
+    }
+}
+
+/// Defines methods for reading and writing the state of maintenance mode
+pub trait MaintenanceStore: Send + Sync {
+    /// Returns the current state of maintenance mode, or `None` if it has never been set
+    fn get_state(&self) -> Result<Option<MaintenanceState>, MaintenanceStoreError>;
+
+    /// Replaces the current state of maintenance mode
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The new state
+    fn set_state(&self, state: MaintenanceState) -> Result<(), MaintenanceStoreError>;
+
+    fn clone_box(&self) -> Box<dyn MaintenanceStore>;
+}
+
+impl Clone for Box<dyn MaintenanceStore> {
+    fn clone(&self) -> Box<dyn MaintenanceStore> {
+        self.clone_box()
+    }
+}
@@ -50,6 +50,64 @@ pub enum Permission {
     AllowUnauthenticated,
 }
 
+/// The resource that a request accesses
+///
+/// The resource is determined from the path variables of the requested endpoint; see
+/// [`PermissionMap::get_requested_resource`]. The handlers of scoped endpoints determine the
+/// resources themselves; see [`ResourceAuthorization`]. Authorization handlers may use it to grant
+/// a permission for only some circuits or services.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestedResource {
+    circuit_id: Option<String>,
+    management_type: Option<String>,
+    service_id: Option<String>,
+}
+
+impl RequestedResource {
+    /// Creates a new `RequestedResource` that does not identify any resource
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the circuit being accessed
+    pub fn with_circuit_id(mut self, circuit_id: &str) -> Self {
+        self.circuit_id = Some(circuit_id.to_string());
+        self
+    }
+
+    /// Sets the management type of the circuit being accessed
+    pub fn with_management_type(mut self, management_type: &str) -> Self {
+        self.management_type = Some(management_type.to_string());
+        self
+    }
+
+    /// Sets the ID of the service being accessed
+    pub fn with_service_id(mut self, service_id: &str) -> Self {
+        self.service_id = Some(service_id.to_string());
+        self
+    }
+
+    /// Returns the ID of the circuit being accessed, if known
+    pub fn circuit_id(&self) -> Option<&str> {
+        self.circuit_id.as_deref()
+    }
+
+    /// Returns the management type of the circuit being accessed, if known
+    pub fn management_type(&self) -> Option<&str> {
+        self.management_type.as_deref()
+    }
+
+    /// Returns the ID of the service being accessed, if known
+    pub fn service_id(&self) -> Option<&str> {
+        self.service_id.as_deref()
+    }
+
+    /// Returns `true` if the request does not access a specific resource
+    pub fn is_empty(&self) -> bool {
+        self.circuit_id.is_none() && self.management_type.is_none() && self.service_id.is_none()
+    }
+}
+
 /// An authorization handler's decision about whether to allow, deny, or pass on the request
 pub enum AuthorizationHandlerResult {
     /// The authorization handler has granted the requested permission
@@ -70,6 +128,36 @@ pub trait AuthorizationHandler: Send + Sync {
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError>;
 
+    /// Determines if the given identity has the requested permission for the resource that is
+    /// being accessed
+    ///
+    /// The default implementation ignores the resource and calls
+    /// [`has_permission`](AuthorizationHandler::has_permission). Handlers that grant permissions
+    /// for only some resources override this method, in which case `has_permission` only allows
+    /// the permissions that are granted for every resource.
+    fn has_permission_for_resource(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+        _resource: &RequestedResource,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        self.has_permission(identity, permission_id)
+    }
+
+    /// Determines if the given identity has the requested permission for at least some resources
+    ///
+    /// This is used for endpoints that check the accessed resources themselves, such as endpoints
+    /// that list circuits or whose resource is given in the request body; see
+    /// [`PermissionMap::add_scoped_permission`]. The default implementation calls
+    /// [`has_permission`](AuthorizationHandler::has_permission).
+    fn has_permission_for_any_resource(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        self.has_permission(identity, permission_id)
+    }
+
     /// Clone implementation for `AuthorizationHandler`. The implementation of the `Clone` trait for
     /// `Box<dyn AuthorizationHandler>` calls this method.
     fn clone_box(&self) -> Box<dyn AuthorizationHandler>;
@@ -80,3 +168,48 @@ impl Clone for Box<dyn AuthorizationHandler> {
         self.clone_box()
     }
 }
+
+/// The authorization of a request to a scoped endpoint, for a client that only has the endpoint's
+/// permission for some resources
+///
+/// It is added to the request's extensions, so that the endpoint's handler can check each resource
+/// that the request accesses before accessing it.
+#[derive(Clone)]
+pub struct ResourceAuthorization {
+    identity: Identity,
+    permission_id: String,
+    authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+}
+
+impl ResourceAuthorization {
+    pub(in crate::rest_api) fn new(
+        identity: Identity,
+        permission_id: &str,
+        authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    ) -> Self {
+        Self {
+            identity,
+            permission_id: permission_id.to_string(),
+            authorization_handlers,
+        }
+    }
+
+    /// Returns the identity of the client
+    pub fn identity(&self) -> &Identity {
+        &self.identity
+    }
+
+    /// Returns `true` if the client has the endpoint's permission for the given resource
+    pub fn is_authorized(&self, resource: &RequestedResource) -> bool {
+        for handler in &self.authorization_handlers {
+            match handler.has_permission_for_resource(&self.identity, &self.permission_id, resource)
+            {
+                Ok(AuthorizationHandlerResult::Allow) => return true,
+                Ok(AuthorizationHandlerResult::Deny) => return false,
+                Ok(AuthorizationHandlerResult::Continue) => {}
+                Err(err) => error!("{}", err),
+            }
+        }
+        false
+    }
+}
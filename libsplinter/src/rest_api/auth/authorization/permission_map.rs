@@ -14,7 +14,7 @@
 
 use crate::rest_api::actix_web_1::Method as Actix1Method;
 
-use super::Permission;
+use super::{Permission, RequestedResource};
 
 /// A map used to correlate requests with the permissions that guard them.
 #[derive(Default)]
@@ -43,6 +43,26 @@ impl PermissionMap {
             .push((RequestDefinition::new(method.into(), endpoint), permission));
     }
 
+    /// Sets the permission for the given (method, endpoint) pair of a scoped endpoint, whose
+    /// handler checks the resources that a request accesses itself. A client that has the
+    /// permission for only some resources is allowed to make requests to the endpoint; see
+    /// [`AuthorizationHandler::has_permission_for_any_resource`].
+    ///
+    /// [`AuthorizationHandler::has_permission_for_any_resource`]:
+    ///     super::AuthorizationHandler::has_permission_for_any_resource
+    pub fn add_scoped_permission<M>(&mut self, method: M, endpoint: &str, permission: Permission)
+    where
+        M: Into<Method>,
+    {
+        self.internal.push((
+            RequestDefinition {
+                scoped: true,
+                ..RequestDefinition::new(method.into(), endpoint)
+            },
+            permission,
+        ));
+    }
+
     /// Gets the permission for a request. This will attempt to match the method and endpoint to a
     /// known (method, endpoint) pair, considering path variables of known endpoints.
     pub fn get_permission<M>(&self, method: M, endpoint: &str) -> Option<&Permission>
@@ -55,6 +75,34 @@ impl PermissionMap {
             .map(|(_, perm)| perm)
     }
 
+    /// Gets the resource accessed by a request, based on the values of the path variables of the
+    /// matching endpoint. The `circuit_id` and `circuit` variables identify the circuit and the
+    /// `service_id` variable identifies the service; the returned resource is empty if the
+    /// endpoint has none of these variables or is unknown.
+    pub fn get_requested_resource<M>(&self, method: M, endpoint: &str) -> RequestedResource
+    where
+        M: Into<Method> + Copy,
+    {
+        self.internal
+            .iter()
+            .find(|(req, _)| req.matches(&method.into(), endpoint))
+            .map(|(req, _)| req.requested_resource(endpoint))
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the endpoint that matches the request is scoped; see
+    /// [`add_scoped_permission`](PermissionMap::add_scoped_permission).
+    pub fn is_scoped<M>(&self, method: M, endpoint: &str) -> bool
+    where
+        M: Into<Method> + Copy,
+    {
+        self.internal
+            .iter()
+            .find(|(req, _)| req.matches(&method.into(), endpoint))
+            .map(|(req, _)| req.scoped)
+            .unwrap_or(false)
+    }
+
     /// Takes the contents of another `PermissionMap` and merges them into itself. This consumes the
     /// contents of the other map.
     pub fn append(&mut self, other: &mut PermissionMap) {
@@ -99,6 +147,7 @@ impl From<Actix1Method> for Method {
 struct RequestDefinition {
     method: Method,
     path: Vec<PathComponent>,
+    scoped: bool,
 }
 
 impl RequestDefinition {
@@ -111,7 +160,11 @@ impl RequestDefinition {
             .map(PathComponent::from)
             .collect();
 
-        Self { method, path }
+        Self {
+            method,
+            path,
+            scoped: false,
+        }
     }
 
     /// Checks if the given request matches this definition, considering any variable path
@@ -132,6 +185,28 @@ impl RequestDefinition {
                     .unwrap_or(false)
             })
     }
+
+    /// Gets the resource identified by the path variables of the given endpoint, which must match
+    /// this definition.
+    pub fn requested_resource(&self, endpoint: &str) -> RequestedResource {
+        endpoint
+            .strip_prefix('/')
+            .unwrap_or(endpoint)
+            .split('/')
+            .zip(self.path.iter())
+            .fold(
+                RequestedResource::new(),
+                |resource, (component, path_component)| match path_component {
+                    PathComponent::Variable(name) if name == "circuit_id" || name == "circuit" => {
+                        resource.with_circuit_id(component)
+                    }
+                    PathComponent::Variable(name) if name == "service_id" => {
+                        resource.with_service_id(component)
+                    }
+                    _ => resource,
+                },
+            )
+    }
 }
 
 /// A component of an endpoint path
@@ -139,14 +214,14 @@ impl RequestDefinition {
 enum PathComponent {
     /// A standard path component where matching is done on the internal string
     Text(String),
-    /// A variable path component that matches any string
-    Variable,
+    /// A variable path component that matches any string; contains the name of the variable
+    Variable(String),
 }
 
 impl From<&str> for PathComponent {
     fn from(component: &str) -> Self {
         if component.starts_with('{') && component.ends_with('}') {
-            PathComponent::Variable
+            PathComponent::Variable(component[1..component.len() - 1].into())
         } else {
             PathComponent::Text(component.into())
         }
@@ -156,7 +231,7 @@ impl From<&str> for PathComponent {
 impl PartialEq<&str> for PathComponent {
     fn eq(&self, other: &&str) -> bool {
         match self {
-            PathComponent::Variable => true,
+            PathComponent::Variable(_) => true,
             PathComponent::Text(component) => other == component,
         }
     }
@@ -171,14 +246,14 @@ mod tests {
     fn path_component_parse() {
         assert!(PathComponent::from("") == PathComponent::Text("".into()));
         assert!(PathComponent::from("test") == PathComponent::Text("test".into()));
-        assert!(PathComponent::from("{test}") == PathComponent::Variable);
+        assert!(PathComponent::from("{test}") == PathComponent::Variable("test".into()));
     }
 
     /// Verifies that a `PathComponent` can be correctly compared with a `&str`
     #[test]
     fn path_component_str_comparison() {
-        assert!(PathComponent::Variable == "test1");
-        assert!(PathComponent::Variable == "test2");
+        assert!(PathComponent::Variable("var".into()) == "test1");
+        assert!(PathComponent::Variable("var".into()) == "test2");
         assert!(PathComponent::Text("test1".into()) == "test1");
         assert!(PathComponent::Text("test1".into()) != "test2");
     }
@@ -252,4 +327,63 @@ mod tests {
             None
         );
     }
+
+    /// Verifies that the `PermissionMap` gets the requested resource from the path variables of
+    /// the matching endpoint
+    #[test]
+    fn permission_map_requested_resource() {
+        let perm = Permission::Check {
+            permission_id: "perm",
+            permission_display_name: "",
+            permission_description: "",
+        };
+
+        let mut map = PermissionMap::new();
+        map.add_permission(Actix1Method::Get, "/admin/circuits", perm);
+        map.add_permission(Actix1Method::Get, "/admin/circuits/{circuit_id}", perm);
+        map.add_permission(
+            Actix1Method::Post,
+            "/scabbard/{circuit}/{service_id}/batches",
+            perm,
+        );
+
+        assert!(map
+            .get_requested_resource(&Actix1Method::Get, "/admin/circuits")
+            .is_empty());
+        assert_eq!(
+            map.get_requested_resource(&Actix1Method::Get, "/admin/circuits/abcde-01234"),
+            RequestedResource::new().with_circuit_id("abcde-01234")
+        );
+        assert_eq!(
+            map.get_requested_resource(&Actix1Method::Post, "/scabbard/abcde-01234/a000/batches"),
+            RequestedResource::new()
+                .with_circuit_id("abcde-01234")
+                .with_service_id("a000")
+        );
+        assert!(map
+            .get_requested_resource(&Actix1Method::Get, "/unknown/abcde-01234")
+            .is_empty());
+    }
+
+    /// Verifies that the `PermissionMap` only reports the endpoints that were added as scoped
+    #[test]
+    fn permission_map_scoped() {
+        let perm = Permission::Check {
+            permission_id: "perm",
+            permission_display_name: "",
+            permission_description: "",
+        };
+
+        let mut map = PermissionMap::new();
+        map.add_scoped_permission(Actix1Method::Get, "/admin/circuits", perm);
+        map.add_permission(Actix1Method::Get, "/admin/circuits/{circuit_id}", perm);
+
+        assert_eq!(
+            map.get_permission(&Actix1Method::Get, "/admin/circuits"),
+            Some(&perm)
+        );
+        assert!(map.is_scoped(&Actix1Method::Get, "/admin/circuits"));
+        assert!(!map.is_scoped(&Actix1Method::Get, "/admin/circuits/abcde-01234"));
+        assert!(!map.is_scoped(&Actix1Method::Post, "/admin/circuits"));
+    }
 }
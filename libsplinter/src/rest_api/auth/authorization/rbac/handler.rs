@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "admin-service")]
+use crate::admin::store::AdminServiceStore;
 use crate::error::InternalError;
 
+#[cfg(feature = "rest-api-tokens")]
+use crate::rest_api::auth::token::{get_held_roles, store::ApiTokenStore, token_id_from_identity};
 use crate::rest_api::auth::{
-    authorization::{AuthorizationHandler, AuthorizationHandlerResult},
+    authorization::{AuthorizationHandler, AuthorizationHandlerResult, RequestedResource},
     identity::Identity,
 };
 
-use super::store::{RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
+use super::store::{Role, RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
 
 /// A Role-based authorization handler.
 ///
@@ -27,9 +31,24 @@ use super::store::{RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
 /// it has been assigned.  If one of the identity's assigned roles contains the permission, then
 /// the identity is allowed access. If not, the handler defers to the next handler in the chain.
 ///
+/// A role that is limited to resource scopes only grants its permissions for requests that access
+/// a matching resource, so it is ignored by [`has_permission`], which does not know the resource.
+/// It does allow requests to scoped endpoints, which check each resource themselves; see
+/// [`has_permission_for_any_resource`].
+///
+/// An identity that was resolved from an API token is granted the roles of the token that the
+/// token's owner still holds, if an API token store has been provided.
+///
 /// It currently does not deny any permissions.
+///
+/// [`has_permission`]: AuthorizationHandler::has_permission
+/// [`has_permission_for_any_resource`]: AuthorizationHandler::has_permission_for_any_resource
 pub struct RoleBasedAuthorizationHandler {
     role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+    #[cfg(feature = "admin-service")]
+    admin_service_store: Option<Box<dyn AdminServiceStore>>,
+    #[cfg(feature = "rest-api-tokens")]
+    api_token_store: Option<Box<dyn ApiTokenStore>>,
 }
 
 impl RoleBasedAuthorizationHandler {
@@ -37,7 +56,94 @@ impl RoleBasedAuthorizationHandler {
     pub fn new(role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>) -> Self {
         Self {
             role_based_auth_store,
+            #[cfg(feature = "admin-service")]
+            admin_service_store: None,
+            #[cfg(feature = "rest-api-tokens")]
+            api_token_store: None,
+        }
+    }
+
+    /// Sets the admin service store that is used to look up the management type of the circuit
+    /// being accessed. Without it, roles that are scoped by circuit management type only apply to
+    /// requests that provide the management type.
+    #[cfg(feature = "admin-service")]
+    pub fn with_admin_service_store(
+        mut self,
+        admin_service_store: Box<dyn AdminServiceStore>,
+    ) -> Self {
+        self.admin_service_store = Some(admin_service_store);
+        self
+    }
+
+    /// Sets the API token store that is used to look up the roles of identities that were resolved
+    /// from API tokens. Without it, API tokens are not granted any roles by this handler.
+    #[cfg(feature = "rest-api-tokens")]
+    pub fn with_api_token_store(mut self, api_token_store: Box<dyn ApiTokenStore>) -> Self {
+        self.api_token_store = Some(api_token_store);
+        self
+    }
+
+    /// Returns the roles of the given identity. An identity that was resolved from an API token has
+    /// the roles of the token that its owner still holds, or all of them if the owner is an admin;
+    /// roles of the token that no longer exist are ignored. Returns `None` if the identity can't
+    /// have any roles.
+    fn get_roles(&self, identity: &Identity) -> Result<Option<Vec<Role>>, InternalError> {
+        if let Some(identity) = identity.into() {
+            return self
+                .role_based_auth_store
+                .get_assigned_roles(&identity)
+                .map(|roles| Some(roles.collect()))
+                .map_err(|err| InternalError::from_source(Box::new(err)));
+        }
+
+        #[cfg(feature = "rest-api-tokens")]
+        if let (Some(store), Some(_)) = (&self.api_token_store, token_id_from_identity(identity)) {
+            return get_held_roles(identity, &**store, Some(&*self.role_based_auth_store))?
+                .iter()
+                .filter_map(|role_id| {
+                    self.role_based_auth_store
+                        .get_role(role_id)
+                        .map_err(|err| InternalError::from_source(Box::new(err)))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Some);
         }
+
+        Ok(None)
+    }
+
+    /// Adds the management type of the requested circuit to the resource, if it is not already
+    /// known. The circuit may also be a proposed circuit.
+    #[cfg(feature = "admin-service")]
+    fn with_management_type(
+        &self,
+        resource: &RequestedResource,
+    ) -> Result<RequestedResource, InternalError> {
+        let (store, circuit_id) = match (
+            &self.admin_service_store,
+            resource.circuit_id(),
+            resource.management_type(),
+        ) {
+            (Some(store), Some(circuit_id), None) => (store, circuit_id),
+            _ => return Ok(resource.clone()),
+        };
+
+        let management_type = match store
+            .get_circuit(circuit_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(circuit) => Some(circuit.circuit_management_type().to_string()),
+            None => store
+                .get_proposal(circuit_id)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?
+                .map(|proposal| proposal.circuit().circuit_management_type().to_string()),
+        };
+
+        Ok(match management_type {
+            Some(management_type) => resource.clone().with_management_type(&management_type),
+            None => resource.clone(),
+        })
     }
 }
 
@@ -47,24 +153,78 @@ impl AuthorizationHandler for RoleBasedAuthorizationHandler {
         identity: &Identity,
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError> {
-        match identity.into() {
-            Some(identity) => Ok(self
-                .role_based_auth_store
-                .get_assigned_roles(&identity)
-                .map_err(|err| InternalError::from_source(Box::new(err)))?
-                .find(|role| {
-                    role.id() == ADMIN_ROLE_ID
-                        || role.permissions().iter().any(|perm| perm == permission_id)
-                })
-                .map(|_| AuthorizationHandlerResult::Allow)
-                .unwrap_or(AuthorizationHandlerResult::Continue)),
-            None => Ok(AuthorizationHandlerResult::Continue),
+        self.has_permission_for_resource(identity, permission_id, &RequestedResource::new())
+    }
+
+    fn has_permission_for_resource(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+        resource: &RequestedResource,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        let roles = match self.get_roles(identity)? {
+            Some(roles) => roles,
+            None => return Ok(AuthorizationHandlerResult::Continue),
+        };
+
+        let roles = roles
+            .into_iter()
+            .filter(|role| {
+                role.id() == ADMIN_ROLE_ID
+                    || role.permissions().iter().any(|perm| perm == permission_id)
+            })
+            .collect::<Vec<_>>();
+
+        // Only look up the management type if a role may need it
+        #[cfg(feature = "admin-service")]
+        let resource = &if roles.iter().any(|role| {
+            role.scopes()
+                .iter()
+                .any(|scope| scope.management_type().is_some())
+        }) {
+            self.with_management_type(resource)?
+        } else {
+            resource.clone()
+        };
+
+        if roles.iter().any(|role| role.applies_to(resource)) {
+            Ok(AuthorizationHandlerResult::Allow)
+        } else {
+            Ok(AuthorizationHandlerResult::Continue)
+        }
+    }
+
+    fn has_permission_for_any_resource(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        let roles = match self.get_roles(identity)? {
+            Some(roles) => roles,
+            None => return Ok(AuthorizationHandlerResult::Continue),
+        };
+
+        // Any role with the permission grants it for some resources, regardless of its scopes
+        if roles.iter().any(|role| {
+            role.id() == ADMIN_ROLE_ID
+                || role.permissions().iter().any(|perm| perm == permission_id)
+        }) {
+            Ok(AuthorizationHandlerResult::Allow)
+        } else {
+            Ok(AuthorizationHandlerResult::Continue)
         }
     }
 
     fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
         Box::new(RoleBasedAuthorizationHandler {
             role_based_auth_store: self.role_based_auth_store.clone_box(),
+            #[cfg(feature = "admin-service")]
+            admin_service_store: self
+                .admin_service_store
+                .as_ref()
+                .map(|store| store.clone_boxed()),
+            #[cfg(feature = "rest-api-tokens")]
+            api_token_store: self.api_token_store.clone(),
         })
     }
 }
@@ -73,9 +233,13 @@ impl AuthorizationHandler for RoleBasedAuthorizationHandler {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "admin-service")]
+    use crate::admin::store::{
+        diesel::DieselAdminServiceStore, CircuitBuilder, CircuitNodeBuilder, ServiceBuilder,
+    };
     use crate::rest_api::auth::authorization::rbac::store::{
         AssignmentBuilder, DieselRoleBasedAuthorizationStore, Identity as StoreIdentity,
-        RoleBuilder,
+        RoleBuilder, RoleScopeBuilder,
     };
 
     use crate::migrations::run_sqlite_migrations;
@@ -154,6 +318,301 @@ mod tests {
         assert!(matches!(result, AuthorizationHandlerResult::Continue));
     }
 
+    /// This test checks that a role that is limited to resource scopes only allows requests for
+    /// matching resources.
+    ///
+    /// 1. Assign a role scoped to circuits matching `abcde-*` and a role scoped to a single
+    ///    service
+    /// 2. Verify that the permissions are not allowed without a resource, but are allowed for some
+    ///    resources
+    /// 3. Verify that the circuit permission is only allowed for matching circuits
+    /// 4. Verify that the service permission is only allowed for the matching service
+    #[test]
+    fn allow_scoped_role_for_matching_resource() {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        let role = RoleBuilder::new()
+            .with_id("circuit-reader".into())
+            .with_display_name("Circuit Reader".into())
+            .with_permissions(vec!["circuit.read".to_string()])
+            .with_scopes(vec![RoleScopeBuilder::new()
+                .with_circuit_id("abcde-*".into())
+                .build()
+                .expect("Unable to build scope")])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("service-writer".into())
+            .with_display_name("Service Writer".into())
+            .with_permissions(vec!["scabbard.write".to_string()])
+            .with_scopes(vec![RoleScopeBuilder::new()
+                .with_circuit_id("abcde-01234".into())
+                .with_service_id("a000".into())
+                .build()
+                .expect("Unable to build scope")])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(StoreIdentity::User("some-user-id".into()))
+            .with_roles(vec![
+                "circuit-reader".to_string(),
+                "service-writer".to_string(),
+            ])
+            .build()
+            .expect("Unable to build assignment");
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store);
+        let identity = Identity::User("some-user-id".into());
+
+        let result = handler
+            .has_permission(&identity, "circuit.read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission_for_any_resource(&identity, "circuit.read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_permission_for_any_resource(&identity, "circuit.write")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission_for_resource(
+                &identity,
+                "circuit.read",
+                &RequestedResource::new().with_circuit_id("abcde-01234"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_permission_for_resource(
+                &identity,
+                "circuit.read",
+                &RequestedResource::new().with_circuit_id("fghij-01234"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission_for_resource(
+                &identity,
+                "scabbard.write",
+                &RequestedResource::new()
+                    .with_circuit_id("abcde-01234")
+                    .with_service_id("a000"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_permission_for_resource(
+                &identity,
+                "scabbard.write",
+                &RequestedResource::new()
+                    .with_circuit_id("abcde-01234")
+                    .with_service_id("a001"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission_for_resource(
+                &identity,
+                "scabbard.write",
+                &RequestedResource::new().with_circuit_id("abcde-01234"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// This test checks that a role that is scoped by circuit management type applies to the
+    /// circuits with that management type, which are looked up in the admin service store.
+    #[cfg(feature = "admin-service")]
+    #[test]
+    fn allow_role_scoped_by_management_type() {
+        let pool = create_connection_pool_and_migrate();
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
+        let admin_service_store = DieselAdminServiceStore::new(pool);
+
+        let node = CircuitNodeBuilder::default()
+            .with_node_id("acme-node-000")
+            .with_endpoints(&["tcps://splinterd-node-acme:8044".into()])
+            .build()
+            .expect("Unable to build node");
+        let circuit = CircuitBuilder::default()
+            .with_circuit_id("abcde-01234")
+            .with_roster(&[ServiceBuilder::default()
+                .with_service_id("a000")
+                .with_service_type("scabbard")
+                .with_node_id("acme-node-000")
+                .build()
+                .expect("Unable to build service")])
+            .with_members(&[node.clone()])
+            .with_circuit_management_type("gameroom")
+            .build()
+            .expect("Unable to build circuit");
+        admin_service_store
+            .add_circuit(circuit, vec![node])
+            .expect("Unable to add circuit");
+
+        let role = RoleBuilder::new()
+            .with_id("gameroom-reader".into())
+            .with_display_name("Gameroom Reader".into())
+            .with_permissions(vec!["circuit.read".to_string()])
+            .with_scopes(vec![RoleScopeBuilder::new()
+                .with_management_type("gameroom".into())
+                .build()
+                .expect("Unable to build scope")])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(StoreIdentity::Key("abc123".into()))
+            .with_roles(vec!["gameroom-reader".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let handler = RoleBasedAuthorizationHandler::new(Box::new(role_based_auth_store))
+            .with_admin_service_store(Box::new(admin_service_store));
+        let identity = Identity::Key("abc123".into());
+
+        let result = handler
+            .has_permission_for_resource(
+                &identity,
+                "circuit.read",
+                &RequestedResource::new().with_circuit_id("abcde-01234"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_permission_for_resource(
+                &identity,
+                "circuit.read",
+                &RequestedResource::new().with_circuit_id("fghij-01234"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// This test checks that an identity resolved from an API token is granted the roles of the
+    /// token that its owner holds, and that a revoked token is not granted any roles.
+    ///
+    /// 1. Add a role, assign it to a user and add a token owned by the user with the role
+    /// 2. Verify the token is granted the role's permissions, but no others
+    /// 3. Remove the user's assignment and verify the token is no longer granted the role
+    /// 4. Assign the admin role to the user and verify the token is granted the role again
+    /// 5. Revoke the token and verify it is not granted any roles
+    #[cfg(feature = "rest-api-tokens")]
+    #[test]
+    fn allow_api_token_identity_with_token_roles() {
+        use crate::rest_api::auth::token::{
+            hash_token, store::diesel::DieselApiTokenStore, store::ApiTokenStore, ApiTokenBuilder,
+        };
+
+        let pool = create_connection_pool_and_migrate();
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
+        let api_token_store = DieselApiTokenStore::new(pool);
+
+        let role = RoleBuilder::new()
+            .with_id("circuit-reader".into())
+            .with_display_name("Circuit Reader".into())
+            .with_permissions(vec!["circuit.read".to_string()])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+        let owner = StoreIdentity::User("some-user-id".into());
+        role_based_auth_store
+            .add_assignment(
+                AssignmentBuilder::new()
+                    .with_identity(owner.clone())
+                    .with_roles(vec!["circuit-reader".into()])
+                    .build()
+                    .expect("Unable to build assignment"),
+            )
+            .expect("Unable to add assignment");
+
+        let token = ApiTokenBuilder::new()
+            .with_name("ci".into())
+            .with_owner(Identity::User("some-user-id".into()))
+            .with_token_hash(hash_token("secret"))
+            .with_roles(vec!["circuit-reader".into(), "missing-role".into()])
+            .build()
+            .expect("Unable to build token");
+        api_token_store
+            .add_token(token.clone())
+            .expect("Unable to add token");
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store.clone_box())
+            .with_api_token_store(Box::new(api_token_store.clone()));
+
+        let result = handler
+            .has_permission(&token.identity(), "circuit.read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_permission(&token.identity(), "circuit.write")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        role_based_auth_store
+            .remove_assignment(&owner)
+            .expect("Unable to remove assignment");
+
+        let result = handler
+            .has_permission(&token.identity(), "circuit.read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        role_based_auth_store
+            .add_assignment(
+                AssignmentBuilder::new()
+                    .with_identity(owner)
+                    .with_roles(vec![ADMIN_ROLE_ID.into()])
+                    .build()
+                    .expect("Unable to build assignment"),
+            )
+            .expect("Unable to add assignment");
+
+        let result = handler
+            .has_permission(&token.identity(), "circuit.read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        api_token_store
+            .remove_token(token.id())
+            .expect("Unable to remove token");
+
+        let result = handler
+            .has_permission(&token.identity(), "circuit.read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
     /// This test checks that an identity with an assigned role will return Allow when queried.
     fn test_allow_identity_with_assignment(identity: Identity, store_identity: StoreIdentity) {
         let role_based_auth_store = create_role_based_authorization_store();
@@ -32,7 +32,7 @@ use crate::rest_api::{
         },
         store::{Assignment, Identity, RoleBasedAuthorizationStore},
     },
-    paging::get_response_paging_info,
+    paging::{get_response_cursor_paging_info, get_response_paging_info, Cursor},
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
@@ -96,35 +96,81 @@ fn list_assignments(
             }
         };
 
+    let cursor = match paging_query
+        .cursor
+        .as_deref()
+        .map(Cursor::decode)
+        .transpose()
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Invalid cursor value passed: {}",
+                        err
+                    )))
+                    .into_future(),
+            )
+        }
+    };
+
     let link = format!("{}?", req.uri().path());
 
     Box::new(
         web::block(move || {
-            let assignments = role_based_auth_store
-                .list_assignments()
-                .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+            let limit = paging_query.limit;
+
+            // An explicit offset without a cursor is served as before, so that existing offset
+            // links keep working; otherwise the page is fetched from the store starting after the
+            // cursor
+            if let (Some(offset), None) = (paging_query.offset, cursor.as_ref()) {
+                let assignments = role_based_auth_store
+                    .list_assignments()
+                    .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
 
-            let total = assignments.len();
-            let assignments = assignments
-                .skip(paging_query.offset)
-                .take(paging_query.limit)
+                let total = assignments.len();
+                let assignments = assignments.skip(offset).take(limit).collect::<Vec<_>>();
+                let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+                return Ok((assignments, paging));
+            }
+
+            let total = role_based_auth_store
+                .count_assignments()
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+            // One more assignment than the limit is fetched to find out whether there is a next
+            // page
+            let assignments = role_based_auth_store
+                .list_assignments_page(cursor.as_ref().map(Cursor::key), limit.saturating_add(1))
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?
                 .collect::<Vec<_>>();
+            let (assignments, has_more) = Cursor::split_page(assignments, limit);
+
+            let next_cursor = Cursor::following(
+                cursor.as_ref(),
+                limit,
+                has_more,
+                assignments
+                    .last()
+                    .map(|assignment| assignment.identity().as_str()),
+            );
+            let paging = get_response_cursor_paging_info(
+                limit,
+                cursor.as_ref(),
+                next_cursor.as_ref(),
+                &link,
+                total,
+            );
 
-            Ok((assignments, link, paging_query, total))
+            Ok((assignments, paging))
         })
         .then(
             |res: Result<_, BlockingError<SendableRoleBasedAuthorizationStoreError>>| match res {
-                Ok((assignments, link, paging_query, total)) => {
-                    Ok(HttpResponse::Ok().json(ListAssignmentsResponse {
-                        data: assignments.iter().map(AssignmentResponse::from).collect(),
-                        paging: get_response_paging_info(
-                            Some(paging_query.limit),
-                            Some(paging_query.offset),
-                            &link,
-                            total,
-                        ),
-                    }))
-                }
+                Ok((assignments, paging)) => Ok(HttpResponse::Ok().json(ListAssignmentsResponse {
+                    data: assignments.iter().map(AssignmentResponse::from).collect(),
+                    paging,
+                })),
                 Err(err) => {
                     error!("Unable to list assignments: {}", err);
                     Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
@@ -587,17 +633,15 @@ mod tests {
             );
         }
 
+        // the next page is referenced by a cursor after the last item on this page
+        let mut expected_paging =
+            create_test_paging_response(0, 100, 100, 0, 100, 101, "/authorization/assignments?");
+        expected_paging.next = format!(
+            "/authorization/assignments?limit=100&cursor={}",
+            Cursor::new(100, "id-099").encode()
+        );
         assert_eq!(
-            &to_value(create_test_paging_response(
-                0,
-                100,
-                100,
-                0,
-                100,
-                101,
-                "/authorization/assignments?"
-            ))
-            .expect("failed to convert expected paging"),
+            &to_value(expected_paging).expect("failed to convert expected paging"),
             body.get("paging").expect("no paging field in response"),
         );
 
@@ -16,7 +16,7 @@
 //!
 //! * `GET /authorization/roles` for listing roles
 
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 use crate::actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use crate::error::InvalidStateError;
@@ -31,9 +31,9 @@ use crate::rest_api::{
             },
             RBAC_READ_PERMISSION, RBAC_WRITE_PERMISSION,
         },
-        store::{Role, RoleBasedAuthorizationStore},
+        store::{Role, RoleBasedAuthorizationStore, RoleScope},
     },
-    paging::get_response_paging_info,
+    paging::{get_response_cursor_paging_info, get_response_paging_info, Cursor},
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
@@ -98,35 +98,74 @@ fn list_roles(
             }
         };
 
+    let cursor = match paging_query
+        .cursor
+        .as_deref()
+        .map(Cursor::decode)
+        .transpose()
+    {
+        Ok(cursor) => cursor,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Invalid cursor value passed: {}",
+                        err
+                    )))
+                    .into_future(),
+            )
+        }
+    };
+
     let link = format!("{}?", req.uri().path());
 
     Box::new(
         web::block(move || {
-            let roles = role_based_authorization_store
-                .list_roles()
-                .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+            let limit = paging_query.limit;
 
-            let total = roles.len();
-            let roles = roles
-                .skip(paging_query.offset)
-                .take(paging_query.limit)
+            // An explicit offset without a cursor is served as before, so that existing offset
+            // links keep working; otherwise the page is fetched from the store starting after the
+            // cursor
+            if let (Some(offset), None) = (paging_query.offset, cursor.as_ref()) {
+                let roles = role_based_authorization_store
+                    .list_roles()
+                    .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+
+                let total = roles.len();
+                let roles = roles.skip(offset).take(limit).collect::<Vec<_>>();
+                let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+                return Ok((roles, paging));
+            }
+
+            let total = role_based_authorization_store
+                .count_roles()
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+            // One more role than the limit is fetched to find out whether there is a next page
+            let roles = role_based_authorization_store
+                .list_roles_page(cursor.as_ref().map(Cursor::key), limit.saturating_add(1))
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?
                 .collect::<Vec<_>>();
+            let (roles, has_more) = Cursor::split_page(roles, limit);
+
+            let next_cursor =
+                Cursor::following(cursor.as_ref(), limit, has_more, roles.last().map(Role::id));
+            let paging = get_response_cursor_paging_info(
+                limit,
+                cursor.as_ref(),
+                next_cursor.as_ref(),
+                &link,
+                total,
+            );
 
-            Ok((roles, link, paging_query, total))
+            Ok((roles, paging))
         })
         .then(
             |res: Result<_, BlockingError<SendableRoleBasedAuthorizationStoreError>>| match res {
-                Ok((roles, link, paging_query, total)) => {
-                    Ok(HttpResponse::Ok().json(ListRoleResponse {
-                        data: roles.iter().map(RoleResponse::from).collect(),
-                        paging: get_response_paging_info(
-                            Some(paging_query.limit),
-                            Some(paging_query.offset),
-                            &link,
-                            total,
-                        ),
-                    }))
-                }
+                Ok((roles, paging)) => Ok(HttpResponse::Ok().json(ListRoleResponse {
+                    data: roles.iter().map(RoleResponse::from).collect(),
+                    paging,
+                })),
                 Err(err) => {
                     error!("Unable to list roles: {}", err);
                     Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
@@ -295,6 +334,7 @@ fn update_role(
                 let RoleUpdatePayload {
                     display_name,
                     permissions,
+                    scopes,
                 } = role_update;
                 let mut update_builder = role.into_update_builder();
 
@@ -304,6 +344,15 @@ fn update_role(
                 if let Some(permissions) = permissions {
                     update_builder = update_builder.with_permissions(permissions);
                 }
+                if let Some(scopes) = scopes {
+                    update_builder = update_builder.with_scopes(
+                        scopes
+                            .into_iter()
+                            .map(RoleScope::try_from)
+                            .collect::<Result<_, _>>()
+                            .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?,
+                    );
+                }
 
                 let updated_role = update_builder
                     .build()
@@ -427,6 +476,7 @@ mod tests {
                 role_id: "test-role-1",
                 display_name: "Test Role 1",
                 permissions: &["a".to_string(), "b".to_string(), "c".to_string()],
+                scopes: vec![],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -436,6 +486,7 @@ mod tests {
                 role_id: "test-role-2",
                 display_name: "Test Role 2",
                 permissions: &["x".to_string(), "y".to_string(), "z".to_string()],
+                scopes: vec![],
             })
             .expect("Failed to convert to value"),
             json_roles.get(1).expect("no first item")
@@ -506,23 +557,22 @@ mod tests {
                     role_id: &format!("test-role-{:0>3}", i),
                     display_name: &format!("Test Role {}", i),
                     permissions: &[format!("perm-{}", i)],
+                    scopes: vec![],
                 })
                 .expect("Failed to convert to value"),
                 json_roles.get(i).expect("no first item")
             );
         }
 
+        // the next page is referenced by a cursor after the last item on this page
+        let mut expected_paging =
+            create_test_paging_response(0, 100, 100, 0, 100, 101, "/authorization/roles?");
+        expected_paging.next = format!(
+            "/authorization/roles?limit=100&cursor={}",
+            Cursor::new(100, "test-role-099").encode()
+        );
         assert_eq!(
-            &to_value(create_test_paging_response(
-                0,
-                100,
-                100,
-                0,
-                100,
-                101,
-                "/authorization/roles?"
-            ))
-            .expect("failed to convert expected paging"),
+            &to_value(expected_paging).expect("failed to convert expected paging"),
             body.get("paging").expect("no paging field in response"),
         );
 
@@ -554,6 +604,7 @@ mod tests {
                 role_id: "test-role-100",
                 display_name: "Test Role 100",
                 permissions: &["perm-100".to_string()],
+                scopes: vec![],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -612,6 +663,7 @@ mod tests {
                 role_id: "new_test_role",
                 display_name: "New Test Display Name",
                 permissions: &["my-permission-1".to_string(), "my-permission-2".to_string()],
+                scopes: vec![],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -623,6 +675,84 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    /// Tests a POST /authorization/roles request for a role with resource scopes returns OK.
+    /// Verify that the scopes are returned when getting the role, and that a scope without any
+    /// fields is rejected.
+    #[test]
+    fn test_post_role_with_scopes_ok() {
+        let role_based_auth_store = MemRoleBasedAuthorizationStore::default();
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_roles_resource(Box::new(role_based_auth_store.clone())),
+            make_role_resource(Box::new(role_based_auth_store)),
+        ]);
+
+        let url = Url::parse(&format!("http://{}/authorization/roles", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .post(url.clone())
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&json!({
+                "role_id": "scoped_role",
+                "display_name": "Scoped Role",
+                "permissions": ["circuit.read"],
+                "scopes": [
+                    {"circuit_id": "abcde-*"},
+                    {"management_type": "gameroom", "service_id": "gr00"},
+                ],
+            }))
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = Client::new()
+            .get(
+                Url::parse(&format!(
+                    "http://{}/authorization/roles/scoped_role",
+                    bind_url
+                ))
+                .expect("Failed to parse URL"),
+            )
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+        assert_eq!(
+            json!({
+                "data": {
+                    "role_id": "scoped_role",
+                    "display_name": "Scoped Role",
+                    "permissions": ["circuit.read"],
+                    "scopes": [
+                        {"circuit_id": "abcde-*"},
+                        {"management_type": "gameroom", "service_id": "gr00"},
+                    ],
+                }
+            }),
+            body
+        );
+
+        let resp = Client::new()
+            .post(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&json!({
+                "role_id": "invalid_role",
+                "display_name": "Invalid Role",
+                "permissions": ["circuit.read"],
+                "scopes": [{}],
+            }))
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     /// Tests a POST /authorization/roles request with a valid returns OK.
     /// Verify that the role has been added by querying the list of roles.
     #[test]
@@ -17,20 +17,18 @@
 pub mod assignments;
 pub mod roles;
 
-use crate::rest_api::paging::{DEFAULT_LIMIT, DEFAULT_OFFSET};
+use crate::rest_api::paging::DEFAULT_LIMIT;
 
 #[derive(Deserialize)]
 pub struct PagingQuery {
     #[serde(default = "default_limit")]
     pub limit: usize,
-    #[serde(default = "default_offset")]
-    pub offset: usize,
+    #[serde(default)]
+    pub offset: Option<usize>,
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 fn default_limit() -> usize {
     DEFAULT_LIMIT
 }
-
-fn default_offset() -> usize {
-    DEFAULT_OFFSET
-}
@@ -18,7 +18,7 @@ use std::convert::TryFrom;
 
 use crate::error::InvalidStateError;
 use crate::rest_api::{
-    auth::authorization::rbac::store::{Role, RoleBuilder},
+    auth::authorization::rbac::store::{Role, RoleBuilder, RoleScope, RoleScopeBuilder},
     paging::Paging,
 };
 
@@ -33,6 +33,19 @@ pub struct RoleResponse<'a> {
     pub role_id: &'a str,
     pub display_name: &'a str,
     pub permissions: &'a [String],
+    // Omitted for roles that apply to all resources, so the response is unchanged for them
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<RoleScopeResponse<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct RoleScopeResponse<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub management_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<&'a str>,
 }
 
 #[derive(Deserialize)]
@@ -40,12 +53,22 @@ pub struct RolePayload {
     pub role_id: String,
     pub display_name: String,
     pub permissions: Vec<String>,
+    #[serde(default)]
+    pub scopes: Vec<RoleScopePayload>,
 }
 
 #[derive(Deserialize)]
 pub struct RoleUpdatePayload {
     pub display_name: Option<String>,
     pub permissions: Option<Vec<String>>,
+    pub scopes: Option<Vec<RoleScopePayload>>,
+}
+
+#[derive(Deserialize)]
+pub struct RoleScopePayload {
+    pub circuit_id: Option<String>,
+    pub management_type: Option<String>,
+    pub service_id: Option<String>,
 }
 
 impl<'a> From<&'a Role> for RoleResponse<'a> {
@@ -54,6 +77,17 @@ impl<'a> From<&'a Role> for RoleResponse<'a> {
             role_id: role.id(),
             display_name: role.display_name(),
             permissions: role.permissions(),
+            scopes: role.scopes().iter().map(RoleScopeResponse::from).collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a RoleScope> for RoleScopeResponse<'a> {
+    fn from(scope: &'a RoleScope) -> Self {
+        Self {
+            circuit_id: scope.circuit_id(),
+            management_type: scope.management_type(),
+            service_id: scope.service_id(),
         }
     }
 }
@@ -66,6 +100,31 @@ impl TryFrom<RolePayload> for Role {
             .with_id(payload.role_id)
             .with_display_name(payload.display_name)
             .with_permissions(payload.permissions)
+            .with_scopes(
+                payload
+                    .scopes
+                    .into_iter()
+                    .map(RoleScope::try_from)
+                    .collect::<Result<_, _>>()?,
+            )
             .build()
     }
 }
+
+impl TryFrom<RoleScopePayload> for RoleScope {
+    type Error = InvalidStateError;
+
+    fn try_from(payload: RoleScopePayload) -> Result<Self, Self::Error> {
+        let mut builder = RoleScopeBuilder::new();
+        if let Some(circuit_id) = payload.circuit_id {
+            builder = builder.with_circuit_id(circuit_id);
+        }
+        if let Some(management_type) = payload.management_type {
+            builder = builder.with_management_type(management_type);
+        }
+        if let Some(service_id) = payload.service_id {
+            builder = builder.with_service_id(service_id);
+        }
+        builder.build()
+    }
+}
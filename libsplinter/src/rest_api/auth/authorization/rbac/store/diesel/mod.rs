@@ -28,11 +28,13 @@ use diesel::r2d2::{ConnectionManager, Pool};
 
 use super::{
     Assignment, Identity, Role, RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError,
-    RoleBuilder, ADMIN_ROLE_ID,
+    RoleBuilder, RoleScopeBuilder, ADMIN_ROLE_ID,
 };
 
 use operations::add_assignment::RoleBasedAuthorizationStoreAddAssignment as _;
 use operations::add_role::RoleBasedAuthorizationStoreAddRole as _;
+use operations::count_assignments::RoleBasedAuthorizationStoreCountAssignments as _;
+use operations::count_roles::RoleBasedAuthorizationStoreCountRoles as _;
 use operations::get_assigned_roles::RoleBasedAuthorizationStoreGetAssignedRoles as _;
 use operations::get_assignment::RoleBasedAuthorizationStoreGetAssignment as _;
 use operations::get_role::RoleBasedAuthorizationStoreGetRole as _;
@@ -89,7 +91,25 @@ impl RoleBasedAuthorizationStore
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
         self.connection_pool.execute_read(|connection| {
-            RoleBasedAuthorizationStoreOperations::new(connection).list_roles()
+            RoleBasedAuthorizationStoreOperations::new(connection).list_roles(None, None)
+        })
+    }
+
+    /// Lists a page of roles, in ascending order of role ID.
+    fn list_roles_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).list_roles(after, Some(limit))
+        })
+    }
+
+    /// Returns the number of roles.
+    fn count_roles(&self) -> Result<usize, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).count_roles()
         })
     }
 
@@ -166,7 +186,27 @@ impl RoleBasedAuthorizationStore
     ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
     {
         self.connection_pool.execute_read(|connection| {
-            RoleBasedAuthorizationStoreOperations::new(connection).list_assignments()
+            RoleBasedAuthorizationStoreOperations::new(connection).list_assignments(None, None)
+        })
+    }
+
+    /// Lists a page of assignments, in ascending order of identity.
+    fn list_assignments_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
+    {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection)
+                .list_assignments(after, Some(limit))
+        })
+    }
+
+    /// Returns the number of assignments.
+    fn count_assignments(&self) -> Result<usize, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).count_assignments()
         })
     }
 
@@ -235,7 +275,25 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
         self.connection_pool.execute_read(|connection| {
-            RoleBasedAuthorizationStoreOperations::new(connection).list_roles()
+            RoleBasedAuthorizationStoreOperations::new(connection).list_roles(None, None)
+        })
+    }
+
+    /// Lists a page of roles, in ascending order of role ID.
+    fn list_roles_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).list_roles(after, Some(limit))
+        })
+    }
+
+    /// Returns the number of roles.
+    fn count_roles(&self) -> Result<usize, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).count_roles()
         })
     }
 
@@ -312,7 +370,27 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
     ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
     {
         self.connection_pool.execute_read(|connection| {
-            RoleBasedAuthorizationStoreOperations::new(connection).list_assignments()
+            RoleBasedAuthorizationStoreOperations::new(connection).list_assignments(None, None)
+        })
+    }
+
+    /// Lists a page of assignments, in ascending order of identity.
+    fn list_assignments_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
+    {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection)
+                .list_assignments(after, Some(limit))
+        })
+    }
+
+    /// Returns the number of assignments.
+    fn count_assignments(&self) -> Result<usize, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).count_assignments()
         })
     }
 
@@ -367,9 +445,15 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
     }
 }
 
-impl From<Role> for (models::RoleModel, Vec<models::RolePermissionModel>) {
+impl From<Role>
+    for (
+        models::RoleModel,
+        Vec<models::RolePermissionModel>,
+        Vec<models::RoleScopeModel>,
+    )
+{
     fn from(role: Role) -> Self {
-        let (id, display_name, permissions) = role.into_parts();
+        let (id, display_name, permissions, scopes) = role.into_parts();
 
         let perm_models = permissions
             .into_iter()
@@ -378,16 +462,62 @@ impl From<Role> for (models::RoleModel, Vec<models::RolePermissionModel>) {
                 permission,
             })
             .collect::<Vec<_>>();
-        (models::RoleModel { id, display_name }, perm_models)
+        let scope_models = scopes
+            .into_iter()
+            .enumerate()
+            .map(|(position, scope)| {
+                let (circuit_id, management_type, service_id) = scope.into_parts();
+                models::RoleScopeModel {
+                    role_id: id.clone(),
+                    position: position as i32,
+                    circuit_id,
+                    management_type,
+                    service_id,
+                }
+            })
+            .collect::<Vec<_>>();
+        (
+            models::RoleModel { id, display_name },
+            perm_models,
+            scope_models,
+        )
     }
 }
 
-impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
+impl
+    TryFrom<(
+        models::RoleModel,
+        Vec<models::RolePermissionModel>,
+        Vec<models::RoleScopeModel>,
+    )> for Role
+{
     type Error = InvalidStateError;
 
     fn try_from(
-        (role_model, perm_models): (models::RoleModel, Vec<models::RolePermissionModel>),
+        (role_model, perm_models, mut scope_models): (
+            models::RoleModel,
+            Vec<models::RolePermissionModel>,
+            Vec<models::RoleScopeModel>,
+        ),
     ) -> Result<Self, Self::Error> {
+        scope_models.sort_by_key(|scope| scope.position);
+        let scopes = scope_models
+            .into_iter()
+            .map(|scope| {
+                let mut builder = RoleScopeBuilder::new();
+                if let Some(circuit_id) = scope.circuit_id {
+                    builder = builder.with_circuit_id(circuit_id);
+                }
+                if let Some(management_type) = scope.management_type {
+                    builder = builder.with_management_type(management_type);
+                }
+                if let Some(service_id) = scope.service_id {
+                    builder = builder.with_service_id(service_id);
+                }
+                builder.build()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
         RoleBuilder::new()
             .with_id(role_model.id)
             .with_display_name(role_model.display_name)
@@ -397,6 +527,7 @@ impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
                     .map(|perm| perm.permission)
                     .collect(),
             )
+            .with_scopes(scopes)
             .build()
     }
 }
@@ -497,7 +628,9 @@ impl From<diesel::r2d2::PoolError> for RoleBasedAuthorizationStoreError {
 mod tests {
     use super::*;
 
-    use crate::rest_api::auth::authorization::rbac::store::{AssignmentBuilder, RoleBuilder};
+    use crate::rest_api::auth::authorization::rbac::store::{
+        AssignmentBuilder, RoleBuilder, RoleScopeBuilder,
+    };
 
     use crate::store::sqlite::create_sqlite_connection_pool;
 
@@ -612,6 +745,52 @@ mod tests {
         );
     }
 
+    /// This tests verifies the following:
+    /// 1. Adds two roles via the store API
+    /// 2. Verifies the roles are counted along with the `admin` role
+    /// 3. Verifies the first page of two roles contains the `admin` role and the first new role
+    /// 4. Verifies the page after the first new role contains only the second new role
+    #[test]
+    fn sqlite_list_roles_page() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        for id in &["test-role-2", "test-role-1"] {
+            let role = RoleBuilder::new()
+                .with_id(id.to_string())
+                .with_display_name(id.to_string())
+                .with_permissions(vec!["a".to_string()])
+                .build()
+                .expect("Unable to build role");
+
+            role_based_auth_store
+                .add_role(role)
+                .expect("Unable to add role");
+        }
+
+        assert_eq!(
+            3,
+            role_based_auth_store
+                .count_roles()
+                .expect("Unable to count roles")
+        );
+
+        let role_ids = role_based_auth_store
+            .list_roles_page(None, 2)
+            .expect("Unable to list roles")
+            .map(|role| role.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["admin", "test-role-1"], role_ids);
+
+        let role_ids = role_based_auth_store
+            .list_roles_page(Some("test-role-1"), 2)
+            .expect("Unable to list roles")
+            .map(|role| role.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["test-role-2"], role_ids);
+    }
+
     /// This tests verifies the following:
     /// 1. Adds a role and verifies that it has been inserted
     /// 2. Update the role and verifies that it has been changed, via the store API
@@ -749,6 +928,103 @@ mod tests {
             .expect("Unable to remove role");
     }
 
+    /// This tests verifies the following:
+    /// 1. Adds a role with two resource scopes and verifies the scopes are returned in order
+    /// 2. Updates the role with a single scope and verifies the scopes were replaced
+    /// 3. Updates the role without scopes and verifies the role applies to all resources
+    /// 4. Removes the role and verifies the scopes have been removed
+    #[test]
+    fn sqlite_role_scopes() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
+
+        let circuit_scope = RoleScopeBuilder::new()
+            .with_circuit_id("abcde-*".into())
+            .build()
+            .expect("Unable to build scope");
+        let service_scope = RoleScopeBuilder::new()
+            .with_management_type("gameroom".into())
+            .with_service_id("gr00".into())
+            .build()
+            .expect("Unable to build scope");
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .with_scopes(vec![circuit_scope.clone(), service_scope.clone()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let stored_role = role_based_auth_store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the added role");
+        assert_eq!(
+            &[circuit_scope.clone(), service_scope.clone()],
+            stored_role.scopes()
+        );
+
+        let role = stored_role
+            .into_update_builder()
+            .with_scopes(vec![service_scope.clone()])
+            .build()
+            .expect("Unable to build updated role");
+        role_based_auth_store
+            .update_role(role)
+            .expect("Unable to update role");
+
+        let stored_role = role_based_auth_store
+            .list_roles()
+            .expect("Unable to list roles")
+            .find(|role| role.id() == "test-role")
+            .expect("Did not find the updated role");
+        assert_eq!(&[service_scope], stored_role.scopes());
+
+        let role = stored_role
+            .into_update_builder()
+            .with_scopes(vec![])
+            .build()
+            .expect("Unable to build updated role");
+        role_based_auth_store
+            .update_role(role)
+            .expect("Unable to update role");
+
+        let stored_role = role_based_auth_store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the updated role");
+        assert!(stored_role.scopes().is_empty());
+
+        let role = stored_role
+            .into_update_builder()
+            .with_scopes(vec![circuit_scope])
+            .build()
+            .expect("Unable to build updated role");
+        role_based_auth_store
+            .update_role(role)
+            .expect("Unable to update role");
+
+        role_based_auth_store
+            .remove_role("test-role")
+            .expect("Unable to remove role");
+
+        // verify that the scopes have been removed (in a block, so the connection is dropped)
+        {
+            let connection = pool.get().expect("Unable to get connection");
+            let scopes = schema::rbac_role_scopes::table
+                .filter(schema::rbac_role_scopes::role_id.eq("test-role"))
+                .load::<models::RoleScopeModel>(&*connection)
+                .expect("Unable to load scopes");
+            assert!(scopes.is_empty());
+        }
+    }
+
     /// This test verifies the following:
     /// 1. Adds a role.
     /// 2. Adds an assignment for that role
@@ -927,6 +1203,72 @@ mod tests {
         assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
     }
 
+    /// This test verifies the following:
+    /// 1. Adds a role.
+    /// 2. Add three assignments for that role
+    /// 3. Verifies the assignments are counted
+    /// 4. Verifies the assignments are listed in pages, in order of identity
+    #[test]
+    fn sqlite_list_assignments_page() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        for identity in vec![
+            Identity::User("user-c".into()),
+            Identity::Key("key-a".into()),
+            Identity::User("user-b".into()),
+        ] {
+            let assignment = AssignmentBuilder::new()
+                .with_identity(identity)
+                .with_roles(vec!["test-role".to_string()])
+                .build()
+                .expect("Unable to build assignment");
+
+            role_based_auth_store
+                .add_assignment(assignment)
+                .expect("Unable to add assignment");
+        }
+
+        assert_eq!(
+            3,
+            role_based_auth_store
+                .count_assignments()
+                .expect("Unable to count assignments")
+        );
+
+        let identities = role_based_auth_store
+            .list_assignments_page(None, 2)
+            .expect("Unable to list assignments")
+            .map(|assignment| assignment.identity().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Identity::Key("key-a".into()),
+                Identity::User("user-b".into())
+            ],
+            identities
+        );
+
+        let identities = role_based_auth_store
+            .list_assignments_page(Some("user-b"), 2)
+            .expect("Unable to list assignments")
+            .map(|assignment| assignment.identity().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Identity::User("user-c".into())], identities);
+    }
+
     /// This test verifies the following:
     /// 1. Add two roles
     /// 2. Add an assignment to one of the roles
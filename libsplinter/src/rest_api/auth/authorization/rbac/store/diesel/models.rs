@@ -35,7 +35,9 @@ use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
 use diesel::sqlite::Sqlite;
 
-use super::schema::{rbac_assignments, rbac_identities, rbac_role_permissions, rbac_roles};
+use super::schema::{
+    rbac_assignments, rbac_identities, rbac_role_permissions, rbac_role_scopes, rbac_roles,
+};
 
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
 #[table_name = "rbac_roles"]
@@ -54,6 +56,18 @@ pub(super) struct RolePermissionModel {
     pub permission: String,
 }
 
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "rbac_role_scopes"]
+#[belongs_to(RoleModel, foreign_key = "role_id")]
+#[primary_key(role_id, position)]
+pub(super) struct RoleScopeModel {
+    pub role_id: String,
+    pub position: i32,
+    pub circuit_id: Option<String>,
+    pub management_type: Option<String>,
+    pub service_id: Option<String>,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub(super) enum IdentityModelType {
     Key,
@@ -16,8 +16,8 @@ use diesel::{dsl::insert_into, prelude::*};
 
 use crate::rest_api::auth::authorization::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::{rbac_role_permissions, rbac_roles},
+        models::{RoleModel, RolePermissionModel, RoleScopeModel},
+        schema::{rbac_role_permissions, rbac_role_scopes, rbac_roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
@@ -33,7 +33,11 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, scopes): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleScopeModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_roles::table)
@@ -44,6 +48,12 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            if !scopes.is_empty() {
+                insert_into(rbac_role_scopes::table)
+                    .values(scopes)
+                    .execute(self.conn)?;
+            }
+
             Ok(())
         })
     }
@@ -54,7 +64,11 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
 {
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, scopes): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleScopeModel>,
+        ) = role.into();
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_roles::table)
                 .values(role)
@@ -64,6 +78,12 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            if !scopes.is_empty() {
+                insert_into(rbac_role_scopes::table)
+                    .values(scopes)
+                    .execute(self.conn)?;
+            }
+
             Ok(())
         })
     }
@@ -0,0 +1,39 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::count_star, prelude::*};
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::schema::rbac_roles, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreCountRoles {
+    fn count_roles(&self) -> Result<usize, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreCountRoles for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn count_roles(&self) -> Result<usize, RoleBasedAuthorizationStoreError> {
+        let count = rbac_roles::table
+            .select(count_star())
+            .first::<i64>(self.conn)?;
+
+        Ok(count as usize)
+    }
+}
@@ -20,9 +20,9 @@ use crate::rest_api::auth::authorization::rbac::store::{
     diesel::{
         models::{
             AssignmentModel, IdentityModel, IdentityModelType, IdentityModelTypeMapping, RoleModel,
-            RolePermissionModel,
+            RolePermissionModel, RoleScopeModel,
         },
-        schema::{rbac_identities, rbac_roles},
+        schema::{rbac_identities, rbac_role_scopes, rbac_roles},
     },
     Identity, Role, RoleBasedAuthorizationStoreError,
 };
@@ -41,6 +41,7 @@ impl<'a, C> RoleBasedAuthorizationStoreGetAssignedRoles
 where
     C: diesel::Connection,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
     i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
     <C as diesel::Connection>::Backend: diesel::types::HasSqlType<IdentityModelTypeMapping>,
     IdentityModelType: diesel::deserialize::FromSql<IdentityModelTypeMapping, C::Backend>,
@@ -73,10 +74,17 @@ where
                     .load::<RolePermissionModel>(self.conn)?
                     .grouped_by(&roles);
 
+                let scopes = RoleScopeModel::belonging_to(&roles)
+                    .order(rbac_role_scopes::position)
+                    .load::<RoleScopeModel>(self.conn)?
+                    .grouped_by(&roles);
+
                 Ok(Box::new(
                     roles
                         .into_iter()
                         .zip(perms)
+                        .zip(scopes)
+                        .map(|((role, perms), scopes)| (role, perms, scopes))
                         .map(|models| models.try_into())
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
@@ -18,8 +18,8 @@ use diesel::prelude::*;
 
 use crate::rest_api::auth::authorization::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::rbac_roles,
+        models::{RoleModel, RolePermissionModel, RoleScopeModel},
+        schema::{rbac_role_scopes, rbac_roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
@@ -34,6 +34,7 @@ impl<'a, C> RoleBasedAuthorizationStoreGetRole for RoleBasedAuthorizationStoreOp
 where
     C: diesel::Connection,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
 {
     fn get_role(&self, search_id: &str) -> Result<Option<Role>, RoleBasedAuthorizationStoreError> {
         self.conn.transaction(|| {
@@ -45,9 +46,16 @@ where
                 .load::<RolePermissionModel>(self.conn)?
                 .grouped_by(&roles);
 
+            let scopes = RoleScopeModel::belonging_to(&roles)
+                .order(rbac_role_scopes::position)
+                .load::<RoleScopeModel>(self.conn)?
+                .grouped_by(&roles);
+
             roles
                 .into_iter()
                 .zip(perms)
+                .zip(scopes)
+                .map(|((role, perms), scopes)| (role, perms, scopes))
                 .next()
                 .map(|models| models.try_into())
                 .transpose()
@@ -27,8 +27,12 @@ use crate::rest_api::auth::authorization::rbac::store::{
 use super::RoleBasedAuthorizationStoreOperations;
 
 pub trait RoleBasedAuthorizationStoreListAssignments {
+    /// Lists the assignments. If a limit is given, the assignments are ordered by identity,
+    /// starting after the identity `after`, and at most `limit` assignments are returned.
     fn list_assignments(
         &self,
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>;
 }
 
@@ -43,11 +47,21 @@ where
 {
     fn list_assignments(
         &self,
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
     {
         self.conn
             .transaction::<Box<dyn ExactSizeIterator<Item = Assignment>>, _, _>(|| {
-                let identities = rbac_identities::table.load::<IdentityModel>(self.conn)?;
+                let mut query = rbac_identities::table.into_boxed();
+                if let Some(limit) = limit {
+                    if let Some(after) = after {
+                        query = query.filter(rbac_identities::identity.gt(after));
+                    }
+                    query = query.order(rbac_identities::identity).limit(limit as i64);
+                }
+
+                let identities = query.load::<IdentityModel>(self.conn)?;
 
                 let assignments = AssignmentModel::belonging_to(&identities)
                     .load::<AssignmentModel>(self.conn)?
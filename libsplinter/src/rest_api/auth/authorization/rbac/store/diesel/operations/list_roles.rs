@@ -18,8 +18,8 @@ use diesel::prelude::*;
 
 use crate::rest_api::auth::authorization::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::rbac_roles,
+        models::{RoleModel, RolePermissionModel, RoleScopeModel},
+        schema::{rbac_role_scopes, rbac_roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
@@ -27,8 +27,12 @@ use crate::rest_api::auth::authorization::rbac::store::{
 use super::RoleBasedAuthorizationStoreOperations;
 
 pub trait RoleBasedAuthorizationStoreListRoles {
+    /// Lists the roles. If a limit is given, the roles are ordered by ID, starting after the ID
+    /// `after`, and at most `limit` roles are returned.
     fn list_roles(
         &self,
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
 }
 
@@ -36,22 +40,40 @@ impl<'a, C> RoleBasedAuthorizationStoreListRoles for RoleBasedAuthorizationStore
 where
     C: diesel::Connection,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
 {
     fn list_roles(
         &self,
+        after: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
         self.conn
             .transaction::<Box<dyn ExactSizeIterator<Item = Role>>, _, _>(|| {
-                let roles = rbac_roles::table.load::<RoleModel>(self.conn)?;
+                let mut query = rbac_roles::table.into_boxed();
+                if let Some(limit) = limit {
+                    if let Some(after) = after {
+                        query = query.filter(rbac_roles::id.gt(after));
+                    }
+                    query = query.order(rbac_roles::id).limit(limit as i64);
+                }
+
+                let roles = query.load::<RoleModel>(self.conn)?;
 
                 let perms = RolePermissionModel::belonging_to(&roles)
                     .load::<RolePermissionModel>(self.conn)?
                     .grouped_by(&roles);
 
+                let scopes = RoleScopeModel::belonging_to(&roles)
+                    .order(rbac_role_scopes::position)
+                    .load::<RoleScopeModel>(self.conn)?
+                    .grouped_by(&roles);
+
                 Ok(Box::new(
                     roles
                         .into_iter()
                         .zip(perms)
+                        .zip(scopes)
+                        .map(|((role, perms), scopes)| (role, perms, scopes))
                         .map(|models| models.try_into())
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
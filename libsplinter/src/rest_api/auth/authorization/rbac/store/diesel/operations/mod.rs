@@ -14,6 +14,8 @@
 
 pub(super) mod add_assignment;
 pub(super) mod add_role;
+pub(super) mod count_assignments;
+pub(super) mod count_roles;
 pub(super) mod get_assigned_roles;
 pub(super) mod get_assignment;
 pub(super) mod get_role;
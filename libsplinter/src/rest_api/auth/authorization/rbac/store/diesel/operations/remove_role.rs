@@ -15,7 +15,7 @@
 use diesel::{dsl::delete, prelude::*};
 
 use crate::rest_api::auth::authorization::rbac::store::{
-    diesel::schema::{rbac_role_permissions, rbac_roles},
+    diesel::schema::{rbac_role_permissions, rbac_role_scopes, rbac_roles},
     RoleBasedAuthorizationStoreError,
 };
 
@@ -35,6 +35,9 @@ where
             delete(rbac_role_permissions::table.filter(rbac_role_permissions::role_id.eq(role_id)))
                 .execute(self.conn)?;
 
+            delete(rbac_role_scopes::table.filter(rbac_role_scopes::role_id.eq(role_id)))
+                .execute(self.conn)?;
+
             delete(rbac_roles::table.filter(rbac_roles::id.eq(role_id))).execute(self.conn)?;
 
             Ok(())
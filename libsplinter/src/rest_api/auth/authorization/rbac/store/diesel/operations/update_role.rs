@@ -20,8 +20,8 @@ use diesel::{
 use crate::error::{ConstraintViolationError, ConstraintViolationType};
 use crate::rest_api::auth::authorization::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::{rbac_role_permissions, rbac_roles},
+        models::{RoleModel, RolePermissionModel, RoleScopeModel},
+        schema::{rbac_role_permissions, rbac_role_scopes, rbac_roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
@@ -37,7 +37,11 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, scopes): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleScopeModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
             let updated = update(rbac_roles::table.find(&role.id))
@@ -61,6 +65,15 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            delete(rbac_role_scopes::table.filter(rbac_role_scopes::role_id.eq(&role.id)))
+                .execute(self.conn)?;
+
+            if !scopes.is_empty() {
+                insert_into(rbac_role_scopes::table)
+                    .values(scopes)
+                    .execute(self.conn)?;
+            }
+
             Ok(())
         })
     }
@@ -71,7 +84,11 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
 {
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, scopes): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleScopeModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
             let updated = update(rbac_roles::table.find(&role.id))
@@ -95,6 +112,15 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            delete(rbac_role_scopes::table.filter(rbac_role_scopes::role_id.eq(&role.id)))
+                .execute(self.conn)?;
+
+            if !scopes.is_empty() {
+                insert_into(rbac_role_scopes::table)
+                    .values(scopes)
+                    .execute(self.conn)?;
+            }
+
             Ok(())
         })
     }
@@ -29,6 +29,19 @@ table! {
 joinable!(rbac_role_permissions -> rbac_roles (role_id));
 allow_tables_to_appear_in_same_query!(rbac_roles, rbac_role_permissions);
 
+table! {
+    rbac_role_scopes (role_id, position) {
+        role_id -> Text,
+        position -> Integer,
+        circuit_id -> Nullable<Text>,
+        management_type -> Nullable<Text>,
+        service_id -> Nullable<Text>,
+    }
+}
+
+joinable!(rbac_role_scopes -> rbac_roles (role_id));
+allow_tables_to_appear_in_same_query!(rbac_roles, rbac_role_scopes);
+
 table! {
     rbac_identities (identity) {
         identity -> Text,
@@ -19,6 +19,7 @@ mod diesel;
 mod error;
 
 use crate::error::InvalidStateError;
+use crate::rest_api::auth::authorization::RequestedResource;
 
 #[cfg(feature = "diesel")]
 pub use self::diesel::DieselRoleBasedAuthorizationStore;
@@ -28,11 +29,16 @@ pub use error::RoleBasedAuthorizationStoreError;
 pub const ADMIN_ROLE_ID: &str = "admin";
 
 /// A Role is a named set of permissions.
+///
+/// A role may be limited to a set of resource scopes, in which case its permissions are only
+/// granted for requests that access a resource matching one of the scopes. A role without any
+/// scopes grants its permissions for all resources.
 #[derive(Clone)]
 pub struct Role {
     id: String,
     display_name: String,
     permissions: Vec<String>,
+    scopes: Vec<RoleScope>,
 }
 
 impl Role {
@@ -51,19 +57,172 @@ impl Role {
         &self.permissions
     }
 
+    /// Returns the role's resource scopes. The role applies to all resources if this is empty.
+    pub fn scopes(&self) -> &[RoleScope] {
+        &self.scopes
+    }
+
+    /// Returns `true` if the role's permissions are granted for the given resource.
+    pub fn applies_to(&self, resource: &RequestedResource) -> bool {
+        self.scopes.is_empty() || self.scopes.iter().any(|scope| scope.matches(resource))
+    }
+
     /// Convert this role back into a builder, in order to update its values.
     pub fn into_update_builder(self) -> RoleUpdateBuilder {
         RoleUpdateBuilder {
             id: self.id,
             display_name: Some(self.display_name),
             permissions: self.permissions,
+            scopes: self.scopes,
         }
     }
 
     /// Converts this role into it's constituent parts.  These parts are in the tuple:
-    /// `(id, display_name, permissions)`.
-    pub fn into_parts(self) -> (String, String, Vec<String>) {
-        (self.id, self.display_name, self.permissions)
+    /// `(id, display_name, permissions, scopes)`.
+    pub fn into_parts(self) -> (String, String, Vec<String>, Vec<RoleScope>) {
+        (self.id, self.display_name, self.permissions, self.scopes)
+    }
+}
+
+/// A set of resources that a role's permissions are limited to.
+///
+/// Each field is a pattern that is matched against the corresponding ID of the resource being
+/// accessed, where `*` matches any sequence of characters. A field that is not set matches any
+/// resource, but a field that is set only matches requests that identify that part of the
+/// resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleScope {
+    circuit_id: Option<String>,
+    management_type: Option<String>,
+    service_id: Option<String>,
+}
+
+impl RoleScope {
+    /// Returns the circuit ID pattern of the scope, if set.
+    pub fn circuit_id(&self) -> Option<&str> {
+        self.circuit_id.as_deref()
+    }
+
+    /// Returns the circuit management type pattern of the scope, if set.
+    pub fn management_type(&self) -> Option<&str> {
+        self.management_type.as_deref()
+    }
+
+    /// Returns the service ID pattern of the scope, if set.
+    pub fn service_id(&self) -> Option<&str> {
+        self.service_id.as_deref()
+    }
+
+    /// Returns `true` if the given resource matches each of the scope's patterns.
+    pub fn matches(&self, resource: &RequestedResource) -> bool {
+        field_matches(self.circuit_id.as_deref(), resource.circuit_id())
+            && field_matches(self.management_type.as_deref(), resource.management_type())
+            && field_matches(self.service_id.as_deref(), resource.service_id())
+    }
+
+    /// Converts this scope into it's constituent parts.  These parts are in the tuple:
+    /// `(circuit_id, management_type, service_id)`.
+    pub fn into_parts(self) -> (Option<String>, Option<String>, Option<String>) {
+        (self.circuit_id, self.management_type, self.service_id)
+    }
+}
+
+fn field_matches(pattern: Option<&str>, value: Option<&str>) -> bool {
+    match (pattern, value) {
+        (None, _) => true,
+        (Some(pattern), Some(value)) => pattern_matches(pattern, value),
+        (Some(_), None) => false,
+    }
+}
+
+/// Matches a value against a pattern in which `*` matches any sequence of characters.
+fn pattern_matches(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // split always returns at least one item
+    let first = parts.next().unwrap_or("");
+    let mut remaining = match value.strip_prefix(first) {
+        Some(remaining) => remaining,
+        None => return false,
+    };
+
+    let mut parts = parts.collect::<Vec<_>>();
+    let last = match parts.pop() {
+        Some(last) => last,
+        // The pattern does not contain a wildcard
+        None => return remaining.is_empty(),
+    };
+
+    for part in parts {
+        match remaining.find(part) {
+            Some(idx) => remaining = &remaining[idx + part.len()..],
+            None => return false,
+        }
+    }
+
+    remaining.ends_with(last)
+}
+
+/// A builder to create new role scopes.
+#[derive(Default)]
+pub struct RoleScopeBuilder {
+    circuit_id: Option<String>,
+    management_type: Option<String>,
+    service_id: Option<String>,
+}
+
+impl RoleScopeBuilder {
+    /// Constructs a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the circuit ID pattern for the new scope.
+    pub fn with_circuit_id(mut self, circuit_id: String) -> Self {
+        self.circuit_id = Some(circuit_id);
+        self
+    }
+
+    /// Sets the circuit management type pattern for the new scope.
+    pub fn with_management_type(mut self, management_type: String) -> Self {
+        self.management_type = Some(management_type);
+        self
+    }
+
+    /// Sets the service ID pattern for the new scope.
+    pub fn with_service_id(mut self, service_id: String) -> Self {
+        self.service_id = Some(service_id);
+        self
+    }
+
+    /// Builds the new RoleScope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidStateError`] under the following conditions:
+    /// * none of the patterns were provided
+    /// * an empty pattern was provided
+    pub fn build(self) -> Result<RoleScope, InvalidStateError> {
+        if self.circuit_id.is_none() && self.management_type.is_none() && self.service_id.is_none()
+        {
+            return Err(InvalidStateError::with_message(
+                "A role scope requires a circuit_id, management_type or service_id field".into(),
+            ));
+        }
+
+        if [&self.circuit_id, &self.management_type, &self.service_id]
+            .iter()
+            .any(|pattern| pattern.as_deref() == Some(""))
+        {
+            return Err(InvalidStateError::with_message(
+                "A role scope requires non-empty fields".into(),
+            ));
+        }
+
+        Ok(RoleScope {
+            circuit_id: self.circuit_id,
+            management_type: self.management_type,
+            service_id: self.service_id,
+        })
     }
 }
 
@@ -73,6 +232,7 @@ pub struct RoleBuilder {
     id: Option<String>,
     display_name: Option<String>,
     permissions: Vec<String>,
+    scopes: Vec<RoleScope>,
 }
 
 impl RoleBuilder {
@@ -99,6 +259,12 @@ impl RoleBuilder {
         self
     }
 
+    /// Sets the resource scopes for the new role.
+    pub fn with_scopes(mut self, scopes: Vec<RoleScope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     /// Builds the new Role.
     ///
     /// # Errors
@@ -136,6 +302,7 @@ impl RoleBuilder {
             id,
             display_name,
             permissions: self.permissions,
+            scopes: self.scopes,
         })
     }
 }
@@ -147,6 +314,7 @@ pub struct RoleUpdateBuilder {
     id: String,
     display_name: Option<String>,
     permissions: Vec<String>,
+    scopes: Vec<RoleScope>,
 }
 
 impl RoleUpdateBuilder {
@@ -162,6 +330,12 @@ impl RoleUpdateBuilder {
         self
     }
 
+    /// Updates the resource scopes for the updated role.
+    pub fn with_scopes(mut self, scopes: Vec<RoleScope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     /// Builds the updated Role.
     ///
     /// # Errors
@@ -190,6 +364,7 @@ impl RoleUpdateBuilder {
             id: self.id,
             display_name,
             permissions: self.permissions,
+            scopes: self.scopes,
         })
     }
 }
@@ -203,6 +378,16 @@ pub enum Identity {
     User(String),
 }
 
+impl Identity {
+    /// Returns the public key or user ID of the identity.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Identity::Key(key) => key,
+            Identity::User(user_id) => user_id,
+        }
+    }
+}
+
 impl From<&crate::rest_api::auth::identity::Identity> for Option<Identity> {
     fn from(identity: &crate::rest_api::auth::identity::Identity) -> Self {
         match identity {
@@ -342,6 +527,31 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
 
+    /// Lists a page of roles, in ascending order of role ID.
+    ///
+    /// The page starts with the first role whose ID follows `after`, if provided, and contains at
+    /// most `limit` roles.
+    fn list_roles_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        let mut roles = self
+            .list_roles()?
+            .filter(|role| after.map(|after| role.id() > after).unwrap_or(true))
+            .collect::<Vec<_>>();
+
+        roles.sort_by(|a, b| a.id().cmp(b.id()));
+        roles.truncate(limit);
+
+        Ok(Box::new(roles.into_iter()))
+    }
+
+    /// Returns the number of roles.
+    fn count_roles(&self) -> Result<usize, RoleBasedAuthorizationStoreError> {
+        Ok(self.list_roles()?.len())
+    }
+
     /// Adds a role.
     ///
     /// # Errors
@@ -380,6 +590,36 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>;
 
+    /// Lists a page of assignments, in ascending order of identity.
+    ///
+    /// The page starts with the first assignment whose identity follows `after`, if provided, and
+    /// contains at most `limit` assignments.
+    fn list_assignments_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
+    {
+        let mut assignments = self
+            .list_assignments()?
+            .filter(|assignment| {
+                after
+                    .map(|after| assignment.identity().as_str() > after)
+                    .unwrap_or(true)
+            })
+            .collect::<Vec<_>>();
+
+        assignments.sort_by(|a, b| a.identity().as_str().cmp(b.identity().as_str()));
+        assignments.truncate(limit);
+
+        Ok(Box::new(assignments.into_iter()))
+    }
+
+    /// Returns the number of assignments.
+    fn count_assignments(&self) -> Result<usize, RoleBasedAuthorizationStoreError> {
+        Ok(self.list_assignments()?.len())
+    }
+
     /// Adds an assignment.
     ///
     /// # Errors
@@ -420,3 +660,72 @@ impl Clone for Box<dyn RoleBasedAuthorizationStore> {
         self.clone_box()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that the patterns of a role scope match IDs, with `*` matching any sequence of
+    /// characters.
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("abcde-01234", "abcde-01234"));
+        assert!(!pattern_matches("abcde-01234", "abcde-012345"));
+        assert!(pattern_matches("*", ""));
+        assert!(pattern_matches("*", "abcde-01234"));
+        assert!(pattern_matches("abcde-*", "abcde-01234"));
+        assert!(!pattern_matches("abcde-*", "fghij-01234"));
+        assert!(pattern_matches("*-01234", "abcde-01234"));
+        assert!(pattern_matches("a*-*4", "abcde-01234"));
+        assert!(!pattern_matches("a*-*5", "abcde-01234"));
+        assert!(!pattern_matches("ab*b", "ab"));
+    }
+
+    /// Verifies that a role applies to all resources if it has no scopes, and otherwise only to
+    /// resources that match all of the fields of one of its scopes.
+    #[test]
+    fn test_role_applies_to() {
+        let role = RoleBuilder::new()
+            .with_id("role".into())
+            .with_display_name("Role".into())
+            .with_permissions(vec!["a".into()])
+            .build()
+            .expect("Unable to build role");
+        assert!(role.applies_to(&RequestedResource::new()));
+        assert!(role.applies_to(&RequestedResource::new().with_circuit_id("abcde-01234")));
+
+        let role = role
+            .into_update_builder()
+            .with_scopes(vec![
+                RoleScopeBuilder::new()
+                    .with_circuit_id("abcde-*".into())
+                    .with_service_id("a000".into())
+                    .build()
+                    .expect("Unable to build scope"),
+                RoleScopeBuilder::new()
+                    .with_management_type("gameroom".into())
+                    .build()
+                    .expect("Unable to build scope"),
+            ])
+            .build()
+            .expect("Unable to build role");
+        assert!(!role.applies_to(&RequestedResource::new()));
+        assert!(!role.applies_to(&RequestedResource::new().with_circuit_id("abcde-01234")));
+        assert!(role.applies_to(
+            &RequestedResource::new()
+                .with_circuit_id("abcde-01234")
+                .with_service_id("a000")
+        ));
+        assert!(role.applies_to(
+            &RequestedResource::new()
+                .with_circuit_id("fghij-01234")
+                .with_management_type("gameroom")
+        ));
+
+        assert!(RoleScopeBuilder::new().build().is_err());
+        assert!(RoleScopeBuilder::new()
+            .with_circuit_id("".into())
+            .build()
+            .is_err());
+    }
+}
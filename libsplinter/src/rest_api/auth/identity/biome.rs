@@ -57,14 +57,20 @@ impl IdentityProvider for BiomeUserIdentityProvider {
             _ => return Ok(None),
         };
 
-        let secret = self
+        let key_ring = self
             .token_secret_manager
-            .secret()
+            .key_ring()
             .map_err(|err| InternalError::from_source(err.into()))?;
 
+        // The token is invalid if it was signed with a key that is not in the key ring
+        let key = match key_ring.key_for_token(token) {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
         Ok(decode::<Claims>(
             token,
-            &DecodingKey::from_secret(secret.as_ref()),
+            &DecodingKey::from_secret(key.secret().as_ref()),
             &self.validation,
         )
         .map(|token_data| Identity::User(token_data.claims.user_id()))
@@ -20,13 +20,15 @@ pub mod biome;
 pub mod cylinder;
 #[cfg(feature = "oauth")]
 pub mod oauth;
+#[cfg(feature = "rest-api-tokens")]
+pub mod token;
 
 use crate::error::InternalError;
 
 use super::AuthorizationHeader;
 
 /// A REST API client's identity as determined by an [IdentityProvider]
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Identity {
     /// A custom identity
     Custom(String),
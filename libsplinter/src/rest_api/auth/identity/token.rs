@@ -0,0 +1,160 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An identity provider that resolves API tokens issued by the Splinter REST API
+
+use std::time::SystemTime;
+
+use crate::error::InternalError;
+use crate::rest_api::auth::{
+    token::{hash_token, store::ApiTokenStore},
+    AuthorizationHeader, BearerToken,
+};
+
+use super::{Identity, IdentityProvider};
+
+/// Resolves an API token to the custom identity of the token
+///
+/// This provider only accepts `AuthorizationHeader::Bearer(BearerToken::ApiToken(token))`
+/// authorizations. The token must be in the backing store and must not have expired.
+pub struct ApiTokenIdentityProvider {
+    api_token_store: Box<dyn ApiTokenStore>,
+}
+
+impl ApiTokenIdentityProvider {
+    /// Creates a new API token identity provider that is backed by the given store
+    pub fn new(api_token_store: Box<dyn ApiTokenStore>) -> Self {
+        Self { api_token_store }
+    }
+}
+
+impl IdentityProvider for ApiTokenIdentityProvider {
+    fn get_identity(
+        &self,
+        authorization: &AuthorizationHeader,
+    ) -> Result<Option<Identity>, InternalError> {
+        let token = match authorization {
+            AuthorizationHeader::Bearer(BearerToken::ApiToken(token)) => token,
+            _ => return Ok(None),
+        };
+
+        Ok(self
+            .api_token_store
+            .get_token_by_hash(&hash_token(token))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .filter(|token| !token.is_expired_at(SystemTime::now()))
+            .map(|token| token.identity()))
+    }
+
+    fn clone_box(&self) -> Box<dyn IdentityProvider> {
+        Box::new(Self {
+            api_token_store: self.api_token_store.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::auth::token::{
+        generate_token, store::diesel::DieselApiTokenStore, ApiTokenBuilder,
+    };
+
+    /// Verifies that the provider resolves a stored token to the token's identity, and ignores
+    /// unknown and expired tokens as well as other kinds of authorization.
+    ///
+    /// 1. Add a token without an expiration time and a token that has already expired
+    /// 2. Verify that the first token resolves to its identity
+    /// 3. Verify that the expired token, an unknown token, and a custom authorization with the
+    ///    first token's value are not resolved
+    #[test]
+    fn get_identity() {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+        let store = DieselApiTokenStore::new(pool);
+
+        let active_token = generate_token();
+        let active = ApiTokenBuilder::new()
+            .with_name("active".into())
+            .with_owner(Identity::User("user-1".into()))
+            .with_token_hash(hash_token(&active_token))
+            .build()
+            .expect("Unable to build token");
+        store
+            .add_token(active.clone())
+            .expect("Unable to add token");
+
+        let expired_token = generate_token();
+        let created_at = SystemTime::now() - Duration::from_secs(120);
+        store
+            .add_token(
+                ApiTokenBuilder::new()
+                    .with_name("expired".into())
+                    .with_owner(Identity::User("user-1".into()))
+                    .with_token_hash(hash_token(&expired_token))
+                    .with_created_at(created_at)
+                    .with_expires_at(created_at + Duration::from_secs(60))
+                    .build()
+                    .expect("Unable to build token"),
+            )
+            .expect("Unable to add token");
+
+        let provider = ApiTokenIdentityProvider::new(Box::new(store));
+
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Bearer(BearerToken::ApiToken(
+                    active_token.clone()
+                )))
+                .expect("Failed to get identity"),
+            Some(active.identity())
+        );
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Bearer(BearerToken::ApiToken(
+                    expired_token
+                )))
+                .expect("Failed to get identity"),
+            None
+        );
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Bearer(BearerToken::ApiToken(
+                    generate_token()
+                )))
+                .expect("Failed to get identity"),
+            None
+        );
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Custom(active_token))
+                .expect("Failed to get identity"),
+            None
+        );
+    }
+}
@@ -16,9 +16,13 @@
 
 #[cfg(feature = "rest-api-actix-web-1")]
 pub(crate) mod actix;
+#[cfg(feature = "rest-api-audit")]
+pub mod audit;
 #[cfg(feature = "authorization")]
 pub mod authorization;
 pub mod identity;
+#[cfg(feature = "rest-api-tokens")]
+pub mod token;
 
 use std::str::FromStr;
 
@@ -28,7 +32,10 @@ use crate::error::InvalidArgumentError;
 use super::Method;
 
 #[cfg(feature = "authorization")]
-use authorization::{AuthorizationHandler, AuthorizationHandlerResult, Permission, PermissionMap};
+use authorization::{
+    AuthorizationHandler, AuthorizationHandlerResult, Permission, PermissionMap,
+    ResourceAuthorization,
+};
 #[cfg(feature = "rest-api-actix-web-1")]
 use identity::{Identity, IdentityProvider};
 
@@ -37,6 +44,10 @@ use identity::{Identity, IdentityProvider};
 enum AuthorizationResult {
     /// The client was authorized to the given identity based on the authorization header
     Authorized(Identity),
+    /// The client was authorized for only some of the resources of a scoped endpoint; the endpoint
+    /// must check the resources that the request accesses
+    #[cfg(feature = "authorization")]
+    AuthorizedForResources(ResourceAuthorization),
     /// The requested endpoint does not require authorization
     #[cfg(any(
         feature = "authorization",
@@ -99,8 +110,13 @@ fn authorize(
             Permission::Check { permission_id, .. } => {
                 match get_identity(auth_header, identity_providers) {
                     Some(identity) => {
+                        let resource = permission_map.get_requested_resource(method, endpoint);
                         for handler in authorization_handlers {
-                            match handler.has_permission(&identity, permission_id) {
+                            match handler.has_permission_for_resource(
+                                &identity,
+                                permission_id,
+                                &resource,
+                            ) {
                                 Ok(AuthorizationHandlerResult::Allow) => {
                                     return AuthorizationResult::Authorized(identity)
                                 }
@@ -111,6 +127,32 @@ fn authorize(
                                 Err(err) => error!("{}", err),
                             }
                         }
+
+                        // A scoped endpoint checks the resources itself, so the client only needs
+                        // the permission for some resources
+                        if permission_map.is_scoped(method, endpoint) {
+                            for handler in authorization_handlers {
+                                match handler
+                                    .has_permission_for_any_resource(&identity, permission_id)
+                                {
+                                    Ok(AuthorizationHandlerResult::Allow) => {
+                                        return AuthorizationResult::AuthorizedForResources(
+                                            ResourceAuthorization::new(
+                                                identity,
+                                                permission_id,
+                                                authorization_handlers.to_vec(),
+                                            ),
+                                        )
+                                    }
+                                    Ok(AuthorizationHandlerResult::Deny) => {
+                                        return AuthorizationResult::Unauthorized
+                                    }
+                                    Ok(AuthorizationHandlerResult::Continue) => {}
+                                    Err(err) => error!("{}", err),
+                                }
+                            }
+                        }
+
                         // No handler allowed the request, so deny by default
                         AuthorizationResult::Unauthorized
                     }
@@ -192,6 +234,9 @@ impl FromStr for AuthorizationHeader {
 /// A bearer token of a specific type
 #[derive(PartialEq)]
 pub enum BearerToken {
+    #[cfg(feature = "rest-api-tokens")]
+    /// Contains an API token issued by the Splinter REST API
+    ApiToken(String),
     #[cfg(feature = "biome-credentials")]
     /// Contains a Biome JWT
     Biome(String),
@@ -215,10 +260,12 @@ impl FromStr for BearerToken {
     fn from_str(str: &str) -> Result<Self, Self::Err> {
         let mut parts = str.splitn(2, ':');
         match (parts.next(), parts.next()) {
-            // Allowing lint in case none of `biome-credentials`, `cylinder-jwt`, or `oauth` are
-            // used
+            // Allowing lint in case none of `biome-credentials`, `cylinder-jwt`, `oauth`, or
+            // `rest-api-tokens` are used
             #[allow(unused_variables, clippy::match_single_binding)]
             (Some(token_type), Some(token)) => match token_type {
+                #[cfg(feature = "rest-api-tokens")]
+                "Token" => Ok(BearerToken::ApiToken(token.to_string())),
                 #[cfg(feature = "biome-credentials")]
                 "Biome" => Ok(BearerToken::Biome(token.to_string())),
                 #[cfg(feature = "cylinder-jwt")]
@@ -239,6 +286,9 @@ mod tests {
 
     use crate::error::InternalError;
 
+    #[cfg(feature = "authorization")]
+    use super::authorization::RequestedResource;
+
     /// Verfifies that the `AuthorizationHeader` enum is correctly parsed from strings
     #[test]
     fn parse_authorization_header() {
@@ -279,6 +329,12 @@ mod tests {
             Ok(BearerToken::OAuth2(token)) if token == "test"
         ));
 
+        #[cfg(feature = "rest-api-tokens")]
+        assert!(matches!(
+            "Token:test".parse(),
+            Ok(BearerToken::ApiToken(token)) if token == "test"
+        ));
+
         assert!(matches!(
             "Unknown:test".parse(),
             Ok(BearerToken::Custom(token)) if token == "Unknown:test"
@@ -896,6 +952,65 @@ mod tests {
         ));
     }
 
+    /// Verifies that the `authorize` function returns
+    /// `AuthorizationResult::AuthorizedForResources(_)` for a scoped endpoint when an authorization
+    /// handler only allows the permission for some resources, and that the returned
+    /// `ResourceAuthorization` only authorizes those resources. The same handler must not allow the
+    /// request to an endpoint that is not scoped.
+    #[cfg(feature = "authorization")]
+    #[test]
+    fn authorize_scoped_endpoint_for_resources() {
+        let permission = Permission::Check {
+            permission_id: "permission",
+            permission_display_name: "",
+            permission_description: "",
+        };
+        let permission_map = {
+            let mut map = PermissionMap::new();
+            map.add_scoped_permission(Method::Get, "/test/scoped", permission);
+            map.add_permission(Method::Get, "/test/endpoint", permission);
+            map
+        };
+
+        let resource_authorization = match authorize(
+            &Method::Get,
+            "/test/scoped",
+            Some("auth"),
+            &permission_map,
+            &[Box::new(AlwaysAcceptIdentityProvider)],
+            &[
+                Box::new(AlwaysContinueAuthorizationHandler),
+                Box::new(ScopedAuthorizationHandler),
+            ],
+        ) {
+            AuthorizationResult::AuthorizedForResources(resource_authorization) => {
+                resource_authorization
+            }
+            _ => panic!("Request should have been authorized for resources"),
+        };
+        assert_eq!(
+            resource_authorization.identity(),
+            &Identity::Custom("identity".into())
+        );
+        assert!(resource_authorization
+            .is_authorized(&RequestedResource::new().with_circuit_id("abcde-01234")));
+        assert!(!resource_authorization
+            .is_authorized(&RequestedResource::new().with_circuit_id("fghij-01234")));
+        assert!(!resource_authorization.is_authorized(&RequestedResource::new()));
+
+        assert!(matches!(
+            authorize(
+                &Method::Get,
+                "/test/endpoint",
+                Some("auth"),
+                &permission_map,
+                &[Box::new(AlwaysAcceptIdentityProvider)],
+                &[Box::new(ScopedAuthorizationHandler)],
+            ),
+            AuthorizationResult::Unauthorized
+        ));
+    }
+
     /// An identity provider that always returns `Ok(Some(_))`
     #[derive(Clone)]
     struct AlwaysAcceptIdentityProvider;
@@ -1026,4 +1141,44 @@ mod tests {
             Box::new(self.clone())
         }
     }
+
+    /// An authorization handler that only allows the permission for the `abcde-01234` circuit
+    #[cfg(feature = "authorization")]
+    #[derive(Clone)]
+    struct ScopedAuthorizationHandler;
+
+    #[cfg(feature = "authorization")]
+    impl AuthorizationHandler for ScopedAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Continue)
+        }
+
+        fn has_permission_for_resource(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+            resource: &RequestedResource,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            match resource.circuit_id() {
+                Some("abcde-01234") => Ok(AuthorizationHandlerResult::Allow),
+                _ => Ok(AuthorizationHandlerResult::Continue),
+            }
+        }
+
+        fn has_permission_for_any_resource(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
 }
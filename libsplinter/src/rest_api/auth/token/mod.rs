@@ -0,0 +1,391 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Long-lived, revocable API tokens for automated clients
+//!
+//! An API token is issued by the Splinter REST API and sent by a client as an
+//! `Authorization: Bearer Token:<token>` header. Only a hash of the token is stored, so a token
+//! cannot be recovered once it has been issued; it can only be revoked. The
+//! [`ApiTokenIdentityProvider`](crate::rest_api::auth::identity::token::ApiTokenIdentityProvider)
+//! resolves a token to an [`Identity::Custom`] identity, which is granted the permissions of the
+//! token's roles by the role-based authorization handler, as long as the token's owner still holds
+//! those roles.
+
+mod routes;
+pub mod store;
+
+use std::time::SystemTime;
+
+use openssl::sha::sha256;
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use uuid::Uuid;
+
+use crate::error::{InternalError, InvalidStateError};
+use crate::hex::to_hex;
+#[cfg(feature = "authorization-handler-rbac")]
+use crate::rest_api::auth::authorization::rbac::store::{
+    Identity as RBACIdentity, RoleBasedAuthorizationStore, ADMIN_ROLE_ID,
+};
+use crate::rest_api::auth::identity::Identity;
+
+use self::store::ApiTokenStore;
+
+pub use routes::ApiTokenResourceProvider;
+
+/// The prefix of the custom identity that an API token resolves to; the rest of the identity is
+/// the token's ID
+const API_TOKEN_IDENTITY_PREFIX: &str = "token:";
+
+/// The number of random characters in a newly generated token
+const TOKEN_LENGTH: usize = 32;
+
+/// An API token, as it is stored
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiToken {
+    id: String,
+    name: String,
+    owner: Identity,
+    token_hash: String,
+    created_at: SystemTime,
+    expires_at: Option<SystemTime>,
+    roles: Vec<String>,
+}
+
+impl ApiToken {
+    /// Returns the ID of the token
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name given to the token by its owner
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the identity of the client that created the token
+    pub fn owner(&self) -> &Identity {
+        &self.owner
+    }
+
+    /// Returns the hash of the token; see [`hash_token`]
+    pub fn token_hash(&self) -> &str {
+        &self.token_hash
+    }
+
+    /// Returns the time the token was created
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Returns the time the token expires, if any
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Returns whether or not the token has expired at the given time
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= time)
+            .unwrap_or(false)
+    }
+
+    /// Returns the IDs of the RBAC roles granted to the token
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    /// Returns the identity that the token resolves to
+    pub fn identity(&self) -> Identity {
+        Identity::Custom(format!("{}{}", API_TOKEN_IDENTITY_PREFIX, self.id))
+    }
+}
+
+/// Builds a new `ApiToken`
+#[derive(Default)]
+pub struct ApiTokenBuilder {
+    id: Option<String>,
+    name: Option<String>,
+    owner: Option<Identity>,
+    token_hash: Option<String>,
+    created_at: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
+    roles: Vec<String>,
+}
+
+impl ApiTokenBuilder {
+    /// Creates a new `ApiTokenBuilder`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the token
+    ///
+    /// If not provided, a random ID is generated.
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the name of the token
+    ///
+    /// This field is required.
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the identity of the client that created the token
+    ///
+    /// This field is required.
+    pub fn with_owner(mut self, owner: Identity) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the hash of the token
+    ///
+    /// This field is required.
+    pub fn with_token_hash(mut self, token_hash: String) -> Self {
+        self.token_hash = Some(token_hash);
+        self
+    }
+
+    /// Sets the time the token was created
+    ///
+    /// If not provided, the current time is used.
+    pub fn with_created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    /// Sets the time the token expires
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Sets the IDs of the RBAC roles granted to the token
+    pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+        self.roles = roles;
+        self
+    }
+
+    /// Builds the `ApiToken`
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if any of the required fields were not set, if the name is
+    /// empty, or if the expiration time is not after the time the token was created.
+    pub fn build(self) -> Result<ApiToken, InvalidStateError> {
+        let name = self.name.ok_or_else(|| missing_field("name"))?;
+        let owner = self.owner.ok_or_else(|| missing_field("owner"))?;
+        let token_hash = self.token_hash.ok_or_else(|| missing_field("token_hash"))?;
+        let created_at = self.created_at.unwrap_or_else(SystemTime::now);
+
+        if name.is_empty() {
+            return Err(InvalidStateError::with_message(
+                "Unable to build ApiToken: The name must not be empty".to_string(),
+            ));
+        }
+        if let Some(expires_at) = self.expires_at {
+            if expires_at <= created_at {
+                return Err(InvalidStateError::with_message(
+                    "Unable to build ApiToken: The expiration time must be after the time the \
+                     token was created"
+                        .to_string(),
+                ));
+            }
+        }
+
+        Ok(ApiToken {
+            id: self
+                .id
+                .unwrap_or_else(|| Uuid::new_v4().to_simple().to_string()),
+            name,
+            owner,
+            token_hash,
+            created_at,
+            expires_at: self.expires_at,
+            roles: self.roles,
+        })
+    }
+}
+
+fn missing_field(field: &str) -> InvalidStateError {
+    InvalidStateError::with_message(format!(
+        "Unable to build ApiToken: Missing required field: {}",
+        field
+    ))
+}
+
+/// Generates a new random token
+///
+/// The token is only returned to the client that requested it; the store keeps its hash.
+pub fn generate_token() -> String {
+    let mut rng = thread_rng();
+    std::iter::repeat(())
+        .map(|()| rng.sample(Alphanumeric))
+        .map(char::from)
+        .take(TOKEN_LENGTH)
+        .collect()
+}
+
+/// Computes the hash of a token, which is the hex-encoded SHA-256 hash of the token
+///
+/// Tokens are generated with enough randomness that a fast hash is sufficient; unlike a password,
+/// a token cannot be guessed from a dictionary.
+pub fn hash_token(token: &str) -> String {
+    to_hex(&sha256(token.as_bytes()))
+}
+
+/// Returns the ID of the API token that the given identity was resolved from, if any
+pub fn token_id_from_identity(identity: &Identity) -> Option<&str> {
+    match identity {
+        Identity::Custom(custom) => custom.strip_prefix(API_TOKEN_IDENTITY_PREFIX),
+        _ => None,
+    }
+}
+
+/// Returns the IDs of the roles held by the client with the given identity.
+///
+/// A client that was identified by an API token holds the roles of the token that the token's
+/// owner still holds, or all of the token's roles if the owner has the admin role, so a token never
+/// grants more than its owner currently has. A token that has been revoked or has expired holds no
+/// roles. Other clients hold the roles assigned to them in the RBAC store, if one is provided.
+pub(crate) fn get_held_roles(
+    identity: &Identity,
+    api_token_store: &dyn ApiTokenStore,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        &dyn RoleBasedAuthorizationStore,
+    >,
+) -> Result<Vec<String>, InternalError> {
+    if let Some(token_id) = token_id_from_identity(identity) {
+        let token = match api_token_store
+            .get_token(token_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(token) if !token.is_expired_at(SystemTime::now()) => token,
+            _ => return Ok(vec![]),
+        };
+
+        #[cfg(feature = "authorization-handler-rbac")]
+        {
+            let owner_roles = get_held_roles(
+                token.owner(),
+                api_token_store,
+                role_based_authorization_store,
+            )?;
+            if !owner_roles.iter().any(|role| role == ADMIN_ROLE_ID) {
+                return Ok(token
+                    .roles()
+                    .iter()
+                    .filter(|role| owner_roles.contains(role))
+                    .cloned()
+                    .collect());
+            }
+        }
+
+        return Ok(token.roles().to_vec());
+    }
+
+    #[cfg(feature = "authorization-handler-rbac")]
+    if let (Some(store), Some(identity)) = (
+        role_based_authorization_store,
+        Option::<RBACIdentity>::from(identity),
+    ) {
+        return Ok(store
+            .get_assignment(&identity)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .map(|assignment| assignment.roles().to_vec())
+            .unwrap_or_default());
+    }
+
+    Ok(vec![])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Verify that generated tokens are random and that their hashes are stable.
+    #[test]
+    fn test_generate_and_hash_token() {
+        let token = generate_token();
+        assert_eq!(token.len(), TOKEN_LENGTH);
+        assert_ne!(token, generate_token());
+
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), hash_token(&generate_token()));
+    }
+
+    /// Verify that a token resolves to a custom identity that maps back to the token's ID, and
+    /// that other identities do not.
+    #[test]
+    fn test_token_identity() {
+        let token = ApiTokenBuilder::new()
+            .with_id("token-1".into())
+            .with_name("ci".into())
+            .with_owner(Identity::User("user-1".into()))
+            .with_token_hash(hash_token("secret"))
+            .build()
+            .expect("Unable to build token");
+
+        assert_eq!(token.identity(), Identity::Custom("token:token-1".into()));
+        assert_eq!(token_id_from_identity(&token.identity()), Some("token-1"));
+        assert_eq!(
+            token_id_from_identity(&Identity::Custom("token-1".into())),
+            None
+        );
+        assert_eq!(
+            token_id_from_identity(&Identity::User("token:token-1".into())),
+            None
+        );
+    }
+
+    /// Verify that a token cannot be built without the required fields or with an expiration time
+    /// that is not after its creation time, and that it expires at its expiration time.
+    #[test]
+    fn test_build() {
+        assert!(ApiTokenBuilder::new()
+            .with_owner(Identity::User("user-1".into()))
+            .with_token_hash(hash_token("secret"))
+            .build()
+            .is_err());
+
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        assert!(ApiTokenBuilder::new()
+            .with_name("ci".into())
+            .with_owner(Identity::User("user-1".into()))
+            .with_token_hash(hash_token("secret"))
+            .with_created_at(created_at)
+            .with_expires_at(created_at)
+            .build()
+            .is_err());
+
+        let token = ApiTokenBuilder::new()
+            .with_name("ci".into())
+            .with_owner(Identity::User("user-1".into()))
+            .with_token_hash(hash_token("secret"))
+            .with_created_at(created_at)
+            .with_expires_at(created_at + Duration::from_secs(60))
+            .build()
+            .expect("Unable to build token");
+        assert!(!token.id().is_empty());
+        assert!(!token.is_expired_at(created_at + Duration::from_secs(59)));
+        assert!(token.is_expired_at(created_at + Duration::from_secs(60)));
+    }
+}
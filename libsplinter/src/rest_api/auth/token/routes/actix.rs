@@ -0,0 +1,771 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /authorization/tokens` for listing API tokens
+//! * `POST /authorization/tokens` for creating an API token
+//! * `DELETE /authorization/tokens/{token_id}` for revoking an API token
+
+use std::time::{Duration, SystemTime};
+
+use actix_web::{error::BlockingError, web, Error, HttpMessage, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+
+#[cfg(feature = "authorization-handler-rbac")]
+use crate::rest_api::auth::authorization::rbac::store::{
+    RoleBasedAuthorizationStore, ADMIN_ROLE_ID,
+};
+use crate::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    auth::{
+        identity::Identity,
+        token::{
+            generate_token, get_held_roles, hash_token, store::ApiTokenStore, ApiToken,
+            ApiTokenBuilder,
+        },
+    },
+    paging::get_response_paging_info,
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+use super::{
+    resources::{
+        ApiTokenResponse, CreateApiTokenPayload, CreateApiTokenResponse, ListApiTokensQuery,
+        ListApiTokensResponse,
+    },
+    API_TOKENS_READ_PERMISSION, API_TOKENS_WRITE_PERMISSION,
+};
+
+const AUTHORIZATION_TOKENS_MIN: u32 = 1;
+const AUTHORIZATION_TOKEN_MIN: u32 = 1;
+
+pub fn make_tokens_resource(
+    api_token_store: Box<dyn ApiTokenStore>,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        Box<dyn RoleBasedAuthorizationStore>,
+    >,
+) -> Resource {
+    let list_store = api_token_store.clone();
+    let post_store = api_token_store;
+    #[cfg(feature = "authorization-handler-rbac")]
+    let list_role_based_authorization_store = role_based_authorization_store.clone();
+    Resource::build("/authorization/tokens")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            AUTHORIZATION_TOKENS_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, API_TOKENS_READ_PERMISSION, move |r, _| {
+            list_tokens(
+                r,
+                list_store.clone(),
+                #[cfg(feature = "authorization-handler-rbac")]
+                list_role_based_authorization_store.clone(),
+            )
+        })
+        .add_method(Method::Post, API_TOKENS_WRITE_PERMISSION, move |r, p| {
+            create_token(
+                r,
+                p,
+                post_store.clone(),
+                #[cfg(feature = "authorization-handler-rbac")]
+                role_based_authorization_store.clone(),
+            )
+        })
+}
+
+pub fn make_token_resource(
+    api_token_store: Box<dyn ApiTokenStore>,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        Box<dyn RoleBasedAuthorizationStore>,
+    >,
+) -> Resource {
+    Resource::build("/authorization/tokens/{token_id}")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            AUTHORIZATION_TOKEN_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Delete, API_TOKENS_WRITE_PERMISSION, move |r, _| {
+            revoke_token(
+                r,
+                api_token_store.clone(),
+                #[cfg(feature = "authorization-handler-rbac")]
+                role_based_authorization_store.clone(),
+            )
+        })
+}
+
+fn list_tokens(
+    req: HttpRequest,
+    api_token_store: Box<dyn ApiTokenStore>,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        Box<dyn RoleBasedAuthorizationStore>,
+    >,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    // Only the client's own tokens are listed, so it must be known
+    let identity = match req.extensions().get::<Identity>() {
+        Some(identity) => identity.clone(),
+        None => return unauthorized(),
+    };
+
+    let query = match web::Query::<ListApiTokensQuery>::from_query(req.query_string()) {
+        Ok(query) => query.into_inner(),
+        Err(_) => return bad_request("Invalid query"),
+    };
+
+    if query.limit == 0 {
+        return bad_request("Invalid limit: must be greater than 0");
+    }
+
+    let link = format!("{}?", req.uri().path());
+    let limit = query.limit;
+    let offset = query.offset;
+
+    Box::new(
+        web::block(move || {
+            let tokens = list_visible_tokens(
+                &identity,
+                &*api_token_store,
+                #[cfg(feature = "authorization-handler-rbac")]
+                role_based_authorization_store.as_deref(),
+            )?;
+
+            let total = tokens.len();
+            let tokens = tokens.skip(offset).take(limit).collect::<Vec<_>>();
+            let paging = get_response_paging_info(Some(limit), Some(offset), &link, total);
+
+            Ok((tokens, paging))
+        })
+        .then(|res: Result<_, BlockingError<String>>| match res {
+            Ok((tokens, paging)) => Ok(HttpResponse::Ok().json(ListApiTokensResponse {
+                data: tokens.iter().map(ApiTokenResponse::from).collect(),
+                paging,
+            })),
+            Err(err) => {
+                error!("Unable to list API tokens: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
+    )
+}
+
+fn create_token(
+    req: HttpRequest,
+    payload: web::Payload,
+    api_token_store: Box<dyn ApiTokenStore>,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        Box<dyn RoleBasedAuthorizationStore>,
+    >,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    // The token belongs to the client that requested it, so it must be known
+    let owner = match req.extensions().get::<Identity>() {
+        Some(identity) => identity.clone(),
+        None => return unauthorized(),
+    };
+
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let payload = match serde_json::from_slice::<CreateApiTokenPayload>(&body) {
+                    Ok(payload) => payload,
+                    Err(err) => return bad_request(&format!("Invalid token payload: {}", err)),
+                };
+
+                let now = SystemTime::now();
+                let mut builder = ApiTokenBuilder::new()
+                    .with_name(payload.name)
+                    .with_owner(owner)
+                    .with_created_at(now)
+                    .with_roles(payload.roles);
+                if let Some(expires_in) = payload.expires_in {
+                    match now.checked_add(Duration::from_secs(expires_in)) {
+                        Some(expires_at) => builder = builder.with_expires_at(expires_at),
+                        None => return bad_request("Invalid expires_in"),
+                    }
+                }
+
+                let token = generate_token();
+                let api_token = match builder.with_token_hash(hash_token(&token)).build() {
+                    Ok(api_token) => api_token,
+                    Err(err) => return bad_request(&format!("Invalid token payload: {}", err)),
+                };
+
+                Box::new(
+                    web::block(move || {
+                        // A client can't grant a token more than it is allowed to do itself
+                        let ungranted_roles = get_ungranted_roles(
+                            api_token.owner(),
+                            api_token.roles(),
+                            &*api_token_store,
+                            #[cfg(feature = "authorization-handler-rbac")]
+                            role_based_authorization_store.as_deref(),
+                        )?;
+                        if !ungranted_roles.is_empty() {
+                            return Ok(Err(ungranted_roles));
+                        }
+
+                        api_token_store
+                            .add_token(api_token.clone())
+                            .map_err(|err| err.to_string())?;
+                        Ok(Ok(api_token))
+                    })
+                    .then(move |res: Result<_, BlockingError<String>>| {
+                        match res {
+                            Ok(Ok(api_token)) => Ok(HttpResponse::Ok().json(json!({
+                                "data": CreateApiTokenResponse {
+                                    token: &token,
+                                    details: ApiTokenResponse::from(&api_token),
+                                },
+                            }))),
+                            Ok(Err(ungranted_roles)) => Ok(HttpResponse::Forbidden().json(
+                                ErrorResponse::forbidden(&format!(
+                                    "Unable to grant roles that the client does not hold: {}",
+                                    ungranted_roles.join(", ")
+                                )),
+                            )),
+                            Err(err) => {
+                                error!("Unable to create API token: {}", err);
+                                Ok(HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error()))
+                            }
+                        }
+                    }),
+                ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+            }),
+    )
+}
+
+fn revoke_token(
+    req: HttpRequest,
+    api_token_store: Box<dyn ApiTokenStore>,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        Box<dyn RoleBasedAuthorizationStore>,
+    >,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    // Only the client's own tokens may be revoked, so it must be known
+    let identity = match req.extensions().get::<Identity>() {
+        Some(identity) => identity.clone(),
+        None => return unauthorized(),
+    };
+    let token_id = req.match_info().get("token_id").unwrap_or("").to_string();
+
+    Box::new(
+        web::block(move || {
+            revoke_visible_token(
+                &identity,
+                &token_id,
+                &*api_token_store,
+                #[cfg(feature = "authorization-handler-rbac")]
+                role_based_authorization_store.as_deref(),
+            )
+        })
+        .then(|res: Result<_, BlockingError<String>>| match res {
+            Ok(true) => Ok(HttpResponse::Ok().finish()),
+            Ok(false) => {
+                Ok(HttpResponse::NotFound().json(ErrorResponse::not_found("Token not found")))
+            }
+            Err(err) => {
+                error!("Unable to revoke API token: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
+    )
+}
+
+/// Returns the given roles that the client with the given identity does not hold, and therefore
+/// may not grant to a token. A client with the admin role may grant any role.
+fn get_ungranted_roles(
+    identity: &Identity,
+    roles: &[String],
+    api_token_store: &dyn ApiTokenStore,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        &dyn RoleBasedAuthorizationStore,
+    >,
+) -> Result<Vec<String>, String> {
+    if roles.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let held_roles = get_held_roles(
+        identity,
+        api_token_store,
+        #[cfg(feature = "authorization-handler-rbac")]
+        role_based_authorization_store,
+    )
+    .map_err(|err| err.to_string())?;
+
+    #[cfg(feature = "authorization-handler-rbac")]
+    if held_roles.iter().any(|role| role == ADMIN_ROLE_ID) {
+        return Ok(vec![]);
+    }
+
+    Ok(roles
+        .iter()
+        .filter(|role| !held_roles.contains(role))
+        .cloned()
+        .collect())
+}
+
+/// Returns the tokens that the client with the given identity may see: the tokens it owns, or
+/// every token if it has the admin role.
+fn list_visible_tokens(
+    identity: &Identity,
+    api_token_store: &dyn ApiTokenStore,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        &dyn RoleBasedAuthorizationStore,
+    >,
+) -> Result<Vec<ApiToken>, String> {
+    let is_admin = is_admin(
+        identity,
+        api_token_store,
+        #[cfg(feature = "authorization-handler-rbac")]
+        role_based_authorization_store,
+    )?;
+
+    Ok(api_token_store
+        .list_tokens()
+        .map_err(|err| err.to_string())?
+        .filter(|token| is_admin || token.owner() == identity)
+        .collect())
+}
+
+/// Revokes the token with the given ID if the client with the given identity may see it, as
+/// determined by `list_visible_tokens`. Returns whether the token was revoked; a token that the
+/// client may not see is treated as if it does not exist.
+fn revoke_visible_token(
+    identity: &Identity,
+    token_id: &str,
+    api_token_store: &dyn ApiTokenStore,
+    #[cfg(feature = "authorization-handler-rbac")] role_based_authorization_store: Option<
+        &dyn RoleBasedAuthorizationStore,
+    >,
+) -> Result<bool, String> {
+    let token = match api_token_store
+        .get_token(token_id)
+        .map_err(|err| err.to_string())?
+    {
+        Some(token) => token,
+        None => return Ok(false),
+    };
+
+    if token.owner() != identity
+        && !is_admin(
+            identity,
+            api_token_store,
+            #[cfg(feature = "authorization-handler-rbac")]
+            role_based_authorization_store,
+        )?
+    {
+        return Ok(false);
+    }
+
+    api_token_store
+        .remove_token(token_id)
+        .map_err(|err| err.to_string())?;
+    Ok(true)
+}
+
+/// Returns whether the client with the given identity has the admin role, which allows it to
+/// manage the tokens of other clients.
+#[cfg(feature = "authorization-handler-rbac")]
+fn is_admin(
+    identity: &Identity,
+    api_token_store: &dyn ApiTokenStore,
+    role_based_authorization_store: Option<&dyn RoleBasedAuthorizationStore>,
+) -> Result<bool, String> {
+    Ok(
+        get_held_roles(identity, api_token_store, role_based_authorization_store)
+            .map_err(|err| err.to_string())?
+            .iter()
+            .any(|role| role == ADMIN_ROLE_ID),
+    )
+}
+
+/// Without RBAC, no client has the admin role.
+#[cfg(not(feature = "authorization-handler-rbac"))]
+fn is_admin(_identity: &Identity, _api_token_store: &dyn ApiTokenStore) -> Result<bool, String> {
+    Ok(false)
+}
+
+fn unauthorized() -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::Unauthorized()
+            .json(ErrorResponse::unauthorized())
+            .into_future(),
+    )
+}
+
+fn bad_request(msg: &str) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::BadRequest()
+            .json(ErrorResponse::bad_request(msg))
+            .into_future(),
+    )
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::actix_web_1::{RestApiBuilder, RestApiShutdownHandle};
+    #[cfg(feature = "authorization-handler-rbac")]
+    use crate::rest_api::auth::authorization::rbac::store::{
+        AssignmentBuilder, DieselRoleBasedAuthorizationStore, Identity as RBACIdentity, RoleBuilder,
+    };
+    use crate::rest_api::auth::token::store::diesel::DieselApiTokenStore;
+
+    /// Verifies that a client only sees and revokes its own tokens, that tokens are listed without
+    /// the hash of the token, and that the token endpoints require an authenticated client.
+    ///
+    /// 1. Add a token for each of two users
+    /// 2. Verify that only the first user's token is listed for the first user, with the expected
+    ///    fields
+    /// 3. Verify that the first user can't revoke the second user's token
+    /// 4. Revoke the first user's token and verify it is no longer in the store, and that revoking
+    ///    it again is treated as not found
+    /// 5. Verify that listing, revoking and creating tokens without an authenticated client is
+    ///    rejected, since the insecure REST API does not identify clients
+    #[test]
+    fn list_and_revoke_tokens() {
+        let store = create_store();
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        for (i, (name, owner)) in [("deploy", "user-1"), ("monitor", "user-2")]
+            .iter()
+            .enumerate()
+        {
+            store
+                .add_token(
+                    ApiTokenBuilder::new()
+                        .with_id(format!("token-{}", i))
+                        .with_name(name.to_string())
+                        .with_owner(Identity::User(owner.to_string()))
+                        .with_token_hash(hash_token(name))
+                        .with_created_at(created_at + Duration::from_secs(60 * i as u64))
+                        .with_roles(vec!["circuit-admin".into()])
+                        .build()
+                        .expect("Unable to build token"),
+                )
+                .expect("Unable to add token");
+        }
+
+        let user = Identity::User("user-1".into());
+        let tokens = list_visible_tokens(
+            &user,
+            &*store,
+            #[cfg(feature = "authorization-handler-rbac")]
+            None,
+        )
+        .expect("Unable to list tokens");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            serde_json::to_value(ApiTokenResponse::from(&tokens[0]))
+                .expect("Unable to serialize token"),
+            json!({
+                "token_id": "token-0",
+                "name": "deploy",
+                "owner": { "identity_type": "user", "identity": "user-1" },
+                "created_at": 1_650_000_000,
+                "roles": ["circuit-admin"],
+            })
+        );
+
+        assert_eq!(
+            revoke_visible_token(
+                &user,
+                "token-1",
+                &*store,
+                #[cfg(feature = "authorization-handler-rbac")]
+                None,
+            ),
+            Ok(false)
+        );
+        assert!(store
+            .get_token("token-1")
+            .expect("Unable to get token")
+            .is_some());
+
+        assert_eq!(
+            revoke_visible_token(
+                &user,
+                "token-0",
+                &*store,
+                #[cfg(feature = "authorization-handler-rbac")]
+                None,
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            store.get_token("token-0").expect("Unable to get token"),
+            None
+        );
+        assert_eq!(
+            revoke_visible_token(
+                &user,
+                "token-0",
+                &*store,
+                #[cfg(feature = "authorization-handler-rbac")]
+                None,
+            ),
+            Ok(false)
+        );
+
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_tokens_resource(
+                store.clone(),
+                #[cfg(feature = "authorization-handler-rbac")]
+                None,
+            ),
+            make_token_resource(
+                store.clone(),
+                #[cfg(feature = "authorization-handler-rbac")]
+                None,
+            ),
+        ]);
+
+        let url = Url::parse(&format!("http://{}/authorization/tokens", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .get(url.clone())
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let token_url = Url::parse(&format!("http://{}/authorization/tokens/token-1", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .delete(token_url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let resp = Client::new()
+            .post(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&json!({ "name": "ci" }))
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Verifies that an admin sees and revokes the tokens of every client.
+    ///
+    /// 1. Assign the `admin` role to an admin user and add a token for another user
+    /// 2. Verify that the token is listed for the admin user
+    /// 3. Verify that the admin user can revoke the token
+    #[cfg(feature = "authorization-handler-rbac")]
+    #[test]
+    fn admin_can_manage_all_tokens() {
+        let pool = create_connection_pool_and_migrate();
+        let api_token_store = DieselApiTokenStore::new(pool.clone());
+        let rbac_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        rbac_store
+            .add_assignment(
+                AssignmentBuilder::new()
+                    .with_identity(RBACIdentity::User("admin-1".into()))
+                    .with_roles(vec![ADMIN_ROLE_ID.into()])
+                    .build()
+                    .expect("Unable to build assignment"),
+            )
+            .expect("Unable to add assignment");
+        let token = ApiTokenBuilder::new()
+            .with_name("deploy".into())
+            .with_owner(Identity::User("user-1".into()))
+            .with_token_hash(hash_token("deploy"))
+            .build()
+            .expect("Unable to build token");
+        api_token_store
+            .add_token(token.clone())
+            .expect("Unable to add token");
+
+        let admin = Identity::User("admin-1".into());
+        assert_eq!(
+            list_visible_tokens(&admin, &api_token_store, Some(&rbac_store)),
+            Ok(vec![token.clone()])
+        );
+        assert_eq!(
+            revoke_visible_token(&admin, token.id(), &api_token_store, Some(&rbac_store)),
+            Ok(true)
+        );
+        assert_eq!(
+            api_token_store
+                .get_token(token.id())
+                .expect("Unable to get token"),
+            None
+        );
+    }
+
+    /// Verifies that a client can only grant a token the roles it holds, unless it is an admin.
+    ///
+    /// 1. Add a `circuit-admin` role, assign it to a non-admin user and assign the `admin` role
+    ///    to an admin user
+    /// 2. Verify that the non-admin user can grant the `circuit-admin` role, but not the `admin`
+    ///    role or a role it was not assigned
+    /// 3. Verify that the admin user can grant any role
+    /// 4. Verify that a client identified by a token with the `circuit-admin` role can't grant the
+    ///    `admin` role
+    /// 5. Verify that no roles are held without an RBAC store
+    #[cfg(feature = "authorization-handler-rbac")]
+    #[test]
+    fn non_admin_owner_cannot_grant_admin_role() {
+        let pool = create_connection_pool_and_migrate();
+        let api_token_store = DieselApiTokenStore::new(pool.clone());
+        let rbac_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        rbac_store
+            .add_role(
+                RoleBuilder::new()
+                    .with_id("circuit-admin".into())
+                    .with_display_name("Circuit admin".into())
+                    .with_permissions(vec!["circuit.read".into(), "circuit.write".into()])
+                    .build()
+                    .expect("Unable to build role"),
+            )
+            .expect("Unable to add role");
+        for (user, role) in [("user-1", "circuit-admin"), ("admin-1", ADMIN_ROLE_ID)].iter() {
+            rbac_store
+                .add_assignment(
+                    AssignmentBuilder::new()
+                        .with_identity(RBACIdentity::User(user.to_string()))
+                        .with_roles(vec![role.to_string()])
+                        .build()
+                        .expect("Unable to build assignment"),
+                )
+                .expect("Unable to add assignment");
+        }
+
+        let non_admin = Identity::User("user-1".into());
+        assert_eq!(
+            get_ungranted_roles(
+                &non_admin,
+                &["circuit-admin".into()],
+                &api_token_store,
+                Some(&rbac_store)
+            ),
+            Ok(vec![])
+        );
+        assert_eq!(
+            get_ungranted_roles(
+                &non_admin,
+                &["circuit-admin".into(), ADMIN_ROLE_ID.into(), "other".into()],
+                &api_token_store,
+                Some(&rbac_store)
+            ),
+            Ok(vec![ADMIN_ROLE_ID.to_string(), "other".to_string()])
+        );
+
+        assert_eq!(
+            get_ungranted_roles(
+                &Identity::User("admin-1".into()),
+                &[ADMIN_ROLE_ID.into(), "other".into()],
+                &api_token_store,
+                Some(&rbac_store)
+            ),
+            Ok(vec![])
+        );
+
+        let token = ApiTokenBuilder::new()
+            .with_name("deploy".into())
+            .with_owner(non_admin.clone())
+            .with_token_hash(hash_token("deploy"))
+            .with_created_at(SystemTime::now())
+            .with_roles(vec!["circuit-admin".into()])
+            .build()
+            .expect("Unable to build token");
+        api_token_store
+            .add_token(token.clone())
+            .expect("Unable to add token");
+        assert_eq!(
+            get_ungranted_roles(
+                &token.identity(),
+                &[ADMIN_ROLE_ID.into()],
+                &api_token_store,
+                Some(&rbac_store)
+            ),
+            Ok(vec![ADMIN_ROLE_ID.to_string()])
+        );
+
+        assert_eq!(
+            get_ungranted_roles(
+                &non_admin,
+                &["circuit-admin".into()],
+                &api_token_store,
+                None
+            ),
+            Ok(vec!["circuit-admin".to_string()])
+        );
+    }
+
+    fn create_store() -> Box<dyn ApiTokenStore> {
+        Box::new(DieselApiTokenStore::new(
+            create_connection_pool_and_migrate(),
+        ))
+    }
+
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = crate::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .build_insecure()
+            .expect("Failed to build REST API")
+            .run_insecure();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+}
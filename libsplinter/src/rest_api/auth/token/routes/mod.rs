@@ -0,0 +1,96 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API endpoints for API tokens
+
+mod actix;
+mod resources;
+
+use crate::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization-handler-rbac")]
+use crate::rest_api::auth::authorization::rbac::store::RoleBasedAuthorizationStore;
+use crate::rest_api::auth::authorization::Permission;
+
+use super::store::ApiTokenStore;
+
+const API_TOKENS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "authorization.tokens.read",
+    permission_display_name: "API tokens read",
+    permission_description: "Allows the client to list API tokens",
+};
+
+const API_TOKENS_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "authorization.tokens.write",
+    permission_display_name: "API tokens write",
+    permission_description: "Allows the client to create and revoke API tokens",
+};
+
+/// REST Resource Provider for API tokens
+///
+/// Provides the following endpoints as REST API resources:
+///
+/// * `GET /authorization/tokens` - List the API tokens of the client making the request
+/// * `POST /authorization/tokens` - Create a new API token for the client making the request
+/// * `DELETE /authorization/tokens/{token_id}` - Revoke an API token of the client making the
+///   request
+///
+/// A client may only grant a new token the roles that it holds itself, and may only list and
+/// revoke its own tokens, unless it has the admin role. A client that was identified by an API
+/// token holds the roles of that token that the token's owner still holds; other clients hold the
+/// roles assigned to them in the RBAC store, so tokens can only be granted roles by such clients if
+/// an RBAC store has been provided.
+pub struct ApiTokenResourceProvider {
+    api_token_store: Box<dyn ApiTokenStore>,
+    #[cfg(feature = "authorization-handler-rbac")]
+    role_based_authorization_store: Option<Box<dyn RoleBasedAuthorizationStore>>,
+}
+
+impl ApiTokenResourceProvider {
+    /// Constructs a new resource provider with the given store.
+    pub fn new(api_token_store: Box<dyn ApiTokenStore>) -> Self {
+        Self {
+            api_token_store,
+            #[cfg(feature = "authorization-handler-rbac")]
+            role_based_authorization_store: None,
+        }
+    }
+
+    /// Sets the RBAC store that is used to look up the roles assigned to the client creating,
+    /// listing or revoking tokens.
+    #[cfg(feature = "authorization-handler-rbac")]
+    pub fn with_role_based_authorization_store(
+        mut self,
+        role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+    ) -> Self {
+        self.role_based_authorization_store = Some(role_based_authorization_store);
+        self
+    }
+}
+
+impl RestResourceProvider for ApiTokenResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            actix::make_tokens_resource(
+                self.api_token_store.clone(),
+                #[cfg(feature = "authorization-handler-rbac")]
+                self.role_based_authorization_store.clone(),
+            ),
+            actix::make_token_resource(
+                self.api_token_store.clone(),
+                #[cfg(feature = "authorization-handler-rbac")]
+                self.role_based_authorization_store.clone(),
+            ),
+        ]
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides resources for the API token REST API endpoints
+
+use std::time::SystemTime;
+
+use crate::rest_api::{
+    auth::{identity::Identity, token::ApiToken},
+    paging::{Paging, DEFAULT_LIMIT},
+};
+
+/// The query parameters of `GET /authorization/tokens`
+#[derive(Deserialize)]
+pub struct ListApiTokensQuery {
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub offset: usize,
+}
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+/// The payload of `POST /authorization/tokens`
+#[derive(Deserialize)]
+pub struct CreateApiTokenPayload {
+    pub name: String,
+    /// The number of seconds after which the token expires; the token never expires if not set
+    pub expires_in: Option<u64>,
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListApiTokensResponse<'a> {
+    pub data: Vec<ApiTokenResponse<'a>>,
+    pub paging: Paging,
+}
+
+/// The response to `POST /authorization/tokens`, which is the only time the token itself is
+/// returned
+#[derive(Debug, Serialize)]
+pub struct CreateApiTokenResponse<'a> {
+    pub token: &'a str,
+    #[serde(flatten)]
+    pub details: ApiTokenResponse<'a>,
+}
+
+/// An API token, without the token itself; times are in seconds since the Unix epoch
+#[derive(Debug, Serialize)]
+pub struct ApiTokenResponse<'a> {
+    pub token_id: &'a str,
+    pub name: &'a str,
+    pub owner: IdentityResponse<'a>,
+    pub created_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    pub roles: &'a [String],
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "identity_type", content = "identity")]
+#[serde(rename_all = "lowercase")]
+pub enum IdentityResponse<'a> {
+    Custom(&'a str),
+    Key(&'a str),
+    User(&'a str),
+}
+
+impl<'a> From<&'a ApiToken> for ApiTokenResponse<'a> {
+    fn from(token: &'a ApiToken) -> Self {
+        Self {
+            token_id: token.id(),
+            name: token.name(),
+            owner: IdentityResponse::from(token.owner()),
+            created_at: to_seconds(token.created_at()),
+            expires_at: token.expires_at().map(to_seconds),
+            roles: token.roles(),
+        }
+    }
+}
+
+impl<'a> From<&'a Identity> for IdentityResponse<'a> {
+    fn from(identity: &'a Identity) -> Self {
+        match identity {
+            Identity::Custom(custom) => IdentityResponse::Custom(custom),
+            Identity::Key(key) => IdentityResponse::Key(key),
+            Identity::User(user) => IdentityResponse::User(user),
+        }
+    }
+}
+
+fn to_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,260 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database backed [ApiTokenStore](super::ApiTokenStore) implementation, powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::rest_api::auth::token::ApiToken;
+use crate::store::pool::ConnectionPool;
+
+use super::{ApiTokenStore, ApiTokenStoreError};
+
+use operations::add_token::ApiTokenStoreAddTokenOperation as _;
+use operations::get_token::ApiTokenStoreGetTokenOperation as _;
+use operations::list_tokens::ApiTokenStoreListTokensOperation as _;
+use operations::remove_token::ApiTokenStoreRemoveTokenOperation as _;
+use operations::ApiTokenStoreOperations;
+
+/// A database-backed ApiTokenStore, powered by [`Diesel`](https://crates.io/crates/diesel).
+pub struct DieselApiTokenStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselApiTokenStore<C> {
+    /// Creates a new `DieselApiTokenStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselApiTokenStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselApiTokenStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselApiTokenStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ApiTokenStore for DieselApiTokenStore<diesel::pg::PgConnection> {
+    fn add_token(&self, token: ApiToken) -> Result<(), ApiTokenStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ApiTokenStoreOperations::new(conn).add_token(token))
+    }
+
+    fn get_token(&self, token_id: &str) -> Result<Option<ApiToken>, ApiTokenStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ApiTokenStoreOperations::new(conn).get_token(token_id))
+    }
+
+    fn get_token_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, ApiTokenStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ApiTokenStoreOperations::new(conn).get_token_by_hash(token_hash))
+    }
+
+    fn list_tokens(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = ApiToken>>, ApiTokenStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ApiTokenStoreOperations::new(conn).list_tokens())
+    }
+
+    fn remove_token(&self, token_id: &str) -> Result<(), ApiTokenStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ApiTokenStoreOperations::new(conn).remove_token(token_id))
+    }
+
+    fn clone_box(&self) -> Box<dyn ApiTokenStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ApiTokenStore for DieselApiTokenStore<diesel::sqlite::SqliteConnection> {
+    fn add_token(&self, token: ApiToken) -> Result<(), ApiTokenStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ApiTokenStoreOperations::new(conn).add_token(token))
+    }
+
+    fn get_token(&self, token_id: &str) -> Result<Option<ApiToken>, ApiTokenStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ApiTokenStoreOperations::new(conn).get_token(token_id))
+    }
+
+    fn get_token_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, ApiTokenStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ApiTokenStoreOperations::new(conn).get_token_by_hash(token_hash))
+    }
+
+    fn list_tokens(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = ApiToken>>, ApiTokenStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ApiTokenStoreOperations::new(conn).list_tokens())
+    }
+
+    fn remove_token(&self, token_id: &str) -> Result<(), ApiTokenStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ApiTokenStoreOperations::new(conn).remove_token(token_id))
+    }
+
+    fn clone_box(&self) -> Box<dyn ApiTokenStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, SystemTime};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::auth::identity::Identity;
+    use crate::rest_api::auth::token::{hash_token, ApiTokenBuilder};
+
+    /// Verify that tokens can be added, fetched by ID or hash, listed and removed.
+    ///
+    /// 1. Add a token with every field, then a token without an expiration time or roles one
+    ///    minute later
+    /// 2. Validate each token can be fetched by its ID and by its hash, and that unknown IDs and
+    ///    hashes return `None`
+    /// 3. Validate both tokens are listed, oldest first
+    /// 4. Remove the first token and validate it can no longer be fetched and is no longer listed
+    #[test]
+    fn test_add_get_list_and_remove_tokens() {
+        let store = DieselApiTokenStore::new(create_connection_pool_and_migrate());
+
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let deploy = ApiTokenBuilder::new()
+            .with_id("token-1".into())
+            .with_name("deploy".into())
+            .with_owner(Identity::User("user-1".into()))
+            .with_token_hash(hash_token("secret-1"))
+            .with_created_at(created_at)
+            .with_expires_at(created_at + Duration::from_secs(3600))
+            .with_roles(vec!["circuit-admin".into(), "registry-reader".into()])
+            .build()
+            .expect("Unable to build token");
+        let monitor = ApiTokenBuilder::new()
+            .with_id("token-2".into())
+            .with_name("monitor".into())
+            .with_owner(Identity::Key("abcd".into()))
+            .with_token_hash(hash_token("secret-2"))
+            .with_created_at(created_at + Duration::from_secs(60))
+            .build()
+            .expect("Unable to build token");
+
+        store
+            .add_token(deploy.clone())
+            .expect("Unable to add token");
+        store
+            .add_token(monitor.clone())
+            .expect("Unable to add token");
+
+        assert_eq!(
+            store.get_token("token-1").expect("Unable to get token"),
+            Some(deploy.clone())
+        );
+        assert_eq!(
+            store
+                .get_token_by_hash(&hash_token("secret-2"))
+                .expect("Unable to get token"),
+            Some(monitor.clone())
+        );
+        assert_eq!(
+            store.get_token("token-3").expect("Unable to get token"),
+            None
+        );
+        assert_eq!(
+            store
+                .get_token_by_hash(&hash_token("secret-3"))
+                .expect("Unable to get token"),
+            None
+        );
+
+        assert_eq!(
+            store
+                .list_tokens()
+                .expect("Unable to list tokens")
+                .collect::<Vec<_>>(),
+            vec![deploy, monitor.clone()]
+        );
+
+        store
+            .remove_token("token-1")
+            .expect("Unable to remove token");
+        assert_eq!(
+            store
+                .get_token_by_hash(&hash_token("secret-1"))
+                .expect("Unable to get token"),
+            None
+        );
+        assert_eq!(
+            store
+                .list_tokens()
+                .expect("Unable to list tokens")
+                .collect::<Vec<_>>(),
+            vec![monitor]
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::rest_api::auth::identity::Identity;
+use crate::rest_api::auth::token::{store::ApiTokenStoreError, ApiToken, ApiTokenBuilder};
+
+use super::schema::{rest_api_token_roles, rest_api_tokens};
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "rest_api_tokens"]
+#[primary_key(id)]
+pub struct ApiTokenModel {
+    pub id: String,
+    pub name: String,
+    pub owner: String,
+    pub owner_type: String,
+    pub token_hash: String,
+    // The time the token was created, in seconds since the Unix epoch
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "rest_api_token_roles"]
+#[belongs_to(ApiTokenModel, foreign_key = "token_id")]
+#[primary_key(token_id, role_id)]
+pub struct ApiTokenRoleModel {
+    pub token_id: String,
+    pub role_id: String,
+}
+
+impl TryFrom<&ApiToken> for ApiTokenModel {
+    type Error = ApiTokenStoreError;
+
+    fn try_from(token: &ApiToken) -> Result<Self, Self::Error> {
+        let (owner, owner_type) = match token.owner() {
+            Identity::Custom(custom) => (custom.clone(), "custom"),
+            Identity::Key(key) => (key.clone(), "key"),
+            Identity::User(user) => (user.clone(), "user"),
+        };
+
+        Ok(ApiTokenModel {
+            id: token.id().to_string(),
+            name: token.name().to_string(),
+            owner,
+            owner_type: owner_type.to_string(),
+            token_hash: token.token_hash().to_string(),
+            created_at: to_seconds(token.created_at())?,
+            expires_at: token.expires_at().map(to_seconds).transpose()?,
+        })
+    }
+}
+
+impl From<&ApiToken> for Vec<ApiTokenRoleModel> {
+    fn from(token: &ApiToken) -> Self {
+        token
+            .roles()
+            .iter()
+            .map(|role_id| ApiTokenRoleModel {
+                token_id: token.id().to_string(),
+                role_id: role_id.clone(),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<(ApiTokenModel, Vec<ApiTokenRoleModel>)> for ApiToken {
+    type Error = ApiTokenStoreError;
+
+    fn try_from(
+        (model, roles): (ApiTokenModel, Vec<ApiTokenRoleModel>),
+    ) -> Result<Self, Self::Error> {
+        let owner = match model.owner_type.as_str() {
+            "custom" => Identity::Custom(model.owner),
+            "key" => Identity::Key(model.owner),
+            "user" => Identity::User(model.owner),
+            owner_type => {
+                return Err(ApiTokenStoreError::Internal(InternalError::with_message(
+                    format!("Invalid API token owner type: {}", owner_type),
+                )))
+            }
+        };
+
+        let mut builder = ApiTokenBuilder::new()
+            .with_id(model.id)
+            .with_name(model.name)
+            .with_owner(owner)
+            .with_token_hash(model.token_hash)
+            .with_created_at(from_seconds(model.created_at))
+            .with_roles(roles.into_iter().map(|role| role.role_id).collect());
+
+        if let Some(expires_at) = model.expires_at {
+            builder = builder.with_expires_at(from_seconds(expires_at));
+        }
+
+        builder
+            .build()
+            .map_err(|err| ApiTokenStoreError::Internal(InternalError::from_source(Box::new(err))))
+    }
+}
+
+fn to_seconds(time: SystemTime) -> Result<i64, ApiTokenStoreError> {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            ApiTokenStoreError::Internal(InternalError::with_message(
+                "API token time is out of range".to_string(),
+            ))
+        })
+}
+
+fn from_seconds(seconds: i64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64)
+}
@@ -0,0 +1,80 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::rest_api::auth::token::{
+    store::{
+        diesel::{
+            models::{ApiTokenModel, ApiTokenRoleModel},
+            schema::{rest_api_token_roles, rest_api_tokens},
+        },
+        ApiTokenStoreError,
+    },
+    ApiToken,
+};
+
+use super::ApiTokenStoreOperations;
+
+pub(in crate::rest_api::auth::token::store::diesel) trait ApiTokenStoreAddTokenOperation {
+    fn add_token(&self, token: ApiToken) -> Result<(), ApiTokenStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ApiTokenStoreAddTokenOperation for ApiTokenStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_token(&self, token: ApiToken) -> Result<(), ApiTokenStoreError> {
+        let model = ApiTokenModel::try_from(&token)?;
+        let roles = Vec::<ApiTokenRoleModel>::from(&token);
+
+        self.conn.transaction::<_, ApiTokenStoreError, _>(|| {
+            insert_into(rest_api_tokens::table)
+                .values(model)
+                .execute(self.conn)?;
+
+            if !roles.is_empty() {
+                insert_into(rest_api_token_roles::table)
+                    .values(roles)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ApiTokenStoreAddTokenOperation
+    for ApiTokenStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_token(&self, token: ApiToken) -> Result<(), ApiTokenStoreError> {
+        let model = ApiTokenModel::try_from(&token)?;
+        let roles = Vec::<ApiTokenRoleModel>::from(&token);
+
+        self.conn.transaction::<_, ApiTokenStoreError, _>(|| {
+            insert_into(rest_api_tokens::table)
+                .values(model)
+                .execute(self.conn)?;
+
+            if !roles.is_empty() {
+                insert_into(rest_api_token_roles::table)
+                    .values(roles)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
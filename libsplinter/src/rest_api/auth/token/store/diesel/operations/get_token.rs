@@ -0,0 +1,77 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::token::{
+    store::{
+        diesel::{
+            models::{ApiTokenModel, ApiTokenRoleModel},
+            schema::{rest_api_token_roles, rest_api_tokens},
+        },
+        ApiTokenStoreError,
+    },
+    ApiToken,
+};
+
+use super::ApiTokenStoreOperations;
+
+pub(in crate::rest_api::auth::token::store::diesel) trait ApiTokenStoreGetTokenOperation {
+    fn get_token(&self, token_id: &str) -> Result<Option<ApiToken>, ApiTokenStoreError>;
+
+    fn get_token_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, ApiTokenStoreError>;
+}
+
+impl<'a, C> ApiTokenStoreGetTokenOperation for ApiTokenStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn get_token(&self, token_id: &str) -> Result<Option<ApiToken>, ApiTokenStoreError> {
+        self.conn.transaction(|| {
+            let token = rest_api_tokens::table
+                .find(token_id)
+                .first::<ApiTokenModel>(self.conn)
+                .optional()?;
+
+            token.map(|token| load_roles(self.conn, token)).transpose()
+        })
+    }
+
+    fn get_token_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, ApiTokenStoreError> {
+        self.conn.transaction(|| {
+            let token = rest_api_tokens::table
+                .filter(rest_api_tokens::token_hash.eq(token_hash))
+                .first::<ApiTokenModel>(self.conn)
+                .optional()?;
+
+            token.map(|token| load_roles(self.conn, token)).transpose()
+        })
+    }
+}
+
+fn load_roles<C>(conn: &C, token: ApiTokenModel) -> Result<ApiToken, ApiTokenStoreError>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    let roles = ApiTokenRoleModel::belonging_to(&token)
+        .order(rest_api_token_roles::role_id)
+        .load::<ApiTokenRoleModel>(conn)?;
+
+    ApiToken::try_from((token, roles))
+}
@@ -0,0 +1,68 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::token::{
+    store::{
+        diesel::{
+            models::{ApiTokenModel, ApiTokenRoleModel},
+            schema::{rest_api_token_roles, rest_api_tokens},
+        },
+        ApiTokenStoreError,
+    },
+    ApiToken,
+};
+
+use super::ApiTokenStoreOperations;
+
+pub(in crate::rest_api::auth::token::store::diesel) trait ApiTokenStoreListTokensOperation {
+    fn list_tokens(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = ApiToken>>, ApiTokenStoreError>;
+}
+
+impl<'a, C> ApiTokenStoreListTokensOperation for ApiTokenStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn list_tokens(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = ApiToken>>, ApiTokenStoreError> {
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = ApiToken>>, _, _>(|| {
+                let tokens = rest_api_tokens::table
+                    .order((rest_api_tokens::created_at, rest_api_tokens::id))
+                    .load::<ApiTokenModel>(self.conn)?;
+
+                let roles = ApiTokenRoleModel::belonging_to(&tokens)
+                    .order(rest_api_token_roles::role_id)
+                    .load::<ApiTokenRoleModel>(self.conn)?
+                    .grouped_by(&tokens);
+
+                Ok(Box::new(
+                    tokens
+                        .into_iter()
+                        .zip(roles)
+                        .map(ApiToken::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                        .into_iter(),
+                ))
+            })
+    }
+}
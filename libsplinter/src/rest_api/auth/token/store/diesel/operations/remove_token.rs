@@ -0,0 +1,44 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::rest_api::auth::token::store::{
+    diesel::schema::{rest_api_token_roles, rest_api_tokens},
+    ApiTokenStoreError,
+};
+
+use super::ApiTokenStoreOperations;
+
+pub(in crate::rest_api::auth::token::store::diesel) trait ApiTokenStoreRemoveTokenOperation {
+    fn remove_token(&self, token_id: &str) -> Result<(), ApiTokenStoreError>;
+}
+
+impl<'a, C> ApiTokenStoreRemoveTokenOperation for ApiTokenStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn remove_token(&self, token_id: &str) -> Result<(), ApiTokenStoreError> {
+        self.conn.transaction::<_, ApiTokenStoreError, _>(|| {
+            delete(rest_api_token_roles::table.filter(rest_api_token_roles::token_id.eq(token_id)))
+                .execute(self.conn)?;
+
+            delete(rest_api_tokens::table.filter(rest_api_tokens::id.eq(token_id)))
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
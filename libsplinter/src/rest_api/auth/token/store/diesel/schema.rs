@@ -0,0 +1,35 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+table! {
+    rest_api_tokens (id) {
+        id -> Text,
+        name -> Text,
+        owner -> Text,
+        owner_type -> Text,
+        token_hash -> Text,
+        created_at -> BigInt,
+        expires_at -> Nullable<BigInt>,
+    }
+}
+
+table! {
+    rest_api_token_roles (token_id, role_id) {
+        token_id -> Text,
+        role_id -> Text,
+    }
+}
+
+joinable!(rest_api_token_roles -> rest_api_tokens (token_id));
+allow_tables_to_appear_in_same_query!(rest_api_tokens, rest_api_token_roles);
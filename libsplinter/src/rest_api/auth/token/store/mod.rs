@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for API tokens
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+use super::ApiToken;
+
+pub use error::ApiTokenStoreError;
+
+/// Defines methods for creating, reading and revoking API tokens
+pub trait ApiTokenStore: Send + Sync {
+    /// Adds a new token
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The token to add
+    fn add_token(&self, token: ApiToken) -> Result<(), ApiTokenStoreError>;
+
+    /// Returns the token with the given ID, if it exists
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The ID of the token
+    fn get_token(&self, token_id: &str) -> Result<Option<ApiToken>, ApiTokenStoreError>;
+
+    /// Returns the token with the given hash, if it exists
+    ///
+    /// # Arguments
+    ///
+    /// * `token_hash` - The hash of the token; see [`hash_token`](super::hash_token)
+    fn get_token_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, ApiTokenStoreError>;
+
+    /// Returns all tokens, including expired tokens, from oldest to newest
+    fn list_tokens(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = ApiToken>>, ApiTokenStoreError>;
+
+    /// Removes the token with the given ID; once removed, the token can no longer be used
+    ///
+    /// # Arguments
+    ///
+    /// * `token_id` - The ID of the token
+    fn remove_token(&self, token_id: &str) -> Result<(), ApiTokenStoreError>;
+
+    fn clone_box(&self) -> Box<dyn ApiTokenStore>;
+}
+
+impl Clone for Box<dyn ApiTokenStore> {
+    fn clone(&self) -> Box<dyn ApiTokenStore> {
+        self.clone_box()
+    }
+}
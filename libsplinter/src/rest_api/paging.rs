@@ -13,6 +13,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::error::InvalidArgumentError;
+use crate::hex::{parse_hex, to_hex};
+
 pub const DEFAULT_LIMIT: usize = 100;
 pub const DEFAULT_OFFSET: usize = 0;
 
@@ -37,14 +40,7 @@ pub fn get_response_paging_info(
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
     let offset = offset.unwrap_or(DEFAULT_OFFSET);
 
-    let base_link = {
-        // if the link does not already contain ? add it to the end
-        if !link.contains('?') {
-            format!("{}?limit={}&", link, limit)
-        } else {
-            format!("{}limit={}&", link, limit)
-        }
-    };
+    let base_link = get_base_link(link, limit);
 
     let current_link = format!("{}offset={}", base_link, offset);
 
@@ -81,6 +77,132 @@ pub fn get_response_paging_info(
     }
 }
 
+/// Creates the paging information for a page of results that was fetched using a cursor.
+///
+/// The links that do not depend on the current position (`first`, `prev` and `last`) are the same
+/// offset links returned by `get_response_paging_info`, so the response has the same shape for
+/// clients that are unaware of cursors. `current` and `next` use the given cursors instead, when
+/// they are provided; without a next cursor, this is the last page, so `next` refers to the
+/// current page.
+pub fn get_response_cursor_paging_info(
+    limit: usize,
+    cursor: Option<&Cursor>,
+    next_cursor: Option<&Cursor>,
+    link: &str,
+    query_count: usize,
+) -> Paging {
+    let offset = cursor.map(Cursor::offset).unwrap_or(DEFAULT_OFFSET);
+    let mut paging = get_response_paging_info(Some(limit), Some(offset), link, query_count);
+
+    let base_link = get_base_link(link, limit);
+    if let Some(cursor) = cursor {
+        paging.current = format!("{}cursor={}", base_link, cursor.encode());
+    }
+    paging.next = match next_cursor {
+        Some(next_cursor) => format!("{}cursor={}", base_link, next_cursor.encode()),
+        None => paging.current.clone(),
+    };
+
+    paging
+}
+
+fn get_base_link(link: &str, limit: usize) -> String {
+    // if the link does not already contain ? add it to the end
+    if !link.contains('?') {
+        format!("{}?limit={}&", link, limit)
+    } else {
+        format!("{}limit={}&", link, limit)
+    }
+}
+
+/// An opaque position in a paged list of items.
+///
+/// A cursor holds the ordering key of the last item on the previous page; the page it refers to
+/// starts with the item that follows that key, so items that are added or removed while a client
+/// is paging do not cause other items to be skipped or returned twice. The offset of the page is
+/// kept only so that the paging information can still report it; it is not used to decide whether
+/// there are more items, since it is out of date once items before the cursor are removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    offset: usize,
+    key: String,
+}
+
+impl Cursor {
+    pub fn new<K: Into<String>>(offset: usize, key: K) -> Self {
+        Cursor {
+            offset,
+            key: key.into(),
+        }
+    }
+
+    /// Returns the cursor for the page after the current one, if there is one.
+    ///
+    /// Whether there is a following page is determined by the caller, which should fetch one item
+    /// more than the limit after the current cursor's key; see [`Cursor::split_page`].
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The cursor used to fetch the current page, or `None` for the first page
+    /// * `limit` - The maximum number of items on a page
+    /// * `has_more` - Whether there are items after the current page
+    /// * `last_key` - The ordering key of the last item on the current page
+    pub fn following(
+        current: Option<&Cursor>,
+        limit: usize,
+        has_more: bool,
+        last_key: Option<&str>,
+    ) -> Option<Self> {
+        if !has_more {
+            return None;
+        }
+
+        let next_offset = current.map(Cursor::offset).unwrap_or(DEFAULT_OFFSET) + limit;
+        last_key.map(|key| Cursor::new(next_offset, key))
+    }
+
+    /// Splits the items fetched for a page, which should be at most one more than the limit, into
+    /// the items on the page and whether there are more items after it.
+    pub fn split_page<T>(mut items: Vec<T>, limit: usize) -> (Vec<T>, bool) {
+        let has_more = items.len() > limit;
+        items.truncate(limit);
+        (items, has_more)
+    }
+
+    /// Returns the offset of the page the cursor refers to.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the ordering key of the last item before the page the cursor refers to.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Encodes the cursor for use as the `cursor` query parameter.
+    pub fn encode(&self) -> String {
+        to_hex(format!("{}:{}", self.offset, self.key).as_bytes())
+    }
+
+    /// Decodes a cursor from the value of the `cursor` query parameter.
+    pub fn decode(value: &str) -> Result<Self, InvalidArgumentError> {
+        let invalid = || InvalidArgumentError::new("cursor", "cursor is not valid");
+
+        let decoded = parse_hex(value)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(invalid)?;
+        let mut parts = decoded.splitn(2, ':');
+        let offset = parts
+            .next()
+            .and_then(|offset| offset.parse::<usize>().ok())
+            .ok_or_else(invalid)?;
+        let key = parts.next().ok_or_else(invalid)?;
+
+        Ok(Cursor::new(offset, key))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,6 +260,85 @@ mod tests {
         assert_eq!(test_paging_response, generated_paging_response);
     }
 
+    /// Verify that a cursor survives being encoded and decoded, and that invalid values are
+    /// rejected.
+    #[test]
+    fn test_cursor_encode_decode() {
+        let cursor = Cursor::new(200, "abcde-01234:x");
+        assert_eq!(
+            Cursor::decode(&cursor.encode()).expect("invalid cursor"),
+            cursor
+        );
+
+        assert!(Cursor::decode("not hex").is_err());
+        assert!(Cursor::decode(&to_hex(b"200")).is_err());
+        assert!(Cursor::decode(&to_hex(b"abc:key")).is_err());
+    }
+
+    /// Verify that the next cursor is only returned when there are more items to page through,
+    /// regardless of the offset of the current cursor.
+    #[test]
+    fn test_following_cursor() {
+        assert_eq!(
+            Cursor::following(None, 100, true, Some("key")),
+            Some(Cursor::new(100, "key"))
+        );
+        assert_eq!(
+            Cursor::following(Some(&Cursor::new(100, "a")), 100, true, Some("b")),
+            Some(Cursor::new(200, "b"))
+        );
+        // the offset may be past the total if items before the cursor were removed
+        assert_eq!(
+            Cursor::following(Some(&Cursor::new(900, "a")), 100, true, Some("b")),
+            Some(Cursor::new(1000, "b"))
+        );
+        assert_eq!(
+            Cursor::following(Some(&Cursor::new(100, "a")), 100, false, Some("b")),
+            None
+        );
+        assert_eq!(Cursor::following(None, 100, true, None), None);
+    }
+
+    /// Verify that the extra item fetched for a page is removed and indicates there are more items.
+    #[test]
+    fn test_split_page() {
+        assert_eq!(Cursor::split_page(vec![1, 2, 3], 2), (vec![1, 2], true));
+        assert_eq!(Cursor::split_page(vec![1, 2], 2), (vec![1, 2], false));
+        assert_eq!(Cursor::split_page(Vec::<u8>::new(), 2), (vec![], false));
+    }
+
+    /// Verify that the cursor paging info matches the offset paging info, apart from the
+    /// `current` and `next` links.
+    #[test]
+    fn test_cursor_paging_response() {
+        let cursor = Cursor::new(100, "a");
+        let next_cursor = Cursor::new(200, "b");
+        let test_paging_response = get_response_cursor_paging_info(
+            DEFAULT_LIMIT,
+            Some(&cursor),
+            Some(&next_cursor),
+            TEST_LINK,
+            1000,
+        );
+
+        let mut generated_paging_response =
+            create_test_paging_response(100, DEFAULT_LIMIT, 200, 0, 900);
+        generated_paging_response.current =
+            format!("{}limit=100&cursor={}", TEST_LINK, cursor.encode());
+        generated_paging_response.next =
+            format!("{}limit=100&cursor={}", TEST_LINK, next_cursor.encode());
+        assert_eq!(test_paging_response, generated_paging_response);
+
+        // the first page has no cursor, and a page without a next cursor is the last page, so
+        // its next link refers to itself
+        let test_paging_response =
+            get_response_cursor_paging_info(DEFAULT_LIMIT, None, None, TEST_LINK, 1000);
+        let mut generated_paging_response =
+            create_test_paging_response(DEFAULT_OFFSET, DEFAULT_LIMIT, 100, 0, 900);
+        generated_paging_response.next = generated_paging_response.current.clone();
+        assert_eq!(test_paging_response, generated_paging_response);
+    }
+
     fn create_test_paging_response(
         offset: usize,
         limit: usize,
@@ -68,4 +68,18 @@ impl ErrorResponse {
             message: message.to_string(),
         }
     }
+
+    pub fn gone(message: &str) -> ErrorResponse {
+        ErrorResponse {
+            code: "410".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn payload_too_large(message: &str) -> ErrorResponse {
+        ErrorResponse {
+            code: "413".to_string(),
+            message: message.to_string(),
+        }
+    }
 }
@@ -42,11 +42,15 @@ impl SecretManager for AutoSecretManager {
     }
 }
 
-fn generate_random_secret() -> String {
+pub(super) fn generate_random_secret() -> String {
+    generate_random_string(SECRET_LENGTH)
+}
+
+pub(super) fn generate_random_string(length: usize) -> String {
     let mut rng = rand::thread_rng();
     std::iter::repeat(())
         .map(|()| rng.sample(Alphanumeric))
         .map(char::from)
-        .take(SECRET_LENGTH)
+        .take(length)
         .collect()
 }
@@ -0,0 +1,415 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use fs2::FileExt;
+
+use crate::error::{InternalError, InvalidStateError};
+
+use super::auto_secret_manager::generate_random_string;
+use super::key_ring::{generate_key, retain_keys};
+use super::{KeyRing, SecretManager, SecretManagerError, SigningKey, DEFAULT_KEY_RETENTION};
+
+/// A SecretManager that keeps a key ring in a file
+///
+/// The file is only readable by its owner. It is read again whenever it has been modified, so
+/// REST API instances that share the file, for example on a network file system, see a rotation
+/// made by any one of them. A rotation holds an exclusive lock on a `.lock` file next to the key
+/// ring file, so that concurrent rotations by several instances do not drop each other's keys.
+pub struct FileSecretManager {
+    path: PathBuf,
+    key_retention: Duration,
+    cache: Mutex<Option<CachedKeys>>,
+}
+
+struct CachedKeys {
+    // The file is replaced rather than modified in place, so a new inode also means a new file
+    inode: u64,
+    modified: SystemTime,
+    keys: Vec<SigningKey>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct YamlSigningKey {
+    key_id: String,
+    secret: String,
+    // The time the key was created, in seconds since the Unix epoch
+    created_at: u64,
+}
+
+impl FileSecretManager {
+    /// Creates a new `FileSecretManager`, creating the file with a new key if it does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the key ring file
+    pub fn new<P: Into<PathBuf>>(path: P) -> Result<Self, SecretManagerError> {
+        let manager = FileSecretManager {
+            path: path.into(),
+            key_retention: DEFAULT_KEY_RETENTION,
+            cache: Mutex::new(None),
+        };
+
+        if !manager.path.exists() {
+            manager
+                .create_file()
+                .map_err(|err| SecretManagerError::UpdateSecretError(Box::new(err)))?;
+        }
+
+        Ok(manager)
+    }
+
+    /// Sets how long a key is still accepted after it has been replaced by a rotation. This
+    /// should be at least as long as the duration of the tokens signed with the key.
+    pub fn with_key_retention(mut self, key_retention: Duration) -> Self {
+        self.key_retention = key_retention;
+        self
+    }
+
+    /// Returns the keys in the file, from newest to oldest, reading the file only if it has been
+    /// modified since it was last read.
+    fn load_keys(&self) -> Result<Vec<SigningKey>, InternalError> {
+        let (inode, modified) = fs::metadata(&self.path)
+            .and_then(|metadata| Ok((metadata.ino(), metadata.modified()?)))
+            .map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Unable to read key ring file {}", self.path.display()),
+                )
+            })?;
+
+        let mut cache = self.cache.lock().map_err(|_| {
+            InternalError::with_message("File secret manager's cache lock poisoned".to_string())
+        })?;
+
+        match &*cache {
+            Some(cached) if cached.inode == inode && cached.modified == modified => {
+                Ok(cached.keys.clone())
+            }
+            _ => {
+                let keys = read_keys(&self.path)?;
+                *cache = Some(CachedKeys {
+                    inode,
+                    modified,
+                    keys: keys.clone(),
+                });
+                Ok(keys)
+            }
+        }
+    }
+
+    /// Creates the file with a single new key. If the file is created by another process at the
+    /// same time, the other process' file is kept.
+    fn create_file(&self) -> Result<(), InternalError> {
+        let temp_path = self.write_temp_file(&[generate_key()])?;
+
+        // Linking fails if the file already exists, unlike renaming, which would replace it
+        let result = fs::hard_link(&temp_path, &self.path);
+        let _ = fs::remove_file(&temp_path);
+        match result {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => Ok(()),
+            Err(err) => Err(InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to create key ring file {}", self.path.display()),
+            )),
+        }
+    }
+
+    /// Takes an exclusive lock on the lock file next to the key ring file, waiting for any other
+    /// holder to release it. The lock is released when the returned file is dropped.
+    ///
+    /// A separate file is locked because the key ring file is replaced, rather than modified, when
+    /// it is written.
+    fn lock(&self) -> Result<File, InternalError> {
+        let mut lock_path = self.path.clone().into_os_string();
+        lock_path.push(".lock");
+        let lock_path = PathBuf::from(lock_path);
+
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .mode(0o600)
+            .open(&lock_path)
+            .and_then(|file| {
+                file.lock_exclusive()?;
+                Ok(file)
+            })
+            .map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Unable to lock key ring file {}", lock_path.display()),
+                )
+            })
+    }
+
+    /// Replaces the contents of the file with the given keys
+    fn write_keys(&self, keys: &[SigningKey]) -> Result<(), InternalError> {
+        let temp_path = self.write_temp_file(keys)?;
+
+        // Renaming the temporary file replaces the file atomically, so a reader never sees a
+        // partially written file
+        fs::rename(&temp_path, &self.path).map_err(|err| {
+            let _ = fs::remove_file(&temp_path);
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to write key ring file {}", self.path.display()),
+            )
+        })
+    }
+
+    /// Writes the keys to a new temporary file next to the key ring file, returning the path of
+    /// the temporary file.
+    fn write_temp_file(&self, keys: &[SigningKey]) -> Result<PathBuf, InternalError> {
+        let yaml_keys = keys
+            .iter()
+            .map(YamlSigningKey::from)
+            .collect::<Vec<YamlSigningKey>>();
+        let output = serde_yaml::to_vec(&yaml_keys).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                "Unable to serialize key ring".to_string(),
+            )
+        })?;
+
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(format!(".{}.temp", generate_random_string(8)));
+        let temp_path = PathBuf::from(temp_path);
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&temp_path)
+            .and_then(|mut file| {
+                file.write_all(&output)?;
+                Ok(file)
+            })
+            .map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Unable to write key ring file {}", temp_path.display()),
+                )
+            })?;
+
+        file.sync_all().map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to write key ring file {}", temp_path.display()),
+            )
+        })?;
+
+        Ok(temp_path)
+    }
+}
+
+impl SecretManager for FileSecretManager {
+    fn secret(&self) -> Result<String, SecretManagerError> {
+        Ok(self.key_ring()?.current().secret().to_string())
+    }
+
+    /// Adds a new key to the file and removes the keys that are no longer accepted
+    fn update_secret(&mut self) -> Result<(), SecretManagerError> {
+        // The lock is held until the new file has replaced the old one, so that no other rotation
+        // reads the keys in between
+        let _lock = self
+            .lock()
+            .map_err(|err| SecretManagerError::UpdateSecretError(Box::new(err)))?;
+
+        let mut keys = self
+            .load_keys()
+            .map_err(|err| SecretManagerError::UpdateSecretError(Box::new(err)))?;
+        keys.insert(0, generate_key());
+
+        self.write_keys(&retain_keys(keys, self.key_retention, SystemTime::now()))
+            .map_err(|err| SecretManagerError::UpdateSecretError(Box::new(err)))
+    }
+
+    fn key_ring(&self) -> Result<KeyRing, SecretManagerError> {
+        let keys = self
+            .load_keys()
+            .map_err(|err| SecretManagerError::SecretError(Box::new(err)))?;
+        let mut keys = retain_keys(keys, self.key_retention, SystemTime::now()).into_iter();
+        let current = keys.next().ok_or_else(|| {
+            SecretManagerError::SecretError(Box::new(InvalidStateError::with_message(format!(
+                "Key ring file {} does not contain any keys",
+                self.path.display()
+            ))))
+        })?;
+
+        Ok(KeyRing::new(current, keys.collect()))
+    }
+}
+
+fn read_keys(path: &Path) -> Result<Vec<SigningKey>, InternalError> {
+    let file = File::open(path).map_err(|err| {
+        InternalError::from_source_with_message(
+            Box::new(err),
+            format!("Unable to open key ring file {}", path.display()),
+        )
+    })?;
+
+    let yaml_keys: Vec<YamlSigningKey> = serde_yaml::from_reader(file).map_err(|err| {
+        InternalError::from_source_with_message(
+            Box::new(err),
+            format!("Unable to parse key ring file {}", path.display()),
+        )
+    })?;
+
+    Ok(yaml_keys.into_iter().map(SigningKey::from).collect())
+}
+
+impl From<&SigningKey> for YamlSigningKey {
+    fn from(key: &SigningKey) -> Self {
+        YamlSigningKey {
+            key_id: key.key_id().to_string(),
+            secret: key.secret().to_string(),
+            created_at: key
+                .created_at()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|created_at| created_at.as_secs())
+                .unwrap_or(0),
+        }
+    }
+}
+
+impl From<YamlSigningKey> for SigningKey {
+    fn from(key: YamlSigningKey) -> Self {
+        SigningKey::new(
+            &key.key_id,
+            &key.secret,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(key.created_at),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::PermissionsExt;
+
+    use tempfile::TempDir;
+
+    /// Verify that a manager creates the key ring file if it does not exist, and that another
+    /// manager for the same file reads the existing key instead of replacing it.
+    #[test]
+    fn test_create_key_ring_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("biome_access_token_keys.yaml");
+
+        let manager_1 = FileSecretManager::new(&path).expect("Unable to create manager");
+        let key_ring = manager_1.key_ring().expect("Unable to get key ring");
+        assert_eq!(key_ring.keys().len(), 1);
+
+        let mode = fs::metadata(&path)
+            .expect("Unable to read metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let manager_2 = FileSecretManager::new(&path).expect("Unable to create manager");
+        assert_eq!(
+            manager_2.key_ring().expect("Unable to get key ring"),
+            key_ring
+        );
+
+        // Only the key ring file remains in the directory
+        assert_eq!(
+            fs::read_dir(temp_dir.path())
+                .expect("Unable to read dir")
+                .count(),
+            1
+        );
+    }
+
+    /// Verify that rotating the key keeps the previous key until the retention period has
+    /// passed, and that other managers for the same file see the rotation.
+    ///
+    /// 1. Create two managers for the same file
+    /// 2. Rotate the key with the first manager and validate the second manager uses the new key
+    ///    and still accepts the previous key
+    /// 3. Rotate the key with a manager that does not retain replaced keys and validate only the
+    ///    new key is left in the file
+    #[test]
+    fn test_key_rotation() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("keys.yaml");
+
+        let mut manager_1 = FileSecretManager::new(&path).expect("Unable to create manager");
+        let manager_2 = FileSecretManager::new(&path).expect("Unable to create manager");
+        let original = manager_2.key_ring().expect("Unable to get key ring");
+
+        manager_1.update_secret().expect("Unable to update secret");
+        let rotated = manager_2.key_ring().expect("Unable to get key ring");
+        assert_eq!(rotated.keys().len(), 2);
+        assert_ne!(rotated.current(), original.current());
+        assert_eq!(
+            rotated.get(original.current().key_id()),
+            Some(original.current())
+        );
+        assert_eq!(
+            manager_2.secret().expect("Unable to get secret"),
+            rotated.current().secret()
+        );
+
+        let mut manager_3 = FileSecretManager::new(&path)
+            .expect("Unable to create manager")
+            .with_key_retention(Duration::from_secs(0));
+        manager_3.update_secret().expect("Unable to update secret");
+        let key_ring = manager_3.key_ring().expect("Unable to get key ring");
+        assert_eq!(key_ring.keys().len(), 1);
+        assert_eq!(
+            read_keys(&path).expect("Unable to read keys"),
+            key_ring.keys().to_vec()
+        );
+    }
+
+    /// Verify that concurrent rotations by managers for the same file keep every new key.
+    ///
+    /// 1. Create a manager for the file
+    /// 2. Rotate the key several times from each of several threads, each with its own manager
+    /// 3. Validate that the file contains the original key and every key added by a rotation
+    #[test]
+    fn test_concurrent_key_rotation() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("keys.yaml");
+
+        FileSecretManager::new(&path).expect("Unable to create manager");
+
+        let threads = (0..4)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut manager =
+                        FileSecretManager::new(&path).expect("Unable to create manager");
+                    for _ in 0..5 {
+                        manager.update_secret().expect("Unable to update secret");
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().expect("Rotation thread panicked");
+        }
+
+        assert_eq!(read_keys(&path).expect("Unable to read keys").len(), 21);
+    }
+}
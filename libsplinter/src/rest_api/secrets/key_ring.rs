@@ -0,0 +1,239 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A set of identified secrets used to sign and verify tokens
+
+#[cfg(feature = "rest-api-persistent-secrets")]
+use std::time::Duration;
+use std::time::SystemTime;
+
+use jsonwebtoken::decode_header;
+
+#[cfg(feature = "rest-api-persistent-secrets")]
+use super::auto_secret_manager::{generate_random_secret, generate_random_string};
+
+/// The key ID of the only key in the key ring of a secret manager that does not support key
+/// rotation
+pub const DEFAULT_KEY_ID: &str = "default";
+
+#[cfg(feature = "rest-api-persistent-secrets")]
+const KEY_ID_LENGTH: usize = 16;
+
+/// A secret, identified by a key ID, that is used to sign and verify tokens
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningKey {
+    key_id: String,
+    secret: String,
+    created_at: SystemTime,
+}
+
+impl SigningKey {
+    /// Creates a new `SigningKey`
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id` - The ID of the key; this is added to the header of the tokens signed with it
+    /// * `secret` - The secret used to sign and verify tokens
+    /// * `created_at` - The time the key was created
+    pub fn new(key_id: &str, secret: &str, created_at: SystemTime) -> Self {
+        SigningKey {
+            key_id: key_id.to_string(),
+            secret: secret.to_string(),
+            created_at,
+        }
+    }
+
+    /// Returns the ID of the key
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Returns the secret
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    /// Returns the time the key was created
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+}
+
+/// The keys of a secret manager
+///
+/// The current key is used to sign new tokens. The key ring also contains the keys that were
+/// replaced by a rotation but are still accepted, so that tokens signed before the rotation remain
+/// valid until they expire.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyRing {
+    // The current key is always first, followed by the previous keys from newest to oldest
+    keys: Vec<SigningKey>,
+}
+
+impl KeyRing {
+    /// Creates a new `KeyRing`
+    ///
+    /// # Arguments
+    ///
+    /// * `current` - The key used to sign new tokens
+    /// * `previous` - The keys that are still accepted when verifying tokens, from newest to
+    ///   oldest
+    pub fn new(current: SigningKey, previous: Vec<SigningKey>) -> Self {
+        let mut keys = Vec::with_capacity(previous.len() + 1);
+        keys.push(current);
+        keys.extend(previous);
+        KeyRing { keys }
+    }
+
+    /// Returns the key used to sign new tokens
+    pub fn current(&self) -> &SigningKey {
+        &self.keys[0]
+    }
+
+    /// Returns the key with the given ID, if it is in the key ring
+    pub fn get(&self, key_id: &str) -> Option<&SigningKey> {
+        self.keys.iter().find(|key| key.key_id == key_id)
+    }
+
+    /// Returns all of the keys in the key ring, starting with the current key
+    pub fn keys(&self) -> &[SigningKey] {
+        &self.keys
+    }
+
+    /// Returns the key that should be used to verify the given token
+    ///
+    /// The key is selected by the `kid` field of the token's header. Tokens without a key ID were
+    /// issued before key rotation was supported and are verified with the current key. Returns
+    /// `None` if the token's header cannot be read or the key is no longer in the key ring.
+    pub fn key_for_token(&self, token: &str) -> Option<&SigningKey> {
+        match decode_header(token).ok()?.kid {
+            Some(key_id) => self.get(&key_id),
+            None => Some(self.current()),
+        }
+    }
+}
+
+/// Generates a new key with a random ID and secret
+#[cfg(feature = "rest-api-persistent-secrets")]
+pub(super) fn generate_key() -> SigningKey {
+    SigningKey {
+        key_id: generate_random_string(KEY_ID_LENGTH),
+        secret: generate_random_secret(),
+        created_at: SystemTime::now(),
+    }
+}
+
+/// Removes the keys that should no longer be accepted from a list of keys ordered from newest to
+/// oldest.
+///
+/// A key that has been replaced is kept until `retention` has passed since its successor was
+/// created. The newest key is always kept.
+#[cfg(feature = "rest-api-persistent-secrets")]
+pub(super) fn retain_keys(
+    keys: Vec<SigningKey>,
+    retention: Duration,
+    now: SystemTime,
+) -> Vec<SigningKey> {
+    let mut successor_created_at: Option<SystemTime> = None;
+    keys.into_iter()
+        .filter(|key| {
+            let keep = match successor_created_at {
+                None => true,
+                Some(replaced_at) => replaced_at + retention > now,
+            };
+            successor_created_at = Some(key.created_at);
+            keep
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    use crate::rest_api::sessions::ClaimsBuilder;
+
+    /// Verify that the key ring selects the key used to verify a token by the token's key ID.
+    ///
+    /// 1. Create tokens with the IDs of the current and previous keys, an unknown key ID, and no
+    ///    key ID
+    /// 2. Validate the keys with matching IDs are returned for the first two tokens
+    /// 3. Validate no key is returned for the unknown key ID
+    /// 4. Validate the current key is returned for the token without a key ID
+    #[test]
+    fn test_key_for_token() {
+        let current = SigningKey::new("key-2", "secret-2", SystemTime::now());
+        let previous = SigningKey::new("key-1", "secret-1", SystemTime::UNIX_EPOCH);
+        let key_ring = KeyRing::new(current.clone(), vec![previous.clone()]);
+
+        assert_eq!(
+            key_ring.key_for_token(&token(Some("key-2"))),
+            Some(&current)
+        );
+        assert_eq!(
+            key_ring.key_for_token(&token(Some("key-1"))),
+            Some(&previous)
+        );
+        assert_eq!(key_ring.key_for_token(&token(Some("key-0"))), None);
+        assert_eq!(key_ring.key_for_token(&token(None)), Some(&current));
+        assert_eq!(key_ring.key_for_token("not a token"), None);
+    }
+
+    /// Verify that replaced keys are only kept until the retention period has passed since they
+    /// were replaced, and that the newest key is always kept.
+    #[cfg(feature = "rest-api-persistent-secrets")]
+    #[test]
+    fn test_retain_keys() {
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(60 * 60);
+
+        let key_3 = SigningKey::new("key-3", "secret-3", now - 2 * hour);
+        let key_2 = SigningKey::new("key-2", "secret-2", now - 5 * hour);
+        let key_1 = SigningKey::new("key-1", "secret-1", now - 10 * hour);
+
+        // key-2 was replaced two hours ago and key-1 five hours ago
+        assert_eq!(
+            retain_keys(
+                vec![key_3.clone(), key_2.clone(), key_1.clone()],
+                3 * hour,
+                now
+            ),
+            vec![key_3.clone(), key_2.clone()]
+        );
+        assert_eq!(
+            retain_keys(vec![key_3.clone(), key_2, key_1], hour, now),
+            vec![key_3.clone()]
+        );
+        assert_eq!(
+            retain_keys(vec![key_3.clone()], Duration::from_secs(0), now),
+            vec![key_3]
+        );
+    }
+
+    fn token(key_id: Option<&str>) -> String {
+        let claims = ClaimsBuilder::default()
+            .with_user_id("user")
+            .with_issuer("self-issued")
+            .with_duration(Duration::from_secs(60))
+            .build()
+            .expect("Unable to build claims");
+        let mut header = Header::default();
+        header.kid = key_id.map(String::from);
+        encode(&header, &claims, &EncodingKey::from_secret(b"secret")).expect("Unable to encode")
+    }
+}
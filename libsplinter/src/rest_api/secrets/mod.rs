@@ -16,9 +16,31 @@
 
 mod auto_secret_manager;
 mod error;
+#[cfg(feature = "rest-api-persistent-secrets")]
+mod file_secret_manager;
+mod key_ring;
+#[cfg(feature = "rest-api-persistent-secrets")]
+pub mod store;
+#[cfg(feature = "rest-api-persistent-secrets")]
+mod store_secret_manager;
+
+#[cfg(feature = "rest-api-persistent-secrets")]
+use std::time::Duration;
+use std::time::SystemTime;
 
 pub use auto_secret_manager::AutoSecretManager;
 pub use error::SecretManagerError;
+#[cfg(feature = "rest-api-persistent-secrets")]
+pub use file_secret_manager::FileSecretManager;
+pub use key_ring::{KeyRing, SigningKey, DEFAULT_KEY_ID};
+#[cfg(feature = "rest-api-persistent-secrets")]
+pub use store_secret_manager::StoreSecretManager;
+
+/// The length of time a key that has been replaced by a rotation is still accepted by the
+/// persistent secret managers, unless configured otherwise; this matches the default duration of
+/// a Biome refresh token.
+#[cfg(feature = "rest-api-persistent-secrets")]
+const DEFAULT_KEY_RETENTION: Duration = Duration::from_secs(60 * 60 * 24 * 60);
 
 /// Defines a manager for fetching and/or generating a secret.
 pub trait SecretManager: Sync + Send {
@@ -27,4 +49,17 @@ pub trait SecretManager: Sync + Send {
 
     /// Updates the secret
     fn update_secret(&mut self) -> Result<(), SecretManagerError>;
+
+    /// Returns the key ring used to sign and verify tokens
+    ///
+    /// The default implementation returns a key ring that only contains the secret, with the key
+    /// ID [`DEFAULT_KEY_ID`](constant.DEFAULT_KEY_ID.html). Managers that support key rotation
+    /// return the previous keys as well, so that tokens signed before a call to `update_secret`
+    /// are still accepted.
+    fn key_ring(&self) -> Result<KeyRing, SecretManagerError> {
+        Ok(KeyRing::new(
+            SigningKey::new(DEFAULT_KEY_ID, &self.secret()?, SystemTime::UNIX_EPOCH),
+            vec![],
+        ))
+    }
 }
@@ -0,0 +1,199 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database backed [SecretStore](super::SecretStore) implementation, powered by
+//! [`Diesel`](https://crates.io/crates/diesel).
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::rest_api::secrets::SigningKey;
+use crate::store::pool::ConnectionPool;
+
+use super::{SecretStore, SecretStoreError};
+
+use operations::add_key::SecretStoreAddKeyOperation as _;
+use operations::list_keys::SecretStoreListKeysOperation as _;
+use operations::remove_key::SecretStoreRemoveKeyOperation as _;
+use operations::SecretStoreOperations;
+
+/// A database-backed SecretStore, powered by [`Diesel`](https://crates.io/crates/diesel).
+pub struct DieselSecretStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselSecretStore<C> {
+    /// Creates a new `DieselSecretStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselSecretStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselSecretStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselSecretStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl SecretStore for DieselSecretStore<diesel::pg::PgConnection> {
+    fn list_keys(&self, key_ring: &str) -> Result<Vec<SigningKey>, SecretStoreError> {
+        self.connection_pool
+            .execute_read(|conn| SecretStoreOperations::new(conn).list_keys(key_ring))
+    }
+
+    fn add_key(&self, key_ring: &str, key: SigningKey) -> Result<(), SecretStoreError> {
+        self.connection_pool
+            .execute_write(|conn| SecretStoreOperations::new(conn).add_key(key_ring, key))
+    }
+
+    fn remove_key(&self, key_ring: &str, key_id: &str) -> Result<(), SecretStoreError> {
+        self.connection_pool
+            .execute_write(|conn| SecretStoreOperations::new(conn).remove_key(key_ring, key_id))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn SecretStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl SecretStore for DieselSecretStore<diesel::sqlite::SqliteConnection> {
+    fn list_keys(&self, key_ring: &str) -> Result<Vec<SigningKey>, SecretStoreError> {
+        self.connection_pool
+            .execute_read(|conn| SecretStoreOperations::new(conn).list_keys(key_ring))
+    }
+
+    fn add_key(&self, key_ring: &str, key: SigningKey) -> Result<(), SecretStoreError> {
+        self.connection_pool
+            .execute_write(|conn| SecretStoreOperations::new(conn).add_key(key_ring, key))
+    }
+
+    fn remove_key(&self, key_ring: &str, key_id: &str) -> Result<(), SecretStoreError> {
+        self.connection_pool
+            .execute_write(|conn| SecretStoreOperations::new(conn).remove_key(key_ring, key_id))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn SecretStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, SystemTime};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that keys can be added to and removed from a key ring.
+    ///
+    /// 1. Add two keys to one key ring and one key to another key ring
+    /// 2. Validate the keys of the first key ring are listed from newest to oldest, even though
+    ///    they have the same creation time
+    /// 3. Validate a key with an ID that is already in the key ring cannot be added
+    /// 4. Remove the first key and validate only the second key is listed
+    /// 5. Remove the first key again and validate no error is returned
+    /// 6. Validate the key of the other key ring is unchanged
+    #[test]
+    fn test_add_list_remove_keys() {
+        let store = DieselSecretStore::new(create_connection_pool_and_migrate());
+
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_650_000_000);
+        let key_1 = SigningKey::new("key-1", "secret-1", created_at);
+        let key_2 = SigningKey::new("key-2", "secret-2", created_at);
+        let other_key = SigningKey::new("key-1", "other-secret", created_at);
+
+        store
+            .add_key("access", key_1.clone())
+            .expect("Unable to add key");
+        store
+            .add_key("access", key_2.clone())
+            .expect("Unable to add key");
+        store
+            .add_key("refresh", other_key.clone())
+            .expect("Unable to add key");
+
+        assert_eq!(
+            store.list_keys("access").expect("Unable to list keys"),
+            vec![key_2.clone(), key_1.clone()]
+        );
+
+        assert!(store.add_key("access", key_1).is_err());
+
+        store
+            .remove_key("access", "key-1")
+            .expect("Unable to remove key");
+        assert_eq!(
+            store.list_keys("access").expect("Unable to list keys"),
+            vec![key_2]
+        );
+
+        store
+            .remove_key("access", "key-1")
+            .expect("Unable to remove key");
+        assert_eq!(
+            store.list_keys("refresh").expect("Unable to list keys"),
+            vec![other_key]
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{Duration, SystemTime};
+
+use crate::rest_api::secrets::SigningKey;
+
+use super::schema::rest_api_secret_key;
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "rest_api_secret_key"]
+#[primary_key(key_ring, key_id)]
+pub struct SecretKeyModel {
+    pub key_ring: String,
+    pub key_id: String,
+    pub secret: String,
+    // The time the key was created, in seconds since the Unix epoch
+    pub created_at: i64,
+    pub position: i64,
+}
+
+impl From<SecretKeyModel> for SigningKey {
+    fn from(model: SecretKeyModel) -> Self {
+        SigningKey::new(
+            &model.key_id,
+            &model.secret,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(model.created_at.max(0) as u64),
+        )
+    }
+}
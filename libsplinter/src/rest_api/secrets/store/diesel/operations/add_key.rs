@@ -0,0 +1,113 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add key" operation for the `DieselSecretStore`.
+
+use std::convert::TryFrom;
+use std::time::SystemTime;
+
+use diesel::{dsl::insert_into, dsl::max, prelude::*};
+
+use crate::error::InternalError;
+use crate::rest_api::secrets::{
+    store::{
+        diesel::{models::SecretKeyModel, schema::rest_api_secret_key},
+        SecretStoreError,
+    },
+    SigningKey,
+};
+
+use super::SecretStoreOperations;
+
+pub(in crate::rest_api::secrets::store::diesel) trait SecretStoreAddKeyOperation {
+    fn add_key(&self, key_ring: &str, key: SigningKey) -> Result<(), SecretStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> SecretStoreAddKeyOperation for SecretStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_key(&self, key_ring: &str, key: SigningKey) -> Result<(), SecretStoreError> {
+        let created_at = key
+            .created_at()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .and_then(|created_at| i64::try_from(created_at.as_secs()).ok())
+            .ok_or_else(|| {
+                SecretStoreError::Internal(InternalError::with_message(format!(
+                    "Creation time of key {} is out of range",
+                    key.key_id()
+                )))
+            })?;
+
+        self.conn.transaction::<(), _, _>(|| {
+            let position = rest_api_secret_key::table
+                .filter(rest_api_secret_key::key_ring.eq(key_ring))
+                .select(max(rest_api_secret_key::position))
+                .first::<Option<i64>>(self.conn)?
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            insert_into(rest_api_secret_key::table)
+                .values(SecretKeyModel {
+                    key_ring: key_ring.to_string(),
+                    key_id: key.key_id().to_string(),
+                    secret: key.secret().to_string(),
+                    created_at,
+                    position,
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> SecretStoreAddKeyOperation
+    for SecretStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_key(&self, key_ring: &str, key: SigningKey) -> Result<(), SecretStoreError> {
+        let created_at = key
+            .created_at()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .and_then(|created_at| i64::try_from(created_at.as_secs()).ok())
+            .ok_or_else(|| {
+                SecretStoreError::Internal(InternalError::with_message(format!(
+                    "Creation time of key {} is out of range",
+                    key.key_id()
+                )))
+            })?;
+
+        self.conn.transaction::<(), _, _>(|| {
+            let position = rest_api_secret_key::table
+                .filter(rest_api_secret_key::key_ring.eq(key_ring))
+                .select(max(rest_api_secret_key::position))
+                .first::<Option<i64>>(self.conn)?
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            insert_into(rest_api_secret_key::table)
+                .values(SecretKeyModel {
+                    key_ring: key_ring.to_string(),
+                    key_id: key.key_id().to_string(),
+                    secret: key.secret().to_string(),
+                    created_at,
+                    position,
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
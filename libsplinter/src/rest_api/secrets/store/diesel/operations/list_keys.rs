@@ -0,0 +1,48 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list keys" operation for the `DieselSecretStore`.
+
+use diesel::prelude::*;
+
+use crate::rest_api::secrets::{
+    store::{
+        diesel::{models::SecretKeyModel, schema::rest_api_secret_key},
+        SecretStoreError,
+    },
+    SigningKey,
+};
+
+use super::SecretStoreOperations;
+
+pub(in crate::rest_api::secrets::store::diesel) trait SecretStoreListKeysOperation {
+    fn list_keys(&self, key_ring: &str) -> Result<Vec<SigningKey>, SecretStoreError>;
+}
+
+impl<'a, C> SecretStoreListKeysOperation for SecretStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn list_keys(&self, key_ring: &str) -> Result<Vec<SigningKey>, SecretStoreError> {
+        Ok(rest_api_secret_key::table
+            .filter(rest_api_secret_key::key_ring.eq(key_ring))
+            .order(rest_api_secret_key::position.desc())
+            .load::<SecretKeyModel>(self.conn)?
+            .into_iter()
+            .map(SigningKey::from)
+            .collect())
+    }
+}
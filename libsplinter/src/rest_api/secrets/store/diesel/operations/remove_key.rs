@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove key" operation for the `DieselSecretStore`.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::rest_api::secrets::store::{diesel::schema::rest_api_secret_key, SecretStoreError};
+
+use super::SecretStoreOperations;
+
+pub(in crate::rest_api::secrets::store::diesel) trait SecretStoreRemoveKeyOperation {
+    fn remove_key(&self, key_ring: &str, key_id: &str) -> Result<(), SecretStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> SecretStoreRemoveKeyOperation for SecretStoreOperations<'a, diesel::pg::PgConnection> {
+    fn remove_key(&self, key_ring: &str, key_id: &str) -> Result<(), SecretStoreError> {
+        delete(
+            rest_api_secret_key::table
+                .filter(rest_api_secret_key::key_ring.eq(key_ring))
+                .filter(rest_api_secret_key::key_id.eq(key_id)),
+        )
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> SecretStoreRemoveKeyOperation
+    for SecretStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_key(&self, key_ring: &str, key_id: &str) -> Result<(), SecretStoreError> {
+        delete(
+            rest_api_secret_key::table
+                .filter(rest_api_secret_key::key_ring.eq(key_ring))
+                .filter(rest_api_secret_key::key_id.eq(key_id)),
+        )
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+}
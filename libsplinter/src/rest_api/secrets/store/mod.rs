@@ -0,0 +1,62 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for the keys used to sign and verify REST API tokens.
+//!
+//! Keys are grouped into named key rings, so that a single store may hold the keys of several
+//! secret managers, such as those for Biome access tokens and refresh tokens. Storing the keys in
+//! a shared database allows every REST API instance that uses the database to verify the tokens
+//! issued by the others.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+pub use error::SecretStoreError;
+
+use super::SigningKey;
+
+pub trait SecretStore: Send + Sync {
+    /// Lists the keys of a key ring, from the most recently added to the oldest
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ring` - The name of the key ring
+    fn list_keys(&self, key_ring: &str) -> Result<Vec<SigningKey>, SecretStoreError>;
+
+    /// Adds a key to a key ring; the key becomes the current key of the key ring
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ring` - The name of the key ring
+    /// * `key` - The key to add
+    fn add_key(&self, key_ring: &str, key: SigningKey) -> Result<(), SecretStoreError>;
+
+    /// Removes a key from a key ring. Removing a key that is not in the key ring is not an error,
+    /// as more than one REST API instance may remove the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ring` - The name of the key ring
+    /// * `key_id` - The ID of the key to remove
+    fn remove_key(&self, key_ring: &str, key_id: &str) -> Result<(), SecretStoreError>;
+
+    fn clone_boxed(&self) -> Box<dyn SecretStore>;
+}
+
+impl Clone for Box<dyn SecretStore> {
+    fn clone(&self) -> Box<dyn SecretStore> {
+        self.clone_boxed()
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{Duration, SystemTime};
+
+use crate::error::InvalidStateError;
+
+use super::key_ring::{generate_key, retain_keys};
+use super::store::SecretStore;
+use super::{KeyRing, SecretManager, SecretManagerError, SigningKey, DEFAULT_KEY_RETENTION};
+
+/// A SecretManager that keeps a key ring in a [`SecretStore`](store/trait.SecretStore.html)
+///
+/// The keys are read from the store each time they are used, so every REST API instance that
+/// shares the store and the key ring name signs tokens with the same key, and a rotation made by
+/// one instance is seen by all of them.
+pub struct StoreSecretManager {
+    store: Box<dyn SecretStore>,
+    key_ring: String,
+    key_retention: Duration,
+}
+
+impl StoreSecretManager {
+    /// Creates a new `StoreSecretManager`, adding a new key to the key ring if it does not have
+    /// any keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store that holds the keys
+    /// * `key_ring` - The name of the key ring in the store, such as "biome_access_token"
+    pub fn new(store: Box<dyn SecretStore>, key_ring: &str) -> Result<Self, SecretManagerError> {
+        if store
+            .list_keys(key_ring)
+            .map_err(|err| SecretManagerError::SecretError(Box::new(err)))?
+            .is_empty()
+        {
+            store
+                .add_key(key_ring, generate_key())
+                .map_err(|err| SecretManagerError::UpdateSecretError(Box::new(err)))?;
+        }
+
+        Ok(StoreSecretManager {
+            store,
+            key_ring: key_ring.to_string(),
+            key_retention: DEFAULT_KEY_RETENTION,
+        })
+    }
+
+    /// Sets how long a key is still accepted after it has been replaced by a rotation. This
+    /// should be at least as long as the duration of the tokens signed with the key.
+    pub fn with_key_retention(mut self, key_retention: Duration) -> Self {
+        self.key_retention = key_retention;
+        self
+    }
+
+    fn list_keys(&self) -> Result<Vec<SigningKey>, SecretManagerError> {
+        self.store
+            .list_keys(&self.key_ring)
+            .map_err(|err| SecretManagerError::SecretError(Box::new(err)))
+    }
+}
+
+impl SecretManager for StoreSecretManager {
+    fn secret(&self) -> Result<String, SecretManagerError> {
+        Ok(self.key_ring()?.current().secret().to_string())
+    }
+
+    /// Adds a new key to the key ring and removes the keys that are no longer accepted
+    fn update_secret(&mut self) -> Result<(), SecretManagerError> {
+        self.store
+            .add_key(&self.key_ring, generate_key())
+            .map_err(|err| SecretManagerError::UpdateSecretError(Box::new(err)))?;
+
+        let keys = self.list_keys()?;
+        let retained = retain_keys(keys.clone(), self.key_retention, SystemTime::now());
+        for key in keys.iter().filter(|key| !retained.contains(key)) {
+            self.store
+                .remove_key(&self.key_ring, key.key_id())
+                .map_err(|err| SecretManagerError::UpdateSecretError(Box::new(err)))?;
+        }
+
+        Ok(())
+    }
+
+    fn key_ring(&self) -> Result<KeyRing, SecretManagerError> {
+        let mut keys =
+            retain_keys(self.list_keys()?, self.key_retention, SystemTime::now()).into_iter();
+        let current = keys.next().ok_or_else(|| {
+            SecretManagerError::SecretError(Box::new(InvalidStateError::with_message(format!(
+                "Key ring {} does not contain any keys",
+                self.key_ring
+            ))))
+        })?;
+
+        Ok(KeyRing::new(current, keys.collect()))
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::secrets::store::diesel::DieselSecretStore;
+
+    /// Verify that managers sharing a store use the same key ring, and that rotating the key
+    /// keeps the previous key until the retention period has passed.
+    ///
+    /// 1. Create two managers for the same key ring and validate they use the same secret
+    /// 2. Rotate the key with the first manager and validate the second manager uses the new key
+    ///    and still accepts the previous key
+    /// 3. Rotate the key with a manager that does not retain replaced keys and validate only the
+    ///    new key is in the key ring, both in the manager and in the store
+    /// 4. Validate that a manager for another key ring uses a different key
+    #[test]
+    fn test_shared_key_ring_rotation() {
+        let store = DieselSecretStore::new(create_connection_pool_and_migrate());
+
+        let mut manager_1 = StoreSecretManager::new(Box::new(store.clone()), "access")
+            .expect("Unable to create manager");
+        let manager_2 = StoreSecretManager::new(Box::new(store.clone()), "access")
+            .expect("Unable to create manager");
+
+        let original = manager_1.key_ring().expect("Unable to get key ring");
+        assert_eq!(original.keys().len(), 1);
+        assert_eq!(
+            manager_2.key_ring().expect("Unable to get key ring"),
+            original
+        );
+
+        manager_1.update_secret().expect("Unable to update secret");
+        let rotated = manager_2.key_ring().expect("Unable to get key ring");
+        assert_eq!(rotated.keys().len(), 2);
+        assert_ne!(rotated.current(), original.current());
+        assert_eq!(
+            rotated.get(original.current().key_id()),
+            Some(original.current())
+        );
+        assert_eq!(
+            manager_2.secret().expect("Unable to get secret"),
+            rotated.current().secret()
+        );
+
+        let mut manager_3 = StoreSecretManager::new(Box::new(store.clone()), "access")
+            .expect("Unable to create manager")
+            .with_key_retention(Duration::from_secs(0));
+        manager_3.update_secret().expect("Unable to update secret");
+        let key_ring = manager_3.key_ring().expect("Unable to get key ring");
+        assert_eq!(key_ring.keys().len(), 1);
+        assert_eq!(
+            store.list_keys("access").expect("Unable to list keys"),
+            key_ring.keys().to_vec()
+        );
+
+        let other =
+            StoreSecretManager::new(Box::new(store), "refresh").expect("Unable to create manager");
+        assert_ne!(
+            other.key_ring().expect("Unable to get key ring").current(),
+            key_ring.current()
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
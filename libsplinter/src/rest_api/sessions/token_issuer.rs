@@ -44,21 +44,32 @@ impl AccessTokenIssuer {
 
 impl TokenIssuer<Claims> for AccessTokenIssuer {
     fn issue_token_with_claims(&self, claims: Claims) -> Result<String, TokenIssuerError> {
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.secret_manager.secret()?.as_ref()),
-        )?;
-        Ok(token)
+        issue_token(&*self.secret_manager, claims)
     }
 
     #[cfg(feature = "biome-credentials")]
     fn issue_refresh_token_with_claims(&self, claims: Claims) -> Result<String, TokenIssuerError> {
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.refresh_secret_manager.secret()?.as_ref()),
-        )?;
-        Ok(token)
+        issue_token(&*self.refresh_secret_manager, claims)
     }
 }
+
+/// Signs the claims with the current key of the secret manager's key ring, adding the ID of the
+/// key to the token's header so the token can still be verified after the key has been rotated.
+fn issue_token(
+    secret_manager: &dyn SecretManager,
+    claims: Claims,
+) -> Result<String, TokenIssuerError> {
+    let key_ring = secret_manager.key_ring()?;
+    let key = key_ring.current();
+
+    let header = Header {
+        kid: Some(key.key_id().to_string()),
+        ..Default::default()
+    };
+
+    Ok(encode(
+        &header,
+        &claims,
+        &EncodingKey::from_secret(key.secret().as_ref()),
+    )?)
+}
@@ -170,6 +170,34 @@ impl ServiceNetworkSender for AdminServiceNetworkSender {
             .map_err(|err| ServiceSendError(Box::new(err)))?;
         Ok(())
     }
+
+    /// Send the message bytes to the given recipient (another admin service) through the given
+    /// relay nodes, in order
+    fn send_with_relay_route(
+        &self,
+        recipient: &str,
+        message: &[u8],
+        relay_route: &[String],
+    ) -> Result<(), ServiceSendError> {
+        let mut admin_direct_message = AdminDirectMessage::new();
+        admin_direct_message.set_circuit("admin".into());
+        admin_direct_message.set_sender(self.message_sender.to_string());
+        admin_direct_message.set_recipient(recipient.into());
+        admin_direct_message.set_payload(message.to_vec());
+        admin_direct_message.set_relay_route(relay_route.to_vec().into());
+
+        let bytes = admin_direct_message
+            .write_to_bytes()
+            .map_err(|err| ServiceSendError(Box::new(err)))?;
+
+        let msg = create_message(bytes, CircuitMessageType::ADMIN_DIRECT_MESSAGE)
+            .map_err(|err| ServiceSendError(Box::new(err)))?;
+
+        self.outgoing_sender
+            .send(msg)
+            .map_err(|err| ServiceSendError(Box::new(err)))?;
+        Ok(())
+    }
 }
 
 /// This implementation of ServiceNetworkSender can be used by any service that does not require
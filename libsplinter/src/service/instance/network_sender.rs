@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::error::InvalidStateError;
+
 use super::{ServiceMessageContext, ServiceSendError};
 
 /// The ServiceNetworkSender trait allows a service to send its own messages, such as replies to
@@ -43,6 +45,23 @@ pub trait ServiceNetworkSender: Send {
         message: &[u8],
         sender: &str,
     ) -> Result<(), ServiceSendError>;
+
+    /// Send the message bytes to the given recipient (another service) through the given relay
+    /// nodes, in order. Only senders that support relayed routes accept a non-empty route.
+    fn send_with_relay_route(
+        &self,
+        recipient: &str,
+        message: &[u8],
+        relay_route: &[String],
+    ) -> Result<(), ServiceSendError> {
+        if relay_route.is_empty() {
+            self.send(recipient, message)
+        } else {
+            Err(ServiceSendError(Box::new(InvalidStateError::with_message(
+                "This sender does not support relayed routes".into(),
+            ))))
+        }
+    }
 }
 
 impl Clone for Box<dyn ServiceNetworkSender> {
@@ -50,6 +50,8 @@ pub struct MemoryStoreFactory {
     inflight_request_store: MemoryInflightOAuthRequestStore,
     #[cfg(feature = "biome-profile")]
     biome_profile_store: MemoryUserProfileStore,
+    #[cfg(feature = "registry")]
+    registry: crate::registry::DieselRegistry<SqliteConnection>,
     // to be used for sqlite in memory implementations
     pool: Pool<ConnectionManager<SqliteConnection>>,
 }
@@ -100,6 +102,8 @@ impl MemoryStoreFactory {
             inflight_request_store,
             #[cfg(feature = "biome-profile")]
             biome_profile_store,
+            #[cfg(feature = "registry")]
+            registry: crate::registry::DieselRegistry::new(pool.clone()),
             pool,
         })
     }
@@ -142,7 +146,7 @@ impl StoreFactory for MemoryStoreFactory {
 
     #[cfg(feature = "registry")]
     fn get_registry_store(&self) -> Box<dyn crate::registry::RwRegistry> {
-        Box::new(crate::registry::DieselRegistry::new(self.pool.clone()))
+        Box::new(self.registry.clone())
     }
 
     #[cfg(feature = "authorization-handler-rbac")]
@@ -164,4 +168,44 @@ impl StoreFactory for MemoryStoreFactory {
             self.pool.clone(),
         ))
     }
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durability_store(&self) -> Box<dyn crate::circuit::durability::store::DurabilityStore> {
+        Box::new(
+            crate::circuit::durability::store::diesel::DieselDurabilityStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "rest-api-persistent-secrets")]
+    fn get_secret_store(&self) -> Box<dyn crate::rest_api::secrets::store::SecretStore> {
+        Box::new(crate::rest_api::secrets::store::diesel::DieselSecretStore::new(self.pool.clone()))
+    }
+
+    #[cfg(feature = "authorization-handler-maintenance")]
+    fn get_maintenance_store(
+        &self,
+    ) -> Box<dyn crate::rest_api::auth::authorization::maintenance::store::MaintenanceStore> {
+        Box::new(
+            crate::rest_api::auth::authorization::maintenance::store::diesel::DieselMaintenanceStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
+    #[cfg(feature = "rest-api-audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::rest_api::auth::audit::store::AuditStore> {
+        Box::new(
+            crate::rest_api::auth::audit::store::diesel::DieselAuditStore::new(self.pool.clone()),
+        )
+    }
+
+    #[cfg(feature = "rest-api-tokens")]
+    fn get_api_token_store(&self) -> Box<dyn crate::rest_api::auth::token::store::ApiTokenStore> {
+        Box::new(
+            crate::rest_api::auth::token::store::diesel::DieselApiTokenStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
 }
@@ -64,4 +64,22 @@ pub trait StoreFactory {
 
     #[cfg(feature = "node-id-store")]
     fn get_node_id_store(&self) -> Box<dyn crate::node_id::store::NodeIdStore>;
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durability_store(&self) -> Box<dyn crate::circuit::durability::store::DurabilityStore>;
+
+    #[cfg(feature = "rest-api-persistent-secrets")]
+    fn get_secret_store(&self) -> Box<dyn crate::rest_api::secrets::store::SecretStore>;
+
+    #[cfg(feature = "authorization-handler-maintenance")]
+    fn get_maintenance_store(
+        &self,
+    ) -> Box<dyn crate::rest_api::auth::authorization::maintenance::store::MaintenanceStore>;
+
+    #[cfg(feature = "rest-api-audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::rest_api::auth::audit::store::AuditStore>;
+
+    /// Get a new `ApiTokenStore`
+    #[cfg(feature = "rest-api-tokens")]
+    fn get_api_token_store(&self) -> Box<dyn crate::rest_api::auth::token::store::ApiTokenStore>;
 }
@@ -61,11 +61,19 @@ pub fn create_postgres_connection_pool(
 /// A `StoryFactory` backed by a PostgreSQL database.
 pub struct PgStoreFactory {
     pool: Pool<ConnectionManager<PgConnection>>,
+    // All registry stores share this registry, so that their subscribers are notified of changes
+    // made through any of them
+    #[cfg(feature = "registry")]
+    registry: crate::registry::DieselRegistry<PgConnection>,
 }
 
 impl PgStoreFactory {
     pub fn new(pool: Pool<ConnectionManager<PgConnection>>) -> Self {
-        Self { pool }
+        Self {
+            #[cfg(feature = "registry")]
+            registry: crate::registry::DieselRegistry::new(pool.clone()),
+            pool,
+        }
     }
 }
 
@@ -112,7 +120,7 @@ impl StoreFactory for PgStoreFactory {
 
     #[cfg(feature = "registry")]
     fn get_registry_store(&self) -> Box<dyn crate::registry::RwRegistry> {
-        Box::new(crate::registry::DieselRegistry::new(self.pool.clone()))
+        Box::new(self.registry.clone())
     }
 
     #[cfg(feature = "authorization-handler-rbac")]
@@ -138,4 +146,44 @@ impl StoreFactory for PgStoreFactory {
             self.pool.clone(),
         ))
     }
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durability_store(&self) -> Box<dyn crate::circuit::durability::store::DurabilityStore> {
+        Box::new(
+            crate::circuit::durability::store::diesel::DieselDurabilityStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "rest-api-persistent-secrets")]
+    fn get_secret_store(&self) -> Box<dyn crate::rest_api::secrets::store::SecretStore> {
+        Box::new(crate::rest_api::secrets::store::diesel::DieselSecretStore::new(self.pool.clone()))
+    }
+
+    #[cfg(feature = "authorization-handler-maintenance")]
+    fn get_maintenance_store(
+        &self,
+    ) -> Box<dyn crate::rest_api::auth::authorization::maintenance::store::MaintenanceStore> {
+        Box::new(
+            crate::rest_api::auth::authorization::maintenance::store::diesel::DieselMaintenanceStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
+    #[cfg(feature = "rest-api-audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::rest_api::auth::audit::store::AuditStore> {
+        Box::new(
+            crate::rest_api::auth::audit::store::diesel::DieselAuditStore::new(self.pool.clone()),
+        )
+    }
+
+    #[cfg(feature = "rest-api-tokens")]
+    fn get_api_token_store(&self) -> Box<dyn crate::rest_api::auth::token::store::ApiTokenStore> {
+        Box::new(
+            crate::rest_api::auth::token::store::diesel::DieselApiTokenStore::new(
+                self.pool.clone(),
+            ),
+        )
+    }
 }
@@ -94,21 +94,27 @@ pub fn create_sqlite_connection_pool_with_write_exclusivity(
 /// A `StoreFactory` backed by a SQLite database.
 pub struct SqliteStoreFactory {
     pool: Arc<RwLock<Pool<ConnectionManager<SqliteConnection>>>>,
+    // All registry stores share this registry, so that their subscribers are notified of changes
+    // made through any of them
+    #[cfg(feature = "registry")]
+    registry: crate::registry::DieselRegistry<SqliteConnection>,
 }
 
 impl SqliteStoreFactory {
     /// Create a new `SqliteStoreFactory`.
     pub fn new(pool: Pool<ConnectionManager<SqliteConnection>>) -> Self {
-        Self {
-            pool: Arc::new(RwLock::new(pool)),
-        }
+        Self::new_with_write_exclusivity(Arc::new(RwLock::new(pool)))
     }
 
     /// Create a new `SqliteStoreFactory` with shared write-exclusivity.
     pub fn new_with_write_exclusivity(
         pool: Arc<RwLock<Pool<ConnectionManager<SqliteConnection>>>>,
     ) -> Self {
-        Self { pool }
+        Self {
+            #[cfg(feature = "registry")]
+            registry: crate::registry::DieselRegistry::new_with_write_exclusivity(pool.clone()),
+            pool,
+        }
     }
 }
 
@@ -165,9 +171,7 @@ impl StoreFactory for SqliteStoreFactory {
 
     #[cfg(feature = "registry")]
     fn get_registry_store(&self) -> Box<dyn crate::registry::RwRegistry> {
-        Box::new(crate::registry::DieselRegistry::new_with_write_exclusivity(
-            self.pool.clone(),
-        ))
+        Box::new(self.registry.clone())
     }
 
     #[cfg(feature = "authorization-handler-rbac")]
@@ -190,6 +194,52 @@ impl StoreFactory for SqliteStoreFactory {
             ),
         )
     }
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durability_store(&self) -> Box<dyn crate::circuit::durability::store::DurabilityStore> {
+        Box::new(
+            crate::circuit::durability::store::diesel::DieselDurabilityStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "rest-api-persistent-secrets")]
+    fn get_secret_store(&self) -> Box<dyn crate::rest_api::secrets::store::SecretStore> {
+        Box::new(
+            crate::rest_api::secrets::store::diesel::DieselSecretStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "authorization-handler-maintenance")]
+    fn get_maintenance_store(
+        &self,
+    ) -> Box<dyn crate::rest_api::auth::authorization::maintenance::store::MaintenanceStore> {
+        Box::new(
+            crate::rest_api::auth::authorization::maintenance::store::diesel::DieselMaintenanceStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+    #[cfg(feature = "rest-api-audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::rest_api::auth::audit::store::AuditStore> {
+        Box::new(
+            crate::rest_api::auth::audit::store::diesel::DieselAuditStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "rest-api-tokens")]
+    fn get_api_token_store(&self) -> Box<dyn crate::rest_api::auth::token::store::ApiTokenStore> {
+        Box::new(
+            crate::rest_api::auth::token::store::diesel::DieselApiTokenStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
 }
 
 #[derive(Default, Debug)]
@@ -0,0 +1,406 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains an in-memory implementation of the [metrics::Recorder](https://docs.rs/metrics/0.17.0/metrics/trait.Recorder.html)
+//! trait. PrometheusRecorder aggregates the values recorded by the metrics macros and renders
+//! them in the Prometheus text exposition format, so they may be scraped by a Prometheus server.
+//!
+//! Available if the `tap-prometheus` feature is enabled
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use metrics::{GaugeValue, Key, Recorder, Unit};
+
+use crate::error::InternalError;
+
+/// The upper bounds of the buckets used for every histogram, matching the Prometheus client
+/// library defaults
+const HISTOGRAM_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The content type of the text rendered by `PrometheusRecorder::render`
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// A metric name and its labels, sorted by label key
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct MetricKey {
+    name: String,
+    labels: Vec<(String, String)>,
+}
+
+impl From<&Key> for MetricKey {
+    fn from(key: &Key) -> Self {
+        let (name, labels) = key.clone().into_parts();
+        let mut labels = labels
+            .iter()
+            .map(|label| (label.key().to_string(), label.value().to_string()))
+            .collect::<Vec<_>>();
+        labels.sort();
+
+        MetricKey {
+            name: name.to_string(),
+            labels,
+        }
+    }
+}
+
+struct HistogramEntry {
+    buckets: [u64; HISTOGRAM_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl HistogramEntry {
+    fn new() -> Self {
+        HistogramEntry {
+            buckets: [0; HISTOGRAM_BUCKETS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn record(&mut self, value: f64) {
+        for (bucket, upper_bound) in self.buckets.iter_mut().zip(HISTOGRAM_BUCKETS.iter()) {
+            if value <= *upper_bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Metrics {
+    counters: BTreeMap<MetricKey, u64>,
+    gauges: BTreeMap<MetricKey, f64>,
+    histograms: BTreeMap<MetricKey, HistogramEntry>,
+}
+
+/// Enables using the metrics macros and exposing the metrics data to a Prometheus server
+///
+/// Cloned recorders share the same metrics, so a clone may be kept to render the metrics after
+/// the recorder has been installed.
+#[derive(Clone, Default)]
+pub struct PrometheusRecorder {
+    metrics: Arc<Mutex<Metrics>>,
+}
+
+impl PrometheusRecorder {
+    /// Initialize metric collection by creating a PrometheusRecorder and adding it to the metrics
+    /// library as the recorder. Returns a handle to the recorder that is used to render the
+    /// collected metrics.
+    pub fn init() -> Result<Self, InternalError> {
+        let recorder = Self::default();
+        metrics::set_boxed_recorder(Box::new(recorder.clone()))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        Ok(recorder)
+    }
+
+    /// Render the current value of every recorded metric in the Prometheus text exposition
+    /// format.
+    ///
+    /// Metric names are sanitized to only contain the characters allowed by Prometheus; for
+    /// example, `splinter.peer_manager.peers` is rendered as `splinter_peer_manager_peers`.
+    pub fn render(&self) -> Result<String, InternalError> {
+        let metrics = self.metrics.lock().map_err(|_| {
+            InternalError::with_message("PrometheusRecorder lock was poisoned".to_string())
+        })?;
+
+        let mut output = String::new();
+
+        render_values(&mut output, "counter", &metrics.counters, |value| {
+            value.to_string()
+        });
+        render_values(&mut output, "gauge", &metrics.gauges, |value| {
+            format_float(*value)
+        });
+
+        let mut previous_name = None;
+        for (key, histogram) in metrics.histograms.iter() {
+            let name = sanitize_name(&key.name);
+            if previous_name.as_ref() != Some(&name) {
+                let _ = writeln!(output, "# TYPE {} histogram", name);
+            }
+            for (count, upper_bound) in histogram.buckets.iter().zip(HISTOGRAM_BUCKETS.iter()) {
+                let _ = writeln!(
+                    output,
+                    "{}_bucket{} {}",
+                    name,
+                    format_labels(&key.labels, Some(&format_float(*upper_bound))),
+                    count
+                );
+            }
+            let _ = writeln!(
+                output,
+                "{}_bucket{} {}",
+                name,
+                format_labels(&key.labels, Some("+Inf")),
+                histogram.count
+            );
+            let _ = writeln!(
+                output,
+                "{}_sum{} {}",
+                name,
+                format_labels(&key.labels, None),
+                format_float(histogram.sum)
+            );
+            let _ = writeln!(
+                output,
+                "{}_count{} {}",
+                name,
+                format_labels(&key.labels, None),
+                histogram.count
+            );
+            previous_name = Some(name);
+        }
+
+        Ok(output)
+    }
+
+    fn with_metrics<F>(&self, f: F)
+    where
+        F: FnOnce(&mut Metrics),
+    {
+        match self.metrics.lock() {
+            Ok(mut metrics) => f(&mut metrics),
+            Err(_) => error!("Unable to record metric, PrometheusRecorder lock was poisoned"),
+        }
+    }
+}
+
+impl Recorder for PrometheusRecorder {
+    fn increment_counter(&self, key: &Key, value: u64) {
+        self.with_metrics(|metrics| {
+            *metrics.counters.entry(MetricKey::from(key)).or_insert(0) += value;
+        })
+    }
+
+    fn update_gauge(&self, key: &Key, value: GaugeValue) {
+        self.with_metrics(|metrics| {
+            let gauge = metrics.gauges.entry(MetricKey::from(key)).or_insert(0.0);
+            match value {
+                GaugeValue::Absolute(total) => *gauge = total,
+                GaugeValue::Increment(amount) => *gauge += amount,
+                GaugeValue::Decrement(amount) => *gauge -= amount,
+            }
+        })
+    }
+
+    fn record_histogram(&self, key: &Key, value: f64) {
+        self.with_metrics(|metrics| {
+            metrics
+                .histograms
+                .entry(MetricKey::from(key))
+                .or_insert_with(HistogramEntry::new)
+                .record(value);
+        })
+    }
+
+    fn register_counter(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
+        self.with_metrics(|metrics| {
+            metrics.counters.entry(MetricKey::from(key)).or_insert(0);
+        })
+    }
+
+    fn register_gauge(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
+        self.with_metrics(|metrics| {
+            metrics.gauges.entry(MetricKey::from(key)).or_insert(0.0);
+        })
+    }
+
+    fn register_histogram(
+        &self,
+        key: &Key,
+        _unit: Option<Unit>,
+        _description: Option<&'static str>,
+    ) {
+        self.with_metrics(|metrics| {
+            metrics
+                .histograms
+                .entry(MetricKey::from(key))
+                .or_insert_with(HistogramEntry::new);
+        })
+    }
+}
+
+/// Render a `# TYPE` line for each metric name followed by one sample line for each set of labels
+fn render_values<T, F>(
+    output: &mut String,
+    metric_type: &str,
+    values: &BTreeMap<MetricKey, T>,
+    format_value: F,
+) where
+    F: Fn(&T) -> String,
+{
+    let mut previous_name = None;
+    for (key, value) in values.iter() {
+        let name = sanitize_name(&key.name);
+        if previous_name.as_ref() != Some(&name) {
+            let _ = writeln!(output, "# TYPE {} {}", name, metric_type);
+        }
+        let _ = writeln!(
+            output,
+            "{}{} {}",
+            name,
+            format_labels(&key.labels, None),
+            format_value(value)
+        );
+        previous_name = Some(name);
+    }
+}
+
+/// Replace any character that is not valid in a Prometheus metric or label name with `_`
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if sanitized
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(true)
+    {
+        sanitized.insert(0, '_');
+    }
+
+    sanitized
+}
+
+fn format_labels(labels: &[(String, String)], le: Option<&str>) -> String {
+    let mut formatted = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", sanitize_name(key), escape_label_value(value)))
+        .collect::<Vec<_>>();
+
+    if let Some(le) = le {
+        formatted.push(format!("le=\"{}\"", le));
+    }
+
+    if formatted.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", formatted.join(","))
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 {
+            "+Inf".to_string()
+        } else {
+            "-Inf".to_string()
+        }
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use metrics::Label;
+
+    /// Test that counters and gauges are aggregated and rendered in the Prometheus text format.
+    ///
+    /// 1. Increment a counter twice and verify the rendered value is the sum
+    /// 2. Set, increment and decrement a gauge and verify the rendered value
+    /// 3. Verify metric names are sanitized and each name has a single `# TYPE` line
+    #[test]
+    fn test_render_counters_and_gauges() {
+        let recorder = PrometheusRecorder::default();
+
+        let committed = Key::from_parts(
+            "splinter.scabbard.committed_batches",
+            vec![Label::new("service", "abcde-01234::a000")],
+        );
+        recorder.increment_counter(&committed, 1);
+        recorder.increment_counter(&committed, 2);
+
+        let peers = Key::from_name("splinter.peer_manager.peers");
+        recorder.update_gauge(&peers, GaugeValue::Absolute(3.0));
+        recorder.update_gauge(&peers, GaugeValue::Increment(2.0));
+        recorder.update_gauge(&peers, GaugeValue::Decrement(1.0));
+
+        let rendered = recorder.render().expect("Unable to render metrics");
+
+        assert_eq!(
+            rendered,
+            "# TYPE splinter_scabbard_committed_batches counter\n\
+             splinter_scabbard_committed_batches{service=\"abcde-01234::a000\"} 3\n\
+             # TYPE splinter_peer_manager_peers gauge\n\
+             splinter_peer_manager_peers 4\n"
+        );
+    }
+
+    /// Test that histograms are rendered as cumulative buckets along with their sum and count.
+    #[test]
+    fn test_render_histogram() {
+        let recorder = PrometheusRecorder::default();
+
+        let latency = Key::from_name("splinter.latency");
+        recorder.record_histogram(&latency, 0.25);
+        recorder.record_histogram(&latency, 3.0);
+        recorder.record_histogram(&latency, 20.0);
+
+        let rendered = recorder.render().expect("Unable to render metrics");
+
+        assert!(rendered.starts_with("# TYPE splinter_latency histogram\n"));
+        assert!(rendered.contains("splinter_latency_bucket{le=\"0.1\"} 0\n"));
+        assert!(rendered.contains("splinter_latency_bucket{le=\"0.25\"} 1\n"));
+        assert!(rendered.contains("splinter_latency_bucket{le=\"5\"} 2\n"));
+        assert!(rendered.contains("splinter_latency_bucket{le=\"10\"} 2\n"));
+        assert!(rendered.contains("splinter_latency_bucket{le=\"+Inf\"} 3\n"));
+        assert!(rendered.contains("splinter_latency_sum 23.25\n"));
+        assert!(rendered.ends_with("splinter_latency_count 3\n"));
+    }
+
+    /// Test that label values are escaped and invalid name characters are replaced.
+    #[test]
+    fn test_escape_labels_and_names() {
+        assert_eq!(
+            sanitize_name("splinter.peers-total"),
+            "splinter_peers_total"
+        );
+        assert_eq!(sanitize_name("0peers"), "_0peers");
+        assert_eq!(
+            format_labels(
+                &[("node".to_string(), "a \"quoted\"\\value\n".to_string())],
+                None
+            ),
+            "{node=\"a \\\"quoted\\\"\\\\value\\n\"}"
+        );
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
 use std::io::{self, Cursor, Read, Write};
 use std::thread;
 use std::time::Duration;
@@ -19,13 +20,37 @@ use std::time::Duration;
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
 const HEADER_LENGTH: usize = 6;
+#[cfg(feature = "frame-compression")]
+const V2_HEADER_LENGTH: usize = 7;
+
+/// Set in the flags of a v2 frame header if the frame's data is compressed
+#[cfg(feature = "frame-compression")]
+const FLAG_COMPRESSED: u8 = 0x01;
+
+/// Data shorter than this is not worth compressing and is sent as-is in v2 frames
+#[cfg(feature = "frame-compression")]
+const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// The highest frame version supported
+#[cfg(not(feature = "frame-compression"))]
+pub const MAX_FRAME_VERSION: FrameVersion = FrameVersion::V1;
+/// The highest frame version supported
+#[cfg(feature = "frame-compression")]
+pub const MAX_FRAME_VERSION: FrameVersion = FrameVersion::V2;
 
 /// An error that may be returned during frame-related operations
 #[derive(Debug)]
 pub enum FrameError {
     IoError(io::Error),
     InvalidChecksum,
-    InvalidHeaderLength(usize),
+    InvalidHeaderLength {
+        expected: usize,
+        actual: usize,
+    },
+    #[cfg(feature = "frame-compression")]
+    InvalidFlags(u8),
+    #[cfg(feature = "frame-compression")]
+    DecompressionFailure(String),
     UnsupportedVersion,
     HandshakeFailure(String),
 }
@@ -35,11 +60,19 @@ impl std::fmt::Display for FrameError {
         match self {
             FrameError::IoError(err) => f.write_str(&err.to_string()),
             FrameError::InvalidChecksum => f.write_str("Invalid checksum in frame header"),
-            FrameError::InvalidHeaderLength(n) => write!(
+            FrameError::InvalidHeaderLength { expected, actual } => write!(
                 f,
                 "Invalid header length expected {} but was {}",
-                HEADER_LENGTH, n
+                expected, actual
             ),
+            #[cfg(feature = "frame-compression")]
+            FrameError::InvalidFlags(flags) => {
+                write!(f, "Invalid flags in frame header: {:#04x}", flags)
+            }
+            #[cfg(feature = "frame-compression")]
+            FrameError::DecompressionFailure(msg) => {
+                write!(f, "Unable to decompress frame: {}", msg)
+            }
             FrameError::UnsupportedVersion => f.write_str("Unsupported frame version"),
             FrameError::HandshakeFailure(msg) => f.write_str(msg),
         }
@@ -51,7 +84,11 @@ impl std::error::Error for FrameError {
         match self {
             FrameError::IoError(err) => Some(&*err),
             FrameError::InvalidChecksum => None,
-            FrameError::InvalidHeaderLength(_) => None,
+            FrameError::InvalidHeaderLength { .. } => None,
+            #[cfg(feature = "frame-compression")]
+            FrameError::InvalidFlags(_) => None,
+            #[cfg(feature = "frame-compression")]
+            FrameError::DecompressionFailure(_) => None,
             FrameError::UnsupportedVersion => None,
             FrameError::HandshakeFailure(_) => None,
         }
@@ -71,6 +108,20 @@ impl From<io::Error> for FrameError {
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum FrameVersion {
     V1 = 1,
+    /// Adds a flags byte to the header, which marks whether or not the data is compressed
+    #[cfg(feature = "frame-compression")]
+    V2 = 2,
+}
+
+impl FrameVersion {
+    fn from_u16(version: u16) -> Option<Self> {
+        match version {
+            1 => Some(FrameVersion::V1),
+            #[cfg(feature = "frame-compression")]
+            2 => Some(FrameVersion::V2),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for FrameVersion {
@@ -114,35 +165,51 @@ impl Frame {
         };
 
         match frame_header {
-            FrameHeader::V1 { length } => {
-                let mut buffer = vec![0; length as usize];
-                let mut remaining = &mut buffer[..];
-
-                while !remaining.is_empty() {
-                    match reader.read(remaining) {
-                        Ok(0) => break,
-                        Ok(n) => {
-                            let tmp = remaining;
-                            remaining = &mut tmp[n..];
-                        }
-                        Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                        Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                            thread::sleep(Duration::from_millis(100));
-                        }
-                        Err(e) => return Err(FrameError::IoError(e)),
-                    }
-                }
-                if !remaining.is_empty() {
-                    Err(FrameError::IoError(io::Error::new(
-                        io::ErrorKind::UnexpectedEof,
-                        "Could not receive complete frame",
-                    )))
-                } else {
-                    Ok(Self { data: buffer })
+            FrameHeader::V1 { length } => Ok(Self {
+                data: read_data(reader, length)?,
+            }),
+            #[cfg(feature = "frame-compression")]
+            FrameHeader::V2 { length, flags } => {
+                let data = read_data(reader, length)?;
+                if flags & FLAG_COMPRESSED == 0 {
+                    return Ok(Self { data });
                 }
+
+                lz4_flex::decompress_size_prepended(&data)
+                    .map(|data| Self { data })
+                    .map_err(|err| FrameError::DecompressionFailure(err.to_string()))
+            }
+        }
+    }
+}
+
+/// Read exactly `length` bytes of frame data from the given reader.
+fn read_data<R: Read>(reader: &mut R, length: u32) -> Result<Vec<u8>, FrameError> {
+    let mut buffer = vec![0; length as usize];
+    let mut remaining = &mut buffer[..];
+
+    while !remaining.is_empty() {
+        match reader.read(remaining) {
+            Ok(0) => break,
+            Ok(n) => {
+                let tmp = remaining;
+                remaining = &mut tmp[n..];
             }
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => return Err(FrameError::IoError(e)),
         }
     }
+    if !remaining.is_empty() {
+        Err(FrameError::IoError(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Could not receive complete frame",
+        )))
+    } else {
+        Ok(buffer)
+    }
 }
 
 /// A Frame of referenced data to be transmitted using a specified version.
@@ -162,12 +229,38 @@ impl<'a> FrameRef<'a> {
 
     /// Write the frame to the given writer.
     ///
+    /// Version 2 frames compress the data, if it is large enough for compression to be worthwhile
+    /// and the compressed data is smaller than the original.
+    ///
     /// # Errors
     ///
     /// Returns a FrameError if an IO error occurs.
     pub fn write<W: Write>(self, writer: &mut W) -> Result<(), FrameError> {
+        #[cfg(feature = "frame-compression")]
+        let data = match self.version {
+            FrameVersion::V2 if self.data.len() >= COMPRESSION_THRESHOLD => {
+                let compressed = lz4_flex::compress_prepend_size(self.data);
+                if compressed.len() < self.data.len() {
+                    Cow::Owned(compressed)
+                } else {
+                    Cow::Borrowed(self.data)
+                }
+            }
+            _ => Cow::Borrowed(self.data),
+        };
+        #[cfg(not(feature = "frame-compression"))]
+        let data = Cow::Borrowed(self.data);
+
         let frame_header = match self.version {
-            FrameVersion::V1 => FrameHeader::v1(self.data.len() as u32),
+            FrameVersion::V1 => FrameHeader::v1(data.len() as u32),
+            #[cfg(feature = "frame-compression")]
+            FrameVersion::V2 => {
+                let flags = match data {
+                    Cow::Owned(_) => FLAG_COMPRESSED,
+                    Cow::Borrowed(_) => 0,
+                };
+                FrameHeader::v2(data.len() as u32, flags)
+            }
         };
         loop {
             match frame_header.write(writer) {
@@ -180,7 +273,7 @@ impl<'a> FrameRef<'a> {
             }
         }
 
-        let mut buffer = self.data;
+        let mut buffer = &data[..];
         while !buffer.is_empty() {
             match writer.write(buffer) {
                 Ok(0) => {
@@ -207,7 +300,14 @@ impl<'a> FrameRef<'a> {
 /// Each variant corresponds to the implementation for a given version.
 #[derive(Debug, PartialEq)]
 enum FrameHeader {
-    V1 { length: u32 },
+    V1 {
+        length: u32,
+    },
+    #[cfg(feature = "frame-compression")]
+    V2 {
+        length: u32,
+        flags: u8,
+    },
 }
 
 impl FrameHeader {
@@ -216,6 +316,12 @@ impl FrameHeader {
         FrameHeader::V1 { length }
     }
 
+    /// Construct a version 2 frame header.
+    #[cfg(feature = "frame-compression")]
+    fn v2(length: u32, flags: u8) -> Self {
+        FrameHeader::V2 { length, flags }
+    }
+
     /// Read a FrameHeader from the given reader.
     ///
     /// This function uses the first 2 bytes of the stream to read the version, and constructs the
@@ -240,7 +346,10 @@ impl FrameHeader {
 
                 let n = reader.read(&mut cursor.get_mut()[std::mem::size_of::<u16>()..])?;
                 if n != HEADER_LENGTH + 1 - std::mem::size_of::<u16>() {
-                    return Err(FrameError::InvalidHeaderLength(n));
+                    return Err(FrameError::InvalidHeaderLength {
+                        expected: HEADER_LENGTH,
+                        actual: n,
+                    });
                 }
 
                 let checksum = compute_checksum(&cursor.get_ref()[..HEADER_LENGTH]);
@@ -252,6 +361,34 @@ impl FrameHeader {
                     length: cursor.read_u32::<BigEndian>()?,
                 })
             }
+            #[cfg(feature = "frame-compression")]
+            2 => {
+                // Header length + checksum byte
+                let mut buffer = [0u8; V2_HEADER_LENGTH + 1];
+                let mut cursor = Cursor::new(&mut buffer[..]);
+                cursor.write_u16::<BigEndian>(2u16)?;
+
+                let n = reader.read(&mut cursor.get_mut()[std::mem::size_of::<u16>()..])?;
+                if n != V2_HEADER_LENGTH + 1 - std::mem::size_of::<u16>() {
+                    return Err(FrameError::InvalidHeaderLength {
+                        expected: V2_HEADER_LENGTH,
+                        actual: n,
+                    });
+                }
+
+                let checksum = compute_checksum(&cursor.get_ref()[..V2_HEADER_LENGTH]);
+                if checksum != cursor.get_ref()[V2_HEADER_LENGTH] {
+                    return Err(FrameError::InvalidChecksum);
+                }
+
+                let length = cursor.read_u32::<BigEndian>()?;
+                let flags = cursor.read_u8()?;
+                if flags & !FLAG_COMPRESSED != 0 {
+                    return Err(FrameError::InvalidFlags(flags));
+                }
+
+                Ok(FrameHeader::V2 { length, flags })
+            }
             _ => Err(FrameError::UnsupportedVersion),
         }
     }
@@ -279,6 +416,20 @@ impl FrameHeader {
 
                 writer.write_all(&cursor.into_inner()[..])?;
             }
+            #[cfg(feature = "frame-compression")]
+            FrameHeader::V2 { length, flags } => {
+                let mut header_bytes = [0u8; V2_HEADER_LENGTH + 1];
+                let mut cursor = Cursor::new(&mut header_bytes[..]);
+
+                cursor.write_u16::<BigEndian>(2)?;
+                cursor.write_u32::<BigEndian>(length)?;
+                cursor.write_u8(flags)?;
+
+                cursor.get_mut()[V2_HEADER_LENGTH] =
+                    compute_checksum(&cursor.get_ref()[..V2_HEADER_LENGTH]);
+
+                writer.write_all(&cursor.into_inner()[..])?;
+            }
         }
 
         Ok(())
@@ -313,8 +464,10 @@ pub enum FrameNegotiation {
         min: FrameVersion,
         max: FrameVersion,
     },
-    /// The Inbound variant transmits receives the min and max and decides if it should send its
-    /// version or `0`, depending on whether or not it falls in the range.
+    /// The Inbound variant receives the min and max and decides which version it should send,
+    /// given the highest version it supports. It sends the highest version it supports that falls
+    /// in the range, or `0` if there is no such version. Every version up to and including the
+    /// given version is supported, so that older peers fall back to an earlier version.
     Inbound { version: FrameVersion },
 }
 
@@ -324,7 +477,7 @@ impl FrameNegotiation {
         FrameNegotiation::Outbound { min, max }
     }
 
-    /// Construct the inbound side of a negotiation with the given version.
+    /// Construct the inbound side of a negotiation with the given highest supported version.
     pub fn inbound(version: FrameVersion) -> Self {
         FrameNegotiation::Inbound { version }
     }
@@ -349,23 +502,26 @@ impl FrameNegotiation {
 
                 let frame_version = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
 
-                match frame_version {
-                    0 => Err(FrameError::UnsupportedVersion),
-                    1 => Ok(FrameVersion::V1),
-                    _ => Err(FrameError::UnsupportedVersion),
+                if frame_version < min as u16 || frame_version > max as u16 {
+                    return Err(FrameError::UnsupportedVersion);
                 }
+
+                FrameVersion::from_u16(frame_version).ok_or(FrameError::UnsupportedVersion)
             }
             FrameNegotiation::Inbound { version } => {
                 let min = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
                 let max = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
-                if min > version as u16 || max < version as u16 {
-                    stream.write_u16::<BigEndian>(0).map_err(Self::map_io_err)?;
-                    Err(FrameError::UnsupportedVersion)
-                } else {
-                    stream
-                        .write_u16::<BigEndian>(version as u16)
-                        .map_err(Self::map_io_err)?;
-                    Ok(version)
+                match FrameVersion::from_u16(std::cmp::min(version as u16, max)) {
+                    Some(version) if version as u16 >= min => {
+                        stream
+                            .write_u16::<BigEndian>(version as u16)
+                            .map_err(Self::map_io_err)?;
+                        Ok(version)
+                    }
+                    _ => {
+                        stream.write_u16::<BigEndian>(0).map_err(Self::map_io_err)?;
+                        Err(FrameError::UnsupportedVersion)
+                    }
                 }
             }
         }
@@ -498,10 +654,9 @@ mod tests {
             .expect("Unable to write frame header");
 
         header_cursor.set_position(0);
-        let FrameHeader::V1 { length } =
-            FrameHeader::read(&mut header_cursor).expect("Unable to read header");
+        let frame_header = FrameHeader::read(&mut header_cursor).expect("Unable to read header");
 
-        assert_eq!(100, length);
+        assert_eq!(FrameHeader::v1(100), frame_header);
     }
 
     /// Test that outbound frame version negotiation works:
@@ -676,6 +831,140 @@ mod tests {
         assert_eq!(input.to_vec(), frame.data);
     }
 
+    /// Test a round-trip write and read of a v2 FrameHeader, with and without the compressed flag.
+    #[cfg(feature = "frame-compression")]
+    #[test]
+    fn round_trip_v2() {
+        for flags in &[0, FLAG_COMPRESSED] {
+            let mut header_cursor = Cursor::new(vec![0u8; V2_HEADER_LENGTH + 1]);
+
+            let frame_header = FrameHeader::v2(100, *flags);
+
+            frame_header
+                .write(&mut header_cursor)
+                .expect("Unable to write frame header");
+
+            header_cursor.set_position(0);
+            let frame_header =
+                FrameHeader::read(&mut header_cursor).expect("Unable to read header");
+
+            assert_eq!(FrameHeader::v2(100, *flags), frame_header);
+        }
+    }
+
+    /// Test that a v2 frame header with unknown flags set will return an error when read.
+    #[cfg(feature = "frame-compression")]
+    #[test]
+    fn fail_v2_flags() {
+        let mut header_cursor = Cursor::new(vec![0u8; V2_HEADER_LENGTH + 1]);
+
+        FrameHeader::v2(2, 0x80)
+            .write(&mut header_cursor)
+            .expect("Unable to write frame header");
+
+        header_cursor.set_position(0);
+
+        match FrameHeader::read(&mut header_cursor) {
+            Ok(_) => panic!("Should not have produced a frame header"),
+            Err(FrameError::InvalidFlags(0x80)) => (),
+            Err(err) => panic!("Produced invalid error: {}", err),
+        }
+    }
+
+    /// Write a v2 frame with data that is larger than the compression threshold and verify that:
+    /// 1. the header marks the data as compressed
+    /// 2. the data written is smaller than the original
+    /// 3. the original data is read back from the stream
+    #[cfg(feature = "frame-compression")]
+    #[test]
+    fn frame_round_trip_v2_compressed() {
+        let input = b"hello world".repeat(1024);
+        let frame_ref = FrameRef::new(FrameVersion::V2, &input);
+
+        let mut cursor = Cursor::new(vec![]);
+
+        frame_ref.write(&mut cursor).expect("Unable to write data");
+
+        cursor.set_position(0);
+        match FrameHeader::read(&mut cursor).expect("Unable to read header") {
+            FrameHeader::V2 { length, flags } => {
+                assert_eq!(FLAG_COMPRESSED, flags);
+                assert!((length as usize) < input.len());
+            }
+            header => panic!("Unexpected header: {:?}", header),
+        }
+
+        cursor.set_position(0);
+        let frame = Frame::read(&mut cursor).expect("Unable to read frame");
+
+        assert_eq!(input, frame.data);
+    }
+
+    /// Write a v2 frame with data that is smaller than the compression threshold and verify that
+    /// it is sent uncompressed and read back from the stream.
+    #[cfg(feature = "frame-compression")]
+    #[test]
+    fn frame_round_trip_v2_uncompressed() {
+        let input = b"hello world";
+        let frame_ref = FrameRef::new(FrameVersion::V2, input);
+
+        let mut cursor = Cursor::new(vec![]);
+
+        frame_ref.write(&mut cursor).expect("Unable to write data");
+
+        cursor.set_position(0);
+        assert_eq!(
+            FrameHeader::v2(input.len() as u32, 0),
+            FrameHeader::read(&mut cursor).expect("Unable to read header")
+        );
+
+        cursor.set_position(0);
+        let frame = Frame::read(&mut cursor).expect("Unable to read frame");
+
+        assert_eq!(input.to_vec(), frame.data);
+    }
+
+    /// Test that frame version negotiation agrees on the highest version both ends support:
+    /// 1. An outbound end supporting V1-V2 and an inbound end supporting V2 agree on V2
+    /// 2. An outbound end supporting V1-V2 and an older inbound end supporting V1 fall back to V1
+    /// 3. An older outbound end supporting only V1 and an inbound end supporting V2 fall back to
+    ///    V1
+    #[cfg(feature = "frame-compression")]
+    #[test]
+    fn negotiation_v2_fallback() {
+        let cases = [
+            (FrameVersion::V2, FrameVersion::V2, FrameVersion::V2),
+            (FrameVersion::V2, FrameVersion::V1, FrameVersion::V1),
+            (FrameVersion::V1, FrameVersion::V2, FrameVersion::V1),
+        ];
+
+        for (outbound_max, inbound_version, expected) in cases.iter().copied() {
+            let (mut tx, mut rx) = stream::byte_stream_pair();
+
+            let (done_tx, done_rx) = std::sync::mpsc::channel();
+            let join_handle = thread::spawn(move || {
+                let res = FrameNegotiation::inbound(inbound_version)
+                    .negotiate(&mut rx)
+                    .expect("Should have successfully negotiated");
+
+                done_rx.recv().unwrap();
+
+                res
+            });
+
+            let version = FrameNegotiation::outbound(FrameVersion::V1, outbound_max)
+                .negotiate(&mut tx)
+                .expect("Unable to negotiate a valid version");
+
+            done_tx.send(1u8).expect("unable to send stop signal");
+
+            let remote_res = join_handle.join().expect("Unable to join thread");
+
+            assert_eq!(expected, version);
+            assert_eq!(expected, remote_res);
+        }
+    }
+
     #[cfg(not(target_os = "unix"))]
     mod stream {
         use std::io::{Error as IoError, Read, Write};
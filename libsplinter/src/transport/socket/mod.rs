@@ -17,7 +17,9 @@ mod tcp;
 mod tls;
 
 pub use tcp::TcpTransport;
-pub use tls::{TlsConnection, TlsInitError, TlsTransport};
+#[cfg(feature = "ws-transport")]
+pub(crate) use tls::TlsContexts;
+pub use tls::{TlsConnection, TlsInitError, TlsReloader, TlsTransport};
 
 #[cfg(test)]
 pub mod tests {
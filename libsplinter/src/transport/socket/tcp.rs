@@ -21,7 +21,9 @@ use crate::transport::{
     SendError, Transport,
 };
 
-use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+use super::frame::{
+    Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion, MAX_FRAME_VERSION,
+};
 
 const PROTOCOL_PREFIX: &str = "tcp://";
 
@@ -49,7 +51,7 @@ impl Transport for TcpTransport {
         // Connect a std::net::TcpStream to make sure connect() block
         let mut stream = TcpStream::connect(address)?;
 
-        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, MAX_FRAME_VERSION)
             .negotiate(&mut stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => ConnectError::ProtocolError(
@@ -96,13 +98,12 @@ impl Listener for TcpListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (mut stream, _) = self.listener.accept()?;
 
-        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+        let frame_version = FrameNegotiation::inbound(MAX_FRAME_VERSION)
             .negotiate(&mut stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
                     "Local {} protocol version {} not supported by remote",
-                    PROTOCOL_PREFIX,
-                    FrameVersion::V1
+                    PROTOCOL_PREFIX, MAX_FRAME_VERSION
                 )),
                 FrameError::IoError(err) => AcceptError::from(err),
                 err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
@@ -12,20 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-#[cfg(feature = "ws-transport")]
-use std::path::Path;
-
-#[cfg(feature = "ws-transport")]
-use openssl::error::ErrorStack;
-#[cfg(feature = "ws-transport")]
-use openssl::ssl::{SslAcceptor, SslConnector, SslFiletype, SslMethod, SslVerifyMode};
-
 pub struct TlsConfig {
     ca_certs_file: Option<String>,
     server_cert_file: String,
     server_private_key_file: String,
     client_cert_file: String,
     client_private_key_file: String,
+    crl_file: Option<String>,
 }
 
 impl TlsConfig {
@@ -48,6 +41,10 @@ impl TlsConfig {
     pub fn client_private_key_file(&self) -> &str {
         &self.client_private_key_file
     }
+
+    pub fn crl_file(&self) -> &Option<String> {
+        &self.crl_file
+    }
 }
 
 #[derive(Default)]
@@ -57,6 +54,7 @@ pub struct TlsConfigBuilder {
     server_private_key_file: Option<String>,
     client_cert_file: Option<String>,
     client_private_key_file: Option<String>,
+    crl_file: Option<String>,
 }
 
 impl TlsConfigBuilder {
@@ -67,6 +65,7 @@ impl TlsConfigBuilder {
             server_private_key_file: None,
             client_cert_file: None,
             client_private_key_file: None,
+            crl_file: None,
         }
     }
 
@@ -95,6 +94,11 @@ impl TlsConfigBuilder {
         self
     }
 
+    pub fn with_crl_file(mut self, crl_file: String) -> Self {
+        self.crl_file = Some(crl_file);
+        self
+    }
+
     pub fn build(self) -> Result<TlsConfig, TlsConfigBuilderError> {
         let ca_certs_file = self.ca_certs_file;
         let server_cert_file = self
@@ -116,6 +120,7 @@ impl TlsConfigBuilder {
             server_private_key_file,
             client_cert_file,
             client_private_key_file,
+            crl_file: self.crl_file,
         })
     }
 }
@@ -137,46 +142,6 @@ impl std::fmt::Display for TlsConfigBuilderError {
     }
 }
 
-#[cfg(feature = "ws-transport")]
-pub(super) fn build_connector(config: &TlsConfig) -> Result<SslConnector, ErrorStack> {
-    let mut builder = SslConnector::builder(SslMethod::tls())?;
-
-    builder.set_private_key_file(
-        Path::new(config.client_private_key_file()),
-        SslFiletype::PEM,
-    )?;
-    builder.set_certificate_chain_file(Path::new(config.client_cert_file()))?;
-    builder.check_private_key()?;
-
-    if let Some(ca_certs_file) = config.ca_certs_file() {
-        builder.set_ca_file(Path::new(ca_certs_file))?;
-    } else {
-        builder.set_verify(SslVerifyMode::NONE);
-    }
-
-    Ok(builder.build())
-}
-
-#[cfg(feature = "ws-transport")]
-pub(super) fn build_acceptor(config: &TlsConfig) -> Result<SslAcceptor, ErrorStack> {
-    let mut builder = SslAcceptor::mozilla_modern(SslMethod::tls())?;
-
-    builder.set_private_key_file(
-        Path::new(config.server_private_key_file()),
-        SslFiletype::PEM,
-    )?;
-    builder.set_certificate_chain_file(Path::new(config.server_cert_file()))?;
-    builder.check_private_key()?;
-
-    if let Some(ca_certs_file) = config.ca_certs_file() {
-        builder.set_ca_file(Path::new(ca_certs_file))?;
-    } else {
-        builder.set_verify(SslVerifyMode::NONE);
-    }
-
-    Ok(builder.build())
-}
-
 #[cfg(test)]
 pub(super) mod tests {
     use openssl::asn1::Asn1Time;
@@ -13,12 +13,13 @@
 // limitations under the License.
 
 use std::net::TcpListener;
+use std::sync::{Arc, RwLock};
 use std::thread;
 use std::time::Duration;
 
-use openssl::ssl::SslAcceptor;
 use tungstenite::{accept, handshake::HandshakeError};
 
+use crate::transport::socket::TlsContexts;
 use crate::transport::{AcceptError, Connection, Listener};
 
 use super::connection::WsConnection;
@@ -28,19 +29,19 @@ use super::transport::WS_PROTOCOL_PREFIX;
 pub(super) struct WsListener {
     listener: TcpListener,
     local_endpoint: String,
-    acceptor: Option<SslAcceptor>,
+    tls_contexts: Option<Arc<RwLock<TlsContexts>>>,
 }
 
 impl WsListener {
     pub fn new(
         listener: TcpListener,
         local_endpoint: String,
-        acceptor: Option<SslAcceptor>,
+        tls_contexts: Option<Arc<RwLock<TlsContexts>>>,
     ) -> Self {
         WsListener {
             listener,
             local_endpoint,
-            acceptor,
+            tls_contexts,
         }
     }
 }
@@ -49,7 +50,13 @@ impl Listener for WsListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (stream, _) = self.listener.accept()?;
 
-        if let Some(acceptor) = &self.acceptor {
+        if let Some(tls_contexts) = &self.tls_contexts {
+            // read the acceptor for each connection, so a reloaded acceptor is used
+            let acceptor = tls_contexts
+                .read()
+                .map_err(|_| AcceptError::ProtocolError("TLS contexts lock was poisoned".into()))?
+                .acceptor
+                .clone();
             let remote_endpoint = format!("{}{}", WSS_PROTOCOL_PREFIX, stream.peer_addr()?);
 
             let websocket = accept(acceptor.accept(stream)?).map_or_else(
@@ -17,11 +17,11 @@ use std::thread;
 use std::time::Duration;
 
 use openssl::error::ErrorStack;
-use openssl::ssl::{SslAcceptor, SslConnector};
 use tungstenite::{client, handshake::HandshakeError};
 use url::{ParseError, Url};
 
-use crate::transport::tls::{build_acceptor, build_connector, TlsConfig};
+use crate::transport::socket::{TlsInitError, TlsReloader};
+use crate::transport::tls::TlsConfig;
 use crate::transport::{ConnectError, Connection, ListenError, Listener, Transport};
 
 use super::connection::WsConnection;
@@ -30,11 +30,6 @@ use super::listener::WsListener;
 pub(super) const WS_PROTOCOL_PREFIX: &str = "ws://";
 pub(super) const WSS_PROTOCOL_PREFIX: &str = "wss://";
 
-struct TlsInner {
-    acceptor: SslAcceptor,
-    connector: SslConnector,
-}
-
 /// A WebSocket-based `Transport`.
 ///
 /// Supports endpoints of the format `ws://ip_or_host:port`.
@@ -95,22 +90,26 @@ struct TlsInner {
 /// ```
 #[derive(Default)]
 pub struct WsTransport {
-    tls_inner: Option<TlsInner>,
+    tls: Option<TlsReloader>,
 }
 
 impl WsTransport {
     pub fn new(config: Option<&TlsConfig>) -> Result<Self, WsInitError> {
         if let Some(conf) = config {
             Ok(WsTransport {
-                tls_inner: Some(TlsInner {
-                    acceptor: build_acceptor(conf)?,
-                    connector: build_connector(conf)?,
-                }),
+                tls: Some(TlsReloader::from_config(conf)?),
             })
         } else {
-            Ok(WsTransport { tls_inner: None })
+            Ok(WsTransport { tls: None })
         }
     }
+
+    /// Returns a `TlsReloader` that can be used to reload the transport's certificates, keys and
+    /// certificate revocation list after the transport has been started, or `None` if TLS is not
+    /// configured.
+    pub fn reloader(&self) -> Option<TlsReloader> {
+        self.tls.clone()
+    }
 }
 
 fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
@@ -169,8 +168,8 @@ impl Transport for WsTransport {
             let remote_endpoint = format!("{}{}", WSS_PROTOCOL_PREFIX, stream.peer_addr()?);
             let local_endpoint = format!("{}{}", WSS_PROTOCOL_PREFIX, stream.local_addr()?);
 
-            let tls_stream = self
-                .tls_inner
+            let connector = self
+                .tls
                 .as_ref()
                 .ok_or_else(|| {
                     ConnectError::ProtocolError(format!(
@@ -178,8 +177,12 @@ impl Transport for WsTransport {
                         WSS_PROTOCOL_PREFIX
                     ))
                 })?
+                .contexts()
+                .read()
+                .map_err(|_| ConnectError::ProtocolError("TLS contexts lock was poisoned".into()))?
                 .connector
-                .connect(&dns_name, stream)?;
+                .clone();
+            let tls_stream = connector.connect(&dns_name, stream)?;
 
             let (websocket, _) = client(endpoint, tls_stream).map_or_else(
                 {
@@ -231,7 +234,7 @@ impl Transport for WsTransport {
                 None,
             )))
         } else if let Some(address) = bind.strip_prefix(WSS_PROTOCOL_PREFIX) {
-            let inner = self.tls_inner.as_ref().ok_or_else(|| {
+            let tls = self.tls.as_ref().ok_or_else(|| {
                 ListenError::ProtocolError(
                     "TLS support required for the wss:// protocol".to_string(),
                 )
@@ -251,7 +254,7 @@ impl Transport for WsTransport {
             Ok(Box::new(WsListener::new(
                 tcp_listener,
                 local_endpoint,
-                Some(inner.acceptor.clone()),
+                Some(tls.contexts().clone()),
             )))
         } else {
             Err(ListenError::ProtocolError(format!(
@@ -286,6 +289,12 @@ impl std::fmt::Display for WsInitError {
     }
 }
 
+impl From<TlsInitError> for WsInitError {
+    fn from(error: TlsInitError) -> Self {
+        WsInitError::ProtocolError(error.to_string())
+    }
+}
+
 impl From<ErrorStack> for WsInitError {
     fn from(error: ErrorStack) -> Self {
         WsInitError::ProtocolError(format!("OpenSSL error: {}", error))
@@ -0,0 +1,33 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS scabbard_raft_state (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    current_term     BIGINT NOT NULL,
+    voted_for        BYTEA,
+    applied_index    BIGINT NOT NULL,
+    PRIMARY KEY (circuit_id, service_id)
+);
+
+CREATE TABLE IF NOT EXISTS scabbard_raft_log (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    log_index        BIGINT NOT NULL,
+    term             BIGINT NOT NULL,
+    proposal         BYTEA,
+    proposal_data    BYTEA NOT NULL,
+    PRIMARY KEY (circuit_id, service_id, log_index)
+);
@@ -0,0 +1,54 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS scabbard_v3_service (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    status           TEXT NOT NULL,
+    coordinator      TEXT NOT NULL,
+    epoch            BIGINT NOT NULL,
+    consensus_state  TEXT NOT NULL,
+    alarm            BIGINT,
+    admin_keys       TEXT NOT NULL,
+    PRIMARY KEY (circuit_id, service_id)
+);
+
+CREATE TABLE IF NOT EXISTS scabbard_v3_peer (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    peer_service_id  TEXT NOT NULL,
+    position         INTEGER NOT NULL,
+    PRIMARY KEY (circuit_id, service_id, peer_service_id)
+);
+
+CREATE TABLE IF NOT EXISTS scabbard_v3_vote (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    peer_service_id  TEXT NOT NULL,
+    vote             BOOLEAN NOT NULL,
+    PRIMARY KEY (circuit_id, service_id, peer_service_id)
+);
+
+CREATE TABLE IF NOT EXISTS scabbard_v3_batch (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    batch_id         TEXT NOT NULL,
+    position         BIGINT NOT NULL,
+    batch            BINARY NOT NULL,
+    status           TEXT NOT NULL,
+    epoch            BIGINT,
+    state_root       TEXT,
+    PRIMARY KEY (circuit_id, service_id, batch_id)
+);
@@ -0,0 +1,37 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS scabbard_two_phase_in_flight (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    proposal         BINARY NOT NULL,
+    proposal_data    BINARY NOT NULL,
+    PRIMARY KEY (circuit_id, service_id)
+);
+
+CREATE TABLE IF NOT EXISTS scabbard_two_phase_verified_peer (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    peer_id          BINARY NOT NULL,
+    PRIMARY KEY (circuit_id, service_id, peer_id)
+);
+
+CREATE TABLE IF NOT EXISTS scabbard_two_phase_decision (
+    circuit_id       TEXT NOT NULL,
+    service_id       TEXT NOT NULL,
+    proposal_id      BINARY NOT NULL,
+    decision         TEXT NOT NULL,
+    PRIMARY KEY (circuit_id, service_id, proposal_id)
+);
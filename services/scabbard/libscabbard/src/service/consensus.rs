@@ -19,6 +19,8 @@ use std::thread::{Builder, JoinHandle};
 use std::time::Duration;
 
 use protobuf::Message;
+#[cfg(feature = "consensus-raft")]
+use splinter::consensus::raft::RaftEngine;
 use splinter::consensus::{
     error::{ConsensusSendError, ProposalManagerError},
     two_phase::v1::TwoPhaseEngine as TwoPhaseEngineV1,
@@ -26,14 +28,15 @@ use splinter::consensus::{
     ConsensusEngine, ConsensusMessage, ConsensusNetworkSender, PeerId, Proposal, ProposalId,
     ProposalManager, ProposalUpdate, StartupState,
 };
-use transact::protos::IntoBytes;
+use transact::protocol::batch::BatchPair;
+use transact::protos::{FromBytes, IntoBytes};
 
 use crate::protos::scabbard::{ProposedBatch, ScabbardMessage, ScabbardMessage_Type};
 
 use super::error::{ScabbardConsensusManagerError, ScabbardError};
 use super::shared::ScabbardShared;
 use super::state::ScabbardState;
-use super::ScabbardVersion;
+use super::{ScabbardConsensus, ScabbardVersion};
 
 /// Component used by the service to manage and interact with consenus
 pub struct ScabbardConsensusManager {
@@ -52,6 +55,7 @@ impl ScabbardConsensusManager {
         state: Arc<Mutex<ScabbardState>>,
         // The coordinator timeout for the two-phase commit consensus engine
         coordinator_timeout: Duration,
+        consensus_type: ScabbardConsensus,
     ) -> Result<Self, ScabbardConsensusManagerError> {
         let peer_ids = shared
             .lock()
@@ -81,8 +85,21 @@ impl ScabbardConsensusManager {
 
         let thread_handle = Builder::new()
             .name(format!("consensus-{}", service_id))
-            .spawn(move || match version {
-                ScabbardVersion::V1 => {
+            .spawn(move || match (consensus_type, version) {
+                #[cfg(feature = "consensus-raft")]
+                (ScabbardConsensus::Raft(store), _) => {
+                    let mut raft_engine = RaftEngine::new(store);
+                    if let Err(err) = raft_engine.run(
+                        consensus_msg_rx,
+                        proposal_update_rx,
+                        Box::new(consensus_network_sender),
+                        Box::new(proposal_manager),
+                        startup_state,
+                    ) {
+                        error!("raft consensus exited with an error: {}", err)
+                    }
+                }
+                (ScabbardConsensus::TwoPhaseCommit(_), ScabbardVersion::V1) => {
                     let mut two_phase_engine = TwoPhaseEngineV1::new(coordinator_timeout);
                     if let Err(err) = two_phase_engine.run(
                         consensus_msg_rx,
@@ -94,8 +111,9 @@ impl ScabbardConsensusManager {
                         error!("two phase consensus exited with an error: {}", err)
                     }
                 }
-                ScabbardVersion::V2 => {
-                    let mut two_phase_engine = TwoPhaseEngineV2::new(coordinator_timeout);
+                (ScabbardConsensus::TwoPhaseCommit(store), ScabbardVersion::V2) => {
+                    let mut two_phase_engine =
+                        TwoPhaseEngineV2::new_with_store(coordinator_timeout, store);
                     if let Err(err) = two_phase_engine.run(
                         consensus_msg_rx,
                         proposal_update_rx,
@@ -330,6 +348,36 @@ impl ProposalManager for ScabbardProposalManager {
 
         Ok(())
     }
+
+    fn get_proposal_data(&self, id: &ProposalId) -> Result<Option<Vec<u8>>, ProposalManagerError> {
+        self.shared
+            .lock()
+            .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?
+            .get_open_proposal(id)
+            .map(|(_, batch)| batch.clone().into_bytes())
+            .transpose()
+            .map_err(|err| ProposalManagerError::Internal(Box::new(err)))
+    }
+
+    fn add_proposal_data(
+        &self,
+        proposal: &Proposal,
+        data: Vec<u8>,
+    ) -> Result<(), ProposalManagerError> {
+        let mut shared = self
+            .shared
+            .lock()
+            .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
+
+        // The batch may already have been received from the service that proposed it
+        if shared.get_open_proposal(&proposal.id).is_none() {
+            let batch = BatchPair::from_bytes(&data)
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+            shared.add_open_proposal(proposal.clone(), batch);
+        }
+
+        Ok(())
+    }
 }
 
 pub struct ScabbardConsensusNetworkSender {
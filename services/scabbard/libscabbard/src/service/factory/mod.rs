@@ -32,6 +32,13 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use sawtooth::receipt::store::diesel::DieselReceiptStore;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use sawtooth::receipt::store::ReceiptStore;
+#[cfg(all(
+    feature = "consensus-raft",
+    any(feature = "postgres", feature = "sqlite")
+))]
+use splinter::consensus::raft::RaftStore;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use splinter::consensus::two_phase::v2::TwoPhaseStore;
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 use splinter::error::InternalError;
 use splinter::error::{InvalidArgumentError, InvalidStateError};
@@ -53,7 +60,7 @@ use crate::service::ScabbardStatePurgeHandler;
 use crate::service::{
     error::ScabbardError,
     state::merkle_state::{self, MerkleState, MerkleStateConfig},
-    Scabbard, ScabbardVersion, SERVICE_TYPE,
+    Scabbard, ScabbardConsensus, ScabbardVersion, SERVICE_TYPE,
 };
 #[cfg(feature = "diesel")]
 use crate::store::diesel::DieselCommitHashStore;
@@ -61,6 +68,13 @@ use crate::store::diesel::DieselCommitHashStore;
 use crate::store::transact::factory::{LmdbDatabaseFactory, LmdbDatabasePurgeHandle};
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use crate::store::CommitHashStore;
+#[cfg(all(
+    feature = "consensus-raft",
+    any(feature = "postgres", feature = "sqlite")
+))]
+use crate::store::DieselRaftStore;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use crate::store::DieselTwoPhaseStore;
 
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 const DEFAULT_LMDB_DIR: &str = "/var/lib/splinter";
@@ -535,6 +549,27 @@ impl ServiceArgValidator for ScabbardArgValidator {
             }
         }
 
+        if let Some(consensus) = args.get("consensus") {
+            match consensus.as_str() {
+                "2pc" => (),
+                #[cfg(feature = "consensus-raft")]
+                "raft" => {
+                    if args.get("version").map(String::as_str).unwrap_or("1") != "1" {
+                        return Err(InvalidArgumentError::new(
+                            "consensus",
+                            "raft consensus is only supported by scabbard version 1",
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(InvalidArgumentError::new(
+                        "consensus",
+                        format!("unsupported consensus: {}", consensus),
+                    ))
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -551,6 +586,8 @@ impl ServiceFactory for ScabbardFactory {
     ///   will share state with
     ///
     /// `args` may include the following optional entries:
+    /// - `consensus`: the consensus algorithm to use (possible values: "2pc", or "raft" if the
+    ///   `consensus-raft` feature is enabled) (default: "2pc"); Raft requires version "1"
     /// - `coordinator_timeout`: the length of time (in milliseconds) that the network has to
     ///   commit a proposal before the coordinator rejects it (if not provided, default is 30
     ///   seconds)
@@ -690,6 +727,26 @@ impl ScabbardFactory {
             .transpose()?;
         let version = ScabbardVersion::try_from(args.get("version").map(String::as_str))
             .map_err(FactoryCreateError::InvalidArguments)?;
+        let consensus = match args.get("consensus").map(String::as_str) {
+            None | Some("2pc") => ScabbardConsensus::TwoPhaseCommit(
+                self.create_two_phase_store(circuit_id, &service_id),
+            ),
+            #[cfg(feature = "consensus-raft")]
+            Some("raft") => {
+                if version != ScabbardVersion::V1 {
+                    return Err(FactoryCreateError::InvalidArguments(
+                        "raft consensus is only supported by scabbard version 1".into(),
+                    ));
+                }
+                ScabbardConsensus::Raft(self.create_raft_store(circuit_id, &service_id))
+            }
+            Some(consensus) => {
+                return Err(FactoryCreateError::InvalidArguments(format!(
+                    "unsupported consensus: {}",
+                    consensus
+                )))
+            }
+        };
 
         #[cfg(feature = "lmdb")]
         let (merkle_state, state_purge): (_, Box<dyn ScabbardStatePurgeHandler>) =
@@ -787,10 +844,60 @@ impl ScabbardFactory {
                 .new_verifier(),
             admin_keys,
             coordinator_timeout,
+            consensus,
         )
         .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))
     }
 
+    /// Create the store for the state of a service's two-phase commit consensus engine.
+    #[cfg(any(feature = "postgres", feature = "sqlite"))]
+    fn create_two_phase_store(&self, circuit_id: &str, service_id: &str) -> Box<dyn TwoPhaseStore> {
+        match &self.store_factory_config {
+            #[cfg(feature = "postgres")]
+            ScabbardFactoryStorageConfig::Postgres { pool } => Box::new(DieselTwoPhaseStore::new(
+                pool.clone(),
+                circuit_id,
+                service_id,
+            )),
+            #[cfg(feature = "sqlite")]
+            ScabbardFactoryStorageConfig::Sqlite { pool } => Box::new(DieselTwoPhaseStore::new(
+                pool.clone(),
+                circuit_id,
+                service_id,
+            )),
+            #[cfg(feature = "sqlite")]
+            ScabbardFactoryStorageConfig::SqliteExclusiveWrites { pool } => {
+                Box::new(DieselTwoPhaseStore::new_with_write_exclusivity(
+                    pool.clone(),
+                    circuit_id,
+                    service_id,
+                ))
+            }
+        }
+    }
+
+    /// Create the store for the state of a service's Raft consensus engine.
+    #[cfg(all(
+        feature = "consensus-raft",
+        any(feature = "postgres", feature = "sqlite")
+    ))]
+    fn create_raft_store(&self, circuit_id: &str, service_id: &str) -> Box<dyn RaftStore> {
+        match &self.store_factory_config {
+            #[cfg(feature = "postgres")]
+            ScabbardFactoryStorageConfig::Postgres { pool } => {
+                Box::new(DieselRaftStore::new(pool.clone(), circuit_id, service_id))
+            }
+            #[cfg(feature = "sqlite")]
+            ScabbardFactoryStorageConfig::Sqlite { pool } => {
+                Box::new(DieselRaftStore::new(pool.clone(), circuit_id, service_id))
+            }
+            #[cfg(feature = "sqlite")]
+            ScabbardFactoryStorageConfig::SqliteExclusiveWrites { pool } => Box::new(
+                DieselRaftStore::new_with_write_exclusivity(pool.clone(), circuit_id, service_id),
+            ),
+        }
+    }
+
     /// Check that the LMDB files doesn't exist for the given service.
     #[cfg(feature = "lmdb")]
     #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
@@ -1057,6 +1164,49 @@ mod tests {
         assert_eq!(scabbard.coordinator_timeout, Duration::from_millis(123));
     }
 
+    /// Verify that the `consensus` service argument selects Raft for a new `Scabbard` instance,
+    /// and that Raft is rejected for scabbard version 2.
+    #[cfg(feature = "consensus-raft")]
+    #[test]
+    fn create_with_raft_consensus() {
+        let factory = get_factory();
+        let mut args = get_mock_args();
+        args.insert("consensus".into(), "raft".into());
+
+        let service = factory
+            .create("".into(), "", "", args.clone())
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+
+        assert!(matches!(
+            scabbard.consensus_type,
+            ScabbardConsensus::Raft(_)
+        ));
+
+        args.insert("version".into(), "2".into());
+        assert!(
+            factory.create("".into(), "", "", args).is_err(),
+            "Creating scabbard v2 with raft consensus did not fail"
+        );
+    }
+
+    /// Verify that `Scabbard` creation fails when the `consensus` argument is not supported.
+    #[test]
+    fn create_with_unsupported_consensus() {
+        let factory = get_factory();
+        let mut args = get_mock_args();
+        args.insert("consensus".into(), "pbft".into());
+
+        assert!(
+            factory.create("".into(), "", "", args.clone()).is_err(),
+            "Creating scabbard with unsupported consensus did not fail"
+        );
+        assert!(ScabbardArgValidator.validate(&args).is_err());
+    }
+
     /// Verify that `Scabbard` creation fails when the `peer_services` argument isn't specified.
     #[test]
     fn create_without_peer_services() {
@@ -14,7 +14,7 @@
 
 //! Scabbard is a Splinter `Service` that runs the Sawtooth Sabre smart contract engine using the
 //! `transact` library for state. Scabbard uses two-phase consensus to reach agreement on
-//! transactions.
+//! transactions; with the "consensus-raft" feature, Raft consensus may be used instead.
 
 mod consensus;
 mod error;
@@ -23,6 +23,7 @@ pub(crate) mod factory;
 mod rest_api;
 mod shared;
 mod state;
+mod state_sync;
 #[cfg(feature = "scabbardv3")]
 pub mod v3;
 
@@ -35,8 +36,10 @@ use std::time::Duration;
 use cylinder::Verifier as SignatureVerifier;
 use protobuf::Message;
 use sawtooth::receipt::store::ReceiptStore;
+#[cfg(feature = "consensus-raft")]
+use splinter::consensus::raft::RaftStore;
 use splinter::{
-    consensus::{Proposal, ProposalUpdate},
+    consensus::{two_phase::v2::TwoPhaseStore, Proposal, ProposalUpdate},
     orchestrator::OrchestratableService,
     service::instance::{
         ServiceDestroyError, ServiceError, ServiceInstance, ServiceMessageContext,
@@ -50,7 +53,9 @@ use transact::{
 
 use crate::store::CommitHashStore;
 
-use super::protos::scabbard::{ScabbardMessage, ScabbardMessage_Type};
+use super::protos::scabbard::{
+    ScabbardMessage, ScabbardMessage_Type, StateEntry, StateSyncRequest, StateSyncResponse,
+};
 
 use consensus::ScabbardConsensusManager;
 use error::ScabbardError;
@@ -63,6 +68,7 @@ pub use state::{
     BatchInfo, BatchInfoIter, BatchStatus, Events, StateChange, StateChangeEvent, StateIter,
 };
 use state::{ScabbardState, StateSubscriber};
+use state_sync::{StateSync, MAX_CHUNK_SIZE};
 
 const SERVICE_TYPE: &str = "scabbard";
 
@@ -88,6 +94,18 @@ impl TryFrom<Option<&str>> for ScabbardVersion {
     }
 }
 
+/// Specifies the consensus algorithm scabbard uses to agree on batches.
+#[derive(Clone)]
+pub enum ScabbardConsensus {
+    /// Two-phase commit, which requires every service to agree on each batch; the engine's state
+    /// is saved in the given store when using scabbard version 2.
+    TwoPhaseCommit(Box<dyn TwoPhaseStore>),
+    /// Raft, which only requires a majority of the services to be available; the engine's state
+    /// is saved in the given store. Only supported by scabbard version 1.
+    #[cfg(feature = "consensus-raft")]
+    Raft(Box<dyn RaftStore>),
+}
+
 /// A handler for purging a scabbard instances state
 pub trait ScabbardStatePurgeHandler: Send + Sync {
     /// Purge the scabbard instances state.
@@ -105,7 +123,9 @@ pub struct Scabbard {
     purge_handler: Arc<dyn ScabbardStatePurgeHandler>,
     /// The coordinator timeout for the two-phase commit consensus engine
     coordinator_timeout: Duration,
+    consensus_type: ScabbardConsensus,
     consensus: Arc<Mutex<Option<ScabbardConsensusManager>>>,
+    state_sync: Arc<Mutex<StateSync>>,
 }
 
 impl Scabbard {
@@ -128,6 +148,8 @@ impl Scabbard {
         // The coordinator timeout for the two-phase commit consensus engine; if `None`, the
         // default value will be used (30 seconds).
         coordinator_timeout: Option<Duration>,
+        // The consensus algorithm to use
+        consensus_type: ScabbardConsensus,
     ) -> Result<Self, ScabbardError> {
         let shared = ScabbardShared::new(
             VecDeque::new(),
@@ -163,7 +185,9 @@ impl Scabbard {
             state: Arc::new(Mutex::new(state)),
             purge_handler: purge_handler.into(),
             coordinator_timeout,
+            consensus_type,
             consensus: Arc::new(Mutex::new(None)),
+            state_sync: Arc::new(Mutex::new(StateSync::default())),
         })
     }
 
@@ -307,6 +331,231 @@ impl Scabbard {
 
         Ok(())
     }
+
+    /// Send a request for the current state root to all peer services. The state is synced once
+    /// a majority of the peers report the same state root; if the peers have no state beyond the
+    /// initial state, the responses are ignored.
+    fn request_state_sync(&self) -> Result<(), ScabbardError> {
+        let shared = self
+            .shared
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?;
+
+        *self
+            .state_sync
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)? =
+            StateSync::new(shared.peer_services().len());
+
+        request_state_roots(&shared)
+    }
+
+    /// Send the peer service that requested it either this service's current state root, or the
+    /// next chunk of entries of the requested state root.
+    fn send_state_sync_response(
+        &self,
+        recipient: &str,
+        request: StateSyncRequest,
+    ) -> Result<(), ScabbardError> {
+        let shared = self
+            .shared
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?;
+
+        if !shared.peer_services().contains(recipient) {
+            warn!("Ignoring state sync request from non-peer {}", recipient);
+            return Ok(());
+        }
+
+        let mut response = StateSyncResponse::new();
+        {
+            let state = self.state.lock().map_err(|_| ScabbardError::LockPoisoned)?;
+            if request.get_state_root().is_empty() {
+                response.set_state_root(state.current_state_root().to_string());
+                response.set_root_only(true);
+            } else {
+                // Entries are read from the requested root, so every chunk is consistent even if
+                // this service commits a batch during the transfer
+                response.set_state_root(request.get_state_root().to_string());
+                let mut entries = state
+                    .get_state_at_root(request.get_state_root())?
+                    .skip_while(|entry| match entry {
+                        Ok((address, _)) => {
+                            !request.get_start_after().is_empty()
+                                && address.as_str() <= request.get_start_after()
+                        }
+                        Err(_) => false,
+                    })
+                    .peekable();
+
+                let mut chunk = vec![];
+                let mut chunk_size = 0;
+                while chunk_size < MAX_CHUNK_SIZE {
+                    let (address, value) = match entries.next() {
+                        Some(entry) => entry?,
+                        None => break,
+                    };
+                    chunk_size += address.len() + value.len();
+                    let mut state_entry = StateEntry::new();
+                    state_entry.set_address(address);
+                    state_entry.set_value(value);
+                    chunk.push(state_entry);
+                }
+                response.set_has_more(entries.peek().is_some());
+                response.set_entries(chunk.into());
+            }
+        }
+
+        let mut msg = ScabbardMessage::new();
+        msg.set_message_type(ScabbardMessage_Type::STATE_SYNC_RESPONSE);
+        msg.set_state_sync_response(response);
+        let msg_bytes = msg
+            .write_to_bytes()
+            .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
+
+        shared
+            .network_sender()
+            .ok_or(ScabbardError::NotConnected)?
+            .send(recipient, msg_bytes.as_slice())
+            .map_err(|err| ScabbardError::Internal(Box::new(err)))
+    }
+
+    /// Handle a state sync response from a peer service: either record the peer's state root,
+    /// or add a chunk of entries of the agreed state root and request the next one. The initial
+    /// state is replaced once all entries have been received and they produce the agreed root.
+    fn sync_state(
+        &self,
+        sender: &str,
+        mut response: StateSyncResponse,
+    ) -> Result<(), ScabbardError> {
+        let shared = self
+            .shared
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?;
+        if !shared.peer_services().contains(sender) {
+            warn!("Ignoring state sync response from non-peer {}", sender);
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().map_err(|_| ScabbardError::LockPoisoned)?;
+        if !state.is_genesis_state() {
+            return Ok(());
+        }
+
+        let mut state_sync = self
+            .state_sync
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?;
+        let network_sender = shared.network_sender().ok_or(ScabbardError::NotConnected)?;
+
+        if response.get_root_only() {
+            if let Some(transfer) = state_sync.announce_root(sender, response.get_state_root()) {
+                // The peers have no state beyond the initial state
+                if transfer.state_root == state.current_state_root() {
+                    state_sync.finish();
+                    return Ok(());
+                }
+
+                let mut request = StateSyncRequest::new();
+                request.set_state_root(transfer.state_root.clone());
+                network_sender
+                    .send(&transfer.peer, &state_sync_request_bytes(request)?)
+                    .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
+            } else if state_sync.take_stalled() {
+                debug!("Peers do not agree on a state root yet; requesting state roots again");
+                request_state_roots(&shared)?;
+            }
+            return Ok(());
+        }
+
+        let transfer = match state_sync.transfer_mut(sender, response.get_state_root()) {
+            Some(transfer) => transfer,
+            None => {
+                warn!(
+                    "Ignoring unexpected state sync response from {} for state root {}",
+                    sender,
+                    response.get_state_root()
+                );
+                return Ok(());
+            }
+        };
+
+        let entries = response
+            .take_entries()
+            .into_iter()
+            .map(|mut entry| (entry.take_address(), entry.take_value()))
+            .collect::<Vec<_>>();
+        let last_address = entries.last().map(|(address, _)| address.clone());
+
+        let result = state.apply_state_sync_chunk(transfer.partial_state_root.as_deref(), entries);
+        match result {
+            Ok(partial_state_root) => transfer.partial_state_root = Some(partial_state_root),
+            Err(err) => {
+                error!("Unable to sync state from {}: {}", sender, err);
+                state_sync.reset();
+                return request_state_roots(&shared);
+            }
+        }
+
+        match last_address {
+            Some(last_address) if response.get_has_more() => {
+                let mut request = StateSyncRequest::new();
+                request.set_state_root(transfer.state_root.clone());
+                request.set_start_after(last_address);
+                network_sender
+                    .send(sender, &state_sync_request_bytes(request)?)
+                    .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
+            }
+            _ => {
+                let result = state.finish_state_sync(
+                    transfer.partial_state_root.as_deref(),
+                    &transfer.state_root,
+                );
+                let state_root = transfer.state_root.clone();
+                match result {
+                    Ok(synced) => {
+                        state_sync.finish();
+                        if synced {
+                            info!(
+                                "Synced state of {} from {} at state root {}",
+                                self.service_id, sender, state_root
+                            );
+                        }
+                    }
+                    // The peer sent entries that do not match the root its peers agreed on
+                    Err(err) => {
+                        error!("Unable to sync state from {}: {}", sender, err);
+                        state_sync.reset();
+                        request_state_roots(&shared)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Send a request for the current state root to all peer services.
+fn request_state_roots(shared: &ScabbardShared) -> Result<(), ScabbardError> {
+    let msg_bytes = state_sync_request_bytes(StateSyncRequest::new())?;
+    let network_sender = shared.network_sender().ok_or(ScabbardError::NotConnected)?;
+    for peer in shared.peer_services() {
+        // Peers may not be running yet, in which case they will have the initial state too
+        if let Err(err) = network_sender.send(peer, msg_bytes.as_slice()) {
+            debug!("Unable to request state sync from {}: {}", peer, err);
+        }
+    }
+
+    Ok(())
+}
+
+fn state_sync_request_bytes(request: StateSyncRequest) -> Result<Vec<u8>, ScabbardError> {
+    let mut msg = ScabbardMessage::new();
+    msg.set_message_type(ScabbardMessage_Type::STATE_SYNC_REQUEST);
+    msg.set_state_sync_request(request);
+    msg.write_to_bytes()
+        .map_err(|err| ScabbardError::Internal(Box::new(err)))
 }
 
 impl ServiceInstance for Scabbard {
@@ -350,12 +599,26 @@ impl ServiceInstance for Scabbard {
                 self.shared.clone(),
                 self.state.clone(),
                 self.coordinator_timeout,
+                self.consensus_type.clone(),
             )
             .map_err(|err| {
                 ServiceStartError::Internal(format!("Unable to start consensus: {}", err))
             })?,
         );
 
+        // A service that was added to an existing circuit starts with the initial state, so it
+        // must request the current state from its peers
+        if self.version == ScabbardVersion::V2
+            && self
+                .state
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("state lock poisoned".into()))?
+                .is_genesis_state()
+        {
+            self.request_state_sync()
+                .map_err(|err| ServiceStartError::Internal(err.to_string()))?;
+        }
+
         Ok(())
     }
 
@@ -414,7 +677,7 @@ impl ServiceInstance for Scabbard {
     fn handle_message(
         &self,
         message_bytes: &[u8],
-        _message_context: &ServiceMessageContext,
+        message_context: &ServiceMessageContext,
     ) -> Result<(), ServiceError> {
         let message: ScabbardMessage = Message::parse_from_bytes(message_bytes)?;
 
@@ -512,6 +775,31 @@ impl ServiceInstance for Scabbard {
                 }
                 Ok(())
             }
+            ScabbardMessage_Type::STATE_SYNC_REQUEST => match self.version {
+                ScabbardVersion::V1 => {
+                    warn!("Scabbard V1 does not accept STATE_SYNC_REQUEST messages");
+                    Ok(())
+                }
+                ScabbardVersion::V2 => {
+                    let mut message = message;
+                    self.send_state_sync_response(
+                        &message_context.sender,
+                        message.take_state_sync_request(),
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+                }
+            },
+            ScabbardMessage_Type::STATE_SYNC_RESPONSE => match self.version {
+                ScabbardVersion::V1 => {
+                    warn!("Scabbard V1 does not accept STATE_SYNC_RESPONSE messages");
+                    Ok(())
+                }
+                ScabbardVersion::V2 => {
+                    let mut message = message;
+                    self.sync_state(&message_context.sender, message.take_state_sync_response())
+                        .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+                }
+            },
             _ => Err(ServiceError::InvalidMessageFormat(Box::new(
                 ScabbardError::MessageTypeUnset,
             ))),
@@ -541,6 +829,7 @@ pub mod tests {
 
     use cylinder::{secp256k1::Secp256k1Context, VerifierFactory};
     use sawtooth::receipt::store::{ReceiptIter, ReceiptStoreError};
+    use splinter::consensus::two_phase::v2::MemoryTwoPhaseStore;
     use splinter::service::instance::{
         ServiceConnectionError, ServiceDisconnectionError, ServiceMessageContext,
         ServiceNetworkSender, ServiceSendError,
@@ -571,6 +860,7 @@ pub mod tests {
             Secp256k1Context::new().new_verifier(),
             vec![],
             None,
+            ScabbardConsensus::TwoPhaseCommit(Box::new(MemoryTwoPhaseStore::new())),
         )
         .expect("failed to create service");
         assert_eq!(service.service_id(), "new_scabbard");
@@ -595,6 +885,7 @@ pub mod tests {
             Secp256k1Context::new().new_verifier(),
             vec![],
             None,
+            ScabbardConsensus::TwoPhaseCommit(Box::new(MemoryTwoPhaseStore::new())),
         )
         .expect("failed to create service");
         let registry = MockServiceNetworkRegistry::new();
@@ -618,6 +909,7 @@ pub mod tests {
             Secp256k1Context::new().new_verifier(),
             vec![],
             None,
+            ScabbardConsensus::TwoPhaseCommit(Box::new(MemoryTwoPhaseStore::new())),
         )
         .expect("failed to create service");
         test_connect_and_disconnect(&mut service);
@@ -87,6 +87,7 @@ mod tests {
         protocol::command::{BytesEntry, Command, SetState},
     };
 
+    use splinter::consensus::two_phase::v2::MemoryTwoPhaseStore;
     #[cfg(feature = "authorization")]
     use splinter::rest_api::auth::authorization::{
         AuthorizationHandler, AuthorizationHandlerResult,
@@ -105,7 +106,8 @@ mod tests {
 
     use crate::service::state::merkle_state::{MerkleState, MerkleStateConfig};
     use crate::service::{
-        state::ScabbardState, Scabbard, ScabbardStatePurgeHandler, ScabbardVersion,
+        state::ScabbardState, Scabbard, ScabbardConsensus, ScabbardStatePurgeHandler,
+        ScabbardVersion,
     };
     use crate::store::{
         transact::{TransactCommitHashStore, CURRENT_STATE_ROOT_INDEX},
@@ -186,6 +188,7 @@ mod tests {
             Secp256k1Context::new().new_verifier(),
             vec![],
             None,
+            ScabbardConsensus::TwoPhaseCommit(Box::new(MemoryTwoPhaseStore::new())),
         )
         .expect("Failed to create scabbard");
 
@@ -26,7 +26,7 @@ const SCABBARD_READ_PERMISSION: Permission = Permission::Check {
     permission_description: "Allows the client to read scabbard services' state and batch statuses",
 };
 #[cfg(all(feature = "authorization", feature = "rest-api-actix-web-1"))]
-const SCABBARD_WRITE_PERMISSION: Permission = Permission::Check {
+pub(crate) const SCABBARD_WRITE_PERMISSION: Permission = Permission::Check {
     permission_id: "scabbard.write",
     permission_display_name: "Scabbard write",
     permission_description: "Allows the client to submit batches to scabbard services",
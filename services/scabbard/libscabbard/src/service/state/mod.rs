@@ -66,6 +66,8 @@ pub struct ScabbardState {
     context_manager: ContextManager,
     executor: Option<Executor>,
     current_state_root: String,
+    // The state root of a new service's state, which only contains the admin keys
+    genesis_state_root: String,
     receipt_store: Arc<dyn ReceiptStore>,
     pending_changes: Option<(String, Vec<TransactionReceipt>)>,
     event_subscribers: Vec<Box<dyn StateSubscriber>>,
@@ -85,6 +87,30 @@ impl ScabbardState {
         #[cfg(feature = "metrics")] circuit_id: String,
         admin_keys: Vec<String>,
     ) -> Result<Self, ScabbardStateError> {
+        // Initial state (admin keys)
+        let mut admin_keys_entry = Setting_Entry::new();
+        admin_keys_entry.set_key(ADMINISTRATORS_SETTING_KEY.into());
+        admin_keys_entry.set_value(admin_keys.join(","));
+        let mut admin_keys_setting = Setting::new();
+        admin_keys_setting.set_entries(vec![admin_keys_entry].into());
+        let admin_keys_setting_bytes = admin_keys_setting.write_to_bytes().map_err(|err| {
+            ScabbardStateError(format!(
+                "failed to write admin keys setting to bytes: {}",
+                err
+            ))
+        })?;
+        let initial_state_changes = vec![TransactStateChange::Set {
+            key: ADMINISTRATORS_SETTING_ADDRESS.into(),
+            value: admin_keys_setting_bytes,
+        }];
+
+        let initial_state_root = merkle_state
+            .get_initial_state_root()
+            .map_err(|err| ScabbardStateError(err.to_string()))?;
+
+        let genesis_state_root =
+            merkle_state.compute_state_id(&initial_state_root, &initial_state_changes)?;
+
         let current_state_root = if let Some(current_state_root) = commit_hash_store
             .get_current_commit_hash()
             .map_err(|err| ScabbardStateError(err.to_string()))?
@@ -92,31 +118,9 @@ impl ScabbardState {
             debug!("Restoring scabbard state on root {}", current_state_root);
             current_state_root
         } else {
-            // Set initial state (admin keys)
-            let mut admin_keys_entry = Setting_Entry::new();
-            admin_keys_entry.set_key(ADMINISTRATORS_SETTING_KEY.into());
-            admin_keys_entry.set_value(admin_keys.join(","));
-            let mut admin_keys_setting = Setting::new();
-            admin_keys_setting.set_entries(vec![admin_keys_entry].into());
-            let admin_keys_setting_bytes = admin_keys_setting.write_to_bytes().map_err(|err| {
-                ScabbardStateError(format!(
-                    "failed to write admin keys setting to bytes: {}",
-                    err
-                ))
-            })?;
-            let admin_keys_state_change = TransactStateChange::Set {
-                key: ADMINISTRATORS_SETTING_ADDRESS.into(),
-                value: admin_keys_setting_bytes,
-            };
-
-            let initial_state_root = merkle_state
-                .get_initial_state_root()
-                .map_err(|err| ScabbardStateError(err.to_string()))?;
-
-            let new_state_root = merkle_state.commit(
-                &initial_state_root,
-                vec![admin_keys_state_change].as_slice(),
-            )?;
+            // Set initial state
+            let new_state_root =
+                merkle_state.commit(&initial_state_root, &initial_state_changes)?;
 
             // store the new state root to the commit store
             commit_hash_store
@@ -140,6 +144,7 @@ impl ScabbardState {
             context_manager,
             executor: None,
             current_state_root,
+            genesis_state_root,
             receipt_store,
             pending_changes: None,
             event_subscribers: vec![],
@@ -214,11 +219,96 @@ impl ScabbardState {
         ))
     }
 
+    /// Fetch all entries in state at the given state root, in address order. This may be an
+    /// earlier state root than the current one, as long as it has not been pruned.
+    pub fn get_state_at_root(&self, state_root: &str) -> Result<StateIter, ScabbardStateError> {
+        Ok(Box::new(
+            self.merkle_state
+                .leaves(&state_root.to_string(), None)
+                .map_err(|err| ScabbardStateError(err.to_string()))?
+                .map(|res| res.map_err(|e| ScabbardStateError(e.to_string()))),
+        ))
+    }
+
     /// Get the current state root hash.
     pub fn current_state_root(&self) -> &str {
         &self.current_state_root
     }
 
+    /// Returns true if no changes have been committed to state since it was initialized with the
+    /// admin keys.
+    pub fn is_genesis_state(&self) -> bool {
+        self.current_state_root == self.genesis_state_root
+    }
+
+    /// Adds entries that were read from another service's state to a partially synced state,
+    /// and returns the state root of the result. This is used by a service that was added to an
+    /// existing circuit to catch up to its peers one chunk of entries at a time.
+    ///
+    /// The first chunk is added to the empty state, when `partial_state_root` is `None`. The
+    /// current state is not changed until the sync is finished with
+    /// [`finish_state_sync`](Self::finish_state_sync).
+    pub fn apply_state_sync_chunk(
+        &mut self,
+        partial_state_root: Option<&str>,
+        entries: Vec<(String, Vec<u8>)>,
+    ) -> Result<String, ScabbardStateError> {
+        let base_state_root = match partial_state_root {
+            Some(state_root) => state_root.to_string(),
+            None => self
+                .merkle_state
+                .get_initial_state_root()
+                .map_err(|err| ScabbardStateError(err.to_string()))?,
+        };
+
+        let state_changes = entries
+            .into_iter()
+            .map(|(key, value)| TransactStateChange::Set { key, value })
+            .collect::<Vec<_>>();
+
+        Ok(self.merkle_state.commit(&base_state_root, &state_changes)?)
+    }
+
+    /// Replaces the initial state with a state that was synced with
+    /// [`apply_state_sync_chunk`](Self::apply_state_sync_chunk).
+    ///
+    /// Returns `Ok(false)` if the state has already changed since it was initialized, as state
+    /// can only be synced once. Returns an error if the synced state does not have the expected
+    /// state root, which is the root that the service's peers agreed on.
+    pub fn finish_state_sync(
+        &mut self,
+        partial_state_root: Option<&str>,
+        expected_state_root: &str,
+    ) -> Result<bool, ScabbardStateError> {
+        if !self.is_genesis_state() || self.pending_changes.is_some() {
+            return Ok(false);
+        }
+
+        let synced_state_root = match partial_state_root {
+            Some(state_root) => state_root.to_string(),
+            None => self
+                .merkle_state
+                .get_initial_state_root()
+                .map_err(|err| ScabbardStateError(err.to_string()))?,
+        };
+        if synced_state_root != expected_state_root {
+            return Err(ScabbardStateError(format!(
+                "synced state root {} does not match expected state root {}",
+                synced_state_root, expected_state_root
+            )));
+        }
+
+        self.current_state_root = synced_state_root;
+        self.write_current_state_root()?;
+
+        info!(
+            "synced state for new state root {}",
+            self.current_state_root
+        );
+
+        Ok(true)
+    }
+
     pub fn prepare_change(&mut self, batch: BatchPair) -> Result<String, ScabbardStateError> {
         let executor = self.executor.as_ref().ok_or_else(|| {
             ScabbardStateError("attempting to prepare a change on a stopped service".into())
@@ -0,0 +1,166 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracks the progress of a service that was added to an existing circuit while it syncs its
+//! state from its peers.
+//!
+//! The service first collects the current state root of each peer. A state root is only trusted
+//! once more than half of the peers report it; the entries of that root are then fetched from one
+//! of those peers, one chunk at a time, and the state root that results from them must match the
+//! agreed root.
+
+use std::collections::HashMap;
+
+/// The maximum total size of the entries that are sent in a single state sync response; a chunk
+/// always contains at least one entry.
+pub const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The transfer of the entries of the agreed state root from a single peer
+#[derive(Debug, PartialEq)]
+pub struct Transfer {
+    /// The peer that sends the entries
+    pub peer: String,
+    /// The state root the peers agreed on
+    pub state_root: String,
+    /// The state root of the entries that have been received so far; `None` before the first
+    /// chunk is received
+    pub partial_state_root: Option<String>,
+}
+
+/// The progress of a state sync
+#[derive(Debug, Default)]
+pub struct StateSync {
+    peer_count: usize,
+    announced_roots: HashMap<String, String>,
+    transfer: Option<Transfer>,
+    finished: bool,
+}
+
+impl StateSync {
+    /// Starts a new state sync with the given number of peers.
+    pub fn new(peer_count: usize) -> Self {
+        Self {
+            peer_count,
+            announced_roots: HashMap::new(),
+            transfer: None,
+            finished: false,
+        }
+    }
+
+    /// Records the current state root of a peer. Returns the transfer that should be started if
+    /// the state root has now been reported by a quorum of the peers.
+    pub fn announce_root(&mut self, peer: &str, state_root: &str) -> Option<&Transfer> {
+        if self.finished || self.transfer.is_some() {
+            return None;
+        }
+
+        self.announced_roots
+            .insert(peer.to_string(), state_root.to_string());
+
+        let votes = self
+            .announced_roots
+            .values()
+            .filter(|root| *root == state_root)
+            .count();
+        if votes >= self.quorum() {
+            self.transfer = Some(Transfer {
+                peer: peer.to_string(),
+                state_root: state_root.to_string(),
+                partial_state_root: None,
+            });
+            self.transfer.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if every peer has reported its state root without a quorum agreeing on one,
+    /// which may happen while a batch is being committed. The announcements are cleared so that
+    /// the roots can be requested again.
+    pub fn take_stalled(&mut self) -> bool {
+        if !self.finished
+            && self.transfer.is_none()
+            && self.announced_roots.len() >= self.peer_count
+        {
+            self.announced_roots.clear();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the transfer in progress, if it is from the given peer for the given state root.
+    pub fn transfer_mut(&mut self, peer: &str, state_root: &str) -> Option<&mut Transfer> {
+        self.transfer
+            .as_mut()
+            .filter(|transfer| transfer.peer == peer && transfer.state_root == state_root)
+    }
+
+    /// Aborts the transfer in progress, so that the state roots are collected again.
+    pub fn reset(&mut self) {
+        self.announced_roots.clear();
+        self.transfer = None;
+    }
+
+    /// Ends the state sync; any further state roots that are reported are ignored.
+    pub fn finish(&mut self) {
+        self.announced_roots.clear();
+        self.transfer = None;
+        self.finished = true;
+    }
+
+    fn quorum(&self) -> usize {
+        self.peer_count / 2 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that a transfer only starts once a majority of the peers agree on a state root,
+    /// and that it can only be continued by the chosen peer for the agreed root.
+    #[test]
+    fn transfer_requires_quorum() {
+        let mut sync = StateSync::new(3);
+
+        assert_eq!(sync.announce_root("peer-1", "bad-root"), None);
+        assert_eq!(sync.announce_root("peer-2", "good-root"), None);
+        assert_eq!(
+            sync.announce_root("peer-3", "good-root"),
+            Some(&Transfer {
+                peer: "peer-3".into(),
+                state_root: "good-root".into(),
+                partial_state_root: None,
+            })
+        );
+
+        assert!(sync.transfer_mut("peer-1", "bad-root").is_none());
+        assert!(sync.transfer_mut("peer-3", "bad-root").is_none());
+        assert!(sync.transfer_mut("peer-3", "good-root").is_some());
+        assert!(!sync.take_stalled());
+    }
+
+    /// Verifies that the roots are collected again if every peer reported a different root.
+    #[test]
+    fn stalled_without_quorum() {
+        let mut sync = StateSync::new(2);
+
+        assert_eq!(sync.announce_root("peer-1", "root-1"), None);
+        assert!(!sync.take_stalled());
+        assert_eq!(sync.announce_root("peer-2", "root-2"), None);
+        assert!(sync.take_stalled());
+        assert!(!sync.take_stalled());
+    }
+}
@@ -17,26 +17,38 @@ use splinter::service::ServiceId;
 
 pub struct ScabbardArguments {
     peers: Vec<ServiceId>,
+    admin_keys: Vec<String>,
 }
 
 impl ScabbardArguments {
-    pub fn new(peers: Vec<ServiceId>) -> Result<Self, InvalidArgumentError> {
-        Ok(Self { peers })
+    pub fn new(
+        peers: Vec<ServiceId>,
+        admin_keys: Vec<String>,
+    ) -> Result<Self, InvalidArgumentError> {
+        Ok(Self { peers, admin_keys })
     }
 
     pub fn peers(&self) -> &Vec<ServiceId> {
         &self.peers
     }
+
+    pub fn admin_keys(&self) -> &Vec<String> {
+        &self.admin_keys
+    }
 }
 
 #[derive(Default)]
 pub struct ScabbardArgumentsBuilder {
     peers: Option<Vec<ServiceId>>,
+    admin_keys: Option<Vec<String>>,
 }
 
 impl ScabbardArgumentsBuilder {
     pub fn new() -> Self {
-        Self { peers: None }
+        Self {
+            peers: None,
+            admin_keys: None,
+        }
     }
 
     pub fn with_peers(mut self, peers: Vec<ServiceId>) -> Self {
@@ -44,11 +56,20 @@ impl ScabbardArgumentsBuilder {
         self
     }
 
+    pub fn with_admin_keys(mut self, admin_keys: Vec<String>) -> Self {
+        self.admin_keys = Some(admin_keys);
+        self
+    }
+
     pub fn build(self) -> Result<ScabbardArguments, InvalidArgumentError> {
         let peers = self
             .peers
             .ok_or_else(|| InvalidArgumentError::new("peers", "must be set"))?;
 
-        ScabbardArguments::new(peers)
+        let admin_keys = self
+            .admin_keys
+            .ok_or_else(|| InvalidArgumentError::new("admin_keys", "must be set"))?;
+
+        ScabbardArguments::new(peers, admin_keys)
     }
 }
@@ -25,14 +25,17 @@ impl ArgumentsConverter<ScabbardArguments, Vec<(String, String)>>
     for ScabbardArgumentsVecConverter
 {
     fn to_right(&self, left: ScabbardArguments) -> Result<Vec<(String, String)>, InternalError> {
-        Ok(vec![(
-            "peer_services".to_string(),
-            left.peers()
-                .iter()
-                .map(|service_id| service_id.to_string())
-                .collect::<Vec<String>>()
-                .join(","),
-        )])
+        Ok(vec![
+            (
+                "peer_services".to_string(),
+                left.peers()
+                    .iter()
+                    .map(|service_id| service_id.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+            ),
+            ("admin_keys".to_string(), left.admin_keys().join(",")),
+        ])
     }
 
     fn to_left(&self, right: Vec<(String, String)>) -> Result<ScabbardArguments, InternalError> {
@@ -49,6 +52,10 @@ impl ArgumentsConverter<ScabbardArguments, Vec<(String, String)>>
                         .map_err(|err| InternalError::from_source(Box::new(err)))?;
                     arg_builder = arg_builder.with_peers(peers);
                 }
+                "admin_keys" => {
+                    let admin_keys = parse_list(&value).map_err(InternalError::with_message)?;
+                    arg_builder = arg_builder.with_admin_keys(admin_keys);
+                }
                 _ => {
                     return Err(InternalError::with_message(format!(
                         "Received unknown argument: {}",
@@ -0,0 +1,203 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Executes the batches agreed on by scabbard v3 services against each service's merkle state.
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use std::collections::HashMap;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use std::sync::{Arc, Mutex};
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use diesel::r2d2::{ConnectionManager, Pool};
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use sawtooth::receipt::store::{diesel::DieselReceiptStore, ReceiptStore};
+use splinter::error::InternalError;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use splinter::service::FullyQualifiedServiceId;
+use transact::protocol::batch::BatchPair;
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use crate::service::state::{
+    merkle_state::{MerkleState, MerkleStateConfig},
+    ScabbardState,
+};
+use crate::store::ScabbardService;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use crate::store::{diesel::DieselCommitHashStore, CommitHashStore};
+
+/// Executes batches against the merkle state of scabbard v3 services.
+///
+/// A batch is executed once when it is voted on and again when it is committed, so no changes are
+/// held in memory between the two phases.
+pub trait BatchExecutor: Send + Sync {
+    /// Returns the current state root of the service's merkle state
+    fn current_state_root(&self, service: &ScabbardService) -> Result<String, InternalError>;
+
+    /// Executes the batch on top of the service's current state and returns the resulting state
+    /// root, without committing the changes. An error is returned if the batch is invalid.
+    fn execute(&self, service: &ScabbardService, batch: BatchPair)
+        -> Result<String, InternalError>;
+
+    /// Executes the batch on top of the service's current state, commits the changes and returns
+    /// the new state root
+    fn commit(&self, service: &ScabbardService, batch: BatchPair) -> Result<String, InternalError>;
+}
+
+/// A `BatchExecutor` that keeps each service's merkle state in a SQL database, using the same
+/// tables as scabbard v2 services.
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub struct SqlBatchExecutor {
+    storage: Storage,
+    states: Mutex<HashMap<FullyQualifiedServiceId, ScabbardState>>,
+}
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+enum Storage {
+    #[cfg(feature = "postgres")]
+    Postgres {
+        pool: Pool<ConnectionManager<diesel::pg::PgConnection>>,
+    },
+    #[cfg(feature = "sqlite")]
+    Sqlite {
+        pool: Pool<ConnectionManager<diesel::SqliteConnection>>,
+    },
+}
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+impl SqlBatchExecutor {
+    /// Creates a new `SqlBatchExecutor` that stores merkle state in a PostgreSQL database.
+    #[cfg(feature = "postgres")]
+    pub fn new_postgres(pool: Pool<ConnectionManager<diesel::pg::PgConnection>>) -> Self {
+        Self {
+            storage: Storage::Postgres { pool },
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a new `SqlBatchExecutor` that stores merkle state in a SQLite database.
+    #[cfg(feature = "sqlite")]
+    pub fn new_sqlite(pool: Pool<ConnectionManager<diesel::SqliteConnection>>) -> Self {
+        Self {
+            storage: Storage::Sqlite { pool },
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs `f` with the service's state, loading the state and starting its executor first if
+    /// this is the first batch for the service since the executor was created.
+    fn with_state<F, T>(&self, service: &ScabbardService, f: F) -> Result<T, InternalError>
+    where
+        F: FnOnce(&mut ScabbardState) -> Result<T, InternalError>,
+    {
+        let mut states = self.states.lock().map_err(|_| {
+            InternalError::with_message("SqlBatchExecutor state lock was poisoned".into())
+        })?;
+
+        if !states.contains_key(service.service_id()) {
+            let mut state = self.create_state(service)?;
+            state
+                .start_executor()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            states.insert(service.service_id().clone(), state);
+        }
+
+        let state = states
+            .get_mut(service.service_id())
+            .expect("State was inserted above");
+        f(state)
+    }
+
+    fn create_state(&self, service: &ScabbardService) -> Result<ScabbardState, InternalError> {
+        let circuit_id = service.service_id().circuit_id().as_str();
+        let service_id = service.service_id().service_id().as_str();
+        let tree_name = format!("{}::{}", circuit_id, service_id);
+
+        let (merkle_state_config, receipt_store, commit_hash_store) = match &self.storage {
+            #[cfg(feature = "postgres")]
+            Storage::Postgres { pool } => (
+                MerkleStateConfig::Postgres {
+                    pool: pool.clone(),
+                    tree_name: tree_name.clone(),
+                },
+                Arc::new(DieselReceiptStore::new(pool.clone(), Some(tree_name)))
+                    as Arc<dyn ReceiptStore>,
+                Box::new(DieselCommitHashStore::new(
+                    pool.clone(),
+                    circuit_id,
+                    service_id,
+                )) as Box<dyn CommitHashStore>,
+            ),
+            #[cfg(feature = "sqlite")]
+            Storage::Sqlite { pool } => (
+                MerkleStateConfig::Sqlite {
+                    pool: pool.clone(),
+                    tree_name: tree_name.clone(),
+                },
+                Arc::new(DieselReceiptStore::new(pool.clone(), Some(tree_name)))
+                    as Arc<dyn ReceiptStore>,
+                Box::new(DieselCommitHashStore::new(
+                    pool.clone(),
+                    circuit_id,
+                    service_id,
+                )) as Box<dyn CommitHashStore>,
+            ),
+        };
+
+        let merkle_state = MerkleState::new(merkle_state_config)?;
+
+        ScabbardState::new(
+            merkle_state,
+            commit_hash_store,
+            receipt_store,
+            #[cfg(feature = "metrics")]
+            service_id.to_string(),
+            #[cfg(feature = "metrics")]
+            circuit_id.to_string(),
+            service.admin_keys().to_vec(),
+        )
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+impl BatchExecutor for SqlBatchExecutor {
+    fn current_state_root(&self, service: &ScabbardService) -> Result<String, InternalError> {
+        self.with_state(service, |state| Ok(state.current_state_root().to_string()))
+    }
+
+    fn execute(
+        &self,
+        service: &ScabbardService,
+        batch: BatchPair,
+    ) -> Result<String, InternalError> {
+        self.with_state(service, |state| {
+            let result = state.prepare_change(batch);
+            state
+                .rollback()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            result.map_err(|err| InternalError::from_source(Box::new(err)))
+        })
+    }
+
+    fn commit(&self, service: &ScabbardService, batch: BatchPair) -> Result<String, InternalError> {
+        self.with_state(service, |state| {
+            state
+                .prepare_change(batch)
+                .and_then(|_| state.commit())
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            Ok(state.current_state_root().to_string())
+        })
+    }
+}
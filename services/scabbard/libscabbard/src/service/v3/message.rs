@@ -12,6 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// Messages sent between scabbard v3 services to agree on batches using two-phase commit
+#[derive(Clone, Debug, PartialEq)]
 pub enum ScabbardMessage {
-    Message,
+    /// Sent by the coordinator to ask its peers to vote on the batch for an epoch, along with the
+    /// state root that results from executing the batch
+    VoteRequest {
+        epoch: u64,
+        batch: Vec<u8>,
+        state_root: String,
+    },
+    /// Sent by a participant to the coordinator in response to a `VoteRequest`
+    Vote { epoch: u64, accept: bool },
+    /// Sent by the coordinator when all participants have voted to accept the epoch's batch
+    Commit { epoch: u64 },
+    /// Sent by the coordinator when the epoch's batch was rejected or voting timed out
+    Abort { epoch: u64 },
+    /// Sent by a participant that has not received the coordinator's decision for an epoch
+    DecisionRequest { epoch: u64 },
+}
+
+impl ScabbardMessage {
+    /// Returns the epoch the message applies to
+    pub fn epoch(&self) -> u64 {
+        match self {
+            ScabbardMessage::VoteRequest { epoch, .. }
+            | ScabbardMessage::Vote { epoch, .. }
+            | ScabbardMessage::Commit { epoch }
+            | ScabbardMessage::Abort { epoch }
+            | ScabbardMessage::DecisionRequest { epoch } => *epoch,
+        }
+    }
 }
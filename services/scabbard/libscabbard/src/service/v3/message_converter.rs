@@ -12,20 +12,125 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use protobuf::Message;
 use splinter::error::InternalError;
 use splinter::service::MessageConverter;
 
+use crate::protos::scabbard_v3::{ScabbardV3Message, ScabbardV3Message_Type};
+
 use super::message::ScabbardMessage;
 
 #[derive(Clone)]
 pub struct ScabbardMessageByteConverter {}
 
 impl MessageConverter<ScabbardMessage, Vec<u8>> for ScabbardMessageByteConverter {
-    fn to_left(&self, _right: Vec<u8>) -> Result<ScabbardMessage, InternalError> {
-        Ok(ScabbardMessage::Message)
+    fn to_left(&self, right: Vec<u8>) -> Result<ScabbardMessage, InternalError> {
+        let mut proto: ScabbardV3Message = Message::parse_from_bytes(&right)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let epoch = proto.get_epoch();
+        match proto.get_message_type() {
+            ScabbardV3Message_Type::VOTE_REQUEST => Ok(ScabbardMessage::VoteRequest {
+                epoch,
+                batch: proto.take_batch(),
+                state_root: proto.take_state_root(),
+            }),
+            ScabbardV3Message_Type::VOTE => Ok(ScabbardMessage::Vote {
+                epoch,
+                accept: proto.get_accept(),
+            }),
+            ScabbardV3Message_Type::COMMIT => Ok(ScabbardMessage::Commit { epoch }),
+            ScabbardV3Message_Type::ABORT => Ok(ScabbardMessage::Abort { epoch }),
+            ScabbardV3Message_Type::DECISION_REQUEST => {
+                Ok(ScabbardMessage::DecisionRequest { epoch })
+            }
+            ScabbardV3Message_Type::UNSET => Err(InternalError::with_message(
+                "Received scabbard message with unset message type".to_string(),
+            )),
+        }
+    }
+
+    fn to_right(&self, left: ScabbardMessage) -> Result<Vec<u8>, InternalError> {
+        let mut proto = ScabbardV3Message::new();
+        proto.set_epoch(left.epoch());
+
+        match left {
+            ScabbardMessage::VoteRequest {
+                batch, state_root, ..
+            } => {
+                proto.set_message_type(ScabbardV3Message_Type::VOTE_REQUEST);
+                proto.set_batch(batch);
+                proto.set_state_root(state_root);
+            }
+            ScabbardMessage::Vote { accept, .. } => {
+                proto.set_message_type(ScabbardV3Message_Type::VOTE);
+                proto.set_accept(accept);
+            }
+            ScabbardMessage::Commit { .. } => {
+                proto.set_message_type(ScabbardV3Message_Type::COMMIT);
+            }
+            ScabbardMessage::Abort { .. } => {
+                proto.set_message_type(ScabbardV3Message_Type::ABORT);
+            }
+            ScabbardMessage::DecisionRequest { .. } => {
+                proto.set_message_type(ScabbardV3Message_Type::DECISION_REQUEST);
+            }
+        }
+
+        proto
+            .write_to_bytes()
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that each `ScabbardMessage` variant can be converted to bytes and back.
+    #[test]
+    fn test_message_round_trip() {
+        let converter = ScabbardMessageByteConverter {};
+
+        let messages = vec![
+            ScabbardMessage::VoteRequest {
+                epoch: 1,
+                batch: b"batch".to_vec(),
+                state_root: "abcdef".into(),
+            },
+            ScabbardMessage::Vote {
+                epoch: 1,
+                accept: true,
+            },
+            ScabbardMessage::Vote {
+                epoch: 2,
+                accept: false,
+            },
+            ScabbardMessage::Commit { epoch: 3 },
+            ScabbardMessage::Abort { epoch: 4 },
+            ScabbardMessage::DecisionRequest { epoch: 5 },
+        ];
+
+        for message in messages {
+            let bytes = converter
+                .to_right(message.clone())
+                .expect("Unable to convert message to bytes");
+            let converted = converter
+                .to_left(bytes)
+                .expect("Unable to convert bytes to message");
+            assert_eq!(message, converted);
+        }
     }
 
-    fn to_right(&self, _left: ScabbardMessage) -> Result<Vec<u8>, InternalError> {
-        Ok(vec![])
+    /// Verify that a message with an unset type cannot be converted.
+    #[test]
+    fn test_message_unset_type() {
+        let converter = ScabbardMessageByteConverter {};
+
+        let bytes = ScabbardV3Message::new()
+            .write_to_bytes()
+            .expect("Unable to write proto");
+
+        assert!(converter.to_left(bytes).is_err());
     }
 }
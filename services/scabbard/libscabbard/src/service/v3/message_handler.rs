@@ -12,20 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use log::info;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::warn;
 use splinter::{
     error::InternalError,
     service::{FullyQualifiedServiceId, MessageHandler, MessageSender},
 };
 
-use super::ScabbardMessage;
+use crate::store::ScabbardStore;
+
+use super::two_phase::TwoPhaseCommit;
+use super::{BatchExecutor, ScabbardMessage};
 
-#[derive(Default)]
-pub struct ScabbardMessageHandler {}
+pub struct ScabbardMessageHandler {
+    store: Arc<dyn ScabbardStore + Send + Sync>,
+    executor: Arc<dyn BatchExecutor>,
+    coordinator_timeout: Duration,
+}
 
 impl ScabbardMessageHandler {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(
+        store: Arc<dyn ScabbardStore + Send + Sync>,
+        executor: Arc<dyn BatchExecutor>,
+        coordinator_timeout: Duration,
+    ) -> Self {
+        Self {
+            store,
+            executor,
+            coordinator_timeout,
+        }
     }
 }
 
@@ -34,15 +51,32 @@ impl MessageHandler for ScabbardMessageHandler {
 
     fn handle_message(
         &mut self,
-        _sender: &dyn MessageSender<Self::Message>,
+        sender: &dyn MessageSender<Self::Message>,
         to_service: FullyQualifiedServiceId,
         from_service: FullyQualifiedServiceId,
-        _message: Self::Message,
+        message: Self::Message,
     ) -> Result<(), InternalError> {
-        info!(
-            "handling scabbard message, to: {} from: {}",
-            to_service, from_service
-        );
-        Ok(())
+        let service = match self
+            .store
+            .get_service(&to_service)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(service) => service,
+            None => {
+                warn!(
+                    "Ignoring message from {}, service {} does not exist",
+                    from_service, to_service
+                );
+                return Ok(());
+            }
+        };
+
+        TwoPhaseCommit::new(
+            &*self.store,
+            &*self.executor,
+            sender,
+            self.coordinator_timeout,
+        )
+        .handle_message(service, from_service.service_id(), message)
     }
 }
@@ -11,18 +11,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use splinter::service::{MessageHandlerFactory, Routable, ServiceType};
 
+use crate::store::ScabbardStore;
+
 use super::message_handler::ScabbardMessageHandler;
+use super::{BatchExecutor, DEFAULT_COORDINATOR_TIMEOUT};
 
 const SCABBARD_SERVICE_TYPES: &[ServiceType<'static>] = &[ServiceType::new_static("scabbard:v3")];
 
-#[derive(Clone, Default)]
-pub struct ScabbardMessageHandlerFactory {}
+#[derive(Clone)]
+pub struct ScabbardMessageHandlerFactory {
+    store: Arc<dyn ScabbardStore + Send + Sync>,
+    executor: Arc<dyn BatchExecutor>,
+    coordinator_timeout: Duration,
+}
 
 impl ScabbardMessageHandlerFactory {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(
+        store: Arc<dyn ScabbardStore + Send + Sync>,
+        executor: Arc<dyn BatchExecutor>,
+    ) -> Self {
+        Self {
+            store,
+            executor,
+            coordinator_timeout: Duration::from_secs(DEFAULT_COORDINATOR_TIMEOUT),
+        }
+    }
+
+    /// Sets how long a participant waits for the coordinator's decision before asking for it
+    /// again
+    pub fn with_coordinator_timeout(mut self, coordinator_timeout: Duration) -> Self {
+        self.coordinator_timeout = coordinator_timeout;
+        self
     }
 }
 
@@ -30,7 +54,11 @@ impl MessageHandlerFactory for ScabbardMessageHandlerFactory {
     type MessageHandler = ScabbardMessageHandler;
 
     fn new_handler(&self) -> Self::MessageHandler {
-        ScabbardMessageHandler::new()
+        ScabbardMessageHandler::new(
+            self.store.clone(),
+            self.executor.clone(),
+            self.coordinator_timeout,
+        )
     }
 
     fn clone_boxed(&self) -> Box<dyn MessageHandlerFactory<MessageHandler = Self::MessageHandler>> {
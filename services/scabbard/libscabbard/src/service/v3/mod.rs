@@ -14,22 +14,35 @@
 
 mod arguments;
 mod arguments_converter;
+mod executor;
 mod lifecycle;
 mod message;
 mod message_converter;
 mod message_handler;
 mod message_handler_factory;
+#[cfg(feature = "rest-api-actix-web-1")]
+mod rest_api;
 mod timer_filter;
 mod timer_handler;
 mod timer_handler_factory;
+mod two_phase;
 
 pub use arguments::{ScabbardArguments, ScabbardArgumentsBuilder};
 pub use arguments_converter::ScabbardArgumentsVecConverter;
+pub use executor::BatchExecutor;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub use executor::SqlBatchExecutor;
 pub use lifecycle::ScabbardLifecycle;
 pub use message::ScabbardMessage;
 pub use message_converter::ScabbardMessageByteConverter;
 pub use message_handler::ScabbardMessageHandler;
 pub use message_handler_factory::ScabbardMessageHandlerFactory;
+#[cfg(feature = "rest-api-actix-web-1")]
+pub use rest_api::{make_batches_resource, ScabbardV3ResourceProvider};
 pub use timer_filter::ScabbardTimerFilter;
 pub use timer_handler::ScabbardTimerHandler;
 pub use timer_handler_factory::{ScabbardTimerHandlerFactory, ScabbardTimerHandlerFactoryBuilder};
+
+// The default amount of time the coordinator waits for votes, and participants wait for a
+// decision, in seconds
+const DEFAULT_COORDINATOR_TIMEOUT: u64 = 30;
@@ -0,0 +1,188 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API for submitting batches to scabbard v3 services.
+
+use std::sync::Arc;
+
+use actix_web::{error::BlockingError, web, Error as ActixError, HttpRequest, HttpResponse};
+use futures::{stream::Stream, Future, IntoFuture};
+use splinter::{
+    rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard, Resource, RestResourceProvider},
+    service::FullyQualifiedServiceId,
+};
+use transact::protocol::batch::BatchPair;
+use transact::protos::{FromBytes, IntoBytes};
+
+use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::service::rest_api::SCABBARD_WRITE_PERMISSION;
+use crate::store::{BatchStatus, ScabbardBatch, ScabbardStore, ScabbardStoreError};
+
+/// Provides the REST API resources of scabbard v3 services.
+pub struct ScabbardV3ResourceProvider {
+    store: Arc<dyn ScabbardStore + Send + Sync>,
+}
+
+impl ScabbardV3ResourceProvider {
+    /// Constructs a new resource provider that queues batches in the given store.
+    pub fn new(store: Arc<dyn ScabbardStore + Send + Sync>) -> Self {
+        Self { store }
+    }
+}
+
+impl RestResourceProvider for ScabbardV3ResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![make_batches_resource(self.store.clone())]
+    }
+}
+
+/// Makes the `POST /scabbard/v3/{circuit}/{service_id}/batches` resource, which adds a list of
+/// batches to the service's queue. The coordinator proposes the queued batches in order.
+pub fn make_batches_resource(store: Arc<dyn ScabbardStore + Send + Sync>) -> Resource {
+    let resource = Resource::build("/scabbard/v3/{circuit}/{service_id}/batches")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::SCABBARD_ADD_BATCHES_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ));
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Post,
+            SCABBARD_WRITE_PERMISSION,
+            move |request, payload| add_batches(request, payload, store.clone()),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |request, payload| {
+            add_batches(request, payload, store.clone())
+        })
+    }
+}
+
+fn add_batches(
+    request: HttpRequest,
+    payload: web::Payload,
+    store: Arc<dyn ScabbardStore + Send + Sync>,
+) -> Box<dyn Future<Item = HttpResponse, Error = ActixError>> {
+    let circuit = request.match_info().get("circuit").unwrap_or("");
+    let service_id = request.match_info().get("service_id").unwrap_or("");
+    let service_id =
+        match FullyQualifiedServiceId::new_from_string(format!("{}::{}", circuit, service_id)) {
+            Ok(service_id) => service_id,
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid circuit or service ID"))
+                        .into_future(),
+                )
+            }
+        };
+
+    Box::new(
+        payload
+            .from_err::<ActixError>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, ActixError>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let batches: Vec<BatchPair> = match Vec::from_bytes(&body) {
+                    Ok(batches) if !batches.is_empty() => batches,
+                    _ => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(
+                                    "Invalid body: not a valid list of batches",
+                                ))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = ActixError>>
+                    }
+                };
+
+                Box::new(
+                    web::block(move || queue_batches(&*store, &service_id, batches)).then(
+                        |res: Result<_, BlockingError<QueueError>>| match res {
+                            Ok(()) => Ok(HttpResponse::Accepted().finish()),
+                            Err(BlockingError::Error(QueueError::NotFound(msg))) => {
+                                Ok(HttpResponse::NotFound().json(ErrorResponse::not_found(&msg)))
+                            }
+                            Err(BlockingError::Error(QueueError::Invalid(msg))) => {
+                                Ok(HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&msg)))
+                            }
+                            Err(BlockingError::Error(QueueError::Internal(msg))) => {
+                                error!("Failed to add batches: {}", msg);
+                                Ok(HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error()))
+                            }
+                            Err(BlockingError::Canceled) => {
+                                error!("Failed to add batches: blocking operation canceled");
+                                Ok(HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error()))
+                            }
+                        },
+                    ),
+                ) as Box<dyn Future<Item = HttpResponse, Error = ActixError>>
+            }),
+    )
+}
+
+#[derive(Debug)]
+enum QueueError {
+    NotFound(String),
+    Invalid(String),
+    Internal(String),
+}
+
+fn queue_batches(
+    store: &dyn ScabbardStore,
+    service_id: &FullyQualifiedServiceId,
+    batches: Vec<BatchPair>,
+) -> Result<(), QueueError> {
+    if store
+        .get_service(service_id)
+        .map_err(|err| QueueError::Internal(err.to_string()))?
+        .is_none()
+    {
+        return Err(QueueError::NotFound(format!(
+            "Service {} does not exist",
+            service_id
+        )));
+    }
+
+    for batch in batches {
+        let batch_id = batch.batch().header_signature().to_string();
+        let bytes = batch
+            .into_bytes()
+            .map_err(|err| QueueError::Internal(err.to_string()))?;
+        store
+            .add_batch(
+                service_id,
+                ScabbardBatch::new(batch_id.clone(), bytes, BatchStatus::Queued),
+            )
+            .map_err(|err| match err {
+                ScabbardStoreError::ConstraintViolation(_) => {
+                    QueueError::Invalid(format!("Batch {} was already submitted", batch_id))
+                }
+                err => QueueError::Internal(err.to_string()),
+            })?;
+    }
+
+    Ok(())
+}
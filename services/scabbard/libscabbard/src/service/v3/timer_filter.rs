@@ -12,25 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use log::info;
+use std::sync::Arc;
+
 use splinter::error::InternalError;
 use splinter::service::{FullyQualifiedServiceId, Routable, ServiceType, TimerFilter};
 
+use crate::store::ScabbardStore;
+
 const STATIC_TYPES: &[ServiceType] = &[ServiceType::new_static("scabbard:v3")];
 
-#[derive(Default)]
-pub struct ScabbardTimerFilter {}
+pub struct ScabbardTimerFilter {
+    store: Arc<dyn ScabbardStore + Send + Sync>,
+}
 
 impl ScabbardTimerFilter {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(store: Arc<dyn ScabbardStore + Send + Sync>) -> Self {
+        Self { store }
     }
 }
 
 impl TimerFilter for ScabbardTimerFilter {
     fn filter(&self) -> Result<Vec<FullyQualifiedServiceId>, InternalError> {
-        info!("filtering for scabbard timer");
-        Ok(vec![])
+        self.store
+            .list_ready_services()
+            .map_err(|err| InternalError::from_source(Box::new(err)))
     }
 }
 
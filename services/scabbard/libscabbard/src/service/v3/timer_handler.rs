@@ -12,21 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use log::info;
+use std::sync::Arc;
+use std::time::Duration;
 
 use splinter::{
     error::InternalError,
     service::{FullyQualifiedServiceId, MessageSender, TimerHandler},
 };
 
-use super::ScabbardMessage;
+use crate::store::ScabbardStore;
 
-#[derive(Default)]
-pub struct ScabbardTimerHandler {}
+use super::two_phase::TwoPhaseCommit;
+use super::{BatchExecutor, ScabbardMessage};
+
+pub struct ScabbardTimerHandler {
+    store: Arc<dyn ScabbardStore + Send + Sync>,
+    executor: Arc<dyn BatchExecutor>,
+    coordinator_timeout: Duration,
+}
 
 impl ScabbardTimerHandler {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(
+        store: Arc<dyn ScabbardStore + Send + Sync>,
+        executor: Arc<dyn BatchExecutor>,
+        coordinator_timeout: Duration,
+    ) -> Self {
+        Self {
+            store,
+            executor,
+            coordinator_timeout,
+        }
     }
 }
 
@@ -35,10 +50,24 @@ impl TimerHandler for ScabbardTimerHandler {
 
     fn handle_timer(
         &mut self,
-        _sender: &dyn MessageSender<Self::Message>,
+        sender: &dyn MessageSender<Self::Message>,
         service: FullyQualifiedServiceId,
     ) -> Result<(), InternalError> {
-        info!("handling scabbard timer for: {}", service);
-        Ok(())
+        let scabbard_service = match self
+            .store
+            .get_service(&service)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(scabbard_service) => scabbard_service,
+            None => return Ok(()),
+        };
+
+        TwoPhaseCommit::new(
+            &*self.store,
+            &*self.executor,
+            sender,
+            self.coordinator_timeout,
+        )
+        .handle_timer(scabbard_service)
     }
 }
@@ -12,20 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use splinter::error::{InternalError, InvalidArgumentError};
 use splinter::service::{TimerHandler, TimerHandlerFactory};
 
+use crate::store::ScabbardStore;
+
 use super::ScabbardMessageByteConverter;
 use super::ScabbardTimerHandler;
+use super::{BatchExecutor, DEFAULT_COORDINATOR_TIMEOUT};
 
-#[derive(Clone, Default)]
-pub struct ScabbardTimerHandlerFactory;
+#[derive(Clone)]
+pub struct ScabbardTimerHandlerFactory {
+    store: Arc<dyn ScabbardStore + Send + Sync>,
+    executor: Arc<dyn BatchExecutor>,
+    coordinator_timeout: Duration,
+}
 
 impl TimerHandlerFactory for ScabbardTimerHandlerFactory {
     type Message = Vec<u8>;
 
     fn new_handler(&self) -> Result<Box<dyn TimerHandler<Message = Self::Message>>, InternalError> {
-        let timer_handler = ScabbardTimerHandler::new();
+        let timer_handler = ScabbardTimerHandler::new(
+            self.store.clone(),
+            self.executor.clone(),
+            self.coordinator_timeout,
+        );
         Ok(Box::new(
             timer_handler.into_handler(ScabbardMessageByteConverter {}),
         ))
@@ -37,10 +51,52 @@ impl TimerHandlerFactory for ScabbardTimerHandlerFactory {
 }
 
 #[derive(Default)]
-pub struct ScabbardTimerHandlerFactoryBuilder {}
+pub struct ScabbardTimerHandlerFactoryBuilder {
+    store: Option<Arc<dyn ScabbardStore + Send + Sync>>,
+    executor: Option<Arc<dyn BatchExecutor>>,
+    coordinator_timeout: Option<Duration>,
+}
 
 impl ScabbardTimerHandlerFactoryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the store that holds the state of the scabbard services
+    pub fn with_store(mut self, store: Arc<dyn ScabbardStore + Send + Sync>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Sets the executor that applies batches to the merkle state of the scabbard services
+    pub fn with_batch_executor(mut self, executor: Arc<dyn BatchExecutor>) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
+    /// Sets how long the coordinator waits for votes before aborting an epoch
+    pub fn with_coordinator_timeout(mut self, coordinator_timeout: Duration) -> Self {
+        self.coordinator_timeout = Some(coordinator_timeout);
+        self
+    }
+
     pub fn build(self) -> Result<ScabbardTimerHandlerFactory, InvalidArgumentError> {
-        Ok(ScabbardTimerHandlerFactory {})
+        let store = self
+            .store
+            .ok_or_else(|| InvalidArgumentError::new("store", "must be set"))?;
+
+        let executor = self
+            .executor
+            .ok_or_else(|| InvalidArgumentError::new("executor", "must be set"))?;
+
+        let coordinator_timeout = self
+            .coordinator_timeout
+            .unwrap_or_else(|| Duration::from_secs(DEFAULT_COORDINATOR_TIMEOUT));
+
+        Ok(ScabbardTimerHandlerFactory {
+            store,
+            executor,
+            coordinator_timeout,
+        })
     }
 }
@@ -0,0 +1,1057 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The two-phase commit process used by scabbard v3 services to agree on batches.
+//!
+//! All of the state of the process is kept in a [`ScabbardStore`], so the message and timer
+//! handlers can be recreated for every message or wake up. The store is always updated before
+//! any messages are sent.
+//!
+//! Every batch is executed by a [`BatchExecutor`] before it is voted on. The coordinator sends
+//! the state root that results from the batch with its vote request, and a participant only
+//! accepts the batch if it computes the same state root. The batch is executed again and its
+//! changes committed once the decision to commit it is made.
+
+use std::time::{Duration, SystemTime};
+
+use log::{debug, info, warn};
+use splinter::error::InternalError;
+use splinter::service::{MessageSender, ServiceId};
+use transact::{protocol::batch::BatchPair, protos::FromBytes};
+
+use crate::store::{
+    BatchStatus, ScabbardBatch, ScabbardService, ScabbardStore, ScabbardStoreError, ServiceStatus,
+    TwoPhaseState,
+};
+
+use super::{BatchExecutor, ScabbardMessage};
+
+pub struct TwoPhaseCommit<'a> {
+    store: &'a dyn ScabbardStore,
+    executor: &'a dyn BatchExecutor,
+    sender: &'a dyn MessageSender<ScabbardMessage>,
+    timeout: Duration,
+}
+
+impl<'a> TwoPhaseCommit<'a> {
+    /// Creates a new `TwoPhaseCommit`
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store that holds the service's two-phase commit state
+    /// * `executor` - Executes batches against the service's merkle state
+    /// * `sender` - Used to send messages to the service's peers
+    /// * `timeout` - How long the coordinator waits for votes and a participant waits for a
+    ///   decision
+    pub fn new(
+        store: &'a dyn ScabbardStore,
+        executor: &'a dyn BatchExecutor,
+        sender: &'a dyn MessageSender<ScabbardMessage>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            store,
+            executor,
+            sender,
+            timeout,
+        }
+    }
+
+    /// Handles a message sent to `service` by one of its peers.
+    pub fn handle_message(
+        &self,
+        service: ScabbardService,
+        from_service: &ServiceId,
+        message: ScabbardMessage,
+    ) -> Result<(), InternalError> {
+        if service.status() != &ServiceStatus::Finalized {
+            warn!(
+                "Ignoring message for {}, service is not running",
+                service.service_id()
+            );
+            return Ok(());
+        }
+
+        if !service.peers().contains(from_service) {
+            warn!(
+                "Ignoring message for {} from {}, which is not a peer",
+                service.service_id(),
+                from_service
+            );
+            return Ok(());
+        }
+
+        let is_from_coordinator = from_service == service.coordinator();
+
+        match message {
+            ScabbardMessage::VoteRequest {
+                epoch,
+                batch,
+                state_root,
+            } if is_from_coordinator => self.handle_vote_request(service, epoch, batch, state_root),
+            ScabbardMessage::Vote { epoch, accept } => {
+                self.handle_vote(service, from_service, epoch, accept)
+            }
+            ScabbardMessage::Commit { epoch } if is_from_coordinator => {
+                self.handle_decision(service, epoch, true)
+            }
+            ScabbardMessage::Abort { epoch } if is_from_coordinator => {
+                self.handle_decision(service, epoch, false)
+            }
+            ScabbardMessage::DecisionRequest { epoch } => {
+                self.handle_decision_request(service, from_service, epoch)
+            }
+            _ => {
+                warn!(
+                    "Ignoring message for {} from {}, which is not the coordinator",
+                    service.service_id(),
+                    from_service
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Handles a wake up from the timer for `service`.
+    ///
+    /// An idle coordinator proposes the next queued batch. A coordinator that is still waiting
+    /// for votes when its alarm passes aborts the epoch. A participant that is still waiting for
+    /// a decision when its alarm passes asks the coordinator for it again.
+    pub fn handle_timer(&self, service: ScabbardService) -> Result<(), InternalError> {
+        if service.status() != &ServiceStatus::Finalized {
+            return Ok(());
+        }
+
+        let alarm_passed = service
+            .alarm()
+            .map(|alarm| alarm <= SystemTime::now())
+            .unwrap_or(false);
+
+        match service.state() {
+            TwoPhaseState::Idle if service.is_coordinator() => self.propose_next_batch(service),
+            TwoPhaseState::Voting { .. } if alarm_passed => {
+                info!(
+                    "Timed out waiting for votes for {} epoch {}",
+                    service.service_id(),
+                    service.epoch()
+                );
+                self.decide(service, false)
+            }
+            TwoPhaseState::WaitingForDecision if alarm_passed => {
+                let epoch = service.epoch();
+                let coordinator = service.coordinator().clone();
+                let service = service
+                    .into_builder()
+                    .with_alarm(self.alarm()?)
+                    .build()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                self.store.update_service(service).map_err(to_internal)?;
+
+                self.sender
+                    .send(&coordinator, ScabbardMessage::DecisionRequest { epoch })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts a new epoch for the oldest queued batch and requests the votes of all peers.
+    ///
+    /// The batch is executed first; a batch that is invalid is aborted without requesting any
+    /// votes.
+    fn propose_next_batch(&self, service: ScabbardService) -> Result<(), InternalError> {
+        let batch = match self
+            .store
+            .get_next_queued_batch(service.service_id())
+            .map_err(to_internal)?
+        {
+            Some(batch) => batch,
+            None => return Ok(()),
+        };
+
+        let epoch = service.epoch() + 1;
+        let peers = service.peers().to_vec();
+
+        let state_root = match self.execute(&service, batch.batch()) {
+            Ok(state_root) => state_root,
+            Err(err) => {
+                warn!(
+                    "Aborting batch {} for {} epoch {}, batch is invalid: {}",
+                    batch.batch_id(),
+                    service.service_id(),
+                    epoch,
+                    err
+                );
+                self.store
+                    .update_batch(
+                        service.service_id(),
+                        batch.with_status(BatchStatus::Aborted(epoch)),
+                    )
+                    .map_err(to_internal)?;
+
+                let service = service
+                    .into_builder()
+                    .with_epoch(epoch)
+                    .build()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                return self.store.update_service(service).map_err(to_internal);
+            }
+        };
+
+        self.store
+            .update_batch(
+                service.service_id(),
+                batch
+                    .clone()
+                    .with_status(BatchStatus::Pending(epoch))
+                    .with_state_root(state_root.clone()),
+            )
+            .map_err(to_internal)?;
+
+        let service = service
+            .into_builder()
+            .with_epoch(epoch)
+            .with_state(&TwoPhaseState::Voting { votes: vec![] })
+            .with_alarm(self.alarm()?)
+            .build()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        if peers.is_empty() {
+            // With no peers to vote, the batch can be committed immediately
+            return self.decide(service, true);
+        }
+
+        self.store.update_service(service).map_err(to_internal)?;
+
+        for peer in peers {
+            self.sender.send(
+                &peer,
+                ScabbardMessage::VoteRequest {
+                    epoch,
+                    batch: batch.batch().to_vec(),
+                    state_root: state_root.clone(),
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_vote_request(
+        &self,
+        service: ScabbardService,
+        epoch: u64,
+        batch: Vec<u8>,
+        state_root: String,
+    ) -> Result<(), InternalError> {
+        if service.state() != &TwoPhaseState::Idle {
+            warn!(
+                "Ignoring vote request for {} epoch {}, still waiting for the decision for epoch \
+                 {}",
+                service.service_id(),
+                epoch,
+                service.epoch()
+            );
+            return Ok(());
+        }
+
+        if epoch <= service.epoch() {
+            warn!(
+                "Ignoring vote request for {} epoch {}, current epoch is {}",
+                service.service_id(),
+                epoch,
+                service.epoch()
+            );
+            return Ok(());
+        }
+
+        let coordinator = service.coordinator().clone();
+
+        let accept = match self.execute(&service, &batch) {
+            Ok(computed_state_root) if computed_state_root != state_root => {
+                warn!(
+                    "Rejecting batch for {} epoch {}, resulting state root {} does not match the \
+                     coordinator's state root {}",
+                    service.service_id(),
+                    epoch,
+                    computed_state_root,
+                    state_root
+                );
+                false
+            }
+            Ok(_) => {
+                let batch_id = BatchPair::from_bytes(&batch)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?
+                    .batch()
+                    .header_signature()
+                    .to_string();
+                match self.store.add_batch(
+                    service.service_id(),
+                    ScabbardBatch::new(batch_id, batch, BatchStatus::Pending(epoch))
+                        .with_state_root(state_root),
+                ) {
+                    Ok(()) => true,
+                    Err(ScabbardStoreError::ConstraintViolation(_)) => {
+                        warn!(
+                            "Rejecting batch for {} epoch {}, batch was already received",
+                            service.service_id(),
+                            epoch
+                        );
+                        false
+                    }
+                    Err(err) => return Err(to_internal(err)),
+                }
+            }
+            Err(err) => {
+                warn!(
+                    "Rejecting batch for {} epoch {}, batch is invalid: {}",
+                    service.service_id(),
+                    epoch,
+                    err
+                );
+                false
+            }
+        };
+
+        let builder = service.into_builder().with_epoch(epoch);
+        let service = if accept {
+            builder
+                .with_state(&TwoPhaseState::WaitingForDecision)
+                .with_alarm(self.alarm()?)
+                .build()
+        } else {
+            // A participant that rejects the batch does not need to wait for the decision
+            builder
+                .with_state(&TwoPhaseState::Idle)
+                .clear_alarm()
+                .build()
+        }
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        self.store.update_service(service).map_err(to_internal)?;
+
+        self.sender
+            .send(&coordinator, ScabbardMessage::Vote { epoch, accept })
+    }
+
+    fn handle_vote(
+        &self,
+        service: ScabbardService,
+        from_service: &ServiceId,
+        epoch: u64,
+        accept: bool,
+    ) -> Result<(), InternalError> {
+        let mut votes = match service.state() {
+            TwoPhaseState::Voting { votes } if epoch == service.epoch() => votes.clone(),
+            _ => {
+                warn!(
+                    "Ignoring vote for {} epoch {} from {}, not waiting for votes for that epoch",
+                    service.service_id(),
+                    epoch,
+                    from_service
+                );
+                return Ok(());
+            }
+        };
+
+        if votes.iter().any(|(peer, _)| peer == from_service) {
+            warn!(
+                "Ignoring duplicate vote for {} epoch {} from {}",
+                service.service_id(),
+                epoch,
+                from_service
+            );
+            return Ok(());
+        }
+
+        if !accept {
+            info!(
+                "{} rejected the batch for {} epoch {}",
+                from_service,
+                service.service_id(),
+                epoch
+            );
+            return self.decide(service, false);
+        }
+
+        votes.push((from_service.clone(), accept));
+
+        if votes.len() == service.peers().len() {
+            return self.decide(service, true);
+        }
+
+        let service = service
+            .into_builder()
+            .with_state(&TwoPhaseState::Voting { votes })
+            .build()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        self.store.update_service(service).map_err(to_internal)
+    }
+
+    /// Records the coordinator's decision for the current epoch and notifies all peers.
+    fn decide(&self, service: ScabbardService, commit: bool) -> Result<(), InternalError> {
+        let epoch = service.epoch();
+        let peers = service.peers().to_vec();
+
+        self.finish_epoch(service, commit)?;
+
+        let message = if commit {
+            ScabbardMessage::Commit { epoch }
+        } else {
+            ScabbardMessage::Abort { epoch }
+        };
+
+        for peer in peers {
+            self.sender.send(&peer, message.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_decision(
+        &self,
+        service: ScabbardService,
+        epoch: u64,
+        commit: bool,
+    ) -> Result<(), InternalError> {
+        if service.state() != &TwoPhaseState::WaitingForDecision || epoch != service.epoch() {
+            debug!(
+                "Ignoring decision for {} epoch {}, not waiting for a decision for that epoch",
+                service.service_id(),
+                epoch
+            );
+            return Ok(());
+        }
+
+        self.finish_epoch(service, commit)
+    }
+
+    fn handle_decision_request(
+        &self,
+        service: ScabbardService,
+        from_service: &ServiceId,
+        epoch: u64,
+    ) -> Result<(), InternalError> {
+        if !service.is_coordinator() {
+            warn!(
+                "Ignoring decision request for {} from {}, this service is not the coordinator",
+                service.service_id(),
+                from_service
+            );
+            return Ok(());
+        }
+
+        let message = match self
+            .store
+            .get_batch_for_epoch(service.service_id(), epoch)
+            .map_err(to_internal)?
+            .map(|batch| batch.status().clone())
+        {
+            Some(BatchStatus::Committed(_)) => ScabbardMessage::Commit { epoch },
+            // The decision has not been made yet; it will be sent once it is
+            Some(BatchStatus::Pending(_)) => return Ok(()),
+            _ => ScabbardMessage::Abort { epoch },
+        };
+
+        self.sender.send(from_service, message)
+    }
+
+    /// Marks the current epoch's batch as committed or aborted and returns the service to idle.
+    ///
+    /// A batch that is committed is applied to the service's merkle state first. If the state
+    /// root already matches the batch's state root, the changes were applied before the service
+    /// was last stopped and are not applied again.
+    fn finish_epoch(&self, service: ScabbardService, commit: bool) -> Result<(), InternalError> {
+        let epoch = service.epoch();
+
+        if let Some(batch) = self
+            .store
+            .get_batch_for_epoch(service.service_id(), epoch)
+            .map_err(to_internal)?
+        {
+            if commit {
+                self.apply(&service, &batch)?;
+            }
+
+            let status = if commit {
+                BatchStatus::Committed(epoch)
+            } else {
+                BatchStatus::Aborted(epoch)
+            };
+
+            info!(
+                "{} batch {} for {} in epoch {}",
+                if commit { "Committed" } else { "Aborted" },
+                batch.batch_id(),
+                service.service_id(),
+                epoch
+            );
+
+            self.store
+                .update_batch(service.service_id(), batch.with_status(status))
+                .map_err(to_internal)?;
+        }
+
+        let service = service
+            .into_builder()
+            .with_state(&TwoPhaseState::Idle)
+            .clear_alarm()
+            .build()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        self.store.update_service(service).map_err(to_internal)
+    }
+
+    /// Executes the serialized batch against the service's current state, without committing it,
+    /// and returns the resulting state root.
+    fn execute(&self, service: &ScabbardService, batch: &[u8]) -> Result<String, InternalError> {
+        let batch_pair = BatchPair::from_bytes(batch)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        self.executor.execute(service, batch_pair)
+    }
+
+    /// Commits the batch's changes to the service's merkle state, unless they have already been
+    /// committed.
+    fn apply(&self, service: &ScabbardService, batch: &ScabbardBatch) -> Result<(), InternalError> {
+        let expected_state_root = batch.state_root().ok_or_else(|| {
+            InternalError::with_message(format!(
+                "Batch {} for {} has no state root",
+                batch.batch_id(),
+                service.service_id()
+            ))
+        })?;
+
+        if self.executor.current_state_root(service)? == expected_state_root {
+            return Ok(());
+        }
+
+        let batch_pair = BatchPair::from_bytes(batch.batch())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let state_root = self.executor.commit(service, batch_pair)?;
+        if state_root != expected_state_root {
+            return Err(InternalError::with_message(format!(
+                "Committing batch {} for {} resulted in state root {}, expected {}",
+                batch.batch_id(),
+                service.service_id(),
+                state_root,
+                expected_state_root
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn alarm(&self) -> Result<SystemTime, InternalError> {
+        SystemTime::now().checked_add(self.timeout).ok_or_else(|| {
+            InternalError::with_message("Unable to compute the time of the next alarm".into())
+        })
+    }
+}
+
+fn to_internal(err: ScabbardStoreError) -> InternalError {
+    InternalError::from_source(Box::new(err))
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use splinter::service::FullyQualifiedServiceId;
+    use transact::{
+        families::command::CommandTransactionBuilder,
+        protocol::command::{BytesEntry, Command, SetState},
+        protos::IntoBytes,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::store::{DieselScabbardStore, ScabbardServiceBuilder};
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Verify that a batch is committed by the coordinator and the participant when the
+    /// participant accepts the batch.
+    ///
+    /// 1. Add a coordinator and a participant to the store, and queue a valid batch on the
+    ///    coordinator
+    /// 2. Wake up the coordinator and validate it sends a vote request with the batch's state
+    ///    root to the participant
+    /// 3. Pass the vote request to the participant and validate it votes to accept the batch
+    /// 4. Pass the vote to the coordinator and validate it sends a commit, marks the batch as
+    ///    committed and applies the batch to its state
+    /// 5. Pass the commit to the participant and validate it marks its batch as committed,
+    ///    applies the batch to its state and returns to idle
+    #[test]
+    fn test_two_phase_commit() {
+        let store = DieselScabbardStore::new(create_connection_pool_and_migrate());
+        let executor = MockBatchExecutor::default();
+        let sender = MockMessageSender::default();
+        let two_phase = TwoPhaseCommit::new(&store, &executor, &sender, TIMEOUT);
+
+        let (coordinator, participant) = add_services(&store);
+        let (batch_id, batch) = create_batch();
+        store
+            .add_batch(
+                &coordinator,
+                ScabbardBatch::new(batch_id.clone(), batch.clone(), BatchStatus::Queued),
+            )
+            .expect("Unable to add batch");
+
+        assert_eq!(
+            store
+                .list_ready_services()
+                .expect("Unable to list ready services"),
+            vec![coordinator.clone()]
+        );
+
+        two_phase
+            .handle_timer(get_service(&store, &coordinator))
+            .expect("Unable to handle timer");
+
+        let state_root = MockBatchExecutor::next_state_root(INITIAL_STATE_ROOT, &batch_id);
+        let vote_request = ScabbardMessage::VoteRequest {
+            epoch: 1,
+            batch: batch.clone(),
+            state_root: state_root.clone(),
+        };
+        assert_eq!(
+            sender.take(),
+            vec![(participant.service_id().clone(), vote_request.clone())]
+        );
+
+        two_phase
+            .handle_message(
+                get_service(&store, &participant),
+                coordinator.service_id(),
+                vote_request,
+            )
+            .expect("Unable to handle vote request");
+
+        let vote = ScabbardMessage::Vote {
+            epoch: 1,
+            accept: true,
+        };
+        assert_eq!(
+            sender.take(),
+            vec![(coordinator.service_id().clone(), vote.clone())]
+        );
+        assert_eq!(
+            get_service(&store, &participant).state(),
+            &TwoPhaseState::WaitingForDecision
+        );
+
+        two_phase
+            .handle_message(
+                get_service(&store, &coordinator),
+                participant.service_id(),
+                vote,
+            )
+            .expect("Unable to handle vote");
+
+        let commit = ScabbardMessage::Commit { epoch: 1 };
+        assert_eq!(
+            sender.take(),
+            vec![(participant.service_id().clone(), commit.clone())]
+        );
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&coordinator, 1)
+                .expect("Unable to get batch"),
+            Some(
+                ScabbardBatch::new(batch_id.clone(), batch.clone(), BatchStatus::Committed(1))
+                    .with_state_root(state_root.clone())
+            )
+        );
+        assert_eq!(executor.state_root(&coordinator), state_root);
+
+        two_phase
+            .handle_message(
+                get_service(&store, &participant),
+                coordinator.service_id(),
+                commit,
+            )
+            .expect("Unable to handle commit");
+
+        assert!(sender.take().is_empty());
+        let participant_service = get_service(&store, &participant);
+        assert_eq!(participant_service.state(), &TwoPhaseState::Idle);
+        assert_eq!(participant_service.alarm(), None);
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&participant, 1)
+                .expect("Unable to get batch"),
+            Some(
+                ScabbardBatch::new(batch_id, batch, BatchStatus::Committed(1))
+                    .with_state_root(state_root.clone())
+            )
+        );
+        assert_eq!(executor.state_root(&participant), state_root);
+    }
+
+    /// Verify that a batch is aborted when the participant computes a different state root than
+    /// the coordinator.
+    ///
+    /// 1. Add a coordinator and a participant to the store, where the participant's state differs
+    ///    from the coordinator's, and queue a valid batch on the coordinator
+    /// 2. Wake up the coordinator and pass the vote request to the participant
+    /// 3. Validate the participant votes to reject the batch and remains idle
+    /// 4. Pass the vote to the coordinator and validate it sends an abort, marks the batch as
+    ///    aborted and does not apply the batch to its state
+    #[test]
+    fn test_two_phase_abort_state_root_mismatch() {
+        let store = DieselScabbardStore::new(create_connection_pool_and_migrate());
+        let coordinator_executor = MockBatchExecutor::default();
+        let participant_executor = MockBatchExecutor::new("diverged");
+        let sender = MockMessageSender::default();
+        let coordinator_two_phase =
+            TwoPhaseCommit::new(&store, &coordinator_executor, &sender, TIMEOUT);
+        let participant_two_phase =
+            TwoPhaseCommit::new(&store, &participant_executor, &sender, TIMEOUT);
+
+        let (coordinator, participant) = add_services(&store);
+        let (batch_id, batch) = create_batch();
+        store
+            .add_batch(
+                &coordinator,
+                ScabbardBatch::new(batch_id, batch, BatchStatus::Queued),
+            )
+            .expect("Unable to add batch");
+
+        coordinator_two_phase
+            .handle_timer(get_service(&store, &coordinator))
+            .expect("Unable to handle timer");
+
+        let (_, vote_request) = sender.take().pop().expect("No vote request sent");
+        participant_two_phase
+            .handle_message(
+                get_service(&store, &participant),
+                coordinator.service_id(),
+                vote_request,
+            )
+            .expect("Unable to handle vote request");
+
+        let vote = ScabbardMessage::Vote {
+            epoch: 1,
+            accept: false,
+        };
+        assert_eq!(
+            sender.take(),
+            vec![(coordinator.service_id().clone(), vote.clone())]
+        );
+        assert_eq!(
+            get_service(&store, &participant).state(),
+            &TwoPhaseState::Idle
+        );
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&participant, 1)
+                .expect("Unable to get batch"),
+            None
+        );
+
+        coordinator_two_phase
+            .handle_message(
+                get_service(&store, &coordinator),
+                participant.service_id(),
+                vote,
+            )
+            .expect("Unable to handle vote");
+
+        assert_eq!(
+            sender.take(),
+            vec![(
+                participant.service_id().clone(),
+                ScabbardMessage::Abort { epoch: 1 }
+            )]
+        );
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&coordinator, 1)
+                .expect("Unable to get batch")
+                .map(|batch| batch.status().clone()),
+            Some(BatchStatus::Aborted(1))
+        );
+        assert_eq!(
+            get_service(&store, &coordinator).state(),
+            &TwoPhaseState::Idle
+        );
+        assert_eq!(
+            coordinator_executor.state_root(&coordinator),
+            INITIAL_STATE_ROOT
+        );
+    }
+
+    /// Verify that the coordinator aborts an invalid batch without requesting any votes.
+    ///
+    /// 1. Add a coordinator and a participant to the store, and queue an invalid batch on the
+    ///    coordinator
+    /// 2. Wake up the coordinator and validate it does not send any messages
+    /// 3. Validate the batch is aborted and the coordinator is idle in the batch's epoch
+    #[test]
+    fn test_two_phase_invalid_batch() {
+        let store = DieselScabbardStore::new(create_connection_pool_and_migrate());
+        let executor = MockBatchExecutor::default();
+        let sender = MockMessageSender::default();
+        let two_phase = TwoPhaseCommit::new(&store, &executor, &sender, TIMEOUT);
+
+        let (coordinator, _) = add_services(&store);
+        store
+            .add_batch(
+                &coordinator,
+                ScabbardBatch::new("invalid".into(), b"invalid".to_vec(), BatchStatus::Queued),
+            )
+            .expect("Unable to add batch");
+
+        two_phase
+            .handle_timer(get_service(&store, &coordinator))
+            .expect("Unable to handle timer");
+
+        assert!(sender.take().is_empty());
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&coordinator, 1)
+                .expect("Unable to get batch")
+                .map(|batch| batch.status().clone()),
+            Some(BatchStatus::Aborted(1))
+        );
+        let coordinator_service = get_service(&store, &coordinator);
+        assert_eq!(coordinator_service.state(), &TwoPhaseState::Idle);
+        assert_eq!(coordinator_service.epoch(), 1);
+    }
+
+    /// Verify that the coordinator aborts an epoch if it does not receive all votes before its
+    /// alarm passes.
+    ///
+    /// 1. Add a coordinator and a participant to the store, and queue a batch on the coordinator
+    /// 2. Wake up the coordinator, which has a timeout of zero, and validate it sends a vote
+    ///    request
+    /// 3. Wake up the coordinator again and validate it sends an abort and marks the batch as
+    ///    aborted
+    #[test]
+    fn test_two_phase_vote_timeout() {
+        let store = DieselScabbardStore::new(create_connection_pool_and_migrate());
+        let executor = MockBatchExecutor::default();
+        let sender = MockMessageSender::default();
+        let two_phase = TwoPhaseCommit::new(&store, &executor, &sender, Duration::from_secs(0));
+
+        let (coordinator, participant) = add_services(&store);
+        let (batch_id, batch) = create_batch();
+        store
+            .add_batch(
+                &coordinator,
+                ScabbardBatch::new(batch_id, batch, BatchStatus::Queued),
+            )
+            .expect("Unable to add batch");
+
+        two_phase
+            .handle_timer(get_service(&store, &coordinator))
+            .expect("Unable to handle timer");
+        assert_eq!(sender.take().len(), 1);
+
+        assert_eq!(
+            store
+                .list_ready_services()
+                .expect("Unable to list ready services"),
+            vec![coordinator.clone()]
+        );
+
+        two_phase
+            .handle_timer(get_service(&store, &coordinator))
+            .expect("Unable to handle timer");
+
+        assert_eq!(
+            sender.take(),
+            vec![(
+                participant.service_id().clone(),
+                ScabbardMessage::Abort { epoch: 1 }
+            )]
+        );
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&coordinator, 1)
+                .expect("Unable to get batch")
+                .map(|batch| batch.status().clone()),
+            Some(BatchStatus::Aborted(1))
+        );
+    }
+
+    const INITIAL_STATE_ROOT: &str = "initial";
+
+    /// A `BatchExecutor` whose state roots are derived from the IDs of the committed batches
+    struct MockBatchExecutor {
+        initial_state_root: String,
+        state_roots: Mutex<HashMap<FullyQualifiedServiceId, String>>,
+    }
+
+    impl MockBatchExecutor {
+        fn new(initial_state_root: &str) -> Self {
+            Self {
+                initial_state_root: initial_state_root.into(),
+                state_roots: Mutex::new(HashMap::new()),
+            }
+        }
+
+        fn next_state_root(state_root: &str, batch_id: &str) -> String {
+            format!("{}/{}", state_root, batch_id)
+        }
+
+        fn state_root(&self, service_id: &FullyQualifiedServiceId) -> String {
+            self.state_roots
+                .lock()
+                .expect("State root lock poisoned")
+                .get(service_id)
+                .cloned()
+                .unwrap_or_else(|| self.initial_state_root.clone())
+        }
+    }
+
+    impl Default for MockBatchExecutor {
+        fn default() -> Self {
+            Self::new(INITIAL_STATE_ROOT)
+        }
+    }
+
+    impl BatchExecutor for MockBatchExecutor {
+        fn current_state_root(&self, service: &ScabbardService) -> Result<String, InternalError> {
+            Ok(self.state_root(service.service_id()))
+        }
+
+        fn execute(
+            &self,
+            service: &ScabbardService,
+            batch: BatchPair,
+        ) -> Result<String, InternalError> {
+            Ok(Self::next_state_root(
+                &self.state_root(service.service_id()),
+                batch.batch().header_signature(),
+            ))
+        }
+
+        fn commit(
+            &self,
+            service: &ScabbardService,
+            batch: BatchPair,
+        ) -> Result<String, InternalError> {
+            let state_root = self.execute(service, batch)?;
+            self.state_roots
+                .lock()
+                .expect("State root lock poisoned")
+                .insert(service.service_id().clone(), state_root.clone());
+            Ok(state_root)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockMessageSender {
+        sent: RefCell<Vec<(ServiceId, ScabbardMessage)>>,
+    }
+
+    impl MockMessageSender {
+        fn take(&self) -> Vec<(ServiceId, ScabbardMessage)> {
+            self.sent.borrow_mut().drain(..).collect()
+        }
+    }
+
+    impl MessageSender<ScabbardMessage> for MockMessageSender {
+        fn send(
+            &self,
+            to_service: &ServiceId,
+            message: ScabbardMessage,
+        ) -> Result<(), InternalError> {
+            self.sent.borrow_mut().push((to_service.clone(), message));
+            Ok(())
+        }
+    }
+
+    /// Adds a finalized coordinator, `a000`, and participant, `b000`, to the store
+    fn add_services(
+        store: &dyn ScabbardStore,
+    ) -> (FullyQualifiedServiceId, FullyQualifiedServiceId) {
+        let coordinator = FullyQualifiedServiceId::new_from_string("abcde-01234::a000")
+            .expect("Unable to create service ID");
+        let participant = FullyQualifiedServiceId::new_from_string("abcde-01234::b000")
+            .expect("Unable to create service ID");
+
+        for (service_id, peer) in &[(&coordinator, &participant), (&participant, &coordinator)] {
+            store
+                .add_service(
+                    ScabbardServiceBuilder::new()
+                        .with_service_id(service_id)
+                        .with_peers(&[peer.service_id().clone()])
+                        .with_status(&ServiceStatus::Finalized)
+                        .build()
+                        .expect("Unable to build service"),
+                )
+                .expect("Unable to add service");
+        }
+
+        (coordinator, participant)
+    }
+
+    fn get_service(
+        store: &dyn ScabbardStore,
+        service_id: &FullyQualifiedServiceId,
+    ) -> ScabbardService {
+        store
+            .get_service(service_id)
+            .expect("Unable to get service")
+            .expect("Service does not exist")
+    }
+
+    /// Creates a valid, serialized batch and returns it along with its ID
+    fn create_batch() -> (String, Vec<u8>) {
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let batch = CommandTransactionBuilder::new()
+            .with_commands(vec![Command::SetState(SetState::new(vec![
+                BytesEntry::new("abcdef".into(), b"value".to_vec()),
+            ]))])
+            .into_transaction_builder()
+            .expect("failed to convert to transaction builder")
+            .into_batch_builder(&*signer)
+            .expect("failed to build transaction")
+            .build_pair(&*signer)
+            .expect("Failed to build batch");
+
+        let batch_id = batch.batch().header_signature().to_string();
+        (
+            batch_id,
+            batch.into_bytes().expect("Unable to serialize batch"),
+        )
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -12,20 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::marker::PhantomData;
+use std::sync::Arc;
 
-use log::info;
 use splinter::{error::InternalError, store::command::StoreCommand};
 
-#[derive(Default)]
+use crate::store::scabbard_store::{ScabbardService, ScabbardStoreFactory};
+
 pub struct ScabbardPrepareServiceCommand<C> {
-    _store_factory: PhantomData<C>,
+    store_factory: Arc<dyn ScabbardStoreFactory<C>>,
+    service: ScabbardService,
 }
 
 impl<C> ScabbardPrepareServiceCommand<C> {
-    pub fn new() -> Self {
+    pub fn new(store_factory: Arc<dyn ScabbardStoreFactory<C>>, service: ScabbardService) -> Self {
         Self {
-            _store_factory: PhantomData,
+            store_factory,
+            service,
         }
     }
 }
@@ -33,8 +35,10 @@ impl<C> ScabbardPrepareServiceCommand<C> {
 impl<C> StoreCommand for ScabbardPrepareServiceCommand<C> {
     type Context = C;
 
-    fn execute(&self, _conn: &Self::Context) -> Result<(), InternalError> {
-        info!("executing prepare service command");
-        Ok(())
+    fn execute(&self, conn: &Self::Context) -> Result<(), InternalError> {
+        self.store_factory
+            .new_store(conn)
+            .add_service(self.service.clone())
+            .map_err(|err| InternalError::from_source(Box::new(err)))
     }
 }
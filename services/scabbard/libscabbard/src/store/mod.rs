@@ -21,13 +21,33 @@ pub mod diesel;
 mod error;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 pub(crate) mod pool;
+#[cfg(feature = "consensus-raft")]
+pub mod raft_store;
+#[cfg(feature = "scabbardv3")]
+pub mod scabbard_store;
 pub mod transact;
+pub mod two_phase_store;
 
 #[cfg(feature = "scabbardv3")]
 pub use command::{
     ScabbardFinalizeServiceCommand, ScabbardPrepareServiceCommand, ScabbardPurgeServiceCommand,
     ScabbardRetireServiceCommand,
 };
+#[cfg(all(feature = "consensus-raft", feature = "diesel"))]
+pub use raft_store::diesel::DieselRaftStore;
+#[cfg(all(feature = "scabbardv3", feature = "postgres"))]
+pub use scabbard_store::diesel::factory::PostgresScabbardStoreFactory;
+#[cfg(all(feature = "scabbardv3", feature = "sqlite"))]
+pub use scabbard_store::diesel::factory::SqliteScabbardStoreFactory;
+#[cfg(feature = "diesel")]
+pub use two_phase_store::diesel::DieselTwoPhaseStore;
+#[cfg(all(feature = "scabbardv3", feature = "diesel"))]
+pub use scabbard_store::diesel::DieselScabbardStore;
+#[cfg(feature = "scabbardv3")]
+pub use scabbard_store::{
+    BatchStatus, ScabbardBatch, ScabbardService, ScabbardServiceBuilder, ScabbardStore,
+    ScabbardStoreError, ScabbardStoreFactory, ServiceStatus, TwoPhaseState,
+};
 
 pub use error::CommitHashStoreError;
 
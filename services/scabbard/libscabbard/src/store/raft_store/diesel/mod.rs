@@ -0,0 +1,271 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A database-backed `RaftStore` for scabbard services that use Raft consensus.
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+use splinter::consensus::raft::{HardState, RaftLogEntry, RaftStore};
+use splinter::error::InternalError;
+
+use crate::store::pool::ConnectionPool;
+
+use operations::append_entries::RaftStoreAppendEntriesOperation as _;
+use operations::get_hard_state::RaftStoreGetHardStateOperation as _;
+use operations::list_entries::RaftStoreListEntriesOperation as _;
+use operations::set_hard_state::RaftStoreSetHardStateOperation as _;
+use operations::truncate_entries::RaftStoreTruncateEntriesOperation as _;
+use operations::RaftStoreOperations;
+
+/// Database backed [RaftStore] implementation.
+#[derive(Clone)]
+pub struct DieselRaftStore<Conn: diesel::Connection + 'static> {
+    pool: ConnectionPool<Conn>,
+    circuit_id: Arc<str>,
+    service_id: Arc<str>,
+}
+
+impl<C: diesel::Connection> DieselRaftStore<C> {
+    /// Constructs new DieselRaftStore.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `circuit_id` - The circuit associated with the store
+    /// * `service_id` - The service associated with the store
+    pub fn new(pool: Pool<ConnectionManager<C>>, circuit_id: &str, service_id: &str) -> Self {
+        Self {
+            pool: ConnectionPool::Normal(pool),
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+        }
+    }
+
+    /// Create a new `DieselRaftStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool`: read-write lock-guarded connection pool for the database
+    /// * `circuit_id` - The circuit associated with the store
+    /// * `service_id` - The service associated with the store
+    pub fn new_with_write_exclusivity(
+        pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Self {
+        Self {
+            pool: ConnectionPool::WriteExclusive(pool),
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl RaftStore for DieselRaftStore<diesel::pg::PgConnection> {
+    fn get_hard_state(&self) -> Result<HardState, InternalError> {
+        self.pool.execute_read(|conn| {
+            RaftStoreOperations::new(conn).get_hard_state(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn set_hard_state(&self, hard_state: &HardState) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            RaftStoreOperations::new(conn).set_hard_state(
+                &*self.circuit_id,
+                &*self.service_id,
+                hard_state,
+            )
+        })
+    }
+
+    fn list_entries(&self) -> Result<Vec<RaftLogEntry>, InternalError> {
+        self.pool.execute_read(|conn| {
+            RaftStoreOperations::new(conn).list_entries(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn append_entries(&self, entries: &[RaftLogEntry]) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            RaftStoreOperations::new(conn).append_entries(
+                &*self.circuit_id,
+                &*self.service_id,
+                entries,
+            )
+        })
+    }
+
+    fn truncate_entries(&self, from_index: u64) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            RaftStoreOperations::new(conn).truncate_entries(
+                &*self.circuit_id,
+                &*self.service_id,
+                from_index,
+            )
+        })
+    }
+
+    fn clone_boxed(&self) -> Box<dyn RaftStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl RaftStore for DieselRaftStore<diesel::sqlite::SqliteConnection> {
+    fn get_hard_state(&self) -> Result<HardState, InternalError> {
+        self.pool.execute_read(|conn| {
+            RaftStoreOperations::new(conn).get_hard_state(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn set_hard_state(&self, hard_state: &HardState) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            RaftStoreOperations::new(conn).set_hard_state(
+                &*self.circuit_id,
+                &*self.service_id,
+                hard_state,
+            )
+        })
+    }
+
+    fn list_entries(&self) -> Result<Vec<RaftLogEntry>, InternalError> {
+        self.pool.execute_read(|conn| {
+            RaftStoreOperations::new(conn).list_entries(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn append_entries(&self, entries: &[RaftLogEntry]) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            RaftStoreOperations::new(conn).append_entries(
+                &*self.circuit_id,
+                &*self.service_id,
+                entries,
+            )
+        })
+    }
+
+    fn truncate_entries(&self, from_index: u64) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            RaftStoreOperations::new(conn).truncate_entries(
+                &*self.circuit_id,
+                &*self.service_id,
+                from_index,
+            )
+        })
+    }
+
+    fn clone_boxed(&self) -> Box<dyn RaftStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use splinter::consensus::Proposal;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Test that a DieselRaftStore using a SQLite connection pool can
+    /// 1. Return the default hard state before one has been set
+    /// 2. Set and get the hard state, including clearing the vote
+    /// 3. Append and list entries, with and without proposals
+    /// 4. Truncate the log
+    /// 5. Verify that the state is isolated to the service
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_raft_store() -> Result<(), Box<dyn std::error::Error>> {
+        let pool = create_connection_pool_and_migrate()?;
+        let store = DieselRaftStore::new(pool.clone(), "circuit", "service_1");
+
+        assert_eq!(HardState::default(), store.get_hard_state()?);
+
+        let mut hard_state = HardState {
+            term: 2,
+            voted_for: Some(b"service_2".to_vec().into()),
+            applied_index: 1,
+        };
+        store.set_hard_state(&hard_state)?;
+        assert_eq!(hard_state, store.get_hard_state()?);
+
+        hard_state.term = 3;
+        hard_state.voted_for = None;
+        store.set_hard_state(&hard_state)?;
+        assert_eq!(hard_state, store.get_hard_state()?);
+
+        let mut proposal = Proposal::default();
+        proposal.id = b"proposal".to_vec().into();
+        proposal.summary = b"summary".to_vec();
+        let entries = vec![
+            RaftLogEntry {
+                index: 1,
+                term: 1,
+                proposal: None,
+                proposal_data: vec![],
+            },
+            RaftLogEntry {
+                index: 2,
+                term: 1,
+                proposal: Some(proposal),
+                proposal_data: b"data".to_vec(),
+            },
+            RaftLogEntry {
+                index: 3,
+                term: 2,
+                proposal: None,
+                proposal_data: vec![],
+            },
+        ];
+        store.append_entries(&entries[..2])?;
+        store.append_entries(&entries[2..])?;
+        assert_eq!(entries, store.list_entries()?);
+
+        store.truncate_entries(2)?;
+        assert_eq!(entries[..1].to_vec(), store.list_entries()?);
+
+        // Check that another service on the same circuit has no state.
+        let other_store = DieselRaftStore::new(pool, "circuit", "service_2");
+        assert_eq!(HardState::default(), other_store.get_hard_state()?);
+        assert!(other_store.list_entries()?.is_empty());
+
+        Ok(())
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    #[cfg(feature = "sqlite")]
+    fn create_connection_pool_and_migrate(
+    ) -> Result<Pool<ConnectionManager<SqliteConnection>>, Box<dyn std::error::Error>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder().max_size(1).build(connection_manager)?;
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))?;
+
+        Ok(pool)
+    }
+}
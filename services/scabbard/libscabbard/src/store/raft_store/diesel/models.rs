@@ -0,0 +1,128 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::{TryFrom, TryInto};
+
+use splinter::consensus::{
+    raft::{HardState, RaftLogEntry},
+    Proposal,
+};
+use splinter::error::InternalError;
+
+use super::schema::{scabbard_raft_log, scabbard_raft_state};
+
+/// Database model representation of the Raft `HardState` of a service
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable, AsChangeset)]
+#[table_name = "scabbard_raft_state"]
+#[primary_key(circuit_id, service_id)]
+#[changeset_options(treat_none_as_null = "true")]
+pub struct RaftStateModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub current_term: i64,
+    pub voted_for: Option<Vec<u8>>,
+    pub applied_index: i64,
+}
+
+impl RaftStateModel {
+    pub fn new(
+        circuit_id: &str,
+        service_id: &str,
+        hard_state: &HardState,
+    ) -> Result<Self, InternalError> {
+        Ok(RaftStateModel {
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+            current_term: u64_to_i64(hard_state.term)?,
+            voted_for: hard_state.voted_for.clone().map(Vec::from),
+            applied_index: u64_to_i64(hard_state.applied_index)?,
+        })
+    }
+}
+
+impl TryFrom<RaftStateModel> for HardState {
+    type Error = InternalError;
+
+    fn try_from(model: RaftStateModel) -> Result<Self, Self::Error> {
+        Ok(HardState {
+            term: i64_to_u64(model.current_term)?,
+            voted_for: model.voted_for.map(|voted_for| voted_for.into()),
+            applied_index: i64_to_u64(model.applied_index)?,
+        })
+    }
+}
+
+/// Database model representation of an entry in a service's Raft log
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable)]
+#[table_name = "scabbard_raft_log"]
+#[primary_key(circuit_id, service_id, log_index)]
+pub struct RaftLogEntryModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub log_index: i64,
+    pub term: i64,
+    pub proposal: Option<Vec<u8>>,
+    pub proposal_data: Vec<u8>,
+}
+
+impl RaftLogEntryModel {
+    pub fn new(
+        circuit_id: &str,
+        service_id: &str,
+        entry: &RaftLogEntry,
+    ) -> Result<Self, InternalError> {
+        let proposal = entry
+            .proposal
+            .clone()
+            .map(|proposal| proposal.try_into())
+            .transpose()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(RaftLogEntryModel {
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+            log_index: u64_to_i64(entry.index)?,
+            term: u64_to_i64(entry.term)?,
+            proposal,
+            proposal_data: entry.proposal_data.clone(),
+        })
+    }
+}
+
+impl TryFrom<RaftLogEntryModel> for RaftLogEntry {
+    type Error = InternalError;
+
+    fn try_from(model: RaftLogEntryModel) -> Result<Self, Self::Error> {
+        let proposal = model
+            .proposal
+            .map(|bytes| Proposal::try_from(bytes.as_slice()))
+            .transpose()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(RaftLogEntry {
+            index: i64_to_u64(model.log_index)?,
+            term: i64_to_u64(model.term)?,
+            proposal,
+            proposal_data: model.proposal_data,
+        })
+    }
+}
+
+pub fn u64_to_i64(value: u64) -> Result<i64, InternalError> {
+    i64::try_from(value).map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+pub fn i64_to_u64(value: i64) -> Result<u64, InternalError> {
+    u64::try_from(value).map_err(|err| InternalError::from_source(Box::new(err)))
+}
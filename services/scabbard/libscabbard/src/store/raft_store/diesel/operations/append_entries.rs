@@ -0,0 +1,82 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "append entries" operation for the `DieselRaftStore`.
+
+use diesel::{insert_into, prelude::*};
+use splinter::consensus::raft::RaftLogEntry;
+use splinter::error::InternalError;
+
+use crate::store::raft_store::diesel::{models::RaftLogEntryModel, schema::scabbard_raft_log};
+
+use super::RaftStoreOperations;
+
+pub(in crate::store::raft_store::diesel) trait RaftStoreAppendEntriesOperation {
+    fn append_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        entries: &[RaftLogEntry],
+    ) -> Result<(), InternalError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RaftStoreAppendEntriesOperation for RaftStoreOperations<'a, diesel::pg::PgConnection> {
+    fn append_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        entries: &[RaftLogEntry],
+    ) -> Result<(), InternalError> {
+        let models = entries
+            .iter()
+            .map(|entry| RaftLogEntryModel::new(circuit_id, service_id, entry))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        insert_into(scabbard_raft_log::table)
+            .values(&models)
+            .execute(self.conn)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RaftStoreAppendEntriesOperation
+    for RaftStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn append_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        entries: &[RaftLogEntry],
+    ) -> Result<(), InternalError> {
+        let models = entries
+            .iter()
+            .map(|entry| RaftLogEntryModel::new(circuit_id, service_id, entry))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // SQLite inserts the entries one at a time, so insert them in a single transaction
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                insert_into(scabbard_raft_log::table)
+                    .values(&models)
+                    .execute(self.conn)
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
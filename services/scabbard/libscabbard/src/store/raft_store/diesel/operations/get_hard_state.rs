@@ -0,0 +1,61 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "get hard state" operation for the `DieselRaftStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Binary, Text},
+};
+use splinter::consensus::raft::HardState;
+use splinter::error::InternalError;
+
+use crate::store::raft_store::diesel::{models::RaftStateModel, schema::scabbard_raft_state};
+
+use super::RaftStoreOperations;
+
+pub(in crate::store::raft_store::diesel) trait RaftStoreGetHardStateOperation {
+    fn get_hard_state(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<HardState, InternalError>;
+}
+
+impl<'a, C> RaftStoreGetHardStateOperation for RaftStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<BigInt, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<Binary, C::Backend>,
+{
+    fn get_hard_state(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<HardState, InternalError> {
+        let model = scabbard_raft_state::table
+            .find((circuit_id, service_id))
+            .first::<RaftStateModel>(self.conn)
+            .optional()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        match model {
+            Some(model) => HardState::try_from(model),
+            None => Ok(HardState::default()),
+        }
+    }
+}
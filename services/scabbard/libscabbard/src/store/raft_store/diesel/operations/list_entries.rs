@@ -0,0 +1,60 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list entries" operation for the `DieselRaftStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Binary, Text},
+};
+use splinter::consensus::raft::RaftLogEntry;
+use splinter::error::InternalError;
+
+use crate::store::raft_store::diesel::{models::RaftLogEntryModel, schema::scabbard_raft_log};
+
+use super::RaftStoreOperations;
+
+pub(in crate::store::raft_store::diesel) trait RaftStoreListEntriesOperation {
+    fn list_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Vec<RaftLogEntry>, InternalError>;
+}
+
+impl<'a, C> RaftStoreListEntriesOperation for RaftStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<BigInt, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<Binary, C::Backend>,
+{
+    fn list_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Vec<RaftLogEntry>, InternalError> {
+        scabbard_raft_log::table
+            .filter(scabbard_raft_log::circuit_id.eq(circuit_id))
+            .filter(scabbard_raft_log::service_id.eq(service_id))
+            .order(scabbard_raft_log::log_index)
+            .load::<RaftLogEntryModel>(self.conn)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .into_iter()
+            .map(RaftLogEntry::try_from)
+            .collect()
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "set hard state" operation for the `DieselRaftStore`.
+
+#[cfg(feature = "postgres")]
+use diesel::insert_into;
+use diesel::prelude::*;
+#[cfg(feature = "sqlite")]
+use diesel::replace_into;
+use splinter::consensus::raft::HardState;
+use splinter::error::InternalError;
+
+use crate::store::raft_store::diesel::{models::RaftStateModel, schema::scabbard_raft_state};
+
+use super::RaftStoreOperations;
+
+pub(in crate::store::raft_store::diesel) trait RaftStoreSetHardStateOperation {
+    fn set_hard_state(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        hard_state: &HardState,
+    ) -> Result<(), InternalError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RaftStoreSetHardStateOperation
+    for RaftStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_hard_state(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        hard_state: &HardState,
+    ) -> Result<(), InternalError> {
+        replace_into(scabbard_raft_state::table)
+            .values(RaftStateModel::new(circuit_id, service_id, hard_state)?)
+            .execute(self.conn)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RaftStoreSetHardStateOperation for RaftStoreOperations<'a, diesel::pg::PgConnection> {
+    fn set_hard_state(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        hard_state: &HardState,
+    ) -> Result<(), InternalError> {
+        let model = RaftStateModel::new(circuit_id, service_id, hard_state)?;
+
+        insert_into(scabbard_raft_state::table)
+            .values(&model)
+            .on_conflict((
+                scabbard_raft_state::circuit_id,
+                scabbard_raft_state::service_id,
+            ))
+            .do_update()
+            .set(&model)
+            .execute(self.conn)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "truncate entries" operation for the `DieselRaftStore`.
+
+use diesel::{dsl::delete, prelude::*};
+use splinter::error::InternalError;
+
+use crate::store::raft_store::diesel::{models::u64_to_i64, schema::scabbard_raft_log};
+
+use super::RaftStoreOperations;
+
+pub(in crate::store::raft_store::diesel) trait RaftStoreTruncateEntriesOperation {
+    fn truncate_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        from_index: u64,
+    ) -> Result<(), InternalError>;
+}
+
+impl<'a, C> RaftStoreTruncateEntriesOperation for RaftStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    fn truncate_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        from_index: u64,
+    ) -> Result<(), InternalError> {
+        delete(
+            scabbard_raft_log::table
+                .filter(scabbard_raft_log::circuit_id.eq(circuit_id))
+                .filter(scabbard_raft_log::service_id.eq(service_id))
+                .filter(scabbard_raft_log::log_index.ge(u64_to_i64(from_index)?)),
+        )
+        .execute(self.conn)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
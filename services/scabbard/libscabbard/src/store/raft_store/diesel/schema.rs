@@ -0,0 +1,34 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+table! {
+    scabbard_raft_state (circuit_id, service_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        current_term -> BigInt,
+        voted_for -> Nullable<Binary>,
+        applied_index -> BigInt,
+    }
+}
+
+table! {
+    scabbard_raft_log (circuit_id, service_id, log_index) {
+        circuit_id -> Text,
+        service_id -> Text,
+        log_index -> BigInt,
+        term -> BigInt,
+        proposal -> Nullable<Binary>,
+        proposal_data -> Binary,
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structs for batches submitted to scabbard services
+
+/// A batch that has been submitted to a scabbard service, along with its two-phase commit status
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScabbardBatch {
+    batch_id: String,
+    batch: Vec<u8>,
+    status: BatchStatus,
+    state_root: Option<String>,
+}
+
+impl ScabbardBatch {
+    /// Creates a new `ScabbardBatch`
+    ///
+    /// # Arguments
+    ///
+    ///  * `batch_id` - The header signature of the batch
+    ///  * `batch` - The serialized batch
+    ///  * `status` - The two-phase commit status of the batch
+    pub fn new(batch_id: String, batch: Vec<u8>, status: BatchStatus) -> Self {
+        Self {
+            batch_id,
+            batch,
+            status,
+            state_root: None,
+        }
+    }
+
+    /// Returns the ID of the batch
+    pub fn batch_id(&self) -> &str {
+        &self.batch_id
+    }
+
+    /// Returns the serialized batch
+    pub fn batch(&self) -> &[u8] {
+        &self.batch
+    }
+
+    /// Returns the two-phase commit status of the batch
+    pub fn status(&self) -> &BatchStatus {
+        &self.status
+    }
+
+    /// Returns the state root that results from executing the batch, if it has been executed
+    pub fn state_root(&self) -> Option<&str> {
+        self.state_root.as_deref()
+    }
+
+    /// Returns a copy of the batch with the given status
+    pub fn with_status(self, status: BatchStatus) -> Self {
+        Self { status, ..self }
+    }
+
+    /// Returns a copy of the batch with the state root that results from executing it
+    pub fn with_state_root(self, state_root: String) -> Self {
+        Self {
+            state_root: Some(state_root),
+            ..self
+        }
+    }
+}
+
+/// The two-phase commit status of a batch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchStatus {
+    /// The batch is waiting to be proposed by the coordinator
+    Queued,
+    /// The batch is being agreed upon in the given epoch
+    Pending(u64),
+    /// The batch was committed in the given epoch
+    Committed(u64),
+    /// The batch was aborted in the given epoch
+    Aborted(u64),
+}
+
+impl BatchStatus {
+    /// Returns the epoch the batch was proposed in, if it has been proposed
+    pub fn epoch(&self) -> Option<u64> {
+        match self {
+            BatchStatus::Queued => None,
+            BatchStatus::Pending(epoch)
+            | BatchStatus::Committed(epoch)
+            | BatchStatus::Aborted(epoch) => Some(*epoch),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{DieselConnectionScabbardStore, ScabbardStore, ScabbardStoreFactory};
+
+#[cfg(feature = "sqlite")]
+pub struct SqliteScabbardStoreFactory;
+
+#[cfg(feature = "sqlite")]
+impl ScabbardStoreFactory<diesel::sqlite::SqliteConnection> for SqliteScabbardStoreFactory {
+    fn new_store<'a>(
+        &'a self,
+        conn: &'a diesel::sqlite::SqliteConnection,
+    ) -> Box<dyn ScabbardStore + 'a> {
+        Box::new(DieselConnectionScabbardStore::new(conn))
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub struct PostgresScabbardStoreFactory;
+
+#[cfg(feature = "postgres")]
+impl ScabbardStoreFactory<diesel::pg::PgConnection> for PostgresScabbardStoreFactory {
+    fn new_store<'a>(&'a self, conn: &'a diesel::pg::PgConnection) -> Box<dyn ScabbardStore + 'a> {
+        Box::new(DieselConnectionScabbardStore::new(conn))
+    }
+}
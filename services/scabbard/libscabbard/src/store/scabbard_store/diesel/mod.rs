@@ -0,0 +1,635 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database backed [ScabbardStore](super::ScabbardStore) implementations.
+
+pub mod factory;
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::{
+    connection::AnsiTransactionManager,
+    r2d2::{ConnectionManager, Pool},
+};
+use splinter::service::FullyQualifiedServiceId;
+
+use crate::store::pool::ConnectionPool;
+
+use super::{ScabbardBatch, ScabbardService, ScabbardStore, ScabbardStoreError};
+
+use operations::add_batch::ScabbardStoreAddBatchOperation as _;
+use operations::add_service::ScabbardStoreAddServiceOperation as _;
+use operations::get_batch_for_epoch::ScabbardStoreGetBatchForEpochOperation as _;
+use operations::get_next_queued_batch::ScabbardStoreGetNextQueuedBatchOperation as _;
+use operations::get_service::ScabbardStoreGetServiceOperation as _;
+use operations::list_ready_services::ScabbardStoreListReadyServicesOperation as _;
+use operations::remove_service::ScabbardStoreRemoveServiceOperation as _;
+use operations::update_batch::ScabbardStoreUpdateBatchOperation as _;
+use operations::update_service::ScabbardStoreUpdateServiceOperation as _;
+use operations::ScabbardStoreOperations;
+
+/// A database-backed ScabbardStore, powered by [`Diesel`](https://crates.io/crates/diesel).
+pub struct DieselScabbardStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselScabbardStore<C> {
+    /// Creates a new `DieselScabbardStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselScabbardStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselScabbardStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselScabbardStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ScabbardStore for DieselScabbardStore<diesel::pg::PgConnection> {
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).add_service(service))
+    }
+
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).update_service(service))
+    }
+
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).remove_service(service_id))
+    }
+
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ScabbardStoreOperations::new(conn).get_service(service_id))
+    }
+
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ScabbardStoreOperations::new(conn).list_ready_services())
+    }
+
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).add_batch(service_id, batch))
+    }
+
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).update_batch(service_id, batch)
+        })
+    }
+
+    fn get_next_queued_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).get_next_queued_batch(service_id)
+        })
+    }
+
+    fn get_batch_for_epoch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        epoch: u64,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).get_batch_for_epoch(service_id, epoch)
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ScabbardStore for DieselScabbardStore<diesel::sqlite::SqliteConnection> {
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).add_service(service))
+    }
+
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).update_service(service))
+    }
+
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).remove_service(service_id))
+    }
+
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ScabbardStoreOperations::new(conn).get_service(service_id))
+    }
+
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError> {
+        self.connection_pool
+            .execute_read(|conn| ScabbardStoreOperations::new(conn).list_ready_services())
+    }
+
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.connection_pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).add_batch(service_id, batch))
+    }
+
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).update_batch(service_id, batch)
+        })
+    }
+
+    fn get_next_queued_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).get_next_queued_batch(service_id)
+        })
+    }
+
+    fn get_batch_for_epoch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        epoch: u64,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).get_batch_for_epoch(service_id, epoch)
+        })
+    }
+}
+
+pub struct DieselConnectionScabbardStore<'a, C>
+where
+    C: diesel::Connection<TransactionManager = AnsiTransactionManager> + 'static,
+    C::Backend: diesel::backend::UsesAnsiSavepointSyntax,
+{
+    connection: &'a C,
+}
+
+impl<'a, C> DieselConnectionScabbardStore<'a, C>
+where
+    C: diesel::Connection<TransactionManager = AnsiTransactionManager> + 'static,
+    C::Backend: diesel::backend::UsesAnsiSavepointSyntax,
+{
+    pub fn new(connection: &'a C) -> Self {
+        DieselConnectionScabbardStore { connection }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ScabbardStore for DieselConnectionScabbardStore<'a, diesel::pg::PgConnection> {
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_service(service)
+    }
+
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_service(service)
+    }
+
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).remove_service(service_id)
+    }
+
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_service(service_id)
+    }
+
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_ready_services()
+    }
+
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_batch(service_id, batch)
+    }
+
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_batch(service_id, batch)
+    }
+
+    fn get_next_queued_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_next_queued_batch(service_id)
+    }
+
+    fn get_batch_for_epoch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        epoch: u64,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_batch_for_epoch(service_id, epoch)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ScabbardStore for DieselConnectionScabbardStore<'a, diesel::sqlite::SqliteConnection> {
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_service(service)
+    }
+
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_service(service)
+    }
+
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).remove_service(service_id)
+    }
+
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_service(service_id)
+    }
+
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_ready_services()
+    }
+
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_batch(service_id, batch)
+    }
+
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_batch(service_id, batch)
+    }
+
+    fn get_next_queued_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_next_queued_batch(service_id)
+    }
+
+    fn get_batch_for_epoch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        epoch: u64,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_batch_for_epoch(service_id, epoch)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, SystemTime};
+
+    use diesel::sqlite::SqliteConnection;
+    use splinter::service::ServiceId;
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::store::scabbard_store::{
+        BatchStatus, ScabbardServiceBuilder, ServiceStatus, TwoPhaseState,
+    };
+
+    /// Verify that a service can be added, updated, fetched and removed.
+    ///
+    /// 1. Add a prepared service with admin keys and validate it can be fetched
+    /// 2. Update the service so it is voting with a recorded vote and an alarm, and validate the
+    ///    fetched service matches
+    /// 3. Remove the service and validate it can no longer be fetched
+    #[test]
+    fn test_add_update_remove_service() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselScabbardStore::new(pool);
+
+        let service = ScabbardServiceBuilder::new()
+            .with_service_id(&service_id("a000"))
+            .with_peers(&[peer("b000"), peer("c000")])
+            .with_status(&ServiceStatus::Prepared)
+            .with_admin_keys(&["abcd".to_string(), "ef01".to_string()])
+            .build()
+            .expect("Unable to build service");
+
+        store
+            .add_service(service.clone())
+            .expect("Unable to add service");
+
+        assert_eq!(
+            store
+                .get_service(&service_id("a000"))
+                .expect("Unable to get service"),
+            Some(service.clone())
+        );
+
+        // alarms are stored with a precision of seconds
+        let alarm = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let updated = service
+            .into_builder()
+            .with_status(&ServiceStatus::Finalized)
+            .with_epoch(3)
+            .with_state(&TwoPhaseState::Voting {
+                votes: vec![(peer("b000"), true)],
+            })
+            .with_alarm(alarm)
+            .build()
+            .expect("Unable to build service");
+
+        store
+            .update_service(updated.clone())
+            .expect("Unable to update service");
+
+        assert_eq!(
+            store
+                .get_service(&service_id("a000"))
+                .expect("Unable to get service"),
+            Some(updated)
+        );
+
+        store
+            .remove_service(&service_id("a000"))
+            .expect("Unable to remove service");
+
+        assert_eq!(
+            store
+                .get_service(&service_id("a000"))
+                .expect("Unable to get service"),
+            None
+        );
+    }
+
+    /// Verify that batches are queued in the order they are added and can be fetched by epoch.
+    ///
+    /// 1. Add a service and two queued batches
+    /// 2. Validate the first batch is the next queued batch
+    /// 3. Update the first batch to be pending in epoch 1 with its resulting state root, and
+    ///    validate the second batch is the next queued batch and the first batch is returned for
+    ///    epoch 1
+    /// 4. Validate adding a batch with a duplicate ID returns a constraint violation
+    /// 5. Validate updating a batch that does not exist returns an error
+    #[test]
+    fn test_batch_queue() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselScabbardStore::new(pool);
+
+        store
+            .add_service(finalized_service("a000", "b000"))
+            .expect("Unable to add service");
+
+        let first = ScabbardBatch::new("first".into(), b"first".to_vec(), BatchStatus::Queued);
+        let second = ScabbardBatch::new("second".into(), b"second".to_vec(), BatchStatus::Queued);
+
+        store
+            .add_batch(&service_id("a000"), first.clone())
+            .expect("Unable to add batch");
+        store
+            .add_batch(&service_id("a000"), second.clone())
+            .expect("Unable to add batch");
+
+        assert_eq!(
+            store
+                .get_next_queued_batch(&service_id("a000"))
+                .expect("Unable to get batch"),
+            Some(first.clone())
+        );
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&service_id("a000"), 1)
+                .expect("Unable to get batch"),
+            None
+        );
+
+        let pending = first
+            .with_status(BatchStatus::Pending(1))
+            .with_state_root("state-root".into());
+        store
+            .update_batch(&service_id("a000"), pending.clone())
+            .expect("Unable to update batch");
+
+        assert_eq!(
+            store
+                .get_next_queued_batch(&service_id("a000"))
+                .expect("Unable to get batch"),
+            Some(second.clone())
+        );
+        assert_eq!(
+            store
+                .get_batch_for_epoch(&service_id("a000"), 1)
+                .expect("Unable to get batch"),
+            Some(pending)
+        );
+
+        assert!(matches!(
+            store.add_batch(&service_id("a000"), second),
+            Err(ScabbardStoreError::ConstraintViolation(_))
+        ));
+
+        assert!(store
+            .update_batch(
+                &service_id("a000"),
+                ScabbardBatch::new("unknown".into(), vec![], BatchStatus::Aborted(2)),
+            )
+            .is_err());
+    }
+
+    /// Verify that only finalized services with work to do are listed as ready.
+    ///
+    /// 1. Add a finalized coordinator and a finalized participant and validate neither is ready
+    /// 2. Add a queued batch to both and validate only the coordinator is ready
+    /// 3. Set an alarm in the past on the participant and validate it is ready
+    /// 4. Set the coordinator to voting with an alarm in the future and validate it is no longer
+    ///    ready
+    #[test]
+    fn test_list_ready_services() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselScabbardStore::new(pool);
+
+        let coordinator = finalized_service("a000", "b000");
+        let participant = finalized_service("b000", "a000");
+
+        store
+            .add_service(coordinator.clone())
+            .expect("Unable to add service");
+        store
+            .add_service(participant.clone())
+            .expect("Unable to add service");
+
+        assert!(store
+            .list_ready_services()
+            .expect("Unable to list services")
+            .is_empty());
+
+        for id in &["a000", "b000"] {
+            store
+                .add_batch(
+                    &service_id(id),
+                    ScabbardBatch::new("batch".into(), b"batch".to_vec(), BatchStatus::Queued),
+                )
+                .expect("Unable to add batch");
+        }
+
+        assert_eq!(
+            store
+                .list_ready_services()
+                .expect("Unable to list services"),
+            vec![service_id("a000")]
+        );
+
+        store
+            .update_service(
+                participant
+                    .into_builder()
+                    .with_state(&TwoPhaseState::WaitingForDecision)
+                    .with_alarm(SystemTime::now() - Duration::from_secs(60))
+                    .build()
+                    .expect("Unable to build service"),
+            )
+            .expect("Unable to update service");
+
+        let mut ready = store
+            .list_ready_services()
+            .expect("Unable to list services");
+        ready.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+        assert_eq!(ready, vec![service_id("a000"), service_id("b000")]);
+
+        store
+            .update_service(
+                coordinator
+                    .into_builder()
+                    .with_state(&TwoPhaseState::Voting { votes: vec![] })
+                    .with_alarm(SystemTime::now() + Duration::from_secs(60))
+                    .build()
+                    .expect("Unable to build service"),
+            )
+            .expect("Unable to update service");
+
+        assert_eq!(
+            store
+                .list_ready_services()
+                .expect("Unable to list services"),
+            vec![service_id("b000")]
+        );
+    }
+
+    fn service_id(service_id: &str) -> FullyQualifiedServiceId {
+        FullyQualifiedServiceId::new_from_string(format!("abcde-01234::{}", service_id))
+            .expect("Unable to create service ID")
+    }
+
+    fn peer(service_id: &str) -> ServiceId {
+        ServiceId::new(service_id).expect("Unable to create service ID")
+    }
+
+    fn finalized_service(id: &str, peer_id: &str) -> ScabbardService {
+        ScabbardServiceBuilder::new()
+            .with_service_id(&service_id(id))
+            .with_peers(&[peer(peer_id)])
+            .with_status(&ServiceStatus::Finalized)
+            .build()
+            .expect("Unable to build service")
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
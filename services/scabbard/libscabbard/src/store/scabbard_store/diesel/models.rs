@@ -0,0 +1,278 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime};
+
+use splinter::error::{InternalError, InvalidStateError};
+use splinter::service::ServiceId;
+
+use crate::store::scabbard_store::{
+    BatchStatus, ScabbardBatch, ScabbardService, ScabbardStoreError, ServiceStatus, TwoPhaseState,
+};
+
+use super::schema::{scabbard_v3_batch, scabbard_v3_peer, scabbard_v3_service, scabbard_v3_vote};
+
+/// Database model representation of a `ScabbardService`
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "scabbard_v3_service"]
+#[primary_key(circuit_id, service_id)]
+pub struct ScabbardServiceModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub status: String,
+    pub coordinator: String,
+    pub epoch: i64,
+    pub consensus_state: String,
+    pub alarm: Option<i64>,
+    pub admin_keys: String,
+}
+
+impl TryFrom<&ScabbardService> for ScabbardServiceModel {
+    type Error = ScabbardStoreError;
+
+    fn try_from(service: &ScabbardService) -> Result<Self, Self::Error> {
+        Ok(ScabbardServiceModel {
+            circuit_id: service.service_id().circuit_id().as_str().into(),
+            service_id: service.service_id().service_id().as_str().into(),
+            status: String::from(service.status()),
+            coordinator: service.coordinator().as_str().into(),
+            epoch: epoch_to_i64(service.epoch())?,
+            consensus_state: String::from(service.state()),
+            alarm: service.alarm().map(system_time_to_i64).transpose()?,
+            admin_keys: service.admin_keys().join(","),
+        })
+    }
+}
+
+/// Database model representation of the peers of a `ScabbardService`
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "scabbard_v3_peer"]
+#[primary_key(circuit_id, service_id, peer_service_id)]
+pub struct ScabbardPeerModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub peer_service_id: String,
+    pub position: i32,
+}
+
+impl TryFrom<&ScabbardService> for Vec<ScabbardPeerModel> {
+    type Error = ScabbardStoreError;
+
+    fn try_from(service: &ScabbardService) -> Result<Self, Self::Error> {
+        service
+            .peers()
+            .iter()
+            .enumerate()
+            .map(|(idx, peer)| {
+                Ok(ScabbardPeerModel {
+                    circuit_id: service.service_id().circuit_id().as_str().into(),
+                    service_id: service.service_id().service_id().as_str().into(),
+                    peer_service_id: peer.as_str().into(),
+                    position: i32::try_from(idx).map_err(|_| {
+                        ScabbardStoreError::Internal(InternalError::with_message(
+                            "Unable to convert index into i32".to_string(),
+                        ))
+                    })?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Database model representation of the votes a coordinating `ScabbardService` has received
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "scabbard_v3_vote"]
+#[primary_key(circuit_id, service_id, peer_service_id)]
+pub struct ScabbardVoteModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub peer_service_id: String,
+    pub vote: bool,
+}
+
+impl From<&ScabbardService> for Vec<ScabbardVoteModel> {
+    fn from(service: &ScabbardService) -> Self {
+        match service.state() {
+            TwoPhaseState::Voting { votes } => votes
+                .iter()
+                .map(|(peer, vote)| ScabbardVoteModel {
+                    circuit_id: service.service_id().circuit_id().as_str().into(),
+                    service_id: service.service_id().service_id().as_str().into(),
+                    peer_service_id: peer.as_str().into(),
+                    vote: *vote,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// Database model representation of a `ScabbardBatch`
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable, QueryableByName)]
+#[table_name = "scabbard_v3_batch"]
+#[primary_key(circuit_id, service_id, batch_id)]
+pub struct ScabbardBatchModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub batch_id: String,
+    pub position: i64,
+    pub batch: Vec<u8>,
+    pub status: String,
+    pub epoch: Option<i64>,
+    pub state_root: Option<String>,
+}
+
+impl TryFrom<ScabbardBatchModel> for ScabbardBatch {
+    type Error = ScabbardStoreError;
+
+    fn try_from(model: ScabbardBatchModel) -> Result<Self, Self::Error> {
+        let status = match (model.status.as_str(), model.epoch) {
+            ("QUEUED", None) => BatchStatus::Queued,
+            ("PENDING", Some(epoch)) => BatchStatus::Pending(epoch_from_i64(epoch)?),
+            ("COMMITTED", Some(epoch)) => BatchStatus::Committed(epoch_from_i64(epoch)?),
+            ("ABORTED", Some(epoch)) => BatchStatus::Aborted(epoch_from_i64(epoch)?),
+            (status, epoch) => {
+                return Err(ScabbardStoreError::InvalidState(
+                    InvalidStateError::with_message(format!(
+                        "Invalid batch status {} with epoch {:?}",
+                        status, epoch
+                    )),
+                ))
+            }
+        };
+
+        let batch = ScabbardBatch::new(model.batch_id, model.batch, status);
+        Ok(match model.state_root {
+            Some(state_root) => batch.with_state_root(state_root),
+            None => batch,
+        })
+    }
+}
+
+impl From<&BatchStatus> for String {
+    fn from(status: &BatchStatus) -> Self {
+        match *status {
+            BatchStatus::Queued => "QUEUED".into(),
+            BatchStatus::Pending(_) => "PENDING".into(),
+            BatchStatus::Committed(_) => "COMMITTED".into(),
+            BatchStatus::Aborted(_) => "ABORTED".into(),
+        }
+    }
+}
+
+impl From<&ServiceStatus> for String {
+    fn from(status: &ServiceStatus) -> Self {
+        match *status {
+            ServiceStatus::Prepared => "PREPARED".into(),
+            ServiceStatus::Finalized => "FINALIZED".into(),
+            ServiceStatus::Retired => "RETIRED".into(),
+        }
+    }
+}
+
+impl TryFrom<&str> for ServiceStatus {
+    type Error = ScabbardStoreError;
+
+    fn try_from(status: &str) -> Result<Self, Self::Error> {
+        match status {
+            "PREPARED" => Ok(ServiceStatus::Prepared),
+            "FINALIZED" => Ok(ServiceStatus::Finalized),
+            "RETIRED" => Ok(ServiceStatus::Retired),
+            _ => Err(ScabbardStoreError::Internal(InternalError::with_message(
+                format!("Unknown status {}", status),
+            ))),
+        }
+    }
+}
+
+impl From<&TwoPhaseState> for String {
+    fn from(state: &TwoPhaseState) -> Self {
+        match *state {
+            TwoPhaseState::Idle => "IDLE".into(),
+            TwoPhaseState::Voting { .. } => "VOTING".into(),
+            TwoPhaseState::WaitingForDecision => "WAITING_FOR_DECISION".into(),
+        }
+    }
+}
+
+/// Builds the `TwoPhaseState` from its stored name and the votes recorded for the service
+pub fn two_phase_state_from_model(
+    state: &str,
+    votes: Vec<ScabbardVoteModel>,
+) -> Result<TwoPhaseState, ScabbardStoreError> {
+    match state {
+        "IDLE" => Ok(TwoPhaseState::Idle),
+        "VOTING" => Ok(TwoPhaseState::Voting {
+            votes: votes
+                .into_iter()
+                .map(|vote| Ok((ServiceId::new(vote.peer_service_id)?, vote.vote)))
+                .collect::<Result<_, ScabbardStoreError>>()?,
+        }),
+        "WAITING_FOR_DECISION" => Ok(TwoPhaseState::WaitingForDecision),
+        _ => Err(ScabbardStoreError::Internal(InternalError::with_message(
+            format!("Unknown consensus state {}", state),
+        ))),
+    }
+}
+
+/// Splits the stored, comma-separated admin keys of a service
+pub fn admin_keys_from_model(admin_keys: &str) -> Vec<String> {
+    if admin_keys.is_empty() {
+        vec![]
+    } else {
+        admin_keys.split(',').map(String::from).collect()
+    }
+}
+
+pub fn epoch_to_i64(epoch: u64) -> Result<i64, ScabbardStoreError> {
+    i64::try_from(epoch).map_err(|_| {
+        ScabbardStoreError::Internal(InternalError::with_message(
+            "Unable to convert epoch into i64".to_string(),
+        ))
+    })
+}
+
+pub fn epoch_from_i64(epoch: i64) -> Result<u64, ScabbardStoreError> {
+    u64::try_from(epoch).map_err(|_| {
+        ScabbardStoreError::Internal(InternalError::with_message(
+            "Unable to convert epoch into u64".to_string(),
+        ))
+    })
+}
+
+/// Converts a `SystemTime` into the number of seconds since the unix epoch
+pub fn system_time_to_i64(time: SystemTime) -> Result<i64, ScabbardStoreError> {
+    let seconds = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_err(|err| ScabbardStoreError::Internal(InternalError::from_source(Box::new(err))))?
+        .as_secs();
+
+    i64::try_from(seconds).map_err(|_| {
+        ScabbardStoreError::Internal(InternalError::with_message(
+            "Unable to convert time into i64".to_string(),
+        ))
+    })
+}
+
+/// Converts a number of seconds since the unix epoch into a `SystemTime`
+pub fn system_time_from_i64(seconds: i64) -> Result<SystemTime, ScabbardStoreError> {
+    let seconds = u64::try_from(seconds).map_err(|_| {
+        ScabbardStoreError::Internal(InternalError::with_message(
+            "Unable to convert time into u64".to_string(),
+        ))
+    })?;
+
+    Ok(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+}
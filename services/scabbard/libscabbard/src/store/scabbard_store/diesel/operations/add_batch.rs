@@ -0,0 +1,166 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add batch" operation for the `DieselScabbardStore`.
+
+use diesel::{
+    dsl::{insert_into, max},
+    prelude::*,
+};
+use splinter::error::{ConstraintViolationError, ConstraintViolationType, InvalidStateError};
+use splinter::service::FullyQualifiedServiceId;
+
+use crate::store::scabbard_store::{
+    diesel::{
+        models::{epoch_to_i64, ScabbardBatchModel, ScabbardServiceModel},
+        schema::{scabbard_v3_batch, scabbard_v3_service},
+    },
+    ScabbardBatch, ScabbardStoreError,
+};
+
+use super::ScabbardStoreOperations;
+
+pub(in crate::store::scabbard_store::diesel) trait ScabbardStoreAddBatchOperation {
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ScabbardStoreAddBatchOperation for ScabbardStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            let circuit_id = service_id.circuit_id().as_str();
+            let service_id = service_id.service_id().as_str();
+
+            scabbard_v3_service::table
+                .filter(scabbard_v3_service::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_service::service_id.eq(service_id))
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist in ScabbardStore",
+                    )))
+                })?;
+
+            if scabbard_v3_batch::table
+                .filter(scabbard_v3_batch::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_batch::service_id.eq(service_id))
+                .filter(scabbard_v3_batch::batch_id.eq(batch.batch_id()))
+                .first::<ScabbardBatchModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Err(ScabbardStoreError::ConstraintViolation(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+                ));
+            }
+
+            // Batches are queued in the order they are added
+            let position = scabbard_v3_batch::table
+                .filter(scabbard_v3_batch::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_batch::service_id.eq(service_id))
+                .select(max(scabbard_v3_batch::position))
+                .first::<Option<i64>>(self.conn)?
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            insert_into(scabbard_v3_batch::table)
+                .values(ScabbardBatchModel {
+                    circuit_id: circuit_id.into(),
+                    service_id: service_id.into(),
+                    batch_id: batch.batch_id().into(),
+                    position,
+                    batch: batch.batch().to_vec(),
+                    status: String::from(batch.status()),
+                    epoch: batch.status().epoch().map(epoch_to_i64).transpose()?,
+                    state_root: batch.state_root().map(String::from),
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ScabbardStoreAddBatchOperation
+    for ScabbardStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            let circuit_id = service_id.circuit_id().as_str();
+            let service_id = service_id.service_id().as_str();
+
+            scabbard_v3_service::table
+                .filter(scabbard_v3_service::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_service::service_id.eq(service_id))
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist in ScabbardStore",
+                    )))
+                })?;
+
+            if scabbard_v3_batch::table
+                .filter(scabbard_v3_batch::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_batch::service_id.eq(service_id))
+                .filter(scabbard_v3_batch::batch_id.eq(batch.batch_id()))
+                .first::<ScabbardBatchModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Err(ScabbardStoreError::ConstraintViolation(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+                ));
+            }
+
+            // Batches are queued in the order they are added
+            let position = scabbard_v3_batch::table
+                .filter(scabbard_v3_batch::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_batch::service_id.eq(service_id))
+                .select(max(scabbard_v3_batch::position))
+                .first::<Option<i64>>(self.conn)?
+                .map(|position| position + 1)
+                .unwrap_or(0);
+
+            insert_into(scabbard_v3_batch::table)
+                .values(ScabbardBatchModel {
+                    circuit_id: circuit_id.into(),
+                    service_id: service_id.into(),
+                    batch_id: batch.batch_id().into(),
+                    position,
+                    batch: batch.batch().to_vec(),
+                    status: String::from(batch.status()),
+                    epoch: batch.status().epoch().map(epoch_to_i64).transpose()?,
+                    state_root: batch.state_root().map(String::from),
+                })
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "get next queued batch" operation for the `DieselScabbardStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Binary, Text},
+};
+use splinter::service::FullyQualifiedServiceId;
+
+use crate::store::scabbard_store::{
+    diesel::{models::ScabbardBatchModel, schema::scabbard_v3_batch},
+    ScabbardBatch, ScabbardStoreError,
+};
+
+use super::ScabbardStoreOperations;
+
+pub(in crate::store::scabbard_store::diesel) trait ScabbardStoreGetNextQueuedBatchOperation {
+    fn get_next_queued_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError>;
+}
+
+impl<'a, C> ScabbardStoreGetNextQueuedBatchOperation for ScabbardStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<BigInt, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<Binary, C::Backend>,
+{
+    fn get_next_queued_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError> {
+        scabbard_v3_batch::table
+            .filter(scabbard_v3_batch::circuit_id.eq(service_id.circuit_id().as_str()))
+            .filter(scabbard_v3_batch::service_id.eq(service_id.service_id().as_str()))
+            .filter(scabbard_v3_batch::status.eq("QUEUED"))
+            .order(scabbard_v3_batch::position)
+            .first::<ScabbardBatchModel>(self.conn)
+            .optional()?
+            .map(ScabbardBatch::try_from)
+            .transpose()
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "get service" operation for the `DieselScabbardStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Bool, HasSqlType, Integer, Text},
+};
+use splinter::service::{FullyQualifiedServiceId, ServiceId};
+
+use crate::store::scabbard_store::{
+    diesel::{
+        models::{
+            admin_keys_from_model, epoch_from_i64, system_time_from_i64,
+            two_phase_state_from_model, ScabbardPeerModel, ScabbardServiceModel, ScabbardVoteModel,
+        },
+        schema::{scabbard_v3_peer, scabbard_v3_service, scabbard_v3_vote},
+    },
+    ScabbardService, ScabbardServiceBuilder, ScabbardStoreError, ServiceStatus,
+};
+
+use super::ScabbardStoreOperations;
+
+pub(in crate::store::scabbard_store::diesel) trait ScabbardStoreGetServiceOperation {
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError>;
+}
+
+impl<'a, C> ScabbardStoreGetServiceOperation for ScabbardStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    C::Backend: HasSqlType<Bool>,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<Integer, C::Backend>,
+    bool: diesel::deserialize::FromSql<Bool, C::Backend>,
+{
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError> {
+        self.conn.transaction::<Option<ScabbardService>, _, _>(|| {
+            // Fetch the `service` entry with the matching `service_id`.
+            // return None if the `service` does not exist
+            let service: ScabbardServiceModel = match scabbard_v3_service::table
+                .filter(scabbard_v3_service::circuit_id.eq(service_id.circuit_id().as_str()))
+                .filter(scabbard_v3_service::service_id.eq(service_id.service_id().as_str()))
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()?
+            {
+                Some(service) => service,
+                None => return Ok(None),
+            };
+
+            let peers = scabbard_v3_peer::table
+                .filter(scabbard_v3_peer::circuit_id.eq(service_id.circuit_id().as_str()))
+                .filter(scabbard_v3_peer::service_id.eq(service_id.service_id().as_str()))
+                .order(scabbard_v3_peer::position)
+                .load::<ScabbardPeerModel>(self.conn)?
+                .into_iter()
+                .map(|peer| ServiceId::new(peer.peer_service_id))
+                .collect::<Result<Vec<ServiceId>, _>>()?;
+
+            let votes = scabbard_v3_vote::table
+                .filter(scabbard_v3_vote::circuit_id.eq(service_id.circuit_id().as_str()))
+                .filter(scabbard_v3_vote::service_id.eq(service_id.service_id().as_str()))
+                .load::<ScabbardVoteModel>(self.conn)?;
+
+            let mut builder = ScabbardServiceBuilder::new()
+                .with_service_id(service_id)
+                .with_peers(&peers)
+                .with_coordinator(&ServiceId::new(service.coordinator)?)
+                .with_status(&ServiceStatus::try_from(service.status.as_str())?)
+                .with_epoch(epoch_from_i64(service.epoch)?)
+                .with_state(&two_phase_state_from_model(
+                    &service.consensus_state,
+                    votes,
+                )?)
+                .with_admin_keys(&admin_keys_from_model(&service.admin_keys));
+
+            if let Some(alarm) = service.alarm {
+                builder = builder.with_alarm(system_time_from_i64(alarm)?);
+            }
+
+            Ok(Some(builder.build()?))
+        })
+    }
+}
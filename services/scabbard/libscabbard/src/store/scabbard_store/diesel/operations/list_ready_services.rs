@@ -0,0 +1,93 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list ready services" operation for the `DieselScabbardStore`.
+
+use std::time::SystemTime;
+
+use diesel::{prelude::*, sql_types::Text};
+use splinter::service::{CircuitId, FullyQualifiedServiceId, ServiceId};
+
+use crate::store::scabbard_store::{
+    diesel::{
+        models::system_time_to_i64,
+        schema::{scabbard_v3_batch, scabbard_v3_service},
+    },
+    ScabbardStoreError,
+};
+
+use super::ScabbardStoreOperations;
+
+pub(in crate::store::scabbard_store::diesel) trait ScabbardStoreListReadyServicesOperation {
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError>;
+}
+
+impl<'a, C> ScabbardStoreListReadyServicesOperation for ScabbardStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+{
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError> {
+        let now = system_time_to_i64(SystemTime::now())?;
+
+        self.conn
+            .transaction::<Vec<FullyQualifiedServiceId>, _, _>(|| {
+                // Services with an alarm that has passed
+                let mut ready = scabbard_v3_service::table
+                    .filter(scabbard_v3_service::status.eq("FINALIZED"))
+                    .filter(scabbard_v3_service::alarm.le(now))
+                    .select((
+                        scabbard_v3_service::circuit_id,
+                        scabbard_v3_service::service_id,
+                    ))
+                    .load::<(String, String)>(self.conn)?;
+
+                // Idle coordinators that have batches waiting to be proposed
+                let coordinators = scabbard_v3_service::table
+                    .inner_join(
+                        scabbard_v3_batch::table.on(scabbard_v3_batch::circuit_id
+                            .eq(scabbard_v3_service::circuit_id)
+                            .and(
+                                scabbard_v3_batch::service_id.eq(scabbard_v3_service::service_id),
+                            )),
+                    )
+                    .filter(scabbard_v3_service::status.eq("FINALIZED"))
+                    .filter(scabbard_v3_service::consensus_state.eq("IDLE"))
+                    .filter(scabbard_v3_service::coordinator.eq(scabbard_v3_service::service_id))
+                    .filter(scabbard_v3_batch::status.eq("QUEUED"))
+                    .select((
+                        scabbard_v3_service::circuit_id,
+                        scabbard_v3_service::service_id,
+                    ))
+                    .distinct()
+                    .load::<(String, String)>(self.conn)?;
+
+                for service in coordinators {
+                    if !ready.contains(&service) {
+                        ready.push(service);
+                    }
+                }
+
+                ready
+                    .into_iter()
+                    .map(|(circuit_id, service_id)| {
+                        Ok(FullyQualifiedServiceId::new(
+                            CircuitId::new(circuit_id)?,
+                            ServiceId::new(service_id)?,
+                        ))
+                    })
+                    .collect()
+            })
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides database operations for the `DieselScabbardStore`.
+
+pub(super) mod add_batch;
+pub(super) mod add_service;
+pub(super) mod get_batch_for_epoch;
+pub(super) mod get_next_queued_batch;
+pub(super) mod get_service;
+pub(super) mod list_ready_services;
+pub(super) mod remove_service;
+pub(super) mod update_batch;
+pub(super) mod update_service;
+
+pub struct ScabbardStoreOperations<'a, C> {
+    conn: &'a C,
+}
+
+impl<'a, C: diesel::Connection> ScabbardStoreOperations<'a, C> {
+    pub fn new(conn: &'a C) -> Self {
+        ScabbardStoreOperations { conn }
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove service" operation for the `DieselScabbardStore`.
+
+use diesel::{dsl::delete, prelude::*};
+use splinter::service::FullyQualifiedServiceId;
+
+use crate::store::scabbard_store::{
+    diesel::schema::{scabbard_v3_batch, scabbard_v3_peer, scabbard_v3_service, scabbard_v3_vote},
+    ScabbardStoreError,
+};
+
+use super::ScabbardStoreOperations;
+
+pub(in crate::store::scabbard_store::diesel) trait ScabbardStoreRemoveServiceOperation {
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError>;
+}
+
+impl<'a, C> ScabbardStoreRemoveServiceOperation for ScabbardStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        let circuit_id = service_id.circuit_id().as_str();
+        let service_id = service_id.service_id().as_str();
+
+        self.conn.transaction::<(), _, _>(|| {
+            delete(
+                scabbard_v3_batch::table
+                    .filter(scabbard_v3_batch::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_batch::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(
+                scabbard_v3_vote::table
+                    .filter(scabbard_v3_vote::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_vote::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(
+                scabbard_v3_peer::table
+                    .filter(scabbard_v3_peer::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_peer::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(
+                scabbard_v3_service::table
+                    .filter(scabbard_v3_service::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_service::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "update batch" operation for the `DieselScabbardStore`.
+
+use diesel::{dsl::update, prelude::*};
+use splinter::error::InvalidStateError;
+use splinter::service::FullyQualifiedServiceId;
+
+use crate::store::scabbard_store::{
+    diesel::{models::epoch_to_i64, schema::scabbard_v3_batch},
+    ScabbardBatch, ScabbardStoreError,
+};
+
+use super::ScabbardStoreOperations;
+
+pub(in crate::store::scabbard_store::diesel) trait ScabbardStoreUpdateBatchOperation {
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ScabbardStoreUpdateBatchOperation
+    for ScabbardStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            let updated = update(
+                scabbard_v3_batch::table
+                    .filter(scabbard_v3_batch::circuit_id.eq(service_id.circuit_id().as_str()))
+                    .filter(scabbard_v3_batch::service_id.eq(service_id.service_id().as_str()))
+                    .filter(scabbard_v3_batch::batch_id.eq(batch.batch_id())),
+            )
+            .set((
+                scabbard_v3_batch::status.eq(String::from(batch.status())),
+                scabbard_v3_batch::epoch.eq(batch
+                    .status()
+                    .epoch()
+                    .map(epoch_to_i64)
+                    .transpose()?),
+                scabbard_v3_batch::state_root.eq(batch.state_root()),
+            ))
+            .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(ScabbardStoreError::InvalidState(
+                    InvalidStateError::with_message(String::from(
+                        "Batch does not exist in ScabbardStore",
+                    )),
+                ));
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ScabbardStoreUpdateBatchOperation
+    for ScabbardStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            let updated = update(
+                scabbard_v3_batch::table
+                    .filter(scabbard_v3_batch::circuit_id.eq(service_id.circuit_id().as_str()))
+                    .filter(scabbard_v3_batch::service_id.eq(service_id.service_id().as_str()))
+                    .filter(scabbard_v3_batch::batch_id.eq(batch.batch_id())),
+            )
+            .set((
+                scabbard_v3_batch::status.eq(String::from(batch.status())),
+                scabbard_v3_batch::epoch.eq(batch
+                    .status()
+                    .epoch()
+                    .map(epoch_to_i64)
+                    .transpose()?),
+                scabbard_v3_batch::state_root.eq(batch.state_root()),
+            ))
+            .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(ScabbardStoreError::InvalidState(
+                    InvalidStateError::with_message(String::from(
+                        "Batch does not exist in ScabbardStore",
+                    )),
+                ));
+            }
+
+            Ok(())
+        })
+    }
+}
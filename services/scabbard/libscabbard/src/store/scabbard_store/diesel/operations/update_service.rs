@@ -0,0 +1,153 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "update service" operation for the `DieselScabbardStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+};
+use splinter::error::InvalidStateError;
+
+use crate::store::scabbard_store::{
+    diesel::{
+        models::{ScabbardPeerModel, ScabbardServiceModel, ScabbardVoteModel},
+        schema::{scabbard_v3_peer, scabbard_v3_service, scabbard_v3_vote},
+    },
+    ScabbardService, ScabbardStoreError,
+};
+
+use super::ScabbardStoreOperations;
+
+pub(in crate::store::scabbard_store::diesel) trait ScabbardStoreUpdateServiceOperation {
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ScabbardStoreUpdateServiceOperation
+    for ScabbardStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            let circuit_id = service.service_id().circuit_id().as_str();
+            let service_id = service.service_id().service_id().as_str();
+
+            scabbard_v3_service::table
+                .filter(scabbard_v3_service::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_service::service_id.eq(service_id))
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist in ScabbardStore",
+                    )))
+                })?;
+
+            delete(
+                scabbard_v3_vote::table
+                    .filter(scabbard_v3_vote::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_vote::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(
+                scabbard_v3_peer::table
+                    .filter(scabbard_v3_peer::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_peer::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(
+                scabbard_v3_service::table
+                    .filter(scabbard_v3_service::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_service::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            insert_into(scabbard_v3_service::table)
+                .values(ScabbardServiceModel::try_from(&service)?)
+                .execute(self.conn)?;
+
+            insert_into(scabbard_v3_peer::table)
+                .values(&Vec::<ScabbardPeerModel>::try_from(&service)?)
+                .execute(self.conn)?;
+
+            insert_into(scabbard_v3_vote::table)
+                .values(&Vec::<ScabbardVoteModel>::from(&service))
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ScabbardStoreUpdateServiceOperation
+    for ScabbardStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            let circuit_id = service.service_id().circuit_id().as_str();
+            let service_id = service.service_id().service_id().as_str();
+
+            scabbard_v3_service::table
+                .filter(scabbard_v3_service::circuit_id.eq(circuit_id))
+                .filter(scabbard_v3_service::service_id.eq(service_id))
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist in ScabbardStore",
+                    )))
+                })?;
+
+            delete(
+                scabbard_v3_vote::table
+                    .filter(scabbard_v3_vote::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_vote::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(
+                scabbard_v3_peer::table
+                    .filter(scabbard_v3_peer::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_peer::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(
+                scabbard_v3_service::table
+                    .filter(scabbard_v3_service::circuit_id.eq(circuit_id))
+                    .filter(scabbard_v3_service::service_id.eq(service_id)),
+            )
+            .execute(self.conn)?;
+
+            insert_into(scabbard_v3_service::table)
+                .values(ScabbardServiceModel::try_from(&service)?)
+                .execute(self.conn)?;
+
+            insert_into(scabbard_v3_peer::table)
+                .values(&Vec::<ScabbardPeerModel>::try_from(&service)?)
+                .execute(self.conn)?;
+
+            insert_into(scabbard_v3_vote::table)
+                .values(&Vec::<ScabbardVoteModel>::from(&service))
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
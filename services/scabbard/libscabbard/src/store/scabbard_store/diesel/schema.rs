@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+table! {
+    scabbard_v3_service (circuit_id, service_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        status -> Text,
+        coordinator -> Text,
+        epoch -> BigInt,
+        consensus_state -> Text,
+        alarm -> Nullable<BigInt>,
+        admin_keys -> Text,
+    }
+}
+
+table! {
+    scabbard_v3_peer (circuit_id, service_id, peer_service_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        peer_service_id -> Text,
+        position -> Integer,
+    }
+}
+
+table! {
+    scabbard_v3_vote (circuit_id, service_id, peer_service_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        peer_service_id -> Text,
+        vote -> Bool,
+    }
+}
+
+table! {
+    scabbard_v3_batch (circuit_id, service_id, batch_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        batch_id -> Text,
+        position -> BigInt,
+        batch -> Binary,
+        status -> Text,
+        epoch -> Nullable<BigInt>,
+        state_root -> Nullable<Text>,
+    }
+}
+
+allow_tables_to_appear_in_same_query!(
+    scabbard_v3_service,
+    scabbard_v3_peer,
+    scabbard_v3_vote,
+    scabbard_v3_batch,
+);
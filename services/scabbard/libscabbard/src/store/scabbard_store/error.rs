@@ -0,0 +1,112 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error types and logic for ScabbardStores.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use splinter::error::{
+    ConstraintViolationError, ConstraintViolationType, InternalError, InvalidArgumentError,
+    InvalidStateError, ResourceTemporarilyUnavailableError,
+};
+
+/// Error type for the [ScabbardStore](super::ScabbardStore) trait.
+#[derive(Debug)]
+pub enum ScabbardStoreError {
+    ConstraintViolation(ConstraintViolationError),
+    Internal(InternalError),
+    InvalidArgument(InvalidArgumentError),
+    InvalidState(InvalidStateError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl Display for ScabbardStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScabbardStoreError::ConstraintViolation(e) => e.fmt(f),
+            ScabbardStoreError::Internal(e) => e.fmt(f),
+            ScabbardStoreError::InvalidArgument(e) => e.fmt(f),
+            ScabbardStoreError::InvalidState(e) => e.fmt(f),
+            ScabbardStoreError::ResourceTemporarilyUnavailable(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ScabbardStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScabbardStoreError::ConstraintViolation(e) => Some(e),
+            ScabbardStoreError::Internal(e) => Some(e),
+            ScabbardStoreError::InvalidArgument(e) => Some(e),
+            ScabbardStoreError::InvalidState(e) => Some(e),
+            ScabbardStoreError::ResourceTemporarilyUnavailable(e) => Some(e),
+        }
+    }
+}
+
+impl From<InternalError> for ScabbardStoreError {
+    fn from(err: InternalError) -> Self {
+        ScabbardStoreError::Internal(err)
+    }
+}
+
+impl From<InvalidArgumentError> for ScabbardStoreError {
+    fn from(err: InvalidArgumentError) -> Self {
+        ScabbardStoreError::InvalidArgument(err)
+    }
+}
+
+impl From<InvalidStateError> for ScabbardStoreError {
+    fn from(err: InvalidStateError) -> Self {
+        ScabbardStoreError::InvalidState(err)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for ScabbardStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        ScabbardStoreError::ResourceTemporarilyUnavailable(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for ScabbardStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::DatabaseError(db_err_kind, _) => match db_err_kind {
+                diesel::result::DatabaseErrorKind::UniqueViolation => {
+                    ScabbardStoreError::ConstraintViolation(
+                        ConstraintViolationError::from_source_with_violation_type(
+                            ConstraintViolationType::Unique,
+                            Box::new(err),
+                        ),
+                    )
+                }
+                diesel::result::DatabaseErrorKind::ForeignKeyViolation => {
+                    ScabbardStoreError::ConstraintViolation(
+                        ConstraintViolationError::from_source_with_violation_type(
+                            ConstraintViolationType::ForeignKey,
+                            Box::new(err),
+                        ),
+                    )
+                }
+                _ => ScabbardStoreError::Internal(InternalError::from_source(Box::new(err))),
+            },
+            _ => ScabbardStoreError::Internal(InternalError::from_source(Box::new(err))),
+        }
+    }
+}
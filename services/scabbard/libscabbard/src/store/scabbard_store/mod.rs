@@ -0,0 +1,117 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stores the state of scabbard v3 services and their two-phase commit process.
+
+mod batch;
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+mod service;
+
+use splinter::service::FullyQualifiedServiceId;
+
+pub use batch::{BatchStatus, ScabbardBatch};
+pub use error::ScabbardStoreError;
+pub use service::{ScabbardService, ScabbardServiceBuilder, ServiceStatus, TwoPhaseState};
+
+pub trait ScabbardStore {
+    /// Add a new service
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The service to add
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError>;
+
+    /// Update an existing service, including its two-phase commit state
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The updated service
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError>;
+
+    /// Remove a service and all of its batches
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The ID of the service to remove
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError>;
+
+    /// Fetch a service
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The ID of the service to fetch
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError>;
+
+    /// List the finalized services that have work for the timer to do; that is, services whose
+    /// alarm has passed or idle coordinators with queued batches
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError>;
+
+    /// Add a batch for a service
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The ID of the service the batch was submitted to
+    /// * `batch` - The batch to add
+    fn add_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError>;
+
+    /// Update the status of an existing batch
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The ID of the service the batch was submitted to
+    /// * `batch` - The updated batch
+    fn update_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        batch: ScabbardBatch,
+    ) -> Result<(), ScabbardStoreError>;
+
+    /// Fetch the oldest queued batch for a service
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The ID of the service
+    fn get_next_queued_batch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError>;
+
+    /// Fetch the batch that was proposed in the given epoch
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The ID of the service
+    /// * `epoch` - The epoch the batch was proposed in
+    fn get_batch_for_epoch(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        epoch: u64,
+    ) -> Result<Option<ScabbardBatch>, ScabbardStoreError>;
+}
+
+pub trait ScabbardStoreFactory<C>: Sync + Send {
+    fn new_store<'a>(&'a self, conn: &'a C) -> Box<dyn ScabbardStore + 'a>;
+}
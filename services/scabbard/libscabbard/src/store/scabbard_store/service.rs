@@ -0,0 +1,291 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structs for building scabbard services
+
+use std::time::SystemTime;
+
+use splinter::error::InvalidStateError;
+use splinter::service::{FullyQualifiedServiceId, ServiceId};
+
+/// Native representation of a scabbard v3 service and its two-phase commit state
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScabbardService {
+    service_id: FullyQualifiedServiceId,
+    peers: Vec<ServiceId>,
+    coordinator: ServiceId,
+    status: ServiceStatus,
+    epoch: u64,
+    state: TwoPhaseState,
+    alarm: Option<SystemTime>,
+    admin_keys: Vec<String>,
+}
+
+impl ScabbardService {
+    /// Returns the ID of the service
+    pub fn service_id(&self) -> &FullyQualifiedServiceId {
+        &self.service_id
+    }
+
+    /// Returns the list of peer services
+    pub fn peers(&self) -> &[ServiceId] {
+        &self.peers
+    }
+
+    /// Returns the ID of the service that coordinates two-phase commit
+    pub fn coordinator(&self) -> &ServiceId {
+        &self.coordinator
+    }
+
+    /// Returns true if this service is the two-phase commit coordinator
+    pub fn is_coordinator(&self) -> bool {
+        self.service_id.service_id() == &self.coordinator
+    }
+
+    /// Returns the status of the service
+    pub fn status(&self) -> &ServiceStatus {
+        &self.status
+    }
+
+    /// Returns the current two-phase commit epoch
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Returns the two-phase commit state of the service
+    pub fn state(&self) -> &TwoPhaseState {
+        &self.state
+    }
+
+    /// Returns the time at which the service should be woken up, if any
+    pub fn alarm(&self) -> Option<SystemTime> {
+        self.alarm
+    }
+
+    /// Returns the keys that are allowed to administer the service's smart contracts
+    pub fn admin_keys(&self) -> &[String] {
+        &self.admin_keys
+    }
+
+    pub fn into_builder(self) -> ScabbardServiceBuilder {
+        let builder = ScabbardServiceBuilder::new()
+            .with_service_id(&self.service_id)
+            .with_peers(&self.peers)
+            .with_coordinator(&self.coordinator)
+            .with_status(&self.status)
+            .with_epoch(self.epoch)
+            .with_state(&self.state)
+            .with_admin_keys(&self.admin_keys);
+
+        match self.alarm {
+            Some(alarm) => builder.with_alarm(alarm),
+            None => builder,
+        }
+    }
+}
+
+/// Builder for creating a `ScabbardService`
+#[derive(Default, Clone)]
+pub struct ScabbardServiceBuilder {
+    service_id: Option<FullyQualifiedServiceId>,
+    peers: Option<Vec<ServiceId>>,
+    coordinator: Option<ServiceId>,
+    status: Option<ServiceStatus>,
+    epoch: Option<u64>,
+    state: Option<TwoPhaseState>,
+    alarm: Option<SystemTime>,
+    admin_keys: Option<Vec<String>>,
+}
+
+impl ScabbardServiceBuilder {
+    /// Creates a new `ScabbardServiceBuilder`
+    pub fn new() -> Self {
+        ScabbardServiceBuilder::default()
+    }
+
+    /// Returns the service ID
+    pub fn service_id(&self) -> Option<FullyQualifiedServiceId> {
+        self.service_id.clone()
+    }
+
+    /// Returns the list of peer services
+    pub fn peers(&self) -> Option<Vec<ServiceId>> {
+        self.peers.clone()
+    }
+
+    /// Returns the status of the service
+    pub fn status(&self) -> Option<ServiceStatus> {
+        self.status.clone()
+    }
+
+    /// Sets the service ID
+    ///
+    /// # Arguments
+    ///
+    ///  * `service_id` - The fully qualified ID of the service
+    pub fn with_service_id(mut self, service_id: &FullyQualifiedServiceId) -> Self {
+        self.service_id = Some(service_id.clone());
+        self
+    }
+
+    /// Sets the peer services
+    ///
+    /// # Arguments
+    ///
+    ///  * `peers` - The services this service shares state with
+    pub fn with_peers(mut self, peers: &[ServiceId]) -> Self {
+        self.peers = Some(peers.to_vec());
+        self
+    }
+
+    /// Sets the two-phase commit coordinator
+    ///
+    /// If not set, the coordinator is the service with the lowest ID out of the service and its
+    /// peers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `coordinator` - The ID of the coordinating service
+    pub fn with_coordinator(mut self, coordinator: &ServiceId) -> Self {
+        self.coordinator = Some(coordinator.clone());
+        self
+    }
+
+    /// Sets the status of the service
+    ///
+    /// # Arguments
+    ///
+    ///  * `status` - The status of the service
+    pub fn with_status(mut self, status: &ServiceStatus) -> Self {
+        self.status = Some(status.clone());
+        self
+    }
+
+    /// Sets the two-phase commit epoch
+    ///
+    /// # Arguments
+    ///
+    ///  * `epoch` - The current epoch, defaults to 0
+    pub fn with_epoch(mut self, epoch: u64) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    /// Sets the two-phase commit state
+    ///
+    /// # Arguments
+    ///
+    ///  * `state` - The current state, defaults to `TwoPhaseState::Idle`
+    pub fn with_state(mut self, state: &TwoPhaseState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Sets the time at which the service should be woken up by the timer
+    ///
+    /// # Arguments
+    ///
+    ///  * `alarm` - The time to wake up the service
+    pub fn with_alarm(mut self, alarm: SystemTime) -> Self {
+        self.alarm = Some(alarm);
+        self
+    }
+
+    /// Sets the admin keys of the service's initial state
+    ///
+    /// # Arguments
+    ///
+    ///  * `admin_keys` - The keys that are allowed to administer the service's smart contracts
+    pub fn with_admin_keys(mut self, admin_keys: &[String]) -> Self {
+        self.admin_keys = Some(admin_keys.to_vec());
+        self
+    }
+
+    /// Removes the alarm, so the service is not woken up by the timer
+    pub fn clear_alarm(mut self) -> Self {
+        self.alarm = None;
+        self
+    }
+
+    /// Builds the `ScabbardService`
+    ///
+    /// Returns an error if the service ID or status is not set
+    pub fn build(self) -> Result<ScabbardService, InvalidStateError> {
+        let service_id = self.service_id.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "unable to build, missing field: `service_id`".to_string(),
+            )
+        })?;
+
+        let status = self.status.ok_or_else(|| {
+            InvalidStateError::with_message("unable to build, missing field: `status`".to_string())
+        })?;
+
+        let peers = self.peers.unwrap_or_default();
+
+        if peers.contains(service_id.service_id()) {
+            return Err(InvalidStateError::with_message(
+                "unable to build, a service cannot be its own peer".to_string(),
+            ));
+        }
+
+        let coordinator = match self.coordinator {
+            Some(coordinator) => coordinator,
+            None => peers
+                .iter()
+                .chain(std::iter::once(service_id.service_id()))
+                .min_by(|a, b| a.as_str().cmp(b.as_str()))
+                .cloned()
+                .expect("There will always be at least one service (self)"),
+        };
+
+        let epoch = self.epoch.unwrap_or(0);
+        let state = self.state.unwrap_or(TwoPhaseState::Idle);
+
+        Ok(ScabbardService {
+            service_id,
+            peers,
+            coordinator,
+            status,
+            epoch,
+            state,
+            alarm: self.alarm,
+            admin_keys: self.admin_keys.unwrap_or_default(),
+        })
+    }
+}
+
+/// The lifecycle status of a scabbard service
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServiceStatus {
+    /// The service has been prepared but may not yet process messages
+    Prepared,
+    /// The service is running
+    Finalized,
+    /// The service has been retired and no longer processes messages
+    Retired,
+}
+
+/// The two-phase commit state of a scabbard service
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TwoPhaseState {
+    /// No batch is currently being agreed upon
+    Idle,
+    /// The coordinator has requested votes for the current epoch's batch and is collecting the
+    /// votes of its peers
+    Voting { votes: Vec<(ServiceId, bool)> },
+    /// The participant has voted to accept the current epoch's batch and is waiting for the
+    /// coordinator's decision
+    WaitingForDecision,
+}
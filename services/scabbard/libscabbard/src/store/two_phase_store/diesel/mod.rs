@@ -0,0 +1,334 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A database-backed `TwoPhaseStore` for scabbard services that use two-phase commit consensus.
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+use splinter::consensus::two_phase::v2::{InFlightProposal, TwoPhaseDecision, TwoPhaseStore};
+use splinter::consensus::ProposalId;
+use splinter::error::InternalError;
+
+use crate::store::pool::ConnectionPool;
+
+use operations::add_decision::TwoPhaseStoreAddDecisionOperation as _;
+use operations::get_decision::TwoPhaseStoreGetDecisionOperation as _;
+use operations::get_in_flight_proposal::TwoPhaseStoreGetInFlightProposalOperation as _;
+use operations::remove_decisions_except::TwoPhaseStoreRemoveDecisionsExceptOperation as _;
+use operations::remove_in_flight_proposal::TwoPhaseStoreRemoveInFlightProposalOperation as _;
+use operations::set_in_flight_proposal::TwoPhaseStoreSetInFlightProposalOperation as _;
+use operations::TwoPhaseStoreOperations;
+
+/// Database backed [TwoPhaseStore] implementation.
+#[derive(Clone)]
+pub struct DieselTwoPhaseStore<Conn: diesel::Connection + 'static> {
+    pool: ConnectionPool<Conn>,
+    circuit_id: Arc<str>,
+    service_id: Arc<str>,
+}
+
+impl<C: diesel::Connection> DieselTwoPhaseStore<C> {
+    /// Constructs new DieselTwoPhaseStore.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `circuit_id` - The circuit associated with the store
+    /// * `service_id` - The service associated with the store
+    pub fn new(pool: Pool<ConnectionManager<C>>, circuit_id: &str, service_id: &str) -> Self {
+        Self {
+            pool: ConnectionPool::Normal(pool),
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+        }
+    }
+
+    /// Create a new `DieselTwoPhaseStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool`: read-write lock-guarded connection pool for the database
+    /// * `circuit_id` - The circuit associated with the store
+    /// * `service_id` - The service associated with the store
+    pub fn new_with_write_exclusivity(
+        pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Self {
+        Self {
+            pool: ConnectionPool::WriteExclusive(pool),
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl TwoPhaseStore for DieselTwoPhaseStore<diesel::pg::PgConnection> {
+    fn get_in_flight_proposal(&self) -> Result<Option<InFlightProposal>, InternalError> {
+        self.pool.execute_read(|conn| {
+            TwoPhaseStoreOperations::new(conn)
+                .get_in_flight_proposal(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn set_in_flight_proposal(&self, proposal: &InFlightProposal) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn).set_in_flight_proposal(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal,
+            )
+        })
+    }
+
+    fn remove_in_flight_proposal(&self) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn)
+                .remove_in_flight_proposal(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn add_decision(
+        &self,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn).add_decision(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal_id,
+                decision,
+            )
+        })
+    }
+
+    fn get_decision(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<TwoPhaseDecision>, InternalError> {
+        self.pool.execute_read(|conn| {
+            TwoPhaseStoreOperations::new(conn).get_decision(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal_id,
+            )
+        })
+    }
+
+    fn remove_decisions_except(&self, proposal_id: &ProposalId) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn).remove_decisions_except(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal_id,
+            )
+        })
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TwoPhaseStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl TwoPhaseStore for DieselTwoPhaseStore<diesel::sqlite::SqliteConnection> {
+    fn get_in_flight_proposal(&self) -> Result<Option<InFlightProposal>, InternalError> {
+        self.pool.execute_read(|conn| {
+            TwoPhaseStoreOperations::new(conn)
+                .get_in_flight_proposal(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn set_in_flight_proposal(&self, proposal: &InFlightProposal) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn).set_in_flight_proposal(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal,
+            )
+        })
+    }
+
+    fn remove_in_flight_proposal(&self) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn)
+                .remove_in_flight_proposal(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn add_decision(
+        &self,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn).add_decision(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal_id,
+                decision,
+            )
+        })
+    }
+
+    fn get_decision(
+        &self,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<TwoPhaseDecision>, InternalError> {
+        self.pool.execute_read(|conn| {
+            TwoPhaseStoreOperations::new(conn).get_decision(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal_id,
+            )
+        })
+    }
+
+    fn remove_decisions_except(&self, proposal_id: &ProposalId) -> Result<(), InternalError> {
+        self.pool.execute_write(|conn| {
+            TwoPhaseStoreOperations::new(conn).remove_decisions_except(
+                &*self.circuit_id,
+                &*self.service_id,
+                proposal_id,
+            )
+        })
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TwoPhaseStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use splinter::consensus::Proposal;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Test that a DieselTwoPhaseStore using a SQLite connection pool can
+    /// 1. Return no in-flight proposal before one has been set
+    /// 2. Set and get the in-flight proposal, including replacing its verified peers
+    /// 3. Remove the in-flight proposal
+    /// 4. Add and get decisions, including replacing a decision
+    /// 5. Remove all decisions except the given one
+    /// 6. Verify that the state is isolated to the service
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_two_phase_store() -> Result<(), Box<dyn std::error::Error>> {
+        let pool = create_connection_pool_and_migrate()?;
+        let store = DieselTwoPhaseStore::new(pool.clone(), "circuit", "service_1");
+
+        assert_eq!(None, store.get_in_flight_proposal()?);
+
+        let mut proposal = InFlightProposal {
+            proposal: Proposal {
+                id: b"proposal_1".to_vec().into(),
+                summary: b"summary".to_vec(),
+                ..Default::default()
+            },
+            proposal_data: b"data".to_vec(),
+            peers_verified: HashSet::new(),
+        };
+        store.set_in_flight_proposal(&proposal)?;
+        assert_eq!(Some(proposal.clone()), store.get_in_flight_proposal()?);
+
+        proposal.peers_verified.insert(b"service_1".to_vec().into());
+        proposal.peers_verified.insert(b"service_2".to_vec().into());
+        store.set_in_flight_proposal(&proposal)?;
+        assert_eq!(Some(proposal.clone()), store.get_in_flight_proposal()?);
+
+        let proposal = InFlightProposal {
+            proposal: Proposal {
+                id: b"proposal_2".to_vec().into(),
+                ..Default::default()
+            },
+            proposal_data: vec![],
+            peers_verified: HashSet::new(),
+        };
+        store.set_in_flight_proposal(&proposal)?;
+        assert_eq!(Some(proposal), store.get_in_flight_proposal()?);
+
+        store.remove_in_flight_proposal()?;
+        assert_eq!(None, store.get_in_flight_proposal()?);
+
+        let proposal_id = b"proposal_1".to_vec().into();
+        assert_eq!(None, store.get_decision(&proposal_id)?);
+        store.add_decision(&proposal_id, TwoPhaseDecision::Reject)?;
+        assert_eq!(
+            Some(TwoPhaseDecision::Reject),
+            store.get_decision(&proposal_id)?
+        );
+        store.add_decision(&proposal_id, TwoPhaseDecision::Apply)?;
+        assert_eq!(
+            Some(TwoPhaseDecision::Apply),
+            store.get_decision(&proposal_id)?
+        );
+
+        let later_proposal_id = b"proposal_2".to_vec().into();
+        store.add_decision(&later_proposal_id, TwoPhaseDecision::Apply)?;
+        store.remove_decisions_except(&later_proposal_id)?;
+        assert_eq!(None, store.get_decision(&proposal_id)?);
+        assert_eq!(
+            Some(TwoPhaseDecision::Apply),
+            store.get_decision(&later_proposal_id)?
+        );
+
+        // Check that another service on the same circuit has no state.
+        store.set_in_flight_proposal(&InFlightProposal {
+            proposal: Proposal {
+                id: b"proposal_3".to_vec().into(),
+                ..Default::default()
+            },
+            proposal_data: vec![],
+            peers_verified: HashSet::new(),
+        })?;
+        let other_store = DieselTwoPhaseStore::new(pool, "circuit", "service_2");
+        assert_eq!(None, other_store.get_in_flight_proposal()?);
+        assert_eq!(None, other_store.get_decision(&later_proposal_id)?);
+
+        Ok(())
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    #[cfg(feature = "sqlite")]
+    fn create_connection_pool_and_migrate(
+    ) -> Result<Pool<ConnectionManager<SqliteConnection>>, Box<dyn std::error::Error>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder().max_size(1).build(connection_manager)?;
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))?;
+
+        Ok(pool)
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use splinter::consensus::{two_phase::v2::TwoPhaseDecision, PeerId, ProposalId};
+use splinter::error::InternalError;
+
+use super::schema::{
+    scabbard_two_phase_decision, scabbard_two_phase_in_flight, scabbard_two_phase_verified_peer,
+};
+
+/// Database model representation of the proposal a service is evaluating
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable)]
+#[table_name = "scabbard_two_phase_in_flight"]
+#[primary_key(circuit_id, service_id)]
+pub struct TwoPhaseInFlightModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub proposal: Vec<u8>,
+    pub proposal_data: Vec<u8>,
+}
+
+/// Database model representation of a peer that verified the proposal a service is evaluating
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable)]
+#[table_name = "scabbard_two_phase_verified_peer"]
+#[primary_key(circuit_id, service_id, peer_id)]
+pub struct TwoPhaseVerifiedPeerModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub peer_id: Vec<u8>,
+}
+
+impl TwoPhaseVerifiedPeerModel {
+    pub fn new(circuit_id: &str, service_id: &str, peer_id: &PeerId) -> Self {
+        TwoPhaseVerifiedPeerModel {
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+            peer_id: peer_id.clone().into(),
+        }
+    }
+}
+
+/// Database model representation of the decision made for a proposal
+#[derive(Debug, PartialEq, Identifiable, Insertable, Queryable, AsChangeset)]
+#[table_name = "scabbard_two_phase_decision"]
+#[primary_key(circuit_id, service_id, proposal_id)]
+pub struct TwoPhaseDecisionModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub proposal_id: Vec<u8>,
+    pub decision: String,
+}
+
+impl TwoPhaseDecisionModel {
+    pub fn new(
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Self {
+        let decision = match decision {
+            TwoPhaseDecision::Apply => "apply",
+            TwoPhaseDecision::Reject => "reject",
+        };
+
+        TwoPhaseDecisionModel {
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+            proposal_id: proposal_id.clone().into(),
+            decision: decision.into(),
+        }
+    }
+}
+
+impl TryFrom<TwoPhaseDecisionModel> for TwoPhaseDecision {
+    type Error = InternalError;
+
+    fn try_from(model: TwoPhaseDecisionModel) -> Result<Self, Self::Error> {
+        match model.decision.as_str() {
+            "apply" => Ok(TwoPhaseDecision::Apply),
+            "reject" => Ok(TwoPhaseDecision::Reject),
+            decision => Err(InternalError::with_message(format!(
+                "Unknown two-phase decision: {}",
+                decision
+            ))),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "postgres")]
+use diesel::insert_into;
+use diesel::prelude::*;
+#[cfg(feature = "sqlite")]
+use diesel::replace_into;
+use splinter::consensus::{two_phase::v2::TwoPhaseDecision, ProposalId};
+use splinter::error::InternalError;
+
+use crate::store::two_phase_store::diesel::{
+    models::TwoPhaseDecisionModel, schema::scabbard_two_phase_decision,
+};
+
+use super::TwoPhaseStoreOperations;
+
+pub(in crate::store::two_phase_store::diesel) trait TwoPhaseStoreAddDecisionOperation {
+    fn add_decision(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), InternalError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TwoPhaseStoreAddDecisionOperation
+    for TwoPhaseStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_decision(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), InternalError> {
+        replace_into(scabbard_two_phase_decision::table)
+            .values(TwoPhaseDecisionModel::new(
+                circuit_id,
+                service_id,
+                proposal_id,
+                decision,
+            ))
+            .execute(self.conn)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TwoPhaseStoreAddDecisionOperation
+    for TwoPhaseStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_decision(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+        decision: TwoPhaseDecision,
+    ) -> Result<(), InternalError> {
+        let model = TwoPhaseDecisionModel::new(circuit_id, service_id, proposal_id, decision);
+
+        insert_into(scabbard_two_phase_decision::table)
+            .values(&model)
+            .on_conflict((
+                scabbard_two_phase_decision::circuit_id,
+                scabbard_two_phase_decision::service_id,
+                scabbard_two_phase_decision::proposal_id,
+            ))
+            .do_update()
+            .set(&model)
+            .execute(self.conn)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{Binary, Text},
+};
+use splinter::consensus::{two_phase::v2::TwoPhaseDecision, ProposalId};
+use splinter::error::InternalError;
+
+use crate::store::two_phase_store::diesel::{
+    models::TwoPhaseDecisionModel, schema::scabbard_two_phase_decision,
+};
+
+use super::TwoPhaseStoreOperations;
+
+pub(in crate::store::two_phase_store::diesel) trait TwoPhaseStoreGetDecisionOperation {
+    fn get_decision(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<TwoPhaseDecision>, InternalError>;
+}
+
+impl<'a, C> TwoPhaseStoreGetDecisionOperation for TwoPhaseStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<Binary, C::Backend>,
+{
+    fn get_decision(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+    ) -> Result<Option<TwoPhaseDecision>, InternalError> {
+        let proposal_id: &[u8] = proposal_id.as_ref();
+
+        scabbard_two_phase_decision::table
+            .find((circuit_id, service_id, proposal_id))
+            .first::<TwoPhaseDecisionModel>(self.conn)
+            .optional()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .map(TwoPhaseDecision::try_from)
+            .transpose()
+    }
+}
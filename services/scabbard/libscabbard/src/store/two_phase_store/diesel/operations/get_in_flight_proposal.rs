@@ -0,0 +1,86 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{Binary, Text},
+};
+use splinter::consensus::{two_phase::v2::InFlightProposal, Proposal};
+use splinter::error::InternalError;
+
+use crate::store::two_phase_store::diesel::{
+    models::{TwoPhaseInFlightModel, TwoPhaseVerifiedPeerModel},
+    schema::{scabbard_two_phase_in_flight, scabbard_two_phase_verified_peer},
+};
+
+use super::TwoPhaseStoreOperations;
+
+pub(in crate::store::two_phase_store::diesel) trait TwoPhaseStoreGetInFlightProposalOperation {
+    fn get_in_flight_proposal(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Option<InFlightProposal>, InternalError>;
+}
+
+impl<'a, C> TwoPhaseStoreGetInFlightProposalOperation for TwoPhaseStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<Binary, C::Backend>,
+{
+    fn get_in_flight_proposal(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Option<InFlightProposal>, InternalError> {
+        let in_flight = self
+            .conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                let in_flight = match scabbard_two_phase_in_flight::table
+                    .find((circuit_id, service_id))
+                    .first::<TwoPhaseInFlightModel>(self.conn)
+                    .optional()?
+                {
+                    Some(in_flight) => in_flight,
+                    None => return Ok(None),
+                };
+
+                let peers_verified = scabbard_two_phase_verified_peer::table
+                    .filter(scabbard_two_phase_verified_peer::circuit_id.eq(circuit_id))
+                    .filter(scabbard_two_phase_verified_peer::service_id.eq(service_id))
+                    .load::<TwoPhaseVerifiedPeerModel>(self.conn)?
+                    .into_iter()
+                    .map(|peer| peer.peer_id.into())
+                    .collect::<HashSet<_>>();
+
+                Ok(Some((in_flight, peers_verified)))
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        in_flight
+            .map(|(in_flight, peers_verified)| {
+                Ok(InFlightProposal {
+                    proposal: Proposal::try_from(in_flight.proposal.as_slice())
+                        .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                    proposal_data: in_flight.proposal_data,
+                    peers_verified,
+                })
+            })
+            .transpose()
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides database operations for the `DieselTwoPhaseStore`.
+
+pub(super) mod add_decision;
+pub(super) mod get_decision;
+pub(super) mod get_in_flight_proposal;
+pub(super) mod remove_decisions_except;
+pub(super) mod remove_in_flight_proposal;
+pub(super) mod set_in_flight_proposal;
+
+pub struct TwoPhaseStoreOperations<'a, C> {
+    conn: &'a C,
+}
+
+impl<'a, C: diesel::Connection> TwoPhaseStoreOperations<'a, C> {
+    pub fn new(conn: &'a C) -> Self {
+        TwoPhaseStoreOperations { conn }
+    }
+}
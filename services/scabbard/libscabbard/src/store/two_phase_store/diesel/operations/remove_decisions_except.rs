@@ -0,0 +1,80 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+use splinter::consensus::ProposalId;
+use splinter::error::InternalError;
+
+use crate::store::two_phase_store::diesel::schema::scabbard_two_phase_decision;
+
+use super::TwoPhaseStoreOperations;
+
+pub(in crate::store::two_phase_store::diesel) trait TwoPhaseStoreRemoveDecisionsExceptOperation {
+    fn remove_decisions_except(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+    ) -> Result<(), InternalError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TwoPhaseStoreRemoveDecisionsExceptOperation
+    for TwoPhaseStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_decisions_except(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+    ) -> Result<(), InternalError> {
+        let proposal_id: &[u8] = proposal_id.as_ref();
+
+        delete(
+            scabbard_two_phase_decision::table
+                .filter(scabbard_two_phase_decision::circuit_id.eq(circuit_id))
+                .filter(scabbard_two_phase_decision::service_id.eq(service_id))
+                .filter(scabbard_two_phase_decision::proposal_id.ne(proposal_id)),
+        )
+        .execute(self.conn)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TwoPhaseStoreRemoveDecisionsExceptOperation
+    for TwoPhaseStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_decisions_except(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal_id: &ProposalId,
+    ) -> Result<(), InternalError> {
+        let proposal_id: &[u8] = proposal_id.as_ref();
+
+        delete(
+            scabbard_two_phase_decision::table
+                .filter(scabbard_two_phase_decision::circuit_id.eq(circuit_id))
+                .filter(scabbard_two_phase_decision::service_id.eq(service_id))
+                .filter(scabbard_two_phase_decision::proposal_id.ne(proposal_id)),
+        )
+        .execute(self.conn)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use splinter::error::InternalError;
+
+use super::set_in_flight_proposal::delete_in_flight_proposal;
+use super::TwoPhaseStoreOperations;
+
+pub(in crate::store::two_phase_store::diesel) trait TwoPhaseStoreRemoveInFlightProposalOperation {
+    fn remove_in_flight_proposal(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<(), InternalError>;
+}
+
+impl<'a, C> TwoPhaseStoreRemoveInFlightProposalOperation for TwoPhaseStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    fn remove_in_flight_proposal(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<(), InternalError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                delete_in_flight_proposal(self.conn, circuit_id, service_id)
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+
+use diesel::{dsl::delete, insert_into, prelude::*};
+use splinter::consensus::two_phase::v2::InFlightProposal;
+use splinter::error::InternalError;
+
+use crate::store::two_phase_store::diesel::{
+    models::{TwoPhaseInFlightModel, TwoPhaseVerifiedPeerModel},
+    schema::{scabbard_two_phase_in_flight, scabbard_two_phase_verified_peer},
+};
+
+use super::TwoPhaseStoreOperations;
+
+pub(in crate::store::two_phase_store::diesel) trait TwoPhaseStoreSetInFlightProposalOperation {
+    fn set_in_flight_proposal(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal: &InFlightProposal,
+    ) -> Result<(), InternalError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TwoPhaseStoreSetInFlightProposalOperation
+    for TwoPhaseStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_in_flight_proposal(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal: &InFlightProposal,
+    ) -> Result<(), InternalError> {
+        let (in_flight, peers) = to_models(circuit_id, service_id, proposal)?;
+
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                delete_in_flight_proposal(self.conn, circuit_id, service_id)?;
+
+                insert_into(scabbard_two_phase_in_flight::table)
+                    .values(&in_flight)
+                    .execute(self.conn)?;
+
+                if !peers.is_empty() {
+                    insert_into(scabbard_two_phase_verified_peer::table)
+                        .values(&peers)
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TwoPhaseStoreSetInFlightProposalOperation
+    for TwoPhaseStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_in_flight_proposal(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        proposal: &InFlightProposal,
+    ) -> Result<(), InternalError> {
+        let (in_flight, peers) = to_models(circuit_id, service_id, proposal)?;
+
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                delete_in_flight_proposal(self.conn, circuit_id, service_id)?;
+
+                insert_into(scabbard_two_phase_in_flight::table)
+                    .values(&in_flight)
+                    .execute(self.conn)?;
+
+                if !peers.is_empty() {
+                    insert_into(scabbard_two_phase_verified_peer::table)
+                        .values(&peers)
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+fn to_models(
+    circuit_id: &str,
+    service_id: &str,
+    proposal: &InFlightProposal,
+) -> Result<(TwoPhaseInFlightModel, Vec<TwoPhaseVerifiedPeerModel>), InternalError> {
+    let in_flight = TwoPhaseInFlightModel {
+        circuit_id: circuit_id.into(),
+        service_id: service_id.into(),
+        proposal: proposal
+            .proposal
+            .clone()
+            .try_into()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?,
+        proposal_data: proposal.proposal_data.clone(),
+    };
+    let peers = proposal
+        .peers_verified
+        .iter()
+        .map(|peer_id| TwoPhaseVerifiedPeerModel::new(circuit_id, service_id, peer_id))
+        .collect();
+
+    Ok((in_flight, peers))
+}
+
+/// Removes the in-flight proposal and its verified peers for the given service.
+pub(super) fn delete_in_flight_proposal<C: diesel::Connection>(
+    conn: &C,
+    circuit_id: &str,
+    service_id: &str,
+) -> Result<(), diesel::result::Error> {
+    delete(
+        scabbard_two_phase_verified_peer::table
+            .filter(scabbard_two_phase_verified_peer::circuit_id.eq(circuit_id))
+            .filter(scabbard_two_phase_verified_peer::service_id.eq(service_id)),
+    )
+    .execute(conn)?;
+
+    delete(
+        scabbard_two_phase_in_flight::table
+            .filter(scabbard_two_phase_in_flight::circuit_id.eq(circuit_id))
+            .filter(scabbard_two_phase_in_flight::service_id.eq(service_id)),
+    )
+    .execute(conn)?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+table! {
+    scabbard_two_phase_in_flight (circuit_id, service_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        proposal -> Binary,
+        proposal_data -> Binary,
+    }
+}
+
+table! {
+    scabbard_two_phase_verified_peer (circuit_id, service_id, peer_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        peer_id -> Binary,
+    }
+}
+
+table! {
+    scabbard_two_phase_decision (circuit_id, service_id, proposal_id) {
+        circuit_id -> Text,
+        service_id -> Text,
+        proposal_id -> Binary,
+        decision -> Text,
+    }
+}
@@ -158,6 +158,10 @@ impl ConfigBuilder {
                 None => None,
             })
             .ok_or_else(|| ConfigError::MissingValue("server key".to_string()))?;
+        let tls_crl_file = self.partial_configs.iter().find_map(|p| {
+            p.tls_crl_file()
+                .map(|v| (get_tls_file_path(&tls_cert_dir.0, &v), p.source()))
+        });
         #[cfg(feature = "https-bind")]
         let tls_rest_api_cert = self
             .partial_configs
@@ -207,6 +211,7 @@ impl ConfigBuilder {
             tls_client_key,
             tls_server_cert,
             tls_server_key,
+            tls_crl_file,
             #[cfg(feature = "https-bind")]
             tls_rest_api_cert,
             #[cfg(feature = "https-bind")]
@@ -262,6 +267,11 @@ impl ConfigBuilder {
                 .ok_or_else(|| {
                     ConfigError::MissingValue("registry forced refresh interval".to_string())
                 })?,
+            #[cfg(feature = "registry-remote-signature")]
+            registry_publisher_keys: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.registry_publisher_keys().map(|v| (v, p.source()))),
             heartbeat: self
                 .partial_configs
                 .iter()
@@ -274,6 +284,21 @@ impl ConfigBuilder {
                 .ok_or_else(|| {
                     ConfigError::MissingValue("admin service coordinator timeout".to_string())
                 })?,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_max_age: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_event_max_age().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_max_count: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_event_max_count().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-expiry")]
+            admin_proposal_expiry: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_proposal_expiry().map(|v| (v, p.source()))),
             state_dir,
             tls_insecure: self
                 .partial_configs
@@ -356,6 +381,11 @@ impl ConfigBuilder {
                 .partial_configs
                 .iter()
                 .find_map(|p| p.influx_password().map(|v| (v, p.source()))),
+            #[cfg(feature = "tap-prometheus")]
+            metrics_exporter: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.metrics_exporter().map(|v| (v, p.source()))),
             peering_key: self
                 .partial_configs
                 .iter()
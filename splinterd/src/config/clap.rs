@@ -17,6 +17,8 @@
 use crate::config::{ConfigError, ConfigSource, PartialConfig, PartialConfigBuilder};
 use clap::{ArgMatches, ErrorKind};
 
+#[cfg(feature = "tap-prometheus")]
+use crate::config::MetricsExporter;
 use crate::config::ScabbardState;
 
 /// `PartialConfig` builder which holds command line arguments, represented as clap `ArgMatches`.
@@ -55,6 +57,7 @@ impl<'a> PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
             .with_tls_client_key(self.matches.value_of("tls_client_key").map(String::from))
             .with_tls_server_cert(self.matches.value_of("tls_server_cert").map(String::from))
             .with_tls_server_key(self.matches.value_of("tls_server_key").map(String::from))
+            .with_tls_crl_file(self.matches.value_of("tls_crl_file").map(String::from))
             .with_network_endpoints(
                 self.matches
                     .values_of("network_endpoints")
@@ -182,6 +185,40 @@ impl<'a> PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                 .with_influx_password(self.matches.value_of("influx_password").map(String::from))
         }
 
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            partial_config = partial_config
+                .with_admin_event_max_age(parse_value(&self.matches, "admin_event_max_age")?)
+                .with_admin_event_max_count(parse_value(&self.matches, "admin_event_max_count")?)
+        }
+
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        {
+            partial_config = partial_config
+                .with_admin_proposal_expiry(parse_value(&self.matches, "admin_proposal_expiry")?)
+        }
+
+        #[cfg(feature = "registry-remote-signature")]
+        {
+            partial_config = partial_config.with_registry_publisher_keys(
+                self.matches
+                    .values_of("registry_publisher_keys")
+                    .map(|values| values.map(String::from).collect::<Vec<String>>()),
+            )
+        }
+
+        #[cfg(feature = "tap-prometheus")]
+        {
+            partial_config = partial_config.with_metrics_exporter(
+                self.matches.value_of("metrics_exporter").map(|s| match s {
+                    "influx" => MetricsExporter::Influx,
+                    "prometheus" => MetricsExporter::Prometheus,
+                    // Clap is configured to only accept these two values.
+                    _ => unreachable!(),
+                }),
+            )
+        }
+
         partial_config =
             partial_config.with_verbosity(match self.matches.occurrences_of("verbose") {
                 0 => None,
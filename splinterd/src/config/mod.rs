@@ -53,6 +53,7 @@ pub struct Config {
     tls_client_key: (String, ConfigSource),
     tls_server_cert: (String, ConfigSource),
     tls_server_key: (String, ConfigSource),
+    tls_crl_file: Option<(String, ConfigSource)>,
     #[cfg(feature = "https-bind")]
     tls_rest_api_cert: (String, ConfigSource),
     #[cfg(feature = "https-bind")]
@@ -69,8 +70,16 @@ pub struct Config {
     registries: (Vec<String>, ConfigSource),
     registry_auto_refresh: (u64, ConfigSource),
     registry_forced_refresh: (u64, ConfigSource),
+    #[cfg(feature = "registry-remote-signature")]
+    registry_publisher_keys: Option<(Vec<String>, ConfigSource)>,
     heartbeat: (u64, ConfigSource),
     admin_timeout: (Duration, ConfigSource),
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_max_age: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_max_count: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    admin_proposal_expiry: Option<(Duration, ConfigSource)>,
     state_dir: (String, ConfigSource),
     tls_insecure: (bool, ConfigSource),
     no_tls: (bool, ConfigSource),
@@ -101,6 +110,8 @@ pub struct Config {
     influx_username: Option<(String, ConfigSource)>,
     #[cfg(feature = "tap")]
     influx_password: Option<(String, ConfigSource)>,
+    #[cfg(feature = "tap-prometheus")]
+    metrics_exporter: Option<(MetricsExporter, ConfigSource)>,
     peering_key: (String, ConfigSource),
     root_logger: (RootConfig, ConfigSource),
     appenders: Option<Vec<(AppenderConfig, ConfigSource)>>,
@@ -175,6 +186,14 @@ impl Config {
         }
     }
 
+    pub fn tls_crl_file(&self) -> Option<&str> {
+        if let Some((file, _)) = &self.tls_crl_file {
+            Some(file)
+        } else {
+            None
+        }
+    }
+
     pub fn display_name(&self) -> Option<&str> {
         if let Some((name, _)) = &self.display_name {
             Some(name)
@@ -203,6 +222,14 @@ impl Config {
         self.registry_forced_refresh.0
     }
 
+    #[cfg(feature = "registry-remote-signature")]
+    pub fn registry_publisher_keys(&self) -> &[String] {
+        self.registry_publisher_keys
+            .as_ref()
+            .map(|(keys, _)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
     pub fn heartbeat(&self) -> u64 {
         self.heartbeat.0
     }
@@ -211,6 +238,27 @@ impl Config {
         self.admin_timeout.0
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_max_age(&self) -> Option<Duration> {
+        self.admin_event_max_age
+            .as_ref()
+            .map(|(max_age, _)| *max_age)
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_max_count(&self) -> Option<u64> {
+        self.admin_event_max_count
+            .as_ref()
+            .map(|(max_count, _)| *max_count)
+    }
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    pub fn admin_proposal_expiry(&self) -> Option<Duration> {
+        self.admin_proposal_expiry
+            .as_ref()
+            .map(|(expiry, _)| *expiry)
+    }
+
     pub fn state_dir(&self) -> &str {
         &self.state_dir.0
     }
@@ -340,6 +388,15 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn metrics_exporter(&self) -> Option<&MetricsExporter> {
+        if let Some((exporter, _)) = &self.metrics_exporter {
+            Some(exporter)
+        } else {
+            None
+        }
+    }
+
     pub fn peering_key(&self) -> &str {
         &self.peering_key.0
     }
@@ -407,6 +464,14 @@ impl Config {
         }
     }
 
+    fn tls_crl_file_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.tls_crl_file {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
     fn display_name_source(&self) -> Option<&ConfigSource> {
         if let Some((_, source)) = &self.display_name {
             Some(source)
@@ -435,6 +500,13 @@ impl Config {
         &self.registry_forced_refresh.1
     }
 
+    #[cfg(feature = "registry-remote-signature")]
+    fn registry_publisher_keys_source(&self) -> Option<&ConfigSource> {
+        self.registry_publisher_keys
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
     fn heartbeat_source(&self) -> &ConfigSource {
         &self.heartbeat.1
     }
@@ -443,6 +515,25 @@ impl Config {
         &self.admin_timeout.1
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    fn admin_event_max_age_source(&self) -> Option<&ConfigSource> {
+        self.admin_event_max_age.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    fn admin_event_max_count_source(&self) -> Option<&ConfigSource> {
+        self.admin_event_max_count
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    fn admin_proposal_expiry_source(&self) -> Option<&ConfigSource> {
+        self.admin_proposal_expiry
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
     fn state_dir_source(&self) -> &ConfigSource {
         &self.state_dir.1
     }
@@ -572,6 +663,15 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn metrics_exporter_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.metrics_exporter {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
     fn peering_key_source(&self) -> &ConfigSource {
         &self.peering_key.1
     }
@@ -660,6 +760,9 @@ impl Config {
             self.tls_server_key(),
             self.tls_server_key_source()
         );
+        if let (Some(file), Some(source)) = (self.tls_crl_file(), self.tls_crl_file_source()) {
+            debug!("Config: tls_crl_file: {} (source: {:?})", file, source);
+        }
         #[cfg(feature = "https-bind")]
         {
             debug!(
@@ -725,6 +828,14 @@ impl Config {
             self.registry_forced_refresh(),
             self.registry_forced_refresh_source()
         );
+        #[cfg(feature = "registry-remote-signature")]
+        if let Some(source) = self.registry_publisher_keys_source() {
+            debug!(
+                "Config: registry_publisher_keys: {:?} (source: {:?})",
+                self.registry_publisher_keys(),
+                source,
+            );
+        }
         debug!(
             "Config: state_dir: {} (source: {:?})",
             self.state_dir(),
@@ -740,6 +851,37 @@ impl Config {
             self.admin_timeout(),
             self.admin_timeout_source()
         );
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            if let (Some(max_age), Some(source)) = (
+                self.admin_event_max_age(),
+                self.admin_event_max_age_source(),
+            ) {
+                debug!(
+                    "Config: admin_event_max_age: {:?} (source: {:?})",
+                    max_age, source,
+                );
+            }
+            if let (Some(max_count), Some(source)) = (
+                self.admin_event_max_count(),
+                self.admin_event_max_count_source(),
+            ) {
+                debug!(
+                    "Config: admin_event_max_count: {} (source: {:?})",
+                    max_count, source,
+                );
+            }
+        }
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        if let (Some(expiry), Some(source)) = (
+            self.admin_proposal_expiry(),
+            self.admin_proposal_expiry_source(),
+        ) {
+            debug!(
+                "Config: admin_proposal_expiry: {:?} (source: {:?})",
+                expiry, source,
+            );
+        }
         debug!(
             "database: {} (source: {:?})",
             self.database(),
@@ -851,6 +993,17 @@ impl Config {
                 debug!("Config: influx_password: <HIDDEN> (source: {:?})", source,);
             }
         }
+        #[cfg(feature = "tap-prometheus")]
+        {
+            if let (Some(exporter), Some(source)) =
+                (self.metrics_exporter(), self.metrics_exporter_source())
+            {
+                debug!(
+                    "Config: metrics_exporter: {:?} (source: {:?})",
+                    exporter, source,
+                );
+            }
+        }
         if let Some(loggers) = &self.loggers {
             for logger in loggers {
                 debug!("Config: logger: {:?} (source: {:?})", logger.0, logger.1);
@@ -904,6 +1057,16 @@ pub enum ScabbardState {
     Lmdb,
 }
 
+/// The recorder used to export the metrics collected by the metrics macros
+#[cfg(feature = "tap-prometheus")]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MetricsExporter {
+    /// Send metrics to an InfluxDB database
+    Influx,
+    /// Serve metrics in the Prometheus text format from the REST API's `/metrics` endpoint
+    Prometheus,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
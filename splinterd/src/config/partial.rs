@@ -19,6 +19,8 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use super::logging::{RootConfig, UnnamedAppenderConfig, UnnamedLoggerConfig};
+#[cfg(feature = "tap-prometheus")]
+use super::MetricsExporter;
 use super::ScabbardState;
 
 /// `ConfigSource` displays the source of configuration values, used to identify which of the various
@@ -44,6 +46,7 @@ pub struct PartialConfig {
     tls_client_key: Option<String>,
     tls_server_cert: Option<String>,
     tls_server_key: Option<String>,
+    tls_crl_file: Option<String>,
     #[cfg(feature = "https-bind")]
     tls_rest_api_cert: Option<String>,
     #[cfg(feature = "https-bind")]
@@ -60,8 +63,16 @@ pub struct PartialConfig {
     registries: Option<Vec<String>>,
     registry_auto_refresh: Option<u64>,
     registry_forced_refresh: Option<u64>,
+    #[cfg(feature = "registry-remote-signature")]
+    registry_publisher_keys: Option<Vec<String>>,
     heartbeat: Option<u64>,
     admin_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_max_age: Option<Duration>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_max_count: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    admin_proposal_expiry: Option<Duration>,
     state_dir: Option<String>,
     tls_insecure: Option<bool>,
     no_tls: Option<bool>,
@@ -92,6 +103,8 @@ pub struct PartialConfig {
     influx_username: Option<String>,
     #[cfg(feature = "tap")]
     influx_password: Option<String>,
+    #[cfg(feature = "tap-prometheus")]
+    metrics_exporter: Option<MetricsExporter>,
     peering_key: Option<String>,
     root_logger: Option<RootConfig>,
     appenders: Option<HashMap<String, UnnamedAppenderConfig>>,
@@ -114,6 +127,7 @@ impl PartialConfig {
             tls_client_key: None,
             tls_server_cert: None,
             tls_server_key: None,
+            tls_crl_file: None,
             #[cfg(feature = "https-bind")]
             tls_rest_api_cert: None,
             #[cfg(feature = "https-bind")]
@@ -130,8 +144,16 @@ impl PartialConfig {
             registries: None,
             registry_auto_refresh: None,
             registry_forced_refresh: None,
+            #[cfg(feature = "registry-remote-signature")]
+            registry_publisher_keys: None,
             heartbeat: None,
             admin_timeout: None,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_max_age: None,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_max_count: None,
+            #[cfg(feature = "admin-service-proposal-expiry")]
+            admin_proposal_expiry: None,
             state_dir: None,
             tls_insecure: None,
             no_tls: None,
@@ -162,6 +184,8 @@ impl PartialConfig {
             influx_username: None,
             #[cfg(feature = "tap")]
             influx_password: None,
+            #[cfg(feature = "tap-prometheus")]
+            metrics_exporter: None,
             peering_key: None,
             appenders: None,
             loggers: None,
@@ -205,6 +229,10 @@ impl PartialConfig {
         self.tls_server_key.clone()
     }
 
+    pub fn tls_crl_file(&self) -> Option<String> {
+        self.tls_crl_file.clone()
+    }
+
     #[cfg(feature = "https-bind")]
     pub fn tls_rest_api_cert(&self) -> Option<String> {
         self.tls_rest_api_cert.clone()
@@ -260,6 +288,11 @@ impl PartialConfig {
         self.registry_forced_refresh
     }
 
+    #[cfg(feature = "registry-remote-signature")]
+    pub fn registry_publisher_keys(&self) -> Option<Vec<String>> {
+        self.registry_publisher_keys.clone()
+    }
+
     pub fn heartbeat(&self) -> Option<u64> {
         self.heartbeat
     }
@@ -268,6 +301,21 @@ impl PartialConfig {
         self.admin_timeout
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_max_age(&self) -> Option<Duration> {
+        self.admin_event_max_age
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn admin_event_max_count(&self) -> Option<u64> {
+        self.admin_event_max_count
+    }
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    pub fn admin_proposal_expiry(&self) -> Option<Duration> {
+        self.admin_proposal_expiry
+    }
+
     pub fn state_dir(&self) -> Option<String> {
         self.state_dir.clone()
     }
@@ -349,6 +397,11 @@ impl PartialConfig {
         self.influx_password.clone()
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn metrics_exporter(&self) -> Option<MetricsExporter> {
+        self.metrics_exporter
+    }
+
     pub fn peering_key(&self) -> Option<String> {
         self.peering_key.clone()
     }
@@ -457,6 +510,18 @@ impl PartialConfig {
         self
     }
 
+    /// Adds a `tls_crl_file` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_crl_file` - Certificate revocation list (*.pem file) used to reject revoked peer
+    ///                   certificates.
+    ///
+    pub fn with_tls_crl_file(mut self, tls_crl_file: Option<String>) -> Self {
+        self.tls_crl_file = tls_crl_file;
+        self
+    }
+
     /// Adds a `tls_rest_api_cert` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -607,6 +672,22 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "registry-remote-signature")]
+    /// Adds a `registry_publisher_keys` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_publisher_keys` - The hex-encoded public keys that remote registry files must
+    ///   be signed by.
+    ///
+    pub fn with_registry_publisher_keys(
+        mut self,
+        registry_publisher_keys: Option<Vec<String>>,
+    ) -> Self {
+        self.registry_publisher_keys = registry_publisher_keys;
+        self
+    }
+
     /// Adds a `heartbeat` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -629,6 +710,44 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "admin-service-event-retention")]
+    /// Adds an `admin_event_max_age` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_age` - How long admin service events are kept before being compacted (in seconds).
+    ///
+    pub fn with_admin_event_max_age(mut self, max_age: Option<u64>) -> Self {
+        self.admin_event_max_age = max_age.map(Duration::from_secs);
+        self
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    /// Adds an `admin_event_max_count` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_count` - The number of the most recent admin service events that are kept when
+    ///   compacting.
+    ///
+    pub fn with_admin_event_max_count(mut self, max_count: Option<u64>) -> Self {
+        self.admin_event_max_count = max_count;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    /// Adds an `admin_proposal_expiry` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiry` - How long a circuit proposal may remain pending before it is expired (in
+    ///   seconds).
+    ///
+    pub fn with_admin_proposal_expiry(mut self, expiry: Option<u64>) -> Self {
+        self.admin_proposal_expiry = expiry.map(Duration::from_secs);
+        self
+    }
+
     /// Adds a `state_dir` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -835,6 +954,18 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    /// Adds a `metrics_exporter` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `metrics_exporter` - Option of MetricsExporter value for where to export metrics
+    ///
+    pub fn with_metrics_exporter(mut self, metrics_exporter: Option<MetricsExporter>) -> Self {
+        self.metrics_exporter = metrics_exporter;
+        self
+    }
+
     /// Adds an `peering_key` value to the `PartialConfig` object.
     ///
     /// # Arguments
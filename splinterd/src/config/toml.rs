@@ -24,6 +24,8 @@ use std::collections::HashMap;
 use std::convert::TryInto;
 
 use super::logging::{default_pattern, UnnamedAppenderConfig, UnnamedLoggerConfig};
+#[cfg(feature = "tap-prometheus")]
+use super::MetricsExporter;
 use super::ScabbardState;
 
 /// `TOML_VERSION` represents the version of the toml config file.
@@ -158,6 +160,7 @@ struct TomlConfig {
     tls_client_key: Option<String>,
     tls_server_cert: Option<String>,
     tls_server_key: Option<String>,
+    tls_crl_file: Option<String>,
     #[cfg(feature = "https-bind")]
     tls_rest_api_cert: Option<String>,
     #[cfg(feature = "https-bind")]
@@ -174,8 +177,16 @@ struct TomlConfig {
     registries: Option<Vec<String>>,
     registry_auto_refresh: Option<u64>,
     registry_forced_refresh: Option<u64>,
+    #[cfg(feature = "registry-remote-signature")]
+    registry_publisher_keys: Option<Vec<String>>,
     heartbeat: Option<u64>,
     admin_timeout: Option<u64>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_max_age: Option<u64>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_max_count: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    admin_proposal_expiry: Option<u64>,
     version: Option<String>,
     #[cfg(feature = "rest-api-cors")]
     allow_list: Option<Vec<String>>,
@@ -201,6 +212,8 @@ struct TomlConfig {
     influx_username: Option<String>,
     #[cfg(feature = "tap")]
     influx_password: Option<String>,
+    #[cfg(feature = "tap-prometheus")]
+    metrics_exporter: Option<MetricsExporterToml>,
     peering_key: Option<String>,
     appenders: Option<HashMap<String, TomlUnnamedAppenderConfig>>,
     loggers: Option<HashMap<String, TomlUnnamedLoggerConfig>>,
@@ -277,6 +290,7 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
             .with_tls_client_key(self.toml_config.tls_client_key)
             .with_tls_server_cert(self.toml_config.tls_server_cert)
             .with_tls_server_key(self.toml_config.tls_server_key)
+            .with_tls_crl_file(self.toml_config.tls_crl_file)
             .with_network_endpoints(self.toml_config.network_endpoints)
             .with_advertised_endpoints(self.toml_config.advertised_endpoints)
             .with_peers(self.toml_config.peers)
@@ -332,6 +346,31 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
                 .with_influx_password(self.toml_config.influx_password)
         }
 
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            partial_config = partial_config
+                .with_admin_event_max_age(self.toml_config.admin_event_max_age)
+                .with_admin_event_max_count(self.toml_config.admin_event_max_count)
+        }
+
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        {
+            partial_config =
+                partial_config.with_admin_proposal_expiry(self.toml_config.admin_proposal_expiry)
+        }
+
+        #[cfg(feature = "registry-remote-signature")]
+        {
+            partial_config = partial_config
+                .with_registry_publisher_keys(self.toml_config.registry_publisher_keys)
+        }
+
+        #[cfg(feature = "tap-prometheus")]
+        {
+            partial_config = partial_config
+                .with_metrics_exporter(self.toml_config.metrics_exporter.map(|inner| inner.into()))
+        }
+
         if let Some(mut loggers) = self.toml_config.loggers {
             if let Some(unnamed) = loggers.remove("root") {
                 partial_config = partial_config
@@ -428,6 +467,25 @@ impl From<ScabbardStateToml> for ScabbardState {
     }
 }
 
+#[cfg(feature = "tap-prometheus")]
+#[derive(Deserialize, Debug)]
+pub enum MetricsExporterToml {
+    #[serde(rename = "influx")]
+    Influx,
+    #[serde(rename = "prometheus")]
+    Prometheus,
+}
+
+#[cfg(feature = "tap-prometheus")]
+impl From<MetricsExporterToml> for MetricsExporter {
+    fn from(other: MetricsExporterToml) -> Self {
+        match other {
+            MetricsExporterToml::Influx => MetricsExporter::Influx,
+            MetricsExporterToml::Prometheus => MetricsExporter::Prometheus,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config::LoggerConfig;
@@ -740,10 +798,14 @@ mod tests {
             peering_key = "splinterd"
             heartbeat = 30
             admin_timeout = 30
+            admin_event_max_age = 604800
+            admin_event_max_count = 10000
+            admin_proposal_expiry = 86400
             allow_keys_file = "allow_keys"
             registries = ["file:///etc/splinter/registry.yaml"]
             registry_auto_refresh = 600
             registry_forced_refresh = 10
+            registry_publisher_keys = ["02d1fbda5d14f2fbd3e8fbbb6a79d4a2c2d15ea4c2ff4f5c5f1a8a9e1d5e9c3a7b"]
             tls_cert_dir = "/etc/splinter/certs"
             tls_ca_file = "/etc/splinter/certs/ca.pem"
             tls_client_cert = "/etc/splinter/certs/client.crt"
@@ -761,6 +823,7 @@ mod tests {
             influx_db = "database"
             influx_username = "username"
             influx_password = "pa$$w0rd"
+            metrics_exporter = "prometheus"
             [appenders.stdout]
             kind = "stdout"
             pattern = "[{d(%Y-%m-%d %H:%M:%S%.3f)}] T[{T}] {l} [{M}] {m}\n"
@@ -802,12 +865,29 @@ mod tests {
             toml.admin_timeout(),
             Some(duration) if duration == Duration::from_secs(30)
         ));
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            assert_eq!(
+                toml.admin_event_max_age(),
+                Some(Duration::from_secs(604800))
+            );
+            assert_eq!(toml.admin_event_max_count(), Some(10000));
+        }
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        assert_eq!(
+            toml.admin_proposal_expiry(),
+            Some(Duration::from_secs(86400))
+        );
         //assert!(matches!(toml.allow_keys_file() , Some(text) if text == "allow_keys"));
         assert!(
             matches!(toml.registries() ,Some(vec) if vec[..] == ["file:///etc/splinter/registry.yaml"])
         );
         assert!(matches!(toml.registry_auto_refresh(), Some(600)));
         assert!(matches!(toml.registry_forced_refresh(), Some(10)));
+        #[cfg(feature = "registry-remote-signature")]
+        assert!(
+            matches!(toml.registry_publisher_keys(), Some(vec) if vec[..] == ["02d1fbda5d14f2fbd3e8fbbb6a79d4a2c2d15ea4c2ff4f5c5f1a8a9e1d5e9c3a7b"])
+        );
         assert!(matches!(toml.tls_cert_dir() , Some(text) if text == "/etc/splinter/certs"));
         assert!(matches!(toml.tls_ca_file() , Some(text) if text == "/etc/splinter/certs/ca.pem"));
         assert!(
@@ -846,6 +926,12 @@ mod tests {
             assert!(matches!(toml.influx_password() , Some(text) if text == "pa$$w0rd"));
         }
 
+        #[cfg(feature = "tap-prometheus")]
+        assert_eq!(
+            toml.metrics_exporter(),
+            Some(crate::config::MetricsExporter::Prometheus)
+        );
+
         let appenders = toml.appenders();
         assert!(appenders.is_some());
         let appenders = appenders.unwrap();
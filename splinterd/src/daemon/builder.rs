@@ -15,8 +15,12 @@
 use std::time::Duration;
 
 use cylinder::Signer;
+#[cfg(feature = "admin-service-event-retention")]
+use splinter::admin::store::EventRetention;
 use splinter::mesh::Mesh;
 use splinter::peer::PeerAuthorizationToken;
+#[cfg(feature = "tap-prometheus")]
+use splinter::tap::prometheus::PrometheusRecorder;
 
 use crate::daemon::error::CreateError;
 use crate::daemon::SplinterDaemon;
@@ -66,6 +70,14 @@ pub struct SplinterDaemonBuilder {
     signers: Option<Vec<Box<dyn Signer>>>,
     peering_token: Option<PeerAuthorizationToken>,
     enable_lmdb_state: bool,
+    #[cfg(feature = "tap-prometheus")]
+    prometheus_recorder: Option<PrometheusRecorder>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention: EventRetention,
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    admin_proposal_expiry: Option<Duration>,
+    #[cfg(feature = "registry-remote-signature")]
+    registry_publisher_keys: Vec<cylinder::PublicKey>,
 }
 
 impl SplinterDaemonBuilder {
@@ -244,6 +256,30 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn with_prometheus_recorder(mut self, value: Option<PrometheusRecorder>) -> Self {
+        self.prometheus_recorder = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    pub fn with_admin_event_retention(mut self, value: EventRetention) -> Self {
+        self.admin_event_retention = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    pub fn with_admin_proposal_expiry(mut self, value: Option<Duration>) -> Self {
+        self.admin_proposal_expiry = value;
+        self
+    }
+
+    #[cfg(feature = "registry-remote-signature")]
+    pub fn with_registry_publisher_keys(mut self, value: Vec<cylinder::PublicKey>) -> Self {
+        self.registry_publisher_keys = value;
+        self
+    }
+
     pub fn build(self) -> Result<SplinterDaemon, CreateError> {
         let heartbeat = self.heartbeat.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: heartbeat".to_string())
@@ -391,6 +427,14 @@ impl SplinterDaemonBuilder {
             signers,
             peering_token,
             enable_lmdb_state: self.enable_lmdb_state,
+            #[cfg(feature = "tap-prometheus")]
+            prometheus_recorder: self.prometheus_recorder,
+            #[cfg(feature = "admin-service-event-retention")]
+            admin_event_retention: self.admin_event_retention,
+            #[cfg(feature = "admin-service-proposal-expiry")]
+            admin_proposal_expiry: self.admin_proposal_expiry,
+            #[cfg(feature = "registry-remote-signature")]
+            registry_publisher_keys: self.registry_publisher_keys,
         })
     }
 }
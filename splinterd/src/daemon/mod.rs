@@ -32,12 +32,20 @@ use scabbard::service::ScabbardArgValidator;
 use scabbard::service::ScabbardFactoryBuilder;
 use splinter::admin::rest_api::CircuitResourceProvider;
 use splinter::admin::service::{admin_service_id, AdminService, AdminServiceBuilder};
+#[cfg(feature = "admin-service-event-retention")]
+use splinter::admin::store::{EventLogCompactor, EventRetention};
+#[cfg(all(feature = "biome-credentials", feature = "rest-api-persistent-secrets"))]
+use splinter::biome::credentials::rest_api::BiomeCredentialsRestConfigBuilder;
 #[cfg(feature = "biome-credentials")]
 use splinter::biome::credentials::rest_api::BiomeCredentialsRestResourceProviderBuilder;
 #[cfg(feature = "biome-key-management")]
 use splinter::biome::key_management::rest_api::BiomeKeyManagementRestResourceProvider;
 #[cfg(feature = "biome-profile")]
 use splinter::biome::profile::rest_api::BiomeProfileRestResourceProvider;
+#[cfg(feature = "circuit-durability")]
+use splinter::circuit::durability::{store::DurabilityStore, OutboxResender};
+#[cfg(feature = "circuit-durability")]
+use splinter::circuit::handlers::CircuitDirectMessageAckHandler;
 use splinter::circuit::handlers::{
     AdminDirectMessageHandler, CircuitDirectMessageHandler, CircuitErrorHandler,
     CircuitMessageHandler, ServiceConnectRequestHandler, ServiceDisconnectRequestHandler,
@@ -57,10 +65,14 @@ use splinter::orchestrator::ServiceOrchestratorBuilder;
 use splinter::peer::interconnect::NetworkMessageSender;
 use splinter::peer::interconnect::PeerInterconnectBuilder;
 use splinter::peer::PeerAuthorizationToken;
+#[cfg(feature = "registry-notifications")]
+use splinter::peer::PeerEndpointUpdater;
 use splinter::peer::PeerManager;
 use splinter::protos::circuit::CircuitMessageType;
 use splinter::protos::network::NetworkMessageType;
 use splinter::public_key::PublicKey;
+#[cfg(feature = "registry-remote-signature")]
+use splinter::registry::RemoteYamlSignatureVerification;
 use splinter::registry::{
     LocalYamlRegistry, RegistryReader, RemoteYamlRegistry, RwRegistry, UnifiedRegistry,
 };
@@ -80,11 +92,15 @@ use splinter::rest_api::auth::authorization::rbac::{
 use splinter::rest_api::auth::authorization::AuthorizationHandler;
 #[cfg(feature = "authorization")]
 use splinter::rest_api::auth::authorization::Permission;
+#[cfg(all(feature = "biome-credentials", feature = "rest-api-persistent-secrets"))]
+use splinter::rest_api::secrets::StoreSecretManager;
 #[cfg(feature = "oauth")]
 use splinter::rest_api::OAuthConfig;
 use splinter::rest_api::{AuthConfig, Method, Resource, RestApiBuilder, RestResourceProvider};
 use splinter::runtime::service::instance::{ServiceProcessor, ServiceProcessorShutdownHandle};
 use splinter::service::instance::ServiceArgValidator;
+#[cfg(feature = "tap-prometheus")]
+use splinter::tap::prometheus::PrometheusRecorder;
 use splinter::threading::lifecycle::ShutdownHandle;
 use splinter::transport::{
     inproc::InprocTransport, multi::MultiTransport, AcceptError, Connection, Incoming, Listener,
@@ -101,6 +117,12 @@ pub use store::ConnectionUri;
 const ADMIN_SERVICE_PROCESSOR_INCOMING_CAPACITY: usize = 8;
 const ADMIN_SERVICE_PROCESSOR_OUTGOING_CAPACITY: usize = 8;
 const ADMIN_SERVICE_PROCESSOR_CHANNEL_CAPACITY: usize = 8;
+#[cfg(feature = "admin-service-event-retention")]
+const ADMIN_EVENT_COMPACTION_INTERVAL: Duration = Duration::from_secs(60);
+#[cfg(all(feature = "biome-credentials", feature = "rest-api-persistent-secrets"))]
+const BIOME_ACCESS_TOKEN_KEY_RING: &str = "biome_access_token";
+#[cfg(all(feature = "biome-credentials", feature = "rest-api-persistent-secrets"))]
+const BIOME_REFRESH_TOKEN_KEY_RING: &str = "biome_refresh_token";
 
 pub struct SplinterDaemon {
     #[cfg(feature = "authorization-handler-allow-keys")]
@@ -147,6 +169,14 @@ pub struct SplinterDaemon {
     #[cfg(feature = "config-allow-keys")]
     allow_keys_file: String,
     enable_lmdb_state: bool,
+    #[cfg(feature = "tap-prometheus")]
+    prometheus_recorder: Option<PrometheusRecorder>,
+    #[cfg(feature = "admin-service-event-retention")]
+    admin_event_retention: EventRetention,
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    admin_proposal_expiry: Option<Duration>,
+    #[cfg(feature = "registry-remote-signature")]
+    registry_publisher_keys: Vec<cylinder::PublicKey>,
 }
 
 impl SplinterDaemon {
@@ -332,12 +362,27 @@ impl SplinterDaemon {
 
         let network_sender = interconnect.new_network_sender();
 
+        // Resend messages on circuits with guaranteed delivery when a peer reconnects
+        #[cfg(feature = "circuit-durability")]
+        let mut outbox_resender = OutboxResender::start(
+            &node_id,
+            store_factory.get_durability_store(),
+            routing_reader.clone(),
+            Box::new(network_sender.clone()),
+            peer_connector.clone(),
+        )
+        .map_err(|err| {
+            StartError::NetworkError(format!("Unable to start outbox resender: {}", err))
+        })?;
+
         // Set up the Circuit dispatcher
         let circuit_dispatcher = set_up_circuit_dispatcher(
             network_sender.clone(),
             &node_id,
             routing_reader.clone(),
             routing_writer.clone(),
+            #[cfg(feature = "circuit-durability")]
+            store_factory.get_durability_store(),
             self.signers
                 .iter()
                 .map(|signer| Ok(signer.public_key()?.into()))
@@ -477,13 +522,35 @@ impl SplinterDaemon {
                 )
             })?;
 
+        #[cfg(feature = "registry-remote-signature")]
         let (registry, mut registry_shutdown) = create_registry(
             &self.state_dir,
             &self.registries,
             self.registry_auto_refresh,
             self.registry_forced_refresh,
+            &self.registry_publisher_keys,
             &*store_factory,
         );
+        #[cfg(not(feature = "registry-remote-signature"))]
+        let (registry, mut registry_shutdown) = create_registry(
+            &self.state_dir,
+            &self.registries,
+            self.registry_auto_refresh,
+            self.registry_forced_refresh,
+            &*store_factory,
+        );
+
+        // Update the endpoints of peers when their nodes change in the registry, so that they can
+        // be reconnected at their new endpoints
+        #[cfg(feature = "registry-notifications")]
+        if let Err(err) =
+            registry.add_subscriber(Box::new(PeerEndpointUpdater::new(peer_connector.clone())))
+        {
+            warn!(
+                "Unable to subscribe to registry changes; peer endpoints will not be updated: {}",
+                err
+            );
+        }
 
         let mut admin_service_builder = AdminServiceBuilder::new();
 
@@ -517,10 +584,41 @@ impl SplinterDaemon {
 
         admin_service_builder = admin_service_builder.with_service_arg_validators(validators);
 
+        #[cfg(feature = "admin-service-proposal-expiry")]
+        if let Some(expiry) = self.admin_proposal_expiry {
+            // The node's key signs the expiry requests this node submits
+            let signer = self.signers.first().cloned().ok_or_else(|| {
+                StartError::AdminServiceError(
+                    "A node key is required to expire circuit proposals".to_string(),
+                )
+            })?;
+            admin_service_builder = admin_service_builder.with_proposal_expiry(expiry, signer);
+        }
+
         let admin_service = admin_service_builder.build().map_err(|err| {
             StartError::AdminServiceError(format!("unable to create admin service: {}", err))
         })?;
 
+        // Remove admin service events that fall outside of the retention policy
+        #[cfg(feature = "admin-service-event-retention")]
+        let mut event_log_compactor = if self.admin_event_retention.is_limited() {
+            Some(
+                EventLogCompactor::start(
+                    store_factory.get_admin_service_store(),
+                    self.admin_event_retention.clone(),
+                    ADMIN_EVENT_COMPACTION_INTERVAL,
+                )
+                .map_err(|err| {
+                    StartError::AdminServiceError(format!(
+                        "unable to start admin event log compactor: {}",
+                        err
+                    ))
+                })?,
+            )
+        } else {
+            None
+        };
+
         #[cfg(feature = "authorization")]
         let node_id_clone = node_id.clone();
         let display_name: String = self
@@ -584,6 +682,8 @@ impl SplinterDaemon {
                     MaintenanceModeAuthorizationHandler::new(Some(rbac_store.clone()));
                 #[cfg(not(feature = "authorization-handler-rbac"))]
                 let maintenance_mode_auth_handler = MaintenanceModeAuthorizationHandler::default();
+                let maintenance_mode_auth_handler = maintenance_mode_auth_handler
+                    .with_maintenance_store(store_factory.get_maintenance_store());
                 rest_api_builder =
                     rest_api_builder.add_resources(maintenance_mode_auth_handler.resources());
                 authorization_handlers.push(Box::new(maintenance_mode_auth_handler));
@@ -591,8 +691,13 @@ impl SplinterDaemon {
 
             #[cfg(feature = "authorization-handler-rbac")]
             {
-                authorization_handlers
-                    .push(Box::new(RoleBasedAuthorizationHandler::new(rbac_store)));
+                let rbac_handler = RoleBasedAuthorizationHandler::new(rbac_store)
+                    .with_admin_service_store(store_factory.get_admin_service_store());
+                // API tokens are granted the roles they were created with
+                #[cfg(feature = "rest-api-tokens")]
+                let rbac_handler =
+                    rbac_handler.with_api_token_store(store_factory.get_api_token_store());
+                authorization_handlers.push(Box::new(rbac_handler));
                 rest_api_builder = rest_api_builder.add_resources(
                     RoleBasedAuthorizationResourceProvider::new(
                         store_factory.get_role_based_authorization_store(),
@@ -601,6 +706,14 @@ impl SplinterDaemon {
                 );
             }
 
+            // Record the requests that modify state, and all requests that are denied, in the
+            // database
+            #[cfg(feature = "rest-api-audit")]
+            {
+                rest_api_builder =
+                    rest_api_builder.with_audit_store(store_factory.get_audit_store());
+            }
+
             rest_api_builder = rest_api_builder
                 .with_authorization_handlers(authorization_handlers)
                 .add_resource(Resource::build("/openapi.yaml").add_method(
@@ -622,6 +735,18 @@ impl SplinterDaemon {
                         )
                     },
                 ));
+
+            #[cfg(feature = "tap-prometheus")]
+            {
+                if let Some(recorder) = self.prometheus_recorder.clone() {
+                    rest_api_builder =
+                        rest_api_builder.add_resource(Resource::build("/metrics").add_method(
+                            Method::Get,
+                            routes::METRICS_READ_PERMISSION,
+                            move |_, _| routes::get_metrics(&recorder),
+                        ));
+                }
+            }
         }
         #[cfg(not(feature = "authorization"))]
         {
@@ -641,6 +766,16 @@ impl SplinterDaemon {
                         )
                     }),
                 );
+
+            #[cfg(feature = "tap-prometheus")]
+            {
+                if let Some(recorder) = self.prometheus_recorder.clone() {
+                    rest_api_builder = rest_api_builder.add_resource(
+                        Resource::build("/metrics")
+                            .add_method(Method::Get, move |_, _| routes::get_metrics(&recorder)),
+                    );
+                }
+            }
         }
 
         #[cfg(feature = "rest-api-cors")]
@@ -675,6 +810,49 @@ impl SplinterDaemon {
                     biome_credentials_builder.with_key_store(store_factory.get_biome_key_store())
             }
 
+            // Keep the token signing keys in the database, so that tokens remain valid when the
+            // daemon restarts and are accepted by every REST API that shares the database
+            #[cfg(feature = "rest-api-persistent-secrets")]
+            {
+                let credentials_config = BiomeCredentialsRestConfigBuilder::default()
+                    .build()
+                    .map_err(|err| {
+                        StartError::RestApiError(format!(
+                            "Unable to build Biome credentials config: {}",
+                            err
+                        ))
+                    })?;
+
+                let token_secret_manager = StoreSecretManager::new(
+                    store_factory.get_secret_store(),
+                    BIOME_ACCESS_TOKEN_KEY_RING,
+                )
+                .map_err(|err| {
+                    StartError::RestApiError(format!(
+                        "Unable to load Biome access token keys: {}",
+                        err
+                    ))
+                })?
+                .with_key_retention(credentials_config.access_token_duration());
+
+                let refresh_token_secret_manager = StoreSecretManager::new(
+                    store_factory.get_secret_store(),
+                    BIOME_REFRESH_TOKEN_KEY_RING,
+                )
+                .map_err(|err| {
+                    StartError::RestApiError(format!(
+                        "Unable to load Biome refresh token keys: {}",
+                        err
+                    ))
+                })?
+                .with_key_retention(credentials_config.refresh_token_duration());
+
+                biome_credentials_builder = biome_credentials_builder
+                    .with_credentials_config(credentials_config)
+                    .with_token_secret_manager(token_secret_manager)
+                    .with_refresh_token_secret_manager(refresh_token_secret_manager);
+            }
+
             let biome_credentials_resource_provider =
                 biome_credentials_builder.build().map_err(|err| {
                     StartError::RestApiError(format!(
@@ -763,6 +941,16 @@ impl SplinterDaemon {
             }
         }
 
+        // Add API tokens issued by this node as an auth provider
+        #[cfg(feature = "rest-api-tokens")]
+        auth_configs.push(AuthConfig::ApiToken {
+            api_token_store: store_factory.get_api_token_store(),
+            #[cfg(feature = "authorization-handler-rbac")]
+            role_based_authorization_store: Some(
+                store_factory.get_role_based_authorization_store(),
+            ),
+        });
+
         rest_api_builder = rest_api_builder.with_auth_configs(auth_configs);
 
         #[cfg(feature = "biome-key-management")]
@@ -830,6 +1018,27 @@ impl SplinterDaemon {
             error!("Unable to cleanly shut down network dispatch loop: {}", err);
         }
 
+        #[cfg(feature = "circuit-durability")]
+        {
+            outbox_resender.signal_shutdown();
+            if let Err(err) = outbox_resender.wait_for_shutdown() {
+                error!("Unable to cleanly shut down outbox resender: {}", err);
+            }
+        }
+
+        #[cfg(feature = "admin-service-event-retention")]
+        {
+            if let Some(mut event_log_compactor) = event_log_compactor.take() {
+                event_log_compactor.signal_shutdown();
+                if let Err(err) = event_log_compactor.wait_for_shutdown() {
+                    error!(
+                        "Unable to cleanly shut down admin event log compactor: {}",
+                        err
+                    );
+                }
+            }
+        }
+
         registry_shutdown.signal_shutdown();
         if let Err(err) = registry_shutdown.wait_for_shutdown() {
             error!("Unable to cleanly shut down network dispatch loop: {}", err);
@@ -1006,6 +1215,7 @@ fn set_up_circuit_dispatcher(
     node_id: &str,
     routing_reader: Box<dyn RoutingTableReader>,
     routing_writer: Box<dyn RoutingTableWriter>,
+    #[cfg(feature = "circuit-durability")] durability_store: Box<dyn DurabilityStore>,
     public_keys: Vec<PublicKey>,
 ) -> Dispatcher<CircuitMessageType> {
     let mut dispatcher = Dispatcher::<CircuitMessageType>::new(Box::new(network_sender));
@@ -1023,8 +1233,18 @@ fn set_up_circuit_dispatcher(
 
     let direct_message_handler =
         CircuitDirectMessageHandler::new(node_id.to_string(), routing_reader.clone());
+    #[cfg(feature = "circuit-durability")]
+    let direct_message_handler =
+        direct_message_handler.with_durability_store(durability_store.clone());
     dispatcher.set_handler(Box::new(direct_message_handler));
 
+    #[cfg(feature = "circuit-durability")]
+    dispatcher.set_handler(Box::new(CircuitDirectMessageAckHandler::new(
+        node_id.to_string(),
+        routing_reader.clone(),
+        durability_store,
+    )));
+
     let circuit_error_handler =
         CircuitErrorHandler::new(node_id.to_string(), routing_reader.clone());
     dispatcher.set_handler(Box::new(circuit_error_handler));
@@ -1042,6 +1262,7 @@ fn create_registry(
     registries: &[String],
     auto_refresh_interval: u64,
     forced_refresh_interval: u64,
+    #[cfg(feature = "registry-remote-signature")] registry_publisher_keys: &[cylinder::PublicKey],
     store_factory: &dyn splinter::store::StoreFactory,
 ) -> (Box<dyn RwRegistry>, RegistryShutdownHandle) {
     let mut registry_shutdown_handle = RegistryShutdownHandle::new();
@@ -1083,12 +1304,36 @@ fn create_registry(
                 } else {
                     None
                 };
-                match RemoteYamlRegistry::new(
+                // If publisher keys are configured, only accept remote registry files that are
+                // signed by one of them
+                #[cfg(feature = "registry-remote-signature")]
+                let remote_registry = if registry_publisher_keys.is_empty() {
+                    RemoteYamlRegistry::new(
+                        registry,
+                        state_dir,
+                        auto_refresh_interval,
+                        forced_refresh_interval,
+                    )
+                } else {
+                    RemoteYamlRegistry::new_signed(
+                        registry,
+                        state_dir,
+                        auto_refresh_interval,
+                        forced_refresh_interval,
+                        RemoteYamlSignatureVerification::new(
+                            Secp256k1Context::new().new_verifier(),
+                            registry_publisher_keys.to_vec(),
+                        ),
+                    )
+                };
+                #[cfg(not(feature = "registry-remote-signature"))]
+                let remote_registry = RemoteYamlRegistry::new(
                     registry,
                     state_dir,
                     auto_refresh_interval,
                     forced_refresh_interval,
-                ) {
+                );
+                match remote_registry {
                     Ok(mut registry) => {
                         // this should alwasy return some
                         if let Some(shutdown_handle) = registry.take_shutdown_handle() {
@@ -31,10 +31,14 @@ use cylinder::{load_key_from_path, secp256k1::Secp256k1Context, Context, Signer}
 use log4rs::Handle;
 use logging::{configure_logging, default_log_settings};
 
+#[cfg(feature = "admin-service-event-retention")]
+use splinter::admin::store::EventRetention;
 use splinter::error::InternalError;
 use splinter::peer::PeerAuthorizationToken;
 #[cfg(feature = "tap")]
 use splinter::tap::influx::InfluxRecorder;
+#[cfg(feature = "tap-prometheus")]
+use splinter::tap::prometheus::PrometheusRecorder;
 
 use crate::config::{
     ClapPartialConfigBuilder, Config, ConfigBuilder, ConfigError, DefaultPartialConfigBuilder,
@@ -50,7 +54,7 @@ use std::fs;
 use std::path::Path;
 
 use error::UserError;
-use transport::build_transport;
+use transport::{build_transport, reload_tls_on_sighup};
 
 fn create_config(_toml_path: Option<&str>, _matches: ArgMatches) -> Result<Config, UserError> {
     let mut builder = ConfigBuilder::new();
@@ -341,6 +345,15 @@ fn main() {
                 .takes_value(true)
                 .alias("server-key"),
         )
+        .arg(
+            Arg::with_name("tls_crl_file")
+                .long("tls-crl-file")
+                .help(
+                    "File path to the certificate revocation list used to reject revoked peer \
+                    certificates",
+                )
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("tls_insecure")
                 .long("tls-insecure")
@@ -490,6 +503,70 @@ fn main() {
                 .takes_value(true),
         );
 
+    #[cfg(feature = "admin-service-event-retention")]
+    let app = app
+        .arg(
+            Arg::with_name("admin_event_max_age")
+                .long("admin-event-max-age")
+                .value_name("seconds")
+                .long_help(
+                    "How long admin service events are kept before they are compacted (in \
+                     seconds); by default events are not removed based on age",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("admin_event_max_count")
+                .long("admin-event-max-count")
+                .value_name("count")
+                .long_help(
+                    "The number of most recent admin service events kept when compacting; by \
+                     default events are not removed based on count",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    let app = app.arg(
+        Arg::with_name("admin_proposal_expiry")
+            .long("admin-proposal-expiry")
+            .value_name("seconds")
+            .long_help(
+                "How long a circuit proposal may remain pending on this node before the node \
+                 requests that the proposal's members expire it (in seconds); by default \
+                 proposals do not expire",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "registry-remote-signature")]
+    let app = app.arg(
+        Arg::with_name("registry_publisher_keys")
+            .long("registry-publisher-keys")
+            .value_name("public-key")
+            .long_help(
+                "Hex-encoded public keys of the publishers trusted to sign remote Splinter \
+                 registry files; if provided, remote registry files without a valid signature \
+                 from one of these keys are rejected",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true)
+            .alias("registry-publisher-key"),
+    );
+
+    #[cfg(feature = "tap-prometheus")]
+    let app = app.arg(
+        Arg::with_name("metrics_exporter")
+            .long("metrics-exporter")
+            .possible_values(&["influx", "prometheus"])
+            .long_help(
+                "Specifies where metrics are exported; `prometheus` serves metrics from the \
+                 REST API's /metrics endpoint",
+            )
+            .takes_value(true),
+    );
+
     let app = app.arg(
         Arg::with_name("scabbard_state")
             .long("scabbard-state")
@@ -517,7 +594,13 @@ fn main() {
 
 #[cfg(feature = "tap")]
 fn setup_metrics_recorder(config: &Config) -> Result<(), UserError> {
-    let metrics_configured = config.influx_db().is_some()
+    #[cfg(feature = "tap-prometheus")]
+    let influx_selected = config.metrics_exporter() == Some(&config::MetricsExporter::Influx);
+    #[cfg(not(feature = "tap-prometheus"))]
+    let influx_selected = false;
+
+    let metrics_configured = influx_selected
+        || config.influx_db().is_some()
         || config.influx_url().is_some()
         || config.influx_username().is_some()
         || config.influx_password().is_some();
@@ -546,6 +629,19 @@ fn setup_metrics_recorder(config: &Config) -> Result<(), UserError> {
     Ok(())
 }
 
+#[cfg(feature = "tap-prometheus")]
+fn setup_prometheus_recorder(config: &Config) -> Result<PrometheusRecorder, UserError> {
+    if config.influx_db().is_some()
+        || config.influx_url().is_some()
+        || config.influx_username().is_some()
+        || config.influx_password().is_some()
+    {
+        warn!("Ignoring InfluxDB configuration, metrics are exported to Prometheus");
+    }
+
+    PrometheusRecorder::init().map_err(UserError::InternalError)
+}
+
 fn get_config_file(matches: &'_ ArgMatches) -> Result<String, UserError> {
     if let Some(value) = matches.value_of("config") {
         return Ok(value.to_string());
@@ -615,10 +711,21 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
     }
 
     // set up metric recorder as soon as possilbe
-    #[cfg(feature = "tap")]
+    #[cfg(feature = "tap-prometheus")]
+    let prometheus_recorder = match config.metrics_exporter() {
+        Some(config::MetricsExporter::Prometheus) => Some(setup_prometheus_recorder(&config)?),
+        _ => {
+            setup_metrics_recorder(&config)?;
+            None
+        }
+    };
+    #[cfg(all(feature = "tap", not(feature = "tap-prometheus")))]
     setup_metrics_recorder(&config)?;
 
-    let transport = build_transport(&config)?;
+    let (transport, tls_reloaders) = build_transport(&config)?;
+    if !tls_reloaders.is_empty() {
+        reload_tls_on_sighup(tls_reloaders)?;
+    }
 
     let rest_api_endpoint = config.rest_api_endpoint();
 
@@ -702,6 +809,41 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
         }
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    {
+        daemon_builder = daemon_builder.with_prometheus_recorder(prometheus_recorder);
+    }
+
+    #[cfg(feature = "admin-service-event-retention")]
+    {
+        daemon_builder = daemon_builder.with_admin_event_retention(EventRetention::new(
+            config.admin_event_max_age(),
+            config.admin_event_max_count(),
+        ));
+    }
+
+    #[cfg(feature = "admin-service-proposal-expiry")]
+    {
+        daemon_builder = daemon_builder.with_admin_proposal_expiry(config.admin_proposal_expiry());
+    }
+
+    #[cfg(feature = "registry-remote-signature")]
+    {
+        let registry_publisher_keys = config
+            .registry_publisher_keys()
+            .iter()
+            .map(|key| {
+                cylinder::PublicKey::new_from_hex(key).map_err(|err| {
+                    UserError::InvalidArgument(format!(
+                        "Invalid registry publisher key {}: {}",
+                        key, err
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        daemon_builder = daemon_builder.with_registry_publisher_keys(registry_publisher_keys);
+    }
+
     let (signers, peering_token) = load_signer_keys(config.config_dir(), config.peering_key())?;
     daemon_builder = daemon_builder
         .with_signers(signers)
@@ -24,6 +24,8 @@ use splinter::circuit::routing::RoutingTableWriter;
 use splinter::error::InternalError;
 use splinter::events::Reactor;
 use splinter::orchestrator::ServiceOrchestratorBuilder;
+#[cfg(feature = "registry-notifications")]
+use splinter::peer::PeerEndpointUpdater;
 use splinter::peer::PeerManagerConnector;
 use splinter::public_key::PublicKey;
 use splinter::registry::{LocalYamlRegistry, RegistryReader, UnifiedRegistry};
@@ -104,6 +106,13 @@ impl RunnableAdminSubsystem {
             registry = Box::new(UnifiedRegistry::new(registry, read_only_registries));
         }
 
+        // Update the endpoints of peers when their nodes change in the registry, so that they can
+        // be reconnected at their new endpoints
+        #[cfg(feature = "registry-notifications")]
+        registry
+            .add_subscriber(Box::new(PeerEndpointUpdater::new(peer_connector.clone())))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
         let orchestrator_connection = service_transport
             .connect("inproc://orchestator")
             .map_err(|err| InternalError::from_source(Box::new(err)))?;
@@ -138,6 +138,7 @@ impl RunnableNode {
                             .store_factory
                             .get_role_based_authorization_store(),
                     ))
+                    .with_maintenance_store(admin_subsystem.store_factory.get_maintenance_store())
                     .resources(),
                 ];
 
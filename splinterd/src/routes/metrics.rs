@@ -0,0 +1,48 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use splinter::actix_web::{Error, HttpResponse};
+use splinter::futures::{Future, IntoFuture};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::rest_api::ErrorResponse;
+use splinter::tap::prometheus::{PrometheusRecorder, PROMETHEUS_CONTENT_TYPE};
+
+#[cfg(feature = "authorization")]
+pub const METRICS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "metrics.read",
+    permission_display_name: "Metrics read",
+    permission_description: "Allows the client to get the node's metrics in the Prometheus format",
+};
+
+pub fn get_metrics(
+    recorder: &PrometheusRecorder,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match recorder.render() {
+        Ok(metrics) => Box::new(
+            HttpResponse::Ok()
+                .content_type(PROMETHEUS_CONTENT_TYPE)
+                .body(metrics)
+                .into_future(),
+        ),
+        Err(err) => {
+            error!("Unable to render metrics: {}", err);
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            )
+        }
+    }
+}
@@ -13,6 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "tap-prometheus")]
+mod metrics;
 mod status;
 
+#[cfg(feature = "tap-prometheus")]
+pub use metrics::*;
 pub use status::*;
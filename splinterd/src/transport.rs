@@ -14,10 +14,12 @@
 
 use std::fs;
 use std::path::Path;
+use std::thread;
 
+use signal_hook::{consts::SIGHUP, iterator::Signals};
 use splinter::transport::multi::MultiTransport;
 use splinter::transport::socket::TcpTransport;
-use splinter::transport::socket::TlsTransport;
+use splinter::transport::socket::{TlsReloader, TlsTransport};
 use splinter::transport::tls::{TlsConfig, TlsConfigBuilder};
 #[cfg(feature = "ws-transport")]
 use splinter::transport::ws::WsTransport;
@@ -28,7 +30,13 @@ use crate::error::GetTransportError;
 
 type SendableTransport = Box<dyn Transport + Send>;
 
-pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportError> {
+/// Builds the transport used by the daemon.
+///
+/// If TLS is enabled, a `TlsReloader` for each TLS-enabled transport is also returned.
+pub fn build_transport(
+    config: &Config,
+) -> Result<(MultiTransport, Vec<TlsReloader>), GetTransportError> {
+    let mut tls_reloaders = vec![];
     let mut transports: Vec<SendableTransport> = vec![
         // add tcp transport
         // this will be default for endpoints without a prefix
@@ -43,24 +51,45 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
         validate_tls_config(&tls_config)?;
         print_tls_config(&tls_config)?;
 
-        transports.push(Box::new(TlsTransport::new(
-            tls_config.ca_certs_file().to_owned(),
-            tls_config.client_private_key_file().to_string(),
-            tls_config.client_cert_file().to_string(),
-            tls_config.server_private_key_file().to_string(),
-            tls_config.server_cert_file().to_string(),
-        )?));
+        let tls_transport = TlsTransport::from_config(&tls_config)?;
+        tls_reloaders.push(tls_transport.reloader());
+        transports.push(Box::new(tls_transport));
 
         #[cfg(feature = "ws-transport")]
-        transports.push(Box::new(WsTransport::new(Some(&tls_config)).map_err(
-            |e| GetTransportError::Cert(format!("Failed to create WebSocket transport: {}", e)),
-        )?));
+        {
+            let ws_transport = WsTransport::new(Some(&tls_config)).map_err(|e| {
+                GetTransportError::Cert(format!("Failed to create WebSocket transport: {}", e))
+            })?;
+            tls_reloaders.extend(ws_transport.reloader());
+            transports.push(Box::new(ws_transport));
+        }
     } else {
         #[cfg(feature = "ws-transport")]
         transports.push(Box::new(WsTransport::default()));
     }
 
-    Ok(MultiTransport::new(transports))
+    Ok((MultiTransport::new(transports), tls_reloaders))
+}
+
+/// Reloads the TLS-enabled transports' certificates, keys and certificate revocation list
+/// whenever the process receives a SIGHUP. Existing connections are left open.
+pub fn reload_tls_on_sighup(tls_reloaders: Vec<TlsReloader>) -> Result<(), GetTransportError> {
+    let mut signals = Signals::new(&[SIGHUP])?;
+
+    thread::Builder::new()
+        .name("TlsReloader".into())
+        .spawn(move || {
+            for _ in signals.forever() {
+                info!("Received SIGHUP, reloading TLS certificates");
+                for tls_reloader in &tls_reloaders {
+                    if let Err(err) = tls_reloader.reload() {
+                        error!("Unable to reload TLS certificates: {}", err);
+                    }
+                }
+            }
+        })?;
+
+    Ok(())
 }
 
 fn build_tls_config(config: &Config) -> Result<TlsConfig, GetTransportError> {
@@ -76,6 +105,10 @@ fn build_tls_config(config: &Config) -> Result<TlsConfig, GetTransportError> {
         builder = builder.with_ca_certs_file(config.tls_ca_file().to_string());
     }
 
+    if let Some(tls_crl_file) = config.tls_crl_file() {
+        builder = builder.with_crl_file(tls_crl_file.to_string());
+    }
+
     builder
         .build()
         .map_err(|e| GetTransportError::Cert(format!("TLS config error: {}", e)))
@@ -123,6 +156,15 @@ fn validate_tls_config(tls_config: &TlsConfig) -> Result<(), GetTransportError>
         }
     }
 
+    if let Some(crl_file) = tls_config.crl_file() {
+        if !Path::new(&crl_file).is_file() {
+            return Err(GetTransportError::Cert(format!(
+                "Must provide a valid certificate revocation list file: {}",
+                crl_file
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -146,6 +188,9 @@ fn print_tls_config(tls_config: &TlsConfig) -> Result<(), GetTransportError> {
     if let Some(ca_path) = tls_config.ca_certs_file() {
         debug!("Using ca certs file: {:?}", ca_path);
     }
+    if let Some(crl_path) = tls_config.crl_file() {
+        debug!("Using certificate revocation list file: {:?}", crl_path);
+    }
 
     Ok(())
 }